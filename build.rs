@@ -0,0 +1,33 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Embeds `stdlib/*.rcc` into the binary as a `(name, source)` table, so `std:` imports work
+/// no matter where the executable is run from.
+fn main() {
+    let stdlib = Path::new(env!("CARGO_MANIFEST_DIR")).join("stdlib");
+    println!("cargo:rerun-if-changed={}", stdlib.display());
+
+    let mut modules: Vec<PathBuf> = fs::read_dir(&stdlib)
+        .expect("stdlib directory is missing")
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rcc"))
+        .collect();
+    modules.sort();
+
+    let mut table = String::from("&[\n");
+    for path in &modules {
+        let name = path.file_stem().unwrap().to_string_lossy();
+        table.push_str(&format!(
+            "    ({:?}, include_str!({:?})),\n",
+            name,
+            path.display().to_string()
+        ));
+    }
+    table.push_str("]\n");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("stdlib_modules.rs"), table)
+        .expect("failed to write the embedded stdlib table");
+}
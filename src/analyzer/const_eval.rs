@@ -0,0 +1,95 @@
+use crate::{
+    ast::nodes::*,
+    interpreter::operators,
+    runtime::{
+        natives::math, BoolValue, CallStack, FloatValue, IntValue, NullValue, RuntimeValue,
+        StrValue,
+    },
+    symbol_table::SymbolKind,
+    tokens::BinaryOperator,
+};
+
+use super::SemanticAnalyzer;
+
+pub trait ConstScope {
+    fn constant(&self, name: &str) -> Option<RuntimeValue>;
+
+    fn builtin(&self, _object: &str, _property: &str) -> Option<RuntimeValue> {
+        None
+    }
+}
+
+impl ConstScope for SemanticAnalyzer {
+    fn constant(&self, name: &str) -> Option<RuntimeValue> {
+        self.symbol_table
+            .lookup(name)
+            .filter(|symbol| symbol.is_constant && symbol.kind == SymbolKind::Variable)?
+            .value
+            .clone()
+    }
+
+    fn builtin(&self, object: &str, property: &str) -> Option<RuntimeValue> {
+        let symbol = self.symbol_table.lookup(object)?;
+        let Some(Stmt::ImportDecl(import)) = symbol.declaration.as_deref() else {
+            return None;
+        };
+        if import.module_specifier != "std:math" || import.default_import.as_deref() != Some(object)
+        {
+            return None;
+        }
+        math::constant(property).map(|value| RuntimeValue::Float(FloatValue::new(value)))
+    }
+}
+
+pub fn fold(scope: &dyn ConstScope, expr: &Expr) -> Option<RuntimeValue> {
+    match expr {
+        Expr::IntLiteral(lit) => Some(RuntimeValue::Int(IntValue::new(lit.value))),
+        Expr::FloatLiteral(lit) => Some(RuntimeValue::Float(FloatValue::new(lit.value))),
+        Expr::StrLiteral(lit) => Some(RuntimeValue::Str(StrValue::new(lit.value.clone()))),
+        Expr::BoolLiteral(lit) => Some(RuntimeValue::Bool(BoolValue::new(lit.value))),
+        Expr::NullLiteral(_) => Some(RuntimeValue::Null(NullValue::new())),
+        Expr::Identifier(ident) => scope.constant(&ident.name),
+        Expr::Member(member) => match member.object.as_ref() {
+            Expr::Identifier(object) => scope.builtin(&object.name, &member.property),
+            _ => None,
+        },
+        Expr::Unary(unary) => operators::apply_unary_operation(
+            fold(scope, &unary.operand)?,
+            unary.operator,
+            unary.position,
+            &None,
+            operators::is_truthy,
+        )
+        .ok(),
+        Expr::Binary(binary) if binary.operator != BinaryOperator::Range => {
+            operators::apply_binary_operation(
+                fold(scope, &binary.left)?,
+                fold(scope, &binary.right)?,
+                binary.operator,
+                binary.position,
+                &None,
+                &CallStack::new(),
+                operators::is_truthy,
+            )
+            .ok()
+        }
+        Expr::Conditional(conditional) => {
+            if operators::is_truthy(&fold(scope, &conditional.condition)?) {
+                fold(scope, &conditional.then_expr)
+            } else {
+                fold(scope, &conditional.else_expr)
+            }
+        }
+        Expr::TemplateStr(template) => {
+            let mut text = String::new();
+            for part in &template.parts {
+                match part {
+                    TemplateStrPart::String(lit) => text.push_str(&lit.value),
+                    TemplateStrPart::Expr(expr) => text.push_str(&fold(scope, expr)?.to_string()),
+                }
+            }
+            Some(RuntimeValue::Str(StrValue::new(text)))
+        }
+        _ => None,
+    }
+}
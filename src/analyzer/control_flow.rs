@@ -1,21 +1,20 @@
 use crate::{
     ast::{nodes::*, types::*},
     error::RaccoonError,
+    interpreter::operators,
     symbol_table::SymbolKind,
+    tokens::Position,
 };
 
-use super::SemanticAnalyzer;
+use super::{const_eval, Lint, SemanticAnalyzer};
 
 pub fn check_if_stmt(analyzer: &mut SemanticAnalyzer, stmt: &IfStmt) -> Result<Type, RaccoonError> {
     let cond_type = analyzer.check_expr(&stmt.condition)?;
+    check_constant_condition(analyzer, &stmt.condition, false);
 
-    if !matches!(cond_type.kind(), TypeKind::Bool) {
-        return Err(RaccoonError::new(
-            format!("If condition must be boolean, got '{:?}'", cond_type),
-            stmt.position,
-            analyzer.file.clone(),
-        ));
-    }
+    analyzer
+        .type_checker
+        .check_condition(&cond_type, "If", stmt.position)?;
 
     let narrowing_info = analyzer
         .type_inference
@@ -45,14 +44,11 @@ pub fn check_while_stmt(
     stmt: &WhileStmt,
 ) -> Result<Type, RaccoonError> {
     let cond_type = analyzer.check_expr(&stmt.condition)?;
+    check_constant_condition(analyzer, &stmt.condition, true);
 
-    if !matches!(cond_type.kind(), TypeKind::Bool) {
-        return Err(RaccoonError::new(
-            format!("While condition must be boolean, got '{:?}'", cond_type),
-            stmt.position,
-            analyzer.file.clone(),
-        ));
-    }
+    analyzer
+        .type_checker
+        .check_condition(&cond_type, "While", stmt.position)?;
 
     let prev_in_loop = analyzer.in_loop;
     analyzer.in_loop = true;
@@ -76,14 +72,11 @@ pub fn check_do_while_stmt(
     analyzer.in_loop = prev_in_loop;
 
     let cond_type = analyzer.check_expr(&stmt.condition)?;
+    check_constant_condition(analyzer, &stmt.condition, true);
 
-    if !matches!(cond_type.kind(), TypeKind::Bool) {
-        return Err(RaccoonError::new(
-            format!("Do-while condition must be boolean, got '{:?}'", cond_type),
-            stmt.position,
-            analyzer.file.clone(),
-        ));
-    }
+    analyzer
+        .type_checker
+        .check_condition(&cond_type, "Do-while", stmt.position)?;
 
     Ok(PrimitiveType::void())
 }
@@ -100,13 +93,10 @@ pub fn check_for_stmt(
 
     if let Some(ref condition) = stmt.condition {
         let cond_type = analyzer.check_expr(condition)?;
-        if !matches!(cond_type.kind(), TypeKind::Bool) {
-            return Err(RaccoonError::new(
-                format!("For condition must be boolean, got '{:?}'", cond_type),
-                stmt.position,
-                analyzer.file.clone(),
-            ));
-        }
+        check_constant_condition(analyzer, condition, true);
+        analyzer
+            .type_checker
+            .check_condition(&cond_type, "For", stmt.position)?;
     }
 
     if let Some(ref increment) = stmt.increment {
@@ -130,37 +120,23 @@ pub fn check_for_in_stmt(
 ) -> Result<Type, RaccoonError> {
     let iterable_type = analyzer.check_expr(&stmt.iterable)?;
 
-    let element_type = if let Type::Array(ref list_type) = iterable_type {
-        list_type.element_type.clone()
-    } else if matches!(iterable_type.kind(), TypeKind::Str) {
-        PrimitiveType::str()
-    } else {
-        return Err(RaccoonError::new(
-            format!("Cannot iterate over type '{:?}'", iterable_type),
-            stmt.position,
-            analyzer.file.clone(),
-        ));
+    let element_type = match &iterable_type {
+        Type::Array(list_type) => list_type.element_type.clone(),
+        Type::Map(map_type) => map_type.key_type.clone(),
+        _ => match iterable_type.kind() {
+            TypeKind::Str | TypeKind::Object => PrimitiveType::str(),
+            TypeKind::Any => PrimitiveType::any(),
+            _ => {
+                return Err(RaccoonError::type_error(
+                    format!("Cannot iterate over type '{:?}'", iterable_type),
+                    stmt.position,
+                    analyzer.file.clone(),
+                ));
+            }
+        },
     };
 
-    analyzer.symbol_table.enter_scope();
-
-    analyzer.symbol_table.define(
-        stmt.variable.clone(),
-        SymbolKind::Variable,
-        element_type,
-        false,
-        None,
-    );
-
-    let prev_in_loop = analyzer.in_loop;
-    analyzer.in_loop = true;
-
-    analyzer.check_stmt(&stmt.body)?;
-
-    analyzer.in_loop = prev_in_loop;
-    analyzer.symbol_table.exit_scope();
-
-    Ok(PrimitiveType::void())
+    check_loop_body(analyzer, &stmt.pattern, element_type, &stmt.body, stmt.position)
 }
 
 pub fn check_for_of_stmt(
@@ -169,32 +145,60 @@ pub fn check_for_of_stmt(
 ) -> Result<Type, RaccoonError> {
     let iterable_type = analyzer.check_expr(&stmt.iterable)?;
 
-    let element_type = if let Type::Array(ref list_type) = iterable_type {
-        list_type.element_type.clone()
-    } else if matches!(iterable_type.kind(), TypeKind::Str) {
-        PrimitiveType::str()
-    } else {
-        return Err(RaccoonError::new(
-            format!("Cannot iterate over type '{:?}'", iterable_type),
-            stmt.position,
-            analyzer.file.clone(),
-        ));
+    let element_type = match &iterable_type {
+        Type::Array(list_type) => list_type.element_type.clone(),
+        _ => match iterable_type.kind() {
+            TypeKind::Str => PrimitiveType::str(),
+            TypeKind::Map | TypeKind::Object | TypeKind::Any => PrimitiveType::any(),
+            _ => {
+                return Err(RaccoonError::type_error(
+                    format!("Cannot iterate over type '{:?}'", iterable_type),
+                    stmt.position,
+                    analyzer.file.clone(),
+                ));
+            }
+        },
     };
 
+    check_loop_body(analyzer, &stmt.pattern, element_type, &stmt.body, stmt.position)
+}
+
+fn check_loop_body(
+    analyzer: &mut SemanticAnalyzer,
+    pattern: &VarPattern,
+    element_type: Type,
+    body: &Stmt,
+    position: Position,
+) -> Result<Type, RaccoonError> {
     analyzer.symbol_table.enter_scope();
 
-    analyzer.symbol_table.define(
-        stmt.variable.clone(),
-        SymbolKind::Variable,
-        element_type,
-        false,
-        None,
-    );
+    match pattern {
+        VarPattern::Identifier(name) => analyzer.declare_binding(
+            name,
+            SymbolKind::Variable,
+            element_type,
+            false,
+            None,
+            position,
+        ),
+        VarPattern::Destructuring(pattern) => {
+            for name in pattern.bound_names() {
+                analyzer.declare_binding(
+                    &name,
+                    SymbolKind::Variable,
+                    PrimitiveType::any(),
+                    false,
+                    None,
+                    position,
+                );
+            }
+        }
+    }
 
     let prev_in_loop = analyzer.in_loop;
     analyzer.in_loop = true;
 
-    analyzer.check_stmt(&stmt.body)?;
+    analyzer.check_stmt(body)?;
 
     analyzer.in_loop = prev_in_loop;
     analyzer.symbol_table.exit_scope();
@@ -206,18 +210,289 @@ pub fn check_switch_stmt(
     analyzer: &mut SemanticAnalyzer,
     stmt: &SwitchStmt,
 ) -> Result<Type, RaccoonError> {
-    let discriminant_type = analyzer.check_expr(&stmt.discriminant)?;
+    check_switch_cases(analyzer, &stmt.discriminant, &stmt.cases)?;
+    Ok(PrimitiveType::void())
+}
+
+pub fn check_switch_expr(
+    analyzer: &mut SemanticAnalyzer,
+    expr: &SwitchExpr,
+) -> Result<Type, RaccoonError> {
+    check_switch_cases(analyzer, &expr.discriminant, &expr.cases)
+}
+
+fn check_switch_cases(
+    analyzer: &mut SemanticAnalyzer,
+    discriminant: &Expr,
+    cases: &[SwitchCase],
+) -> Result<Type, RaccoonError> {
+    let discriminant_type = analyzer.check_expr(discriminant)?;
+
+    let mut seen_values = Vec::new();
+    let mut default_position = None;
+    let mut case_types: Vec<Type> = Vec::new();
 
-    for case in &stmt.cases {
-        if let Some(ref test) = case.test {
-            let test_type = analyzer.check_expr(test)?;
-            let _ = (discriminant_type.clone(), test_type);
+    let prev_in_loop = analyzer.in_loop;
+    analyzer.in_loop = true;
+
+    for case in cases {
+        if case.is_default() {
+            if let Some(position) = default_position {
+                analyzer.warn(
+                    Lint::DuplicateCase,
+                    "Duplicate 'default' case in switch",
+                    position,
+                );
+            }
+            default_position = case.consequent.first().map(Stmt::position);
         }
 
+        for test in &case.tests {
+            analyzer.check_expr(test)?;
+
+            if let Some(key) = case_literal_key(test) {
+                if seen_values.contains(&key) {
+                    analyzer.warn(
+                        Lint::DuplicateCase,
+                        format!("Duplicate case value {} in switch", key),
+                        test.position(),
+                    );
+                } else {
+                    seen_values.push(key);
+                }
+            }
+        }
+
+        check_reachable(analyzer, &case.consequent);
+        let mut case_type = PrimitiveType::void();
         for consequent_stmt in &case.consequent {
-            analyzer.check_stmt(consequent_stmt)?;
+            case_type = analyzer.check_stmt(consequent_stmt)?;
         }
+        case_types.push(case_type);
     }
 
-    Ok(PrimitiveType::void())
+    analyzer.in_loop = prev_in_loop;
+
+    if let (Type::Enum(enum_type), None) = (&discriminant_type, default_position) {
+        check_enum_coverage(analyzer, enum_type, discriminant, cases);
+    }
+
+    match case_types.split_first() {
+        Some((first, rest)) if rest.iter().all(|t| t.equals(first)) => Ok(first.clone()),
+        _ => Ok(PrimitiveType::any()),
+    }
+}
+
+fn check_enum_coverage(
+    analyzer: &mut SemanticAnalyzer,
+    enum_type: &EnumType,
+    discriminant: &Expr,
+    cases: &[SwitchCase],
+) {
+    let covered: Vec<&str> = cases
+        .iter()
+        .flat_map(|case| &case.tests)
+        .filter_map(|test| match test {
+            Expr::Member(member) => match member.object.as_ref() {
+                Expr::Identifier(ident) if ident.name == enum_type.name => {
+                    Some(member.property.as_str())
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    let missing: Vec<String> = enum_member_names(analyzer, enum_type)
+        .into_iter()
+        .filter(|name| !covered.contains(&name.as_str()))
+        .collect();
+
+    if !missing.is_empty() {
+        analyzer.warn(
+            Lint::NonExhaustiveSwitch,
+            format!(
+                "Switch over enum {} does not handle {}",
+                enum_type.name,
+                missing
+                    .iter()
+                    .map(|name| format!("{}.{}", enum_type.name, name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            discriminant.position(),
+        );
+    }
+}
+
+fn enum_member_names(analyzer: &SemanticAnalyzer, enum_type: &EnumType) -> Vec<String> {
+    let declaration = analyzer
+        .symbol_table
+        .lookup(&enum_type.name)
+        .and_then(|symbol| symbol.declaration.clone());
+
+    match declaration.as_deref() {
+        Some(Stmt::EnumDecl(decl)) => decl.members.iter().map(|m| m.name.clone()).collect(),
+        _ => {
+            let mut names: Vec<String> = enum_type.members.keys().cloned().collect();
+            names.sort();
+            names
+        }
+    }
+}
+
+fn case_literal_key(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::IntLiteral(lit) => Some(lit.value.to_string()),
+        Expr::FloatLiteral(lit) => Some(lit.value.to_string()),
+        Expr::StrLiteral(lit) => Some(format!("{:?}", lit.value)),
+        Expr::BoolLiteral(lit) => Some(lit.value.to_string()),
+        Expr::NullLiteral(_) => Some("null".to_string()),
+        _ => None,
+    }
+}
+
+/// Warns when `condition` always has the same value, except a literal `true` loop.
+pub fn check_constant_condition(analyzer: &mut SemanticAnalyzer, condition: &Expr, is_loop: bool) {
+    if is_loop && is_literal_true(condition) {
+        return;
+    }
+    if let Some(value) = const_eval::fold(analyzer, condition) {
+        analyzer.warn(
+            Lint::ConstantCondition,
+            format!("Condition is always {}", operators::is_truthy(&value)),
+            condition.position(),
+        );
+    }
+}
+
+/// Warns at the first statement that no path reaches.
+pub fn check_reachable(analyzer: &mut SemanticAnalyzer, stmts: &[Stmt]) {
+    let Some(end) = stmts.iter().position(|stmt| !completes(stmt)) else {
+        return;
+    };
+    let unreachable = stmts[end + 1..]
+        .iter()
+        .find(|stmt| !matches!(stmt, Stmt::FnDecl(_)));
+    if let Some(stmt) = unreachable {
+        analyzer.warn(Lint::UnreachableCode, "Unreachable code", stmt.position());
+    }
+}
+
+/// Fails when a function that must return a value can run off the end of `body`.
+pub fn check_returns(
+    analyzer: &SemanticAnalyzer,
+    what: &str,
+    return_type: &Type,
+    body: &[Stmt],
+    position: Position,
+) -> Result<(), RaccoonError> {
+    let returned = match return_type {
+        Type::Future(future) => &future.inner_type,
+        other => other,
+    };
+    if !accepts_null(returned) && body.iter().all(completes) {
+        return Err(RaccoonError::semantic_error(
+            format!(
+                "{} is declared to return '{}' but not every path returns a value",
+                what, return_type
+            ),
+            position,
+            analyzer.file.clone(),
+        ));
+    }
+    Ok(())
+}
+
+fn accepts_null(return_type: &Type) -> bool {
+    match return_type {
+        Type::Nullable(_) => true,
+        Type::Union(union) => union.types.iter().any(accepts_null),
+        other => matches!(
+            other.kind(),
+            TypeKind::Void | TypeKind::Null | TypeKind::Any | TypeKind::Unknown
+        ),
+    }
+}
+
+fn completes(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::ReturnStmt(_) | Stmt::ThrowStmt(_) | Stmt::BreakStmt(_) | Stmt::ContinueStmt(_) => {
+            false
+        }
+        Stmt::Block(block) => block.statements.iter().all(completes),
+        Stmt::IfStmt(stmt) => match &stmt.else_branch {
+            Some(else_branch) => completes(&stmt.then_branch) || completes(else_branch),
+            None => true,
+        },
+        Stmt::WhileStmt(stmt) => !is_literal_true(&stmt.condition) || jumps(&stmt.body, true),
+        Stmt::DoWhileStmt(stmt) => {
+            jumps(&stmt.body, true)
+                || (!is_literal_true(&stmt.condition)
+                    && (completes(&stmt.body) || jumps(&stmt.body, false)))
+        }
+        Stmt::ForStmt(stmt) => {
+            stmt.condition.as_ref().is_some_and(|c| !is_literal_true(c)) || jumps(&stmt.body, true)
+        }
+        // Cases fall through, so every path ends by running the last case.
+        Stmt::SwitchStmt(stmt) => {
+            !stmt.cases.iter().any(SwitchCase::is_default)
+                || stmt
+                    .cases
+                    .iter()
+                    .flat_map(|case| &case.consequent)
+                    .any(|s| jumps(s, true))
+                || stmt
+                    .cases
+                    .last()
+                    .is_some_and(|case| case.consequent.iter().all(completes))
+        }
+        Stmt::TryStmt(stmt) => {
+            let finishes = |block: &Block| block.statements.iter().all(completes);
+            stmt.finally_block.as_ref().is_none_or(finishes)
+                && (finishes(&stmt.try_block)
+                    || stmt
+                        .catch_clauses
+                        .iter()
+                        .any(|clause| finishes(&clause.body)))
+        }
+        Stmt::UsingStmt(stmt) => stmt.body.statements.iter().all(completes),
+        Stmt::DeferStmt(stmt) => stmt.body.statements.iter().all(completes),
+        _ => true,
+    }
+}
+
+fn jumps(stmt: &Stmt, is_break: bool) -> bool {
+    let any = |stmts: &[Stmt]| stmts.iter().any(|s| jumps(s, is_break));
+    match stmt {
+        Stmt::BreakStmt(_) => is_break,
+        Stmt::ContinueStmt(_) => !is_break,
+        Stmt::Block(block) => any(&block.statements),
+        Stmt::IfStmt(stmt) => {
+            jumps(&stmt.then_branch, is_break)
+                || stmt
+                    .else_branch
+                    .as_ref()
+                    .is_some_and(|s| jumps(s, is_break))
+        }
+        Stmt::SwitchStmt(stmt) if !is_break => stmt.cases.iter().any(|case| any(&case.consequent)),
+        Stmt::TryStmt(stmt) => {
+            any(&stmt.try_block.statements)
+                || stmt
+                    .catch_clauses
+                    .iter()
+                    .any(|clause| any(&clause.body.statements))
+                || stmt
+                    .finally_block
+                    .as_ref()
+                    .is_some_and(|block| any(&block.statements))
+        }
+        Stmt::UsingStmt(stmt) => any(&stmt.body.statements),
+        Stmt::DeferStmt(stmt) => any(&stmt.body.statements),
+        _ => false,
+    }
+}
+
+fn is_literal_true(condition: &Expr) -> bool {
+    matches!(condition, Expr::BoolLiteral(lit) if lit.value)
 }
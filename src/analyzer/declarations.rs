@@ -1,13 +1,21 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, convert::Infallible};
 
 use crate::{
     ast::{nodes::*, types::*},
     error::RaccoonError,
-    symbol_table::SymbolKind,
+    runtime::RuntimeValue,
+    symbol_table::{SymbolItem, SymbolKind},
     type_system::TypeResolver,
 };
 
-use super::SemanticAnalyzer;
+use super::{
+    const_eval, control_flow,
+    expressions::{
+        check_tuple_arity, declare_parameters, find_in_class_chain, format_params, overload_set,
+        param_names,
+    },
+    SemanticAnalyzer,
+};
 
 pub fn register_class(
     analyzer: &mut SemanticAnalyzer,
@@ -19,7 +27,7 @@ pub fn register_class(
             .symbol_table
             .lookup(superclass_name)
             .ok_or_else(|| {
-                RaccoonError::new(
+                RaccoonError::semantic_error(
                     format!("Superclass '{}' not found", superclass_name),
                     decl.position,
                     analyzer.file.clone(),
@@ -27,7 +35,7 @@ pub fn register_class(
             })?;
 
         if super_symbol.kind != SymbolKind::Class {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::semantic_error(
                 format!("'{}' is not a class", superclass_name),
                 decl.position,
                 analyzer.file.clone(),
@@ -39,7 +47,7 @@ pub fn register_class(
         }
     }
 
-    let class_type = ClassType {
+    let mut class_type = ClassType {
         name: decl.name.clone(),
         superclass,
         properties: HashMap::new(),
@@ -51,12 +59,78 @@ pub fn register_class(
     analyzer.symbol_table.define(
         decl.name.clone(),
         SymbolKind::Class,
-        Type::Class(Box::new(class_type)),
+        Type::Class(Box::new(class_type.clone())),
         false,
         Some(Box::new(Stmt::ClassDecl(decl.clone()))),
     );
 
-    Ok(())
+    // Once the class is defined, so static members can name it, as `create(): Counter` does.
+    record_static_members(analyzer, decl, &mut class_type);
+    analyzer
+        .symbol_table
+        .update_symbol_type(&decl.name, Type::Class(Box::new(class_type)))
+}
+
+fn record_static_members(
+    analyzer: &SemanticAnalyzer,
+    decl: &ClassDecl,
+    class_type: &mut ClassType,
+) {
+    let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
+    let resolve = |declared: &Type| {
+        resolver
+            .resolve(declared)
+            .unwrap_or_else(|_| PrimitiveType::any())
+    };
+
+    for prop in decl.properties.iter().filter(|prop| prop.is_static) {
+        class_type.properties.insert(
+            prop.name.clone(),
+            ClassPropertyInfo {
+                property_type: resolve(&prop.property_type),
+                access_modifier: prop.access_modifier,
+                is_static: true,
+            },
+        );
+    }
+
+    for method in decl.methods.iter().filter(|method| method.is_static) {
+        let Ok(method_type) =
+            method_type(method, |declared| Ok::<_, Infallible>(resolve(declared)));
+        class_type.methods.insert(
+            method.name.clone(),
+            ClassMethodInfo {
+                method_type,
+                access_modifier: method.access_modifier,
+                is_static: true,
+            },
+        );
+    }
+}
+
+fn method_type<E>(
+    method: &ClassMethod,
+    resolve: impl Fn(&Type) -> Result<Type, E>,
+) -> Result<FunctionType, E> {
+    let mut return_type = match &method.return_type {
+        Some(declared) => resolve(declared)?,
+        None => PrimitiveType::unknown(),
+    };
+    if method.is_async && !matches!(return_type, Type::Future(_)) {
+        return_type = Type::Future(Box::new(FutureType {
+            inner_type: return_type,
+        }));
+    }
+    Ok(FunctionType {
+        params: method
+            .parameters
+            .iter()
+            .map(|param| resolve(&param.param_type))
+            .collect::<Result<_, _>>()?,
+        return_type,
+        is_variadic: method.parameters.iter().any(|param| param.is_rest),
+        param_names: param_names(&method.parameters),
+    })
 }
 
 pub fn register_interface(
@@ -78,6 +152,19 @@ pub fn register_interface(
         );
     }
 
+    for method in &decl.methods {
+        let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
+        let method_type = method_type(method, |declared| resolver.resolve(declared))?;
+
+        properties.insert(
+            method.name.clone(),
+            InterfaceProperty {
+                property_type: Type::Function(Box::new(method_type)),
+                optional: false,
+            },
+        );
+    }
+
     let interface_type = InterfaceType {
         name: decl.name.clone(),
         properties,
@@ -95,23 +182,110 @@ pub fn register_interface(
     Ok(())
 }
 
+pub fn register_implements(
+    analyzer: &mut SemanticAnalyzer,
+    decl: &ClassDecl,
+) -> Result<(), RaccoonError> {
+    if decl.implements.is_empty() {
+        return Ok(());
+    }
+
+    for name in &decl.implements {
+        let kind = analyzer
+            .symbol_table
+            .lookup(name)
+            .map(|symbol| &symbol.kind);
+        let message = match kind {
+            Some(SymbolKind::Interface) => continue,
+            Some(_) => format!("'{}' is not an interface", name),
+            None => format!("Interface '{}' not found", name),
+        };
+        return Err(RaccoonError::semantic_error(
+            message,
+            decl.position,
+            analyzer.file.clone(),
+        ));
+    }
+
+    let interfaces = InterfaceDecl::lineage(&decl.implements, |name| {
+        match analyzer.symbol_table.lookup(name)?.declaration.as_deref()? {
+            Stmt::InterfaceDecl(interface) => Some(interface.clone()),
+            _ => None,
+        }
+    });
+    let inherited = |declares: &dyn Fn(&ClassDecl) -> bool| {
+        decl.superclass.as_ref().is_some_and(|superclass| {
+            find_in_class_chain(analyzer, superclass, |ancestor| {
+                declares(ancestor).then_some(())
+            })
+            .is_some()
+        })
+    };
+
+    let mut registered = decl.clone();
+    registered
+        .methods
+        .extend(InterfaceDecl::default_methods(&interfaces, |name| {
+            let declares = |class: &ClassDecl| {
+                class
+                    .methods
+                    .iter()
+                    .any(|method| method.is_instance_method(name))
+            };
+            declares(decl) || inherited(&declares)
+        }));
+
+    for interface in &interfaces {
+        for prop in interface.properties.iter().filter(|prop| !prop.optional) {
+            let declares = |class: &ClassDecl| declares_member(class, &prop.name);
+            if !declares(&registered) && !inherited(&declares) {
+                return Err(RaccoonError::semantic_error(
+                    format!(
+                        "Class '{}' does not implement '{}' from interface '{}'",
+                        decl.name, prop.name, interface.name
+                    ),
+                    decl.position,
+                    analyzer.file.clone(),
+                ));
+            }
+        }
+    }
+
+    if let Some(symbol) = analyzer.symbol_table.lookup_mut(&decl.name) {
+        symbol.declaration = Some(Box::new(Stmt::ClassDecl(registered)));
+    }
+    Ok(())
+}
+
+fn declares_member(class: &ClassDecl, name: &str) -> bool {
+    class
+        .properties
+        .iter()
+        .any(|prop| !prop.is_static && prop.name == name)
+        || class
+            .methods
+            .iter()
+            .any(|method| method.is_instance_method(name))
+        || class.accessors.iter().any(|accessor| accessor.name == name)
+}
+
 pub fn register_enum(analyzer: &mut SemanticAnalyzer, decl: &EnumDecl) -> Result<(), RaccoonError> {
     let mut members = HashMap::new();
     let mut current_value = 0i64;
 
     for member in &decl.members {
         if let Some(ref value_expr) = member.value {
-            match value_expr {
-                Expr::IntLiteral(lit) => {
-                    current_value = lit.value;
+            match const_eval::fold(analyzer, value_expr) {
+                Some(RuntimeValue::Int(value)) => {
+                    current_value = value.value;
                     members.insert(member.name.clone(), EnumValue::Int(current_value));
                 }
-                Expr::StrLiteral(lit) => {
-                    members.insert(member.name.clone(), EnumValue::Str(lit.value.clone()));
+                Some(RuntimeValue::Str(value)) => {
+                    members.insert(member.name.clone(), EnumValue::Str(value.value));
                 }
                 _ => {
-                    return Err(RaccoonError::new(
-                        "Enum member value must be int or string literal",
+                    return Err(RaccoonError::semantic_error(
+                        "Enum member value must be a constant int or string",
                         decl.position,
                         analyzer.file.clone(),
                     ));
@@ -175,7 +349,7 @@ pub fn register_function(
         PrimitiveType::unknown()
     };
 
-    if decl.is_async {
+    if returns_future(decl) {
         if !matches!(return_type, Type::Future(_)) {
             return_type = Type::Future(Box::new(FutureType {
                 inner_type: return_type,
@@ -186,13 +360,49 @@ pub fn register_function(
     let fn_type = FunctionType {
         params: param_types,
         return_type,
-        is_variadic: false,
+        is_variadic: decl.parameters.iter().any(|param| param.is_rest),
+        param_names: param_names(&decl.parameters),
+    };
+
+    let existing = analyzer
+        .symbol_table
+        .lookup_current_scope(&decl.name)
+        .filter(|symbol| symbol.kind == SymbolKind::Function);
+    let symbol_type = match existing {
+        Some(existing) => {
+            let mut overloads = match &existing.symbol_type {
+                Type::Function(existing_type) => vec![existing_type.as_ref().clone()],
+                other => overload_set(other)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+            };
+            if overloads.iter().any(|other| other.params == fn_type.params) {
+                return Err(RaccoonError::semantic_error(
+                    format!(
+                        "Function '{}' already has an overload taking {}",
+                        decl.name,
+                        format_params(&fn_type.params)
+                    ),
+                    decl.position,
+                    analyzer.file.clone(),
+                ));
+            }
+            overloads.push(fn_type);
+            let members = overloads
+                .into_iter()
+                .map(|overload| Type::Function(Box::new(overload)))
+                .collect();
+            Type::Intersection(Box::new(IntersectionType::new(members)))
+        }
+        None => Type::Function(Box::new(fn_type)),
     };
 
     analyzer.symbol_table.define(
         decl.name.clone(),
         SymbolKind::Function,
-        Type::Function(Box::new(fn_type)),
+        symbol_type,
         false,
         Some(Box::new(Stmt::FnDecl(decl.clone()))),
     );
@@ -216,11 +426,14 @@ pub fn check_var_decl(
         if matches!(explicit_type.kind(), TypeKind::Unknown | TypeKind::Any) {
             var_type = init_type;
         } else {
+            analyzer
+                .type_checker
+                .check_widening(&init_type, &explicit_type, decl.position)?;
             if !init_type.is_assignable_to(&explicit_type) {
-                return Err(RaccoonError::new(
+                return Err(RaccoonError::type_error(
                     format!(
-                        "Cannot assign type '{:?}' to variable of type '{:?}'",
-                        init_type, explicit_type
+                        "Cannot assign type '{}' to variable of type '{}'",
+                        init_type, decl.type_annotation
                     ),
                     decl.position,
                     analyzer.file.clone(),
@@ -230,7 +443,7 @@ pub fn check_var_decl(
         }
     } else {
         if matches!(explicit_type.kind(), TypeKind::Unknown) {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::semantic_error(
                 "Variable must have a type annotation or an initializer",
                 decl.position,
                 analyzer.file.clone(),
@@ -239,25 +452,56 @@ pub fn check_var_decl(
         var_type = explicit_type;
     }
 
+    check_tuple_arity(analyzer, &decl.pattern, &var_type, decl.position)?;
+
+    if let VarPattern::Destructuring(pattern) = &decl.pattern {
+        for name in pattern.bound_names() {
+            analyzer.declare_binding(
+                &name,
+                SymbolKind::Variable,
+                PrimitiveType::any(),
+                decl.is_constant,
+                None,
+                decl.position,
+            );
+        }
+    }
+
     if let VarPattern::Identifier(ref name) = decl.pattern {
-        analyzer.symbol_table.define(
-            name.clone(),
+        analyzer.declare_binding(
+            name,
             SymbolKind::Variable,
             var_type.clone(),
             decl.is_constant,
             Some(Box::new(Stmt::VarDecl(decl.clone()))),
+            decl.position,
         );
+
+        let is_global = analyzer.symbol_table.get_scope_depth() == 1;
+        if decl.is_constant && is_global {
+            let value = decl
+                .initializer
+                .as_ref()
+                .and_then(|init| const_eval::fold(analyzer, init));
+            if let Some(value) = value {
+                analyzer.symbol_table.fold_constant(name, value);
+            }
+        }
     }
 
     Ok(var_type)
 }
 
+fn returns_future(decl: &FnDecl) -> bool {
+    decl.is_async || decl.decorators.iter().any(|d| d.name == "debounce")
+}
+
 pub fn check_fn_decl(analyzer: &mut SemanticAnalyzer, decl: &FnDecl) -> Result<Type, RaccoonError> {
     let fn_symbol = analyzer
         .symbol_table
         .lookup(&decl.name)
         .ok_or_else(|| {
-            RaccoonError::new(
+            RaccoonError::semantic_error(
                 format!("Function '{}' not found", decl.name),
                 decl.position,
                 analyzer.file.clone(),
@@ -267,9 +511,12 @@ pub fn check_fn_decl(analyzer: &mut SemanticAnalyzer, decl: &FnDecl) -> Result<T
 
     let prev_function = analyzer.current_function.clone();
     let prev_async = analyzer.in_async_function;
+    let prev_static = analyzer.in_static_method;
 
     analyzer.current_function = Some(fn_symbol.clone());
     analyzer.in_async_function = decl.is_async;
+    // A function declared inside a static method gets its own `this` from `bind`.
+    analyzer.in_static_method = false;
 
     analyzer.symbol_table.enter_scope();
 
@@ -282,17 +529,7 @@ pub fn check_fn_decl(analyzer: &mut SemanticAnalyzer, decl: &FnDecl) -> Result<T
     };
     let param_types = param_types?;
 
-    for (param, param_type) in decl.parameters.iter().zip(param_types.iter()) {
-        if let VarPattern::Identifier(ref name) = param.pattern {
-            analyzer.symbol_table.define(
-                name.clone(),
-                SymbolKind::Parameter,
-                param_type.clone(),
-                false,
-                None,
-            );
-        }
-    }
+    declare_parameters(analyzer, &decl.parameters, &param_types)?;
 
     let explicit_return_type = if let Some(ref ret_type) = decl.return_type {
         let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
@@ -301,13 +538,22 @@ pub fn check_fn_decl(analyzer: &mut SemanticAnalyzer, decl: &FnDecl) -> Result<T
         None
     };
 
+    control_flow::check_reachable(analyzer, &decl.body);
+    for stmt in decl.body.iter() {
+        analyzer.check_stmt(stmt)?;
+    }
+
     let mut final_return_type = if let Some(explicit) = explicit_return_type {
+        if !decl.is_declare {
+            let what = format!("Function '{}'", decl.name);
+            control_flow::check_returns(analyzer, &what, &explicit, &decl.body, decl.position)?;
+        }
         explicit
     } else {
         analyzer.infer_function_return_type(&decl.body)?
     };
 
-    if decl.is_async && !matches!(final_return_type, Type::Future(_)) {
+    if returns_future(decl) && !matches!(final_return_type, Type::Future(_)) {
         final_return_type = Type::Future(Box::new(FutureType {
             inner_type: final_return_type,
         }));
@@ -316,21 +562,41 @@ pub fn check_fn_decl(analyzer: &mut SemanticAnalyzer, decl: &FnDecl) -> Result<T
     let updated_fn_type = FunctionType {
         params: param_types,
         return_type: final_return_type.clone(),
-        is_variadic: false,
+        is_variadic: decl.parameters.iter().any(|param| param.is_rest),
+        param_names: param_names(&decl.parameters),
     };
 
+    let symbol_type = match overload_set(&fn_symbol.symbol_type) {
+        Some(overloads) => {
+            let members = overloads
+                .into_iter()
+                .map(|overload| {
+                    if overload.params == updated_fn_type.params {
+                        updated_fn_type.clone()
+                    } else {
+                        overload.clone()
+                    }
+                })
+                .map(|overload| Type::Function(Box::new(overload)))
+                .collect();
+            Type::Intersection(Box::new(IntersectionType::new(members)))
+        }
+        None => Type::Function(Box::new(updated_fn_type)),
+    };
     analyzer
         .symbol_table
-        .update_symbol_type(&decl.name, Type::Function(Box::new(updated_fn_type)))?;
+        .update_symbol_type(&decl.name, symbol_type)?;
 
     analyzer.symbol_table.exit_scope();
     analyzer.current_function = prev_function;
     analyzer.in_async_function = prev_async;
+    analyzer.in_static_method = prev_static;
 
     Ok(Type::Function(Box::new(FunctionType {
         params: vec![],
         return_type: final_return_type,
         is_variadic: false,
+        param_names: Vec::new(),
     })))
 }
 
@@ -342,7 +608,7 @@ pub fn check_class_decl(
         .symbol_table
         .lookup(&decl.name)
         .ok_or_else(|| {
-            RaccoonError::new(
+            RaccoonError::semantic_error(
                 format!("Class '{}' not found", decl.name),
                 decl.position,
                 analyzer.file.clone(),
@@ -365,6 +631,29 @@ pub fn check_class_decl(
         );
     }
 
+    if let Some(constructor) = &decl.constructor {
+        for param in &constructor.parameters {
+            if let Some(default_value) = &param.default_value {
+                analyzer.check_expr(default_value)?;
+            }
+
+            if let VarPattern::Identifier(ref name) = param.pattern {
+                let param_type = {
+                    let resolver =
+                        TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
+                    resolver.resolve(&param.param_type)?
+                };
+                analyzer.symbol_table.define(
+                    name.clone(),
+                    SymbolKind::Parameter,
+                    param_type,
+                    false,
+                    None,
+                );
+            }
+        }
+    }
+
     for prop in &decl.properties {
         if let Some(ref initializer) = prop.initializer {
             let init_type = analyzer.check_expr(initializer)?;
@@ -373,8 +662,11 @@ pub fn check_class_decl(
                 resolver.resolve(&prop.property_type)?
             };
 
+            analyzer
+                .type_checker
+                .check_widening(&init_type, &prop_type, decl.position)?;
             if !init_type.is_assignable_to(&prop_type) {
-                return Err(RaccoonError::new(
+                return Err(RaccoonError::type_error(
                     format!(
                         "Property '{}' initializer type '{:?}' not assignable to '{:?}'",
                         prop.name, init_type, prop_type
@@ -387,7 +679,59 @@ pub fn check_class_decl(
     }
 
     analyzer.symbol_table.exit_scope();
+
+    if let Type::Class(ref class_type) = class_symbol.symbol_type {
+        for method in decl.methods.iter().filter(|method| method.is_static) {
+            if let Some(info) = class_type.methods.get(&method.name) {
+                check_static_method(analyzer, decl, method, &info.method_type)?;
+            }
+        }
+    }
+
     analyzer.current_class = prev_class;
 
     Ok(PrimitiveType::void())
 }
+
+fn check_static_method(
+    analyzer: &mut SemanticAnalyzer,
+    decl: &ClassDecl,
+    method: &ClassMethod,
+    method_type: &FunctionType,
+) -> Result<(), RaccoonError> {
+    let prev_function = analyzer.current_function.replace(SymbolItem::new(
+        method.name.clone(),
+        SymbolKind::Function,
+        Type::Function(Box::new(method_type.clone())),
+        false,
+        None,
+    ));
+    let prev_async = analyzer.in_async_function;
+    let prev_static = analyzer.in_static_method;
+    analyzer.in_async_function = method.is_async;
+    analyzer.in_static_method = true;
+
+    analyzer.symbol_table.enter_scope();
+    declare_parameters(analyzer, &method.parameters, &method_type.params)?;
+
+    control_flow::check_reachable(analyzer, &method.body);
+    for stmt in method.body.iter() {
+        analyzer.check_stmt(stmt)?;
+    }
+    if method.return_type.is_some() {
+        let what = format!("Static method '{}.{}'", decl.name, method.name);
+        control_flow::check_returns(
+            analyzer,
+            &what,
+            &method_type.return_type,
+            &method.body,
+            decl.position,
+        )?;
+    }
+
+    analyzer.symbol_table.exit_scope();
+    analyzer.current_function = prev_function;
+    analyzer.in_async_function = prev_async;
+    analyzer.in_static_method = prev_static;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::tokens::Position;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    UnusedVariable,
+    UnusedParameter,
+    UnusedImport,
+    Shadowing,
+    DuplicateCase,
+    NonExhaustiveSwitch,
+    UnreachableCode,
+    ConstantCondition,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 8] = [
+        Lint::UnusedVariable,
+        Lint::UnusedParameter,
+        Lint::UnusedImport,
+        Lint::Shadowing,
+        Lint::DuplicateCase,
+        Lint::NonExhaustiveSwitch,
+        Lint::UnreachableCode,
+        Lint::ConstantCondition,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnusedVariable => "unused-variable",
+            Lint::UnusedParameter => "unused-parameter",
+            Lint::UnusedImport => "unused-import",
+            Lint::Shadowing => "shadowing",
+            Lint::DuplicateCase => "duplicate-case",
+            Lint::NonExhaustiveSwitch => "non-exhaustive-switch",
+            Lint::UnreachableCode => "unreachable-code",
+            Lint::ConstantCondition => "constant-condition",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Lint> {
+        Lint::ALL.into_iter().find(|lint| lint.name() == name)
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Allow,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Allow => "allow",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LintLevels {
+    default: Severity,
+    levels: HashMap<Lint, Severity>,
+}
+
+impl LintLevels {
+    pub fn new(default: Severity) -> Self {
+        Self {
+            default,
+            levels: HashMap::new(),
+        }
+    }
+
+    pub fn set(&mut self, lint: Lint, severity: Severity) {
+        self.levels.insert(lint, severity);
+    }
+
+    pub fn set_all(&mut self, severity: Severity) {
+        self.default = severity;
+        self.levels.clear();
+    }
+
+    pub fn level(&self, lint: Lint) -> Severity {
+        self.levels.get(&lint).copied().unwrap_or(self.default)
+    }
+}
+
+impl Default for LintLevels {
+    fn default() -> Self {
+        Self::new(Severity::Warning)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub lint: Lint,
+    pub severity: Severity,
+    pub message: String,
+    pub position: Position,
+    pub file: Option<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.severity, self.lint)?;
+        if let Some(file) = &self.file {
+            write!(f, " {}", file)?;
+        }
+        write!(
+            f,
+            " {}:{} -> {}",
+            self.position.0, self.position.1, self.message
+        )
+    }
+}
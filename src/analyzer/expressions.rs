@@ -2,12 +2,14 @@ use std::collections::HashMap;
 
 use crate::{
     ast::{nodes::*, types::*},
-    error::RaccoonError,
+    error::{did_you_mean, RaccoonError},
+    runtime::TemplateTagRegistry,
     symbol_table::SymbolKind,
+    tokens::{AccessModifier, Position, UnaryOperator},
     type_system::TypeResolver,
 };
 
-use super::SemanticAnalyzer;
+use super::{control_flow, SemanticAnalyzer};
 
 pub fn check_expr(analyzer: &mut SemanticAnalyzer, expr: &Expr) -> Result<Type, RaccoonError> {
     match expr {
@@ -18,11 +20,17 @@ pub fn check_expr(analyzer: &mut SemanticAnalyzer, expr: &Expr) -> Result<Type,
         Expr::Member(e) => check_member_expr(analyzer, e),
         Expr::MethodCall(e) => check_method_call_expr(analyzer, e),
         Expr::Index(e) => check_index_expr(analyzer, e),
+        Expr::Slice(e) => check_slice_expr(analyzer, e),
         Expr::Await(e) => check_await_expr(analyzer, e),
-        Expr::This(_) => check_this_expr(analyzer),
+        Expr::This(e) => check_this_expr(analyzer, e.position),
         Expr::Super(_) => check_super_expr(analyzer),
-        Expr::TypeOf(_) => Ok(PrimitiveType::str()),
+        Expr::TypeOf(e) => {
+            analyzer.check_expr(&e.operand)?;
+            Ok(PrimitiveType::str())
+        }
         Expr::InstanceOf(e) => check_instanceof_expr(analyzer, e),
+        Expr::As(e) => check_as_expr(analyzer, e),
+        Expr::Is(e) => check_is_expr(analyzer, e),
         Expr::ArrowFn(e) => check_arrow_fn_expr(analyzer, e),
         Expr::Identifier(e) => check_identifier(analyzer, e),
         Expr::Assignment(e) => check_assignment(analyzer, e),
@@ -32,8 +40,8 @@ pub fn check_expr(analyzer: &mut SemanticAnalyzer, expr: &Expr) -> Result<Type,
         Expr::OptionalChaining(e) => check_optional_chaining_expr(analyzer, e),
         Expr::NullAssertion(e) => check_null_assertion_expr(analyzer, e),
         Expr::UnaryUpdate(e) => check_unary_update_expr(analyzer, e),
-        Expr::TemplateStr(_) => Ok(PrimitiveType::str()),
-        Expr::TaggedTemplate(_) => Ok(PrimitiveType::str()),
+        Expr::TemplateStr(e) => check_template_str(analyzer, e),
+        Expr::TaggedTemplate(e) => check_tagged_template(analyzer, e),
         Expr::IntLiteral(_) => Ok(PrimitiveType::int()),
         Expr::BigIntLiteral(_) => Ok(PrimitiveType::bigint()),
         Expr::FloatLiteral(_) => Ok(PrimitiveType::float()),
@@ -41,17 +49,49 @@ pub fn check_expr(analyzer: &mut SemanticAnalyzer, expr: &Expr) -> Result<Type,
         Expr::BoolLiteral(_) => Ok(PrimitiveType::bool()),
         Expr::NullLiteral(_) => Ok(PrimitiveType::null()),
         Expr::ArrayLiteral(e) => check_array_literal(analyzer, e),
+        Expr::TupleLiteral(e) => check_tuple_literal(analyzer, e),
         Expr::ObjectLiteral(e) => check_object_literal(analyzer, e),
-        Expr::Spread(_) => Err(RaccoonError::new(
+        Expr::Spread(_) => Err(RaccoonError::semantic_error(
             "Spread operator cannot be used outside of function calls",
             (1, 1),
             analyzer.file.clone(),
         )),
         Expr::Match(e) => check_match_expr(analyzer, e),
+        Expr::Switch(e) => analyzer.check_switch_expr(e),
+        Expr::Do(e) => check_do_expr(analyzer, e),
         Expr::Class(e) => check_class_expr(analyzer, e),
     }
 }
 
+pub fn check_template_str(
+    analyzer: &mut SemanticAnalyzer,
+    expr: &TemplateStrExpr,
+) -> Result<Type, RaccoonError> {
+    for part in &expr.parts {
+        if let TemplateStrPart::Expr(part) = part {
+            analyzer.check_expr(part)?;
+        }
+    }
+    Ok(PrimitiveType::str())
+}
+
+pub fn check_tagged_template(
+    analyzer: &mut SemanticAnalyzer,
+    expr: &TaggedTemplateExpr,
+) -> Result<Type, RaccoonError> {
+    let builtin_tag = match expr.tag.as_ref() {
+        Expr::Identifier(ident) if analyzer.symbol_table.lookup(&ident.name).is_none() => {
+            TemplateTagRegistry::new().get(&ident.name).is_some()
+        }
+        _ => false,
+    };
+    if !builtin_tag {
+        analyzer.check_expr(&expr.tag)?;
+    }
+    check_template_str(analyzer, &expr.template)?;
+    Ok(PrimitiveType::any())
+}
+
 pub fn check_binary_expr(
     analyzer: &mut SemanticAnalyzer,
     expr: &BinaryExpr,
@@ -63,7 +103,7 @@ pub fn check_binary_expr(
         .type_checker
         .are_types_compatible(&left_type, &right_type, expr.operator)
     {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::type_error(
             format!(
                 "Cannot apply operator {:?} to types '{:?}' and '{:?}'",
                 expr.operator, left_type, right_type
@@ -94,28 +134,61 @@ pub fn check_call_expr(
 ) -> Result<Type, RaccoonError> {
     let callee_type = analyzer.check_expr(&expr.callee)?;
 
+    if matches!(callee_type.kind(), TypeKind::Any) {
+        for arg in expr.args.iter().chain(expr.named_args.values()) {
+            analyzer.check_expr(arg)?;
+        }
+        return Ok(PrimitiveType::any());
+    }
+
+    if let Some(overloads) = overload_set(&callee_type) {
+        let overloads = overloads.into_iter().cloned().collect();
+        return check_overloaded_call(analyzer, expr, overloads);
+    }
+
     if let Type::Function(fn_type) = callee_type {
-        if expr.args.len() != fn_type.params.len() {
-            return Err(RaccoonError::new(
-                format!(
-                    "Function expects {} arguments, got {}",
-                    fn_type.params.len(),
-                    expr.args.len()
-                ),
+        // A rest parameter, always the last, takes any number of trailing arguments.
+        let fixed = fn_type
+            .params
+            .len()
+            .saturating_sub(usize::from(fn_type.is_variadic));
+        let arg_count = expr.args.len() + expr.named_args.len();
+        if arg_count < fixed || (!fn_type.is_variadic && arg_count > fixed) {
+            let expected = if fn_type.is_variadic {
+                format!("at least {}", fixed)
+            } else {
+                fixed.to_string()
+            };
+            return Err(RaccoonError::semantic_error(
+                format!("Function expects {} arguments, got {}", expected, arg_count),
                 expr.position,
                 analyzer.file.clone(),
             ));
         }
 
+        let declared = declared_param_types(analyzer, &expr.callee);
         for (i, arg) in expr.args.iter().enumerate() {
             let arg_type = analyzer.check_expr(arg)?;
-            if !arg_type.is_assignable_to(&fn_type.params[i]) {
-                return Err(RaccoonError::new(
+            let param_type = match fn_type.params.get(i.min(fixed)) {
+                Some(Type::Array(rest)) if i >= fixed => rest.element_type.clone(),
+                Some(param_type) => param_type.clone(),
+                None => PrimitiveType::any(),
+            };
+            analyzer
+                .type_checker
+                .check_widening(&arg_type, &param_type, expr.position)?;
+            if !arg_type.is_assignable_to(&param_type) {
+                let expected = declared
+                    .as_ref()
+                    .filter(|_| i < fixed)
+                    .and_then(|types| types.get(i))
+                    .unwrap_or(&param_type);
+                return Err(RaccoonError::type_error(
                     format!(
-                        "Argument {}: type '{:?}' not assignable to '{:?}'",
+                        "Argument {}: type '{}' not assignable to '{}'",
                         i + 1,
                         arg_type,
-                        fn_type.params[i]
+                        expected
                     ),
                     expr.position,
                     analyzer.file.clone(),
@@ -123,23 +196,137 @@ pub fn check_call_expr(
             }
         }
 
+        check_named_args(
+            analyzer,
+            &expr.named_args,
+            expr.args.len(),
+            &fn_type.param_names,
+            &fn_type.params,
+            expr.position,
+        )?;
+
         return Ok(fn_type.return_type);
     }
 
-    Err(RaccoonError::new(
+    Err(RaccoonError::type_error(
         format!("Cannot call non-function type '{:?}'", callee_type),
         expr.position,
         analyzer.file.clone(),
     ))
 }
 
+pub fn overload_set(fn_type: &Type) -> Option<Vec<&FunctionType>> {
+    let Type::Intersection(intersection) = fn_type else {
+        return None;
+    };
+    intersection
+        .types
+        .iter()
+        .map(|member| match member {
+            Type::Function(overload) => Some(overload.as_ref()),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn format_params(params: &[Type]) -> String {
+    let params: Vec<String> = params.iter().map(Type::to_string).collect();
+    format!("({})", params.join(", "))
+}
+
+fn check_overloaded_call(
+    analyzer: &mut SemanticAnalyzer,
+    expr: &CallExpr,
+    overloads: Vec<FunctionType>,
+) -> Result<Type, RaccoonError> {
+    let mut arg_types = Vec::new();
+    for arg in &expr.args {
+        arg_types.push(analyzer.check_expr(arg)?);
+    }
+    let arg_count = expr.args.len() + expr.named_args.len();
+
+    let mut best: Option<(&FunctionType, usize)> = None;
+    for overload in &overloads {
+        if overload.params.len() != arg_count {
+            continue;
+        }
+        let mut score = 0;
+        let fits = arg_types.iter().zip(&overload.params).all(|(arg, param)| {
+            score += if arg == param {
+                2
+            } else if matches!(param.kind(), TypeKind::Any | TypeKind::Unknown) {
+                0
+            } else if arg.is_assignable_to(param) {
+                1
+            } else {
+                return false;
+            };
+            true
+        });
+        if fits && best.is_none_or(|(_, top)| score > top) {
+            best = Some((overload, score));
+        }
+    }
+
+    let Some((overload, _)) = best else {
+        let name = match expr.callee.as_ref() {
+            Expr::Identifier(ident) => ident.name.as_str(),
+            _ => "function",
+        };
+        let declared: Vec<String> = overloads
+            .iter()
+            .map(|overload| format_params(&overload.params))
+            .collect();
+        return Err(RaccoonError::semantic_error(
+            format!(
+                "No overload of '{}' accepts {}; declared: {}",
+                name,
+                format_params(&arg_types),
+                declared.join(", ")
+            ),
+            expr.position,
+            analyzer.file.clone(),
+        ));
+    };
+
+    check_named_args(
+        analyzer,
+        &expr.named_args,
+        expr.args.len(),
+        &overload.param_names,
+        &overload.params,
+        expr.position,
+    )?;
+    Ok(overload.return_type.clone())
+}
+
+fn declared_param_types(analyzer: &SemanticAnalyzer, callee: &Expr) -> Option<Vec<Type>> {
+    let Expr::Identifier(ident) = callee else {
+        return None;
+    };
+    let symbol = analyzer.symbol_table.lookup(&ident.name)?;
+    match symbol.declaration.as_deref()? {
+        Stmt::FnDecl(decl) => Some(
+            decl.parameters
+                .iter()
+                .map(|param| param.param_type.clone())
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
 pub fn check_new_expr(
     analyzer: &mut SemanticAnalyzer,
     expr: &NewExpr,
 ) -> Result<Type, RaccoonError> {
+    analyzer
+        .symbol_table
+        .reference(&expr.class_name, expr.position);
+
     if expr.class_name == "Map" {
         if expr.type_args.len() != 2 {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::semantic_error(
                 "Map requires exactly two type arguments",
                 expr.position,
                 analyzer.file.clone(),
@@ -156,11 +343,19 @@ pub fn check_new_expr(
         })));
     }
 
+    let builtin_builder = expr.class_name == "StringBuilder";
+    if builtin_builder && analyzer.symbol_table.lookup(&expr.class_name).is_none() {
+        for arg in &expr.args {
+            analyzer.check_expr(arg)?;
+        }
+        return Ok(PrimitiveType::any());
+    }
+
     let class_symbol = analyzer
         .symbol_table
         .lookup(&expr.class_name)
         .ok_or_else(|| {
-            RaccoonError::new(
+            RaccoonError::semantic_error(
                 format!("Class '{}' not found", expr.class_name),
                 expr.position,
                 analyzer.file.clone(),
@@ -168,14 +363,34 @@ pub fn check_new_expr(
         })?;
 
     if class_symbol.kind != SymbolKind::Class {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::semantic_error(
             format!("'{}' is not a class", expr.class_name),
             expr.position,
             analyzer.file.clone(),
         ));
     }
 
-    Ok(class_symbol.symbol_type.clone())
+    let class_type = class_symbol.symbol_type.clone();
+    let parameters = find_in_class_chain(analyzer, &expr.class_name, |decl| {
+        decl.constructor
+            .as_ref()
+            .map(|ctor| ctor.parameters.clone())
+    });
+    if let Some(parameters) = parameters {
+        check_declared_named_args(
+            analyzer,
+            &expr.named_args,
+            expr.args.len(),
+            &parameters,
+            expr.position,
+        )?;
+    }
+
+    for arg in expr.args.iter().chain(expr.named_args.values()) {
+        analyzer.check_expr(arg)?;
+    }
+
+    Ok(class_type)
 }
 
 pub fn check_member_expr(
@@ -184,12 +399,32 @@ pub fn check_member_expr(
 ) -> Result<Type, RaccoonError> {
     let object_type = analyzer.check_expr(&expr.object)?;
 
+    if matches!(object_type.kind(), TypeKind::Any) {
+        return Ok(PrimitiveType::any());
+    }
+
+    if let Some(class_type) = class_reference(analyzer, &expr.object) {
+        return check_static_member(analyzer, &class_type, &expr.property, expr.position);
+    }
+
     if let Type::Class(ref class_type) = object_type {
-        if let Some(prop_info) = class_type.properties.get(&expr.property) {
-            return Ok(prop_info.property_type.clone());
+        check_private_member(analyzer, &class_type.name, &expr.property, expr.position)?;
+        check_not_static(analyzer, &class_type.name, &expr.property, expr.position)?;
+
+        let declared = find_in_class_chain(analyzer, &class_type.name, |decl| {
+            decl.properties
+                .iter()
+                .find(|prop| prop.name == expr.property && !prop.is_static)
+                .map(|prop| prop.property_type.clone())
+        });
+        if let Some(property_type) = declared {
+            let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
+            return Ok(resolver
+                .resolve(&property_type)
+                .unwrap_or_else(|_| PrimitiveType::any()));
         }
 
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::semantic_error(
             format!(
                 "Property '{}' does not exist on class '{}'",
                 expr.property, class_type.name
@@ -205,6 +440,12 @@ pub fn check_member_expr(
         }
     }
 
+    if let Type::Enum(ref enum_type) = object_type {
+        if enum_type.members.contains_key(&expr.property) {
+            return Ok(object_type.clone());
+        }
+    }
+
     if matches!(object_type.kind(), TypeKind::Str) {
         if expr.property == "length" {
             return Ok(PrimitiveType::int());
@@ -217,7 +458,15 @@ pub fn check_member_expr(
         }
     }
 
-    Err(RaccoonError::new(
+    if matches!(object_type, Type::Function(_)) || overload_set(&object_type).is_some() {
+        match expr.property.as_str() {
+            "name" => return Ok(PrimitiveType::str()),
+            "arity" => return Ok(PrimitiveType::int()),
+            _ => {}
+        }
+    }
+
+    Err(RaccoonError::type_error(
         format!(
             "Property '{}' does not exist on type '{:?}'",
             expr.property, object_type
@@ -233,38 +482,53 @@ pub fn check_method_call_expr(
 ) -> Result<Type, RaccoonError> {
     let object_type = analyzer.check_expr(&expr.object)?;
 
+    if let Some(class_type) = class_reference(analyzer, &expr.object) {
+        return check_static_call(analyzer, &class_type, expr);
+    }
+
     if let Type::Class(ref class_type) = object_type {
-        if let Some(method_info) = class_type.methods.get(&expr.method) {
-            if expr.args.len() != method_info.method_type.params.len() {
-                return Err(RaccoonError::new(
-                    format!(
-                        "Method '{}' expects {} arguments, got {}",
-                        expr.method,
-                        method_info.method_type.params.len(),
-                        expr.args.len()
-                    ),
-                    expr.position,
-                    analyzer.file.clone(),
-                ));
+        check_private_member(analyzer, &class_type.name, &expr.method, expr.position)?;
+        check_not_static(analyzer, &class_type.name, &expr.method, expr.position)?;
+
+        let method = find_in_class_chain(analyzer, &class_type.name, |decl| {
+            decl.methods
+                .iter()
+                .find(|m| m.name == expr.method && !m.is_static)
+                .cloned()
+        });
+        if let Some(method) = method {
+            for arg in &expr.args {
+                analyzer.check_expr(arg)?;
             }
+            check_declared_named_args(
+                analyzer,
+                &expr.named_args,
+                expr.args.len(),
+                &method.parameters,
+                expr.position,
+            )?;
 
-            for (i, arg) in expr.args.iter().enumerate() {
-                let arg_type = analyzer.check_expr(arg)?;
-                if !arg_type.is_assignable_to(&method_info.method_type.params[i]) {
-                    return Err(RaccoonError::new(
-                        format!(
-                            "Argument {}: type '{:?}' not assignable to '{:?}'",
-                            i + 1,
-                            arg_type,
-                            method_info.method_type.params[i]
-                        ),
-                        expr.position,
-                        analyzer.file.clone(),
-                    ));
-                }
-            }
+            let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
+            return Ok(method
+                .return_type
+                .and_then(|return_type| resolver.resolve(&return_type).ok())
+                .unwrap_or_else(PrimitiveType::any));
+        }
+    }
 
-            return Ok(method_info.method_type.return_type.clone());
+    if let Type::Enum(_) = object_type {
+        for arg in &expr.args {
+            analyzer.check_expr(arg)?;
+        }
+        match expr.method.as_str() {
+            "values" | "names" | "entries" => {
+                return Ok(Type::Array(Box::new(ArrayType {
+                    element_type: PrimitiveType::any(),
+                })))
+            }
+            "fromValue" => return Ok(object_type.clone()),
+            "has" => return Ok(PrimitiveType::bool()),
+            _ => {}
         }
     }
 
@@ -274,7 +538,16 @@ pub fn check_method_call_expr(
         }
     }
 
-    Err(RaccoonError::new(
+    if matches!(object_type, Type::Function(_)) || overload_set(&object_type).is_some() {
+        if let Some(result) = check_function_method(&object_type, &expr.method, expr.args.len()) {
+            for arg in &expr.args {
+                analyzer.check_expr(arg)?;
+            }
+            return Ok(result);
+        }
+    }
+
+    Err(RaccoonError::type_error(
         format!(
             "Method '{}' does not exist on type '{:?}'",
             expr.method, object_type
@@ -284,6 +557,240 @@ pub fn check_method_call_expr(
     ))
 }
 
+fn class_reference(analyzer: &SemanticAnalyzer, object: &Expr) -> Option<ClassType> {
+    let Expr::Identifier(ident) = object else {
+        return None;
+    };
+    if analyzer
+        .type_inference
+        .get_narrowed_type(&ident.name)
+        .is_some()
+    {
+        return None;
+    }
+    let symbol = analyzer.symbol_table.lookup(&ident.name)?;
+    match &symbol.symbol_type {
+        Type::Class(class_type) if symbol.kind == SymbolKind::Class => {
+            Some(class_type.as_ref().clone())
+        }
+        _ => None,
+    }
+}
+
+fn check_static_member(
+    analyzer: &SemanticAnalyzer,
+    class_type: &ClassType,
+    member: &str,
+    position: Position,
+) -> Result<Type, RaccoonError> {
+    check_private_member(analyzer, &class_type.name, member, position)?;
+
+    if let Some(prop) = class_type
+        .properties
+        .get(member)
+        .filter(|prop| prop.is_static)
+    {
+        return Ok(prop.property_type.clone());
+    }
+    if let Some(method) = class_type
+        .methods
+        .get(member)
+        .filter(|method| method.is_static)
+    {
+        return Ok(Type::Function(Box::new(method.method_type.clone())));
+    }
+
+    Err(missing_static_member(
+        analyzer,
+        &class_type.name,
+        member,
+        position,
+    ))
+}
+
+fn check_static_call(
+    analyzer: &mut SemanticAnalyzer,
+    class_type: &ClassType,
+    expr: &MethodCallExpr,
+) -> Result<Type, RaccoonError> {
+    check_private_member(analyzer, &class_type.name, &expr.method, expr.position)?;
+
+    let method_type = match class_type.methods.get(&expr.method) {
+        Some(method) if method.is_static => method.method_type.clone(),
+        _ => {
+            return Err(missing_static_member(
+                analyzer,
+                &class_type.name,
+                &expr.method,
+                expr.position,
+            ))
+        }
+    };
+    let parameters = find_in_class_chain(analyzer, &class_type.name, |decl| {
+        decl.methods
+            .iter()
+            .find(|method| method.name == expr.method && method.is_static)
+            .map(|method| method.parameters.clone())
+    })
+    .unwrap_or_default();
+
+    let required = parameters
+        .iter()
+        .filter(|param| param.default_value.is_none() && !param.is_optional && !param.is_rest)
+        .count();
+    let accepted = if method_type.is_variadic {
+        usize::MAX
+    } else {
+        parameters.len()
+    };
+    let arg_count = expr.args.len() + expr.named_args.len();
+    if arg_count < required || arg_count > accepted {
+        let expected = if required == accepted {
+            required.to_string()
+        } else if accepted == usize::MAX {
+            format!("at least {}", required)
+        } else {
+            format!("{} to {}", required, accepted)
+        };
+        return Err(RaccoonError::semantic_error(
+            format!(
+                "Static method '{}.{}' expects {} arguments, got {}",
+                class_type.name, expr.method, expected, arg_count
+            ),
+            expr.position,
+            analyzer.file.clone(),
+        ));
+    }
+
+    for (i, arg) in expr.args.iter().enumerate() {
+        let arg_type = analyzer.check_expr(arg)?;
+        let Some(param_type) = method_type.params.get(i) else {
+            continue;
+        };
+        if parameters.get(i).is_some_and(|param| param.is_rest) {
+            continue;
+        }
+        analyzer
+            .type_checker
+            .check_widening(&arg_type, param_type, expr.position)?;
+        if !arg_type.is_assignable_to(param_type) {
+            return Err(RaccoonError::type_error(
+                format!(
+                    "Argument {}: type '{}' not assignable to '{}'",
+                    i + 1,
+                    arg_type,
+                    param_type
+                ),
+                expr.position,
+                analyzer.file.clone(),
+            ));
+        }
+    }
+    check_declared_named_args(
+        analyzer,
+        &expr.named_args,
+        expr.args.len(),
+        &parameters,
+        expr.position,
+    )?;
+
+    Ok(method_type.return_type)
+}
+
+fn missing_static_member(
+    analyzer: &SemanticAnalyzer,
+    class_name: &str,
+    member: &str,
+    position: Position,
+) -> RaccoonError {
+    let on_instances = find_in_class_chain(analyzer, class_name, |decl| {
+        let declared = decl
+            .properties
+            .iter()
+            .any(|prop| prop.name == member && !prop.is_static)
+            || decl
+                .methods
+                .iter()
+                .any(|method| method.name == member && !method.is_static);
+        declared.then_some(())
+    })
+    .is_some();
+
+    let message = if on_instances {
+        format!(
+            "'{}' is an instance member of class '{}' and cannot be accessed on the class",
+            member, class_name
+        )
+    } else {
+        format!(
+            "Static member '{}' does not exist on class '{}'",
+            member, class_name
+        )
+    };
+    RaccoonError::semantic_error(message, position, analyzer.file.clone())
+}
+
+fn check_not_static(
+    analyzer: &SemanticAnalyzer,
+    class_name: &str,
+    member: &str,
+    position: Position,
+) -> Result<(), RaccoonError> {
+    let owner = find_in_class_chain(analyzer, class_name, |decl| {
+        let instance_member = decl
+            .properties
+            .iter()
+            .any(|prop| prop.name == member && !prop.is_static)
+            || decl
+                .methods
+                .iter()
+                .any(|method| method.name == member && !method.is_static);
+        if instance_member {
+            return Some(None);
+        }
+        let static_member = decl.properties.iter().any(|prop| prop.name == member)
+            || decl.methods.iter().any(|method| method.name == member);
+        static_member.then(|| Some(decl.name.clone()))
+    });
+
+    match owner {
+        Some(Some(owner)) => Err(RaccoonError::semantic_error(
+            format!(
+                "'{}' is a static member of class '{}'; access it as {}.{}",
+                member, owner, owner, member
+            ),
+            position,
+            analyzer.file.clone(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn check_function_method(fn_type: &Type, method: &str, arg_count: usize) -> Option<Type> {
+    let Type::Function(function) = fn_type else {
+        return match method {
+            "bind" | "call" | "apply" => Some(PrimitiveType::any()),
+            "isAsync" => Some(PrimitiveType::bool()),
+            "toString" | "toStr" => Some(PrimitiveType::str()),
+            _ => None,
+        };
+    };
+    match method {
+        "bind" => {
+            let partial = arg_count.saturating_sub(1);
+            Some(Type::Function(Box::new(FunctionType {
+                params: function.params.iter().skip(partial).cloned().collect(),
+                param_names: function.param_names.iter().skip(partial).cloned().collect(),
+                ..function.as_ref().clone()
+            })))
+        }
+        "call" | "apply" => Some(function.return_type.clone()),
+        "isAsync" => Some(PrimitiveType::bool()),
+        "toString" | "toStr" => Some(PrimitiveType::str()),
+        _ => None,
+    }
+}
+
 pub fn check_index_expr(
     analyzer: &mut SemanticAnalyzer,
     expr: &IndexExpr,
@@ -291,11 +798,61 @@ pub fn check_index_expr(
     let object_type = analyzer.check_expr(&expr.object)?;
     let index_type = analyzer.check_expr(&expr.index)?;
 
+    if let (Type::Tuple(tuple), Some(index)) = (&object_type, constant_index(&expr.index)) {
+        let len = tuple.element_types.len() as i64;
+        let resolved = if index < 0 { len + index } else { index };
+        if !(0..len).contains(&resolved) {
+            return Err(RaccoonError::semantic_error(
+                format!("Tuple index {} out of bounds (length: {})", index, len),
+                expr.position,
+                analyzer.file.clone(),
+            ));
+        }
+        return Ok(tuple.element_types[resolved as usize].clone());
+    }
+
     analyzer
         .type_checker
         .validate_index_expr(&object_type, &index_type, expr.position)
 }
 
+fn constant_index(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::IntLiteral(lit) => Some(lit.value),
+        Expr::Unary(unary) if matches!(unary.operator, UnaryOperator::Negate) => {
+            constant_index(&unary.operand).map(|value| -value)
+        }
+        _ => None,
+    }
+}
+
+pub fn check_slice_expr(
+    analyzer: &mut SemanticAnalyzer,
+    expr: &SliceExpr,
+) -> Result<Type, RaccoonError> {
+    let object_type = analyzer.check_expr(&expr.object)?;
+
+    for bound in expr.start.iter().chain(expr.end.iter()) {
+        let bound_type = analyzer.check_expr(bound)?;
+        if !matches!(bound_type.kind(), TypeKind::Int | TypeKind::Any) {
+            return Err(RaccoonError::type_error(
+                format!("Slice bounds must be integers, got '{:?}'", bound_type),
+                expr.position,
+                analyzer.file.clone(),
+            ));
+        }
+    }
+
+    match object_type.kind() {
+        TypeKind::Array | TypeKind::Str | TypeKind::Any => Ok(object_type),
+        _ => Err(RaccoonError::type_error(
+            format!("Cannot slice value of type '{:?}'", object_type),
+            expr.position,
+            analyzer.file.clone(),
+        )),
+    }
+}
+
 pub fn check_await_expr(
     analyzer: &mut SemanticAnalyzer,
     expr: &AwaitExpr,
@@ -306,7 +863,11 @@ pub fn check_await_expr(
         return Ok(future_type.inner_type);
     }
 
-    Err(RaccoonError::new(
+    if matches!(expr_type.kind(), TypeKind::Any) {
+        return Ok(PrimitiveType::any());
+    }
+
+    Err(RaccoonError::type_error(
         format!(
             "Cannot await non-Future type '{:?}'. Expected Future<T>",
             expr_type
@@ -316,12 +877,27 @@ pub fn check_await_expr(
     ))
 }
 
-pub fn check_this_expr(analyzer: &SemanticAnalyzer) -> Result<Type, RaccoonError> {
+pub fn check_this_expr(
+    analyzer: &SemanticAnalyzer,
+    position: Position,
+) -> Result<Type, RaccoonError> {
+    if analyzer.in_static_method {
+        return Err(RaccoonError::semantic_error(
+            "Cannot use 'this' in a static method",
+            position,
+            analyzer.file.clone(),
+        ));
+    }
+
     if let Some(ref current_class) = analyzer.current_class {
         return Ok(current_class.symbol_type.clone());
     }
 
-    Err(RaccoonError::new(
+    if analyzer.current_function.is_some() {
+        return Ok(PrimitiveType::any());
+    }
+
+    Err(RaccoonError::semantic_error(
         "Cannot use 'this' outside of class",
         (0, 0),
         analyzer.file.clone(),
@@ -334,7 +910,7 @@ pub fn check_super_expr(analyzer: &SemanticAnalyzer) -> Result<Type, RaccoonErro
             if let Some(ref superclass) = class_type.superclass {
                 return Ok(Type::Class(superclass.clone()));
             }
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::semantic_error(
                 "Cannot use 'super' in class without superclass",
                 (0, 0),
                 analyzer.file.clone(),
@@ -342,7 +918,7 @@ pub fn check_super_expr(analyzer: &SemanticAnalyzer) -> Result<Type, RaccoonErro
         }
     }
 
-    Err(RaccoonError::new(
+    Err(RaccoonError::semantic_error(
         "Cannot use 'super' outside of class",
         (0, 0),
         analyzer.file.clone(),
@@ -357,7 +933,7 @@ pub fn check_instanceof_expr(
 
     let type_symbol = analyzer.symbol_table.lookup(&expr.type_name);
     if type_symbol.is_none() || type_symbol.as_ref().unwrap().kind != SymbolKind::Class {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::semantic_error(
             format!("'{}' is not a class", expr.type_name),
             expr.position,
             analyzer.file.clone(),
@@ -367,10 +943,26 @@ pub fn check_instanceof_expr(
     Ok(PrimitiveType::bool())
 }
 
+pub fn check_as_expr(analyzer: &mut SemanticAnalyzer, expr: &AsExpr) -> Result<Type, RaccoonError> {
+    analyzer.check_expr(&expr.operand)?;
+    let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
+    resolver.resolve(&expr.target_type)
+}
+
+pub fn check_is_expr(analyzer: &mut SemanticAnalyzer, expr: &IsExpr) -> Result<Type, RaccoonError> {
+    analyzer.check_expr(&expr.operand)?;
+    let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
+    resolver.resolve(&expr.target_type)?;
+    Ok(PrimitiveType::bool())
+}
+
 pub fn check_arrow_fn_expr(
     analyzer: &mut SemanticAnalyzer,
     expr: &ArrowFnExpr,
 ) -> Result<Type, RaccoonError> {
+    analyzer
+        .type_checker
+        .check_implicit_any(&expr.parameters, expr.position)?;
     analyzer.symbol_table.enter_scope();
 
     let param_types: Result<Vec<_>, _> = {
@@ -382,23 +974,14 @@ pub fn check_arrow_fn_expr(
     };
     let param_types = param_types?;
 
-    for (param, param_type) in expr.parameters.iter().zip(param_types.iter()) {
-        if let VarPattern::Identifier(ref name) = param.pattern {
-            analyzer.symbol_table.define(
-                name.clone(),
-                SymbolKind::Parameter,
-                param_type.clone(),
-                false,
-                None,
-            );
-        }
-    }
+    declare_parameters(analyzer, &expr.parameters, &param_types)?;
 
     let inferred_return_type = match &expr.body {
         ArrowFnBody::Expr(body_expr) => analyzer.check_expr(body_expr)?,
         ArrowFnBody::Block(stmts) => {
+            control_flow::check_reachable(analyzer, stmts);
             let mut last_return_type = PrimitiveType::void();
-            for stmt in stmts {
+            for stmt in stmts.iter() {
                 if let Stmt::ReturnStmt(ret) = stmt {
                     if let Some(ref value) = ret.value {
                         last_return_type = analyzer.check_expr(value)?;
@@ -415,12 +998,16 @@ pub fn check_arrow_fn_expr(
         let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
         let resolved_type = resolver.resolve(explicit_type)?;
 
+        if let ArrowFnBody::Block(stmts) = &expr.body {
+            let what = "Arrow function";
+            control_flow::check_returns(analyzer, what, &resolved_type, stmts, expr.position)?;
+        }
         if !inferred_return_type.is_assignable_to(&resolved_type) {
             analyzer.symbol_table.exit_scope();
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::type_error(
                 format!(
-                    "Function body returns '{:?}' but declared return type is '{:?}'",
-                    inferred_return_type, resolved_type
+                    "Function body returns '{}' but declared return type is '{}'",
+                    inferred_return_type, explicit_type
                 ),
                 expr.position,
                 analyzer.file.clone(),
@@ -437,6 +1024,7 @@ pub fn check_arrow_fn_expr(
         params: param_types,
         return_type,
         is_variadic: false,
+        param_names: param_names(&expr.parameters),
     })))
 }
 
@@ -444,6 +1032,10 @@ pub fn check_identifier(
     analyzer: &mut SemanticAnalyzer,
     identifier: &Identifier,
 ) -> Result<Type, RaccoonError> {
+    analyzer
+        .symbol_table
+        .reference(&identifier.name, identifier.position);
+
     if let Some(narrowed_type) = analyzer.type_inference.get_narrowed_type(&identifier.name) {
         return Ok(narrowed_type);
     }
@@ -452,11 +1044,15 @@ pub fn check_identifier(
         .symbol_table
         .lookup(&identifier.name)
         .ok_or_else(|| {
-            RaccoonError::new(
+            let error = RaccoonError::semantic_error(
                 format!("Undefined variable '{}'", identifier.name),
                 identifier.position,
                 analyzer.file.clone(),
-            )
+            );
+            match did_you_mean(&identifier.name, analyzer.symbol_table.names()) {
+                Some(suggestion) => error.with_suggestion(suggestion),
+                None => error,
+            }
         })?;
 
     Ok(symbol.symbol_type.clone())
@@ -466,6 +1062,20 @@ pub fn check_assignment(
     analyzer: &mut SemanticAnalyzer,
     assignment: &Assignment,
 ) -> Result<Type, RaccoonError> {
+    match &*assignment.target {
+        Expr::Identifier(ident) => check_constant_target(analyzer, ident)?,
+        Expr::Member(member) => check_readonly_target(
+            analyzer,
+            &member.object,
+            Some(&member.property),
+            assignment.position,
+        )?,
+        Expr::Index(index) => {
+            check_readonly_target(analyzer, &index.object, None, assignment.position)?
+        }
+        _ => {}
+    }
+
     let target_type = analyzer.check_expr(&assignment.target)?;
     let value_type = analyzer.check_expr(&assignment.value)?;
 
@@ -477,6 +1087,316 @@ pub fn check_assignment(
     )
 }
 
+fn check_constant_target(
+    analyzer: &SemanticAnalyzer,
+    ident: &Identifier,
+) -> Result<(), RaccoonError> {
+    let Some(symbol) = analyzer.symbol_table.lookup(&ident.name) else {
+        return Ok(());
+    };
+    if !symbol.is_constant {
+        return Ok(());
+    }
+
+    let message = match symbol.declaration.as_deref() {
+        Some(Stmt::VarDecl(decl)) => format!(
+            "Cannot assign to constant '{}' (declared at {}:{})",
+            ident.name, decl.position.0, decl.position.1
+        ),
+        _ => format!("Cannot assign to constant '{}'", ident.name),
+    };
+
+    Err(RaccoonError::semantic_error(message, ident.position, analyzer.file.clone()))
+}
+
+fn check_readonly_target(
+    analyzer: &mut SemanticAnalyzer,
+    object: &Expr,
+    property: Option<&str>,
+    position: Position,
+) -> Result<(), RaccoonError> {
+    if let Expr::This(_) = object {
+        return Ok(());
+    }
+
+    let object_type = analyzer.check_expr(object)?;
+    let message = match (&object_type, property) {
+        (Type::Readonly(_), Some(property)) => Some(format!(
+            "Cannot assign to property '{}' of a readonly value",
+            property
+        )),
+        (Type::Readonly(_), None) => Some("Cannot assign through a readonly value".to_string()),
+        (Type::Object(object_type), Some(property))
+            if object_type
+                .properties
+                .get(property)
+                .is_some_and(|prop| prop.readonly) =>
+        {
+            Some(format!("Cannot assign to readonly property '{}'", property))
+        }
+        (Type::Class(class_type), Some(property))
+            if class_declares_readonly(analyzer, &class_type.name, property) =>
+        {
+            Some(format!(
+                "Cannot assign to readonly property '{}' of {}",
+                property, class_type.name
+            ))
+        }
+        _ => None,
+    };
+
+    match message {
+        Some(message) => Err(RaccoonError::semantic_error(
+            message,
+            position,
+            analyzer.file.clone(),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn class_declares_readonly(analyzer: &SemanticAnalyzer, class_name: &str, property: &str) -> bool {
+    find_in_class_chain(analyzer, class_name, |decl| {
+        decl.properties
+            .iter()
+            .find(|prop| prop.name == property)
+            .map(|prop| prop.is_readonly)
+    })
+    .unwrap_or(false)
+}
+
+fn check_private_member(
+    analyzer: &SemanticAnalyzer,
+    class_name: &str,
+    member: &str,
+    position: Position,
+) -> Result<(), RaccoonError> {
+    let owner = find_in_class_chain(analyzer, class_name, |decl| {
+        let is_private = decl.properties.iter().any(|prop| {
+            prop.name == member && prop.access_modifier == AccessModifier::Private
+        }) || decl.methods.iter().any(|method| {
+            method.name == member && method.access_modifier == AccessModifier::Private
+        });
+        is_private.then(|| decl.name.clone())
+    });
+    let Some(owner) = owner else {
+        return Ok(());
+    };
+
+    let inside_owner = analyzer.current_class.as_ref().is_some_and(|current| {
+        find_in_class_chain(analyzer, &current.name, |decl| {
+            (decl.name == owner).then_some(())
+        })
+        .is_some()
+    });
+
+    if inside_owner {
+        Ok(())
+    } else {
+        Err(RaccoonError::semantic_error(
+            format!("Member '{}' is private to class {}", member, owner),
+            position,
+            analyzer.file.clone(),
+        ))
+    }
+}
+
+/// Declares parameters in order, so a default only sees the parameters before it.
+pub fn declare_parameters(
+    analyzer: &mut SemanticAnalyzer,
+    parameters: &[FnParam],
+    param_types: &[Type],
+) -> Result<(), RaccoonError> {
+    for (param, param_type) in parameters.iter().zip(param_types) {
+        if let Some(default_value) = &param.default_value {
+            analyzer.check_expr(default_value)?;
+        }
+
+        check_tuple_arity(analyzer, &param.pattern, param_type, param.position)?;
+
+        match &param.pattern {
+            VarPattern::Identifier(name) => analyzer.declare_binding(
+                name,
+                SymbolKind::Parameter,
+                param_type.clone(),
+                false,
+                None,
+                param.position,
+            ),
+            VarPattern::Destructuring(pattern) => {
+                for name in pattern.bound_names() {
+                    analyzer.declare_binding(
+                        &name,
+                        SymbolKind::Parameter,
+                        PrimitiveType::any(),
+                        false,
+                        None,
+                        param.position,
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects array destructuring of a tuple type whose arity the pattern cannot match.
+pub fn check_tuple_arity(
+    analyzer: &SemanticAnalyzer,
+    pattern: &VarPattern,
+    value_type: &Type,
+    position: Position,
+) -> Result<(), RaccoonError> {
+    let (VarPattern::Destructuring(DestructuringPattern::Array(pattern)), Type::Tuple(tuple)) =
+        (pattern, value_type)
+    else {
+        return Ok(());
+    };
+
+    let arity = tuple.element_types.len();
+    let bindings = pattern.elements.len();
+    let matches = if pattern.rest.is_some() {
+        arity >= bindings
+    } else {
+        arity == bindings
+    };
+    if matches {
+        return Ok(());
+    }
+
+    Err(RaccoonError::semantic_error(
+        format!(
+            "Cannot destructure a tuple of {} elements into {} bindings",
+            arity, bindings
+        ),
+        position,
+        analyzer.file.clone(),
+    ))
+}
+
+/// Names of the parameters that can be bound by name; destructured parameters have none.
+pub fn param_names(parameters: &[FnParam]) -> Vec<String> {
+    parameters
+        .iter()
+        .map(|param| match &param.pattern {
+            VarPattern::Identifier(name) => name.clone(),
+            VarPattern::Destructuring(_) => String::new(),
+        })
+        .collect()
+}
+
+fn check_declared_named_args(
+    analyzer: &mut SemanticAnalyzer,
+    named_args: &HashMap<String, Expr>,
+    positional: usize,
+    parameters: &[FnParam],
+    position: Position,
+) -> Result<(), RaccoonError> {
+    let param_types: Vec<Type> = {
+        let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
+        parameters
+            .iter()
+            .map(|param| {
+                resolver
+                    .resolve(&param.param_type)
+                    .unwrap_or_else(|_| PrimitiveType::any())
+            })
+            .collect()
+    };
+
+    check_named_args(
+        analyzer,
+        named_args,
+        positional,
+        &param_names(parameters),
+        &param_types,
+        position,
+    )
+}
+
+fn check_named_args(
+    analyzer: &mut SemanticAnalyzer,
+    named_args: &HashMap<String, Expr>,
+    positional: usize,
+    param_names: &[String],
+    param_types: &[Type],
+    position: Position,
+) -> Result<(), RaccoonError> {
+    let mut names: Vec<&String> = named_args.keys().collect();
+    names.sort();
+
+    for name in names {
+        let arg_type = analyzer.check_expr(&named_args[name])?;
+        if param_names.is_empty() {
+            continue;
+        }
+
+        let Some(index) = param_names.iter().position(|param| param == name) else {
+            let error = RaccoonError::semantic_error(
+                format!("Unknown named argument '{}'", name),
+                position,
+                analyzer.file.clone(),
+            );
+            let candidates = param_names.iter().map(String::as_str);
+            return Err(match did_you_mean(name, candidates) {
+                Some(suggestion) => error.with_suggestion(suggestion),
+                None => error,
+            });
+        };
+
+        if index < positional {
+            return Err(RaccoonError::semantic_error(
+                format!("Argument '{}' was already passed positionally", name),
+                position,
+                analyzer.file.clone(),
+            ));
+        }
+
+        if let Some(param_type) = param_types.get(index) {
+            analyzer
+                .type_checker
+                .check_widening(&arg_type, param_type, position)?;
+            if !arg_type.is_assignable_to(param_type) {
+                return Err(RaccoonError::type_error(
+                    format!(
+                        "Argument '{}': type '{:?}' not assignable to '{:?}'",
+                        name, arg_type, param_type
+                    ),
+                    position,
+                    analyzer.file.clone(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn find_in_class_chain<T>(
+    analyzer: &SemanticAnalyzer,
+    class_name: &str,
+    mut find: impl FnMut(&ClassDecl) -> Option<T>,
+) -> Option<T> {
+    let mut current = Some(class_name.to_string());
+
+    while let Some(name) = current.take() {
+        let Some(symbol) = analyzer.symbol_table.lookup(&name) else {
+            break;
+        };
+        let Some(Stmt::ClassDecl(decl)) = symbol.declaration.as_deref() else {
+            break;
+        };
+
+        if let Some(found) = find(decl) {
+            return Some(found);
+        }
+        current = decl.superclass.clone();
+    }
+
+    None
+}
+
 pub fn check_range_expr(
     analyzer: &mut SemanticAnalyzer,
     expr: &RangeExpr,
@@ -494,14 +1414,11 @@ pub fn check_conditional_expr(
     expr: &ConditionalExpr,
 ) -> Result<Type, RaccoonError> {
     let cond_type = analyzer.check_expr(&expr.condition)?;
+    control_flow::check_constant_condition(analyzer, &expr.condition, false);
 
-    if !matches!(cond_type.kind(), TypeKind::Bool) {
-        return Err(RaccoonError::new(
-            "Conditional expression condition must be boolean",
-            expr.position,
-            analyzer.file.clone(),
-        ));
-    }
+    analyzer
+        .type_checker
+        .check_condition(&cond_type, "Conditional expression", expr.position)?;
 
     let then_type = analyzer.check_expr(&expr.then_expr)?;
     let else_type = analyzer.check_expr(&expr.else_expr)?;
@@ -577,10 +1494,14 @@ pub fn check_unary_update_expr(
     analyzer: &mut SemanticAnalyzer,
     expr: &UnaryUpdateExpr,
 ) -> Result<Type, RaccoonError> {
+    if let Expr::Identifier(ident) = &*expr.operand {
+        check_constant_target(analyzer, ident)?;
+    }
+
     let operand_type = analyzer.check_expr(&expr.operand)?;
 
     if !analyzer.type_checker.is_numeric_type(&operand_type) {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::type_error(
             "Increment/decrement requires numeric type",
             expr.position,
             analyzer.file.clone(),
@@ -590,6 +1511,18 @@ pub fn check_unary_update_expr(
     Ok(operand_type)
 }
 
+pub fn check_tuple_literal(
+    analyzer: &mut SemanticAnalyzer,
+    tuple: &TupleLiteral,
+) -> Result<Type, RaccoonError> {
+    let mut element_types = Vec::new();
+    for element in &tuple.elements {
+        element_types.push(analyzer.check_expr(element)?);
+    }
+
+    Ok(Type::Tuple(Box::new(TupleType::new(element_types))))
+}
+
 pub fn check_array_literal(
     analyzer: &mut SemanticAnalyzer,
     list: &ArrayLiteral,
@@ -659,6 +1592,22 @@ pub fn check_object_literal(
     })))
 }
 
+pub fn check_do_expr(
+    analyzer: &mut SemanticAnalyzer,
+    expr: &DoExpr,
+) -> Result<Type, RaccoonError> {
+    analyzer.symbol_table.enter_scope();
+
+    control_flow::check_reachable(analyzer, &expr.body.statements);
+    let mut result_type = PrimitiveType::null();
+    for stmt in &expr.body.statements {
+        result_type = analyzer.check_stmt(stmt)?;
+    }
+
+    analyzer.symbol_table.exit_scope();
+    Ok(result_type)
+}
+
 pub fn check_match_expr(
     _analyzer: &mut SemanticAnalyzer,
     _expr: &MatchExpr,
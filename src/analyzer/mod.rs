@@ -1,16 +1,22 @@
 use crate::{
     ast::{nodes::*, types::*},
     error::RaccoonError,
-    symbol_table::{SymbolItem, SymbolTable},
-    type_system::{TypeChecker, TypeInferenceEngine},
+    tokens::Position,
+    symbol_table::{SymbolItem, SymbolKind, SymbolTable},
+    type_system::{StrictnessProfile, TypeChecker, TypeInferenceEngine},
 };
 
+pub mod const_eval;
 pub mod control_flow;
 pub mod declarations;
+pub mod diagnostics;
 pub mod expressions;
+pub mod modules;
 pub mod statements;
 pub mod types;
 
+pub use diagnostics::{Diagnostic, Lint, LintLevels, Severity};
+
 pub struct SemanticAnalyzer {
     pub file: Option<String>,
     pub symbol_table: SymbolTable,
@@ -19,7 +25,12 @@ pub struct SemanticAnalyzer {
     pub current_function: Option<SymbolItem>,
     pub current_class: Option<SymbolItem>,
     pub in_loop: bool,
+    pub in_catch: bool,
     pub in_async_function: bool,
+    /// Set while checking the body of a static method, where `this` is an error.
+    pub in_static_method: bool,
+    pub warnings: Vec<Diagnostic>,
+    pub lint_levels: LintLevels,
 }
 
 impl SemanticAnalyzer {
@@ -32,7 +43,11 @@ impl SemanticAnalyzer {
             current_function: None,
             current_class: None,
             in_loop: false,
+            in_catch: false,
             in_async_function: false,
+            in_static_method: false,
+            warnings: Vec::new(),
+            lint_levels: LintLevels::default(),
         }
     }
 
@@ -45,37 +60,201 @@ impl SemanticAnalyzer {
             current_function: None,
             current_class: None,
             in_loop: false,
+            in_catch: false,
             in_async_function: false,
+            in_static_method: false,
+            warnings: Vec::new(),
+            lint_levels: LintLevels::default(),
         }
     }
 
+    pub fn with_file(mut self, file: Option<String>) -> Self {
+        self.type_checker.file = file.clone();
+        self.type_inference.file = file.clone();
+        self.type_inference.type_checker.file = file.clone();
+        self.file = file;
+        self
+    }
+
+    pub fn with_lint_levels(mut self, lint_levels: LintLevels) -> Self {
+        self.lint_levels = lint_levels;
+        self
+    }
+
+    pub fn with_strictness(mut self, strictness: StrictnessProfile) -> Self {
+        self.type_checker.strictness = strictness;
+        self
+    }
+
+    /// Analyzes the program, failing with the first error.
     pub fn analyze(&mut self, program: &Program) -> Result<(), RaccoonError> {
-        self.first_pass(program)?;
-        self.second_pass(program)?;
-        Ok(())
+        let mut errors = self.analyze_recovering(program);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+
+    /// Analyzes the program, reporting an error for each failing top-level statement.
+    pub fn analyze_recovering(&mut self, program: &Program) -> Vec<RaccoonError> {
+        if let Err(error) = self.first_pass(program) {
+            return vec![error];
+        }
+        let mut errors = self.second_pass(program);
+        if let Err(error) = modules::check_import_cycles(self, program) {
+            errors.push(error);
+        }
+        self.report_unused();
+        self.warnings.sort_by_key(|warning| warning.position);
+        errors
+    }
+
+    pub fn has_denied_warnings(&self) -> bool {
+        self.warnings
+            .iter()
+            .any(|warning| warning.severity == Severity::Error)
+    }
+
+    fn report_unused(&mut self) {
+        for symbol in self.symbol_table.take_unused() {
+            let (lint, what) = match symbol.kind {
+                SymbolKind::Parameter => (Lint::UnusedParameter, "parameter"),
+                SymbolKind::Import => (Lint::UnusedImport, "import"),
+                _ => (Lint::UnusedVariable, "variable"),
+            };
+            let position = symbol.position.unwrap_or((0, 0));
+            self.warn(
+                lint,
+                format!("Unused {} '{}'", what, symbol.name),
+                position,
+            );
+        }
     }
 
     fn first_pass(&mut self, program: &Program) -> Result<(), RaccoonError> {
+        for decl in crate::runtime::builtins::errors::declarations() {
+            declarations::register_class(self, decl)?;
+        }
+        for decl in crate::runtime::builtins::errors::enums() {
+            declarations::register_enum(self, decl)?;
+        }
+
+        // Types go first so a function can name one declared further down.
+        let mut named_types = Vec::new();
         for stmt in &program.stmts {
             match stmt {
                 Stmt::ClassDecl(decl) => declarations::register_class(self, decl)?,
-                Stmt::InterfaceDecl(decl) => declarations::register_interface(self, decl)?,
                 Stmt::EnumDecl(decl) => declarations::register_enum(self, decl)?,
-                Stmt::TypeAliasDecl(decl) => declarations::register_type_alias(self, decl)?,
-                Stmt::FnDecl(decl) => declarations::register_function(self, decl)?,
+                Stmt::InterfaceDecl(_) | Stmt::TypeAliasDecl(_) => named_types.push(stmt),
                 _ => {}
             }
         }
+        self.register_named_types(named_types)?;
+
+        // Once the interfaces are known, as a class may implement one declared after it.
+        for stmt in &program.stmts {
+            if let Stmt::ClassDecl(decl) = stmt {
+                declarations::register_implements(self, decl)?;
+            }
+        }
+
+        for stmt in &program.stmts {
+            if let Stmt::FnDecl(decl) = stmt {
+                declarations::register_function(self, decl)?;
+            }
+        }
+
+        // Core functions such as `min` give way to anything the program declares by that name.
+        for decl in crate::runtime::builtins::errors::functions() {
+            if self.symbol_table.lookup(&decl.name).is_none() {
+                declarations::register_function(self, decl)?;
+            }
+        }
+
+        for stmt in &program.stmts {
+            let declared = match stmt {
+                Stmt::ClassDecl(decl) => Some((&decl.name, decl.position)),
+                Stmt::EnumDecl(decl) => Some((&decl.name, decl.position)),
+                Stmt::InterfaceDecl(decl) => Some((&decl.name, decl.position)),
+                Stmt::TypeAliasDecl(decl) => Some((&decl.name, decl.position)),
+                Stmt::FnDecl(decl) => Some((&decl.name, decl.position)),
+                _ => None,
+            };
+            if let Some((name, position)) = declared {
+                self.symbol_table.declared_at(name, position);
+            }
+        }
         Ok(())
     }
 
-    fn second_pass(&mut self, program: &Program) -> Result<(), RaccoonError> {
-        for stmt in &program.stmts {
-            statements::check_stmt(self, stmt)?;
+    fn register_named_types(&mut self, mut pending: Vec<&Stmt>) -> Result<(), RaccoonError> {
+        while !pending.is_empty() {
+            let mut failed = Vec::new();
+            let mut first_error = None;
+            for stmt in &pending {
+                let result = match stmt {
+                    Stmt::InterfaceDecl(decl) => declarations::register_interface(self, decl),
+                    Stmt::TypeAliasDecl(decl) => declarations::register_type_alias(self, decl),
+                    _ => Ok(()),
+                };
+                if let Err(error) = result {
+                    failed.push(*stmt);
+                    first_error.get_or_insert(error);
+                }
+            }
+            if failed.len() == pending.len() {
+                return Err(first_error.unwrap());
+            }
+            pending = failed;
         }
         Ok(())
     }
 
+    fn second_pass(&mut self, program: &Program) -> Vec<RaccoonError> {
+        control_flow::check_reachable(self, &program.stmts);
+        let mut errors = Vec::new();
+        let depth = self.symbol_table.get_scope_depth();
+        for stmt in &program.stmts {
+            if let Err(error) = statements::check_stmt(self, stmt) {
+                errors.push(error);
+                self.leave_failed_stmt(depth);
+                // The variables of a failed declaration are still declared, so their uses do
+                // not each report them as undefined.
+                if let Stmt::VarDecl(decl) = stmt {
+                    let names = match &decl.pattern {
+                        VarPattern::Identifier(name) => vec![name.clone()],
+                        VarPattern::Destructuring(pattern) => pattern.bound_names(),
+                    };
+                    for name in names {
+                        if !self.symbol_table.is_defined_in_current_scope(&name) {
+                            self.symbol_table.define(
+                                name,
+                                SymbolKind::Variable,
+                                PrimitiveType::any(),
+                                decl.is_constant,
+                                None,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    fn leave_failed_stmt(&mut self, depth: usize) {
+        while self.symbol_table.get_scope_depth() > depth {
+            self.symbol_table.exit_scope();
+        }
+        self.current_function = None;
+        self.current_class = None;
+        self.in_loop = false;
+        self.in_catch = false;
+        self.in_async_function = false;
+        self.in_static_method = false;
+    }
+
     pub(in crate::analyzer) fn check_stmt(&mut self, stmt: &Stmt) -> Result<Type, RaccoonError> {
         statements::check_stmt(self, stmt)
     }
@@ -154,6 +333,69 @@ impl SemanticAnalyzer {
         control_flow::check_switch_stmt(self, stmt)
     }
 
+    pub(in crate::analyzer) fn check_switch_expr(
+        &mut self,
+        expr: &SwitchExpr,
+    ) -> Result<Type, RaccoonError> {
+        control_flow::check_switch_expr(self, expr)
+    }
+
+    pub(in crate::analyzer) fn warn(
+        &mut self,
+        lint: Lint,
+        message: impl Into<String>,
+        position: Position,
+    ) {
+        let severity = self.lint_levels.level(lint);
+        if severity == Severity::Allow {
+            return;
+        }
+
+        self.warnings.push(Diagnostic {
+            lint,
+            severity,
+            message: message.into(),
+            position,
+            file: self.file.clone(),
+        });
+    }
+
+    pub(in crate::analyzer) fn declare_binding(
+        &mut self,
+        name: &str,
+        kind: SymbolKind,
+        symbol_type: Type,
+        is_constant: bool,
+        declaration: Option<Box<Stmt>>,
+        position: Position,
+    ) {
+        let shadowed = self
+            .symbol_table
+            .lookup_outer(name)
+            .and_then(|outer| outer.position)
+            .filter(|_| self.symbol_table.lookup_current_scope(name).is_none());
+
+        if let Some(outer) = shadowed {
+            self.warn(
+                Lint::Shadowing,
+                format!(
+                    "'{}' shadows a binding declared at {}:{}",
+                    name, outer.0, outer.1
+                ),
+                position,
+            );
+        }
+
+        self.symbol_table.define_at(
+            name.to_string(),
+            kind,
+            symbol_type,
+            is_constant,
+            declaration,
+            position,
+        );
+    }
+
     pub(in crate::analyzer) fn infer_function_return_type(
         &mut self,
         body: &[Stmt],
@@ -0,0 +1,151 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use crate::{
+    ast::nodes::*,
+    error::RaccoonError,
+    runtime::{import_chain, resolve_module_path},
+    tokens::Position,
+    Lexer, Parser,
+};
+
+use super::SemanticAnalyzer;
+
+// A named import copies the export when it runs, so in a cycle it fails if the other module
+// hasn't reached that export yet. Namespace imports see exports as they are made.
+pub fn check_import_cycles(
+    analyzer: &SemanticAnalyzer,
+    program: &Program,
+) -> Result<(), RaccoonError> {
+    let Some(file) = &analyzer.file else {
+        return Ok(());
+    };
+    let Ok(path) = std::fs::canonicalize(file) else {
+        return Ok(());
+    };
+    Initialization::default().run(path, file, program)
+}
+
+struct Running {
+    path: PathBuf,
+    exported: HashSet<String>,
+}
+
+#[derive(Default)]
+struct Initialization {
+    running: Vec<Running>,
+    done: HashSet<PathBuf>,
+}
+
+impl Initialization {
+    fn run(&mut self, path: PathBuf, file: &str, program: &Program) -> Result<(), RaccoonError> {
+        self.running.push(Running {
+            path: path.clone(),
+            exported: HashSet::new(),
+        });
+
+        for stmt in &program.stmts {
+            match stmt {
+                Stmt::ImportDecl(decl) => {
+                    let names = decl
+                        .named_imports
+                        .iter()
+                        .map(|spec| spec.imported.as_str())
+                        .chain(decl.default_import.as_ref().map(|_| "default"));
+                    self.import(file, &decl.module_specifier, names, decl.position)?;
+                }
+                Stmt::ExportDecl(decl) => {
+                    if let Some(specifier) = &decl.module_specifier {
+                        let names = decl.specifiers.iter().map(|spec| spec.local.as_str());
+                        self.import(file, specifier, names, decl.position)?;
+                    }
+                    let module = self.running.last_mut().unwrap();
+                    module.exported.extend(exported_names(decl));
+                }
+                _ => {}
+            }
+        }
+
+        self.running.pop();
+        self.done.insert(path);
+        Ok(())
+    }
+
+    fn import<'a>(
+        &mut self,
+        file: &str,
+        specifier: &str,
+        names: impl IntoIterator<Item = &'a str>,
+        position: Position,
+    ) -> Result<(), RaccoonError> {
+        if !specifier.starts_with("./") && !specifier.starts_with("../") {
+            return Ok(());
+        }
+        let Ok(target) = resolve_module_path(Some(&file.to_string()), specifier) else {
+            return Ok(());
+        };
+        let Ok(path) = std::fs::canonicalize(&target) else {
+            return Ok(());
+        };
+
+        if let Some(start) = self.running.iter().position(|module| module.path == path) {
+            let exported = &self.running[start].exported;
+            if let Some(name) = names.into_iter().find(|name| !exported.contains(*name)) {
+                let mut chain: Vec<PathBuf> = self.running[start..]
+                    .iter()
+                    .map(|module| module.path.clone())
+                    .collect();
+                chain.push(path);
+                return Err(RaccoonError::semantic_error(
+                    format!(
+                        "Cannot import '{}' from {} before it is initialized: {}",
+                        name,
+                        specifier,
+                        import_chain(&chain)
+                    ),
+                    position,
+                    Some(file.to_string()),
+                ));
+            }
+            return Ok(());
+        }
+        if self.done.contains(&path) {
+            return Ok(());
+        }
+
+        let target = target.to_string_lossy().into_owned();
+        let Ok(source) = std::fs::read_to_string(&target) else {
+            return Ok(());
+        };
+        let Ok(tokens) = Lexer::new(source, Some(target.clone())).tokenize() else {
+            return Ok(());
+        };
+        let Ok(program) = Parser::new(tokens, Some(target.clone())).parse() else {
+            return Ok(());
+        };
+        self.run(path, &target, &program)
+    }
+}
+
+fn exported_names(decl: &ExportDecl) -> Vec<String> {
+    if decl.is_default {
+        return vec!["default".to_string()];
+    }
+    let declared = decl.declaration.as_deref().and_then(|stmt| match stmt {
+        Stmt::FnDecl(decl) => Some(decl.name.clone()),
+        Stmt::ClassDecl(decl) => Some(decl.name.clone()),
+        Stmt::EnumDecl(decl) => Some(decl.name.clone()),
+        Stmt::VarDecl(decl) => match &decl.pattern {
+            VarPattern::Identifier(name) => Some(name.clone()),
+            _ => None,
+        },
+        _ => None,
+    });
+    declared
+        .into_iter()
+        .chain(
+            decl.specifiers
+                .iter()
+                .map(|spec| spec.exported.clone().unwrap_or_else(|| spec.local.clone())),
+        )
+        .collect()
+}
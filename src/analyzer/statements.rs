@@ -1,10 +1,11 @@
 use crate::{
     ast::{nodes::*, types::*},
     error::RaccoonError,
+    symbol_table::SymbolKind,
     type_system::TypeResolver,
 };
 
-use super::SemanticAnalyzer;
+use super::{control_flow, declarations, SemanticAnalyzer};
 
 pub fn check_stmt(analyzer: &mut SemanticAnalyzer, stmt: &Stmt) -> Result<Type, RaccoonError> {
     match stmt {
@@ -20,8 +21,11 @@ pub fn check_stmt(analyzer: &mut SemanticAnalyzer, stmt: &Stmt) -> Result<Type,
         Stmt::ClassDecl(decl) => analyzer.check_class_decl(decl),
         Stmt::InterfaceDecl(_) => Ok(PrimitiveType::void()),
         Stmt::EnumDecl(_) => Ok(PrimitiveType::void()),
-        Stmt::TypeAliasDecl(_) => Ok(PrimitiveType::void()),
-        Stmt::ImportDecl(_) => Ok(PrimitiveType::void()),
+        Stmt::TypeAliasDecl(decl) => {
+            declarations::register_type_alias(analyzer, decl)?;
+            Ok(PrimitiveType::void())
+        }
+        Stmt::ImportDecl(decl) => check_import_decl(analyzer, decl),
         Stmt::ExportDecl(decl) => check_export_decl(analyzer, decl),
         Stmt::Block(block) => check_block(analyzer, block),
         Stmt::IfStmt(stmt) => analyzer.check_if_stmt(stmt),
@@ -37,7 +41,38 @@ pub fn check_stmt(analyzer: &mut SemanticAnalyzer, stmt: &Stmt) -> Result<Type,
         Stmt::ExprStmt(stmt) => analyzer.check_expr(&stmt.expression),
         Stmt::TryStmt(stmt) => check_try_stmt(analyzer, stmt),
         Stmt::ThrowStmt(stmt) => check_throw_stmt(analyzer, stmt),
+        Stmt::AssertStmt(stmt) => check_assert_stmt(analyzer, stmt),
+        Stmt::UsingStmt(stmt) => check_using_stmt(analyzer, stmt),
+        Stmt::DeferStmt(stmt) => check_defer_stmt(analyzer, stmt),
+    }
+}
+
+pub fn check_import_decl(
+    analyzer: &mut SemanticAnalyzer,
+    decl: &ImportDecl,
+) -> Result<Type, RaccoonError> {
+    let names = decl
+        .default_import
+        .iter()
+        .chain(decl.namespace_import.iter())
+        .chain(
+            decl.named_imports
+                .iter()
+                .map(|spec| spec.local.as_ref().unwrap_or(&spec.imported)),
+        );
+
+    for name in names.cloned().collect::<Vec<_>>() {
+        analyzer.declare_binding(
+            &name,
+            SymbolKind::Import,
+            PrimitiveType::any(),
+            true,
+            Some(Box::new(Stmt::ImportDecl(decl.clone()))),
+            decl.position,
+        );
     }
+
+    Ok(PrimitiveType::void())
 }
 
 pub fn check_export_decl(
@@ -45,18 +80,27 @@ pub fn check_export_decl(
     decl: &ExportDecl,
 ) -> Result<Type, RaccoonError> {
     if let Some(ref declaration) = decl.declaration {
-        return analyzer.check_stmt(declaration);
+        let declared_type = analyzer.check_stmt(declaration)?;
+        if let Stmt::VarDecl(VarDecl {
+            pattern: VarPattern::Identifier(name),
+            ..
+        }) = declaration.as_ref()
+        {
+            analyzer.symbol_table.mark_used(name);
+        }
+        return Ok(declared_type);
     }
 
     for spec in &decl.specifiers {
         let symbol = analyzer.symbol_table.lookup(&spec.local);
         if symbol.is_none() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::semantic_error(
                 format!("Cannot export '{}': not found", spec.local),
                 decl.position,
                 analyzer.file.clone(),
             ));
         }
+        analyzer.symbol_table.reference(&spec.local, decl.position);
     }
 
     Ok(PrimitiveType::void())
@@ -65,6 +109,7 @@ pub fn check_export_decl(
 pub fn check_block(analyzer: &mut SemanticAnalyzer, block: &Block) -> Result<Type, RaccoonError> {
     analyzer.symbol_table.enter_scope();
 
+    control_flow::check_reachable(analyzer, &block.statements);
     for stmt in &block.statements {
         analyzer.check_stmt(stmt)?;
     }
@@ -78,7 +123,7 @@ pub fn check_return_stmt(
     stmt: &ReturnStmt,
 ) -> Result<Type, RaccoonError> {
     if analyzer.current_function.is_none() {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::semantic_error(
             "Return statement outside function",
             stmt.position,
             analyzer.file.clone(),
@@ -95,7 +140,7 @@ pub fn check_return_stmt(
 
 pub fn check_break_stmt(analyzer: &SemanticAnalyzer) -> Result<Type, RaccoonError> {
     if !analyzer.in_loop {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::semantic_error(
             "Break statement outside loop",
             (0, 0),
             analyzer.file.clone(),
@@ -106,7 +151,7 @@ pub fn check_break_stmt(analyzer: &SemanticAnalyzer) -> Result<Type, RaccoonErro
 
 pub fn check_continue_stmt(analyzer: &SemanticAnalyzer) -> Result<Type, RaccoonError> {
     if !analyzer.in_loop {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::semantic_error(
             "Continue statement outside loop",
             (0, 0),
             analyzer.file.clone(),
@@ -138,8 +183,14 @@ pub fn check_try_stmt(
             false,
             None,
         );
+        analyzer
+            .symbol_table
+            .declared_at(&catch_clause.error_var, catch_clause.position);
 
+        let prev_in_catch = analyzer.in_catch;
+        analyzer.in_catch = true;
         check_block(analyzer, &catch_clause.body)?;
+        analyzer.in_catch = prev_in_catch;
         analyzer.symbol_table.exit_scope();
     }
 
@@ -154,6 +205,74 @@ pub fn check_throw_stmt(
     analyzer: &mut SemanticAnalyzer,
     stmt: &ThrowStmt,
 ) -> Result<Type, RaccoonError> {
-    analyzer.check_expr(&stmt.value)?;
+    match &stmt.value {
+        Some(value) => {
+            analyzer.check_expr(value)?;
+        }
+        None if !analyzer.in_catch => {
+            return Err(RaccoonError::semantic_error(
+                "A bare 'throw' can only rethrow inside a catch block",
+                stmt.position,
+                analyzer.file.clone(),
+            ));
+        }
+        None => {}
+    }
+    Ok(PrimitiveType::void())
+}
+
+pub fn check_using_stmt(
+    analyzer: &mut SemanticAnalyzer,
+    stmt: &UsingStmt,
+) -> Result<Type, RaccoonError> {
+    let resolver = TypeResolver::new(&analyzer.symbol_table, analyzer.file.clone());
+    let explicit_type = resolver.resolve(&stmt.type_annotation)?;
+    let init_type = analyzer.check_expr(&stmt.initializer)?;
+
+    let resource_type = if matches!(explicit_type.kind(), TypeKind::Unknown | TypeKind::Any) {
+        init_type
+    } else if init_type.is_assignable_to(&explicit_type) {
+        explicit_type
+    } else {
+        return Err(RaccoonError::type_error(
+            format!(
+                "Cannot assign type '{}' to variable of type '{}'",
+                init_type, stmt.type_annotation
+            ),
+            stmt.position,
+            analyzer.file.clone(),
+        ));
+    };
+
+    analyzer.symbol_table.enter_scope();
+    analyzer.symbol_table.define(
+        stmt.name.clone(),
+        SymbolKind::Variable,
+        resource_type,
+        true,
+        None,
+    );
+    analyzer.symbol_table.declared_at(&stmt.name, stmt.position);
+    let result = check_block(analyzer, &stmt.body);
+    analyzer.symbol_table.exit_scope();
+    result
+}
+
+pub fn check_defer_stmt(
+    analyzer: &mut SemanticAnalyzer,
+    stmt: &DeferStmt,
+) -> Result<Type, RaccoonError> {
+    analyzer.check_expr(&stmt.expression)?;
+    check_block(analyzer, &stmt.body)
+}
+
+pub fn check_assert_stmt(
+    analyzer: &mut SemanticAnalyzer,
+    stmt: &AssertStmt,
+) -> Result<Type, RaccoonError> {
+    analyzer.check_expr(&stmt.condition)?;
+    if let Some(message) = &stmt.message {
+        analyzer.check_expr(message)?;
+    }
     Ok(PrimitiveType::void())
 }
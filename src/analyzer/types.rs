@@ -58,6 +58,16 @@ pub fn collect_return_types(
         Stmt::ForInStmt(for_in) => {
             analyzer.collect_return_types(&for_in.body, return_types)?;
         }
+        Stmt::UsingStmt(using) => {
+            for s in &using.body.statements {
+                analyzer.collect_return_types(s, return_types)?;
+            }
+        }
+        Stmt::DeferStmt(defer) => {
+            for s in &defer.body.statements {
+                analyzer.collect_return_types(s, return_types)?;
+            }
+        }
         Stmt::TryStmt(try_stmt) => {
             for s in &try_stmt.try_block.statements {
                 analyzer.collect_return_types(s, return_types)?;
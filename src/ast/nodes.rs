@@ -1,8 +1,13 @@
 use super::types::Type;
 use crate::tokens::{AccessModifier, BinaryOperator, Position, TokenType, UnaryOperator};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
-#[derive(Debug, Clone, PartialEq)]
+/// A function body, shared by the functions created from it.
+pub type Body = Arc<[Stmt]>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NodeType {
     Program,
     VarDecl,
@@ -24,6 +29,8 @@ pub enum NodeType {
     ForInStmt,
     ForOfStmt,
     SwitchStmt,
+    SwitchExpr,
+    DoExpr,
     ReturnStmt,
     BreakStmt,
     ContinueStmt,
@@ -31,6 +38,7 @@ pub enum NodeType {
     TryStmt,
     CatchClause,
     ThrowStmt,
+    AssertStmt,
     BinaryExpr,
     UnaryExpr,
     CallExpr,
@@ -38,11 +46,14 @@ pub enum NodeType {
     MemberExpr,
     MethodCallExpr,
     IndexExpr,
+    SliceExpr,
     AwaitExpr,
     ThisExpr,
     SuperExpr,
     TypeOfExpr,
     InstanceOfExpr,
+    AsExpr,
+    IsExpr,
     ArrowFnExpr,
     Identifier,
     Assignment,
@@ -72,14 +83,14 @@ pub trait ASTNodeTrait {
     fn position(&self) -> Position;
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ASTNode {
     Stmt(Stmt),
     Expr(Expr),
     Pattern(DestructuringPattern),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Stmt {
     Program(Program),
     VarDecl(VarDecl),
@@ -104,9 +115,12 @@ pub enum Stmt {
     ExprStmt(ExprStmt),
     TryStmt(TryStmt),
     ThrowStmt(ThrowStmt),
+    AssertStmt(AssertStmt),
+    UsingStmt(UsingStmt),
+    DeferStmt(DeferStmt),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expr {
     Binary(BinaryExpr),
     Unary(UnaryExpr),
@@ -115,11 +129,14 @@ pub enum Expr {
     Member(MemberExpr),
     MethodCall(MethodCallExpr),
     Index(IndexExpr),
+    Slice(SliceExpr),
     Await(AwaitExpr),
     This(ThisExpr),
     Super(SuperExpr),
     TypeOf(TypeOfExpr),
     InstanceOf(InstanceOfExpr),
+    As(AsExpr),
+    Is(IsExpr),
     ArrowFn(ArrowFnExpr),
     Identifier(Identifier),
     Assignment(Assignment),
@@ -131,6 +148,8 @@ pub enum Expr {
     UnaryUpdate(UnaryUpdateExpr),
     TemplateStr(TemplateStrExpr),
     TaggedTemplate(TaggedTemplateExpr),
+    Switch(SwitchExpr),
+    Do(DoExpr),
     IntLiteral(IntLiteral),
     BigIntLiteral(BigIntLiteral),
     FloatLiteral(FloatLiteral),
@@ -138,169 +157,249 @@ pub enum Expr {
     BoolLiteral(BoolLiteral),
     NullLiteral(NullLiteral),
     ArrayLiteral(ArrayLiteral),
+    TupleLiteral(TupleLiteral),
     ObjectLiteral(ObjectLiteral),
     Spread(SpreadExpr),
     Match(MatchExpr),
     Class(ClassExpr),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DestructuringPattern {
     Array(ArrayPattern),
     Object(ObjectPattern),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl DestructuringPattern {
+    pub fn bound_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        match self {
+            DestructuringPattern::Array(pattern) => pattern.collect_names(&mut names),
+            DestructuringPattern::Object(pattern) => pattern.collect_names(&mut names),
+        }
+        names
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Program {
     pub stmts: Vec<Stmt>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum VarPattern {
     Identifier(String),
     Destructuring(DestructuringPattern),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VarDecl {
     pub pattern: VarPattern,
     pub type_annotation: Type,
     pub initializer: Option<Expr>,
     pub is_constant: bool,
+    /// Text of the doc comment (`///` or `/** */`) preceding the declaration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FnParam {
     pub pattern: VarPattern,
     pub param_type: Type,
     pub default_value: Option<Expr>,
     pub is_rest: bool,
     pub is_optional: bool,
+    /// Whether the parameter was written without a type, leaving it `any`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_untyped: bool,
+    #[serde(default)]
+    pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FnDecl {
     pub name: String,
     pub type_parameters: Vec<super::types::TypeParameter>,
     pub parameters: Vec<FnParam>,
     pub return_type: Option<Type>,
-    pub body: Vec<Stmt>,
+    pub body: Body,
     pub is_async: bool,
     pub is_declare: bool,
     pub decorators: Vec<DecoratorDecl>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassProperty {
     pub name: String,
     pub property_type: Type,
     pub initializer: Option<Expr>,
     pub decorators: Vec<DecoratorDecl>,
     pub access_modifier: AccessModifier,
+    pub is_readonly: bool,
+    pub is_static: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassMethod {
     pub name: String,
     pub parameters: Vec<FnParam>,
     pub return_type: Option<Type>,
-    pub body: Vec<Stmt>,
+    pub body: Body,
     pub is_async: bool,
     pub decorators: Vec<DecoratorDecl>,
     pub access_modifier: AccessModifier,
     pub is_static: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+impl ClassMethod {
+    pub fn is_instance_method(&self, name: &str) -> bool {
+        !self.is_static && self.name == name
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassDecl {
     pub name: String,
     pub type_parameters: Vec<super::types::TypeParameter>,
     pub superclass: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub implements: Vec<String>,
     pub properties: Vec<ClassProperty>,
     pub constructor: Option<ConstructorDecl>,
     pub methods: Vec<ClassMethod>,
     pub accessors: Vec<PropertyAccessor>,
+    pub static_blocks: Vec<Block>,
     pub decorators: Vec<DecoratorDecl>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ConstructorDecl {
     pub parameters: Vec<FnParam>,
-    pub body: Vec<Stmt>,
+    pub body: Body,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PropertyAccessor {
     pub name: String,
     pub kind: AccessorKind,
     pub parameters: Vec<FnParam>,
     pub return_type: Option<Type>,
-    pub body: Vec<Stmt>,
+    pub body: Body,
     pub access_modifier: AccessModifier,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AccessorKind {
     Get,
     Set,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DecoratorDecl {
     pub name: String,
     pub args: Vec<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceDeclProperty {
     pub name: String,
     pub property_type: Type,
     pub optional: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceDecl {
     pub name: String,
     pub type_parameters: Vec<super::types::TypeParameter>,
     pub properties: Vec<InterfaceDeclProperty>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<ClassMethod>,
     pub extends: Vec<String>,
-    pub position: Position,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    pub position: Position,
+}
+
+impl InterfaceDecl {
+    pub fn lineage(
+        names: &[String],
+        interface: impl Fn(&str) -> Option<InterfaceDecl>,
+    ) -> Vec<InterfaceDecl> {
+        let mut lineage: Vec<InterfaceDecl> = Vec::new();
+        let mut pending: Vec<String> = names.iter().rev().cloned().collect();
+        while let Some(name) = pending.pop() {
+            if lineage.iter().any(|seen| seen.name == name) {
+                continue;
+            }
+            if let Some(decl) = interface(&name) {
+                pending.extend(decl.extends.iter().rev().cloned());
+                lineage.push(decl);
+            }
+        }
+        lineage
+    }
+
+    pub fn default_methods(
+        interfaces: &[InterfaceDecl],
+        declared: impl Fn(&str) -> bool,
+    ) -> Vec<ClassMethod> {
+        let mut defaults: Vec<ClassMethod> = Vec::new();
+        for method in interfaces.iter().flat_map(|interface| &interface.methods) {
+            if !declared(&method.name) && !defaults.iter().any(|d| d.name == method.name) {
+                defaults.push(method.clone());
+            }
+        }
+        defaults
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumMember {
     pub name: String,
     pub value: Option<Expr>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumDecl {
     pub name: String,
     pub members: Vec<EnumMember>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeAliasDecl {
     pub name: String,
     pub alias_type: Type,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ImportSpecifier {
     pub imported: String,
     pub local: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ImportDecl {
     pub default_import: Option<String>,
     pub named_imports: Vec<ImportSpecifier>,
@@ -309,13 +408,13 @@ pub struct ImportDecl {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ExportSpecifier {
     pub local: String,
     pub exported: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ExportDecl {
     pub declaration: Option<Box<Stmt>>,
     pub specifiers: Vec<ExportSpecifier>,
@@ -324,13 +423,13 @@ pub struct ExportDecl {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Block {
     pub statements: Vec<Stmt>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IfStmt {
     pub condition: Expr,
     pub then_branch: Box<Stmt>,
@@ -338,14 +437,14 @@ pub struct IfStmt {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WhileStmt {
     pub condition: Expr,
     pub body: Box<Stmt>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ForStmt {
     pub initializer: Option<Box<Stmt>>,
     pub condition: Option<Expr>,
@@ -354,9 +453,9 @@ pub struct ForStmt {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ForInStmt {
-    pub variable: String,
+    pub pattern: VarPattern,
     pub is_const: bool,
     pub type_annotation: Option<Type>,
     pub iterable: Expr,
@@ -364,9 +463,9 @@ pub struct ForInStmt {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ForOfStmt {
-    pub variable: String,
+    pub pattern: VarPattern,
     pub is_const: bool,
     pub type_annotation: Option<Type>,
     pub iterable: Expr,
@@ -374,49 +473,69 @@ pub struct ForOfStmt {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DoWhileStmt {
     pub body: Box<Stmt>,
     pub condition: Expr,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SwitchCase {
-    pub test: Option<Expr>,
+    pub tests: Vec<Expr>,
     pub consequent: Vec<Stmt>,
+    pub fallthrough: bool,
+}
+
+impl SwitchCase {
+    pub fn is_default(&self) -> bool {
+        self.tests.is_empty()
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SwitchStmt {
     pub discriminant: Expr,
     pub cases: Vec<SwitchCase>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwitchExpr {
+    pub discriminant: Box<Expr>,
+    pub cases: Vec<SwitchCase>,
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoExpr {
+    pub body: Block,
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ReturnStmt {
     pub value: Option<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BreakStmt {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContinueStmt {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ExprStmt {
     pub expression: Expr,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TryStmt {
     pub try_block: Block,
     pub catch_clauses: Vec<CatchClause>,
@@ -424,7 +543,7 @@ pub struct TryStmt {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CatchClause {
     pub error_var: String,
     pub error_type: Option<Type>,
@@ -432,13 +551,73 @@ pub struct CatchClause {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ThrowStmt {
-    pub value: Expr,
+    /// `None` for a bare `throw;`, which rethrows the error the enclosing `catch` is handling.
+    pub value: Option<Expr>,
+    pub position: Position,
+}
+
+/// `using name = resource;`, disposing of the resource once `body` is left.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsingStmt {
+    pub name: String,
+    pub type_annotation: Type,
+    pub initializer: Expr,
+    pub body: Block,
+    pub position: Position,
+}
+
+/// `defer expression;`, evaluating the expression once `body` is left.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeferStmt {
+    pub expression: Expr,
+    pub body: Block,
+    pub position: Position,
+}
+
+/// `assert`, `require` or `ensure`; `source` is the condition as written.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssertStmt {
+    pub kind: AssertKind,
+    pub condition: Expr,
+    pub message: Option<Expr>,
+    pub source: String,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssertKind {
+    Assert,
+    Require,
+    Ensure,
+}
+
+impl AssertKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "assert" => Some(Self::Assert),
+            "require" => Some(Self::Require),
+            "ensure" => Some(Self::Ensure),
+            _ => None,
+        }
+    }
+
+    /// The message for a failed check, e.g. `Precondition failed: amount > 0`.
+    pub fn failure_message(&self, source: &str, message: Option<&str>) -> String {
+        let label = match self {
+            Self::Assert => "Assertion failed",
+            Self::Require => "Precondition failed",
+            Self::Ensure => "Postcondition failed",
+        };
+        match message {
+            Some(message) => format!("{}: {} ({})", label, message, source),
+            None => format!("{}: {}", label, source),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BinaryExpr {
     pub left: Box<Expr>,
     pub operator: BinaryOperator,
@@ -446,14 +625,14 @@ pub struct BinaryExpr {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UnaryExpr {
     pub operator: UnaryOperator,
     pub operand: Box<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CallExpr {
     pub callee: Box<Expr>,
     pub args: Vec<Expr>,
@@ -461,66 +640,94 @@ pub struct CallExpr {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NewExpr {
     pub class_name: String,
     pub type_args: Vec<Type>,
     pub args: Vec<Expr>,
+    pub named_args: HashMap<String, Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MemberExpr {
     pub object: Box<Expr>,
     pub property: String,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MethodCallExpr {
     pub object: Box<Expr>,
     pub method: String,
     pub args: Vec<Expr>,
+    pub named_args: HashMap<String, Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IndexExpr {
     pub object: Box<Expr>,
     pub index: Box<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SliceExpr {
+    pub object: Box<Expr>,
+    pub start: Option<Box<Expr>>,
+    pub end: Option<Box<Expr>>,
+    /// `false` for `xs[a..<b]`, which stops before `b`.
+    pub inclusive: bool,
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AwaitExpr {
     pub expression: Box<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ThisExpr {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SuperExpr {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeOfExpr {
     pub operand: Box<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InstanceOfExpr {
     pub operand: Box<Expr>,
     pub type_name: String,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// `operand as T`: the value converted to `T`, or a `TypeError` when it has no such conversion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsExpr {
+    pub operand: Box<Expr>,
+    pub target_type: Type,
+    pub position: Position,
+}
+
+/// `operand is T`: whether the value already has type `T`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IsExpr {
+    pub operand: Box<Expr>,
+    pub target_type: Type,
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArrowFnExpr {
     pub parameters: Vec<FnParam>,
     pub return_type: Option<Type>,
@@ -529,19 +736,19 @@ pub struct ArrowFnExpr {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ArrowFnBody {
     Expr(Box<Expr>),
-    Block(Vec<Stmt>),
+    Block(Body),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Identifier {
     pub name: String,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Assignment {
     pub target: Box<Expr>,
     pub value: Box<Expr>,
@@ -549,14 +756,16 @@ pub struct Assignment {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RangeExpr {
     pub start: Box<Expr>,
     pub end: Box<Expr>,
+    /// `false` for `start..<end`, which stops before `end`.
+    pub inclusive: bool,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ConditionalExpr {
     pub condition: Box<Expr>,
     pub then_expr: Box<Expr>,
@@ -564,27 +773,27 @@ pub struct ConditionalExpr {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NullCoalescingExpr {
     pub left: Box<Expr>,
     pub right: Box<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OptionalChainingExpr {
     pub object: Box<Expr>,
     pub property: String,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NullAssertionExpr {
     pub operand: Box<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UnaryUpdateExpr {
     pub operator: UpdateOperator,
     pub operand: Box<Expr>,
@@ -592,138 +801,175 @@ pub struct UnaryUpdateExpr {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum UpdateOperator {
     Increment,
     Decrement,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TemplateStrExpr {
     pub parts: Vec<TemplateStrPart>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TemplateStrPart {
     String(StrLiteral),
     Expr(Expr),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TaggedTemplateExpr {
     pub tag: Box<Expr>,
     pub template: TemplateStrExpr,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IntLiteral {
     pub value: i64,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BigIntLiteral {
     pub value: String,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FloatLiteral {
     pub value: f64,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StrLiteral {
     pub value: String,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BoolLiteral {
     pub value: bool,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NullLiteral {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArrayLiteral {
     pub elements: Vec<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// `(a, b)`; a single element needs a trailing comma, `(a,)`, and `()` is the empty tuple.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TupleLiteral {
+    pub elements: Vec<Expr>,
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ObjectLiteralProperty {
     KeyValue { key: String, value: Expr },
     Spread(Expr),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ObjectLiteral {
     pub properties: Vec<ObjectLiteralProperty>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArrayPattern {
     pub elements: Vec<Option<ArrayPatternElement>>,
     pub rest: Option<RestElement>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl ArrayPattern {
+    fn collect_names(&self, names: &mut Vec<String>) {
+        for element in self.elements.iter().flatten() {
+            match element {
+                ArrayPatternElement::Identifier(ident) => names.push(ident.name.clone()),
+                ArrayPatternElement::List(pattern) => pattern.collect_names(names),
+                ArrayPatternElement::Object(pattern) => pattern.collect_names(names),
+            }
+        }
+        if let Some(rest) = &self.rest {
+            names.push(rest.argument.name.clone());
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ArrayPatternElement {
     Identifier(Identifier),
     List(Box<ArrayPattern>),
     Object(Box<ObjectPattern>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ObjectPattern {
     pub properties: Vec<ObjectPatternProperty>,
     pub rest: Option<RestElement>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl ObjectPattern {
+    fn collect_names(&self, names: &mut Vec<String>) {
+        for property in &self.properties {
+            match &property.value {
+                ObjectPatternValue::Identifier(ident) => names.push(ident.name.clone()),
+                ObjectPatternValue::Array(pattern) => pattern.collect_names(names),
+                ObjectPatternValue::Object(pattern) => pattern.collect_names(names),
+            }
+        }
+        if let Some(rest) = &self.rest {
+            names.push(rest.argument.name.clone());
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ObjectPatternProperty {
     pub key: String,
     pub value: ObjectPatternValue,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ObjectPatternValue {
     Identifier(Identifier),
     Array(ArrayPattern),
     Object(ObjectPattern),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RestElement {
     pub argument: Identifier,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SpreadExpr {
     pub argument: Box<Expr>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MatchExpr {
     pub scrutinee: Box<Expr>,
     pub arms: Vec<MatchArm>,
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MatchArm {
     pub pattern: Pattern,
     pub guard: Option<Box<Expr>>,
@@ -731,13 +977,14 @@ pub struct MatchArm {
     pub position: Position,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Pattern {
     Wildcard(Position),
     Literal(Box<Expr>),
     Range(Box<Expr>, Box<Expr>),
     Type(Type),
     Array(Vec<Pattern>),
+    Tuple(Vec<Pattern>),
     Object(Vec<(String, Pattern)>),
     Variable(String),
     Or(Vec<Pattern>),
@@ -750,7 +997,9 @@ impl Pattern {
             Pattern::Literal(expr) => expr.position(),
             Pattern::Range(start, _) => start.position(),
             Pattern::Type(_) => (1, 1),
-            Pattern::Array(patterns) => patterns.first().map(|p| p.position()).unwrap_or((1, 1)),
+            Pattern::Array(patterns) | Pattern::Tuple(patterns) => {
+                patterns.first().map(|p| p.position()).unwrap_or((1, 1))
+            }
             Pattern::Object(_) => (1, 1),
             Pattern::Variable(_) => (1, 1),
             Pattern::Or(patterns) => patterns.first().map(|p| p.position()).unwrap_or((1, 1)),
@@ -758,10 +1007,12 @@ impl Pattern {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassExpr {
     pub type_parameters: Vec<super::types::TypeParameter>,
     pub superclass: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub implements: Vec<String>,
     pub properties: Vec<ClassProperty>,
     pub constructor: Option<ConstructorDecl>,
     pub methods: Vec<ClassMethod>,
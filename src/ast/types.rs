@@ -1,7 +1,9 @@
 use crate::{error::RaccoonError, tokens::AccessModifier};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TypeKind {
     Int,
     BigInt,
@@ -62,7 +64,7 @@ pub trait TypeTrait {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Type {
     Primitive(PrimitiveType),
     Array(Box<ArrayType>),
@@ -176,7 +178,81 @@ impl Type {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Primitive(t) => write!(f, "{}", t.name),
+            Type::Array(a) => match &a.element_type {
+                element @ (Type::Union(_) | Type::Intersection(_) | Type::Function(_)) => {
+                    write!(f, "({})[]", element)
+                }
+                element => write!(f, "{}[]", element),
+            },
+            Type::Tuple(t) => write!(f, "[{}]", join(&t.element_types, ", ")),
+            Type::Map(m) => write!(f, "Map<{}, {}>", m.key_type, m.value_type),
+            Type::Object(o) => {
+                let mut names: Vec<&String> = o.properties.keys().collect();
+                names.sort();
+                let members: Vec<String> = names
+                    .into_iter()
+                    .map(|name| {
+                        let property = &o.properties[name];
+                        let optional = if property.optional { "?" } else { "" };
+                        format!("{}{}: {}", name, optional, property.property_type)
+                    })
+                    .collect();
+                write!(f, "{{ {} }}", members.join("; "))
+            }
+            Type::Nullable(n) => write!(f, "{}?", n.inner_type),
+            Type::Union(u) => write!(f, "{}", join(&u.types, " | ")),
+            Type::Intersection(i) => write!(f, "{}", join(&i.types, " & ")),
+            Type::Function(func) => {
+                let params: Vec<String> = func
+                    .params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, param)| match func.param_names.get(i) {
+                        Some(name) => format!("{}: {}", name, param),
+                        None => param.to_string(),
+                    })
+                    .collect();
+                write!(f, "({}) => {}", params.join(", "), func.return_type)
+            }
+            Type::Interface(i) => write!(f, "{}", i.name),
+            Type::Class(c) => write!(f, "{}", c.name),
+            Type::Enum(e) => write!(f, "{}", e.name),
+            Type::Future(t) => write!(f, "Future<{}>", t.inner_type),
+            Type::TypeRef(r) => write!(f, "{}", r.name),
+            Type::TypeParam(p) => write!(f, "{}", p.name),
+            Type::Generic(g) => write!(f, "{}<{}>", g.base, join(&g.type_args, ", ")),
+            Type::Mapped(m) => write!(
+                f,
+                "{{ {}[{} in {}]{}: {} }}",
+                if m.readonly { "readonly " } else { "" },
+                m.type_parameter,
+                m.constraint,
+                if m.optional { "?" } else { "" },
+                m.value_type
+            ),
+            Type::Indexed(i) => write!(f, "{}[{}]", i.object_type, i.index_type),
+            Type::KeyOf(k) => write!(f, "keyof {}", k.target_type),
+            Type::TypeOf(t) => write!(f, "typeof {}", t.expression_name),
+            Type::Readonly(r) => write!(f, "readonly {}", r.inner_type),
+            Type::PrimitiveTypeObject(p) => write!(f, "{}", p.primitive_type.name),
+            Type::ClassObject(c) => write!(f, "{}", c.class_type.name),
+        }
+    }
+}
+
+fn join(types: &[Type], separator: &str) -> String {
+    types
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PrimitiveType {
     pub kind: TypeKind,
     pub name: String,
@@ -326,9 +402,13 @@ impl PrimitiveType {
     pub fn never() -> Type {
         Type::Primitive(PrimitiveType::new(TypeKind::Never, "never"))
     }
+
+    pub fn symbol() -> Type {
+        Type::Primitive(PrimitiveType::new(TypeKind::Symbol, "symbol"))
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArrayType {
     pub element_type: Type,
 }
@@ -349,13 +429,13 @@ impl ArrayType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MapType {
     pub key_type: Type,
     pub value_type: Type,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NullableType {
     pub inner_type: Type,
 }
@@ -376,7 +456,7 @@ impl NullableType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UnionType {
     pub types: Vec<Type>,
 }
@@ -418,14 +498,30 @@ impl UnionType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionType {
     pub params: Vec<Type>,
     pub return_type: Type,
     pub is_variadic: bool,
+    /// Parameter names used to bind named arguments; empty when unknown.
+    #[serde(default)]
+    pub param_names: Vec<String>,
+}
+
+impl PartialEq for FunctionType {
+    fn eq(&self, other: &Self) -> bool {
+        self.params == other.params
+            && self.return_type == other.return_type
+            && self.is_variadic == other.is_variadic
+    }
 }
 
 impl FunctionType {
+    pub fn with_param_names(mut self, names: Vec<String>) -> Self {
+        self.param_names = names;
+        self
+    }
+
     pub fn equals(&self, other: &FunctionType) -> bool {
         if self.params.len() != other.params.len() {
             return false;
@@ -440,20 +536,20 @@ impl FunctionType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceType {
     pub name: String,
     pub properties: HashMap<String, InterfaceProperty>,
     pub type_parameters: Vec<TypeParameter>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceProperty {
     pub property_type: Type,
     pub optional: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassType {
     pub name: String,
     pub superclass: Option<Box<ClassType>>,
@@ -463,37 +559,38 @@ pub struct ClassType {
     pub type_parameters: Vec<TypeParameter>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassPropertyInfo {
     pub property_type: Type,
     pub access_modifier: AccessModifier,
+    pub is_static: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassMethodInfo {
     pub method_type: FunctionType,
     pub access_modifier: AccessModifier,
     pub is_static: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumType {
     pub name: String,
     pub members: HashMap<String, EnumValue>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EnumValue {
     Int(i64),
     Str(String),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FutureType {
     pub inner_type: Type,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeReference {
     pub name: String,
     pub file: Option<String>,
@@ -516,7 +613,7 @@ impl TypeReference {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeParameter {
     pub name: String,
     pub constraint: Option<Box<Type>>,
@@ -535,23 +632,23 @@ impl TypeParameter {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GenericType {
     pub base: Type,
     pub type_args: Vec<Type>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PrimitiveTypeObjectType {
     pub primitive_type: PrimitiveType,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassObjectType {
     pub class_type: ClassType,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TupleType {
     pub element_types: Vec<Type>,
 }
@@ -591,12 +688,12 @@ impl TupleType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ObjectType {
     pub properties: HashMap<String, ObjectProperty>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ObjectProperty {
     pub property_type: Type,
     pub optional: bool,
@@ -674,7 +771,7 @@ impl ObjectType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IntersectionType {
     pub types: Vec<Type>,
 }
@@ -716,7 +813,7 @@ impl IntersectionType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ReadonlyType {
     pub inner_type: Type,
 }
@@ -741,7 +838,7 @@ impl ReadonlyType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MappedType {
     pub type_parameter: String,
     pub constraint: Box<Type>,
@@ -762,7 +859,7 @@ impl MappedType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IndexedAccessType {
     pub object_type: Box<Type>,
     pub index_type: Box<Type>,
@@ -777,7 +874,7 @@ impl IndexedAccessType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct KeyOfType {
     pub target_type: Box<Type>,
 }
@@ -790,7 +887,7 @@ impl KeyOfType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeOfType {
     pub expression_name: String,
 }
@@ -0,0 +1,250 @@
+use crate::ast::nodes::*;
+use crate::error::RaccoonError;
+use crate::interpreter::Interpreter;
+use crate::ir::{IRCompiler, IROptimizer};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+pub const BENCH_PREFIX: &str = "bench_";
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchStats {
+    pub samples: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl BenchStats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let secs: Vec<f64> = samples.iter().map(|d| d.as_secs_f64()).collect();
+        let count = secs.len() as f64;
+        let mean = secs.iter().sum::<f64>() / count;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / count;
+
+        let mut sorted = secs.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        };
+
+        Some(Self {
+            samples: samples.len(),
+            mean: Duration::from_secs_f64(mean),
+            median: Duration::from_secs_f64(median),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub name: String,
+    pub ast: Result<BenchStats, RaccoonError>,
+    pub ir: Result<BenchStats, RaccoonError>,
+}
+
+pub struct BenchRunner {
+    file: Option<String>,
+    config: BenchConfig,
+}
+
+impl BenchRunner {
+    pub fn new(file: Option<String>, config: BenchConfig) -> Self {
+        Self { file, config }
+    }
+
+    pub fn find_benchmarks(program: &Program) -> Vec<&FnDecl> {
+        program
+            .stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::FnDecl(decl) => Some(decl),
+                Stmt::ExportDecl(export) => match export.declaration.as_deref() {
+                    Some(Stmt::FnDecl(decl)) => Some(decl),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .filter(|decl| decl.name.starts_with(BENCH_PREFIX))
+            .collect()
+    }
+
+    pub async fn run(&self, program: &Program) -> Vec<BenchResult> {
+        let mut results = Vec::new();
+
+        for bench in Self::find_benchmarks(program) {
+            let call = Self::call_expr(bench);
+            let ast = self.run_ast(program, &call).await;
+            let ir = self.run_ir(program, &call).await;
+            results.push(BenchResult {
+                name: bench.name.clone(),
+                ast,
+                ir,
+            });
+        }
+
+        results
+    }
+
+    fn call_expr(bench: &FnDecl) -> Expr {
+        let call = Expr::Call(CallExpr {
+            callee: Box::new(Expr::Identifier(Identifier {
+                name: bench.name.clone(),
+                position: bench.position,
+            })),
+            args: Vec::new(),
+            named_args: HashMap::new(),
+            position: bench.position,
+        });
+
+        if bench.is_async {
+            Expr::Await(AwaitExpr {
+                expression: Box::new(call),
+                position: bench.position,
+            })
+        } else {
+            call
+        }
+    }
+
+    async fn run_ast(&self, program: &Program, call: &Expr) -> Result<BenchStats, RaccoonError> {
+        let mut interpreter = Interpreter::new(self.file.clone());
+        interpreter.interpret(program).await?;
+
+        let mut samples = Vec::with_capacity(self.config.iterations);
+        for i in 0..self.config.warmup + self.config.iterations {
+            let start = Instant::now();
+            interpreter.evaluate_expr(call).await?;
+            let elapsed = start.elapsed();
+            if i >= self.config.warmup {
+                samples.push(elapsed);
+            }
+        }
+
+        Ok(Self::stats(&samples))
+    }
+
+    async fn run_ir(&self, program: &Program, call: &Expr) -> Result<BenchStats, RaccoonError> {
+        let mut interpreter = Interpreter::new(self.file.clone());
+        interpreter.enable_ir_mode();
+        let (mut vm, _) = interpreter.execute_ir_program(program).await?;
+
+        let call_program = Program {
+            stmts: vec![Stmt::ExprStmt(ExprStmt {
+                expression: call.clone(),
+                position: program.position,
+            })],
+            position: program.position,
+        };
+        let compiled = IROptimizer::new(IRCompiler::new().compile(&call_program)?).optimize();
+
+        let mut samples = Vec::with_capacity(self.config.iterations);
+        for i in 0..self.config.warmup + self.config.iterations {
+            let start = Instant::now();
+            vm.execute(compiled.clone()).await?;
+            let elapsed = start.elapsed();
+            if i >= self.config.warmup {
+                samples.push(elapsed);
+            }
+        }
+
+        Ok(Self::stats(&samples))
+    }
+
+    fn stats(samples: &[Duration]) -> BenchStats {
+        BenchStats::from_samples(samples).unwrap_or(BenchStats {
+            samples: 0,
+            mean: Duration::ZERO,
+            median: Duration::ZERO,
+            stddev: Duration::ZERO,
+        })
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1_000.0)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1_000_000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+pub fn format_report(results: &[BenchResult]) -> String {
+    let name_width = results
+        .iter()
+        .map(|r| r.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("benchmark".len());
+
+    let mut output = format!(
+        "{:<width$}  {:>30}  {:>30}\n",
+        "benchmark",
+        "ast (mean / median / stddev)",
+        "ir (mean / median / stddev)",
+        width = name_width
+    );
+
+    for result in results {
+        output.push_str(&format!(
+            "{:<width$}  {:>30}  {:>30}\n",
+            result.name,
+            format_cell(&result.ast),
+            format_cell(&result.ir),
+            width = name_width
+        ));
+    }
+
+    for result in results {
+        for (mode, outcome) in [("ast", &result.ast), ("ir", &result.ir)] {
+            if let Err(error) = outcome {
+                output.push_str(&format!(
+                    "\n{} [{}] failed: {}\n",
+                    result.name, mode, error.message
+                ));
+            }
+        }
+    }
+
+    output
+}
+
+fn format_cell(outcome: &Result<BenchStats, RaccoonError>) -> String {
+    match outcome {
+        Ok(stats) => format!(
+            "{} / {} / {}",
+            format_duration(stats.mean),
+            format_duration(stats.median),
+            format_duration(stats.stddev)
+        ),
+        Err(_) => "error".to_string(),
+    }
+}
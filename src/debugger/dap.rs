@@ -0,0 +1,102 @@
+use serde_json::{json, Value as JsonValue};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+pub struct DapConnection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    seq: i64,
+}
+
+impl DapConnection {
+    pub fn accept(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let (stream, _) = listener.accept()?;
+        let writer = stream.try_clone()?;
+
+        Ok(Self {
+            reader: BufReader::new(stream),
+            writer,
+            seq: 0,
+        })
+    }
+
+    pub fn read_message(&mut self) -> io::Result<Option<JsonValue>> {
+        let mut content_length = None;
+
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+
+            if let Some(length) = line.strip_prefix("Content-Length:") {
+                content_length = length.trim().parse::<usize>().ok();
+            }
+        }
+
+        let Some(length) = content_length else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing Content-Length header",
+            ));
+        };
+
+        let mut body = vec![0; length];
+        self.reader.read_exact(&mut body)?;
+
+        serde_json::from_slice(&body)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn send_response(&mut self, request: &JsonValue, body: JsonValue) {
+        let message = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": true,
+            "body": body,
+        });
+        self.send(message);
+    }
+
+    pub fn send_error(&mut self, request: &JsonValue, error: &str) {
+        let message = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": false,
+            "message": error,
+        });
+        self.send(message);
+    }
+
+    pub fn send_event(&mut self, event: &str, body: JsonValue) {
+        let message = json!({
+            "type": "event",
+            "event": event,
+            "body": body,
+        });
+        self.send(message);
+    }
+
+    fn send(&mut self, mut message: JsonValue) {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+
+        let payload = message.to_string();
+        let _ = write!(
+            self.writer,
+            "Content-Length: {}\r\n\r\n{}",
+            payload.len(),
+            payload
+        );
+        let _ = self.writer.flush();
+    }
+}
@@ -0,0 +1,414 @@
+pub mod dap;
+
+use crate::ast::nodes::Stmt;
+use crate::interpreter::Interpreter;
+use crate::runtime::RuntimeValue;
+use crate::tokens::Position;
+use dap::DapConnection;
+use serde_json::{json, Value as JsonValue};
+use std::collections::{HashMap, HashSet};
+use std::io;
+
+pub const DEFAULT_DEBUG_PORT: u16 = 4711;
+
+const THREAD_ID: i64 = 1;
+const LOCALS_REFERENCE: i64 = 1;
+const GLOBALS_REFERENCE: i64 = 2;
+const FIRST_VALUE_REFERENCE: i64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepMode {
+    Continue,
+    StepIn,
+    StepOver(usize),
+    StepOut(usize),
+}
+
+pub struct Debugger {
+    connection: Option<DapConnection>,
+    breakpoints: HashMap<String, HashSet<usize>>,
+    step_mode: StepMode,
+    last_location: Option<(usize, usize)>,
+    value_handles: Vec<RuntimeValue>,
+}
+
+impl Debugger {
+    pub fn listen(port: u16) -> io::Result<Self> {
+        let connection = DapConnection::accept(port)?;
+        let mut debugger = Self {
+            connection: Some(connection),
+            breakpoints: HashMap::new(),
+            step_mode: StepMode::Continue,
+            last_location: None,
+            value_handles: Vec::new(),
+        };
+        debugger.wait_for_configuration();
+        Ok(debugger)
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    fn wait_for_configuration(&mut self) {
+        while let Some(request) = self.next_request() {
+            match request["command"].as_str().unwrap_or_default() {
+                "initialize" => {
+                    self.respond(
+                        &request,
+                        json!({
+                            "supportsConfigurationDoneRequest": true,
+                            "supportsEvaluateForHovers": true,
+                        }),
+                    );
+                    self.send_event("initialized", json!({}));
+                }
+                "launch" | "attach" => {
+                    if request["arguments"]["stopOnEntry"].as_bool() == Some(true) {
+                        self.step_mode = StepMode::StepIn;
+                    }
+                    self.respond(&request, json!({}));
+                }
+                "configurationDone" => {
+                    self.respond(&request, json!({}));
+                    return;
+                }
+                _ => {
+                    if !self.handle_common_request(&request) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn on_statement(&mut self, interpreter: &Interpreter, stmt: &Stmt) {
+        if !self.is_attached() || matches!(stmt, Stmt::Block(_) | Stmt::Program(_)) {
+            return;
+        }
+
+        let position = stmt.position();
+        let depth = interpreter.call_stack.depth();
+        let repeated_line = self.last_location == Some((position.0, depth));
+        self.last_location = Some((position.0, depth));
+
+        let reason = match self.step_mode {
+            StepMode::StepIn => Some("step"),
+            StepMode::StepOver(from) if depth <= from && !repeated_line => Some("step"),
+            StepMode::StepOut(from) if depth < from => Some("step"),
+            _ => None,
+        };
+
+        let reason = reason.or_else(|| {
+            (!repeated_line && self.has_breakpoint(&interpreter.file, position.0))
+                .then_some("breakpoint")
+        });
+
+        if let Some(reason) = reason {
+            self.pause(interpreter, position, reason);
+        }
+    }
+
+    pub fn finish(&mut self, exit_code: i32) {
+        self.send_event("exited", json!({ "exitCode": exit_code }));
+        self.send_event("terminated", json!({}));
+        self.connection = None;
+    }
+
+    fn has_breakpoint(&self, file: &Option<String>, line: usize) -> bool {
+        file.as_ref()
+            .and_then(|file| self.breakpoints.get(&normalize_path(file)))
+            .is_some_and(|lines| lines.contains(&line))
+    }
+
+    fn pause(&mut self, interpreter: &Interpreter, position: Position, reason: &str) {
+        self.value_handles.clear();
+        self.send_event(
+            "stopped",
+            json!({
+                "reason": reason,
+                "threadId": THREAD_ID,
+                "allThreadsStopped": true,
+            }),
+        );
+
+        let depth = interpreter.call_stack.depth();
+        while let Some(request) = self.next_request() {
+            let resume_mode = match request["command"].as_str().unwrap_or_default() {
+                "continue" => Some(StepMode::Continue),
+                "next" => Some(StepMode::StepOver(depth)),
+                "stepIn" => Some(StepMode::StepIn),
+                "stepOut" => Some(StepMode::StepOut(depth)),
+                "stackTrace" => {
+                    let frames = self.stack_frames(interpreter, position);
+                    let total = frames.len();
+                    self.respond(
+                        &request,
+                        json!({ "stackFrames": frames, "totalFrames": total }),
+                    );
+                    None
+                }
+                "scopes" => {
+                    self.respond(
+                        &request,
+                        json!({ "scopes": [
+                            { "name": "Locals", "variablesReference": LOCALS_REFERENCE, "expensive": false },
+                            { "name": "Globals", "variablesReference": GLOBALS_REFERENCE, "expensive": true },
+                        ]}),
+                    );
+                    None
+                }
+                "variables" => {
+                    let reference = request["arguments"]["variablesReference"]
+                        .as_i64()
+                        .unwrap_or_default();
+                    let variables = self.variables(interpreter, reference);
+                    self.respond(&request, json!({ "variables": variables }));
+                    None
+                }
+                "evaluate" => {
+                    let expression = request["arguments"]["expression"]
+                        .as_str()
+                        .unwrap_or_default();
+                    match interpreter.environment.get(expression.trim(), position) {
+                        Ok(value) => {
+                            let reference = self.reference_for(&value);
+                            self.respond(
+                                &request,
+                                json!({ "result": value.to_string(), "variablesReference": reference }),
+                            );
+                        }
+                        Err(error) => self.respond_error(&request, &error.message),
+                    }
+                    None
+                }
+                _ => {
+                    if !self.handle_common_request(&request) {
+                        return;
+                    }
+                    None
+                }
+            };
+
+            if let Some(mode) = resume_mode {
+                self.step_mode = mode;
+                self.respond(&request, json!({ "allThreadsContinued": true }));
+                return;
+            }
+        }
+    }
+
+    fn handle_common_request(&mut self, request: &JsonValue) -> bool {
+        match request["command"].as_str().unwrap_or_default() {
+            "setBreakpoints" => {
+                let arguments = &request["arguments"];
+                let path = arguments["source"]["path"].as_str().unwrap_or_default();
+                let lines: HashSet<usize> = arguments["breakpoints"]
+                    .as_array()
+                    .map(|breakpoints| {
+                        breakpoints
+                            .iter()
+                            .filter_map(|bp| bp["line"].as_u64())
+                            .map(|line| line as usize)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                let verified: Vec<JsonValue> = lines
+                    .iter()
+                    .map(|line| json!({ "verified": true, "line": line }))
+                    .collect();
+                self.breakpoints.insert(normalize_path(path), lines);
+                self.respond(request, json!({ "breakpoints": verified }));
+                true
+            }
+            "threads" => {
+                self.respond(
+                    request,
+                    json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }),
+                );
+                true
+            }
+            "disconnect" => {
+                self.respond(request, json!({}));
+                self.connection = None;
+                self.step_mode = StepMode::Continue;
+                false
+            }
+            _ => {
+                self.respond(request, json!({}));
+                true
+            }
+        }
+    }
+
+    fn stack_frames(&self, interpreter: &Interpreter, position: Position) -> Vec<JsonValue> {
+        let source = interpreter.file.as_ref().map(|file| {
+            json!({
+                "name": std::path::Path::new(file)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| file.clone()),
+                "path": normalize_path(file),
+            })
+        });
+
+        let frames = interpreter.call_stack.frames();
+        let mut result = Vec::with_capacity(frames.len() + 1);
+        let mut current = position;
+
+        for (id, frame) in frames.iter().rev().enumerate() {
+            result.push(json!({
+                "id": id,
+                "name": frame.function_name,
+                "line": current.0,
+                "column": current.1,
+                "source": source,
+            }));
+            current = frame.call_position;
+        }
+
+        result.push(json!({
+            "id": frames.len(),
+            "name": "<main>",
+            "line": current.0,
+            "column": current.1,
+            "source": source,
+        }));
+
+        result
+    }
+
+    fn variables(&mut self, interpreter: &Interpreter, reference: i64) -> Vec<JsonValue> {
+        let scopes = interpreter.environment.scopes();
+
+        let entries: Vec<(String, RuntimeValue)> = match reference {
+            LOCALS_REFERENCE if scopes.len() > 1 => scopes
+                .last()
+                .map(|scope| scope.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+                .unwrap_or_default(),
+            LOCALS_REFERENCE | GLOBALS_REFERENCE => scopes
+                .first()
+                .map(|scope| {
+                    scope
+                        .iter()
+                        .filter(|(_, v)| !is_native(v))
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => {
+                let index = (reference - FIRST_VALUE_REFERENCE) as usize;
+                match self.value_handles.get(index).cloned() {
+                    Some(value) => children_of(&value),
+                    None => Vec::new(),
+                }
+            }
+        };
+
+        let mut entries = entries;
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        entries
+            .into_iter()
+            .map(|(name, value)| {
+                let reference = self.reference_for(&value);
+                json!({
+                    "name": name,
+                    "value": value.to_string(),
+                    "type": value.get_name(),
+                    "variablesReference": reference,
+                })
+            })
+            .collect()
+    }
+
+    fn reference_for(&mut self, value: &RuntimeValue) -> i64 {
+        if children_of(value).is_empty() {
+            return 0;
+        }
+        self.value_handles.push(value.clone());
+        FIRST_VALUE_REFERENCE + self.value_handles.len() as i64 - 1
+    }
+
+    fn next_request(&mut self) -> Option<JsonValue> {
+        let connection = self.connection.as_mut()?;
+        match connection.read_message() {
+            Ok(Some(message)) if message["type"] == "request" => Some(message),
+            Ok(Some(_)) => self.next_request(),
+            Ok(None) | Err(_) => {
+                self.connection = None;
+                self.step_mode = StepMode::Continue;
+                None
+            }
+        }
+    }
+
+    fn respond(&mut self, request: &JsonValue, body: JsonValue) {
+        if let Some(connection) = self.connection.as_mut() {
+            connection.send_response(request, body);
+        }
+    }
+
+    fn respond_error(&mut self, request: &JsonValue, message: &str) {
+        if let Some(connection) = self.connection.as_mut() {
+            connection.send_error(request, message);
+        }
+    }
+
+    fn send_event(&mut self, event: &str, body: JsonValue) {
+        if let Some(connection) = self.connection.as_mut() {
+            connection.send_event(event, body);
+        }
+    }
+}
+
+fn is_native(value: &RuntimeValue) -> bool {
+    matches!(
+        value,
+        RuntimeValue::NativeFunction(_)
+            | RuntimeValue::NativeAsyncFunction(_)
+            | RuntimeValue::PrimitiveTypeObject(_)
+    )
+}
+
+fn children_of(value: &RuntimeValue) -> Vec<(String, RuntimeValue)> {
+    match value {
+        RuntimeValue::Array(array) => array
+            .elements
+            .read()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("[{}]", i), v.clone()))
+            .collect(),
+        RuntimeValue::Map(map) => map
+            .entries
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect(),
+        RuntimeValue::Object(object) => object
+            .properties
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        RuntimeValue::ClassInstance(instance) => instance
+            .properties
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| path.to_string())
+}
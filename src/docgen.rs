@@ -0,0 +1,585 @@
+use crate::analyzer::SemanticAnalyzer;
+use crate::ast::nodes::*;
+use crate::ast::types::{PrimitiveType, Type, TypeKind};
+use crate::error::RaccoonError;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::symbol_table::{SymbolKind, SymbolTable};
+use crate::tokens::{AccessModifier, CommentKind, Token};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+impl DocFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "md" | "markdown" => Some(DocFormat::Markdown),
+            "html" => Some(DocFormat::Html),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DocItem {
+    pub kind: &'static str,
+    pub name: String,
+    pub signature: String,
+    pub doc: Option<String>,
+    pub members: Vec<DocItem>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleDoc {
+    pub name: String,
+    pub doc: Option<String>,
+    pub items: Vec<DocItem>,
+}
+
+/// Builds API documentation from `///`, `/** */` and `//!` comments.
+pub struct DocGenerator {
+    globals: Vec<String>,
+}
+
+impl DocGenerator {
+    pub fn new(globals: Vec<String>) -> Self {
+        Self { globals }
+    }
+
+    pub fn document(
+        &self,
+        name: &str,
+        source: String,
+        file: Option<String>,
+    ) -> Result<ModuleDoc, RaccoonError> {
+        let tokens = Lexer::new(source, file.clone()).tokenize()?;
+        let doc = Self::module_comment(&tokens);
+        let program = Parser::new(tokens, file.clone()).parse()?;
+
+        let mut symbols = SymbolTable::new(file.clone());
+        for name in &self.globals {
+            if symbols.lookup(name).is_none() {
+                symbols.define(
+                    name.clone(),
+                    SymbolKind::Variable,
+                    PrimitiveType::any(),
+                    false,
+                    None,
+                );
+            }
+        }
+
+        let mut analyzer = SemanticAnalyzer::with_symbol_table(symbols).with_file(file);
+        // The analyzer is best-effort; whatever it resolved before an error is still used.
+        let _ = analyzer.analyze(&program);
+
+        let documenter = Documenter {
+            symbols: &analyzer.symbol_table,
+        };
+        Ok(ModuleDoc {
+            name: name.to_string(),
+            doc,
+            items: documenter.items(&program),
+        })
+    }
+
+    fn module_comment(tokens: &[Token]) -> Option<String> {
+        let lines: Vec<&str> = tokens
+            .first()?
+            .leading_comments
+            .iter()
+            .filter(|comment| comment.kind == CommentKind::Line)
+            .filter_map(|comment| comment.text.strip_prefix("//!"))
+            .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n").trim().to_string())
+    }
+}
+
+struct Documenter<'a> {
+    symbols: &'a SymbolTable,
+}
+
+impl Documenter<'_> {
+    fn items(&self, program: &Program) -> Vec<DocItem> {
+        let has_exports = program
+            .stmts
+            .iter()
+            .any(|stmt| matches!(stmt, Stmt::ExportDecl(_)));
+        let exported: Vec<&str> = program
+            .stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::ExportDecl(export) => Some(export),
+                _ => None,
+            })
+            .flat_map(|export| {
+                let default = match export.declaration.as_deref() {
+                    Some(Stmt::ExprStmt(ExprStmt {
+                        expression: Expr::Identifier(ident),
+                        ..
+                    })) => Some(ident.name.as_str()),
+                    _ => None,
+                };
+                let specifiers = export.specifiers.iter().map(|spec| spec.local.as_str());
+                specifiers.chain(default)
+            })
+            .collect();
+
+        program
+            .stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::ExportDecl(export) => export.declaration.as_deref(),
+                stmt if !has_exports => Some(stmt),
+                stmt if Self::declared_name(stmt).is_some_and(|n| exported.contains(&n)) => {
+                    Some(stmt)
+                }
+                _ => None,
+            })
+            .filter_map(|stmt| self.item(stmt))
+            .collect()
+    }
+
+    fn declared_name(stmt: &Stmt) -> Option<&str> {
+        match stmt {
+            Stmt::FnDecl(decl) => Some(&decl.name),
+            Stmt::ClassDecl(decl) => Some(&decl.name),
+            Stmt::InterfaceDecl(decl) => Some(&decl.name),
+            Stmt::EnumDecl(decl) => Some(&decl.name),
+            Stmt::TypeAliasDecl(decl) => Some(&decl.name),
+            Stmt::VarDecl(VarDecl {
+                pattern: VarPattern::Identifier(name),
+                ..
+            }) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn item(&self, stmt: &Stmt) -> Option<DocItem> {
+        let item = match stmt {
+            Stmt::FnDecl(decl) => DocItem {
+                kind: "fn",
+                name: decl.name.clone(),
+                signature: self.function_signature(decl),
+                doc: decl.doc.clone(),
+                members: Vec::new(),
+            },
+            Stmt::ClassDecl(decl) => self.class(decl),
+            Stmt::InterfaceDecl(decl) => DocItem {
+                kind: "interface",
+                name: decl.name.clone(),
+                signature: format!(
+                    "interface {}{}{}",
+                    decl.name,
+                    type_parameters(&decl.type_parameters),
+                    if decl.extends.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" extends {}", decl.extends.join(", "))
+                    }
+                ),
+                doc: decl.doc.clone(),
+                members: decl
+                    .properties
+                    .iter()
+                    .map(|property| {
+                        let optional = if property.optional { "?" } else { "" };
+                        member(
+                            "property",
+                            &property.name,
+                            format!("{}{}: {}", property.name, optional, property.property_type),
+                            None,
+                        )
+                    })
+                    .chain(decl.methods.iter().map(|method| {
+                        member(
+                            "method",
+                            &method.name,
+                            method_signature(method),
+                            method.doc.clone(),
+                        )
+                    }))
+                    .collect(),
+            },
+            Stmt::EnumDecl(decl) => DocItem {
+                kind: "enum",
+                name: decl.name.clone(),
+                signature: format!("enum {}", decl.name),
+                doc: decl.doc.clone(),
+                members: decl
+                    .members
+                    .iter()
+                    .map(|variant| {
+                        let signature = match variant.value.as_ref().and_then(literal) {
+                            Some(value) => format!("{} = {}", variant.name, value),
+                            None => variant.name.clone(),
+                        };
+                        member("member", &variant.name, signature, None)
+                    })
+                    .collect(),
+            },
+            Stmt::TypeAliasDecl(decl) => DocItem {
+                kind: "type",
+                name: decl.name.clone(),
+                signature: format!("type {} = {}", decl.name, decl.alias_type),
+                doc: decl.doc.clone(),
+                members: Vec::new(),
+            },
+            Stmt::VarDecl(decl) => {
+                let VarPattern::Identifier(name) = &decl.pattern else {
+                    return None;
+                };
+                let keyword = if decl.is_constant { "const" } else { "let" };
+                let signature = match self.variable_type(name, &decl.type_annotation) {
+                    Some(var_type) => format!("{} {}: {}", keyword, name, var_type),
+                    None => format!("{} {}", keyword, name),
+                };
+                DocItem {
+                    kind: keyword,
+                    name: name.clone(),
+                    signature,
+                    doc: decl.doc.clone(),
+                    members: Vec::new(),
+                }
+            }
+            _ => return None,
+        };
+        Some(item)
+    }
+
+    fn class(&self, decl: &ClassDecl) -> DocItem {
+        let mut members = Vec::new();
+
+        if let Some(constructor) = &decl.constructor {
+            let signature = format!("constructor({})", parameters(&constructor.parameters));
+            members.push(member("constructor", "constructor", signature, None));
+        }
+
+        for property in &decl.properties {
+            if property.access_modifier == AccessModifier::Private {
+                continue;
+            }
+            let signature = format!(
+                "{}{}{}: {}",
+                if property.is_static { "static " } else { "" },
+                if property.is_readonly {
+                    "readonly "
+                } else {
+                    ""
+                },
+                property.name,
+                property.property_type
+            );
+            members.push(member(
+                "property",
+                &property.name,
+                signature,
+                property.doc.clone(),
+            ));
+        }
+
+        for accessor in &decl.accessors {
+            if accessor.access_modifier == AccessModifier::Private {
+                continue;
+            }
+            let signature = match accessor.kind {
+                AccessorKind::Get => format!(
+                    "get {}(){}",
+                    accessor.name,
+                    return_annotation(accessor.return_type.as_ref())
+                ),
+                AccessorKind::Set => {
+                    format!(
+                        "set {}({})",
+                        accessor.name,
+                        parameters(&accessor.parameters)
+                    )
+                }
+            };
+            members.push(member("accessor", &accessor.name, signature, None));
+        }
+
+        for method in &decl.methods {
+            if method.access_modifier == AccessModifier::Private {
+                continue;
+            }
+            members.push(member(
+                "method",
+                &method.name,
+                method_signature(method),
+                method.doc.clone(),
+            ));
+        }
+
+        let superclass = match &decl.superclass {
+            Some(superclass) => format!(" extends {}", superclass),
+            None => String::new(),
+        };
+        let implements = if decl.implements.is_empty() {
+            String::new()
+        } else {
+            format!(" implements {}", decl.implements.join(", "))
+        };
+        DocItem {
+            kind: "class",
+            name: decl.name.clone(),
+            signature: format!(
+                "class {}{}{}{}",
+                decl.name,
+                type_parameters(&decl.type_parameters),
+                superclass,
+                implements
+            ),
+            doc: decl.doc.clone(),
+            members,
+        }
+    }
+
+    fn function_signature(&self, decl: &FnDecl) -> String {
+        let return_type = decl.return_type.clone().or_else(|| {
+            match &self.symbols.lookup(&decl.name)?.symbol_type {
+                Type::Function(function) => Some(function.return_type.clone()),
+                _ => None,
+            }
+            .filter(is_known)
+        });
+
+        format!(
+            "{}fn {}{}({}){}",
+            if decl.is_async { "async " } else { "" },
+            decl.name,
+            type_parameters(&decl.type_parameters),
+            parameters(&decl.parameters),
+            return_annotation(return_type.as_ref())
+        )
+    }
+
+    fn variable_type(&self, name: &str, annotation: &Type) -> Option<Type> {
+        if is_known(annotation) {
+            return Some(annotation.clone());
+        }
+        let symbol = self.symbols.lookup(name)?;
+        Some(symbol.symbol_type.clone()).filter(is_known)
+    }
+}
+
+fn member(kind: &'static str, name: &str, signature: String, doc: Option<String>) -> DocItem {
+    DocItem {
+        kind,
+        name: name.to_string(),
+        signature,
+        doc,
+        members: Vec::new(),
+    }
+}
+
+fn is_known(value_type: &Type) -> bool {
+    !matches!(value_type.kind(), TypeKind::Any | TypeKind::Unknown)
+}
+
+fn parameters(params: &[FnParam]) -> String {
+    params
+        .iter()
+        .map(|param| {
+            let name = match &param.pattern {
+                VarPattern::Identifier(name) => name.clone(),
+                VarPattern::Destructuring(DestructuringPattern::Array(_)) => "[...]".to_string(),
+                VarPattern::Destructuring(DestructuringPattern::Object(_)) => "{...}".to_string(),
+            };
+            let rest = if param.is_rest { "..." } else { "" };
+            let optional = if param.is_optional || param.default_value.is_some() {
+                "?"
+            } else {
+                ""
+            };
+            if is_known(&param.param_type) {
+                format!("{}{}{}: {}", rest, name, optional, param.param_type)
+            } else {
+                format!("{}{}{}", rest, name, optional)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn method_signature(method: &ClassMethod) -> String {
+    format!(
+        "{}{}{}({}){}",
+        if method.is_static { "static " } else { "" },
+        if method.is_async { "async " } else { "" },
+        method.name,
+        parameters(&method.parameters),
+        return_annotation(method.return_type.as_ref())
+    )
+}
+
+fn return_annotation(return_type: Option<&Type>) -> String {
+    match return_type {
+        Some(return_type) => format!(": {}", return_type),
+        None => String::new(),
+    }
+}
+
+fn type_parameters(params: &[crate::ast::types::TypeParameter]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let params: Vec<String> = params
+        .iter()
+        .map(|param| match &param.constraint {
+            Some(constraint) => format!("{} extends {}", param.name, constraint),
+            None => param.name.clone(),
+        })
+        .collect();
+    format!("<{}>", params.join(", "))
+}
+
+fn literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::IntLiteral(lit) => Some(lit.value.to_string()),
+        Expr::FloatLiteral(lit) => Some(lit.value.to_string()),
+        Expr::StrLiteral(lit) => Some(format!("{:?}", lit.value)),
+        _ => None,
+    }
+}
+
+impl ModuleDoc {
+    pub fn render(&self, format: DocFormat) -> String {
+        match format {
+            DocFormat::Markdown => self.to_markdown(),
+            DocFormat::Html => self.to_html(),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut output = format!("# {}\n\n", self.name);
+        if let Some(doc) = &self.doc {
+            output.push_str(&format!("{}\n\n", doc));
+        }
+
+        for item in &self.items {
+            output.push_str(&format!("## {} `{}`\n\n", item.kind, item.name));
+            output.push_str(&format!("```rcc\n{}\n```\n\n", item.signature));
+            if let Some(doc) = &item.doc {
+                output.push_str(&format!("{}\n\n", doc));
+            }
+            for member in &item.members {
+                output.push_str(&format!("- `{}`", member.signature));
+                if let Some(doc) = &member.doc {
+                    output.push_str(&format!(" — {}", doc.replace('\n', " ")));
+                }
+                output.push('\n');
+            }
+            if !item.members.is_empty() {
+                output.push('\n');
+            }
+        }
+        output
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut output = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+             </head>\n<body>\n<h1>{}</h1>\n",
+            escape(&self.name),
+            escape(&self.name)
+        );
+        if let Some(doc) = &self.doc {
+            output.push_str(&paragraphs(doc));
+        }
+
+        for item in &self.items {
+            output.push_str(&format!(
+                "<section id=\"{}\">\n<h2>{} <code>{}</code></h2>\n<pre><code>{}</code></pre>\n",
+                escape(&item.name),
+                item.kind,
+                escape(&item.name),
+                escape(&item.signature)
+            ));
+            if let Some(doc) = &item.doc {
+                output.push_str(&paragraphs(doc));
+            }
+            if !item.members.is_empty() {
+                output.push_str("<ul>\n");
+                for member in &item.members {
+                    output.push_str(&format!("<li><code>{}</code>", escape(&member.signature)));
+                    if let Some(doc) = &member.doc {
+                        output.push_str(&format!(" — {}", escape(doc)));
+                    }
+                    output.push_str("</li>\n");
+                }
+                output.push_str("</ul>\n");
+            }
+            output.push_str("</section>\n");
+        }
+
+        output.push_str("</body>\n</html>\n");
+        output
+    }
+}
+
+/// Index page linking every documented module to `<name>.<extension>`.
+pub fn render_index(modules: &[ModuleDoc], format: DocFormat) -> String {
+    let extension = format.extension();
+    match format {
+        DocFormat::Markdown => {
+            let mut output = "# API documentation\n\n".to_string();
+            for module in modules {
+                let summary = module.doc.as_deref().and_then(|doc| doc.lines().next());
+                output.push_str(&format!(
+                    "- [{}]({}.{}){}\n",
+                    module.name,
+                    file_stem(&module.name),
+                    extension,
+                    summary.map(|s| format!(" — {}", s)).unwrap_or_default()
+                ));
+            }
+            output
+        }
+        DocFormat::Html => {
+            let mut output = "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+                              <title>API documentation</title>\n</head>\n<body>\n\
+                              <h1>API documentation</h1>\n<ul>\n"
+                .to_string();
+            for module in modules {
+                output.push_str(&format!(
+                    "<li><a href=\"{}.{}\">{}</a></li>\n",
+                    escape(&file_stem(&module.name)),
+                    extension,
+                    escape(&module.name)
+                ));
+            }
+            output.push_str("</ul>\n</body>\n</html>\n");
+            output
+        }
+    }
+}
+
+/// File name used for a module's page: `std:math` is written as `std-math`.
+pub fn file_stem(module_name: &str) -> String {
+    module_name.replace([':', '/', '\\'], "-")
+}
+
+fn paragraphs(text: &str) -> String {
+    text.split("\n\n")
+        .map(|paragraph| format!("<p>{}</p>\n", escape(paragraph)))
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,4 +1,5 @@
-use crate::runtime::CallStack;
+use crate::runtime::natives::terminal;
+use crate::runtime::{CallStack, RuntimeValue};
 use crate::tokens::{Position, Range};
 use std::fmt;
 use std::fs;
@@ -66,6 +67,10 @@ pub enum ErrorKind {
     TimeoutError,
 
     NetworkError,
+
+    CancellationError,
+
+    AssertionError,
 }
 
 impl ErrorKind {
@@ -102,6 +107,73 @@ impl ErrorKind {
             Self::ControlFlowError => "ControlFlowError",
             Self::TimeoutError => "TimeoutError",
             Self::NetworkError => "NetworkError",
+            Self::CancellationError => "CancellationError",
+            Self::AssertionError => "AssertionError",
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::SyntaxError => "RCC0001",
+            Self::SemanticError => "RCC0002",
+            Self::TypeError => "RCC0003",
+            Self::ReferenceError => "RCC0004",
+            Self::ImportError => "RCC0005",
+            Self::RuntimeError => "RCC0006",
+            Self::NullReferenceError => "RCC0007",
+            Self::DivisionByZeroError => "RCC0008",
+            Self::IndexOutOfRangeError => "RCC0009",
+            Self::InvalidOperationError => "RCC0010",
+            Self::FileNotFoundError => "RCC0011",
+            Self::PermissionDeniedError => "RCC0012",
+            Self::IOError => "RCC0013",
+            Self::ReadError => "RCC0014",
+            Self::WriteError => "RCC0015",
+            Self::StackOverflowError => "RCC0016",
+            Self::OutOfMemoryError => "RCC0017",
+            Self::InternalError => "RCC0018",
+            Self::DeadlockError => "RCC0019",
+            Self::RaceConditionError => "RCC0020",
+            Self::SynchronizationError => "RCC0021",
+            Self::OverflowError => "RCC0022",
+            Self::UnderflowError => "RCC0023",
+            Self::PrecisionLossError => "RCC0024",
+            Self::ValidationError => "RCC0025",
+            Self::ConfigurationError => "RCC0026",
+            Self::EnvironmentVariableError => "RCC0027",
+            Self::LogicError => "RCC0028",
+            Self::ControlFlowError => "RCC0029",
+            Self::TimeoutError => "RCC0030",
+            Self::NetworkError => "RCC0031",
+            Self::CancellationError => "RCC0032",
+            Self::AssertionError => "RCC0033",
+        }
+    }
+
+    /// The builtin class of `std:core` a caught error of this kind is an instance of.
+    pub fn error_class(&self) -> &'static str {
+        match self {
+            Self::TypeError => "TypeError",
+            Self::IndexOutOfRangeError | Self::OverflowError | Self::UnderflowError => "RangeError",
+            Self::IOError
+            | Self::ReadError
+            | Self::WriteError
+            | Self::FileNotFoundError
+            | Self::PermissionDeniedError => "IOError",
+            Self::TimeoutError => "TimeoutError",
+            _ => "Error",
+        }
+    }
+
+    /// The kind a thrown instance of the builtin error class `name` reports.
+    pub fn from_error_class(name: &str) -> Option<Self> {
+        match name {
+            "Error" => Some(Self::RuntimeError),
+            "TypeError" => Some(Self::TypeError),
+            "RangeError" => Some(Self::IndexOutOfRangeError),
+            "IOError" => Some(Self::IOError),
+            "TimeoutError" => Some(Self::TimeoutError),
+            _ => None,
         }
     }
 
@@ -146,6 +218,9 @@ pub struct RaccoonError {
     pub range: Option<Range>,
     pub file: Option<String>,
     pub call_stack: Option<CallStack>,
+    pub suggestion: Option<String>,
+    /// The value a `throw` raised, which a `catch` binds unchanged.
+    pub thrown: Option<Box<RuntimeValue>>,
 }
 
 impl RaccoonError {
@@ -162,6 +237,8 @@ impl RaccoonError {
             range: None,
             file: file.map(|f| f.into()),
             call_stack: None,
+            suggestion: None,
+            thrown: None,
         }
     }
 
@@ -178,6 +255,8 @@ impl RaccoonError {
             range: None,
             file: file.map(|f| f.into()),
             call_stack: Some(call_stack),
+            suggestion: None,
+            thrown: None,
         }
     }
 
@@ -202,6 +281,8 @@ impl RaccoonError {
             range: Some(range),
             file: file.map(|f| f.into()),
             call_stack: None,
+            suggestion: None,
+            thrown: None,
         }
     }
 
@@ -245,6 +326,14 @@ impl RaccoonError {
         Self::with_kind(ErrorKind::TypeError, message, position, file)
     }
 
+    pub fn assertion_error(
+        message: impl Into<String>,
+        position: Position,
+        file: Option<impl Into<String>>,
+    ) -> Self {
+        Self::with_kind(ErrorKind::AssertionError, message, position, file)
+    }
+
     pub fn reference_error(
         message: impl Into<String>,
         position: Position,
@@ -381,9 +470,49 @@ impl RaccoonError {
         Self::with_kind(ErrorKind::ConfigurationError, message, position, file)
     }
 
+    pub fn timeout_error(
+        message: impl Into<String>,
+        position: Position,
+        file: Option<impl Into<String>>,
+    ) -> Self {
+        Self::with_kind(ErrorKind::TimeoutError, message, position, file)
+    }
+
+    pub fn cancellation_error(
+        message: impl Into<String>,
+        position: Position,
+        file: Option<impl Into<String>>,
+    ) -> Self {
+        Self::with_kind(ErrorKind::CancellationError, message, position, file)
+    }
+
+    /// Records `call_stack` unless the error already has one.
+    pub fn attach_call_stack(mut self, call_stack: &CallStack) -> Self {
+        if self.call_stack.is_none() && call_stack.depth() > 0 {
+            self.call_stack = Some(call_stack.clone());
+        }
+        self
+    }
+
+    pub fn with_thrown(mut self, value: RuntimeValue) -> Self {
+        self.thrown = Some(Box::new(value));
+        self
+    }
+
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    pub fn header(&self) -> String {
+        format!("{}[{}]", self.kind.name(), self.kind.code())
+    }
+
     fn get_code_context(&self, context_lines: usize) -> Option<Vec<(usize, String)>> {
         let file_path = self.file.as_ref()?;
-        let content = fs::read_to_string(file_path).ok()?;
+        let content = fs::read_to_string(file_path).ok().or_else(|| {
+            crate::runtime::stdlib::embedded::source_for_file(file_path).map(str::to_string)
+        })?;
         let lines: Vec<&str> = content.lines().collect();
 
         let error_line = self.position.0;
@@ -405,76 +534,146 @@ impl RaccoonError {
     }
 
     pub fn format_with_context(&self) -> String {
+        let palette = Palette::new(terminal::colors_enabled_for(terminal::STDERR));
         let mut output = String::new();
 
-        let header = self.kind.name();
         let file_name = self
             .file
             .as_ref()
             .map(|f| f.as_str())
             .unwrap_or("<unknown file>");
 
-        let line = self.position.0.to_string();
-        let column = self.position.1.to_string();
-        let msg = &self.message;
         output.push_str(&format!(
             "\n{} {} {}:{} -> {}",
-            header, file_name, line, column, msg
+            palette.error(&self.header()),
+            palette.bold(file_name),
+            self.position.0,
+            self.position.1,
+            palette.bold(&self.message)
         ));
 
         if let Some(context) = self.get_code_context(2) {
-            output.push_str("\n");
+            output.push('\n');
             let error_line = self.position.0;
-            let error_col = self.position.1;
 
             for (line_num, line_content) in context {
-                let is_error_line = line_num == error_line;
-
-                let line_num_str = format!("{:4} ", line_num);
-                if is_error_line {
-                    output.push_str(&format!("{} ", line_num_str));
-                    output.push_str("│ ");
-                } else {
-                    output.push_str(&format!("{} ", line_num_str));
-                    output.push_str("│ ");
-                }
-
-                if is_error_line {
-                    output.push_str(&line_content);
-                    output.push_str("\n");
-
-                    let padding = " ".repeat(7 + error_col.saturating_sub(1));
-                    output.push_str(&padding);
-
-                    if let Some(range) = &self.range {
-                        if range.start.0 == range.end.0 && range.start.0 == error_line {
-                            let start_col = range.start.1.saturating_sub(1);
-                            let end_col = range.end.1.saturating_sub(1);
-                            let length = end_col.saturating_sub(start_col).max(1);
-                            output.push_str(&"^".repeat(length));
-                        } else {
-                            output.push_str("^");
-                        }
-                    } else {
-                        output.push_str("^");
-                    }
-                    output.push_str("\n");
-                } else {
-                    output.push_str(&line_content);
-                    output.push_str("\n");
+                output.push_str(&palette.gutter(&format!("{:4}  │ ", line_num)));
+                output.push_str(&line_content);
+                output.push('\n');
+
+                if line_num == error_line {
+                    let (start, width) = self.caret_span(&line_content);
+                    output.push_str(&palette.gutter("      │ "));
+                    output.push_str(&" ".repeat(start));
+                    output.push_str(&palette.error(&"^".repeat(width)));
+                    output.push('\n');
                 }
             }
         }
 
+        if let Some(suggestion) = &self.suggestion {
+            output.push_str(&format!(
+                "      {} {}\n",
+                palette.help("= help:"),
+                suggestion
+            ));
+        }
+
         if let Some(ref stack) = self.call_stack {
             if stack.depth() > 0 {
                 output.push_str(&stack.format_stack_trace());
             }
         }
 
-        output.push_str("\n");
+        output.push('\n');
         output
     }
+
+    fn caret_span(&self, line: &str) -> (usize, usize) {
+        let start = self.position.1.saturating_sub(1);
+
+        if let Some(range) = &self.range {
+            if range.start.0 == range.end.0 && range.start.0 == self.position.0 {
+                let end = range.end.1.saturating_sub(1);
+                return (start, end.saturating_sub(start).max(1));
+            }
+        }
+
+        let word = line
+            .chars()
+            .skip(start)
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .count();
+        (start, word.max(1))
+    }
+}
+
+/// The candidate closest to `name` by edit distance, for "did you mean" hints.
+pub fn did_you_mean<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)))
+        .map(|(_, candidate)| format!("did you mean '{}'?", candidate))
+}
+
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+struct Palette {
+    enabled: bool,
+}
+
+impl Palette {
+    fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    fn paint(&self, text: &str, code: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn error(&self, text: &str) -> String {
+        self.paint(text, "1;31")
+    }
+
+    fn bold(&self, text: &str) -> String {
+        self.paint(text, "1")
+    }
+
+    fn gutter(&self, text: &str) -> String {
+        self.paint(text, "34")
+    }
+
+    fn help(&self, text: &str) -> String {
+        self.paint(text, "1;36")
+    }
 }
 
 impl fmt::Display for RaccoonError {
@@ -482,12 +681,18 @@ impl fmt::Display for RaccoonError {
         if self.file.is_some() {
             write!(f, "{}", self.format_with_context())
         } else {
-            let header = self.kind.name();
-            let message = &self.message;
-            let line = self.position.0.to_string();
-            let column = self.position.1.to_string();
-
-            write!(f, "{} {}:{} → {}", header, line, column, message)
+            write!(
+                f,
+                "{} {}:{} → {}",
+                self.header(),
+                self.position.0,
+                self.position.1,
+                self.message
+            )?;
+            if let Some(suggestion) = &self.suggestion {
+                write!(f, " ({})", suggestion)?;
+            }
+            Ok(())
         }
     }
 }
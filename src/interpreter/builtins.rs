@@ -1,15 +1,45 @@
-use crate::ast::types::PrimitiveType;
+use crate::ast::nodes::{AccessorKind, FnParam, VarPattern};
+use crate::ast::types::{PrimitiveType, Type};
 use crate::error::RaccoonError;
-use crate::runtime::{BoolValue, IntValue, ArrayValue, NullValue, RuntimeValue};
-use crate::tokens::Position;
+use crate::runtime::builtins::future;
+use crate::runtime::{
+    ArrayValue, BoolValue, ClassValue, FutureState, FutureValue, IntValue, MapValue, NullValue,
+    ObjectValue, Rejection, RuntimeValue, StrValue, SymbolValue, TupleValue, TypeObject,
+};
+use crate::tokens::{AccessModifier, Position};
 use async_recursion::async_recursion;
+use futures_util::future::try_join_all;
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, RwLock};
 
+use super::expressions::Expressions;
 use super::helpers::Helpers;
+use super::overloads::Overloads;
 use super::Interpreter;
 
 pub struct Builtins;
 
 impl Builtins {
+    /// Array methods that take a callback.
+    pub const FUNCTIONAL_METHODS: &'static [&'static str] = &[
+        "map",
+        "filter",
+        "reduce",
+        "forEach",
+        "find",
+        "findIndex",
+        "some",
+        "every",
+        "sort",
+        "min",
+        "max",
+    ];
+
     #[async_recursion(?Send)]
     pub async fn handle_array_functional_method(
         interpreter: &mut Interpreter,
@@ -42,7 +72,7 @@ impl Builtins {
                 let callback = &args[0];
                 let mut mapped = Vec::new();
 
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in list.snapshot().iter().enumerate() {
                     let result = Helpers::call_function(
                         interpreter,
                         callback,
@@ -76,7 +106,7 @@ impl Builtins {
                 let callback = &args[0];
                 let mut filtered = Vec::new();
 
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in list.snapshot().iter().enumerate() {
                     let result = Helpers::call_function(
                         interpreter,
                         callback,
@@ -108,7 +138,7 @@ impl Builtins {
                 }
 
                 let callback = &args[0];
-                if list.elements.is_empty() && args.len() < 2 {
+                if list.is_empty() && args.len() < 2 {
                     return Err(RaccoonError::new(
                         "reduce of empty array with no initial value".to_string(),
                         position,
@@ -119,12 +149,12 @@ impl Builtins {
                 let mut accumulator = if args.len() >= 2 {
                     args[1].clone()
                 } else {
-                    list.elements[0].clone()
+                    list.elements.read().unwrap()[0].clone()
                 };
 
                 let start_index = if args.len() >= 2 { 0 } else { 1 };
 
-                for (index, element) in list.elements.iter().enumerate().skip(start_index) {
+                for (index, element) in list.snapshot().iter().enumerate().skip(start_index) {
                     accumulator = Helpers::call_function(
                         interpreter,
                         callback,
@@ -150,7 +180,7 @@ impl Builtins {
                 }
 
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in list.snapshot().iter().enumerate() {
                     Helpers::call_function(
                         interpreter,
                         callback,
@@ -174,7 +204,7 @@ impl Builtins {
                 }
 
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in list.snapshot().iter().enumerate() {
                     let result = Helpers::call_function(
                         interpreter,
                         callback,
@@ -202,7 +232,7 @@ impl Builtins {
                 }
 
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in list.snapshot().iter().enumerate() {
                     let result = Helpers::call_function(
                         interpreter,
                         callback,
@@ -230,7 +260,7 @@ impl Builtins {
                 }
 
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in list.snapshot().iter().enumerate() {
                     let result = Helpers::call_function(
                         interpreter,
                         callback,
@@ -258,7 +288,7 @@ impl Builtins {
                 }
 
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in list.snapshot().iter().enumerate() {
                     let result = Helpers::call_function(
                         interpreter,
                         callback,
@@ -276,6 +306,33 @@ impl Builtins {
                 }
                 Ok(RuntimeValue::Bool(BoolValue::new(true)))
             }
+            "sort" => {
+                let sorted =
+                    Self::sort_values(interpreter, list.snapshot(), args.first(), position).await?;
+                Ok(RuntimeValue::Array(ArrayValue::new(
+                    sorted,
+                    list.element_type.clone(),
+                )))
+            }
+            "min" | "max" => {
+                let wanted = if method == "min" {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                };
+                let mut elements = list.snapshot().into_iter();
+                let Some(mut best) = elements.next() else {
+                    return Ok(RuntimeValue::Null(NullValue::new()));
+                };
+                for element in elements {
+                    let order =
+                        Self::order(interpreter, &element, &best, args.first(), position).await?;
+                    if order == wanted {
+                        best = element;
+                    }
+                }
+                Ok(best)
+            }
             _ => Err(RaccoonError::new(
                 format!("Method '{}' not found on list", method),
                 position,
@@ -283,4 +340,720 @@ impl Builtins {
             )),
         }
     }
+
+    async fn sort_values(
+        interpreter: &mut Interpreter,
+        mut values: Vec<RuntimeValue>,
+        compare: Option<&RuntimeValue>,
+        position: Position,
+    ) -> Result<Vec<RuntimeValue>, RaccoonError> {
+        // Bottom-up merge sort, as each comparison may call back into the program.
+        let mut width = 1;
+        while width < values.len() {
+            let mut merged = Vec::with_capacity(values.len());
+            for run in values.chunks(width * 2) {
+                let (mut left, mut right) = run.split_at(width.min(run.len()));
+                while let (Some(a), Some(b)) = (left.first(), right.first()) {
+                    if Self::order(interpreter, b, a, compare, position).await? == Ordering::Less {
+                        merged.push(b.clone());
+                        right = &right[1..];
+                    } else {
+                        merged.push(a.clone());
+                        left = &left[1..];
+                    }
+                }
+                merged.extend_from_slice(left);
+                merged.extend_from_slice(right);
+            }
+            values = merged;
+            width *= 2;
+        }
+        Ok(values)
+    }
+
+    async fn order(
+        interpreter: &mut Interpreter,
+        a: &RuntimeValue,
+        b: &RuntimeValue,
+        compare: Option<&RuntimeValue>,
+        position: Position,
+    ) -> Result<Ordering, RaccoonError> {
+        let order = match compare {
+            Some(compare) => {
+                let args = vec![a.clone(), b.clone()];
+                match Helpers::call_function(interpreter, compare, args, position).await? {
+                    RuntimeValue::Int(order) => Some(order.value.cmp(&0)),
+                    _ => None,
+                }
+            }
+            None => Overloads::compare(interpreter, a, b, position).await?,
+        };
+        order.ok_or_else(|| {
+            RaccoonError::new(
+                format!("Cannot order {} and {}", a.get_name(), b.get_name()),
+                position,
+                interpreter.file.clone(),
+            )
+        })
+    }
+
+    /// `Future.allLimit(tasks, limit)`: runs at most `limit` tasks at once.
+    pub fn future_all_limit(
+        interpreter: &Interpreter,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> RuntimeValue {
+        let (tasks, limit) = match args.as_slice() {
+            [RuntimeValue::Array(tasks), RuntimeValue::Int(limit)] if limit.value > 0 => {
+                (tasks.snapshot(), limit.value as usize)
+            }
+            _ => {
+                return RuntimeValue::Future(FutureValue::new_rejected(
+                    "Future.allLimit requires an array and a positive limit".to_string(),
+                    PrimitiveType::any(),
+                ))
+            }
+        };
+
+        let results = Rc::new(RefCell::new(vec![
+            RuntimeValue::Null(NullValue::new());
+            tasks.len()
+        ]));
+        let workers = limit.min(tasks.len());
+        let queue = Rc::new(RefCell::new(tasks.into_iter().enumerate()));
+
+        let workers: Vec<_> = (0..workers)
+            .map(|_| {
+                let mut worker = interpreter.fork(interpreter.environment.clone());
+                let queue = Rc::clone(&queue);
+                let results = Rc::clone(&results);
+                async move {
+                    loop {
+                        let Some((index, task)) = queue.borrow_mut().next() else {
+                            return Ok::<(), Rejection>(());
+                        };
+                        let value = match task {
+                            RuntimeValue::Function(_)
+                            | RuntimeValue::NativeFunction(_)
+                            | RuntimeValue::NativeAsyncFunction(_) => {
+                                Helpers::call_function(&mut worker, &task, Vec::new(), position)
+                                    .await
+                                    .map_err(Rejection::from)?
+                            }
+                            value => value,
+                        };
+                        let value = future::settle(&value).await?;
+                        results.borrow_mut()[index] = value;
+                    }
+                }
+            })
+            .collect();
+
+        future::spawn(async move {
+            try_join_all(workers).await?;
+            let values = results.take();
+            Ok(RuntimeValue::Array(ArrayValue::new(
+                values,
+                PrimitiveType::any(),
+            )))
+        })
+    }
+
+    /// `then`, `catch` and `finally` on a future.
+    pub async fn chain_future(
+        interpreter: &mut Interpreter,
+        future: &FutureValue,
+        method: &str,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let Some(callback) = args.first().cloned() else {
+            return Err(RaccoonError::new(
+                format!("Future.{}() requires a callback", method),
+                position,
+                interpreter.file.clone(),
+            ));
+        };
+        for callback in &args {
+            if !matches!(
+                callback,
+                RuntimeValue::Function(_) | RuntimeValue::NativeFunction(_)
+            ) {
+                return Err(RaccoonError::new(
+                    format!("Future.{}() callback must be a function", method),
+                    position,
+                    interpreter.file.clone(),
+                ));
+            }
+        }
+
+        let continuation = match method {
+            "then" => Continuation::Then(callback, args.get(1).cloned()),
+            "catch" => Continuation::Catch(callback),
+            _ => Continuation::Finally(callback),
+        };
+        let chained = FutureValue::new(PrimitiveType::any());
+
+        let state = future.state.read().unwrap().clone();
+        let outcome = match state {
+            FutureState::Resolved(value) => Ok(*value),
+            FutureState::Rejected(error) => Err(error),
+            FutureState::Pending => {
+                let mut forked = interpreter.fork(interpreter.environment.clone());
+                let source = future.clone();
+                let target = chained.clone();
+                tokio::task::spawn_local(async move {
+                    let outcome = source.wait_for_completion().await;
+                    let result = continuation.run(&mut forked, outcome, position).await;
+                    Self::settle_chained(&target, result);
+                });
+                return Ok(RuntimeValue::Future(chained));
+            }
+        };
+
+        let result = continuation.run(interpreter, outcome, position).await;
+        Self::settle_chained(&chained, result);
+        Ok(RuntimeValue::Future(chained))
+    }
+
+    /// `Object.clone(value, {deep})`, or the instance's `__clone__` when it defines one.
+    pub async fn object_clone(
+        interpreter: &mut Interpreter,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let deep = match args.get(1) {
+            Some(RuntimeValue::Object(options)) => options
+                .properties
+                .read()
+                .unwrap()
+                .get("deep")
+                .is_some_and(|deep| interpreter.is_truthy(deep)),
+            _ => false,
+        };
+        let Some(value) = args.into_iter().next() else {
+            return Ok(RuntimeValue::Null(NullValue::new()));
+        };
+
+        let mut copies = HashMap::new();
+        Self::clone_value(interpreter, &value, deep, &mut copies, position).await
+    }
+
+    #[async_recursion(?Send)]
+    async fn clone_value(
+        interpreter: &mut Interpreter,
+        value: &RuntimeValue,
+        deep: bool,
+        copies: &mut HashMap<usize, RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let key = match value {
+            RuntimeValue::Array(list) => Arc::as_ptr(&list.elements) as usize,
+            RuntimeValue::Map(map) => Arc::as_ptr(&map.entries) as usize,
+            RuntimeValue::Object(object) => Arc::as_ptr(&object.properties) as usize,
+            RuntimeValue::ClassInstance(instance) => Arc::as_ptr(&instance.properties) as usize,
+            _ => 0,
+        };
+        if let Some(copy) = copies.get(&key) {
+            return Ok(copy.clone());
+        }
+
+        match value {
+            RuntimeValue::Array(list) => {
+                let copy = ArrayValue::new(Vec::new(), list.element_type.clone());
+                copies.insert(key, RuntimeValue::Array(copy.clone()));
+                for element in list.snapshot() {
+                    let element =
+                        Self::clone_child(interpreter, &element, deep, copies, position).await?;
+                    copy.elements.write().unwrap().push(element);
+                }
+                Ok(RuntimeValue::Array(copy))
+            }
+            RuntimeValue::Tuple(tuple) => {
+                let mut elements = Vec::with_capacity(tuple.elements.len());
+                for element in &tuple.elements {
+                    elements.push(
+                        Self::clone_child(interpreter, element, deep, copies, position).await?,
+                    );
+                }
+                Ok(RuntimeValue::Tuple(TupleValue::new(elements)))
+            }
+            RuntimeValue::Map(map) => {
+                let copy = MapValue::new(
+                    IndexMap::new(),
+                    map.key_type.clone(),
+                    map.value_type.clone(),
+                );
+                copies.insert(key, RuntimeValue::Map(Arc::new(copy.clone())));
+                let entries = map.entries.read().unwrap().clone();
+                for (entry_key, entry) in entries {
+                    let entry =
+                        Self::clone_child(interpreter, &entry, deep, copies, position).await?;
+                    copy.entries.write().unwrap().insert(entry_key, entry);
+                }
+                Ok(RuntimeValue::Map(Arc::new(copy)))
+            }
+            RuntimeValue::Object(object) => {
+                let copy = ObjectValue::new(Vec::new(), object.obj_type.clone());
+                copies.insert(key, RuntimeValue::Object(copy.clone()));
+                for (name, property) in object.snapshot() {
+                    let property =
+                        Self::clone_child(interpreter, &property, deep, copies, position).await?;
+                    copy.properties.write().unwrap().insert(name, property);
+                }
+                Ok(RuntimeValue::Object(copy))
+            }
+            RuntimeValue::ClassInstance(instance) => {
+                if let Some(copy) =
+                    Overloads::call(interpreter, instance, "__clone__", Vec::new(), position)
+                        .await?
+                {
+                    return Ok(copy);
+                }
+
+                let mut copy = (**instance).clone();
+                copy.properties = Arc::new(RwLock::new(HashMap::new()));
+                copy.frozen = Arc::new(AtomicBool::new(false));
+                copy.constructed = Arc::new(AtomicBool::new(true));
+                let copy = Arc::new(copy);
+                copies.insert(key, RuntimeValue::ClassInstance(copy.clone()));
+
+                let properties = instance.properties.read().unwrap().clone();
+                for (name, property) in properties {
+                    let property =
+                        Self::clone_child(interpreter, &property, deep, copies, position).await?;
+                    copy.properties.write().unwrap().insert(name, property);
+                }
+                Ok(RuntimeValue::ClassInstance(copy))
+            }
+            other => Ok(other.clone()),
+        }
+    }
+
+    async fn clone_child(
+        interpreter: &mut Interpreter,
+        child: &RuntimeValue,
+        deep: bool,
+        copies: &mut HashMap<usize, RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        if deep {
+            Self::clone_value(interpreter, child, deep, copies, position).await
+        } else {
+            Ok(child.clone())
+        }
+    }
+
+    /// `reflect.typeOf`, `reflect.members`, `reflect.signature` and `reflect.construct`.
+    pub async fn reflect(
+        interpreter: &mut Interpreter,
+        method: &str,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let target = args
+            .first()
+            .cloned()
+            .unwrap_or(RuntimeValue::Null(NullValue::new()));
+
+        match method {
+            "typeOf" => Ok(RuntimeValue::Type(Self::reflect_type_of(
+                interpreter,
+                &target,
+                position,
+            ))),
+            "members" => Self::reflect_members(interpreter, &target, position),
+            "signature" => Self::reflect_signature(interpreter, &target, position).await,
+            "construct" => {
+                let Some(class) = Self::class_of(interpreter, &target, position) else {
+                    return Err(RaccoonError::new(
+                        format!(
+                            "reflect.construct() expects a class, got {}",
+                            target.get_name()
+                        ),
+                        position,
+                        interpreter.file.clone(),
+                    ));
+                };
+                let arguments = match args.get(1) {
+                    Some(RuntimeValue::Array(list)) => list.snapshot(),
+                    _ => Vec::new(),
+                };
+                Expressions::instantiate(interpreter, &class, arguments, HashMap::new(), position)
+                    .await
+            }
+            _ => Err(RaccoonError::new(
+                format!("reflect has no function '{}'", method),
+                position,
+                interpreter.file.clone(),
+            )),
+        }
+    }
+
+    fn class_of(
+        interpreter: &Interpreter,
+        value: &RuntimeValue,
+        position: Position,
+    ) -> Option<Arc<ClassValue>> {
+        match value {
+            RuntimeValue::Class(class) => Some(class.clone()),
+            RuntimeValue::Type(type_obj) => match type_obj.get_constructor() {
+                Some(RuntimeValue::Class(class)) => Some(class.clone()),
+                _ => None,
+            },
+            RuntimeValue::ClassInstance(instance) => {
+                let class = interpreter
+                    .environment
+                    .get(&instance.class_name, position)
+                    .ok()?;
+                Self::class_of(interpreter, &class, position)
+            }
+            _ => None,
+        }
+    }
+
+    fn reflect_type_of(
+        interpreter: &Interpreter,
+        value: &RuntimeValue,
+        position: Position,
+    ) -> Arc<TypeObject> {
+        if let RuntimeValue::ClassInstance(instance) = value {
+            if let Ok(RuntimeValue::Type(type_obj)) =
+                interpreter.environment.get(&instance.class_name, position)
+            {
+                if type_obj.is_class() {
+                    return type_obj;
+                }
+            }
+        }
+        Arc::new(value.get_type_object())
+    }
+
+    fn reflect_members(
+        interpreter: &Interpreter,
+        target: &RuntimeValue,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let text = |value: String| RuntimeValue::Str(StrValue::new(value));
+        let mut members = Vec::new();
+
+        if let RuntimeValue::Object(object) = target {
+            for (name, value) in object.snapshot() {
+                if SymbolValue::is_property_key(&name) {
+                    continue;
+                }
+                members.push(Self::record(vec![
+                    ("name", text(name)),
+                    ("kind", text("property".to_string())),
+                    ("declaredBy", RuntimeValue::Null(NullValue::new())),
+                    ("access", text("public".to_string())),
+                    ("static", RuntimeValue::Bool(BoolValue::new(false))),
+                    ("type", text(value.get_type().to_string())),
+                ]));
+            }
+            return Ok(RuntimeValue::Array(ArrayValue::new(
+                members,
+                PrimitiveType::any(),
+            )));
+        }
+
+        let Some(class) = Self::class_of(interpreter, target, position) else {
+            return Err(RaccoonError::new(
+                format!(
+                    "reflect.members() expects a class, an instance or an object, got {}",
+                    target.get_name()
+                ),
+                position,
+                interpreter.file.clone(),
+            ));
+        };
+
+        let mut seen = HashSet::new();
+        for declaring in Expressions::class_lineage(interpreter, &class, position) {
+            let declaration = &declaring.declaration;
+            let mut declared = Vec::new();
+            for property in &declaration.properties {
+                declared.push((
+                    property.name.clone(),
+                    "property",
+                    &property.access_modifier,
+                    property.is_static,
+                    property.property_type.to_string(),
+                ));
+            }
+            for method in &declaration.methods {
+                declared.push((
+                    method.name.clone(),
+                    "method",
+                    &method.access_modifier,
+                    method.is_static,
+                    Self::describe_callable(&method.parameters, method.return_type.as_ref()),
+                ));
+            }
+            for accessor in &declaration.accessors {
+                let (kind, member_type) = match accessor.kind {
+                    AccessorKind::Get => (
+                        "getter",
+                        accessor
+                            .return_type
+                            .as_ref()
+                            .map_or("unknown".to_string(), Type::to_string),
+                    ),
+                    AccessorKind::Set => (
+                        "setter",
+                        accessor
+                            .parameters
+                            .first()
+                            .map_or("unknown".to_string(), |param| param.param_type.to_string()),
+                    ),
+                };
+                declared.push((
+                    accessor.name.clone(),
+                    kind,
+                    &accessor.access_modifier,
+                    false,
+                    member_type,
+                ));
+            }
+
+            for (name, kind, access, is_static, member_type) in declared {
+                if !seen.insert((name.clone(), kind, is_static)) {
+                    continue;
+                }
+                let access = match access {
+                    AccessModifier::Public => "public",
+                    AccessModifier::Private => "private",
+                    AccessModifier::Protected => "protected",
+                };
+                members.push(Self::record(vec![
+                    ("name", text(name)),
+                    ("kind", text(kind.to_string())),
+                    ("declaredBy", text(declaring.class_name.clone())),
+                    ("access", text(access.to_string())),
+                    ("static", RuntimeValue::Bool(BoolValue::new(is_static))),
+                    ("type", text(member_type)),
+                ]));
+            }
+        }
+
+        Ok(RuntimeValue::Array(ArrayValue::new(
+            members,
+            PrimitiveType::any(),
+        )))
+    }
+
+    async fn reflect_signature(
+        interpreter: &mut Interpreter,
+        target: &RuntimeValue,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let text = |value: String| RuntimeValue::Str(StrValue::new(value));
+        let flag = |value: bool| RuntimeValue::Bool(BoolValue::new(value));
+        let null = || RuntimeValue::Null(NullValue::new());
+
+        let (name, parameters, return_type, is_async) = match target {
+            RuntimeValue::Function(function) => {
+                let return_type = match &function.fn_type {
+                    Type::Function(fn_type) => fn_type.return_type.to_string(),
+                    _ => "unknown".to_string(),
+                };
+                (
+                    function.name.clone(),
+                    function.parameters.clone(),
+                    return_type,
+                    function.is_async,
+                )
+            }
+            RuntimeValue::NativeFunction(native) => {
+                return Ok(Self::native_signature(&native.fn_type, false));
+            }
+            RuntimeValue::NativeAsyncFunction(native) => {
+                return Ok(Self::native_signature(&native.fn_type, true));
+            }
+            other => match Self::class_of(interpreter, other, position) {
+                Some(class) => {
+                    let parameters = Expressions::class_lineage(interpreter, &class, position)
+                        .iter()
+                        .find_map(|declaring| declaring.declaration.constructor.as_ref())
+                        .map(|constructor| constructor.parameters.clone())
+                        .unwrap_or_default();
+                    (
+                        Some(class.class_name.clone()),
+                        parameters,
+                        class.class_name.clone(),
+                        false,
+                    )
+                }
+                None => {
+                    return Err(RaccoonError::new(
+                        format!(
+                            "reflect.signature() expects a function or class, got {}",
+                            other.get_name()
+                        ),
+                        position,
+                        interpreter.file.clone(),
+                    ))
+                }
+            },
+        };
+
+        let mut params = Vec::with_capacity(parameters.len());
+        for (index, param) in parameters.iter().enumerate() {
+            let default = match &param.default_value {
+                Some(default) => interpreter.evaluate_expr(default).await.unwrap_or(null()),
+                None => null(),
+            };
+            params.push(Self::record(vec![
+                ("name", text(Self::parameter_name(param, index))),
+                ("type", text(param.param_type.to_string())),
+                (
+                    "optional",
+                    flag(param.is_optional || param.default_value.is_some()),
+                ),
+                ("rest", flag(param.is_rest)),
+                ("default", default),
+            ]));
+        }
+
+        Ok(Self::record(vec![
+            ("name", name.map_or(null(), text)),
+            (
+                "params",
+                RuntimeValue::Array(ArrayValue::new(params, PrimitiveType::any())),
+            ),
+            ("returnType", text(return_type)),
+            ("async", flag(is_async)),
+        ]))
+    }
+
+    fn native_signature(fn_type: &Type, is_async: bool) -> RuntimeValue {
+        let Type::Function(fn_type) = fn_type else {
+            return RuntimeValue::Null(NullValue::new());
+        };
+        let last = fn_type.params.len().saturating_sub(1);
+        let params = fn_type
+            .params
+            .iter()
+            .enumerate()
+            .map(|(index, param_type)| {
+                let name = fn_type
+                    .param_names
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_else(|| format!("arg{}", index));
+                Self::record(vec![
+                    ("name", RuntimeValue::Str(StrValue::new(name))),
+                    (
+                        "type",
+                        RuntimeValue::Str(StrValue::new(param_type.to_string())),
+                    ),
+                    ("optional", RuntimeValue::Bool(BoolValue::new(false))),
+                    (
+                        "rest",
+                        RuntimeValue::Bool(BoolValue::new(fn_type.is_variadic && index == last)),
+                    ),
+                    ("default", RuntimeValue::Null(NullValue::new())),
+                ])
+            })
+            .collect();
+
+        Self::record(vec![
+            ("name", RuntimeValue::Null(NullValue::new())),
+            (
+                "params",
+                RuntimeValue::Array(ArrayValue::new(params, PrimitiveType::any())),
+            ),
+            (
+                "returnType",
+                RuntimeValue::Str(StrValue::new(fn_type.return_type.to_string())),
+            ),
+            ("async", RuntimeValue::Bool(BoolValue::new(is_async))),
+        ])
+    }
+
+    fn parameter_name(param: &FnParam, index: usize) -> String {
+        match &param.pattern {
+            VarPattern::Identifier(name) => name.clone(),
+            VarPattern::Destructuring(_) => format!("__param_{}", index),
+        }
+    }
+
+    fn describe_callable(parameters: &[FnParam], return_type: Option<&Type>) -> String {
+        let params: Vec<String> = parameters
+            .iter()
+            .enumerate()
+            .map(|(index, param)| {
+                let rest = if param.is_rest { "..." } else { "" };
+                format!(
+                    "{}{}: {}",
+                    rest,
+                    Self::parameter_name(param, index),
+                    param.param_type
+                )
+            })
+            .collect();
+        let return_type = return_type.map_or("unknown".to_string(), Type::to_string);
+        format!("({}) => {}", params.join(", "), return_type)
+    }
+
+    fn record(fields: Vec<(&str, RuntimeValue)>) -> RuntimeValue {
+        let fields = fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value));
+        RuntimeValue::Object(ObjectValue::new(fields, PrimitiveType::any()))
+    }
+
+    fn settle_chained(target: &FutureValue, result: Result<RuntimeValue, Rejection>) {
+        match result {
+            Ok(RuntimeValue::Future(inner)) => {
+                let target = target.clone();
+                tokio::task::spawn_local(async move {
+                    match inner.wait_for_completion().await {
+                        Ok(value) => target.resolve(value),
+                        Err(error) => target.reject(error),
+                    }
+                });
+            }
+            Ok(value) => target.resolve(value),
+            Err(error) => target.reject(error),
+        }
+    }
+}
+
+enum Continuation {
+    Then(RuntimeValue, Option<RuntimeValue>),
+    Catch(RuntimeValue),
+    Finally(RuntimeValue),
+}
+
+impl Continuation {
+    async fn run(
+        self,
+        interpreter: &mut Interpreter,
+        outcome: Result<RuntimeValue, Rejection>,
+        position: Position,
+    ) -> Result<RuntimeValue, Rejection> {
+        let call = |callback: RuntimeValue, args: Vec<RuntimeValue>| async move {
+            Helpers::call_function(interpreter, &callback, args, position)
+                .await
+                .map_err(Rejection::from)
+        };
+        let reason = |error: Rejection| vec![error.value()];
+
+        match (self, outcome) {
+            (Continuation::Then(on_fulfilled, _), Ok(value)) => {
+                call(on_fulfilled, vec![value]).await
+            }
+            (Continuation::Then(_, Some(on_rejected)), Err(error))
+            | (Continuation::Catch(on_rejected), Err(error)) => {
+                call(on_rejected, reason(error)).await
+            }
+            (Continuation::Then(_, None), Err(error)) => Err(error),
+            (Continuation::Catch(_), Ok(value)) => Ok(value),
+            (Continuation::Finally(on_finally), outcome) => {
+                call(on_finally, Vec::new()).await?;
+                outcome
+            }
+        }
+    }
 }
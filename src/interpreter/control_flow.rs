@@ -1,10 +1,21 @@
 use crate::ast::nodes::*;
 use crate::error::RaccoonError;
-use crate::runtime::{NullValue, RuntimeValue, StrValue};
+use crate::tokens::Position;
+use crate::runtime::types::objects::FunctionType;
+use crate::runtime::types::operations::casting;
+use crate::runtime::{BoundCall, IntValue, MapKey, NullValue, RuntimeValue, StrValue, SymbolValue};
 use async_recursion::async_recursion;
+use std::collections::HashMap;
 
+use super::declarations::Declarations;
+use super::expressions::Expressions;
+use super::helpers::Helpers;
+use super::iteration::Iteration;
 use super::{Interpreter, InterpreterResult};
 
+/// The methods a `using` resource may be disposed of with, in the order they are looked for.
+pub const DISPOSE_METHODS: [&str; 2] = ["__dispose__", "close"];
+
 pub struct ControlFlow;
 
 impl ControlFlow {
@@ -58,7 +69,7 @@ impl ControlFlow {
         while_stmt: &WhileStmt,
     ) -> Result<InterpreterResult, RaccoonError> {
         loop {
-            let condition = interpreter.evaluate_expr(&while_stmt.condition).await?;
+            let condition = Self::evaluate_loop_expr(interpreter, &while_stmt.condition).await?;
             if !interpreter.is_truthy(&condition) {
                 break;
             }
@@ -122,7 +133,7 @@ impl ControlFlow {
 
         loop {
             if let Some(condition) = &for_stmt.condition {
-                let cond_value = interpreter.evaluate_expr(condition).await?;
+                let cond_value = Self::evaluate_loop_expr(interpreter, condition).await?;
                 if !interpreter.is_truthy(&cond_value) {
                     break;
                 }
@@ -139,7 +150,7 @@ impl ControlFlow {
             }
 
             if let Some(increment) = &for_stmt.increment {
-                interpreter.evaluate_expr(increment).await?;
+                Self::evaluate_loop_expr(interpreter, increment).await?;
             }
         }
 
@@ -150,6 +161,16 @@ impl ControlFlow {
         )))
     }
 
+    async fn evaluate_loop_expr(
+        interpreter: &mut Interpreter,
+        expr: &Expr,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        match Expressions::evaluate_int_expr(interpreter, expr) {
+            Some(result) => result,
+            None => interpreter.evaluate_expr(expr).await,
+        }
+    }
+
     #[async_recursion(?Send)]
     pub async fn execute_for_in_stmt(
         interpreter: &mut Interpreter,
@@ -157,8 +178,27 @@ impl ControlFlow {
     ) -> Result<InterpreterResult, RaccoonError> {
         let iterable = interpreter.evaluate_expr(&for_in.iterable).await?;
 
-        let elements = match iterable {
-            RuntimeValue::Array(list) => list.elements,
+        let elements: Box<dyn Iterator<Item = RuntimeValue>> = match iterable {
+            RuntimeValue::Array(list) => Box::new(list.snapshot().into_iter()),
+            RuntimeValue::Range(range) => {
+                Box::new(range.iter().map(|i| RuntimeValue::Int(IntValue::new(i))))
+            }
+            RuntimeValue::Map(map) => Box::new(
+                map.entries
+                    .read()
+                    .unwrap()
+                    .keys()
+                    .map(MapKey::to_value)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ),
+            RuntimeValue::Object(object) => Box::new(
+                object
+                    .snapshot()
+                    .into_keys()
+                    .filter(|key| !SymbolValue::is_property_key(key))
+                    .map(|key| RuntimeValue::Str(StrValue::new(key))),
+            ),
             _ => {
                 return Err(RaccoonError::new(
                     "For-in requires an iterable value".to_string(),
@@ -168,32 +208,22 @@ impl ControlFlow {
             }
         };
 
-        interpreter.environment.push_scope();
-
-        if !elements.is_empty() {
-            interpreter
-                .environment
-                .declare(for_in.variable.clone(), elements[0].clone())?;
-        }
-
         for element in elements {
-            interpreter
-                .environment
-                .assign(&for_in.variable, element, for_in.position)?;
-
-            match interpreter.execute_stmt_internal(&for_in.body).await? {
-                InterpreterResult::Value(_) => {}
+            match Self::execute_loop_iteration(
+                interpreter,
+                &for_in.pattern,
+                element,
+                &for_in.body,
+                for_in.position,
+            )
+            .await?
+            {
+                InterpreterResult::Value(_) | InterpreterResult::Continue => {}
                 InterpreterResult::Break => break,
-                InterpreterResult::Continue => continue,
-                InterpreterResult::Return(v) => {
-                    interpreter.environment.pop_scope();
-                    return Ok(InterpreterResult::Return(v));
-                }
+                InterpreterResult::Return(v) => return Ok(InterpreterResult::Return(v)),
             }
         }
 
-        interpreter.environment.pop_scope();
-
         Ok(InterpreterResult::Value(RuntimeValue::Null(
             NullValue::new(),
         )))
@@ -206,100 +236,141 @@ impl ControlFlow {
     ) -> Result<InterpreterResult, RaccoonError> {
         let iterable = interpreter.evaluate_expr(&for_of.iterable).await?;
 
-        let elements = match iterable {
-            RuntimeValue::Array(list) => list.elements,
-            RuntimeValue::Str(s) => s
-                .value
-                .chars()
-                .map(|c| RuntimeValue::Str(StrValue::new(c.to_string())))
-                .collect(),
-            _ => {
-                return Err(RaccoonError::new(
-                    "For-of requires an iterable value (array or string)".to_string(),
-                    for_of.position,
-                    interpreter.file.clone(),
-                ));
-            }
+        let Some(mut iterator) = Iteration::iterate(interpreter, iterable, for_of.position).await?
+        else {
+            return Err(RaccoonError::new(
+                "For-of requires an iterable value".to_string(),
+                for_of.position,
+                interpreter.file.clone(),
+            ));
         };
 
-        interpreter.environment.push_scope();
-
-        if !elements.is_empty() {
-            interpreter
-                .environment
-                .declare(for_of.variable.clone(), elements[0].clone())?;
-        }
-
-        for element in elements {
-            interpreter
-                .environment
-                .assign(&for_of.variable, element, for_of.position)?;
-
-            match interpreter.execute_stmt_internal(&for_of.body).await? {
-                InterpreterResult::Value(_) => {}
+        while let Some(element) = iterator.next(interpreter, for_of.position).await? {
+            match Self::execute_loop_iteration(
+                interpreter,
+                &for_of.pattern,
+                element,
+                &for_of.body,
+                for_of.position,
+            )
+            .await?
+            {
+                InterpreterResult::Value(_) | InterpreterResult::Continue => {}
                 InterpreterResult::Break => break,
-                InterpreterResult::Continue => continue,
-                InterpreterResult::Return(v) => {
-                    interpreter.environment.pop_scope();
-                    return Ok(InterpreterResult::Return(v));
-                }
+                InterpreterResult::Return(v) => return Ok(InterpreterResult::Return(v)),
             }
         }
 
-        interpreter.environment.pop_scope();
-
         Ok(InterpreterResult::Value(RuntimeValue::Null(
             NullValue::new(),
         )))
     }
 
-    #[async_recursion(?Send)]
+    async fn execute_loop_iteration(
+        interpreter: &mut Interpreter,
+        pattern: &VarPattern,
+        element: RuntimeValue,
+        body: &Stmt,
+        position: Position,
+    ) -> Result<InterpreterResult, RaccoonError> {
+        interpreter.environment.push_scope();
+        let bound = match pattern {
+            VarPattern::Identifier(name) => interpreter
+                .environment
+                .declare(name.clone(), element)
+                .map(|_| ()),
+            VarPattern::Destructuring(pattern) => {
+                Helpers::destructure_pattern(interpreter, pattern, &element, position).await
+            }
+        };
+        let result = match bound {
+            Ok(()) => interpreter.execute_stmt_internal(body).await,
+            Err(error) => Err(error),
+        };
+        interpreter.environment.pop_scope();
+        result
+    }
+
     pub async fn execute_switch_stmt(
         interpreter: &mut Interpreter,
         switch_stmt: &SwitchStmt,
     ) -> Result<InterpreterResult, RaccoonError> {
-        let discriminant_value = interpreter.evaluate_expr(&switch_stmt.discriminant).await?;
-
-        let mut matched = false;
-        let mut fall_through = false;
-
-        for case in &switch_stmt.cases {
-            if !fall_through {
-                if let Some(test_expr) = &case.test {
-                    let test_value = interpreter.evaluate_expr(test_expr).await?;
-                    matched = discriminant_value.equals(&test_value);
-                } else {
-                    matched = true;
+        Self::execute_switch_cases(
+            interpreter,
+            &switch_stmt.discriminant,
+            &switch_stmt.cases,
+            true,
+            switch_stmt.position,
+        )
+        .await
+    }
+
+    /// Runs the cases from the one `discriminant` matches.
+    #[async_recursion(?Send)]
+    pub async fn execute_switch_cases(
+        interpreter: &mut Interpreter,
+        discriminant: &Expr,
+        cases: &[SwitchCase],
+        is_statement: bool,
+        position: Position,
+    ) -> Result<InterpreterResult, RaccoonError> {
+        let discriminant_value = interpreter.evaluate_expr(discriminant).await?;
+
+        let mut start = None;
+        'cases: for (index, case) in cases.iter().enumerate() {
+            for test_expr in &case.tests {
+                let test_value = interpreter.evaluate_expr(test_expr).await?;
+                if discriminant_value.equals(&test_value) {
+                    start = Some(index);
+                    break 'cases;
                 }
             }
+        }
+
+        let Some(start) = start.or_else(|| cases.iter().position(SwitchCase::is_default)) else {
+            return Ok(InterpreterResult::Value(RuntimeValue::Null(
+                NullValue::new(),
+            )));
+        };
+
+        interpreter.environment.push_scope();
+        let result =
+            Self::execute_switch_body(interpreter, &cases[start..], is_statement, position).await;
+        interpreter.environment.pop_scope();
+        result
+    }
 
-            if matched || fall_through {
-                fall_through = true;
-
-                for stmt in &case.consequent {
-                    match interpreter.execute_stmt_internal(stmt).await? {
-                        InterpreterResult::Value(_) => {}
-                        InterpreterResult::Break => {
-                            return Ok(InterpreterResult::Value(RuntimeValue::Null(
-                                NullValue::new(),
-                            )));
-                        }
-                        InterpreterResult::Continue => {
-                            return Err(RaccoonError::new(
-                                "Continue not allowed in switch statement".to_string(),
-                                switch_stmt.position,
-                                interpreter.file.clone(),
-                            ));
-                        }
-                        InterpreterResult::Return(v) => return Ok(InterpreterResult::Return(v)),
+    #[async_recursion(?Send)]
+    async fn execute_switch_body(
+        interpreter: &mut Interpreter,
+        cases: &[SwitchCase],
+        is_statement: bool,
+        position: Position,
+    ) -> Result<InterpreterResult, RaccoonError> {
+        let mut last_value = RuntimeValue::Null(NullValue::new());
+
+        for case in cases {
+            for stmt in &case.consequent {
+                match interpreter.execute_stmt_internal(stmt).await? {
+                    InterpreterResult::Value(v) => last_value = v,
+                    InterpreterResult::Break => return Ok(InterpreterResult::Value(last_value)),
+                    InterpreterResult::Continue => {
+                        return Err(RaccoonError::new(
+                            "Continue not allowed in switch statement".to_string(),
+                            position,
+                            interpreter.file.clone(),
+                        ));
                     }
+                    InterpreterResult::Return(v) => return Ok(InterpreterResult::Return(v)),
                 }
             }
+
+            if !is_statement && !case.fallthrough {
+                break;
+            }
         }
 
-        Ok(InterpreterResult::Value(RuntimeValue::Null(
-            NullValue::new(),
-        )))
+        Ok(InterpreterResult::Value(last_value))
     }
 
     pub async fn execute_return_stmt(
@@ -320,6 +391,9 @@ impl ControlFlow {
         interpreter: &mut Interpreter,
         try_stmt: &TryStmt,
     ) -> Result<InterpreterResult, RaccoonError> {
+        let stack_depth = interpreter.call_stack.depth();
+        let scope_depth = interpreter.environment.depth();
+        let recursion_depth = interpreter.recursion_depth;
         let result = Self::execute_block_internal(interpreter, &try_stmt.try_block).await;
 
         match result {
@@ -330,16 +404,33 @@ impl ControlFlow {
                 Ok(value)
             }
             Err(error) => {
-                for catch_clause in &try_stmt.catch_clauses {
+                interpreter.call_stack.truncate(stack_depth);
+                interpreter.environment.truncate(scope_depth);
+                interpreter.recursion_depth = recursion_depth;
+
+                let error_value = Self::caught_value(interpreter, &error).await;
+                let catch_clause = try_stmt.catch_clauses.iter().find(|clause| {
+                    clause.error_type.as_ref().is_none_or(|error_type| {
+                        casting::value_is(&error_value, error_type, &interpreter.environment)
+                    })
+                });
+
+                if let Some(catch_clause) = catch_clause {
                     interpreter.environment.push_scope();
-                    let error_value = RuntimeValue::Str(StrValue::new(error.message.clone()));
                     interpreter
                         .environment
                         .declare(catch_clause.error_var.clone(), error_value)?;
 
+                    interpreter.handling.push(error);
                     let result =
                         Self::execute_block_internal(interpreter, &catch_clause.body).await;
-                    interpreter.environment.pop_scope();
+                    interpreter.handling.pop();
+
+                    // A failed catch body, rethrows included, leaves the finally block and the
+                    // caller the state the try started with.
+                    interpreter.call_stack.truncate(stack_depth);
+                    interpreter.environment.truncate(scope_depth);
+                    interpreter.recursion_depth = recursion_depth;
 
                     if let Some(finally_block) = &try_stmt.finally_block {
                         Self::execute_block_internal(interpreter, finally_block).await?;
@@ -356,4 +447,146 @@ impl ControlFlow {
             }
         }
     }
+
+    /// Runs the body of a `using`, then disposes of the resource.
+    #[async_recursion(?Send)]
+    pub async fn execute_using_stmt(
+        interpreter: &mut Interpreter,
+        using: &UsingStmt,
+    ) -> Result<InterpreterResult, RaccoonError> {
+        let resource = interpreter.evaluate_expr(&using.initializer).await?;
+        let method = match &resource {
+            RuntimeValue::Null(_) => None,
+            _ => Some(Self::disposal_method(&resource).ok_or_else(|| {
+                RaccoonError::type_error(
+                    format!(
+                        "'{}' cannot be used with 'using': {} has no __dispose__() or close() method",
+                        using.name,
+                        resource.get_name()
+                    ),
+                    using.position,
+                    interpreter.file.clone(),
+                )
+            })?),
+        };
+
+        let stack_depth = interpreter.call_stack.depth();
+        let scope_depth = interpreter.environment.depth();
+        let recursion_depth = interpreter.recursion_depth;
+        interpreter.environment.push_scope();
+        interpreter.environment.declare_constant(
+            using.name.clone(),
+            resource.clone(),
+            using.position,
+        )?;
+        let result = Self::execute_block_internal(interpreter, &using.body).await;
+        interpreter.call_stack.truncate(stack_depth);
+        interpreter.environment.truncate(scope_depth);
+        interpreter.recursion_depth = recursion_depth;
+
+        let disposed = match method {
+            Some(method) => Self::dispose(interpreter, &resource, method, using.position).await,
+            None => Ok(()),
+        };
+        let value = result?;
+        disposed?;
+        Ok(value)
+    }
+
+    /// Runs the body of a `defer`, then the deferred expression.
+    #[async_recursion(?Send)]
+    pub async fn execute_defer_stmt(
+        interpreter: &mut Interpreter,
+        defer: &DeferStmt,
+    ) -> Result<InterpreterResult, RaccoonError> {
+        let stack_depth = interpreter.call_stack.depth();
+        let scope_depth = interpreter.environment.depth();
+        let recursion_depth = interpreter.recursion_depth;
+        let result = Self::execute_block_internal(interpreter, &defer.body).await;
+        interpreter.call_stack.truncate(stack_depth);
+        interpreter.environment.truncate(scope_depth);
+        interpreter.recursion_depth = recursion_depth;
+
+        let deferred = interpreter.evaluate_expr(&defer.expression).await;
+        let value = result?;
+        deferred?;
+        Ok(value)
+    }
+
+    /// The first of `DISPOSE_METHODS` that `resource` has, as a method or a function property.
+    pub fn disposal_method(resource: &RuntimeValue) -> Option<&'static str> {
+        DISPOSE_METHODS.into_iter().find(|method| match resource {
+            RuntimeValue::ClassInstance(instance) => instance.methods.contains_key(*method),
+            RuntimeValue::Object(object) => object
+                .get(method)
+                .is_some_and(|property| FunctionType::is_function(&property)),
+            _ => false,
+        })
+    }
+
+    async fn dispose(
+        interpreter: &mut Interpreter,
+        resource: &RuntimeValue,
+        method: &str,
+        position: Position,
+    ) -> Result<(), RaccoonError> {
+        let result = match resource {
+            RuntimeValue::ClassInstance(instance) => {
+                let function = instance.methods[method].clone();
+                let owner = instance
+                    .method_owners
+                    .get(method)
+                    .cloned()
+                    .unwrap_or_else(|| instance.class_name.clone());
+                Helpers::invoke_method(interpreter, instance, &owner, function, vec![], position)
+                    .await?
+            }
+            _ => {
+                let target = match resource {
+                    RuntimeValue::Object(object) => object.get(method),
+                    _ => None,
+                };
+                let bound = BoundCall {
+                    target: target.unwrap_or(RuntimeValue::Null(NullValue::new())),
+                    this: resource.clone(),
+                    args: Vec::new(),
+                };
+                Helpers::call_bound(interpreter, &bound, Vec::new(), HashMap::new(), position)
+                    .await?
+            }
+        };
+        if let RuntimeValue::Future(future) = result {
+            future.wait_for_completion().await.map_err(|rejection| {
+                Declarations::rejected_error(
+                    &interpreter.environment,
+                    rejection,
+                    position,
+                    interpreter.file.clone(),
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// The value a `catch` binds: the thrown value or a builtin error class instance.
+    pub async fn caught_value(interpreter: &mut Interpreter, error: &RaccoonError) -> RuntimeValue {
+        if let Some(thrown) = &error.thrown {
+            return (**thrown).clone();
+        }
+        let message = RuntimeValue::Str(StrValue::new(error.message.clone()));
+        let Ok(class) =
+            Expressions::resolve_class(interpreter, error.kind.error_class(), error.position)
+        else {
+            return message;
+        };
+        Expressions::instantiate(
+            interpreter,
+            &class,
+            vec![message.clone()],
+            HashMap::new(),
+            error.position,
+        )
+        .await
+        .unwrap_or(message)
+    }
 }
@@ -1,14 +1,24 @@
 use crate::ast::nodes::*;
-use crate::ast::types::{PrimitiveType, Type};
-use crate::error::RaccoonError;
+use crate::ast::types::{
+    InterfaceProperty, InterfaceType, IntersectionType, PrimitiveType, Type, TypeReference,
+};
+use crate::error::{ErrorKind, RaccoonError};
+use crate::runtime::types::operations::casting::TypeScope;
+use crate::runtime::types::operations::overloading::Signature;
 use crate::runtime::{
-    DecoratorTarget, FunctionValue, NullValue, RuntimeValue, TypeKind, TypeObjectBuilder,
+    DecoratedCall, DecoratorTarget, FunctionValue, NullValue, Rejection, RuntimeValue, TypeKind,
+    TypeObjectBuilder,
 };
 use async_recursion::async_recursion;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use super::control_flow::ControlFlow;
+use super::expressions::Expressions;
 use super::{Interpreter, InterpreterResult};
 use crate::runtime::values::{EnumObject, EnumValueData};
+use crate::tokens::Position;
 
 pub struct Declarations;
 
@@ -25,6 +35,13 @@ impl Declarations {
         };
 
         match &decl.pattern {
+            VarPattern::Identifier(name) if decl.is_constant => {
+                interpreter.environment.declare_constant(
+                    name.clone(),
+                    value.clone(),
+                    decl.position,
+                )?;
+            }
             VarPattern::Identifier(name) => {
                 interpreter
                     .environment
@@ -38,12 +55,44 @@ impl Declarations {
                     decl.position,
                 )
                 .await?;
+
+                if decl.is_constant {
+                    for name in pattern.bound_names() {
+                        interpreter.environment.mark_constant(&name, decl.position);
+                    }
+                }
             }
         }
 
         Ok(value)
     }
 
+    /// The function a `fn` declaration binds, before its decorators wrap it.
+    pub fn function_value(decl: &FnDecl) -> FunctionValue {
+        let fn_type = Type::Function(Box::new(crate::ast::types::FunctionType {
+            params: decl
+                .parameters
+                .iter()
+                .map(|p| p.param_type.clone())
+                .collect(),
+            return_type: decl
+                .return_type
+                .clone()
+                .unwrap_or_else(|| PrimitiveType::unknown()),
+            is_variadic: decl.parameters.iter().any(|p| p.is_rest),
+            param_names: Vec::new(),
+        }));
+
+        FunctionValue::new(
+            decl.parameters.clone(),
+            decl.body.clone(),
+            decl.is_async,
+            fn_type,
+        )
+        .with_name(decl.name.clone())
+        .with_decorators(decl.decorators.clone())
+    }
+
     #[async_recursion(?Send)]
     pub async fn execute_fn_decl(
         interpreter: &mut Interpreter,
@@ -62,44 +111,75 @@ impl Declarations {
             interpreter.file.as_deref(),
         )?;
 
-        let fn_type = Type::Function(Box::new(crate::ast::types::FunctionType {
-            params: decl
-                .parameters
-                .iter()
-                .map(|p| p.param_type.clone())
-                .collect(),
-            return_type: decl
-                .return_type
-                .clone()
-                .unwrap_or_else(|| PrimitiveType::unknown()),
-            is_variadic: decl.parameters.iter().any(|p| p.is_rest),
-        }));
-
-        let function = RuntimeValue::Function(
-            FunctionValue::new(
-                decl.parameters.clone(),
-                decl.body.clone(),
-                decl.is_async,
-                fn_type.clone(),
-            )
-            .with_name(decl.name.clone())
-            .with_decorators(decl.decorators.clone()),
-        );
+        let function = Self::function_value(decl);
+        let fn_type = function.fn_type.clone();
+
+        // The decorator nearest the declaration wraps innermost.
+        let function = decorators
+            .iter()
+            .rev()
+            .filter_map(|info| info.spec.wrap.map(|wrap| wrap(info)))
+            .fold(function, |function, decorator| {
+                FunctionValue::new(
+                    decl.parameters.clone(),
+                    Vec::<Stmt>::new(),
+                    decl.is_async,
+                    fn_type.clone(),
+                )
+                .with_name(decl.name.clone())
+                .with_decorators(decl.decorators.clone())
+                .with_decorated(DecoratedCall {
+                    target: RuntimeValue::Function(Arc::new(function)),
+                    decorator,
+                })
+            });
 
-        interpreter
+        let existing = interpreter
             .environment
-            .declare(decl.name.clone(), function)?;
+            .scopes()
+            .last()
+            .and_then(|scope| scope.get(&decl.name))
+            .cloned();
+        match existing {
+            Some(RuntimeValue::Function(existing))
+                if existing.name.as_deref() == Some(decl.name.as_str()) =>
+            {
+                let signature = Signature::of(&decl.parameters);
+                if existing
+                    .declarations()
+                    .any(|other| Signature::of(&other.parameters).same_params(&signature))
+                {
+                    return Err(RaccoonError::new(
+                        format!(
+                            "Function '{}' already has an overload taking {}",
+                            decl.name, signature
+                        ),
+                        decl.position,
+                        interpreter.file.clone(),
+                    ));
+                }
+                interpreter.environment.update(
+                    &decl.name,
+                    RuntimeValue::Function(Arc::new((*existing).clone().with_overload(function))),
+                    decl.position,
+                )?;
+            }
+            _ => interpreter.environment.declare(
+                decl.name.clone(),
+                RuntimeValue::Function(Arc::new(function)),
+            )?,
+        }
 
         for decorator_info in &decorators {
             match decorator_info.spec.name.as_str() {
-                "@deprecated" => {
+                "deprecated" => {
                     let msg = decorator_info
                         .arg_as_string(0)
                         .unwrap_or_else(|| "This function is deprecated".to_string());
-                    eprintln!(
-                        "⚠️  Warning: Function '{}' is deprecated. {}",
+                    interpreter.diagnostics.warn(&format!(
+                        "Function '{}' is deprecated. {}",
                         decl.name, msg
-                    );
+                    ));
                 }
                 _ => {}
             }
@@ -113,6 +193,77 @@ impl Declarations {
         interpreter: &mut Interpreter,
         decl: &ClassDecl,
     ) -> Result<RuntimeValue, RaccoonError> {
+        let decl = &*Self::with_default_methods(interpreter, decl);
+
+        let mut static_properties = HashMap::new();
+        for property in decl.properties.iter().filter(|property| property.is_static) {
+            if let Some(initializer) = &property.initializer {
+                let value = interpreter.evaluate_expr(initializer).await?;
+                static_properties.insert(property.name.clone(), value);
+            }
+        }
+
+        interpreter.environment.declare(
+            decl.name.clone(),
+            Self::class_type_object(decl, static_properties),
+        )?;
+
+        for block in &decl.static_blocks {
+            ControlFlow::execute_block_internal(interpreter, block).await?;
+        }
+
+        Ok(RuntimeValue::Null(NullValue::new()))
+    }
+
+    pub fn with_default_methods<'a>(
+        interpreter: &Interpreter,
+        decl: &'a ClassDecl,
+    ) -> Cow<'a, ClassDecl> {
+        if decl.implements.is_empty() {
+            return Cow::Borrowed(decl);
+        }
+
+        let interfaces = InterfaceDecl::lineage(&decl.implements, |name| {
+            match interpreter.environment.get(name, decl.position) {
+                Ok(RuntimeValue::Type(type_obj)) => match type_obj.get_kind() {
+                    TypeKind::Interface { declaration, .. } => Some((**declaration).clone()),
+                    _ => None,
+                },
+                _ => None,
+            }
+        });
+        let ancestors = decl
+            .superclass
+            .as_ref()
+            .and_then(|name| Expressions::resolve_class(interpreter, name, decl.position).ok())
+            .map(|superclass| Expressions::class_lineage(interpreter, &superclass, decl.position))
+            .unwrap_or_default();
+        let defaults = InterfaceDecl::default_methods(&interfaces, |name| {
+            decl.methods
+                .iter()
+                .any(|method| method.is_instance_method(name))
+                || ancestors.iter().any(|ancestor| {
+                    ancestor
+                        .declaration
+                        .methods
+                        .iter()
+                        .any(|method| method.is_instance_method(name))
+                })
+        });
+
+        if defaults.is_empty() {
+            return Cow::Borrowed(decl);
+        }
+        let mut decl = decl.clone();
+        decl.methods.extend(defaults);
+        Cow::Owned(decl)
+    }
+
+    /// Builds the type object a class name is bound to, given its static property values.
+    pub fn class_type_object(
+        decl: &ClassDecl,
+        static_properties: HashMap<String, RuntimeValue>,
+    ) -> RuntimeValue {
         let class_type = Type::Class(Box::new(crate::ast::types::ClassType {
             name: decl.name.clone(),
             superclass: decl.superclass.as_ref().map(|_s| {
@@ -132,10 +283,7 @@ impl Declarations {
         }));
 
         let mut class_static_methods = HashMap::new();
-        let mut class_static_properties = HashMap::new();
-
         let mut type_static_methods = HashMap::new();
-        let mut type_static_properties = HashMap::new();
 
         for method in &decl.methods {
             if method.is_static {
@@ -150,14 +298,15 @@ impl Declarations {
                         .clone()
                         .unwrap_or_else(|| PrimitiveType::unknown()),
                     is_variadic: method.parameters.iter().any(|p| p.is_rest),
+                    param_names: Vec::new(),
                 }));
 
-                let function_value = RuntimeValue::Function(FunctionValue::new(
+                let function_value = RuntimeValue::Function(Arc::new(FunctionValue::new(
                     method.parameters.clone(),
                     method.body.clone(),
                     method.is_async,
                     fn_type.clone(),
-                ));
+                )));
 
                 class_static_methods.insert(
                     method.name.clone(),
@@ -173,21 +322,14 @@ impl Declarations {
             }
         }
 
-        for property in &decl.properties {
-            if let Some(initializer) = &property.initializer {
-                let value = interpreter.evaluate_expr(initializer).await?;
-                class_static_properties.insert(property.name.clone(), value.clone());
-                type_static_properties.insert(property.name.clone(), value);
-            }
-        }
-
-        let class_value = RuntimeValue::Class(crate::runtime::ClassValue::with_properties(
-            decl.name.clone(),
-            class_static_methods,
-            class_static_properties,
-            class_type.clone(),
-            decl.clone(),
-        ));
+        let class_value =
+            RuntimeValue::Class(Arc::new(crate::runtime::ClassValue::with_properties(
+                decl.name.clone(),
+                class_static_methods,
+                static_properties.clone(),
+                class_type.clone(),
+                decl.clone(),
+            )));
 
         let type_object = TypeObjectBuilder::new(
             class_type,
@@ -197,17 +339,13 @@ impl Declarations {
             },
         )
         .static_methods(type_static_methods)
-        .static_properties(type_static_properties)
+        .static_properties(static_properties)
         .constructor(class_value.clone())
         .documentation(extract_doc_from_decorators(&decl.decorators))
         .decorators(decl.decorators.iter().map(|d| d.name.clone()).collect())
         .build();
 
-        interpreter
-            .environment
-            .declare(decl.name.clone(), RuntimeValue::Type(type_object))?;
-
-        Ok(RuntimeValue::Null(NullValue::new()))
+        RuntimeValue::Type(Arc::new(type_object))
     }
 
     #[async_recursion(?Send)]
@@ -215,37 +353,62 @@ impl Declarations {
         interpreter: &mut Interpreter,
         decl: &EnumDecl,
     ) -> Result<InterpreterResult, RaccoonError> {
+        let mut initializers = Vec::new();
+        for member in &decl.members {
+            let value = match &member.value {
+                Some(init_val) => Some(interpreter.evaluate_expr(init_val).await?),
+                None => None,
+            };
+            initializers.push(value);
+        }
+
+        let enum_value = Self::enum_value(decl, initializers);
+        interpreter.environment.declare(decl.name.clone(), enum_value)?;
+
+        Ok(InterpreterResult::Value(RuntimeValue::Null(
+            NullValue::new(),
+        )))
+    }
+
+    /// The enum `decl` declares; members without an initializer count on from the last.
+    pub fn enum_value(decl: &EnumDecl, initializers: Vec<Option<RuntimeValue>>) -> RuntimeValue {
         let mut members = HashMap::new();
         let mut current_value: i64 = 0;
         let mut variant_names = Vec::new();
 
-        for member in &decl.members {
+        for (member, initializer) in decl.members.iter().zip(initializers) {
             variant_names.push(member.name.clone());
 
-            if let Some(init_val) = &member.value {
-                if let Expr::IntLiteral(int_lit) = init_val {
-                    current_value = int_lit.value;
+            match initializer {
+                Some(RuntimeValue::Int(int)) => {
+                    current_value = int.value;
+                    members.insert(member.name.clone(), EnumValueData::Int(current_value));
+                }
+                Some(RuntimeValue::Str(text)) => {
+                    members.insert(member.name.clone(), EnumValueData::Str(text.value));
+                }
+                Some(_) => {}
+                None => {
                     members.insert(member.name.clone(), EnumValueData::Int(current_value));
-                } else if let Expr::StrLiteral(str_lit) = init_val {
-                    members.insert(
-                        member.name.clone(),
-                        EnumValueData::Str(str_lit.value.clone()),
-                    );
                 }
-                current_value += 1;
-            } else {
-                members.insert(member.name.clone(), EnumValueData::Int(current_value));
-                current_value += 1;
             }
+            current_value += 1;
         }
 
+        Self::enum_type_object(&decl.name, variant_names, members)
+    }
+
+    /// Builds the type object an enum name is bound to, with one static property per member.
+    pub fn enum_type_object(
+        name: &str,
+        variants: Vec<String>,
+        members: HashMap<String, EnumValueData>,
+    ) -> RuntimeValue {
         let enum_type = Type::Enum(Box::new(crate::ast::types::EnumType {
-            name: decl.name.clone(),
+            name: name.to_string(),
             members: HashMap::new(),
         }));
 
-        let enum_obj = EnumObject::new(decl.name.clone(), members.clone(), enum_type.clone());
-
         let mut static_properties = HashMap::new();
         for (member_name, member_data) in &members {
             let member_value = match member_data {
@@ -257,38 +420,214 @@ impl Declarations {
             static_properties.insert(member_name.clone(), member_value);
         }
 
+        let enum_obj = EnumObject::new(name.to_string(), members, enum_type.clone());
+
         let type_object = TypeObjectBuilder::new(
             enum_type,
             TypeKind::Enum {
-                name: decl.name.clone(),
-                variants: variant_names,
+                name: name.to_string(),
+                variants,
             },
         )
         .static_properties(static_properties)
-        .constructor(RuntimeValue::EnumObject(enum_obj))
-        .documentation(format!("Enum {}", decl.name))
+        .constructor(RuntimeValue::EnumObject(Arc::new(enum_obj)))
+        .documentation(format!("Enum {}", name))
         .build();
 
+        RuntimeValue::Type(Arc::new(type_object))
+    }
+
+    /// Binds an alias name to a type object, so `as` and `is` can see through it.
+    pub fn execute_type_alias_decl(
+        interpreter: &mut Interpreter,
+        decl: &TypeAliasDecl,
+    ) -> Result<InterpreterResult, RaccoonError> {
         interpreter
             .environment
-            .declare(decl.name.clone(), RuntimeValue::Type(type_object))?;
+            .declare(decl.name.clone(), Self::alias_type_object(decl))?;
 
         Ok(InterpreterResult::Value(RuntimeValue::Null(
             NullValue::new(),
         )))
     }
 
+    pub fn execute_interface_decl(
+        interpreter: &mut Interpreter,
+        decl: &InterfaceDecl,
+    ) -> Result<InterpreterResult, RaccoonError> {
+        interpreter
+            .environment
+            .declare(decl.name.clone(), Self::interface_type_object(decl))?;
+
+        Ok(InterpreterResult::Value(RuntimeValue::Null(
+            NullValue::new(),
+        )))
+    }
+
+    /// The type object an interface name is bound to.
+    pub fn interface_type_object(decl: &InterfaceDecl) -> RuntimeValue {
+        let properties = decl
+            .properties
+            .iter()
+            .map(|prop| {
+                (
+                    prop.name.clone(),
+                    InterfaceProperty {
+                        property_type: prop.property_type.clone(),
+                        optional: prop.optional,
+                    },
+                )
+            })
+            .collect();
+        let shape = Type::Interface(Box::new(InterfaceType {
+            name: decl.name.clone(),
+            properties,
+            type_parameters: decl.type_parameters.clone(),
+        }));
+        let type_def = if decl.extends.is_empty() {
+            shape
+        } else {
+            let parents = decl.extends.iter().map(|parent| {
+                Type::TypeRef(TypeReference {
+                    name: parent.clone(),
+                    file: None,
+                })
+            });
+            Type::Intersection(Box::new(IntersectionType::new(
+                std::iter::once(shape).chain(parents).collect(),
+            )))
+        };
+
+        let type_object = TypeObjectBuilder::new(
+            type_def,
+            TypeKind::Interface {
+                name: decl.name.clone(),
+                declaration: Box::new(decl.clone()),
+            },
+        )
+        .documentation(
+            decl.doc
+                .clone()
+                .unwrap_or_else(|| format!("Interface {}", decl.name)),
+        )
+        .build();
+
+        RuntimeValue::Type(Arc::new(type_object))
+    }
+
+    /// Builds the type object an alias name is bound to.
+    pub fn alias_type_object(decl: &TypeAliasDecl) -> RuntimeValue {
+        let type_object = TypeObjectBuilder::new(
+            decl.alias_type.clone(),
+            TypeKind::Alias {
+                name: decl.name.clone(),
+                target: Box::new(decl.alias_type.clone()),
+            },
+        )
+        .documentation(
+            decl.doc
+                .clone()
+                .unwrap_or_else(|| format!("Type alias {}", decl.name)),
+        )
+        .build();
+
+        RuntimeValue::Type(Arc::new(type_object))
+    }
+
     pub async fn execute_throw_stmt(
         interpreter: &mut Interpreter,
         throw: &ThrowStmt,
     ) -> Result<InterpreterResult, RaccoonError> {
-        let value = interpreter.evaluate_expr(&throw.value).await?;
-        Err(RaccoonError::new(
-            value.to_string(),
+        let Some(value) = &throw.value else {
+            return Err(interpreter.handling.last().cloned().unwrap_or_else(|| {
+                RaccoonError::new(
+                    "A bare 'throw' can only rethrow inside a catch block",
+                    throw.position,
+                    interpreter.file.clone(),
+                )
+            }));
+        };
+        let value = interpreter.evaluate_expr(value).await?;
+        Err(Self::thrown_error(
+            &interpreter.environment,
+            value,
             throw.position,
             interpreter.file.clone(),
         ))
     }
+
+    /// The error a `throw` of `value` raises.
+    pub fn thrown_error(
+        scope: &impl TypeScope,
+        value: RuntimeValue,
+        position: Position,
+        file: Option<String>,
+    ) -> RaccoonError {
+        let (kind, message) = match &value {
+            RuntimeValue::ClassInstance(instance) if instance.is_error => {
+                let message = match instance.properties.read().unwrap().get("message") {
+                    Some(message) => message.to_string(),
+                    None => String::new(),
+                };
+                (Self::error_kind(scope, &instance.class_name), message)
+            }
+            value => (ErrorKind::RuntimeError, value.to_string()),
+        };
+        RaccoonError::with_kind(kind, message, position, file).with_thrown(value)
+    }
+
+    /// The error an `await` raises for a future that rejected with `rejection`.
+    pub fn rejected_error(
+        scope: &impl TypeScope,
+        rejection: Rejection,
+        position: Position,
+        file: Option<String>,
+    ) -> RaccoonError {
+        match rejection {
+            Rejection::Thrown(value) => Self::thrown_error(scope, *value, position, file),
+            rejection => rejection.into_error(position, file),
+        }
+    }
+
+    fn error_kind(scope: &impl TypeScope, class_name: &str) -> ErrorKind {
+        let mut current = Some(class_name.to_string());
+        let mut visited = Vec::new();
+        while let Some(name) = current {
+            if let Some(kind) = ErrorKind::from_error_class(&name) {
+                return kind;
+            }
+            if visited.contains(&name) {
+                break;
+            }
+            current = scope.superclass_of(&name);
+            visited.push(name);
+        }
+        ErrorKind::RuntimeError
+    }
+
+    #[async_recursion(?Send)]
+    pub async fn execute_assert_stmt(
+        interpreter: &mut Interpreter,
+        assert: &AssertStmt,
+    ) -> Result<InterpreterResult, RaccoonError> {
+        let condition = interpreter.evaluate_expr(&assert.condition).await?;
+        if interpreter.is_truthy(&condition) {
+            return Ok(InterpreterResult::Value(RuntimeValue::Null(
+                NullValue::new(),
+            )));
+        }
+        let message = match &assert.message {
+            Some(message) => Some(interpreter.evaluate_expr(message).await?.to_string()),
+            None => None,
+        };
+        Err(RaccoonError::assertion_error(
+            assert
+                .kind
+                .failure_message(&assert.source, message.as_deref()),
+            assert.position,
+            interpreter.file.clone(),
+        ))
+    }
 }
 
 fn extract_doc_from_decorators(decorators: &[DecoratorDecl]) -> String {
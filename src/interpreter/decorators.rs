@@ -0,0 +1,173 @@
+use crate::ast::types::{PrimitiveType, Type};
+use crate::error::RaccoonError;
+use crate::runtime::{DecoratedCall, FutureValue, NativeDecorator, RuntimeValue};
+use crate::tokens::Position;
+use async_recursion::async_recursion;
+use std::collections::HashMap;
+
+use super::declarations::Declarations;
+use super::helpers::Helpers;
+use super::Interpreter;
+
+pub struct NativeDecorators;
+
+impl NativeDecorators {
+    #[async_recursion(?Send)]
+    pub async fn call(
+        interpreter: &mut Interpreter,
+        decorated: &DecoratedCall,
+        args: Vec<RuntimeValue>,
+        named_args: HashMap<String, RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let decorator = &decorated.decorator;
+        let target = &decorated.target;
+        let result = match decorator {
+            NativeDecorator::Memoize { .. } => {
+                let key = NativeDecorator::cache_key(&args);
+                match decorator.cached(&key) {
+                    Some(value) => Ok(value),
+                    None => {
+                        let result =
+                            Self::run(interpreter, target, args, named_args, position).await;
+                        if let Ok(value) = &result {
+                            decorator.remember(key, value.clone());
+                        }
+                        result
+                    }
+                }
+            }
+            NativeDecorator::Throttle { .. } => match decorator.throttled() {
+                Some(value) => Ok(value),
+                None => {
+                    let result = Self::run(interpreter, target, args, named_args, position).await;
+                    if let Ok(value) = &result {
+                        decorator.ran(value.clone());
+                    }
+                    result
+                }
+            },
+            NativeDecorator::Retry { times, backoff } => {
+                let mut attempt = 0;
+                loop {
+                    let result = Self::run(
+                        interpreter,
+                        target,
+                        args.clone(),
+                        named_args.clone(),
+                        position,
+                    )
+                    .await;
+                    if result.is_ok() || attempt == *times {
+                        break result;
+                    }
+                    tokio::time::sleep(backoff.saturating_mul(1 << attempt.min(16))).await;
+                    attempt += 1;
+                }
+            }
+            NativeDecorator::Debounce { delay, .. } => {
+                return Ok(Self::debounce(
+                    interpreter,
+                    decorated,
+                    *delay,
+                    args,
+                    named_args,
+                    position,
+                ));
+            }
+        };
+
+        match Self::async_return_type(target) {
+            Some(return_type) => Ok(RuntimeValue::Future(match result {
+                Ok(value) => FutureValue::new_resolved(value, return_type),
+                Err(error) => FutureValue::new_rejected(error, return_type),
+            })),
+            None => result,
+        }
+    }
+
+    async fn run(
+        interpreter: &mut Interpreter,
+        target: &RuntimeValue,
+        args: Vec<RuntimeValue>,
+        named_args: HashMap<String, RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let stack_depth = interpreter.call_stack.depth();
+        let scope_depth = interpreter.environment.depth();
+        let value = match Helpers::call_function_with_named_args(
+            interpreter,
+            target,
+            args,
+            named_args,
+            position,
+        )
+        .await
+        {
+            Ok(value) => value,
+            Err(error) => {
+                interpreter.call_stack.truncate(stack_depth);
+                interpreter.environment.truncate(scope_depth);
+                return Err(error);
+            }
+        };
+        match value {
+            RuntimeValue::Future(future) if Self::async_return_type(target).is_some() => future
+                .wait_for_completion()
+                .await
+                .map_err(|rejection| {
+                    Declarations::rejected_error(
+                        &interpreter.environment,
+                        rejection,
+                        position,
+                        interpreter.file.clone(),
+                    )
+                }),
+            value => Ok(value),
+        }
+    }
+
+    fn debounce(
+        interpreter: &Interpreter,
+        decorated: &DecoratedCall,
+        delay: std::time::Duration,
+        args: Vec<RuntimeValue>,
+        named_args: HashMap<String, RuntimeValue>,
+        position: Position,
+    ) -> RuntimeValue {
+        let return_type =
+            Self::async_return_type(&decorated.target).unwrap_or_else(PrimitiveType::any);
+        let future = FutureValue::new(return_type);
+        let generation = decorated.decorator.wait_debounced(future.clone());
+
+        let mut forked = interpreter.fork(interpreter.environment.clone());
+        let decorated = decorated.clone();
+        tokio::task::spawn_local(async move {
+            tokio::time::sleep(delay).await;
+            let Some(waiting) = decorated.decorator.settle_debounced(generation) else {
+                return;
+            };
+            let result =
+                Self::run(&mut forked, &decorated.target, args, named_args, position).await;
+            for future in waiting {
+                match &result {
+                    Ok(value) => future.resolve(value.clone()),
+                    Err(error) => future.reject(error.clone()),
+                }
+            }
+        });
+        RuntimeValue::Future(future)
+    }
+
+    fn async_return_type(target: &RuntimeValue) -> Option<Type> {
+        match target {
+            RuntimeValue::Function(function) if function.is_async => {
+                Some(match &function.fn_type {
+                    Type::Function(fn_type) => fn_type.return_type.clone(),
+                    _ => PrimitiveType::any(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
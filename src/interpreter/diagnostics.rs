@@ -0,0 +1,63 @@
+use crate::runtime::logging::{self, Format, Level};
+use serde_json::Map;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+const SOURCE: &str = "raccoon";
+
+enum Sink {
+    Log,
+    Silent,
+    Writer(Box<dyn Write + Send>),
+}
+
+/// Where the interpreter's warnings about the running program go.
+#[derive(Clone)]
+pub struct Diagnostics {
+    sink: Arc<Mutex<Sink>>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self {
+            sink: Arc::new(Mutex::new(Sink::Log)),
+        }
+    }
+
+    pub fn silence(&self) {
+        *self.sink.lock().unwrap() = Sink::Silent;
+    }
+
+    pub fn redirect(&self, writer: impl Write + Send + 'static) {
+        *self.sink.lock().unwrap() = Sink::Writer(Box::new(writer));
+    }
+
+    pub fn warn(&self, message: &str) {
+        match &mut *self.sink.lock().unwrap() {
+            Sink::Log => logging::log(
+                Format::from_env(),
+                Level::Warn,
+                SOURCE,
+                message,
+                &Map::new(),
+            ),
+            Sink::Silent => {}
+            Sink::Writer(writer) => {
+                let line = logging::render(
+                    Format::from_env(),
+                    Level::Warn,
+                    SOURCE,
+                    message,
+                    &Map::new(),
+                );
+                let _ = writeln!(writer, "{}", line);
+            }
+        }
+    }
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,169 @@
+use crate::ast::nodes::{FnParam, Program, VarPattern};
+use crate::ast::types::{FunctionType, PrimitiveType, Type};
+use crate::error::RaccoonError;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::runtime::{FunctionValue, NullValue, RuntimeValue};
+use crate::tokens::Position;
+use std::sync::Arc;
+
+use super::{Interpreter, InterpreterResult};
+
+/// The `eval(code, {scope})` and `compile(code, params)` builtins.
+pub struct Eval;
+
+enum Scope {
+    Isolated,
+    Current,
+}
+
+impl Eval {
+    pub async fn eval(
+        interpreter: &mut Interpreter,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let code = Self::source(interpreter, args.first(), "eval", position)?;
+        let scope = Self::scope(interpreter, args.get(1), position)?;
+        let program = Self::parse(interpreter, code, "eval", position)?;
+
+        match scope {
+            Scope::Isolated => {
+                let mut isolated = Interpreter::new(None);
+                isolated.sandbox = interpreter.sandbox.clone();
+                isolated.diagnostics = interpreter.diagnostics.clone();
+                isolated.interpret(&program).await
+            }
+            Scope::Current => {
+                let mut last_value = RuntimeValue::Null(NullValue::new());
+                for stmt in &program.stmts {
+                    match interpreter.execute_stmt_internal(stmt).await? {
+                        InterpreterResult::Value(value) => last_value = value,
+                        _ => {
+                            return Err(RaccoonError::new(
+                                "Unexpected control flow statement in eval",
+                                position,
+                                interpreter.file.clone(),
+                            ))
+                        }
+                    }
+                }
+                Ok(last_value)
+            }
+        }
+    }
+
+    pub fn compile(
+        interpreter: &Interpreter,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let code = Self::source(interpreter, args.first(), "compile", position)?;
+        let names: Vec<String> = match args.get(1) {
+            None | Some(RuntimeValue::Null(_)) => Vec::new(),
+            Some(RuntimeValue::Array(list)) => {
+                list.snapshot().iter().map(|n| n.to_string()).collect()
+            }
+            Some(other) => {
+                return Err(RaccoonError::new(
+                    format!(
+                        "compile() expects a list of parameter names, got {}",
+                        other.get_name()
+                    ),
+                    position,
+                    interpreter.file.clone(),
+                ))
+            }
+        };
+        let program = Self::parse(interpreter, code, "compile", position)?;
+
+        let parameters = names
+            .iter()
+            .map(|name| FnParam {
+                pattern: VarPattern::Identifier(name.clone()),
+                param_type: PrimitiveType::any(),
+                default_value: None,
+                is_rest: false,
+                is_optional: false,
+                is_untyped: false,
+                position,
+            })
+            .collect();
+        let fn_type = Type::Function(Box::new(FunctionType {
+            params: vec![PrimitiveType::any(); names.len()],
+            return_type: PrimitiveType::any(),
+            is_variadic: false,
+            param_names: names,
+        }));
+
+        Ok(RuntimeValue::Function(Arc::new(FunctionValue::new(
+            parameters,
+            program.stmts,
+            false,
+            fn_type,
+        ))))
+    }
+
+    fn source(
+        interpreter: &Interpreter,
+        code: Option<&RuntimeValue>,
+        builtin: &str,
+        position: Position,
+    ) -> Result<String, RaccoonError> {
+        match code {
+            Some(RuntimeValue::Str(code)) => Ok(code.value.clone()),
+            other => Err(RaccoonError::new(
+                format!(
+                    "{}() expects source code as a string, got {}",
+                    builtin,
+                    other.map_or("nothing".to_string(), RuntimeValue::get_name)
+                ),
+                position,
+                interpreter.file.clone(),
+            )),
+        }
+    }
+
+    fn scope(
+        interpreter: &Interpreter,
+        options: Option<&RuntimeValue>,
+        position: Position,
+    ) -> Result<Scope, RaccoonError> {
+        let scope = match options {
+            Some(RuntimeValue::Object(options)) => options.get("scope"),
+            _ => None,
+        };
+        match scope.map(|scope| scope.to_string()).as_deref() {
+            None | Some("isolated") => Ok(Scope::Isolated),
+            Some("current") => Ok(Scope::Current),
+            Some(other) => Err(RaccoonError::new(
+                format!(
+                    "eval() scope must be \"isolated\" or \"current\", got \"{}\"",
+                    other
+                ),
+                position,
+                interpreter.file.clone(),
+            )),
+        }
+    }
+
+    fn parse(
+        interpreter: &Interpreter,
+        code: String,
+        builtin: &str,
+        position: Position,
+    ) -> Result<Program, RaccoonError> {
+        let syntax_error = |error: RaccoonError| {
+            RaccoonError::new(
+                format!(
+                    "Syntax error in {}() at {}:{}: {}",
+                    builtin, error.position.0, error.position.1, error.message
+                ),
+                position,
+                interpreter.file.clone(),
+            )
+        };
+        let tokens = Lexer::new(code, None).tokenize().map_err(syntax_error)?;
+        Parser::new(tokens, None).parse().map_err(syntax_error)
+    }
+}
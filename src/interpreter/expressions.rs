@@ -2,14 +2,29 @@ use crate::ast::nodes::*;
 use crate::ast::types::{PrimitiveType, Type};
 use crate::error::RaccoonError;
 use crate::runtime::*;
-use crate::tokens::BinaryOperator;
+use crate::runtime::builtins::{global, StringBuilderValue};
+use crate::runtime::types::objects::FunctionType;
+use crate::runtime::types::operations::casting;
+use crate::runtime::types::special::EnumType;
+use crate::tokens::{AccessModifier, BinaryOperator, Position};
 use async_recursion::async_recursion;
+use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use super::builtins::Builtins;
+use super::declarations::Declarations;
+use super::decorators::NativeDecorators;
+use super::eval::Eval;
 use super::helpers::Helpers;
+use super::iteration::Iteration;
 use super::operators;
-use super::{Interpreter, InterpreterResult};
+use super::overloads::Overloads;
+use super::process::ProcessHooks;
+use super::task_group::TaskGroup;
+use super::{Interpreter, InterpreterResult, CLASS_CONTEXT};
+
+type SuperContext = (Arc<ClassInstance>, Arc<ClassValue>);
 
 pub struct Expressions;
 
@@ -19,6 +34,10 @@ impl Expressions {
         interpreter: &mut Interpreter,
         expr: &Expr,
     ) -> Result<RuntimeValue, RaccoonError> {
+        if let Some(result) = Self::evaluate_int_expr(interpreter, expr) {
+            return result;
+        }
+
         match expr {
             Expr::IntLiteral(lit) => Ok(RuntimeValue::Int(IntValue::new(lit.value))),
             Expr::BigIntLiteral(lit) => {
@@ -45,16 +64,21 @@ impl Expressions {
                     return Ok(builtin_type);
                 }
                 
-                interpreter.environment.get(&ident.name, ident.position)
+                interpreter
+                    .environment
+                    .get(&ident.name, ident.position)
+                    .or_else(|error| global::fallback(&ident.name).ok_or(error))
             }
             Expr::Binary(binary) => Self::evaluate_binary_expr(interpreter, binary).await,
             Expr::Unary(unary) => Self::evaluate_unary_expr(interpreter, unary).await,
             Expr::Assignment(assign) => Self::evaluate_assignment(interpreter, assign).await,
             Expr::Call(call) => Self::evaluate_call_expr(interpreter, call).await,
             Expr::ArrayLiteral(list) => Self::evaluate_array_literal(interpreter, list).await,
+            Expr::TupleLiteral(tuple) => Self::evaluate_tuple_literal(interpreter, tuple).await,
             Expr::ObjectLiteral(obj) => Self::evaluate_object_literal(interpreter, obj).await,
             Expr::Member(member) => Self::evaluate_member_expr(interpreter, member).await,
             Expr::Index(index) => Self::evaluate_index_expr(interpreter, index).await,
+            Expr::Slice(slice) => Self::evaluate_slice_expr(interpreter, slice).await,
             Expr::Conditional(cond) => Self::evaluate_conditional_expr(interpreter, cond).await,
             Expr::UnaryUpdate(update) => Self::evaluate_unary_update(interpreter, update).await,
             Expr::TemplateStr(template) => Self::evaluate_template_str(interpreter, template).await,
@@ -63,6 +87,8 @@ impl Expressions {
             Expr::InstanceOf(instanceof) => {
                 Self::evaluate_instanceof_expr(interpreter, instanceof).await
             }
+            Expr::As(as_expr) => Self::evaluate_as_expr(interpreter, as_expr).await,
+            Expr::Is(is_expr) => Self::evaluate_is_expr(interpreter, is_expr).await,
             Expr::OptionalChaining(opt_chain) => {
                 Self::evaluate_optional_chaining(interpreter, opt_chain).await
             }
@@ -72,8 +98,8 @@ impl Expressions {
             Expr::MethodCall(method_call) => {
                 Self::evaluate_method_call(interpreter, method_call).await
             }
-            Expr::This(_) => Self::evaluate_this_expr(interpreter).await,
-            Expr::Super(_) => Self::evaluate_super_expr(interpreter).await,
+            Expr::This(this) => Self::evaluate_this_expr(interpreter, this.position).await,
+            Expr::Super(sup) => Self::evaluate_super_expr(interpreter, sup.position).await,
             Expr::New(new_expr) => Self::evaluate_new_expr(interpreter, new_expr).await,
             Expr::TaggedTemplate(tagged) => {
                 Self::evaluate_tagged_template(interpreter, tagged).await
@@ -89,6 +115,8 @@ impl Expressions {
                 None::<String>,
             )),
             Expr::Match(match_expr) => Self::evaluate_match_expr(interpreter, match_expr).await,
+            Expr::Switch(switch_expr) => Self::evaluate_switch_expr(interpreter, switch_expr).await,
+            Expr::Do(do_expr) => Self::evaluate_do_expr(interpreter, do_expr).await,
             Expr::Class(class_expr) => Self::evaluate_class_expr(interpreter, class_expr).await,
         }
     }
@@ -101,6 +129,12 @@ impl Expressions {
         let left = Self::evaluate_expr(interpreter, &binary.left).await?;
         let right = Self::evaluate_expr(interpreter, &binary.right).await?;
 
+        if let Some(result) =
+            Overloads::binary(interpreter, &left, &right, binary.operator, binary.position).await?
+        {
+            return Ok(result);
+        }
+
         operators::apply_binary_operation(
             left,
             right,
@@ -112,6 +146,120 @@ impl Expressions {
         )
     }
 
+    /// Fast path for int arithmetic on literals and variables; `None` falls back.
+    pub fn evaluate_int_expr(
+        interpreter: &mut Interpreter,
+        expr: &Expr,
+    ) -> Option<Result<RuntimeValue, RaccoonError>> {
+        use crate::tokens::TokenType;
+
+        match expr {
+            Expr::Binary(binary) => Self::evaluate_int_binary(interpreter, binary),
+            Expr::UnaryUpdate(update) => {
+                let Expr::Identifier(ident) = &*update.operand else {
+                    return None;
+                };
+                let current = match Self::int_operand(interpreter, &update.operand)? {
+                    Ok(current) => current,
+                    Err(error) => return Some(Err(error)),
+                };
+                let updated = match update.operator {
+                    UpdateOperator::Increment => current + 1,
+                    UpdateOperator::Decrement => current - 1,
+                };
+                let result = if update.is_prefix { updated } else { current };
+                Some(
+                    interpreter
+                        .environment
+                        .assign(
+                            &ident.name,
+                            RuntimeValue::Int(IntValue::new(updated)),
+                            ident.position,
+                        )
+                        .map(|()| RuntimeValue::Int(IntValue::new(result))),
+                )
+            }
+            Expr::Assignment(assign) => {
+                let Expr::Identifier(ident) = &*assign.target else {
+                    return None;
+                };
+                let value = match (assign.operator, &*assign.value) {
+                    (TokenType::Assign, Expr::Binary(binary)) => {
+                        Self::evaluate_int_binary(interpreter, binary)?
+                    }
+                    (TokenType::Assign, value) => Self::int_operand(interpreter, value)?
+                        .map(|value| RuntimeValue::Int(IntValue::new(value))),
+                    (operator, value) => {
+                        let operator = Self::compound_operator(operator)?;
+                        let current = Self::int_operand(interpreter, &assign.target)?;
+                        let right = Self::int_operand(interpreter, value)?;
+                        current.and_then(|current| {
+                            Self::int_operation(
+                                interpreter,
+                                current,
+                                right?,
+                                operator,
+                                assign.position,
+                            )
+                        })
+                    }
+                };
+                Some(value.and_then(|value| {
+                    interpreter
+                        .environment
+                        .assign(&ident.name, value.clone(), ident.position)?;
+                    Ok(value)
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    fn evaluate_int_binary(
+        interpreter: &Interpreter,
+        binary: &BinaryExpr,
+    ) -> Option<Result<RuntimeValue, RaccoonError>> {
+        let left = Self::int_operand(interpreter, &binary.left)?;
+        let right = Self::int_operand(interpreter, &binary.right)?;
+        Some(left.and_then(|left| {
+            Self::int_operation(interpreter, left, right?, binary.operator, binary.position)
+        }))
+    }
+
+    fn int_operand(interpreter: &Interpreter, expr: &Expr) -> Option<Result<i64, RaccoonError>> {
+        match expr {
+            Expr::IntLiteral(lit) => Some(Ok(lit.value)),
+            Expr::Identifier(ident) => match interpreter.environment.lookup(&ident.name)? {
+                RuntimeValue::Int(value) => Some(Ok(value.value)),
+                _ => None,
+            },
+            Expr::Binary(binary) => match Self::evaluate_int_binary(interpreter, binary)? {
+                Ok(RuntimeValue::Int(value)) => Some(Ok(value.value)),
+                Ok(_) => None,
+                Err(error) => Some(Err(error)),
+            },
+            _ => None,
+        }
+    }
+
+    fn int_operation(
+        interpreter: &Interpreter,
+        left: i64,
+        right: i64,
+        operator: BinaryOperator,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        operators::apply_binary_operation(
+            RuntimeValue::Int(IntValue::new(left)),
+            RuntimeValue::Int(IntValue::new(right)),
+            operator,
+            position,
+            &interpreter.file,
+            &interpreter.call_stack,
+            operators::is_truthy,
+        )
+    }
+
     #[async_recursion(?Send)]
     async fn evaluate_unary_expr(
         interpreter: &mut Interpreter,
@@ -128,6 +276,19 @@ impl Expressions {
         )
     }
 
+    fn compound_operator(operator: crate::tokens::TokenType) -> Option<BinaryOperator> {
+        use crate::tokens::TokenType;
+
+        match operator {
+            TokenType::PlusAssign => Some(BinaryOperator::Add),
+            TokenType::MinusAssign => Some(BinaryOperator::Subtract),
+            TokenType::MultiplyAssign => Some(BinaryOperator::Multiply),
+            TokenType::DivideAssign => Some(BinaryOperator::Divide),
+            TokenType::ModuloAssign => Some(BinaryOperator::Modulo),
+            _ => None,
+        }
+    }
+
     #[async_recursion(?Send)]
     async fn evaluate_assignment(
         interpreter: &mut Interpreter,
@@ -139,148 +300,166 @@ impl Expressions {
             let current_value = Self::evaluate_expr(interpreter, &assign.target).await?;
             let right_value = Self::evaluate_expr(interpreter, &assign.value).await?;
 
-            match assign.operator {
-                TokenType::PlusAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::Add,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::MinusAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::Subtract,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::MultiplyAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::Multiply,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::DivideAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::Divide,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::ModuloAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::Modulo,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::AmpersandAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::BitwiseAnd,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::BitwiseOrAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::BitwiseOr,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::BitwiseXorAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::BitwiseXor,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::LeftShiftAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::LeftShift,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::RightShiftAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::RightShift,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::UnsignedRightShiftAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::UnsignedRightShift,
-                        assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
-                    )
-                    .await?
-                }
-                TokenType::ExponentAssign => {
-                    operators::apply_binary_op(
-                        current_value,
-                        right_value,
-                        BinaryOperator::Exponent,
+            let overloaded = match Self::compound_operator(assign.operator) {
+                Some(operator) => {
+                    Overloads::binary(
+                        interpreter,
+                        &current_value,
+                        &right_value,
+                        operator,
                         assign.position,
-                        &interpreter.file,
-                        &interpreter.call_stack,
                     )
                     .await?
                 }
-                _ => {
-                    return Err(RaccoonError::new(
-                        format!(
-                            "Unknown compound assignment operator: {:?}",
-                            assign.operator
-                        ),
-                        assign.position,
-                        interpreter.file.clone(),
-                    ));
+                None => None,
+            };
+
+            if let Some(value) = overloaded {
+                value
+            } else {
+                match assign.operator {
+                    TokenType::PlusAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::Add,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::MinusAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::Subtract,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::MultiplyAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::Multiply,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::DivideAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::Divide,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::ModuloAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::Modulo,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::AmpersandAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::BitwiseAnd,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::BitwiseOrAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::BitwiseOr,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::BitwiseXorAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::BitwiseXor,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::LeftShiftAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::LeftShift,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::RightShiftAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::RightShift,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::UnsignedRightShiftAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::UnsignedRightShift,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    TokenType::ExponentAssign => {
+                        operators::apply_binary_op(
+                            current_value,
+                            right_value,
+                            BinaryOperator::Exponent,
+                            assign.position,
+                            &interpreter.file,
+                            &interpreter.call_stack,
+                        )
+                        .await?
+                    }
+                    _ => {
+                        return Err(RaccoonError::new(
+                            format!(
+                                "Unknown compound assignment operator: {:?}",
+                                assign.operator
+                            ),
+                            assign.position,
+                            interpreter.file.clone(),
+                        ));
+                    }
                 }
             }
         } else {
@@ -299,20 +478,24 @@ impl Expressions {
 
                 match &mut object {
                     RuntimeValue::Object(obj) => {
+                        obj.check_writable(&member.property).map_err(|message| {
+                            RaccoonError::new(message, assign.position, interpreter.file.clone())
+                        })?;
                         obj.properties
+                            .write()
+                            .unwrap()
                             .insert(member.property.clone(), final_value.clone());
 
-                        if let Expr::Identifier(ident) = &*member.object {
-                            interpreter.environment.assign(
-                                &ident.name,
-                                object.clone(),
-                                ident.position,
-                            )?;
-                        }
-
                         Ok(final_value)
                     }
                     RuntimeValue::ClassInstance(instance) => {
+                        Self::check_private_access(
+                            interpreter,
+                            instance,
+                            &member.property,
+                            assign.position,
+                        )?;
+
                         if let Some(accessor) = instance.accessors.iter().find(|a| {
                             a.name == member.property && matches!(a.kind, AccessorKind::Set)
                         }) {
@@ -342,7 +525,7 @@ impl Expressions {
                                 }
                             }
 
-                            for stmt in &accessor.body {
+                            for stmt in accessor.body.iter() {
                                 match interpreter.execute_stmt_internal(stmt).await? {
                                     InterpreterResult::Return(_) => break,
                                     _ => {}
@@ -353,14 +536,26 @@ impl Expressions {
                             return Ok(final_value);
                         }
 
+                        instance.check_writable(&member.property).map_err(|message| {
+                            RaccoonError::new(message, assign.position, interpreter.file.clone())
+                        })?;
                         instance
                             .properties
                             .write()
                             .unwrap()
                             .insert(member.property.clone(), final_value.clone());
 
+                        Ok(final_value)
+                    }
+                    RuntimeValue::Type(type_obj)
+                        if type_obj.has_static_property(&member.property) =>
+                    {
+                        Arc::make_mut(type_obj)
+                            .static_properties
+                            .insert(member.property.clone(), final_value.clone());
+
                         if let Expr::Identifier(ident) = &*member.object {
-                            interpreter.environment.assign(
+                            interpreter.environment.update(
                                 &ident.name,
                                 object.clone(),
                                 ident.position,
@@ -377,32 +572,57 @@ impl Expressions {
                 }
             }
             Expr::Index(index_expr) => {
-                let mut object = Self::evaluate_expr(interpreter, &index_expr.object).await?;
+                let object = Self::evaluate_expr(interpreter, &index_expr.object).await?;
                 let idx = Self::evaluate_expr(interpreter, &index_expr.index).await?;
 
-                match (&mut object, &idx) {
+                match (&object, &idx) {
                     (RuntimeValue::Array(list), RuntimeValue::Int(i)) => {
-                        if i.value < 0 || i.value >= list.elements.len() as i64 {
-                            return Err(RaccoonError::new(
-                                format!("[1409] Index out of bounds: {}", i.value),
+                        let Some(position) = operators::resolve_index(i.value, list.len())
+                        else {
+                            return Err(RaccoonError::index_out_of_range_error(
+                                format!(
+                                    "Index {} out of bounds (length: {})",
+                                    i.value,
+                                    list.len()
+                                ),
                                 assign.position,
                                 interpreter.file.clone(),
                             ));
-                        }
-                        list.elements[i.value as usize] = final_value.clone();
+                        };
+                        list.elements.write().unwrap()[position] = final_value.clone();
+                    }
+                    (RuntimeValue::Tuple(_), _) => {
+                        return Err(RaccoonError::new(
+                            "Cannot assign to a tuple element; tuples are immutable",
+                            assign.position,
+                            interpreter.file.clone(),
+                        ));
                     }
                     (RuntimeValue::Map(map), key) => {
-                        map.entries.insert(key.to_string(), final_value.clone());
+                        let key = MapKey::from_value(key).map_err(|message| {
+                            RaccoonError::new(message, assign.position, interpreter.file.clone())
+                        })?;
+                        map.entries.write().unwrap().insert(key, final_value.clone());
                     }
-                    (RuntimeValue::Object(obj), RuntimeValue::Str(key)) => {
-                        obj.properties
-                            .insert(key.value.clone(), final_value.clone());
+                    (
+                        RuntimeValue::Object(obj),
+                        key @ (RuntimeValue::Str(_) | RuntimeValue::Symbol(_)),
+                    ) => {
+                        let key = operators::property_key(key);
+                        obj.check_writable(&key).map_err(|message| {
+                            RaccoonError::new(message, assign.position, interpreter.file.clone())
+                        })?;
+                        obj.properties.write().unwrap().insert(key, final_value.clone());
                     }
-                    (RuntimeValue::ClassInstance(inst), RuntimeValue::Str(key)) => {
-                        inst.properties
-                            .write()
-                            .unwrap()
-                            .insert(key.value.clone(), final_value.clone());
+                    (
+                        RuntimeValue::ClassInstance(inst),
+                        key @ (RuntimeValue::Str(_) | RuntimeValue::Symbol(_)),
+                    ) => {
+                        let key = operators::property_key(key);
+                        inst.check_writable(&key).map_err(|message| {
+                            RaccoonError::new(message, assign.position, interpreter.file.clone())
+                        })?;
+                        inst.properties.write().unwrap().insert(key, final_value.clone());
                     }
                     _ => {
                         return Err(RaccoonError::new(
@@ -413,12 +633,6 @@ impl Expressions {
                     }
                 }
 
-                if let Expr::Identifier(ident) = &*index_expr.object {
-                    interpreter
-                        .environment
-                        .assign(&ident.name, object.clone(), ident.position)?;
-                }
-
                 Ok(final_value)
             }
             _ => Err(RaccoonError::new(
@@ -429,6 +643,95 @@ impl Expressions {
         }
     }
 
+    async fn evaluate_arguments(
+        interpreter: &mut Interpreter,
+        args: &[Expr],
+    ) -> Result<Vec<RuntimeValue>, RaccoonError> {
+        let mut values = Vec::new();
+        for arg in args {
+            let Expr::Spread(spread_expr) = arg else {
+                values.push(Self::evaluate_expr(interpreter, arg).await?);
+                continue;
+            };
+
+            let spread_value = Self::evaluate_expr(interpreter, &spread_expr.argument).await?;
+            let Some(elements) =
+                Iteration::collect(interpreter, spread_value, spread_expr.position).await?
+            else {
+                return Err(RaccoonError::new(
+                    "Spread operator can only be applied to iterable values",
+                    spread_expr.position,
+                    interpreter.file.clone(),
+                ));
+            };
+            values.extend(elements);
+        }
+        Ok(values)
+    }
+
+    async fn evaluate_named_args(
+        interpreter: &mut Interpreter,
+        named_args: &HashMap<String, Expr>,
+    ) -> Result<HashMap<String, RuntimeValue>, RaccoonError> {
+        let mut values = HashMap::new();
+        for (name, expr) in named_args {
+            values.insert(name.clone(), Self::evaluate_expr(interpreter, expr).await?);
+        }
+        Ok(values)
+    }
+
+    fn bind_native_args(
+        interpreter: &Interpreter,
+        fn_type: &Type,
+        mut args: Vec<RuntimeValue>,
+        named_args: HashMap<String, RuntimeValue>,
+        position: Position,
+    ) -> Result<Vec<RuntimeValue>, RaccoonError> {
+        if named_args.is_empty() {
+            return Ok(args);
+        }
+
+        let param_names = match fn_type {
+            Type::Function(fn_type) => fn_type.param_names.as_slice(),
+            _ => &[],
+        };
+        if param_names.is_empty() {
+            return Err(RaccoonError::new(
+                "This native function does not accept named arguments",
+                position,
+                interpreter.file.clone(),
+            ));
+        }
+
+        let positional = args.len();
+        for (name, value) in named_args {
+            let Some(index) = param_names.iter().position(|param| *param == name) else {
+                let mut error = RaccoonError::new(
+                    format!("Unknown named argument '{}'", name),
+                    position,
+                    interpreter.file.clone(),
+                );
+                let candidates = param_names.iter().map(String::as_str);
+                if let Some(suggestion) = crate::error::did_you_mean(&name, candidates) {
+                    error = error.with_suggestion(suggestion);
+                }
+                return Err(error);
+            };
+            if index < positional {
+                return Err(RaccoonError::new(
+                    format!("Argument '{}' was already passed positionally", name),
+                    position,
+                    interpreter.file.clone(),
+                ));
+            }
+            if index >= args.len() {
+                args.resize(index + 1, RuntimeValue::Null(NullValue::new()));
+            }
+            args[index] = value;
+        }
+        Ok(args)
+    }
+
     #[async_recursion(?Send)]
     async fn evaluate_call_expr(
         interpreter: &mut Interpreter,
@@ -447,119 +750,71 @@ impl Expressions {
         }
 
         if matches!(call.callee.as_ref(), Expr::Super(_)) {
-            return Self::evaluate_super_call(interpreter, &call.args).await;
+            return Self::evaluate_super_call(interpreter, &call.args, call.position).await;
         }
 
-        let callee = Self::evaluate_expr(interpreter, &call.callee).await?;
-
-        let mut args = Vec::new();
-        for arg in &call.args {
-            if let Expr::Spread(spread_expr) = arg {
-                let spread_value = Self::evaluate_expr(interpreter, &spread_expr.argument).await?;
-
-                if let RuntimeValue::Array(list) = spread_value {
-                    args.extend(list.elements.clone());
-                } else {
-                    print!("Spread operator can only be applied to arrays");
-                    print!(" at position {:?}", spread_expr.position);
-                    print!(" in file {:?}", interpreter.file.clone());
-                    print!("\n");
-                    println!("Got value: {:?}", spread_value);
-                    println!("Full call expression: {:?}", call);
-
-                    return Err(RaccoonError::new(
-                        "Spread operator can only be applied to arrays",
-                        spread_expr.position,
-                        None::<String>,
-                    ));
-                }
-            } else {
-                args.push(Self::evaluate_expr(interpreter, arg).await?);
+        if let Expr::Identifier(ident) = call.callee.as_ref() {
+            if ident.name == "taskGroup" && !interpreter.environment.exists(&ident.name) {
+                let args = Self::evaluate_arguments(interpreter, &call.args).await?;
+                return Ok(TaskGroup::start(interpreter, args, call.position));
+            }
+            if ident.name == "eval" && !interpreter.environment.exists(&ident.name) {
+                let args = Self::evaluate_arguments(interpreter, &call.args).await?;
+                return Eval::eval(interpreter, args, call.position).await;
+            }
+            if ident.name == "compile" && !interpreter.environment.exists(&ident.name) {
+                let args = Self::evaluate_arguments(interpreter, &call.args).await?;
+                return Eval::compile(interpreter, args, call.position);
             }
         }
 
-        let mut named_args = HashMap::new();
-        for (name, expr) in &call.named_args {
-            named_args.insert(name.clone(), Self::evaluate_expr(interpreter, expr).await?);
-        }
+        let callee = Self::evaluate_expr(interpreter, &call.callee).await?;
+
+        let args = Self::evaluate_arguments(interpreter, &call.args).await?;
+        let named_args = Self::evaluate_named_args(interpreter, &call.named_args).await?;
 
         match callee {
             RuntimeValue::Function(func) => {
+                if let Some(bound) = &func.bound {
+                    return Helpers::call_bound(
+                        interpreter,
+                        bound,
+                        args,
+                        named_args,
+                        call.position,
+                    )
+                    .await;
+                }
+                let func = Helpers::select_overload(
+                    interpreter,
+                    &func,
+                    &args,
+                    named_args.len(),
+                    call.position,
+                )?;
+                if let Some(decorated) = &func.decorated {
+                    return NativeDecorators::call(
+                        interpreter,
+                        decorated,
+                        args,
+                        named_args,
+                        call.position,
+                    )
+                    .await;
+                }
                 interpreter.environment.push_scope();
 
-                let mut positional_index = 0;
-
-                for (i, param) in func.parameters.iter().enumerate() {
-                    let param_name = match &param.pattern {
-                        VarPattern::Identifier(name) => name.clone(),
-                        VarPattern::Destructuring(_) => {
-                            format!("__param_{}", i)
-                        }
-                    };
-
-                    let value = if param.is_rest {
-                        let mut rest_args = Vec::new();
-
-                        while positional_index < args.len() {
-                            rest_args.push(args[positional_index].clone());
-                            positional_index += 1;
-                        }
-
-                        let element_type = match &param.param_type {
-                            Type::Array(list_type) => list_type.element_type.clone(),
-                            _ => PrimitiveType::any(),
-                        };
-
-                        RuntimeValue::Array(ArrayValue::new(rest_args, element_type))
-                    } else if let Some(named_value) = named_args.get(&param_name) {
-                        named_value.clone()
-                    } else if positional_index < args.len() {
-                        let current_arg = &args[positional_index];
-                        let should_use_default =
-                            if let VarPattern::Destructuring(DestructuringPattern::Array(_)) =
-                                &param.pattern
-                            {
-                                !matches!(current_arg, RuntimeValue::Array(_))
-                                    && param.default_value.is_some()
-                            } else {
-                                false
-                            };
-
-                        if should_use_default {
-                            Self::evaluate_expr(interpreter, param.default_value.as_ref().unwrap())
-                                .await?
-                        } else {
-                            let arg = args[positional_index].clone();
-                            positional_index += 1;
-                            arg
-                        }
-                    } else if let Some(default_expr) = &param.default_value {
-                        Self::evaluate_expr(interpreter, default_expr).await?
-                    } else if param.is_optional {
-                        RuntimeValue::Null(crate::runtime::values::NullValue)
-                    } else {
-                        interpreter.environment.pop_scope();
-                        return Err(RaccoonError::new(
-                            format!("Missing required argument for parameter '{}'", param_name),
-                            call.position,
-                            interpreter.file.clone(),
-                        ));
-                    };
-
-                    match &param.pattern {
-                        VarPattern::Identifier(name) => {
-                            interpreter.environment.declare(name.clone(), value)?;
-                        }
-                        VarPattern::Destructuring(pattern) => {
-                            if let Err(e) =
-                                Helpers::destructure_pattern(interpreter, pattern, &value, (0, 0))
-                                    .await
-                            {
-                                interpreter.environment.pop_scope();
-                                return Err(e);
-                            }
-                        }
-                    }
+                if let Err(error) = Helpers::bind_parameters(
+                    interpreter,
+                    &func.parameters,
+                    &args,
+                    &named_args,
+                    call.position,
+                )
+                .await
+                {
+                    interpreter.environment.pop_scope();
+                    return Err(error);
                 }
 
                 let is_async = func.is_async;
@@ -581,60 +836,45 @@ impl Expressions {
 
                     
                     let body = func.body.clone();
-                    let file = interpreter.file.clone();
-                    let type_registry = std::sync::Arc::clone(&interpreter.type_registry);
-                    let stdlib_loader = std::sync::Arc::clone(&interpreter.stdlib_loader);
-                    let decorator_registry = interpreter.decorator_registry.clone();
-                    let registrar = interpreter.registrar.clone();
-                    let module_registry = interpreter.module_registry.clone();
-                    let max_recursion_depth = interpreter.max_recursion_depth;
-
-                    
-                    let env_snapshot = interpreter.environment.clone();
+                    let stack_frame = crate::runtime::StackFrame::new(
+                        function_name,
+                        call.position,
+                        interpreter.file.clone(),
+                    );
 
-                    
+                    let mut async_interpreter = interpreter.fork(interpreter.environment.clone());
                     interpreter.environment.pop_scope();
 
-                    
                     tokio::task::spawn_local(async move {
-                        
-                        let mut async_interpreter = Interpreter {
-                            file,
-                            environment: env_snapshot,
-                            type_registry,
-                            stdlib_loader,
-                            recursion_depth: 0,
-                            max_recursion_depth,
-                            decorator_registry,
-                            registrar,
-                            module_registry,
-                            call_stack: CallStack::new(),
-                            use_ir: false,
-                        };
+                        async_interpreter.call_stack.push(stack_frame);
 
                         let mut result = RuntimeValue::Null(NullValue::new());
 
                         
-                        for stmt in &body {
+                        for stmt in body.iter() {
                             match async_interpreter.execute_stmt_internal(stmt).await {
                                 Ok(InterpreterResult::Value(v)) => result = v,
                                 Ok(InterpreterResult::Return(v)) => {
+                                    async_interpreter.call_stack.pop();
                                     future_clone.resolve(v);
                                     return;
                                 }
                                 Ok(_) => {
+                                    async_interpreter.call_stack.pop();
                                     future_clone.reject(
                                         "Unexpected break/continue in function".to_string(),
                                     );
                                     return;
                                 }
                                 Err(e) => {
-                                    future_clone.reject(e.message);
+                                    async_interpreter.call_stack.pop();
+                                    future_clone.reject(e);
                                     return;
                                 }
                             }
                         }
 
+                        async_interpreter.call_stack.pop();
                         future_clone.resolve(result);
                     });
 
@@ -651,7 +891,7 @@ impl Expressions {
                 interpreter.recursion_depth += 1;
 
                 let mut result = RuntimeValue::Null(NullValue::new());
-                for stmt in &func.body {
+                for stmt in func.body.iter() {
                     match interpreter.execute_stmt_internal(stmt).await? {
                         InterpreterResult::Value(v) => result = v,
                         InterpreterResult::Return(v) => {
@@ -667,7 +907,7 @@ impl Expressions {
                             interpreter.environment.pop_scope();
                             return Err(RaccoonError::with_call_stack(
                                 "Unexpected break/continue in function".to_string(),
-                                (0, 0),
+                                stmt.position(),
                                 interpreter.file.clone(),
                                 stack,
                             ));
@@ -680,21 +920,41 @@ impl Expressions {
                 interpreter.environment.pop_scope();
                 Ok(result)
             }
-            RuntimeValue::NativeFunction(func) => Ok((func.implementation)(args)),
+            RuntimeValue::NativeFunction(func) => {
+                let args = Self::bind_native_args(
+                    interpreter,
+                    &func.fn_type,
+                    args,
+                    named_args,
+                    call.position,
+                )?;
+                let args = Overloads::stringify_args(interpreter, args, call.position).await?;
+                Ok((func.implementation)(args))
+            }
             RuntimeValue::NativeAsyncFunction(func) => {
+                let args = Self::bind_native_args(
+                    interpreter,
+                    &func.fn_type,
+                    args,
+                    named_args,
+                    call.position,
+                )?;
+                interpreter.check_native_call(&func, &args, call.position)?;
                 let result = (func.implementation)(args).await;
-                let return_type = match &func.fn_type {
-                    Type::Function(fn_type) => fn_type.return_type.clone(),
-                    _ => PrimitiveType::any(),
-                };
-                Ok(RuntimeValue::Future(FutureValue::new_resolved(
-                    result,
-                    return_type,
-                )))
+                Ok(Helpers::native_async_result(result, &func.fn_type))
             }
+            // Built-in types like `Symbol` that are also factory functions.
+            RuntimeValue::Type(type_obj) => match type_obj.get_constructor() {
+                Some(RuntimeValue::NativeFunction(func)) => Ok((func.implementation)(args)),
+                _ => Err(RaccoonError::new(
+                    format!("Type '{}' is not callable", type_obj.name()),
+                    call.position,
+                    interpreter.file.clone(),
+                )),
+            },
             _ => Err(RaccoonError::new(
                 "Attempted to call a non-function value".to_string(),
-                (0, 0),
+                call.position,
                 interpreter.file.clone(),
             )),
         }
@@ -709,8 +969,10 @@ impl Expressions {
             
             if let Expr::Spread(spread) = elem {
                 let spread_value = Self::evaluate_expr(interpreter, &spread.argument).await?;
-                if let RuntimeValue::Array(list_val) = spread_value {
-                    elements.extend(list_val.elements);
+                if let Some(spread_elements) =
+                    Iteration::collect(interpreter, spread_value, spread.position).await?
+                {
+                    elements.extend(spread_elements);
                 }
             } else {
                 elements.push(Self::evaluate_expr(interpreter, elem).await?);
@@ -726,11 +988,23 @@ impl Expressions {
         Ok(RuntimeValue::Array(ArrayValue::new(elements, element_type)))
     }
 
+    async fn evaluate_tuple_literal(
+        interpreter: &mut Interpreter,
+        tuple: &TupleLiteral,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let mut elements = Vec::new();
+        for elem in &tuple.elements {
+            elements.push(Self::evaluate_expr(interpreter, elem).await?);
+        }
+
+        Ok(RuntimeValue::Tuple(TupleValue::new(elements)))
+    }
+
     async fn evaluate_object_literal(
         interpreter: &mut Interpreter,
         obj: &ObjectLiteral,
     ) -> Result<RuntimeValue, RaccoonError> {
-        let mut properties = HashMap::new();
+        let mut properties = IndexMap::new();
 
         for prop in &obj.properties {
             match prop {
@@ -742,9 +1016,7 @@ impl Expressions {
                     let spread_value = Self::evaluate_expr(interpreter, expr).await?;
                     
                     if let RuntimeValue::Object(ref obj_val) = spread_value {
-                        for (k, v) in &obj_val.properties {
-                            properties.insert(k.clone(), v.clone());
-                        }
+                        properties.extend(obj_val.snapshot());
                     }
                 }
             }
@@ -770,7 +1042,7 @@ impl Expressions {
                 }
 
                 if let Some(static_method) = class.static_methods.get(&member.property) {
-                    Ok(RuntimeValue::Function((**static_method).clone()))
+                    Ok(RuntimeValue::Function(Arc::new((**static_method).clone())))
                 } else {
                     Err(RaccoonError::new(
                         format!(
@@ -783,7 +1055,7 @@ impl Expressions {
                 }
             }
             RuntimeValue::Object(obj) => {
-                if let Some(value) = obj.properties.get(&member.property) {
+                if let Some(value) = obj.properties.read().unwrap().get(&member.property) {
                     Ok(value.clone())
                 } else {
                     Err(RaccoonError::new(
@@ -794,6 +1066,13 @@ impl Expressions {
                 }
             }
             RuntimeValue::ClassInstance(instance) => {
+                Self::check_private_access(
+                    interpreter,
+                    &instance,
+                    &member.property,
+                    member.position,
+                )?;
+
                 if let Some(value) = instance.properties.read().unwrap().get(&member.property) {
                     return Ok(value.clone());
                 }
@@ -811,7 +1090,7 @@ impl Expressions {
                     )?;
 
                     let mut result = RuntimeValue::Null(NullValue::new());
-                    for stmt in &accessor.body {
+                    for stmt in accessor.body.iter() {
                         match interpreter.execute_stmt_internal(stmt).await? {
                             InterpreterResult::Return(value) => {
                                 result = value;
@@ -841,16 +1120,16 @@ impl Expressions {
                 )),
             },
             RuntimeValue::Array(list) => match member.property.as_str() {
-                "length" => Ok(RuntimeValue::Int(IntValue::new(list.elements.len() as i64))),
+                "length" => Ok(RuntimeValue::Int(IntValue::new(list.len() as i64))),
                 "first" => {
-                    if list.elements.is_empty() {
+                    if list.is_empty() {
                         Err(RaccoonError::new(
                             "List is empty".to_string(),
                             member.position,
                             interpreter.file.clone(),
                         ))
                     } else {
-                        Ok(list.elements[0].clone())
+                        Ok(list.elements.read().unwrap()[0].clone())
                     }
                 }
                 _ => Err(RaccoonError::new(
@@ -859,8 +1138,27 @@ impl Expressions {
                     interpreter.file.clone(),
                 )),
             },
+            RuntimeValue::Range(range) => match member.property.as_str() {
+                "length" => Ok(RuntimeValue::Int(IntValue::new(range.len() as i64))),
+                "start" => Ok(RuntimeValue::Int(IntValue::new(range.start))),
+                "end" => Ok(RuntimeValue::Int(IntValue::new(range.end))),
+                "step" => Ok(RuntimeValue::Int(IntValue::new(range.step))),
+                _ => Err(RaccoonError::new(
+                    format!("Property '{}' not found on range", member.property),
+                    member.position,
+                    interpreter.file.clone(),
+                )),
+            },
+            RuntimeValue::Tuple(tuple) => match member.property.as_str() {
+                "length" => Ok(RuntimeValue::Int(IntValue::new(tuple.elements.len() as i64))),
+                _ => Err(RaccoonError::new(
+                    format!("Property '{}' not found on tuple", member.property),
+                    member.position,
+                    interpreter.file.clone(),
+                )),
+            },
             RuntimeValue::Map(map) => match member.property.as_str() {
-                "size" => Ok(RuntimeValue::Int(IntValue::new(map.entries.len() as i64))),
+                "size" => Ok(RuntimeValue::Int(IntValue::new(map.len() as i64))),
                 _ => Err(RaccoonError::new(
                     format!("Property '{}' not found on map", member.property),
                     member.position,
@@ -869,12 +1167,12 @@ impl Expressions {
             },
             RuntimeValue::EnumObject(enum_obj) => {
                 if let Some(enum_value) = enum_obj.members.get(&member.property) {
-                    Ok(RuntimeValue::Enum(EnumValue::new(
+                    Ok(RuntimeValue::Enum(Arc::new(EnumValue::new(
                         enum_obj.enum_name.clone(),
                         member.property.clone(),
                         enum_value.clone(),
                         enum_obj.enum_type.clone(),
-                    )))
+                    ))))
                 } else {
                     Err(RaccoonError::new(
                         format!(
@@ -919,6 +1217,32 @@ impl Expressions {
                     ))
                 }
             }
+            RuntimeValue::Function(_)
+            | RuntimeValue::NativeFunction(_)
+            | RuntimeValue::NativeAsyncFunction(_) => {
+                FunctionType::property(&object, &member.property).ok_or_else(|| {
+                    RaccoonError::new(
+                        format!("Property '{}' not found on function", member.property),
+                        member.position,
+                        interpreter.file.clone(),
+                    )
+                })
+            }
+            RuntimeValue::Symbol(symbol) if member.property == "description" => Ok(symbol
+                .description
+                .map(|description| RuntimeValue::Str(StrValue::new(description)))
+                .unwrap_or_else(|| RuntimeValue::Null(NullValue::new()))),
+            RuntimeValue::Dynamic(value) => value.get_property(&member.property).ok_or_else(|| {
+                RaccoonError::new(
+                    format!(
+                        "Property '{}' not found on {}",
+                        member.property,
+                        value.type_name()
+                    ),
+                    member.position,
+                    interpreter.file.clone(),
+                )
+            }),
             _ => Err(RaccoonError::new(
                 format!("Cannot access property '{}' on type", member.property),
                 member.position,
@@ -935,25 +1259,71 @@ impl Expressions {
         let object = Self::evaluate_expr(interpreter, &index.object).await?;
         let idx = Self::evaluate_expr(interpreter, &index.index).await?;
 
+        if let RuntimeValue::ClassInstance(instance) = &object {
+            let overloaded = Overloads::index(interpreter, instance, &idx, index.position).await?;
+            if let Some(value) = overloaded {
+                return Ok(value);
+            }
+        }
+
+        if let RuntimeValue::Range(range) = &idx {
+            if let Some(slice) = operators::slice_by_range(&object, range) {
+                return Ok(slice);
+            }
+        }
+
         match (object, idx) {
             (RuntimeValue::Array(list), RuntimeValue::Int(i)) => {
-                if i.value < 0 || i.value >= list.elements.len() as i64 {
-                    println!("Index value: {}", i.value);
-                    println!("List length: {}", list.elements.len());
-                    println!("List: {:?}", list.elements);
-
-                    return Err(RaccoonError::new(
-                        "[1614] Index out of bounds".to_string(),
+                match operators::resolve_index(i.value, list.len()) {
+                    Some(position) => Ok(list.elements.read().unwrap()[position].clone()),
+                    None => Err(RaccoonError::index_out_of_range_error(
+                        format!(
+                            "Index {} out of bounds (length: {})",
+                            i.value,
+                            list.len()
+                        ),
                         index.position,
                         interpreter.file.clone(),
-                    ));
+                    )),
+                }
+            }
+            (RuntimeValue::Range(range), RuntimeValue::Int(i)) => {
+                match operators::resolve_index(i.value, range.len()) {
+                    Some(position) => Ok(RuntimeValue::Int(IntValue::new(
+                        range.get(position).unwrap_or_default(),
+                    ))),
+                    None => Err(RaccoonError::index_out_of_range_error(
+                        format!(
+                            "Range index {} out of bounds (length: {})",
+                            i.value,
+                            range.len()
+                        ),
+                        index.position,
+                        interpreter.file.clone(),
+                    )),
+                }
+            }
+            (RuntimeValue::Tuple(tuple), RuntimeValue::Int(i)) => {
+                match operators::resolve_index(i.value, tuple.elements.len()) {
+                    Some(position) => Ok(tuple.elements[position].clone()),
+                    None => Err(RaccoonError::index_out_of_range_error(
+                        format!(
+                            "Tuple index {} out of bounds (length: {})",
+                            i.value,
+                            tuple.elements.len()
+                        ),
+                        index.position,
+                        interpreter.file.clone(),
+                    )),
                 }
-                Ok(list.elements[i.value as usize].clone())
             }
             (RuntimeValue::Str(s), RuntimeValue::Int(i)) => {
                 let chars: Vec<char> = s.value.chars().collect();
-                if i.value < 0 || i.value >= chars.len() as i64 {
-                    return Err(RaccoonError::new(
+                match operators::resolve_index(i.value, chars.len()) {
+                    Some(position) => Ok(RuntimeValue::Str(StrValue::new(
+                        chars[position].to_string(),
+                    ))),
+                    None => Err(RaccoonError::index_out_of_range_error(
                         format!(
                             "String index {} out of bounds (length: {})",
                             i.value,
@@ -961,28 +1331,37 @@ impl Expressions {
                         ),
                         index.position,
                         interpreter.file.clone(),
-                    ));
-                }
-                Ok(RuntimeValue::Str(StrValue::new(
-                    chars[i.value as usize].to_string(),
-                )))
-            }
-            (RuntimeValue::Map(map), RuntimeValue::Str(key)) => {
-                if let Some(value) = map.entries.get(&key.value) {
-                    Ok(value.clone())
-                } else {
-                    Ok(RuntimeValue::Null(NullValue::new()))
+                    )),
                 }
             }
-            (RuntimeValue::Object(obj), RuntimeValue::Str(key)) => {
-                if let Some(value) = obj.properties.get(&key.value) {
-                    Ok(value.clone())
+            (RuntimeValue::Map(map), key) => {
+                let key = MapKey::from_value(&key).map_err(|message| {
+                    RaccoonError::new(message, index.position, interpreter.file.clone())
+                })?;
+                Ok(map
+                    .entries
+                    .read()
+                    .unwrap()
+                    .get(&key)
+                    .cloned()
+                    .unwrap_or(RuntimeValue::Null(NullValue::new())))
+            }
+            (
+                RuntimeValue::Object(obj),
+                key @ (RuntimeValue::Str(_) | RuntimeValue::Symbol(_)),
+            ) => {
+                if let Some(value) = obj.get(&operators::property_key(&key)) {
+                    Ok(value)
                 } else {
                     Ok(RuntimeValue::Null(NullValue::new()))
                 }
             }
-            (RuntimeValue::ClassInstance(inst), RuntimeValue::Str(key)) => {
-                if let Some(value) = inst.properties.read().unwrap().get(&key.value) {
+            (
+                RuntimeValue::ClassInstance(inst),
+                key @ (RuntimeValue::Str(_) | RuntimeValue::Symbol(_)),
+            ) => {
+                let key = operators::property_key(&key);
+                if let Some(value) = inst.properties.read().unwrap().get(&key) {
                     Ok(value.clone())
                 } else {
                     Ok(RuntimeValue::Null(NullValue::new()))
@@ -996,6 +1375,32 @@ impl Expressions {
         }
     }
 
+    async fn evaluate_slice_expr(
+        interpreter: &mut Interpreter,
+        slice: &SliceExpr,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let object = Self::evaluate_expr(interpreter, &slice.object).await?;
+
+        let mut bounds = [None, None];
+        for (bound, expr) in bounds.iter_mut().zip([&slice.start, &slice.end]) {
+            let Some(expr) = expr else { continue };
+            match Self::evaluate_expr(interpreter, expr).await? {
+                RuntimeValue::Int(i) => *bound = Some(i.value),
+                RuntimeValue::Null(_) => {}
+                other => {
+                    return Err(RaccoonError::new(
+                        format!("Slice bounds must be integers, got {}", other.get_name()),
+                        slice.position,
+                        interpreter.file.clone(),
+                    ))
+                }
+            }
+        }
+
+        operators::slice_value(&object, bounds[0], bounds[1], slice.inclusive)
+            .map_err(|message| RaccoonError::new(message, slice.position, interpreter.file.clone()))
+    }
+
     #[async_recursion(?Send)]
     async fn evaluate_conditional_expr(
         interpreter: &mut Interpreter,
@@ -1082,7 +1487,8 @@ impl Expressions {
                 TemplateStrPart::String(s) => result.push_str(&s.value),
                 TemplateStrPart::Expr(expr) => {
                     let value = Self::evaluate_expr(interpreter, expr).await?;
-                    result.push_str(&value.to_string());
+                    let text = Overloads::stringify(interpreter, &value, template.position).await?;
+                    result.push_str(&text);
                 }
             }
         }
@@ -1094,13 +1500,11 @@ impl Expressions {
         _interpreter: &mut Interpreter,
         arrow: &ArrowFnExpr,
     ) -> Result<RuntimeValue, RaccoonError> {
-        let body = match &arrow.body {
-            ArrowFnBody::Expr(expr) => {
-                vec![Stmt::ReturnStmt(ReturnStmt {
-                    value: Some((**expr).clone()),
-                    position: arrow.position,
-                })]
-            }
+        let body: Body = match &arrow.body {
+            ArrowFnBody::Expr(expr) => std::sync::Arc::new([Stmt::ReturnStmt(ReturnStmt {
+                value: Some((**expr).clone()),
+                position: arrow.position,
+            })]),
             ArrowFnBody::Block(stmts) => stmts.clone(),
         };
 
@@ -1117,14 +1521,15 @@ impl Expressions {
                 .collect(),
             return_type,
             is_variadic: arrow.parameters.iter().any(|p| p.is_rest),
+            param_names: Vec::new(),
         }));
 
-        Ok(RuntimeValue::Function(FunctionValue::new(
+        Ok(RuntimeValue::Function(Arc::new(FunctionValue::new(
             arrow.parameters.clone(),
             body,
             arrow.is_async,
             fn_type,
-        )))
+        ))))
     }
 
     async fn evaluate_typeof_expr(
@@ -1141,7 +1546,10 @@ impl Expressions {
             RuntimeValue::Str(_) => "str",
             RuntimeValue::Bool(_) => "bool",
             RuntimeValue::Null(_) => "null",
+            RuntimeValue::Symbol(_) => "symbol",
             RuntimeValue::Array(_) => "array",
+            RuntimeValue::Tuple(_) => "tuple",
+            RuntimeValue::Range(_) => "range",
             RuntimeValue::Map(_) => "map",
             RuntimeValue::Object(_) => "object",
             RuntimeValue::Class(ref c) => {
@@ -1201,6 +1609,32 @@ impl Expressions {
         }
     }
 
+    async fn evaluate_as_expr(
+        interpreter: &mut Interpreter,
+        as_expr: &AsExpr,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let value = Self::evaluate_expr(interpreter, &as_expr.operand).await?;
+        casting::cast(
+            value,
+            &as_expr.target_type,
+            &interpreter.environment,
+            as_expr.position,
+            &interpreter.file,
+        )
+    }
+
+    async fn evaluate_is_expr(
+        interpreter: &mut Interpreter,
+        is_expr: &IsExpr,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let value = Self::evaluate_expr(interpreter, &is_expr.operand).await?;
+        Ok(RuntimeValue::Bool(BoolValue::new(casting::value_is(
+            &value,
+            &is_expr.target_type,
+            &interpreter.environment,
+        ))))
+    }
+
     async fn evaluate_optional_chaining(
         interpreter: &mut Interpreter,
         opt_chain: &OptionalChainingExpr,
@@ -1213,7 +1647,7 @@ impl Expressions {
 
         match object {
             RuntimeValue::Object(obj) => {
-                if let Some(value) = obj.properties.get(&opt_chain.property) {
+                if let Some(value) = obj.properties.read().unwrap().get(&opt_chain.property) {
                     Ok(value.clone())
                 } else {
                     Ok(RuntimeValue::Null(NullValue::new()))
@@ -1249,12 +1683,13 @@ impl Expressions {
 
     async fn evaluate_this_expr(
         interpreter: &mut Interpreter,
+        position: Position,
     ) -> Result<RuntimeValue, RaccoonError> {
-        match interpreter.environment.get("this", (0, 0)) {
+        match interpreter.environment.get("this", position) {
             Ok(value) => Ok(value),
             Err(_) => Err(RaccoonError::new(
                 "Cannot use 'this' outside of a class method".to_string(),
-                (0, 0),
+                position,
                 interpreter.file.clone(),
             )),
         }
@@ -1262,10 +1697,11 @@ impl Expressions {
 
     async fn evaluate_super_expr(
         interpreter: &mut Interpreter,
+        position: Position,
     ) -> Result<RuntimeValue, RaccoonError> {
         Err(RaccoonError::new(
             "Cannot use 'super' outside of a class method".to_string(),
-            (0, 0),
+            position,
             interpreter.file.clone(),
         ))
     }
@@ -1273,144 +1709,227 @@ impl Expressions {
     async fn evaluate_super_call(
         interpreter: &mut Interpreter,
         args: &[Expr],
+        position: Position,
     ) -> Result<RuntimeValue, RaccoonError> {
-        let this_instance = interpreter.environment.get("this", (0, 0))?;
+        let Some((instance, current_class)) = Self::super_context(interpreter, position)? else {
+            return Err(RaccoonError::new(
+                "Cannot use 'super' outside of a class constructor".to_string(),
+                position,
+                interpreter.file.clone(),
+            ));
+        };
+
+        let lineage = Self::class_lineage(interpreter, &current_class, position);
+        let Some(superclass) = lineage.get(1) else {
+            return Err(RaccoonError::new(
+                "Cannot use 'super' in class without superclass".to_string(),
+                position,
+                interpreter.file.clone(),
+            ));
+        };
 
-        if let RuntimeValue::ClassInstance(instance) = this_instance {
-            let class_name = instance.class_name.clone();
+        let Some((owner, super_constructor)) = lineage[1..].iter().find_map(|declaring| {
+            let constructor = declaring.declaration.constructor.as_ref()?;
+            Some((declaring.class_name.clone(), constructor))
+        }) else {
+            return Err(RaccoonError::new(
+                format!("Superclass '{}' has no constructor", superclass.class_name),
+                position,
+                interpreter.file.clone(),
+            ));
+        };
 
-            let class_value = interpreter.environment.get(&class_name, (0, 0))?;
+        let mut arg_values = Vec::new();
+        for arg in args {
+            arg_values.push(Self::evaluate_expr(interpreter, arg).await?);
+        }
 
-            
-            let class = match &class_value {
-                RuntimeValue::Class(c) => c.clone(),
-                RuntimeValue::Type(type_obj) => {
-                    if let Some(RuntimeValue::Class(c)) = type_obj.get_constructor() {
-                        c.clone()
-                    } else {
-                        return Err(RaccoonError::new(
-                            format!("Type '{}' does not have a valid constructor", class_name),
-                            (0, 0),
-                            interpreter.file.clone(),
-                        ));
-                    }
+        interpreter.environment.push_scope();
+
+        for (param, arg) in super_constructor.parameters.iter().zip(arg_values.iter()) {
+            match &param.pattern {
+                VarPattern::Identifier(name) => {
+                    interpreter.environment.declare(name.clone(), arg.clone())?;
                 }
-                _ => {
-                    return Err(RaccoonError::new(
-                        format!("Class '{}' not found", class_name),
-                        (0, 0),
-                        interpreter.file.clone(),
-                    ));
+                VarPattern::Destructuring(pattern) => {
+                    if let Err(e) =
+                        Helpers::destructure_pattern(interpreter, pattern, arg, position).await
+                    {
+                        interpreter.environment.pop_scope();
+                        return Err(e);
+                    }
                 }
-            };
-
-            if let Some(ref superclass_name) = class.declaration.superclass {
-                let superclass_value = interpreter.environment.get(superclass_name, (0, 0))?;
+            }
+        }
 
-                
-                let superclass = match &superclass_value {
-                    RuntimeValue::Class(sc) => sc.clone(),
-                    RuntimeValue::Type(type_obj) => {
-                        if let Some(RuntimeValue::Class(sc)) = type_obj.get_constructor() {
-                            sc.clone()
-                        } else {
-                            return Err(RaccoonError::new(
-                                format!(
-                                    "Superclass '{}' does not have a valid constructor",
-                                    superclass_name
-                                ),
-                                (0, 0),
-                                interpreter.file.clone(),
-                            ));
+        interpreter.environment.declare(
+            "this".to_string(),
+            RuntimeValue::ClassInstance(instance.clone()),
+        )?;
+        interpreter.environment.declare(
+            CLASS_CONTEXT.to_string(),
+            RuntimeValue::Str(StrValue::new(owner)),
+        )?;
+
+        for stmt in super_constructor.body.iter() {
+            if let Stmt::ExprStmt(expr_stmt) = stmt {
+                if let Expr::Assignment(assign) = &expr_stmt.expression {
+                    if let Expr::Member(member) = &*assign.target {
+                        if let Expr::This(_) = &*member.object {
+                            let value = Self::evaluate_expr(interpreter, &assign.value).await?;
+                            instance
+                                .properties
+                                .write()
+                                .unwrap()
+                                .insert(member.property.clone(), value);
+                            continue;
                         }
                     }
-                    _ => {
-                        return Err(RaccoonError::new(
-                            format!("'{}' is not a class", superclass_name),
-                            (0, 0),
-                            interpreter.file.clone(),
-                        ));
-                    }
-                };
+                }
+            }
 
-                if let Some(ref super_constructor) = superclass.declaration.constructor {
-                    let mut arg_values = Vec::new();
-                    for arg in args {
-                        arg_values.push(Self::evaluate_expr(interpreter, arg).await?);
-                    }
+            match interpreter.execute_stmt_internal(stmt).await? {
+                InterpreterResult::Return(_) => break,
+                _ => {}
+            }
+        }
 
-                    interpreter.environment.push_scope();
+        interpreter.environment.pop_scope();
 
-                    for (param, arg) in super_constructor.parameters.iter().zip(arg_values.iter()) {
-                        match &param.pattern {
-                            VarPattern::Identifier(name) => {
-                                interpreter.environment.declare(name.clone(), arg.clone())?;
-                            }
-                            VarPattern::Destructuring(pattern) => {
-                                if let Err(e) =
-                                    Helpers::destructure_pattern(interpreter, pattern, arg, (0, 0))
-                                        .await
-                                {
-                                    interpreter.environment.pop_scope();
-                                    return Err(e);
-                                }
-                            }
-                        }
-                    }
+        Ok(RuntimeValue::Null(NullValue::new()))
+    }
 
-                    interpreter.environment.declare(
-                        "this".to_string(),
-                        RuntimeValue::ClassInstance(instance.clone()),
-                    )?;
+    async fn evaluate_super_method_call(
+        interpreter: &mut Interpreter,
+        method_call: &MethodCallExpr,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let position = method_call.position;
+        let Some((instance, current_class)) = Self::super_context(interpreter, position)? else {
+            return Err(RaccoonError::new(
+                "Cannot use 'super' outside of a class method".to_string(),
+                position,
+                interpreter.file.clone(),
+            ));
+        };
 
-                    for stmt in &super_constructor.body {
-                        if let Stmt::ExprStmt(expr_stmt) = stmt {
-                            if let Expr::Assignment(assign) = &expr_stmt.expression {
-                                if let Expr::Member(member) = &*assign.target {
-                                    if let Expr::This(_) = &*member.object {
-                                        let value =
-                                            Self::evaluate_expr(interpreter, &assign.value).await?;
-                                        instance
-                                            .properties
-                                            .write()
-                                            .unwrap()
-                                            .insert(member.property.clone(), value);
-                                        continue;
-                                    }
-                                }
-                            }
-                        }
+        let lineage = Self::class_lineage(interpreter, &current_class, position);
+        if lineage.len() < 2 {
+            return Err(RaccoonError::new(
+                "Cannot use 'super' in class without superclass".to_string(),
+                position,
+                interpreter.file.clone(),
+            ));
+        }
 
-                        match interpreter.execute_stmt_internal(stmt).await? {
-                            InterpreterResult::Return(_) => break,
-                            _ => {}
-                        }
-                    }
+        let Some((owner, method)) = lineage[1..].iter().find_map(|declaring| {
+            let method = declaring
+                .declaration
+                .methods
+                .iter()
+                .find(|m| !m.is_static && m.name == method_call.method)?;
+            Some((declaring.class_name.clone(), method))
+        }) else {
+            return Err(RaccoonError::new(
+                format!(
+                    "Method '{}' not found on superclass of {}",
+                    method_call.method, current_class.class_name
+                ),
+                position,
+                interpreter.file.clone(),
+            ));
+        };
 
-                    interpreter.environment.pop_scope();
+        let mut args = Vec::new();
+        for arg in &method_call.args {
+            args.push(Self::evaluate_expr(interpreter, arg).await?);
+        }
 
-                    return Ok(RuntimeValue::Null(NullValue::new()));
-                } else {
-                    return Err(RaccoonError::new(
-                        format!("Superclass '{}' has no constructor", superclass_name),
-                        (0, 0),
-                        interpreter.file.clone(),
-                    ));
-                }
-            } else {
-                return Err(RaccoonError::new(
-                    "Cannot use 'super' in class without superclass".to_string(),
-                    (0, 0),
+        let fn_type = Type::Function(Box::new(crate::ast::types::FunctionType {
+            params: method
+                .parameters
+                .iter()
+                .map(|p| p.param_type.clone())
+                .collect(),
+            return_type: method
+                .return_type
+                .clone()
+                .unwrap_or_else(PrimitiveType::unknown),
+            is_variadic: method.parameters.iter().any(|p| p.is_rest),
+            param_names: Vec::new(),
+        }));
+        let function = FunctionValue::new(
+            method.parameters.clone(),
+            method.body.clone(),
+            method.is_async,
+            fn_type,
+        )
+        .with_name(format!("{}.{}", owner, method_call.method));
+
+        Helpers::invoke_method(interpreter, &instance, &owner, function, args, position).await
+    }
+
+    fn super_context(
+        interpreter: &Interpreter,
+        position: Position,
+    ) -> Result<Option<SuperContext>, RaccoonError> {
+        let Ok(RuntimeValue::ClassInstance(instance)) =
+            interpreter.environment.get("this", position)
+        else {
+            return Ok(None);
+        };
+
+        let class_name = match interpreter.environment.get(CLASS_CONTEXT, position) {
+            Ok(RuntimeValue::Str(name)) => name.value,
+            _ => instance.class_name.clone(),
+        };
+        let class = Self::resolve_class(interpreter, &class_name, position)?;
+        Ok(Some((instance, class)))
+    }
+
+    pub fn resolve_class(
+        interpreter: &Interpreter,
+        class_name: &str,
+        position: Position,
+    ) -> Result<Arc<ClassValue>, RaccoonError> {
+        match interpreter.environment.get(class_name, position)? {
+            RuntimeValue::Class(class) => Ok(class),
+            RuntimeValue::Type(type_obj) => match type_obj.get_constructor() {
+                Some(RuntimeValue::Class(class)) => Ok(class.clone()),
+                _ => Err(RaccoonError::new(
+                    format!("Type '{}' does not have a valid constructor", class_name),
+                    position,
                     interpreter.file.clone(),
-                ));
-            }
-        } else {
-            return Err(RaccoonError::new(
-                "Cannot use 'super' outside of a class constructor".to_string(),
-                (0, 0),
+                )),
+            },
+            _ => Err(RaccoonError::new(
+                format!("Class '{}' not found or not yet implemented", class_name),
+                position,
                 interpreter.file.clone(),
-            ));
+            )),
+        }
+    }
+
+    /// The class followed by its ancestors, nearest first.
+    pub fn class_lineage(
+        interpreter: &Interpreter,
+        class: &Arc<ClassValue>,
+        position: Position,
+    ) -> Vec<Arc<ClassValue>> {
+        let mut lineage = vec![class.clone()];
+        let mut next = class.declaration.superclass.clone();
+
+        while let Some(name) = next {
+            if lineage.iter().any(|ancestor| ancestor.class_name == name) {
+                break;
+            }
+            let Ok(superclass) = Self::resolve_class(interpreter, &name, position) else {
+                break;
+            };
+            next = superclass.declaration.superclass.clone();
+            lineage.push(superclass);
         }
+
+        lineage
     }
 
     async fn evaluate_range_expr(
@@ -1421,16 +1940,11 @@ impl Expressions {
         let end = Self::evaluate_expr(interpreter, &range.end).await?;
 
         match (start, end) {
-            (RuntimeValue::Int(s), RuntimeValue::Int(e)) => {
-                let mut elements = Vec::new();
-                for i in s.value..=e.value {
-                    elements.push(RuntimeValue::Int(IntValue::new(i)));
-                }
-                Ok(RuntimeValue::Array(ArrayValue::new(
-                    elements,
-                    PrimitiveType::int(),
-                )))
-            }
+            (RuntimeValue::Int(s), RuntimeValue::Int(e)) => Ok(RuntimeValue::Range(RangeValue::new(
+                s.value,
+                e.value,
+                range.inclusive,
+            ))),
             _ => Err(RaccoonError::new(
                 "Range operator requires integer operands".to_string(),
                 range.position,
@@ -1468,167 +1982,100 @@ impl Expressions {
             let key_type = new_expr.type_args[0].clone();
             let value_type = new_expr.type_args[1].clone();
 
-            return Ok(RuntimeValue::Map(crate::runtime::MapValue::new(
-                HashMap::new(),
+            return Ok(RuntimeValue::Map(Arc::new(crate::runtime::MapValue::new(
+                IndexMap::new(),
                 key_type,
-                value_type,
-            )));
-        }
-
-        let class_value = interpreter
-            .environment
-            .get(&new_expr.class_name, new_expr.position)?;
-
-        
-        let class = match &class_value {
-            RuntimeValue::Class(c) => c.clone(),
-            RuntimeValue::Type(type_obj) => {
-                if let Some(RuntimeValue::Class(c)) = type_obj.get_constructor() {
-                    c.clone()
-                } else {
-                    return Err(RaccoonError::new(
-                        format!(
-                            "Type '{}' does not have a valid constructor",
-                            new_expr.class_name
-                        ),
-                        new_expr.position,
-                        interpreter.file.clone(),
-                    ));
-                }
-            }
-            _ => {
-                return Err(RaccoonError::new(
-                    format!(
-                        "Class '{}' not found or not yet implemented",
-                        new_expr.class_name
-                    ),
-                    new_expr.position,
-                    interpreter.file.clone(),
-                ));
-            }
-        };
+                value_type,
+            ))));
+        }
+
+        if new_expr.class_name == StringBuilderValue::TYPE_NAME
+            && !interpreter.environment.exists(&new_expr.class_name)
+        {
+            let args = Self::evaluate_arguments(interpreter, &new_expr.args).await?;
+            let initial = args.first().map(|arg| arg.to_string()).unwrap_or_default();
+            return Ok(StringBuilderValue::new(&initial).into_value());
+        }
+
+        let class = Self::resolve_class(interpreter, &new_expr.class_name, new_expr.position)?;
+        let args = Self::evaluate_arguments(interpreter, &new_expr.args).await?;
+        let named_args = Self::evaluate_named_args(interpreter, &new_expr.named_args).await?;
+        Self::instantiate(interpreter, &class, args, named_args, new_expr.position).await
+    }
+
+    /// Builds an instance of `class` and runs the nearest constructor with `args`.
+    #[async_recursion(?Send)]
+    pub async fn instantiate(
+        interpreter: &mut Interpreter,
+        class: &Arc<ClassValue>,
+        args: Vec<RuntimeValue>,
+        named_args: HashMap<String, RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let lineage = Self::class_lineage(interpreter, class, position);
 
-        
         {
             let mut properties = HashMap::new();
             let mut methods = HashMap::new();
+            let mut readonly_properties = Vec::new();
+            let mut private_members = HashMap::new();
+            let mut initializers = Vec::new();
+            let mut accessors = Vec::new();
 
-            if let Some(ref superclass_name) = class.declaration.superclass {
-                let superclass_value = interpreter
-                    .environment
-                    .get(superclass_name, new_expr.position)
-                    .ok();
-                let superclass = match superclass_value {
-                    Some(RuntimeValue::Class(sc)) => Some(sc),
-                    Some(RuntimeValue::Type(type_obj)) => {
-                        if let Some(RuntimeValue::Class(sc)) = type_obj.get_constructor() {
-                            Some(sc.clone())
-                        } else {
-                            None
-                        }
-                    }
-                    _ => None,
-                };
+            let mut method_owners = HashMap::new();
 
-                if let Some(superclass) = superclass {
-                    for prop in &superclass.declaration.properties {
-                        let value = if let Some(init) = &prop.initializer {
-                            Self::evaluate_expr(interpreter, init).await?
-                        } else {
-                            RuntimeValue::Null(NullValue::new())
-                        };
-                        properties.insert(prop.name.clone(), value);
+            let declarations = lineage
+                .iter()
+                .rev()
+                .map(|declaring| (&declaring.class_name, &declaring.declaration));
+
+            for (class_name, declaration) in declarations {
+                for prop in &declaration.properties {
+                    properties.insert(prop.name.clone(), RuntimeValue::Null(NullValue::new()));
+                    if let Some(init) = &prop.initializer {
+                        initializers.push((prop.name.as_str(), init));
                     }
-
-                    for method in &superclass.declaration.methods {
-                        if !method.is_static {
-                            let fn_type =
-                                Type::Function(Box::new(crate::ast::types::FunctionType {
-                                    params: method
-                                        .parameters
-                                        .iter()
-                                        .map(|p| p.param_type.clone())
-                                        .collect(),
-                                    return_type: method
-                                        .return_type
-                                        .clone()
-                                        .unwrap_or_else(|| PrimitiveType::unknown()),
-                                    is_variadic: method.parameters.iter().any(|p| p.is_rest),
-                                }));
-
-                            let function = FunctionValue::new(
-                                method.parameters.clone(),
-                                method.body.clone(),
-                                false,
-                                fn_type,
-                            );
-
-                            methods.insert(method.name.clone(), function);
-                        }
+                    if prop.is_readonly {
+                        readonly_properties.push(prop.name.clone());
+                    }
+                    if prop.access_modifier == AccessModifier::Private {
+                        private_members.insert(prop.name.clone(), class_name.clone());
                     }
                 }
-            }
-
-            for prop in &class.declaration.properties {
-                let value = if let Some(init) = &prop.initializer {
-                    Self::evaluate_expr(interpreter, init).await?
-                } else {
-                    RuntimeValue::Null(NullValue::new())
-                };
-                properties.insert(prop.name.clone(), value);
-            }
-
-            for method in &class.declaration.methods {
-                if !method.is_static {
-                    let fn_type = Type::Function(Box::new(crate::ast::types::FunctionType {
-                        params: method
-                            .parameters
-                            .iter()
-                            .map(|p| p.param_type.clone())
-                            .collect(),
-                        return_type: method
-                            .return_type
-                            .clone()
-                            .unwrap_or_else(|| PrimitiveType::unknown()),
-                        is_variadic: method.parameters.iter().any(|p| p.is_rest),
-                    }));
-
-                    let function = FunctionValue::new(
-                        method.parameters.clone(),
-                        method.body.clone(),
-                        false,
-                        fn_type,
-                    );
-
-                    methods.insert(method.name.clone(), function);
-                }
-            }
-
-            let mut accessors = Vec::new();
 
-            if let Some(ref superclass_name) = class.declaration.superclass {
-                let superclass_value = interpreter
-                    .environment
-                    .get(superclass_name, new_expr.position)
-                    .ok();
-                let superclass = match superclass_value {
-                    Some(RuntimeValue::Class(sc)) => Some(sc),
-                    Some(RuntimeValue::Type(type_obj)) => {
-                        if let Some(RuntimeValue::Class(sc)) = type_obj.get_constructor() {
-                            Some(sc.clone())
-                        } else {
-                            None
+                for method in &declaration.methods {
+                    if !method.is_static {
+                        let fn_type = Type::Function(Box::new(crate::ast::types::FunctionType {
+                            params: method
+                                .parameters
+                                .iter()
+                                .map(|p| p.param_type.clone())
+                                .collect(),
+                            return_type: method
+                                .return_type
+                                .clone()
+                                .unwrap_or_else(|| PrimitiveType::unknown()),
+                            is_variadic: method.parameters.iter().any(|p| p.is_rest),
+                            param_names: Vec::new(),
+                        }));
+
+                        let function = FunctionValue::new(
+                            method.parameters.clone(),
+                            method.body.clone(),
+                            false,
+                            fn_type,
+                        );
+
+                        methods.insert(method.name.clone(), function);
+                        method_owners.insert(method.name.clone(), class_name.clone());
+                        if method.access_modifier == AccessModifier::Private {
+                            private_members.insert(method.name.clone(), class_name.clone());
                         }
                     }
-                    _ => None,
-                };
-
-                if let Some(superclass) = superclass {
-                    accessors.extend(superclass.declaration.accessors.clone());
                 }
-            }
 
-            accessors.extend(class.declaration.accessors.clone());
+                accessors.splice(0..0, declaration.accessors.iter().cloned());
+            }
 
             let instance = crate::runtime::ClassInstance::new(
                 class.class_name.clone(),
@@ -1636,76 +2083,52 @@ impl Expressions {
                 methods,
                 accessors,
                 class.class_type.clone(),
-            );
-
-            if let Some(constructor) = &class.declaration.constructor {
-                let mut args = Vec::new();
-                for arg in &new_expr.args {
-                    args.push(Self::evaluate_expr(interpreter, arg).await?);
-                }
-
+            )
+            .with_readonly_properties(readonly_properties)
+            .with_private_members(private_members)
+            .with_method_owners(method_owners)
+            .with_error(lineage.last().is_some_and(|root| {
+                root.class_name == "Error" && root.declaration.superclass.is_none()
+            }));
+            let instance = Arc::new(instance);
+
+            let constructor = lineage.iter().find_map(|declaring| {
+                let constructor = declaring.declaration.constructor.as_ref()?;
+                Some((declaring.class_name.clone(), constructor))
+            });
+
+            if let Some((constructor_owner, constructor)) = constructor {
                 interpreter.environment.push_scope();
 
-                let mut positional_index = 0;
-
-                for (i, param) in constructor.parameters.iter().enumerate() {
-                    let param_name = match &param.pattern {
-                        VarPattern::Identifier(name) => name.clone(),
-                        VarPattern::Destructuring(_) => {
-                            format!("__param_{}", i)
-                        }
-                    };
-
-                    let value = if param.is_rest {
-                        let mut rest_args = Vec::new();
-                        while positional_index < args.len() {
-                            rest_args.push(args[positional_index].clone());
-                            positional_index += 1;
-                        }
-
-                        let element_type = match &param.param_type {
-                            Type::Array(list_type) => list_type.element_type.clone(),
-                            _ => PrimitiveType::any(),
-                        };
-
-                        RuntimeValue::Array(ArrayValue::new(rest_args, element_type))
-                    } else if positional_index < args.len() {
-                        let arg = args[positional_index].clone();
-                        positional_index += 1;
-                        arg
-                    } else if let Some(default_expr) = &param.default_value {
-                        Self::evaluate_expr(interpreter, default_expr).await?
-                    } else {
-                        interpreter.environment.pop_scope();
-                        return Err(RaccoonError::new(
-                            format!("Missing required argument for parameter '{}'", param_name),
-                            (0, 0),
-                            interpreter.file.clone(),
-                        ));
-                    };
-
-                    match &param.pattern {
-                        VarPattern::Identifier(name) => {
-                            interpreter.environment.declare(name.clone(), value)?;
-                        }
-                        VarPattern::Destructuring(pattern) => {
-                            if let Err(e) =
-                                Helpers::destructure_pattern(interpreter, pattern, &value, (0, 0))
-                                    .await
-                            {
-                                interpreter.environment.pop_scope();
-                                return Err(e);
-                            }
-                        }
-                    }
-                }
-
                 interpreter.environment.declare(
                     "this".to_string(),
                     RuntimeValue::ClassInstance(instance.clone()),
                 )?;
+                interpreter.environment.declare(
+                    CLASS_CONTEXT.to_string(),
+                    RuntimeValue::Str(StrValue::new(constructor_owner)),
+                )?;
+
+                if let Err(error) = Helpers::bind_parameters(
+                    interpreter,
+                    &constructor.parameters,
+                    &args,
+                    &named_args,
+                    position,
+                )
+                .await
+                {
+                    interpreter.environment.pop_scope();
+                    return Err(error);
+                }
+                Self::initialize_properties(interpreter, &instance, &initializers).await?;
 
-                for stmt in &constructor.body {
+                interpreter.call_stack.push(crate::runtime::StackFrame::new(
+                    format!("new {}", class.class_name),
+                    position,
+                    interpreter.file.clone(),
+                ));
+                for stmt in constructor.body.iter() {
                     if let Stmt::ExprStmt(expr_stmt) = stmt {
                         if let Expr::Assignment(assign) = &expr_stmt.expression {
                             if let Expr::Member(member) = &*assign.target {
@@ -1729,19 +2152,122 @@ impl Expressions {
                     }
                 }
 
+                interpreter.call_stack.pop();
+                interpreter.environment.pop_scope();
+            } else {
+                interpreter.environment.push_scope();
+                interpreter.environment.declare(
+                    "this".to_string(),
+                    RuntimeValue::ClassInstance(instance.clone()),
+                )?;
+                Self::initialize_properties(interpreter, &instance, &initializers).await?;
                 interpreter.environment.pop_scope();
             }
 
+            instance.finish_construction();
             Ok(RuntimeValue::ClassInstance(instance))
         }
     }
 
+    async fn initialize_properties(
+        interpreter: &mut Interpreter,
+        instance: &ClassInstance,
+        initializers: &[(&str, &Expr)],
+    ) -> Result<(), RaccoonError> {
+        for (name, initializer) in initializers {
+            let value = Self::evaluate_expr(interpreter, initializer).await?;
+            instance
+                .properties
+                .write()
+                .unwrap()
+                .insert(name.to_string(), value);
+        }
+        Ok(())
+    }
+
+    fn check_private_access(
+        interpreter: &Interpreter,
+        instance: &ClassInstance,
+        member: &str,
+        position: Position,
+    ) -> Result<(), RaccoonError> {
+        if !instance.private_members.contains_key(member) {
+            return Ok(());
+        }
+
+        let this = interpreter.environment.get("this", position).ok();
+        let accessor = match &this {
+            Some(RuntimeValue::ClassInstance(this)) => Some(this.as_ref()),
+            _ => None,
+        };
+
+        instance
+            .check_accessible(member, accessor)
+            .map_err(|message| RaccoonError::new(message, position, interpreter.file.clone()))
+    }
+    async fn evaluate_do_expr(
+        interpreter: &mut Interpreter,
+        do_expr: &DoExpr,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let result =
+            super::control_flow::ControlFlow::execute_block_internal(interpreter, &do_expr.body)
+                .await?;
+
+        match result {
+            InterpreterResult::Value(value) => Ok(value),
+            InterpreterResult::Return(_) => Err(RaccoonError::new(
+                "Cannot return from inside a do expression",
+                do_expr.position,
+                interpreter.file.clone(),
+            )),
+            InterpreterResult::Break | InterpreterResult::Continue => Err(RaccoonError::new(
+                "Cannot break or continue out of a do expression",
+                do_expr.position,
+                interpreter.file.clone(),
+            )),
+        }
+    }
+
+    async fn evaluate_switch_expr(
+        interpreter: &mut Interpreter,
+        switch_expr: &SwitchExpr,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let result = super::control_flow::ControlFlow::execute_switch_cases(
+            interpreter,
+            &switch_expr.discriminant,
+            &switch_expr.cases,
+            false,
+            switch_expr.position,
+        )
+        .await?;
+
+        match result {
+            InterpreterResult::Value(value) => Ok(value),
+            _ => Err(RaccoonError::new(
+                "Cannot return from inside a switch expression",
+                switch_expr.position,
+                interpreter.file.clone(),
+            )),
+        }
+    }
+
+
+
     async fn evaluate_tagged_template(
         interpreter: &mut Interpreter,
         tagged: &TaggedTemplateExpr,
     ) -> Result<RuntimeValue, RaccoonError> {
         let tagged_position = tagged.position;
-        let tag = Self::evaluate_expr(interpreter, &tagged.tag).await?;
+        let builtin_tag = match tagged.tag.as_ref() {
+            Expr::Identifier(ident) if !interpreter.environment.exists(&ident.name) => {
+                interpreter.template_tags.get(&ident.name).cloned()
+            }
+            _ => None,
+        };
+        let tag = match builtin_tag {
+            Some(_) => RuntimeValue::Null(NullValue::new()),
+            None => Self::evaluate_expr(interpreter, &tagged.tag).await?,
+        };
 
         let mut strings = Vec::new();
         let mut values = Vec::new();
@@ -1749,18 +2275,30 @@ impl Expressions {
         for part in &tagged.template.parts {
             match part {
                 TemplateStrPart::String(s) => {
-                    strings.push(RuntimeValue::Str(StrValue::new(s.value.clone())));
+                    strings.push(s.value.clone());
                 }
                 TemplateStrPart::Expr(expr) => {
+                    if strings.len() == values.len() {
+                        strings.push(String::new());
+                    }
                     values.push(Self::evaluate_expr(interpreter, expr).await?);
                 }
             }
         }
 
         if strings.len() == values.len() {
-            strings.push(RuntimeValue::Str(StrValue::new(String::new())));
+            strings.push(String::new());
+        }
+
+        if let Some(spec) = builtin_tag {
+            return Ok(spec.apply(strings, values));
         }
 
+        let strings = strings
+            .into_iter()
+            .map(|s| RuntimeValue::Str(StrValue::new(s)))
+            .collect();
+
         let strings_list = RuntimeValue::Array(ArrayValue::new(strings, PrimitiveType::str()));
 
         let mut args = vec![strings_list];
@@ -1768,97 +2306,79 @@ impl Expressions {
 
         match tag {
             RuntimeValue::Function(func) => {
+                if let Some(bound) = &func.bound {
+                    return Helpers::call_bound(
+                        interpreter,
+                        bound,
+                        args,
+                        HashMap::new(),
+                        tagged_position,
+                    )
+                    .await;
+                }
+                let func = Helpers::select_overload(interpreter, &func, &args, 0, tagged_position)?;
+                if let Some(decorated) = &func.decorated {
+                    return NativeDecorators::call(
+                        interpreter,
+                        decorated,
+                        args,
+                        HashMap::new(),
+                        tagged_position,
+                    )
+                    .await;
+                }
                 interpreter.environment.push_scope();
 
-                let mut positional_index = 0;
-
-                for (i, param) in func.parameters.iter().enumerate() {
-                    let param_name = match &param.pattern {
-                        VarPattern::Identifier(name) => name.clone(),
-                        VarPattern::Destructuring(_) => {
-                            format!("__param_{}", i)
-                        }
-                    };
-
-                    let value = if param.is_rest {
-                        let mut rest_args = Vec::new();
-                        while positional_index < args.len() {
-                            rest_args.push(args[positional_index].clone());
-                            positional_index += 1;
-                        }
-
-                        let element_type = match &param.param_type {
-                            Type::Array(list_type) => list_type.element_type.clone(),
-                            _ => PrimitiveType::any(),
-                        };
-
-                        RuntimeValue::Array(ArrayValue::new(rest_args, element_type))
-                    } else if positional_index < args.len() {
-                        let arg = args[positional_index].clone();
-                        positional_index += 1;
-                        arg
-                    } else if let Some(default_expr) = &param.default_value {
-                        Self::evaluate_expr(interpreter, default_expr).await?
-                    } else if param.is_optional {
-                        RuntimeValue::Null(crate::runtime::values::NullValue)
-                    } else {
-                        interpreter.environment.pop_scope();
-                        return Err(RaccoonError::new(
-                            format!("Missing required argument for parameter '{}'", param_name),
-                            tagged_position,
-                            interpreter.file.clone(),
-                        ));
-                    };
-
-                    match &param.pattern {
-                        VarPattern::Identifier(name) => {
-                            interpreter.environment.declare(name.clone(), value)?;
-                        }
-                        VarPattern::Destructuring(pattern) => {
-                            if let Err(e) =
-                                Helpers::destructure_pattern(interpreter, pattern, &value, (0, 0))
-                                    .await
-                            {
-                                interpreter.environment.pop_scope();
-                                return Err(e);
-                            }
-                        }
-                    }
+                if let Err(error) = Helpers::bind_parameters(
+                    interpreter,
+                    &func.parameters,
+                    &args,
+                    &HashMap::new(),
+                    tagged_position,
+                )
+                .await
+                {
+                    interpreter.environment.pop_scope();
+                    return Err(error);
                 }
 
+                interpreter.call_stack.push(crate::runtime::StackFrame::new(
+                    func.name
+                        .clone()
+                        .unwrap_or_else(|| "<anonymous>".to_string()),
+                    tagged_position,
+                    interpreter.file.clone(),
+                ));
                 let mut result = RuntimeValue::Null(NullValue::new());
-                for stmt in &func.body {
+                for stmt in func.body.iter() {
                     match interpreter.execute_stmt_internal(stmt).await? {
                         InterpreterResult::Value(v) => result = v,
                         InterpreterResult::Return(v) => {
+                            interpreter.call_stack.pop();
                             interpreter.environment.pop_scope();
                             return Ok(v);
                         }
                         _ => {
+                            interpreter.call_stack.pop();
                             interpreter.environment.pop_scope();
                             return Err(RaccoonError::new(
                                 "Unexpected break/continue in function".to_string(),
-                                (0, 0),
+                                stmt.position(),
                                 interpreter.file.clone(),
                             ));
                         }
                     }
                 }
 
+                interpreter.call_stack.pop();
                 interpreter.environment.pop_scope();
                 Ok(result)
             }
             RuntimeValue::NativeFunction(func) => Ok((func.implementation)(args)),
             RuntimeValue::NativeAsyncFunction(func) => {
+                interpreter.check_native_call(&func, &args, tagged_position)?;
                 let result = (func.implementation)(args).await;
-                let return_type = match &func.fn_type {
-                    Type::Function(fn_type) => fn_type.return_type.clone(),
-                    _ => PrimitiveType::any(),
-                };
-                Ok(RuntimeValue::Future(FutureValue::new_resolved(
-                    result,
-                    return_type,
-                )))
+                Ok(Helpers::native_async_result(result, &func.fn_type))
             }
             _ => Err(RaccoonError::new(
                 "Tagged template tag must be a function".to_string(),
@@ -1873,28 +2393,38 @@ impl Expressions {
         interpreter: &mut Interpreter,
         method_call: &MethodCallExpr,
     ) -> Result<RuntimeValue, RaccoonError> {
-        let mut object = Self::evaluate_expr(interpreter, &method_call.object).await?;
-
-        let mut args = Vec::new();
-        for arg in &method_call.args {
-            args.push(Self::evaluate_expr(interpreter, arg).await?);
+        if matches!(method_call.object.as_ref(), Expr::Super(_)) {
+            return Self::evaluate_super_method_call(interpreter, method_call).await;
         }
 
-        let var_info = if let Expr::Identifier(ident) = method_call.object.as_ref() {
-            Some((ident.name.clone(), ident.position))
-        } else {
-            None
-        };
+        if let Expr::Identifier(ident) = method_call.object.as_ref() {
+            if ident.name == "process"
+                && ProcessHooks::METHODS.contains(&method_call.method.as_str())
+                && !interpreter.environment.exists(&ident.name)
+            {
+                let args = Self::evaluate_arguments(interpreter, &method_call.args).await?;
+                return ProcessHooks::call(
+                    interpreter,
+                    &method_call.method,
+                    args,
+                    method_call.position,
+                )
+                .await;
+            }
+        }
 
-        let member_info = if let Expr::Member(member_expr) = method_call.object.as_ref() {
-            if let Expr::This(_) = member_expr.object.as_ref() {
-                Some(member_expr.property.clone())
-            } else {
-                None
+        let mut object = Self::evaluate_expr(interpreter, &method_call.object).await?;
+        if let RuntimeValue::Range(range) = &object {
+            if !interpreter
+                .type_registry
+                .has_instance_method("range", &method_call.method)
+            {
+                object = RuntimeValue::Array(range.to_list());
             }
-        } else {
-            None
-        };
+        }
+
+        let mut args = Self::evaluate_arguments(interpreter, &method_call.args).await?;
+        let named_args = Self::evaluate_named_args(interpreter, &method_call.named_args).await?;
 
         let result = match &mut object {
             RuntimeValue::Class(class) => {
@@ -1904,69 +2434,30 @@ impl Expressions {
                     let is_async = static_method.is_async;
                     let fn_type = static_method.fn_type.clone();
 
-                    let mut positional_index = 0;
-
-                    for (i, param) in static_method.parameters.iter().enumerate() {
-                        let param_name = match &param.pattern {
-                            VarPattern::Identifier(name) => name.clone(),
-                            VarPattern::Destructuring(_) => {
-                                format!("__param_{}", i)
-                            }
-                        };
-
-                        let value = if param.is_rest {
-                            let mut rest_args = Vec::new();
-                            while positional_index < args.len() {
-                                rest_args.push(args[positional_index].clone());
-                                positional_index += 1;
-                            }
-
-                            let element_type = match &param.param_type {
-                                Type::Array(list_type) => list_type.element_type.clone(),
-                                _ => PrimitiveType::any(),
-                            };
-
-                            RuntimeValue::Array(ArrayValue::new(rest_args, element_type))
-                        } else if positional_index < args.len() {
-                            let arg = args[positional_index].clone();
-                            positional_index += 1;
-                            arg
-                        } else if let Some(default_expr) = &param.default_value {
-                            Self::evaluate_expr(interpreter, default_expr).await?
-                        } else {
-                            interpreter.environment.pop_scope();
-                            return Err(RaccoonError::new(
-                                format!("Missing required argument for parameter '{}'", param_name),
-                                (0, 0),
-                                interpreter.file.clone(),
-                            ));
-                        };
-
-                        match &param.pattern {
-                            VarPattern::Identifier(name) => {
-                                interpreter.environment.declare(name.clone(), value)?;
-                            }
-                            VarPattern::Destructuring(pattern) => {
-                                if let Err(e) = Helpers::destructure_pattern(
-                                    interpreter,
-                                    pattern,
-                                    &value,
-                                    (0, 0),
-                                )
-                                .await
-                                {
-                                    interpreter.environment.pop_scope();
-                                    return Err(e);
-                                }
-                            }
-                        }
+                    if let Err(error) = Helpers::bind_parameters(
+                        interpreter,
+                        &static_method.parameters,
+                        &args,
+                        &named_args,
+                        method_call.position,
+                    )
+                    .await
+                    {
+                        interpreter.environment.pop_scope();
+                        return Err(error);
                     }
 
+                    interpreter.call_stack.push(crate::runtime::StackFrame::new(
+                        format!("{}.{}", class.class_name, method_call.method),
+                        method_call.position,
+                        interpreter.file.clone(),
+                    ));
                     let mut result = RuntimeValue::Null(NullValue::new());
-                    for stmt in &static_method.body {
+                    for stmt in static_method.body.iter() {
                         match interpreter.execute_stmt_internal(stmt).await? {
                             InterpreterResult::Value(v) => result = v,
                             InterpreterResult::Return(v) => {
+                                interpreter.call_stack.pop();
                                 interpreter.environment.pop_scope();
 
                                 if is_async {
@@ -1983,16 +2474,18 @@ impl Expressions {
                                 }
                             }
                             _ => {
+                                interpreter.call_stack.pop();
                                 interpreter.environment.pop_scope();
                                 return Err(RaccoonError::new(
                                     "Unexpected break/continue in function".to_string(),
-                                    (0, 0),
+                                    stmt.position(),
                                     interpreter.file.clone(),
                                 ));
                             }
                         }
                     }
 
+                    interpreter.call_stack.pop();
                     interpreter.environment.pop_scope();
 
                     if is_async {
@@ -2020,8 +2513,22 @@ impl Expressions {
             }
 
             RuntimeValue::PrimitiveTypeObject(type_obj) => {
+                if type_obj.type_name == "Future" && method_call.method == "allLimit" {
+                    return Ok(Builtins::future_all_limit(
+                        interpreter,
+                        args,
+                        method_call.position,
+                    ));
+                }
+
                 if let Some(static_method) = type_obj.static_methods.get(&method_call.method) {
-                    
+                    let args = Self::bind_native_args(
+                        interpreter,
+                        &static_method.fn_type,
+                        args,
+                        named_args,
+                        method_call.position,
+                    )?;
                     Ok((static_method.implementation)(args))
                 } else {
                     Err(RaccoonError::new(
@@ -2036,18 +2543,69 @@ impl Expressions {
             }
 
             RuntimeValue::Type(type_obj) => {
+                if type_obj.name() == "Object" && method_call.method == "clone" {
+                    return Builtins::object_clone(interpreter, args, method_call.position).await;
+                }
+                if type_obj.name() == "reflect" {
+                    return Builtins::reflect(
+                        interpreter,
+                        &method_call.method,
+                        args,
+                        method_call.position,
+                    )
+                    .await;
+                }
+                if let Some(result) = EnumType::call_enum_method(
+                    type_obj,
+                    &method_call.method,
+                    &args,
+                    method_call.position,
+                    interpreter.file.clone(),
+                ) {
+                    return result;
+                }
+                if type_obj.name() == "Array" && method_call.method == "from" {
+                    if let Some(source) = args.first().cloned() {
+                        let collected =
+                            Iteration::collect(interpreter, source, method_call.position).await?;
+                        if let Some(elements) = collected {
+                            let list = ArrayValue::new(elements, PrimitiveType::any());
+                            args[0] = RuntimeValue::Array(list);
+                        }
+                    }
+                }
+
+                let handled = interpreter
+                    .type_registry
+                    .has_static_method(&type_obj.name(), &method_call.method);
                 if let Some(static_method) = type_obj.get_static_method(&method_call.method) {
                     
                     match static_method {
                         RuntimeValue::NativeFunction(native_fn) => {
+                            let args = Self::bind_native_args(
+                                interpreter,
+                                &native_fn.fn_type,
+                                args,
+                                named_args,
+                                method_call.position,
+                            )?;
+                            if handled {
+                                return interpreter.type_registry.call_static_method(
+                                    &type_obj.name(),
+                                    &method_call.method,
+                                    args,
+                                    method_call.position,
+                                    interpreter.file.clone(),
+                                );
+                            }
                             Ok((native_fn.implementation)(args))
                         }
                         RuntimeValue::Function(_) => {
-                            
-                            Helpers::call_function(
+                            Helpers::call_function_with_named_args(
                                 interpreter,
                                 static_method,
                                 args,
+                                named_args,
                                 method_call.position,
                             )
                             .await
@@ -2062,6 +2620,27 @@ impl Expressions {
                             interpreter.file.clone(),
                         )),
                     }
+                } else if handled {
+                    interpreter.type_registry.call_static_method(
+                        &type_obj.name(),
+                        &method_call.method,
+                        args,
+                        method_call.position,
+                        interpreter.file.clone(),
+                    )
+                } else if let Some(type_handler) = interpreter
+                    .type_registry
+                    .get_handler("Type")
+                    .filter(|handler| handler.has_instance_method(&method_call.method))
+                {
+                    // Queries on the type itself, such as `name()` or `getMethods()`.
+                    type_handler.call_instance_method(
+                        &mut object,
+                        &method_call.method,
+                        args,
+                        method_call.position,
+                        interpreter.file.clone(),
+                    )
                 } else {
                     Err(RaccoonError::new(
                         format!(
@@ -2076,17 +2655,7 @@ impl Expressions {
             }
 
             RuntimeValue::Array(_) => {
-                if matches!(
-                    method_call.method.as_str(),
-                    "map"
-                        | "filter"
-                        | "reduce"
-                        | "forEach"
-                        | "find"
-                        | "findIndex"
-                        | "some"
-                        | "every"
-                ) {
+                if Builtins::FUNCTIONAL_METHODS.contains(&method_call.method.as_str()) {
                     Builtins::handle_array_functional_method(
                         interpreter,
                         &mut object,
@@ -2107,10 +2676,13 @@ impl Expressions {
             }
             RuntimeValue::Str(_)
             | RuntimeValue::Map(_)
+            | RuntimeValue::Tuple(_)
+            | RuntimeValue::Range(_)
             | RuntimeValue::Int(_)
             | RuntimeValue::Float(_)
             | RuntimeValue::Decimal(_)
-            | RuntimeValue::Bool(_) => interpreter.type_registry.call_instance_method(
+            | RuntimeValue::Bool(_)
+            | RuntimeValue::Symbol(_) => interpreter.type_registry.call_instance_method(
                 &mut object,
                 &method_call.method,
                 args,
@@ -2118,80 +2690,65 @@ impl Expressions {
                 interpreter.file.clone(),
             ),
             RuntimeValue::Object(obj) => {
-                if let Some(method) = obj.properties.get(&method_call.method) {
+                if let Some(method) = obj.get(&method_call.method) {
                     match method {
                         RuntimeValue::Function(func) => {
+                            if let Some(bound) = &func.bound {
+                                return Helpers::call_bound(
+                                    interpreter,
+                                    bound,
+                                    args,
+                                    named_args,
+                                    method_call.position,
+                                )
+                                .await;
+                            }
+                            let func = Helpers::select_overload(
+                                interpreter,
+                                &func,
+                                &args,
+                                named_args.len(),
+                                method_call.position,
+                            )?;
+                            if let Some(decorated) = &func.decorated {
+                                return NativeDecorators::call(
+                                    interpreter,
+                                    decorated,
+                                    args,
+                                    named_args,
+                                    method_call.position,
+                                )
+                                .await;
+                            }
                             interpreter.environment.push_scope();
 
                             let is_async = func.is_async;
                             let fn_type = func.fn_type.clone();
 
-                            let mut positional_index = 0;
-
-                            for (i, param) in func.parameters.iter().enumerate() {
-                                let param_name = match &param.pattern {
-                                    VarPattern::Identifier(name) => name.clone(),
-                                    VarPattern::Destructuring(_) => {
-                                        format!("__param_{}", i)
-                                    }
-                                };
-
-                                let value = if param.is_rest {
-                                    let mut rest_args = Vec::new();
-                                    while positional_index < args.len() {
-                                        rest_args.push(args[positional_index].clone());
-                                        positional_index += 1;
-                                    }
-
-                                    let element_type = match &param.param_type {
-                                        Type::Array(list_type) => list_type.element_type.clone(),
-                                        _ => PrimitiveType::any(),
-                                    };
-
-                                    RuntimeValue::Array(ArrayValue::new(rest_args, element_type))
-                                } else if positional_index < args.len() {
-                                    let arg = args[positional_index].clone();
-                                    positional_index += 1;
-                                    arg
-                                } else if let Some(default_expr) = &param.default_value {
-                                    Self::evaluate_expr(interpreter, default_expr).await?
-                                } else {
-                                    interpreter.environment.pop_scope();
-                                    return Err(RaccoonError::new(
-                                        format!(
-                                            "Missing required argument for parameter '{}'",
-                                            param_name
-                                        ),
-                                        (0, 0),
-                                        interpreter.file.clone(),
-                                    ));
-                                };
-
-                                match &param.pattern {
-                                    VarPattern::Identifier(name) => {
-                                        interpreter.environment.declare(name.clone(), value)?;
-                                    }
-                                    VarPattern::Destructuring(pattern) => {
-                                        if let Err(e) = Helpers::destructure_pattern(
-                                            interpreter,
-                                            pattern,
-                                            &value,
-                                            (0, 0),
-                                        )
-                                        .await
-                                        {
-                                            interpreter.environment.pop_scope();
-                                            return Err(e);
-                                        }
-                                    }
-                                }
+                            if let Err(error) = Helpers::bind_parameters(
+                                interpreter,
+                                &func.parameters,
+                                &args,
+                                &named_args,
+                                method_call.position,
+                            )
+                            .await
+                            {
+                                interpreter.environment.pop_scope();
+                                return Err(error);
                             }
 
+                            interpreter.call_stack.push(crate::runtime::StackFrame::new(
+                                method_call.method.clone(),
+                                method_call.position,
+                                interpreter.file.clone(),
+                            ));
                             let mut result = RuntimeValue::Null(NullValue::new());
-                            for stmt in &func.body {
+                            for stmt in func.body.iter() {
                                 match interpreter.execute_stmt_internal(stmt).await? {
                                     InterpreterResult::Value(v) => result = v,
                                     InterpreterResult::Return(v) => {
+                                        interpreter.call_stack.pop();
                                         interpreter.environment.pop_scope();
 
                                         if is_async {
@@ -2207,16 +2764,18 @@ impl Expressions {
                                         }
                                     }
                                     _ => {
+                                        interpreter.call_stack.pop();
                                         interpreter.environment.pop_scope();
                                         return Err(RaccoonError::new(
                                             "Unexpected break/continue in function".to_string(),
-                                            (0, 0),
+                                            stmt.position(),
                                             interpreter.file.clone(),
                                         ));
                                     }
                                 }
                             }
 
+                            interpreter.call_stack.pop();
                             interpreter.environment.pop_scope();
 
                             if is_async {
@@ -2234,16 +2793,9 @@ impl Expressions {
                         }
                         RuntimeValue::NativeFunction(func) => Ok((func.implementation)(args)),
                         RuntimeValue::NativeAsyncFunction(func) => {
+                            interpreter.check_native_call(&func, &args, method_call.position)?;
                             let result = (func.implementation)(args).await;
-
-                            let return_type = match &func.fn_type {
-                                Type::Function(ft) => ft.return_type.clone(),
-                                _ => PrimitiveType::any(),
-                            };
-                            Ok(RuntimeValue::Future(FutureValue::new_resolved(
-                                result,
-                                return_type,
-                            )))
+                            Ok(Helpers::native_async_result(result, &func.fn_type))
                         }
                         _ => Err(RaccoonError::new(
                             format!("Property '{}' is not a function", method_call.method),
@@ -2265,79 +2817,53 @@ impl Expressions {
             }
             RuntimeValue::ClassInstance(instance) => {
                 if let Some(method) = instance.methods.get(&method_call.method) {
+                    Self::check_private_access(
+                        interpreter,
+                        instance,
+                        &method_call.method,
+                        method_call.position,
+                    )?;
+
                     interpreter.environment.push_scope();
 
                     interpreter.environment.declare(
                         "this".to_string(),
                         RuntimeValue::ClassInstance(instance.clone()),
                     )?;
+                    if let Some(owner) = instance.method_owners.get(&method_call.method) {
+                        interpreter.environment.declare(
+                            CLASS_CONTEXT.to_string(),
+                            RuntimeValue::Str(StrValue::new(owner.clone())),
+                        )?;
+                    }
 
                     let is_async = method.is_async;
                     let fn_type = method.fn_type.clone();
 
-                    let mut positional_index = 0;
-
-                    for (i, param) in method.parameters.iter().enumerate() {
-                        let param_name = match &param.pattern {
-                            VarPattern::Identifier(name) => name.clone(),
-                            VarPattern::Destructuring(_) => {
-                                format!("__param_{}", i)
-                            }
-                        };
-
-                        let value = if param.is_rest {
-                            let mut rest_args = Vec::new();
-                            while positional_index < args.len() {
-                                rest_args.push(args[positional_index].clone());
-                                positional_index += 1;
-                            }
-
-                            let element_type = match &param.param_type {
-                                Type::Array(list_type) => list_type.element_type.clone(),
-                                _ => PrimitiveType::any(),
-                            };
-
-                            RuntimeValue::Array(ArrayValue::new(rest_args, element_type))
-                        } else if positional_index < args.len() {
-                            let arg = args[positional_index].clone();
-                            positional_index += 1;
-                            arg
-                        } else if let Some(default_expr) = &param.default_value {
-                            Self::evaluate_expr(interpreter, default_expr).await?
-                        } else {
-                            interpreter.environment.pop_scope();
-                            return Err(RaccoonError::new(
-                                format!("Missing required argument for parameter '{}'", param_name),
-                                (0, 0),
-                                interpreter.file.clone(),
-                            ));
-                        };
-
-                        match &param.pattern {
-                            VarPattern::Identifier(name) => {
-                                interpreter.environment.declare(name.clone(), value)?;
-                            }
-                            VarPattern::Destructuring(pattern) => {
-                                if let Err(e) = Helpers::destructure_pattern(
-                                    interpreter,
-                                    pattern,
-                                    &value,
-                                    (0, 0),
-                                )
-                                .await
-                                {
-                                    interpreter.environment.pop_scope();
-                                    return Err(e);
-                                }
-                            }
-                        }
+                    if let Err(error) = Helpers::bind_parameters(
+                        interpreter,
+                        &method.parameters,
+                        &args,
+                        &named_args,
+                        method_call.position,
+                    )
+                    .await
+                    {
+                        interpreter.environment.pop_scope();
+                        return Err(error);
                     }
 
+                    interpreter.call_stack.push(crate::runtime::StackFrame::new(
+                        format!("{}.{}", instance.class_name, method_call.method),
+                        method_call.position,
+                        interpreter.file.clone(),
+                    ));
                     let mut result = RuntimeValue::Null(NullValue::new());
-                    for stmt in &method.body {
+                    for stmt in method.body.iter() {
                         match interpreter.execute_stmt_internal(stmt).await? {
                             InterpreterResult::Value(v) => result = v,
                             InterpreterResult::Return(v) => {
+                                interpreter.call_stack.pop();
                                 interpreter.environment.pop_scope();
 
                                 if is_async {
@@ -2354,16 +2880,18 @@ impl Expressions {
                                 }
                             }
                             _ => {
+                                interpreter.call_stack.pop();
                                 interpreter.environment.pop_scope();
                                 return Err(RaccoonError::new(
                                     "Unexpected break/continue in function".to_string(),
-                                    (0, 0),
+                                    stmt.position(),
                                     interpreter.file.clone(),
                                 ));
                             }
                         }
                     }
 
+                    interpreter.call_stack.pop();
                     interpreter.environment.pop_scope();
 
                     if is_async {
@@ -2391,202 +2919,15 @@ impl Expressions {
             }
             RuntimeValue::Future(future) => {
                 match method_call.method.as_str() {
-                    "then" => {
-                        if args.is_empty() {
-                            return Err(RaccoonError::new(
-                                "Future.then() requires at least one callback".to_string(),
-                                method_call.position,
-                                interpreter.file.clone(),
-                            ));
-                        }
-
-                        let on_fulfilled = args[0].clone();
-                        let on_rejected = args.get(1).cloned();
-
-                        
-                        let state = future.state.read().unwrap().clone();
-                        drop(state);
-
-                        
-                        let new_future = FutureValue::new(PrimitiveType::any());
-                        let new_future_clone = new_future.clone();
-
-                        
-                        let state = future.state.read().unwrap().clone();
-                        match state {
-                            FutureState::Resolved(value) => {
-                                
-                                match on_fulfilled {
-                                    RuntimeValue::Function(_) | RuntimeValue::NativeFunction(_) => {
-                                        let call_result = Helpers::call_function(
-                                            interpreter,
-                                            &on_fulfilled,
-                                            vec![*value],
-                                            method_call.position,
-                                        )
-                                        .await?;
-
-                                        
-                                        
-                                        new_future_clone.resolve(call_result);
-                                    }
-                                    _ => {
-                                        return Err(RaccoonError::new(
-                                            "Future.then() callback must be a function".to_string(),
-                                            method_call.position,
-                                            interpreter.file.clone(),
-                                        ));
-                                    }
-                                }
-                            }
-                            FutureState::Rejected(error) => {
-                                if let Some(on_rejected_cb) = on_rejected {
-                                    match on_rejected_cb {
-                                        RuntimeValue::Function(_) | RuntimeValue::NativeFunction(_) => {
-                                            let error_value = RuntimeValue::Str(StrValue::new(error));
-                                            let call_result = Helpers::call_function(
-                                                interpreter,
-                                                &on_rejected_cb,
-                                                vec![error_value],
-                                                method_call.position,
-                                            )
-                                            .await?;
-                                            new_future_clone.resolve(call_result);
-                                        }
-                                        _ => {
-                                            return Err(RaccoonError::new(
-                                                "Future.then() rejection callback must be a function".to_string(),
-                                                method_call.position,
-                                                interpreter.file.clone(),
-                                            ));
-                                        }
-                                    }
-                                } else {
-                                    
-                                    new_future_clone.reject(error);
-                                }
-                            }
-                            FutureState::Pending => {
-                                return Err(RaccoonError::new(
-                                    "Cannot call .then() on a pending Future. Use 'await' to wait for the Future to resolve.".to_string(),
-                                    method_call.position,
-                                    interpreter.file.clone(),
-                                ));
-                            }
-                        }
-
-                        Ok(RuntimeValue::Future(new_future))
-                    }
-                    "catch" => {
-                        if args.is_empty() {
-                            return Err(RaccoonError::new(
-                                "Future.catch() requires a callback".to_string(),
-                                method_call.position,
-                                interpreter.file.clone(),
-                            ));
-                        }
-
-                        let on_rejected = args[0].clone();
-
-                        
-                        let new_future = FutureValue::new(PrimitiveType::any());
-                        let new_future_clone = new_future.clone();
-
-                        let state = future.state.read().unwrap().clone();
-                        match state {
-                            FutureState::Resolved(value) => {
-                                
-                                new_future_clone.resolve(*value);
-                            }
-                            FutureState::Rejected(error) => {
-                                
-                                match on_rejected {
-                                    RuntimeValue::Function(_) | RuntimeValue::NativeFunction(_) => {
-                                        let error_value = RuntimeValue::Str(StrValue::new(error));
-                                        let call_result = Helpers::call_function(
-                                            interpreter,
-                                            &on_rejected,
-                                            vec![error_value],
-                                            method_call.position,
-                                        )
-                                        .await?;
-                                        new_future_clone.resolve(call_result);
-                                    }
-                                    _ => {
-                                        return Err(RaccoonError::new(
-                                            "Future.catch() callback must be a function".to_string(),
-                                            method_call.position,
-                                            interpreter.file.clone(),
-                                        ));
-                                    }
-                                }
-                            }
-                            FutureState::Pending => {
-                                return Err(RaccoonError::new(
-                                    "Cannot call .catch() on a pending Future. Use 'await' to wait for the Future to resolve.".to_string(),
-                                    method_call.position,
-                                    interpreter.file.clone(),
-                                ));
-                            }
-                        }
-
-                        Ok(RuntimeValue::Future(new_future))
-                    }
-                    "finally" => {
-                        if args.is_empty() {
-                            return Err(RaccoonError::new(
-                                "Future.finally() requires a callback".to_string(),
-                                method_call.position,
-                                interpreter.file.clone(),
-                            ));
-                        }
-
-                        let on_finally = args[0].clone();
-
-                        
-                        let new_future = FutureValue::new(PrimitiveType::any());
-                        let new_future_clone = new_future.clone();
-
-                        let state = future.state.read().unwrap().clone();
-
-                        
-                        match on_finally {
-                            RuntimeValue::Function(_) | RuntimeValue::NativeFunction(_) => {
-                                let _ = Helpers::call_function(
-                                    interpreter,
-                                    &on_finally,
-                                    vec![],
-                                    method_call.position,
-                                )
-                                .await?;
-                            }
-                            _ => {
-                                return Err(RaccoonError::new(
-                                    "Future.finally() callback must be a function".to_string(),
-                                    method_call.position,
-                                    interpreter.file.clone(),
-                                ));
-                            }
-                        }
-
-                        
-                        match state {
-                            FutureState::Resolved(value) => {
-                                new_future_clone.resolve(*value);
-                            }
-                            FutureState::Rejected(error) => {
-                                new_future_clone.reject(error);
-                            }
-                            FutureState::Pending => {
-                                return Err(RaccoonError::new(
-                                    "Cannot call .finally() on a pending Future. Use 'await' to wait for the Future to resolve.".to_string(),
-                                    method_call.position,
-                                    interpreter.file.clone(),
-                                ));
-                            }
-                        }
-
-                        Ok(RuntimeValue::Future(new_future))
+                    "then" | "catch" | "finally" => {
+                        Builtins::chain_future(
+                            interpreter,
+                            future,
+                            &method_call.method,
+                            args,
+                            method_call.position,
+                        )
+                        .await
                     }
                     "tap" => {
                         if args.is_empty() {
@@ -2721,6 +3062,46 @@ impl Expressions {
                     )),
                 }
             }
+            RuntimeValue::Function(_)
+            | RuntimeValue::NativeFunction(_)
+            | RuntimeValue::NativeAsyncFunction(_) => match method_call.method.as_str() {
+                "call" | "apply" => {
+                    let bound = FunctionType::invocation(
+                        &object,
+                        &method_call.method,
+                        args,
+                        method_call.position,
+                        interpreter.file.clone(),
+                    )?;
+                    Helpers::call_bound(
+                        interpreter,
+                        &bound,
+                        Vec::new(),
+                        named_args,
+                        method_call.position,
+                    )
+                    .await
+                }
+                _ => match interpreter.type_registry.get_handler("function") {
+                    Some(handler) => handler.call_instance_method(
+                        &mut object,
+                        &method_call.method,
+                        args,
+                        method_call.position,
+                        interpreter.file.clone(),
+                    ),
+                    None => Err(RaccoonError::new(
+                        format!("Method '{}' not found on function", method_call.method),
+                        method_call.position,
+                        interpreter.file.clone(),
+                    )),
+                },
+            },
+            RuntimeValue::Dynamic(value) => value
+                .call_method(&method_call.method, args)
+                .map_err(|message| {
+                    RaccoonError::new(message, method_call.position, interpreter.file.clone())
+                }),
             _ => Err(RaccoonError::new(
                 format!("Method '{}' not found on type", method_call.method),
                 method_call.position,
@@ -2728,28 +3109,6 @@ impl Expressions {
             )),
         };
 
-        let should_update = matches!(object, RuntimeValue::Array(_) | RuntimeValue::Map(_));
-
-        if should_update {
-            if let Some((name, position)) = var_info {
-                interpreter
-                    .environment
-                    .assign(&name, object.clone(), position)?;
-            }
-
-            if let Some(property_name) = member_info {
-                if let Ok(RuntimeValue::ClassInstance(instance)) =
-                    interpreter.environment.get("this", method_call.position)
-                {
-                    instance
-                        .properties
-                        .write()
-                        .unwrap()
-                        .insert(property_name, object);
-                }
-            }
-        }
-
         result
     }
 
@@ -2761,15 +3120,26 @@ impl Expressions {
 
         match future_value {
             RuntimeValue::Future(future) => {
-                
-                match future.wait_for_completion().await {
-                    Ok(value) => Ok(value),
-                    Err(error) => Err(RaccoonError::new(
-                        format!("Future rejected: {}", error),
+                let completion = match &interpreter.sandbox {
+                    Some(sandbox) => {
+                        sandbox
+                            .wait(
+                                future.wait_for_completion(),
+                                await_expr.position,
+                                &interpreter.file,
+                            )
+                            .await?
+                    }
+                    None => future.wait_for_completion().await,
+                };
+                completion.map_err(|rejection| {
+                    Declarations::rejected_error(
+                        &interpreter.environment,
+                        rejection,
                         await_expr.position,
                         interpreter.file.clone(),
-                    )),
-                }
+                    )
+                })
             }
             _ => Err(RaccoonError::new(
                 format!("Cannot await non-future value: {}", future_value.get_name()),
@@ -2875,10 +3245,13 @@ impl Expressions {
                 }
             }
 
-            Pattern::Array(patterns) => {
-                
-                if let RuntimeValue::Array(list_val) = value {
-                    let elements = &list_val.elements;
+            Pattern::Array(patterns) | Pattern::Tuple(patterns) => {
+                let elements = match (pattern, value) {
+                    (Pattern::Array(_), RuntimeValue::Array(list_val)) => Some(list_val.snapshot()),
+                    (Pattern::Tuple(_), RuntimeValue::Tuple(tuple)) => Some(tuple.elements.clone()),
+                    _ => None,
+                };
+                if let Some(elements) = elements {
                     if elements.len() != patterns.len() {
                         return Ok(None); 
                     }
@@ -2910,7 +3283,7 @@ impl Expressions {
 
                     
                     for (key, pattern) in properties {
-                        if let Some(property_value) = obj_val.properties.get(key) {
+                        if let Some(property_value) = obj_val.properties.read().unwrap().get(key) {
                             if let Some(bindings) = Self::match_pattern(pattern, property_value)? {
                                 
                                 for (name, val) in bindings {
@@ -2983,6 +3356,7 @@ impl Expressions {
                         .collect(),
                     return_type,
                     is_variadic: method.parameters.iter().any(|p| p.is_rest),
+                    param_names: Vec::new(),
                 }));
 
                 let function = FunctionValue::new(
@@ -3011,13 +3385,17 @@ impl Expressions {
             name: synthetic_name.clone(),
             type_parameters: class_expr.type_parameters.clone(),
             superclass: class_expr.superclass.clone(),
+            implements: class_expr.implements.clone(),
             properties: class_expr.properties.clone(),
             constructor: class_expr.constructor.clone(),
             methods: class_expr.methods.clone(),
             accessors: class_expr.accessors.clone(),
+            static_blocks: Vec::new(),
             decorators: Vec::new(),
+            doc: None,
             position: class_expr.position,
         };
+        let class_decl = Declarations::with_default_methods(interpreter, &class_decl).into_owned();
 
         let class_type = Type::Primitive(crate::ast::types::PrimitiveType::new(
             crate::ast::types::TypeKind::Class,
@@ -3025,12 +3403,12 @@ impl Expressions {
         ));
 
         
-        Ok(RuntimeValue::Class(ClassValue::with_properties(
+        Ok(RuntimeValue::Class(Arc::new(ClassValue::with_properties(
             synthetic_name,
             static_methods,
             static_properties,
             class_type,
             class_decl,
-        )))
+        ))))
     }
 }
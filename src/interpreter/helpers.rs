@@ -1,12 +1,18 @@
 use crate::ast::nodes::*;
-use crate::ast::types::PrimitiveType;
+use crate::ast::types::{PrimitiveType, Type};
 use crate::error::RaccoonError;
-use crate::runtime::{FutureValue, ArrayValue, NullValue, ObjectValue, RuntimeValue};
+use crate::runtime::types::operations::overloading::{self, Signature};
+use crate::runtime::{
+    ArrayValue, BoundCall, ClassInstance, FunctionValue, FutureValue, MapKey, NullValue,
+    ObjectValue, RuntimeValue, StackFrame, StrValue, TupleValue,
+};
 use crate::tokens::Position;
 use async_recursion::async_recursion;
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use super::{Interpreter, InterpreterResult};
+use super::decorators::NativeDecorators;
+use super::{Interpreter, InterpreterResult, CLASS_CONTEXT};
 
 pub struct Helpers;
 
@@ -35,8 +41,28 @@ impl Helpers {
         value: &RuntimeValue,
         position: Position,
     ) -> Result<(), RaccoonError> {
+        if let RuntimeValue::Range(range) = value {
+            let list = RuntimeValue::Array(range.to_list());
+            return Self::destructure_array_pattern(interpreter, pattern, &list, position).await;
+        }
+
         let elements = match value {
-            RuntimeValue::Array(list) => &list.elements,
+            RuntimeValue::Array(list) => list.snapshot(),
+            RuntimeValue::Tuple(tuple) => {
+                let bindings = pattern.elements.len();
+                let arity = tuple.elements.len();
+                if arity != bindings && (pattern.rest.is_none() || arity < bindings) {
+                    return Err(RaccoonError::new(
+                        format!(
+                            "Cannot destructure a tuple of {} elements into {} bindings",
+                            arity, bindings
+                        ),
+                        position,
+                        interpreter.file.clone(),
+                    ));
+                }
+                tuple.elements.clone()
+            }
             _ => {
                 return Err(RaccoonError::new(
                     format!("Cannot destructure non-list value"),
@@ -88,7 +114,10 @@ impl Helpers {
 
         if let Some(rest) = &pattern.rest {
             let remaining: Vec<RuntimeValue> = elements[index..].to_vec();
-            let rest_value = RuntimeValue::Array(ArrayValue::new(remaining, PrimitiveType::any()));
+            let rest_value = match value {
+                RuntimeValue::Tuple(_) => RuntimeValue::Tuple(TupleValue::new(remaining)),
+                _ => RuntimeValue::Array(ArrayValue::new(remaining, PrimitiveType::any())),
+            };
             interpreter
                 .environment
                 .declare(rest.argument.name.clone(), rest_value)?;
@@ -108,12 +137,16 @@ impl Helpers {
             let prop_value = match value {
                 RuntimeValue::Object(obj) => obj
                     .properties
+                    .read()
+                    .unwrap()
                     .get(&prop.key)
                     .cloned()
                     .unwrap_or(RuntimeValue::Null(NullValue::new())),
                 RuntimeValue::Map(map) => map
                     .entries
-                    .get(&prop.key)
+                    .read()
+                    .unwrap()
+                    .get(&MapKey::Str(prop.key.clone()))
                     .cloned()
                     .unwrap_or(RuntimeValue::Null(NullValue::new())),
                 RuntimeValue::ClassInstance(inst) => inst
@@ -158,16 +191,17 @@ impl Helpers {
             let mut remaining = HashMap::new();
             match value {
                 RuntimeValue::Object(obj) => {
-                    for (key, val) in &obj.properties {
+                    for (key, val) in obj.properties.read().unwrap().iter() {
                         if !pattern.properties.iter().any(|p| p.key == *key) {
                             remaining.insert(key.clone(), val.clone());
                         }
                     }
                 }
                 RuntimeValue::Map(map) => {
-                    for (key, val) in &map.entries {
-                        if !pattern.properties.iter().any(|p| p.key == *key) {
-                            remaining.insert(key.clone(), val.clone());
+                    for (key, val) in map.entries.read().unwrap().iter() {
+                        let key = key.to_string();
+                        if !pattern.properties.iter().any(|p| p.key == key) {
+                            remaining.insert(key, val.clone());
                         }
                     }
                 }
@@ -190,56 +224,245 @@ impl Helpers {
         Ok(())
     }
 
+    /// Binds `parameters` in order, so a default sees the parameters before it.
+    pub async fn bind_parameters(
+        interpreter: &mut Interpreter,
+        parameters: &[FnParam],
+        args: &[RuntimeValue],
+        named_args: &HashMap<String, RuntimeValue>,
+        position: Position,
+    ) -> Result<(), RaccoonError> {
+        let mut positional_index = 0;
+
+        for (i, param) in parameters.iter().enumerate() {
+            let param_name = match &param.pattern {
+                VarPattern::Identifier(name) => name.clone(),
+                VarPattern::Destructuring(_) => format!("__param_{}", i),
+            };
+
+            let value = if param.is_rest {
+                let rest_args = args[positional_index.min(args.len())..].to_vec();
+                positional_index = args.len();
+
+                let element_type = match &param.param_type {
+                    Type::Array(list_type) => list_type.element_type.clone(),
+                    _ => PrimitiveType::any(),
+                };
+
+                RuntimeValue::Array(ArrayValue::new(rest_args, element_type))
+            } else if let Some(named_value) = named_args.get(&param_name) {
+                named_value.clone()
+            } else if let Some(arg) = args.get(positional_index) {
+                let use_default = matches!(
+                    param.pattern,
+                    VarPattern::Destructuring(DestructuringPattern::Array(_))
+                ) && !matches!(arg, RuntimeValue::Array(_))
+                    && param.default_value.is_some();
+
+                match &param.default_value {
+                    Some(default_expr) if use_default => {
+                        interpreter.evaluate_expr(default_expr).await?
+                    }
+                    _ => {
+                        positional_index += 1;
+                        arg.clone()
+                    }
+                }
+            } else if let Some(default_expr) = &param.default_value {
+                interpreter.evaluate_expr(default_expr).await?
+            } else if param.is_optional {
+                RuntimeValue::Null(NullValue::new())
+            } else {
+                return Err(RaccoonError::new(
+                    format!("Missing required argument for parameter '{}'", param_name),
+                    position,
+                    interpreter.file.clone(),
+                ));
+            };
+
+            match &param.pattern {
+                VarPattern::Identifier(name) => {
+                    interpreter.environment.declare(name.clone(), value)?;
+                }
+                VarPattern::Destructuring(pattern) => {
+                    Self::destructure_pattern(interpreter, pattern, &value, position).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The future a call to a native async function evaluates to.
+    pub fn native_async_result(result: RuntimeValue, fn_type: &Type) -> RuntimeValue {
+        if let RuntimeValue::Future(_) = result {
+            return result;
+        }
+        let return_type = match fn_type {
+            Type::Function(fn_type) => fn_type.return_type.clone(),
+            _ => PrimitiveType::any(),
+        };
+        RuntimeValue::Future(FutureValue::new_resolved(result, return_type))
+    }
+
+    pub fn select_overload<'a>(
+        interpreter: &Interpreter,
+        function: &'a FunctionValue,
+        args: &[RuntimeValue],
+        named: usize,
+        position: Position,
+    ) -> Result<&'a FunctionValue, RaccoonError> {
+        if function.overloads.is_empty() {
+            return Ok(function);
+        }
+        let signatures: Vec<Signature> = function
+            .declarations()
+            .map(|declaration| Signature::of(&declaration.parameters))
+            .collect();
+        match overloading::best(&signatures, args, named, &interpreter.environment) {
+            Some(0) => Ok(function),
+            Some(index) => Ok(&function.overloads[index - 1]),
+            None => Err(RaccoonError::type_error(
+                overloading::no_match_message(
+                    function.name.as_deref().unwrap_or("<anonymous>"),
+                    &signatures,
+                    args,
+                ),
+                position,
+                interpreter.file.clone(),
+            )),
+        }
+    }
+
     #[async_recursion(?Send)]
+    pub async fn call_bound(
+        interpreter: &mut Interpreter,
+        bound: &BoundCall,
+        args: Vec<RuntimeValue>,
+        named_args: HashMap<String, RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let args: Vec<RuntimeValue> = bound.args.iter().cloned().chain(args).collect();
+        let bind_this = !matches!(bound.this, RuntimeValue::Null(_));
+        if bind_this {
+            interpreter.environment.push_scope();
+        }
+        let result = match Self::declare_this(interpreter, &bound.this) {
+            Ok(()) => {
+                Self::call_function_with_named_args(
+                    interpreter,
+                    &bound.target,
+                    args,
+                    named_args,
+                    position,
+                )
+                .await
+            }
+            Err(error) => Err(error),
+        };
+        if bind_this {
+            interpreter.environment.pop_scope();
+        }
+
+        match &bound.target {
+            RuntimeValue::Function(function) if function.is_async => {
+                result.map(|value| Self::native_async_result(value, &function.fn_type))
+            }
+            _ => result,
+        }
+    }
+
+    fn declare_this(
+        interpreter: &mut Interpreter,
+        this: &RuntimeValue,
+    ) -> Result<(), RaccoonError> {
+        match this {
+            RuntimeValue::Null(_) => Ok(()),
+            RuntimeValue::ClassInstance(instance) => {
+                interpreter
+                    .environment
+                    .declare("this".to_string(), this.clone())?;
+                interpreter.environment.declare(
+                    CLASS_CONTEXT.to_string(),
+                    RuntimeValue::Str(StrValue::new(instance.class_name.clone())),
+                )
+            }
+            _ => interpreter
+                .environment
+                .declare("this".to_string(), this.clone()),
+        }
+    }
+
     pub async fn call_function(
         interpreter: &mut Interpreter,
         func: &RuntimeValue,
         args: Vec<RuntimeValue>,
         position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        Self::call_function_with_named_args(interpreter, func, args, HashMap::new(), position)
+            .await
+    }
+
+    #[async_recursion(?Send)]
+    pub async fn call_function_with_named_args(
+        interpreter: &mut Interpreter,
+        func: &RuntimeValue,
+        args: Vec<RuntimeValue>,
+        named_args: HashMap<String, RuntimeValue>,
+        position: Position,
     ) -> Result<RuntimeValue, RaccoonError> {
         match func {
             RuntimeValue::Function(fn_val) => {
+                if let Some(bound) = &fn_val.bound {
+                    return Self::call_bound(interpreter, bound, args, named_args, position).await;
+                }
+                let fn_val =
+                    Self::select_overload(interpreter, fn_val, &args, named_args.len(), position)?;
+                if let Some(decorated) = &fn_val.decorated {
+                    return NativeDecorators::call(
+                        interpreter,
+                        decorated,
+                        args,
+                        named_args,
+                        position,
+                    )
+                    .await;
+                }
                 interpreter.environment.push_scope();
 
-                for (i, param) in fn_val.parameters.iter().enumerate() {
-                    let value = if i < args.len() {
-                        args[i].clone()
-                    } else if let Some(default_expr) = &param.default_value {
-                        interpreter.evaluate_expr(default_expr).await?
-                    } else {
-                        interpreter.environment.pop_scope();
-                        return Err(RaccoonError::new(
-                            format!("Missing required argument for parameter {}", i),
-                            position,
-                            interpreter.file.clone(),
-                        ));
-                    };
-
-                    match &param.pattern {
-                        VarPattern::Identifier(name) => {
-                            interpreter.environment.declare(name.clone(), value)?;
-                        }
-                        VarPattern::Destructuring(pattern) => {
-                            if let Err(e) =
-                                Self::destructure_pattern(interpreter, pattern, &value, position)
-                                    .await
-                            {
-                                interpreter.environment.pop_scope();
-                                return Err(e);
-                            }
-                        }
-                    }
+                if let Err(error) = Self::bind_parameters(
+                    interpreter,
+                    &fn_val.parameters,
+                    &args,
+                    &named_args,
+                    position,
+                )
+                .await
+                {
+                    interpreter.environment.pop_scope();
+                    return Err(error);
                 }
 
+                interpreter.call_stack.push(StackFrame::new(
+                    fn_val
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| "<anonymous>".to_string()),
+                    position,
+                    interpreter.file.clone(),
+                ));
+
                 let mut result = RuntimeValue::Null(NullValue::new());
-                for stmt in &fn_val.body {
+                for stmt in fn_val.body.iter() {
                     match interpreter.execute_stmt_internal(stmt).await? {
                         InterpreterResult::Value(v) => result = v,
                         InterpreterResult::Return(v) => {
+                            interpreter.call_stack.pop();
                             interpreter.environment.pop_scope();
                             return Ok(v);
                         }
                         _ => {
+                            interpreter.call_stack.pop();
                             interpreter.environment.pop_scope();
                             return Err(RaccoonError::new(
                                 "Unexpected break/continue in function".to_string(),
@@ -250,20 +473,18 @@ impl Helpers {
                     }
                 }
 
+                interpreter.call_stack.pop();
                 interpreter.environment.pop_scope();
                 Ok(result)
             }
             RuntimeValue::NativeFunction(fn_val) => Ok((fn_val.implementation)(args)),
             RuntimeValue::NativeAsyncFunction(fn_val) => {
+                interpreter.check_native_call(fn_val, &args, position)?;
                 let result = (fn_val.implementation)(args).await;
-                let return_type = match &fn_val.fn_type {
-                    crate::ast::types::Type::Function(fn_type) => fn_type.return_type.clone(),
-                    _ => PrimitiveType::any(),
-                };
-                Ok(RuntimeValue::Future(FutureValue::new_resolved(
-                    result,
-                    return_type,
-                )))
+                Ok(Self::native_async_result(result, &fn_val.fn_type))
+            }
+            RuntimeValue::Dynamic(_) if crate::ir::VM::is_function(func) => {
+                crate::ir::VM::call_from_interpreter(interpreter, func.clone(), args).await
             }
             _ => Err(RaccoonError::new(
                 "Expected a function".to_string(),
@@ -272,4 +493,29 @@ impl Helpers {
             )),
         }
     }
+
+    /// Runs `function` as a method of `instance` declared by `owner`.
+    pub async fn invoke_method(
+        interpreter: &mut Interpreter,
+        instance: &Arc<ClassInstance>,
+        owner: &str,
+        function: FunctionValue,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        interpreter.environment.push_scope();
+        interpreter.environment.declare(
+            "this".to_string(),
+            RuntimeValue::ClassInstance(instance.clone()),
+        )?;
+        interpreter.environment.declare(
+            CLASS_CONTEXT.to_string(),
+            RuntimeValue::Str(StrValue::new(owner.to_string())),
+        )?;
+
+        let function = RuntimeValue::Function(Arc::new(function));
+        let result = Self::call_function(interpreter, &function, args, position).await;
+        interpreter.environment.pop_scope();
+        result
+    }
 }
@@ -0,0 +1,164 @@
+use crate::error::RaccoonError;
+use crate::runtime::type_object::TypeKind;
+use crate::runtime::{ClassInstance, IntValue, NullValue, RangeIter, RuntimeValue, StrValue};
+use crate::tokens::Position;
+use async_recursion::async_recursion;
+use std::sync::Arc;
+
+use super::declarations::Declarations;
+use super::helpers::Helpers;
+use super::overloads::Overloads;
+use super::Interpreter;
+
+/// A source of values for `for-of`, spread and `Array.from`.
+pub enum ValueIterator {
+    Elements(std::vec::IntoIter<RuntimeValue>),
+    Range(RangeIter),
+    Protocol(Arc<ClassInstance>),
+    Function(RuntimeValue),
+}
+
+impl ValueIterator {
+    /// Steps the iterators that run no code; `None` for the others.
+    pub fn next_builtin(&mut self) -> Option<Option<RuntimeValue>> {
+        match self {
+            ValueIterator::Elements(elements) => Some(elements.next()),
+            ValueIterator::Range(range) => {
+                Some(range.next().map(|i| RuntimeValue::Int(IntValue::new(i))))
+            }
+            ValueIterator::Protocol(_) | ValueIterator::Function(_) => None,
+        }
+    }
+
+    pub async fn next(
+        &mut self,
+        interpreter: &mut Interpreter,
+        position: Position,
+    ) -> Result<Option<RuntimeValue>, RaccoonError> {
+        let (step, iterator_name) = match self {
+            ValueIterator::Protocol(iterator) => (
+                Overloads::call(interpreter, iterator, "next", Vec::new(), position).await?,
+                iterator.class_name.clone(),
+            ),
+            ValueIterator::Function(next) => {
+                let step = Helpers::call_function(interpreter, next, Vec::new(), position).await?;
+                let step = match step {
+                    RuntimeValue::Future(future) => {
+                        future.wait_for_completion().await.map_err(|rejection| {
+                            Declarations::rejected_error(
+                                &interpreter.environment,
+                                rejection,
+                                position,
+                                interpreter.file.clone(),
+                            )
+                        })?
+                    }
+                    step => step,
+                };
+                (Some(step), "Iterator".to_string())
+            }
+            builtin => return Ok(builtin.next_builtin().flatten()),
+        };
+
+        let Some(RuntimeValue::Object(step)) = step else {
+            return Err(RaccoonError::new(
+                format!(
+                    "Iterator {}.next() must return an object with 'done' and 'value'",
+                    iterator_name
+                ),
+                position,
+                interpreter.file.clone(),
+            ));
+        };
+
+        let done = step
+            .properties
+            .read()
+            .unwrap()
+            .get("done")
+            .is_some_and(|done| interpreter.is_truthy(done));
+        if done {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            step.get("value")
+                .unwrap_or_else(|| RuntimeValue::Null(NullValue::new())),
+        ))
+    }
+}
+
+pub struct Iteration;
+
+impl Iteration {
+    /// An iterator over `value`, or `None` when it is not iterable.
+    #[async_recursion(?Send)]
+    pub async fn iterate(
+        interpreter: &mut Interpreter,
+        value: RuntimeValue,
+        position: Position,
+    ) -> Result<Option<ValueIterator>, RaccoonError> {
+        let elements = match value {
+            RuntimeValue::Array(list) => list.snapshot(),
+            RuntimeValue::Tuple(tuple) => tuple.elements,
+            RuntimeValue::Range(range) => return Ok(Some(ValueIterator::Range(range.iter()))),
+            RuntimeValue::Str(s) => s
+                .value
+                .chars()
+                .map(|c| RuntimeValue::Str(StrValue::new(c.to_string())))
+                .collect(),
+            RuntimeValue::Map(map) => map.entry_pairs(),
+            RuntimeValue::Object(object) => match object.get("next") {
+                Some(
+                    next @ (RuntimeValue::Function(_)
+                    | RuntimeValue::NativeFunction(_)
+                    | RuntimeValue::NativeAsyncFunction(_)),
+                ) => return Ok(Some(ValueIterator::Function(next))),
+                _ => object.entry_pairs(),
+            },
+            RuntimeValue::Type(type_obj) => match &type_obj.kind {
+                TypeKind::Enum { variants, .. } => variants
+                    .iter()
+                    .filter_map(|variant| type_obj.get_static_property(variant).cloned())
+                    .collect(),
+                _ => return Ok(None),
+            },
+            RuntimeValue::ClassInstance(instance) => {
+                let iterator =
+                    Overloads::call(interpreter, &instance, "__iter__", Vec::new(), position)
+                        .await?;
+                return match iterator {
+                    Some(RuntimeValue::ClassInstance(iterator))
+                        if iterator.methods.contains_key("next") =>
+                    {
+                        Ok(Some(ValueIterator::Protocol(iterator)))
+                    }
+                    Some(other) => Self::iterate(interpreter, other, position).await,
+                    None if instance.methods.contains_key("next") => {
+                        Ok(Some(ValueIterator::Protocol(instance)))
+                    }
+                    None => Ok(None),
+                };
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(ValueIterator::Elements(elements.into_iter())))
+    }
+
+    pub async fn collect(
+        interpreter: &mut Interpreter,
+        value: RuntimeValue,
+        position: Position,
+    ) -> Result<Option<Vec<RuntimeValue>>, RaccoonError> {
+        let Some(mut iterator) = Self::iterate(interpreter, value, position).await? else {
+            return Ok(None);
+        };
+
+        let mut elements = Vec::new();
+        while let Some(element) = iterator.next(interpreter, position).await? {
+            elements.push(element);
+        }
+        Ok(Some(elements))
+    }
+}
@@ -1,19 +1,33 @@
 pub mod builtins;
 pub mod control_flow;
 pub mod declarations;
+pub mod decorators;
+pub mod diagnostics;
+pub mod eval;
 pub mod expressions;
 pub mod helpers;
+pub mod iteration;
 pub mod module_loader;
 pub mod operators;
+pub mod overloads;
+pub mod process;
+pub mod task_group;
 
+use crate::analyzer::SemanticAnalyzer;
 use crate::ast::nodes::*;
 use crate::error::RaccoonError;
 use crate::runtime::{
-    ArrayValue, CallStack, DecoratorRegistry, Environment, FutureValue, ModuleRegistry, NullValue,
-    Registrar, RuntimeValue, StrValue, TypeRegistry,
+    CallStack, DecoratorRegistry, Environment, FutureValue, LoadingModules, ModuleRegistry,
+    NullValue, Registrar, Rejection, RuntimeValue, Sandbox, SandboxConfig, Snapshot,
+    TemplateTagRegistry, TypeRegistry,
 };
+use crate::symbol_table::{SymbolKind, SymbolTable};
 use crate::tokens::{BinaryOperator, Position};
 use async_recursion::async_recursion;
+use std::collections::HashMap;
+
+/// Hidden binding naming the class whose code is running, for `super`.
+pub(crate) const CLASS_CONTEXT: &str = "<class>";
 
 pub enum InterpreterResult {
     Value(RuntimeValue),
@@ -32,10 +46,20 @@ pub struct Interpreter {
     pub recursion_depth: usize,
     pub max_recursion_depth: usize,
     pub decorator_registry: DecoratorRegistry,
+    pub template_tags: TemplateTagRegistry,
     pub registrar: std::sync::Arc<std::sync::Mutex<Registrar>>,
     pub module_registry: std::sync::Arc<ModuleRegistry>,
     pub call_stack: CallStack,
+    /// The errors of the `catch` blocks running now, innermost last, for a bare `throw;`.
+    pub handling: Vec<RaccoonError>,
     pub use_ir: bool,
+    /// Set by `--release`: `assert`, `require` and `ensure` are skipped without evaluating.
+    pub release: bool,
+    pub debugger: Option<std::sync::Arc<std::sync::Mutex<crate::debugger::Debugger>>>,
+    pub sandbox: Option<std::sync::Arc<Sandbox>>,
+    pub ir_stats: Option<std::sync::Arc<std::sync::Mutex<crate::ir::IRStats>>>,
+    pub diagnostics: diagnostics::Diagnostics,
+    pub loading_modules: LoadingModules,
 }
 
 impl Interpreter {
@@ -76,11 +100,18 @@ impl Interpreter {
             crate::runtime::natives::register_http_module(registrar)
         });
 
+        let snapshot = Snapshot::current();
+        if let Some(snapshot) = &snapshot {
+            snapshot.restore(&mut env);
+        }
+
         Self::register_builtins(&mut env, registrar.clone());
 
         crate::runtime::register_stdlib_wrappers(&mut env, registrar.clone());
 
-        let stdlib_loader = std::sync::Arc::new(crate::runtime::StdLibLoader::with_default_path());
+        let stdlib_loader = std::sync::Arc::new(
+            crate::runtime::StdLibLoader::with_default_path().with_snapshot(snapshot),
+        );
         let decorator_registry = DecoratorRegistry::new();
 
         Self {
@@ -91,10 +122,18 @@ impl Interpreter {
             recursion_depth: 0,
             max_recursion_depth: 200,
             decorator_registry,
+            template_tags: TemplateTagRegistry::new(),
             registrar,
             module_registry: std::sync::Arc::new(module_registry),
             call_stack: CallStack::new(),
+            handling: Vec::new(),
             use_ir: false,
+            release: false,
+            debugger: None,
+            sandbox: None,
+            ir_stats: None,
+            diagnostics: diagnostics::Diagnostics::new(),
+            loading_modules: LoadingModules::default(),
         }
     }
 
@@ -115,7 +154,7 @@ impl Interpreter {
         let core_module = self.stdlib_loader.load_module("std:core").await?;
 
         if let RuntimeValue::Object(obj) = core_module {
-            for (name, value) in obj.properties.iter() {
+            for (name, value) in obj.properties.read().unwrap().iter() {
                 let _ = self.environment.declare(name.clone(), value.clone());
             }
         }
@@ -131,11 +170,165 @@ impl Interpreter {
         self.use_ir = false;
     }
 
+    pub fn enable_release_mode(&mut self) {
+        self.release = true;
+    }
+
+    pub fn attach_debugger(&mut self, debugger: crate::debugger::Debugger) {
+        self.debugger = Some(std::sync::Arc::new(std::sync::Mutex::new(debugger)));
+    }
+
+    pub fn enable_sandbox(&mut self, config: SandboxConfig) -> std::sync::Arc<Sandbox> {
+        let sandbox = std::sync::Arc::new(Sandbox::new(config));
+        self.sandbox = Some(sandbox.clone());
+        sandbox
+    }
+
+    /// Makes a top-level `async fn main` the entry point unless the program calls it.
+    pub fn append_async_main(program: &mut Program) {
+        let main = program.stmts.iter().find_map(|stmt| match stmt {
+            Stmt::FnDecl(decl) if decl.name == "main" && decl.is_async => Some(decl),
+            _ => None,
+        });
+        let Some(main) = main else {
+            return;
+        };
+        let calls_main = program.stmts.iter().any(|stmt| {
+            let Stmt::ExprStmt(stmt) = stmt else {
+                return false;
+            };
+            let call = match &stmt.expression {
+                Expr::Await(await_expr) => await_expr.expression.as_ref(),
+                expression => expression,
+            };
+            matches!(call, Expr::Call(call)
+                if matches!(call.callee.as_ref(), Expr::Identifier(ident) if ident.name == "main"))
+        });
+        if calls_main {
+            return;
+        }
+
+        let position = main.position;
+        let args = if main.parameters.is_empty() {
+            Vec::new()
+        } else {
+            vec![Expr::Member(MemberExpr {
+                object: Box::new(Expr::Identifier(Identifier {
+                    name: "process".to_string(),
+                    position,
+                })),
+                property: "argv".to_string(),
+                position,
+            })]
+        };
+        let call = Expr::Call(CallExpr {
+            callee: Box::new(Expr::Identifier(Identifier {
+                name: "main".to_string(),
+                position,
+            })),
+            args,
+            named_args: std::collections::HashMap::new(),
+            position,
+        });
+        program.stmts.push(Stmt::ExprStmt(ExprStmt {
+            expression: Expr::Await(AwaitExpr {
+                expression: Box::new(call),
+                position,
+            }),
+            position,
+        }));
+    }
+
+    /// A detached interpreter over `environment` sharing this one's registries.
+    pub fn fork(&self, environment: Environment) -> Interpreter {
+        Interpreter {
+            file: self.file.clone(),
+            environment,
+            type_registry: std::sync::Arc::clone(&self.type_registry),
+            stdlib_loader: std::sync::Arc::clone(&self.stdlib_loader),
+            recursion_depth: 0,
+            max_recursion_depth: self.max_recursion_depth,
+            decorator_registry: self.decorator_registry.clone(),
+            template_tags: self.template_tags.clone(),
+            registrar: self.registrar.clone(),
+            module_registry: self.module_registry.clone(),
+            call_stack: CallStack::with_profiler(self.call_stack.profiler()),
+            handling: self.handling.clone(),
+            use_ir: false,
+            release: self.release,
+            debugger: self.debugger.clone(),
+            sandbox: self.sandbox.clone(),
+            ir_stats: None,
+            diagnostics: self.diagnostics.clone(),
+            loading_modules: self.loading_modules.clone(),
+        }
+    }
+
+    pub fn check_native_call(
+        &self,
+        func: &crate::runtime::NativeAsyncFunctionValue,
+        args: &[RuntimeValue],
+        position: Position,
+    ) -> Result<(), RaccoonError> {
+        match (&self.sandbox, &func.name) {
+            (Some(sandbox), Some(name)) => {
+                sandbox.check_call(name, func.module, args, position, &self.file)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn enable_ir_stats(&mut self) -> std::sync::Arc<std::sync::Mutex<crate::ir::IRStats>> {
+        let stats = std::sync::Arc::new(std::sync::Mutex::new(crate::ir::IRStats::new()));
+        self.ir_stats = Some(stats.clone());
+        stats
+    }
+
+    pub fn enable_profiling(
+        &mut self,
+    ) -> std::sync::Arc<std::sync::Mutex<crate::runtime::Profiler>> {
+        self.call_stack.enable_profiling()
+    }
+
     #[async_recursion(?Send)]
     pub async fn interpret_with_ir(
         &mut self,
         program: &Program,
     ) -> Result<RuntimeValue, RaccoonError> {
+        let (_, result) = self.execute_ir_program(program).await?;
+        Ok(result)
+    }
+
+    pub fn global_symbols(&self) -> SymbolTable {
+        let mut symbol_table = SymbolTable::new(self.file.clone());
+        for name in self.environment.names() {
+            if symbol_table.lookup(name).is_none() {
+                symbol_table.define(
+                    name.to_string(),
+                    SymbolKind::Variable,
+                    crate::ast::types::PrimitiveType::any(),
+                    false,
+                    None,
+                );
+            }
+        }
+        symbol_table
+    }
+
+    fn folded_constants(&self, program: &Program) -> HashMap<String, RuntimeValue> {
+        let mut analyzer =
+            SemanticAnalyzer::with_symbol_table(self.global_symbols()).with_file(self.file.clone());
+        match analyzer.analyze(program) {
+            Ok(()) => analyzer.symbol_table.constants(),
+            Err(_) => HashMap::new(),
+        }
+    }
+
+    #[async_recursion(?Send)]
+    pub async fn execute_ir_program(
+        &mut self,
+        program: &Program,
+    ) -> Result<(crate::ir::VM, RuntimeValue), RaccoonError> {
         if self.file.is_none() || self.file.as_ref().map_or(false, |f| f == "<root>") {
             self.load_std_core_if_needed().await?;
         }
@@ -148,20 +341,43 @@ impl Interpreter {
             }
         }
 
-        let compiler = crate::ir::IRCompiler::new();
-        let ir_program = compiler.compile(program)?;
+        let compiler = crate::ir::IRCompiler::new()
+            .strip_assertions(self.release)
+            .constants(self.folded_constants(program));
+        let ir_program = compiler.compile(program).map_err(|mut error| {
+            if error.file.is_none() {
+                error.file = self.file.clone();
+            }
+            error
+        })?;
 
         let optimizer = crate::ir::IROptimizer::new(ir_program);
         let optimized_program = optimizer.optimize();
 
-        let mut vm = crate::ir::VM::new(self.environment.clone(), self.type_registry.clone());
+        let mut vm = crate::ir::VM::new(self.environment.clone(), self.type_registry.clone())
+            .with_sandbox(self.sandbox.clone())
+            .with_stats(self.ir_stats.clone())
+            .with_file(self.file.clone());
         let result = vm.execute(optimized_program).await?;
 
-        Ok(result)
+        Ok((vm, result))
     }
 
     #[async_recursion(?Send)]
     pub async fn interpret(&mut self, program: &Program) -> Result<RuntimeValue, RaccoonError> {
+        let module = self
+            .file
+            .as_deref()
+            .and_then(|file| self.loading_modules.enter(file));
+        let result = self.interpret_module(program).await;
+        if let Some(path) = module {
+            self.loading_modules.leave(&path);
+        }
+        result
+    }
+
+    #[async_recursion(?Send)]
+    async fn interpret_module(&mut self, program: &Program) -> Result<RuntimeValue, RaccoonError> {
         if self.use_ir {
             return self.interpret_with_ir(program).await;
         }
@@ -194,7 +410,15 @@ impl Interpreter {
         &mut self,
         stmt: &Stmt,
     ) -> Result<InterpreterResult, RaccoonError> {
-        match stmt {
+        if let Some(sandbox) = &self.sandbox {
+            sandbox.tick(stmt.position(), &self.file)?;
+        }
+
+        if let Some(debugger) = self.debugger.clone() {
+            debugger.lock().unwrap().on_statement(self, stmt);
+        }
+
+        let result = match stmt {
             Stmt::Program(program) => self.interpret(program).await.map(InterpreterResult::Value),
             Stmt::VarDecl(decl) => declarations::Declarations::execute_var_decl(self, decl)
                 .await
@@ -231,35 +455,52 @@ impl Interpreter {
             }
             Stmt::BreakStmt(_) => Ok(InterpreterResult::Break),
             Stmt::ContinueStmt(_) => Ok(InterpreterResult::Continue),
-            Stmt::ExprStmt(expr_stmt) => self
-                .evaluate_expr(&expr_stmt.expression)
-                .await
-                .map(InterpreterResult::Value),
+            Stmt::ExprStmt(expr_stmt) => {
+                match expressions::Expressions::evaluate_int_expr(self, &expr_stmt.expression) {
+                    Some(result) => result,
+                    None => self.evaluate_expr(&expr_stmt.expression).await,
+                }
+                .map(InterpreterResult::Value)
+            }
             Stmt::ClassDecl(decl) => declarations::Declarations::execute_class_decl(self, decl)
                 .await
                 .map(InterpreterResult::Value),
-            Stmt::InterfaceDecl(_) => Ok(InterpreterResult::Value(RuntimeValue::Null(
-                NullValue::new(),
-            ))),
+            Stmt::InterfaceDecl(decl) => {
+                declarations::Declarations::execute_interface_decl(self, decl)
+            }
             Stmt::EnumDecl(enum_decl) => {
                 declarations::Declarations::execute_enum_decl(self, enum_decl).await
             }
-            Stmt::TypeAliasDecl(_) => Ok(InterpreterResult::Value(RuntimeValue::Null(
-                NullValue::new(),
-            ))),
+            Stmt::TypeAliasDecl(decl) => {
+                declarations::Declarations::execute_type_alias_decl(self, decl)
+            }
             Stmt::ImportDecl(import_decl) => {
                 module_loader::ModuleLoader::execute_import_decl(self, import_decl).await
             }
-            Stmt::ExportDecl(_) => Ok(InterpreterResult::Value(RuntimeValue::Null(
-                NullValue::new(),
-            ))),
+            Stmt::ExportDecl(export_decl) => {
+                module_loader::ModuleLoader::execute_export_decl(self, export_decl).await
+            }
             Stmt::TryStmt(try_stmt) => {
                 control_flow::ControlFlow::execute_try_stmt(self, try_stmt).await
             }
             Stmt::ThrowStmt(throw) => {
                 declarations::Declarations::execute_throw_stmt(self, throw).await
             }
-        }
+            Stmt::AssertStmt(_) if self.release => Ok(InterpreterResult::Value(
+                RuntimeValue::Null(NullValue::new()),
+            )),
+            Stmt::AssertStmt(assert) => {
+                declarations::Declarations::execute_assert_stmt(self, assert).await
+            }
+            Stmt::UsingStmt(using) => {
+                control_flow::ControlFlow::execute_using_stmt(self, using).await
+            }
+            Stmt::DeferStmt(defer) => {
+                control_flow::ControlFlow::execute_defer_stmt(self, defer).await
+            }
+        };
+
+        result.map_err(|error| error.attach_call_stack(&self.call_stack))
     }
 
     #[async_recursion(?Send)]
@@ -317,6 +558,14 @@ impl Interpreter {
         &self.decorator_registry
     }
 
+    pub fn get_template_tags(&self) -> &TemplateTagRegistry {
+        &self.template_tags
+    }
+
+    pub fn register_template_tag(&mut self, spec: crate::runtime::TemplateTagSpec) {
+        self.template_tags.register_tag(spec);
+    }
+
     pub fn is_in_stdlib(&self) -> bool {
         if let Some(file) = &self.file {
             file.contains("stdlib") || file.ends_with(".rcc")
@@ -347,6 +596,7 @@ impl Interpreter {
 
     pub fn get_builtin_type(&self, name: &str) -> Option<RuntimeValue> {
         use crate::ast::types::PrimitiveType;
+        use crate::runtime::builtins::future;
         use crate::runtime::{NativeFunctionValue, PrimitiveTypeObject};
         use std::collections::HashMap;
 
@@ -370,13 +620,12 @@ impl Interpreter {
 
                 let reject_fn = NativeFunctionValue::new(
                     |args: Vec<RuntimeValue>| {
-                        let reason_str = if args.is_empty() {
-                            "Unknown error".to_string()
-                        } else {
-                            args[0].to_string()
+                        let reason = match args.into_iter().next() {
+                            Some(reason) => Rejection::Thrown(Box::new(reason)),
+                            None => Rejection::Message("Unknown error".to_string()),
                         };
                         RuntimeValue::Future(FutureValue::new_rejected(
-                            reason_str,
+                            reason,
                             PrimitiveType::any(),
                         ))
                     },
@@ -385,295 +634,27 @@ impl Interpreter {
                 static_methods.insert("reject".to_string(), Box::new(reject_fn));
 
                 let all_fn = NativeFunctionValue::new(
-                    |args: Vec<RuntimeValue>| {
-                        if args.is_empty() {
-                            let list =
-                                RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::any()));
-                            return RuntimeValue::Future(FutureValue::new_resolved(
-                                list,
-                                PrimitiveType::any(),
-                            ));
-                        }
-
-                        match &args[0] {
-                            RuntimeValue::Array(list) => {
-                                let futures = list.elements.clone();
-                                let result_future = FutureValue::new(PrimitiveType::any());
-                                let result_clone = result_future.clone();
-
-                                tokio::task::spawn_local(async move {
-                                    let mut results = Vec::new();
-
-                                    for element in &futures {
-                                        if let RuntimeValue::Future(fut) = element {
-                                            match fut.wait_for_completion().await {
-                                                Ok(val) => results.push(val),
-                                                Err(err) => {
-                                                    result_clone.reject(err);
-                                                    return;
-                                                }
-                                            }
-                                        } else {
-                                            results.push(element.clone());
-                                        }
-                                    }
-
-                                    let result_list = RuntimeValue::Array(ArrayValue::new(
-                                        results,
-                                        PrimitiveType::any(),
-                                    ));
-                                    result_clone.resolve(result_list);
-                                });
-
-                                RuntimeValue::Future(result_future)
-                            }
-                            _ => RuntimeValue::Future(FutureValue::new_rejected(
-                                "Future.all requires an array".to_string(),
-                                PrimitiveType::any(),
-                            )),
-                        }
-                    },
+                    |args: Vec<RuntimeValue>| future::combine(&args, "all", future::all),
                     crate::fn_type!(variadic, PrimitiveType::any()),
                 );
                 static_methods.insert("all".to_string(), Box::new(all_fn));
 
                 let race_fn = NativeFunctionValue::new(
-                    |args: Vec<RuntimeValue>| {
-                        if args.is_empty() {
-                            return RuntimeValue::Future(FutureValue::new_resolved(
-                                RuntimeValue::Null(NullValue::new()),
-                                PrimitiveType::any(),
-                            ));
-                        }
-
-                        match &args[0] {
-                            RuntimeValue::Array(list) => {
-                                if list.elements.is_empty() {
-                                    return RuntimeValue::Future(FutureValue::new_resolved(
-                                        RuntimeValue::Null(NullValue::new()),
-                                        PrimitiveType::any(),
-                                    ));
-                                }
-
-                                let futures = list.elements.clone();
-                                let result_future = FutureValue::new(PrimitiveType::any());
-                                let result_clone = result_future.clone();
-
-                                tokio::task::spawn_local(async move {
-                                    let mut tasks = Vec::new();
-
-                                    for element in &futures {
-                                        if let RuntimeValue::Future(fut) = element {
-                                            let fut_clone = fut.clone();
-                                            tasks.push(tokio::task::spawn_local(async move {
-                                                fut_clone.wait_for_completion().await
-                                            }));
-                                        }
-                                    }
-
-                                    if !tasks.is_empty() {
-                                        for task in tasks {
-                                            if let Ok(result) = task.await {
-                                                match result {
-                                                    Ok(val) => {
-                                                        result_clone.resolve(val);
-                                                        return;
-                                                    }
-                                                    Err(err) => {
-                                                        result_clone.reject(err);
-                                                        return;
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    result_clone.resolve(RuntimeValue::Null(NullValue::new()));
-                                });
-
-                                RuntimeValue::Future(result_future)
-                            }
-                            _ => RuntimeValue::Future(FutureValue::new_rejected(
-                                "Future.race requires an array".to_string(),
-                                PrimitiveType::any(),
-                            )),
-                        }
-                    },
+                    |args: Vec<RuntimeValue>| future::combine(&args, "race", future::race),
                     crate::fn_type!(variadic, PrimitiveType::any()),
                 );
                 static_methods.insert("race".to_string(), Box::new(race_fn));
 
                 let all_settled_fn = NativeFunctionValue::new(
                     |args: Vec<RuntimeValue>| {
-                        if args.is_empty() {
-                            let list =
-                                RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::any()));
-                            return RuntimeValue::Future(FutureValue::new_resolved(
-                                list,
-                                PrimitiveType::any(),
-                            ));
-                        }
-
-                        match &args[0] {
-                            RuntimeValue::Array(list) => {
-                                let mut results = Vec::new();
-
-                                for element in &list.elements {
-                                    if let RuntimeValue::Future(fut) = element {
-                                        let state = fut.state.read().unwrap();
-                                        let result_obj = match &*state {
-                                            crate::runtime::values::FutureState::Resolved(val) => {
-                                                let mut obj_props =
-                                                    std::collections::HashMap::new();
-                                                obj_props.insert(
-                                                    "status".to_string(),
-                                                    RuntimeValue::Str(StrValue::new(
-                                                        "fulfilled".to_string(),
-                                                    )),
-                                                );
-                                                obj_props
-                                                    .insert("value".to_string(), (**val).clone());
-                                                RuntimeValue::Object(
-                                                    crate::runtime::ObjectValue::new(
-                                                        obj_props,
-                                                        PrimitiveType::any(),
-                                                    ),
-                                                )
-                                            }
-                                            crate::runtime::values::FutureState::Rejected(err) => {
-                                                let mut obj_props =
-                                                    std::collections::HashMap::new();
-                                                obj_props.insert(
-                                                    "status".to_string(),
-                                                    RuntimeValue::Str(StrValue::new(
-                                                        "rejected".to_string(),
-                                                    )),
-                                                );
-                                                obj_props.insert(
-                                                    "reason".to_string(),
-                                                    RuntimeValue::Str(StrValue::new(err.clone())),
-                                                );
-                                                RuntimeValue::Object(
-                                                    crate::runtime::ObjectValue::new(
-                                                        obj_props,
-                                                        PrimitiveType::any(),
-                                                    ),
-                                                )
-                                            }
-                                            crate::runtime::values::FutureState::Pending => {
-                                                let mut obj_props =
-                                                    std::collections::HashMap::new();
-                                                obj_props.insert(
-                                                    "status".to_string(),
-                                                    RuntimeValue::Str(StrValue::new(
-                                                        "pending".to_string(),
-                                                    )),
-                                                );
-                                                RuntimeValue::Object(
-                                                    crate::runtime::ObjectValue::new(
-                                                        obj_props,
-                                                        PrimitiveType::any(),
-                                                    ),
-                                                )
-                                            }
-                                        };
-                                        results.push(result_obj);
-                                    } else {
-                                        let mut obj_props = std::collections::HashMap::new();
-                                        obj_props.insert(
-                                            "status".to_string(),
-                                            RuntimeValue::Str(StrValue::new(
-                                                "fulfilled".to_string(),
-                                            )),
-                                        );
-                                        obj_props.insert("value".to_string(), element.clone());
-                                        results.push(RuntimeValue::Object(
-                                            crate::runtime::ObjectValue::new(
-                                                obj_props,
-                                                PrimitiveType::any(),
-                                            ),
-                                        ));
-                                    }
-                                }
-
-                                let result_list = RuntimeValue::Array(ArrayValue::new(
-                                    results,
-                                    PrimitiveType::any(),
-                                ));
-                                RuntimeValue::Future(FutureValue::new_resolved(
-                                    result_list,
-                                    PrimitiveType::any(),
-                                ))
-                            }
-                            _ => RuntimeValue::Future(FutureValue::new_rejected(
-                                "Future.allSettled requires an array".to_string(),
-                                PrimitiveType::any(),
-                            )),
-                        }
+                        future::combine(&args, "allSettled", future::all_settled)
                     },
                     crate::fn_type!(variadic, PrimitiveType::any()),
                 );
                 static_methods.insert("allSettled".to_string(), Box::new(all_settled_fn));
 
                 let any_fn = NativeFunctionValue::new(
-                    |args: Vec<RuntimeValue>| {
-                        if args.is_empty() {
-                            return RuntimeValue::Future(FutureValue::new_rejected(
-                                "All futures rejected".to_string(),
-                                PrimitiveType::any(),
-                            ));
-                        }
-
-                        match &args[0] {
-                            RuntimeValue::Array(list) => {
-                                for element in &list.elements {
-                                    if let RuntimeValue::Future(fut) = element {
-                                        let state = fut.state.read().unwrap();
-                                        if let crate::runtime::values::FutureState::Resolved(val) =
-                                            &*state
-                                        {
-                                            return RuntimeValue::Future(
-                                                FutureValue::new_resolved(
-                                                    (**val).clone(),
-                                                    PrimitiveType::any(),
-                                                ),
-                                            );
-                                        }
-                                    } else {
-                                        return RuntimeValue::Future(FutureValue::new_resolved(
-                                            element.clone(),
-                                            PrimitiveType::any(),
-                                        ));
-                                    }
-                                }
-
-                                for element in &list.elements {
-                                    if let RuntimeValue::Future(fut) = element {
-                                        let state = fut.state.read().unwrap();
-                                        if let crate::runtime::values::FutureState::Rejected(err) =
-                                            &*state
-                                        {
-                                            return RuntimeValue::Future(
-                                                FutureValue::new_rejected(
-                                                    err.clone(),
-                                                    PrimitiveType::any(),
-                                                ),
-                                            );
-                                        }
-                                    }
-                                }
-
-                                RuntimeValue::Future(FutureValue::new_rejected(
-                                    "All futures rejected".to_string(),
-                                    PrimitiveType::any(),
-                                ))
-                            }
-                            _ => RuntimeValue::Future(FutureValue::new_rejected(
-                                "Future.any requires an array".to_string(),
-                                PrimitiveType::any(),
-                            )),
-                        }
-                    },
+                    |args: Vec<RuntimeValue>| future::combine(&args, "any", future::any),
                     crate::fn_type!(variadic, PrimitiveType::any()),
                 );
                 static_methods.insert("any".to_string(), Box::new(any_fn));
@@ -686,11 +667,13 @@ impl Interpreter {
                     inner_type: any_type,
                 }));
 
-                Some(RuntimeValue::PrimitiveTypeObject(PrimitiveTypeObject::new(
-                    "Future".to_string(),
-                    static_methods,
-                    static_properties,
-                    future_type,
+                Some(RuntimeValue::PrimitiveTypeObject(std::sync::Arc::new(
+                    PrimitiveTypeObject::new(
+                        "Future".to_string(),
+                        static_methods,
+                        static_properties,
+                        future_type,
+                    ),
                 )))
             }
             _ => None,
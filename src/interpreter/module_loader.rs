@@ -2,6 +2,7 @@ use crate::ast::nodes::*;
 use crate::ast::types::PrimitiveType;
 use crate::error::RaccoonError;
 use crate::runtime::{NullValue, ObjectValue, RuntimeValue};
+use crate::tokens::Position;
 use async_recursion::async_recursion;
 use std::collections::HashMap;
 
@@ -16,9 +17,11 @@ impl ModuleLoader {
         import_decl: &ImportDecl,
     ) -> Result<InterpreterResult, RaccoonError> {
         let module_spec = &import_decl.module_specifier;
+        let position = import_decl.position;
 
         if let Some(namespace_name) = &import_decl.namespace_import {
-            let namespace_obj = Self::get_module_namespace(interpreter, module_spec).await?;
+            let namespace_obj =
+                Self::get_module_namespace(interpreter, module_spec, position).await?;
             interpreter
                 .environment
                 .declare(namespace_name.clone(), namespace_obj)?;
@@ -31,12 +34,14 @@ impl ModuleLoader {
             let imported_name = &spec.imported;
             let local_name = spec.local.as_ref().unwrap_or(imported_name);
 
-            let value = Self::get_module_export(interpreter, module_spec, imported_name).await?;
+            let value =
+                Self::get_module_export(interpreter, module_spec, imported_name, position).await?;
             interpreter.environment.declare(local_name.clone(), value)?;
         }
 
         if let Some(default_name) = &import_decl.default_import {
-            let value = Self::get_module_export(interpreter, module_spec, "default").await?;
+            let value =
+                Self::get_module_export(interpreter, module_spec, "default", position).await?;
             interpreter
                 .environment
                 .declare(default_name.clone(), value)?;
@@ -50,27 +55,32 @@ impl ModuleLoader {
     pub async fn get_module_namespace(
         interpreter: &Interpreter,
         module_spec: &str,
+        position: Position,
     ) -> Result<RuntimeValue, RaccoonError> {
         if module_spec.starts_with("std:") || module_spec == "std:runtime" {
+            if let Some(sandbox) = &interpreter.sandbox {
+                sandbox.check_module(module_spec, position, &interpreter.file)?;
+            }
             if interpreter.stdlib_loader.module_exists(module_spec) {
-                return interpreter.stdlib_loader.load_module(module_spec).await;
+                let module = interpreter.stdlib_loader.load_module(module_spec).await?;
+                return Ok(Self::restrict_runtime_module(interpreter, module_spec, module));
             } else {
                 return Err(RaccoonError::new(
                     format!("Unknown module: {}", module_spec),
-                    (0, 0),
+                    position,
                     interpreter.file.clone(),
                 ));
             }
         } else if module_spec.starts_with("./") || module_spec.starts_with("../") {
-            let module_path = Self::resolve_relative_path(interpreter, module_spec)?;
-            return Self::load_file_module(interpreter, &module_path).await;
+            let module_path = Self::resolve_relative_path(interpreter, module_spec, position)?;
+            return Self::load_file_module(interpreter, &module_path, position).await;
         } else {
             Err(RaccoonError::new(
                 format!(
                     "Invalid module specifier: {}. Use 'std:' for stdlib or './', '../' for relative paths",
                     module_spec
                 ),
-                (0, 0),
+                position,
                 interpreter.file.clone(),
             ))
         }
@@ -80,36 +90,49 @@ impl ModuleLoader {
         interpreter: &Interpreter,
         module_spec: &str,
         export_name: &str,
+        position: Position,
     ) -> Result<RuntimeValue, RaccoonError> {
         if module_spec.starts_with("std:") || module_spec == "std:runtime" {
+            if let Some(sandbox) = &interpreter.sandbox {
+                sandbox.check_module(module_spec, position, &interpreter.file)?;
+            }
             if interpreter.stdlib_loader.module_exists(module_spec) {
-                return interpreter
+                let export = interpreter
                     .stdlib_loader
-                    .get_module_export(module_spec, export_name)
-                    .await;
+                    .get_module_export(module_spec, export_name, position, &interpreter.file)
+                    .await?;
+                if let (Some(sandbox), RuntimeValue::NativeAsyncFunction(native)) =
+                    (&interpreter.sandbox, &export)
+                {
+                    let file = &interpreter.file;
+                    sandbox.check_native(export_name, native.module, position, file)?;
+                }
+                Ok(export)
             } else {
                 return Err(RaccoonError::new(
                     format!("Unknown module: {}", module_spec),
-                    (0, 0),
+                    position,
                     interpreter.file.clone(),
                 ));
             }
         } else if module_spec.starts_with("./") || module_spec.starts_with("../") {
-            let module_path = Self::resolve_relative_path(interpreter, module_spec)?;
-            let module = Self::load_file_module(interpreter, &module_path).await?;
+            let module_path = Self::resolve_relative_path(interpreter, module_spec, position)?;
+            let module = Self::load_file_module(interpreter, &module_path, position).await?;
 
             if let RuntimeValue::Object(obj) = module {
-                obj.properties.get(export_name).cloned().ok_or_else(|| {
-                    RaccoonError::new(
-                        format!("{} does not export '{}'", module_spec, export_name),
-                        (0, 0),
-                        interpreter.file.clone(),
+                obj.get(export_name).ok_or_else(|| {
+                    Self::missing_export(
+                        interpreter,
+                        module_spec,
+                        &module_path,
+                        export_name,
+                        position,
                     )
                 })
             } else {
                 Err(RaccoonError::new(
                     format!("Module {} is not an object", module_spec),
-                    (0, 0),
+                    position,
                     interpreter.file.clone(),
                 ))
             }
@@ -119,17 +142,47 @@ impl ModuleLoader {
                     "Invalid module specifier: {}. Use 'std:' for stdlib or './', '../' for relative paths",
                     module_spec
                 ),
-                (0, 0),
+                position,
                 interpreter.file.clone(),
             ))
         }
     }
 
+    fn restrict_runtime_module(
+        interpreter: &Interpreter,
+        module_spec: &str,
+        module: RuntimeValue,
+    ) -> RuntimeValue {
+        let (Some(sandbox), RuntimeValue::Object(obj)) = (&interpreter.sandbox, &module) else {
+            return module;
+        };
+        if module_spec != "std:runtime" {
+            return module;
+        }
+
+        let properties: Vec<(String, RuntimeValue)> = obj
+            .properties
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, value)| match value {
+                RuntimeValue::NativeAsyncFunction(native) => {
+                    native.module.is_none_or(|module| sandbox.allows(module))
+                }
+                _ => true,
+            })
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+    }
+
     pub fn resolve_relative_path(
         interpreter: &Interpreter,
         module_spec: &str,
+        position: Position,
     ) -> Result<String, RaccoonError> {
-        use std::path::PathBuf;
+        use std::path::{Component, PathBuf};
 
         let current_dir = if let Some(file) = &interpreter.file {
             PathBuf::from(file)
@@ -140,7 +193,11 @@ impl ModuleLoader {
             std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
         };
 
-        let mut path = current_dir.join(module_spec);
+        let mut path: PathBuf = current_dir
+            .join(module_spec)
+            .components()
+            .filter(|component| *component != Component::CurDir)
+            .collect();
 
         if path.extension().is_none() {
             path.set_extension("rcc");
@@ -149,7 +206,7 @@ impl ModuleLoader {
         path.to_str().map(|s| s.to_string()).ok_or_else(|| {
             RaccoonError::new(
                 format!("Invalid path: {}", module_spec),
-                (0, 0),
+                position,
                 interpreter.file.clone(),
             )
         })
@@ -158,15 +215,20 @@ impl ModuleLoader {
     pub async fn load_file_module(
         interpreter: &Interpreter,
         path: &str,
+        position: Position,
     ) -> Result<RuntimeValue, RaccoonError> {
         use crate::lexer::Lexer;
         use crate::parser::Parser;
         use std::fs;
 
+        if let Some(exports) = interpreter.loading_modules.exports(path) {
+            return Ok(RuntimeValue::Object(exports));
+        }
+
         let source = fs::read_to_string(path).map_err(|e| {
             RaccoonError::new(
                 format!("Failed to read module {}: {}", path, e),
-                (0, 0),
+                position,
                 interpreter.file.clone(),
             )
         })?;
@@ -175,8 +237,8 @@ impl ModuleLoader {
         let mut lexer = Lexer::new(source, file_path.clone());
         let tokens = lexer.tokenize().map_err(|e| {
             RaccoonError::new(
-                format!("Lexer error in {}: {:?}", path, e),
-                (0, 0),
+                format!("Lexer error in {}: {}", path, e.message),
+                e.position,
                 file_path.clone(),
             )
         })?;
@@ -184,131 +246,167 @@ impl ModuleLoader {
         let mut parser = Parser::new(tokens, file_path.clone());
         let program = parser.parse().map_err(|e| {
             RaccoonError::new(
-                format!("Parser error in {}: {:?}", path, e),
-                (0, 0),
+                format!("Parser error in {}: {}", path, e.message),
+                e.position,
                 file_path.clone(),
             )
         })?;
 
         let mut module_interp = Interpreter::new(file_path.clone());
+        module_interp.sandbox = interpreter.sandbox.clone();
+        module_interp.diagnostics = interpreter.diagnostics.clone();
+        module_interp.loading_modules = interpreter.loading_modules.clone();
 
+        let module = interpreter.loading_modules.enter(path);
+        let mut result = Ok(());
+        for stmt in &program.stmts {
+            if let Err(error) = module_interp.execute_stmt(stmt).await {
+                result = Err(error);
+                break;
+            }
+        }
+        let exports = interpreter.loading_modules.exports(path);
+        if let Some(module) = module {
+            interpreter.loading_modules.leave(&module);
+        }
+        result?;
+
+        Ok(RuntimeValue::Object(exports.unwrap_or_else(|| {
+            ObjectValue::new(Vec::new(), PrimitiveType::any())
+        })))
+    }
+
+    #[async_recursion(?Send)]
+    pub async fn execute_export_decl(
+        interpreter: &mut Interpreter,
+        export_decl: &ExportDecl,
+    ) -> Result<InterpreterResult, RaccoonError> {
+        let file_path = interpreter.file.clone();
         let mut exports = HashMap::new();
-        let mut default_export: Option<RuntimeValue> = None;
 
-        for stmt in &program.stmts {
-            match stmt {
-                Stmt::ExportDecl(export_decl) => {
-                    if export_decl.is_default {
-                        if let Some(decl) = &export_decl.declaration {
-                            match decl.as_ref() {
-                                Stmt::ExprStmt(e) => {
-                                    default_export =
-                                        Some(module_interp.eval_expr_public(&e.expression).await?);
-                                }
-                                _ => {
-                                    module_interp.execute_stmt(decl).await?;
-                                    let name = match decl.as_ref() {
-                                        Stmt::FnDecl(f) => &f.name,
-                                        Stmt::ClassDecl(c) => &c.name,
-                                        Stmt::EnumDecl(e) => &e.name,
-                                        _ => {
-                                            return Err(RaccoonError::new(
-                                                "Invalid default export",
-                                                (0, 0),
-                                                file_path.clone(),
-                                            ));
-                                        }
-                                    };
-                                    default_export = Some(module_interp.get_from_env(name)?);
-                                }
-                            }
-                        }
-                    } else if let Some(decl) = &export_decl.declaration {
+        if export_decl.is_default {
+            if let Some(decl) = &export_decl.declaration {
+                match decl.as_ref() {
+                    Stmt::ExprStmt(e) => {
+                        exports.insert(
+                            "default".to_string(),
+                            interpreter.eval_expr_public(&e.expression).await?,
+                        );
+                    }
+                    _ => {
+                        interpreter.execute_stmt(decl).await?;
                         let name = match decl.as_ref() {
-                            Stmt::FnDecl(f) => f.name.clone(),
-                            Stmt::ClassDecl(c) => c.name.clone(),
-                            Stmt::EnumDecl(e) => e.name.clone(),
-                            Stmt::VarDecl(v) => match &v.pattern {
-                                VarPattern::Identifier(id) => id.clone(),
-                                _ => {
-                                    return Err(RaccoonError::new(
-                                        "Cannot export destructured variable",
-                                        (0, 0),
-                                        file_path.clone(),
-                                    ));
-                                }
-                            },
+                            Stmt::FnDecl(f) => &f.name,
+                            Stmt::ClassDecl(c) => &c.name,
+                            Stmt::EnumDecl(e) => &e.name,
                             _ => {
                                 return Err(RaccoonError::new(
-                                    "Invalid export declaration",
-                                    (0, 0),
-                                    file_path.clone(),
+                                    "Invalid default export",
+                                    export_decl.position,
+                                    file_path,
                                 ));
                             }
                         };
-                        module_interp.execute_stmt(decl).await?;
-                        if let Ok(val) = module_interp.get_from_env(&name) {
-                            exports.insert(name, val);
-                        }
-                    } else {
-                        if let Some(module_spec) = &export_decl.module_specifier {
-                            let source_module_path =
-                                Self::resolve_relative_path(&module_interp, module_spec)?;
-                            let source_module = Box::pin(Self::load_file_module(
-                                &module_interp,
-                                &source_module_path,
-                            ))
-                            .await?;
-
-                            if let RuntimeValue::Object(obj) = source_module {
-                                for spec in &export_decl.specifiers {
-                                    let import_name = &spec.local;
-                                    let export_name = spec.exported.as_ref().unwrap_or(import_name);
-
-                                    if let Some(val) = obj.properties.get(import_name) {
-                                        exports.insert(export_name.clone(), val.clone());
-                                    } else {
-                                        return Err(RaccoonError::new(
-                                            format!(
-                                                "{} does not export '{}'",
-                                                module_spec, import_name
-                                            ),
-                                            (0, 0),
-                                            file_path.clone(),
-                                        ));
-                                    }
-                                }
-                            } else {
-                                return Err(RaccoonError::new(
-                                    format!("Module {} is not an object", module_spec),
-                                    (0, 0),
-                                    file_path.clone(),
-                                ));
-                            }
-                        } else {
-                            for spec in &export_decl.specifiers {
-                                let exported_name =
-                                    spec.exported.clone().unwrap_or(spec.local.clone());
-                                if let Ok(val) = module_interp.get_from_env(&spec.local) {
-                                    exports.insert(exported_name, val);
-                                }
-                            }
-                        }
+                        exports.insert("default".to_string(), interpreter.get_from_env(name)?);
                     }
                 }
+            }
+        } else if let Some(decl) = &export_decl.declaration {
+            let name = match decl.as_ref() {
+                Stmt::FnDecl(f) => Some(f.name.clone()),
+                Stmt::ClassDecl(c) => Some(c.name.clone()),
+                Stmt::EnumDecl(e) => Some(e.name.clone()),
+                Stmt::InterfaceDecl(_) | Stmt::TypeAliasDecl(_) => None,
+                Stmt::VarDecl(v) => match &v.pattern {
+                    VarPattern::Identifier(id) => Some(id.clone()),
+                    _ => {
+                        return Err(RaccoonError::new(
+                            "Cannot export destructured variable",
+                            export_decl.position,
+                            file_path,
+                        ));
+                    }
+                },
                 _ => {
-                    module_interp.execute_stmt(stmt).await?;
+                    return Err(RaccoonError::new(
+                        "Invalid export declaration",
+                        export_decl.position,
+                        file_path,
+                    ));
+                }
+            };
+            interpreter.execute_stmt(decl).await?;
+            if let Some(name) = name {
+                if let Ok(val) = interpreter.get_from_env(&name) {
+                    exports.insert(name, val);
+                }
+            }
+        } else if let Some(module_spec) = &export_decl.module_specifier {
+            let source_module_path =
+                Self::resolve_relative_path(interpreter, module_spec, export_decl.position)?;
+            let source_module =
+                Self::load_file_module(interpreter, &source_module_path, export_decl.position)
+                    .await?;
+
+            if let RuntimeValue::Object(obj) = source_module {
+                for spec in &export_decl.specifiers {
+                    let import_name = &spec.local;
+                    let export_name = spec.exported.as_ref().unwrap_or(import_name);
+
+                    if let Some(val) = obj.get(import_name) {
+                        exports.insert(export_name.clone(), val);
+                    } else {
+                        return Err(Self::missing_export(
+                            interpreter,
+                            module_spec,
+                            &source_module_path,
+                            import_name,
+                            export_decl.position,
+                        ));
+                    }
+                }
+            } else {
+                return Err(RaccoonError::new(
+                    format!("Module {} is not an object", module_spec),
+                    export_decl.position,
+                    file_path,
+                ));
+            }
+        } else {
+            for spec in &export_decl.specifiers {
+                let exported_name = spec.exported.clone().unwrap_or(spec.local.clone());
+                if let Ok(val) = interpreter.get_from_env(&spec.local) {
+                    exports.insert(exported_name, val);
                 }
             }
         }
 
-        if let Some(default_val) = default_export {
-            exports.insert("default".into(), default_val);
+        let module = file_path
+            .as_deref()
+            .and_then(|file| interpreter.loading_modules.exports(file));
+        if let Some(module) = module {
+            module.properties.write().unwrap().extend(exports);
         }
 
-        Ok(RuntimeValue::Object(ObjectValue::new(
-            exports,
-            PrimitiveType::any(),
+        Ok(InterpreterResult::Value(RuntimeValue::Null(
+            NullValue::new(),
         )))
     }
+
+    fn missing_export(
+        interpreter: &Interpreter,
+        module_spec: &str,
+        module_path: &str,
+        export_name: &str,
+        position: Position,
+    ) -> RaccoonError {
+        let message = match interpreter.loading_modules.cycle(module_path) {
+            Some(cycle) => format!(
+                "Cannot import '{}' from {} before it is initialized: {}",
+                export_name, module_spec, cycle
+            ),
+            None => format!("{} does not export '{}'", module_spec, export_name),
+        };
+        RaccoonError::new(message, position, interpreter.file.clone())
+    }
 }
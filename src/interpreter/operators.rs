@@ -1,9 +1,7 @@
-use crate::ast::types::PrimitiveType;
-
 use crate::error::RaccoonError;
 use crate::runtime::types::operations;
 use crate::runtime::{
-    ArrayValue, BoolValue, CallStack, FloatValue, IntValue, RuntimeValue, StrValue,
+    ArrayValue, BoolValue, CallStack, FloatValue, IntValue, RangeValue, RuntimeValue, StrValue,
 };
 use crate::tokens::{BinaryOperator, Position, UnaryOperator};
 
@@ -18,6 +16,67 @@ pub fn is_truthy(value: &RuntimeValue) -> bool {
     }
 }
 
+pub fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let resolved = if index < 0 { len as i64 + index } else { index };
+    (0..len as i64).contains(&resolved).then_some(resolved as usize)
+}
+
+/// The key an object stores the property `key` indexes under.
+pub fn property_key(key: &RuntimeValue) -> String {
+    match key {
+        RuntimeValue::Str(s) => s.value.clone(),
+        RuntimeValue::Symbol(symbol) => symbol.property_key(),
+        other => other.to_string(),
+    }
+}
+
+fn slice_bounds(
+    len: usize,
+    start: Option<i64>,
+    end: Option<i64>,
+    inclusive: bool,
+) -> (usize, usize) {
+    let resolve = |bound: i64| if bound < 0 { len as i64 + bound } else { bound };
+    let clamp = |bound: i64| bound.clamp(0, len as i64) as usize;
+
+    let start = start.map_or(0, |start| clamp(resolve(start)));
+    let end = end.map_or(len, |end| clamp(resolve(end) + inclusive as i64));
+    (start, end.max(start))
+}
+
+pub fn slice_value(
+    value: &RuntimeValue,
+    start: Option<i64>,
+    end: Option<i64>,
+    inclusive: bool,
+) -> Result<RuntimeValue, String> {
+    match value {
+        RuntimeValue::Array(list) => {
+            let (start, end) = slice_bounds(list.len(), start, end, inclusive);
+            Ok(RuntimeValue::Array(ArrayValue::new(
+                list.elements.read().unwrap()[start..end].to_vec(),
+                list.element_type.clone(),
+            )))
+        }
+        RuntimeValue::Str(s) => {
+            let chars: Vec<char> = s.value.chars().collect();
+            let (start, end) = slice_bounds(chars.len(), start, end, inclusive);
+            Ok(RuntimeValue::Str(StrValue::new(
+                chars[start..end].iter().collect(),
+            )))
+        }
+        other => Err(format!("Cannot slice value of type {}", other.get_name())),
+    }
+}
+
+/// `value[range]`, for a range value used as an index.
+pub fn slice_by_range(value: &RuntimeValue, range: &RangeValue) -> Option<RuntimeValue> {
+    if range.step != 1 || !matches!(value, RuntimeValue::Array(_) | RuntimeValue::Str(_)) {
+        return None;
+    }
+    slice_value(value, Some(range.start), Some(range.end), range.inclusive).ok()
+}
+
 pub async fn apply_binary_op(
     left: RuntimeValue,
     right: RuntimeValue,
@@ -45,6 +104,7 @@ pub async fn apply_binary_op(
         | BinaryOperator::GreaterEqual
         | BinaryOperator::Equal
         | BinaryOperator::NotEqual
+        | BinaryOperator::In
         | BinaryOperator::And
         | BinaryOperator::Or => {
             operations::apply_binary_operation(left, right, operator, position, file, call_stack)
@@ -53,14 +113,7 @@ pub async fn apply_binary_op(
 
         BinaryOperator::Range => match (left, right) {
             (RuntimeValue::Int(l), RuntimeValue::Int(r)) => {
-                let mut elements = Vec::new();
-                for i in l.value..=r.value {
-                    elements.push(RuntimeValue::Int(IntValue::new(i)));
-                }
-                Ok(RuntimeValue::Array(ArrayValue::new(
-                    elements,
-                    PrimitiveType::int(),
-                )))
+                Ok(RuntimeValue::Range(RangeValue::new(l.value, r.value, true)))
             }
             _ => Err(RaccoonError::with_call_stack(
                 "Range operator requires integer operands".to_string(),
@@ -374,97 +427,18 @@ where
         BinaryOperator::Equal => Ok(RuntimeValue::Bool(BoolValue::new(left.equals(&right)))),
         BinaryOperator::NotEqual => Ok(RuntimeValue::Bool(BoolValue::new(!left.equals(&right)))),
 
-        BinaryOperator::LessThan => match (left, right) {
-            (RuntimeValue::Int(l), RuntimeValue::Int(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value < r.value)))
-            }
-            (RuntimeValue::Float(l), RuntimeValue::Float(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value < r.value)))
-            }
-            (RuntimeValue::Int(l), RuntimeValue::Float(r)) => Ok(RuntimeValue::Bool(
-                BoolValue::new((l.value as f64) < r.value),
-            )),
-            (RuntimeValue::Float(l), RuntimeValue::Int(r)) => Ok(RuntimeValue::Bool(
-                BoolValue::new(l.value < (r.value as f64)),
-            )),
-            (RuntimeValue::Str(l), RuntimeValue::Str(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value < r.value)))
-            }
-            _ => Err(RaccoonError::new(
-                "Invalid operands for less than comparison".to_string(),
-                position,
-                file.clone(),
-            )),
-        },
-
-        BinaryOperator::LessEqual => match (left, right) {
-            (RuntimeValue::Int(l), RuntimeValue::Int(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value <= r.value)))
-            }
-            (RuntimeValue::Float(l), RuntimeValue::Float(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value <= r.value)))
-            }
-            (RuntimeValue::Int(l), RuntimeValue::Float(r)) => Ok(RuntimeValue::Bool(
-                BoolValue::new((l.value as f64) <= r.value),
-            )),
-            (RuntimeValue::Float(l), RuntimeValue::Int(r)) => Ok(RuntimeValue::Bool(
-                BoolValue::new(l.value <= (r.value as f64)),
-            )),
-            (RuntimeValue::Str(l), RuntimeValue::Str(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value <= r.value)))
-            }
-            _ => Err(RaccoonError::new(
-                "Invalid operands for less than or equal comparison".to_string(),
-                position,
-                file.clone(),
-            )),
-        },
-
-        BinaryOperator::GreaterThan => match (left, right) {
-            (RuntimeValue::Int(l), RuntimeValue::Int(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value > r.value)))
-            }
-            (RuntimeValue::Float(l), RuntimeValue::Float(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value > r.value)))
-            }
-            (RuntimeValue::Int(l), RuntimeValue::Float(r)) => Ok(RuntimeValue::Bool(
-                BoolValue::new((l.value as f64) > r.value),
-            )),
-            (RuntimeValue::Float(l), RuntimeValue::Int(r)) => Ok(RuntimeValue::Bool(
-                BoolValue::new(l.value > (r.value as f64)),
-            )),
-            (RuntimeValue::Str(l), RuntimeValue::Str(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value > r.value)))
-            }
-            _ => Err(RaccoonError::new(
-                "Invalid operands for greater than comparison".to_string(),
-                position,
-                file.clone(),
-            )),
-        },
+        BinaryOperator::LessThan => operations::comparison::less_than(left, right, position, file),
+        BinaryOperator::LessEqual => {
+            operations::comparison::less_or_equal(left, right, position, file)
+        }
+        BinaryOperator::GreaterThan => {
+            operations::comparison::greater_than(left, right, position, file)
+        }
+        BinaryOperator::GreaterEqual => {
+            operations::comparison::greater_or_equal(left, right, position, file)
+        }
 
-        BinaryOperator::GreaterEqual => match (left, right) {
-            (RuntimeValue::Int(l), RuntimeValue::Int(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value >= r.value)))
-            }
-            (RuntimeValue::Float(l), RuntimeValue::Float(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value >= r.value)))
-            }
-            (RuntimeValue::Int(l), RuntimeValue::Float(r)) => Ok(RuntimeValue::Bool(
-                BoolValue::new((l.value as f64) >= r.value),
-            )),
-            (RuntimeValue::Float(l), RuntimeValue::Int(r)) => Ok(RuntimeValue::Bool(
-                BoolValue::new(l.value >= (r.value as f64)),
-            )),
-            (RuntimeValue::Str(l), RuntimeValue::Str(r)) => {
-                Ok(RuntimeValue::Bool(BoolValue::new(l.value >= r.value)))
-            }
-            _ => Err(RaccoonError::new(
-                "Invalid operands for greater than or equal comparison".to_string(),
-                position,
-                file.clone(),
-            )),
-        },
+        BinaryOperator::In => operations::comparison::contains(left, right, position, file),
 
         BinaryOperator::And => {
             if !is_truthy(&left) {
@@ -483,14 +457,7 @@ where
 
         BinaryOperator::Range => match (left, right) {
             (RuntimeValue::Int(l), RuntimeValue::Int(r)) => {
-                let mut elements = Vec::new();
-                for i in l.value..=r.value {
-                    elements.push(RuntimeValue::Int(IntValue::new(i)));
-                }
-                Ok(RuntimeValue::Array(ArrayValue::new(
-                    elements,
-                    PrimitiveType::int(),
-                )))
+                Ok(RuntimeValue::Range(RangeValue::new(l.value, r.value, true)))
             }
             _ => Err(RaccoonError::with_call_stack(
                 "Range operator requires integer operands".to_string(),
@@ -0,0 +1,309 @@
+use crate::error::RaccoonError;
+use crate::runtime::types::operations::comparison;
+use crate::runtime::{BoolValue, ClassInstance, RuntimeValue, StrValue};
+use crate::tokens::{BinaryOperator, Position};
+use async_recursion::async_recursion;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use super::helpers::Helpers;
+use super::Interpreter;
+
+/// Dispatch of operators and conversions to magic methods on class instances.
+pub struct Overloads;
+
+impl Overloads {
+    pub fn method_name(operator: BinaryOperator) -> Option<&'static str> {
+        match operator {
+            BinaryOperator::Add => Some("__add__"),
+            BinaryOperator::Subtract => Some("__sub__"),
+            BinaryOperator::Multiply => Some("__mul__"),
+            BinaryOperator::Divide => Some("__div__"),
+            BinaryOperator::Modulo => Some("__mod__"),
+            _ => None,
+        }
+    }
+
+    /// `None` when neither operand overloads `operator`.
+    pub async fn binary(
+        interpreter: &mut Interpreter,
+        left: &RuntimeValue,
+        right: &RuntimeValue,
+        operator: BinaryOperator,
+        position: Position,
+    ) -> Result<Option<RuntimeValue>, RaccoonError> {
+        let instance = |value: &RuntimeValue| match value {
+            RuntimeValue::ClassInstance(instance) => Some(instance.clone()),
+            _ => None,
+        };
+        let holds: Option<fn(Ordering) -> bool> = match operator {
+            BinaryOperator::LessThan => Some(Ordering::is_lt),
+            BinaryOperator::LessEqual => Some(Ordering::is_le),
+            BinaryOperator::GreaterThan => Some(Ordering::is_gt),
+            BinaryOperator::GreaterEqual => Some(Ordering::is_ge),
+            _ => None,
+        };
+        if instance(left).is_none() && instance(right).is_none() {
+            // Lists and tuples may hold instances, which only `compare` can order.
+            return match holds {
+                Some(holds) if Self::sequences(left, right) => {
+                    Self::ordered(interpreter, left, right, holds, position).await
+                }
+                _ => Ok(None),
+            };
+        }
+
+        if let Some(name) = Self::method_name(operator) {
+            if let Some(receiver) = instance(left) {
+                let args = vec![right.clone()];
+                if let Some(result) =
+                    Self::call(interpreter, &receiver, name, args, position).await?
+                {
+                    return Ok(Some(result));
+                }
+            }
+
+            if operator == BinaryOperator::Add {
+                return Self::concat(interpreter, left, right, position).await;
+            }
+            return Ok(None);
+        }
+
+        // `a > b` is `b < a`, and `<=` / `>=` negate the opposite comparison.
+        let (name, receiver, argument, negate) = match operator {
+            BinaryOperator::Equal => ("__eq__", left, right, false),
+            BinaryOperator::NotEqual => ("__eq__", left, right, true),
+            BinaryOperator::LessThan => ("__lt__", left, right, false),
+            BinaryOperator::GreaterThan => ("__lt__", right, left, false),
+            BinaryOperator::LessEqual => ("__lt__", right, left, true),
+            BinaryOperator::GreaterEqual => ("__lt__", left, right, true),
+            _ => return Ok(None),
+        };
+
+        let mut result = None;
+        if let Some(receiver) = instance(receiver) {
+            let args = vec![argument.clone()];
+            result = Self::call(interpreter, &receiver, name, args, position).await?;
+        }
+        if result.is_none() && name == "__eq__" {
+            if let Some(other) = instance(argument) {
+                let args = vec![receiver.clone()];
+                result = Self::call(interpreter, &other, name, args, position).await?;
+            }
+        }
+
+        match (result, holds) {
+            (Some(value), _) => {
+                let truthy = interpreter.is_truthy(&value);
+                Ok(Some(RuntimeValue::Bool(BoolValue::new(truthy != negate))))
+            }
+            (None, Some(holds)) => Self::ordered(interpreter, left, right, holds, position).await,
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Whether `binary` may dispatch on these operands.
+    pub fn applies(left: &RuntimeValue, right: &RuntimeValue) -> bool {
+        matches!(left, RuntimeValue::ClassInstance(_))
+            || matches!(right, RuntimeValue::ClassInstance(_))
+            || Self::sequences(left, right)
+    }
+
+    fn sequences(left: &RuntimeValue, right: &RuntimeValue) -> bool {
+        matches!(
+            (left, right),
+            (RuntimeValue::Array(_), RuntimeValue::Array(_))
+                | (RuntimeValue::Tuple(_), RuntimeValue::Tuple(_))
+        )
+    }
+
+    async fn ordered(
+        interpreter: &mut Interpreter,
+        left: &RuntimeValue,
+        right: &RuntimeValue,
+        holds: fn(Ordering) -> bool,
+        position: Position,
+    ) -> Result<Option<RuntimeValue>, RaccoonError> {
+        let order = Self::compare(interpreter, left, right, position).await?;
+        Ok(order.map(|order| RuntimeValue::Bool(BoolValue::new(holds(order)))))
+    }
+
+    /// The order of `left` and `right`, through `compareTo` for class instances.
+    #[async_recursion(?Send)]
+    pub async fn compare(
+        interpreter: &mut Interpreter,
+        left: &RuntimeValue,
+        right: &RuntimeValue,
+        position: Position,
+    ) -> Result<Option<Ordering>, RaccoonError> {
+        if let Some(order) = comparison::compare(left, right) {
+            return Ok(Some(order));
+        }
+
+        let comparable = |value: &RuntimeValue| match value {
+            RuntimeValue::ClassInstance(instance) if instance.methods.contains_key("compareTo") => {
+                Some(instance.clone())
+            }
+            _ => None,
+        };
+        if let Some(receiver) = comparable(left) {
+            return Self::compare_to(interpreter, &receiver, right, position)
+                .await
+                .map(Some);
+        }
+        if let Some(receiver) = comparable(right) {
+            let order = Self::compare_to(interpreter, &receiver, left, position).await?;
+            return Ok(Some(order.reverse()));
+        }
+
+        match (left, right) {
+            (RuntimeValue::Array(a), RuntimeValue::Array(b)) => {
+                let (a, b) = (a.snapshot(), b.snapshot());
+                Self::lexicographic(interpreter, &a, &b, position).await
+            }
+            (RuntimeValue::Tuple(a), RuntimeValue::Tuple(b)) => {
+                Self::lexicographic(interpreter, &a.elements, &b.elements, position).await
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn lexicographic(
+        interpreter: &mut Interpreter,
+        left: &[RuntimeValue],
+        right: &[RuntimeValue],
+        position: Position,
+    ) -> Result<Option<Ordering>, RaccoonError> {
+        for (a, b) in left.iter().zip(right) {
+            match Self::compare(interpreter, a, b, position).await? {
+                Some(Ordering::Equal) => {}
+                order => return Ok(order),
+            }
+        }
+        Ok(Some(left.len().cmp(&right.len())))
+    }
+
+    async fn compare_to(
+        interpreter: &mut Interpreter,
+        receiver: &Arc<ClassInstance>,
+        other: &RuntimeValue,
+        position: Position,
+    ) -> Result<Ordering, RaccoonError> {
+        let args = vec![other.clone()];
+        match Self::call(interpreter, receiver, "compareTo", args, position).await? {
+            Some(RuntimeValue::Int(order)) => Ok(order.value.cmp(&0)),
+            result => Err(RaccoonError::new(
+                format!(
+                    "{}.compareTo must return an Ordering, got {}",
+                    receiver.class_name,
+                    result.map_or("nothing".to_string(), |value| value.get_name()),
+                ),
+                position,
+                interpreter.file.clone(),
+            )),
+        }
+    }
+
+    pub async fn index(
+        interpreter: &mut Interpreter,
+        instance: &Arc<ClassInstance>,
+        index: &RuntimeValue,
+        position: Position,
+    ) -> Result<Option<RuntimeValue>, RaccoonError> {
+        Self::call(
+            interpreter,
+            instance,
+            "__index__",
+            vec![index.clone()],
+            position,
+        )
+        .await
+    }
+
+    /// String form of a value, using `__str__` for class instances that define it.
+    #[async_recursion(?Send)]
+    pub async fn stringify(
+        interpreter: &mut Interpreter,
+        value: &RuntimeValue,
+        position: Position,
+    ) -> Result<String, RaccoonError> {
+        let RuntimeValue::ClassInstance(instance) = value else {
+            return Ok(value.to_string());
+        };
+
+        match Self::call(interpreter, instance, "__str__", Vec::new(), position).await? {
+            Some(RuntimeValue::Str(s)) => Ok(s.value),
+            Some(other) => Self::stringify(interpreter, &other, position).await,
+            None => Ok(value.to_string()),
+        }
+    }
+
+    /// Converts instances that define `__str__` to strings, leaving other values untouched.
+    pub async fn stringify_args(
+        interpreter: &mut Interpreter,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<Vec<RuntimeValue>, RaccoonError> {
+        let mut converted = Vec::with_capacity(args.len());
+        for arg in args {
+            match &arg {
+                RuntimeValue::ClassInstance(instance)
+                    if instance.methods.contains_key("__str__") =>
+                {
+                    let text = Self::stringify(interpreter, &arg, position).await?;
+                    converted.push(RuntimeValue::Str(StrValue::new(text)));
+                }
+                _ => converted.push(arg),
+            }
+        }
+        Ok(converted)
+    }
+
+    async fn concat(
+        interpreter: &mut Interpreter,
+        left: &RuntimeValue,
+        right: &RuntimeValue,
+        position: Position,
+    ) -> Result<Option<RuntimeValue>, RaccoonError> {
+        if !matches!(left, RuntimeValue::Str(_)) && !matches!(right, RuntimeValue::Str(_)) {
+            return Ok(None);
+        }
+
+        let left = Self::stringify(interpreter, left, position).await?;
+        let right = Self::stringify(interpreter, right, position).await?;
+        Ok(Some(RuntimeValue::Str(StrValue::new(left + &right))))
+    }
+
+    pub async fn call(
+        interpreter: &mut Interpreter,
+        instance: &Arc<ClassInstance>,
+        name: &str,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<Option<RuntimeValue>, RaccoonError> {
+        let Some(method) = instance.methods.get(name) else {
+            return Ok(None);
+        };
+        if method.body.is_empty() {
+            // Instances of classes the VM compiled only list their methods.
+            let args = args.clone();
+            if let Some(result) =
+                crate::ir::VM::call_method_from_interpreter(interpreter, instance, name, args)
+                    .await?
+            {
+                return Ok(Some(result));
+            }
+        }
+
+        let owner = instance
+            .method_owners
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| instance.class_name.clone());
+        let function = method.clone().with_name(format!("{}.{}", owner, name));
+
+        Helpers::invoke_method(interpreter, instance, &owner, function, args, position)
+            .await
+            .map(Some)
+    }
+}
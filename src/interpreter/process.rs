@@ -0,0 +1,204 @@
+use crate::error::RaccoonError;
+use crate::runtime::{FromRaccoon, IntValue, NullValue, RuntimeValue, StrValue};
+use crate::tokens::Position;
+use std::cell::RefCell;
+
+use super::declarations::Declarations;
+use super::helpers::Helpers;
+use super::Interpreter;
+
+thread_local! {
+    static EXIT_HANDLERS: RefCell<Vec<RuntimeValue>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The `process.onSignal`, `process.onExit` and `process.exit` builtins.
+pub struct ProcessHooks;
+
+impl ProcessHooks {
+    pub const METHODS: [&'static str; 3] = ["onSignal", "onExit", "exit"];
+
+    pub async fn call(
+        interpreter: &mut Interpreter,
+        method: &str,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        // Listening for signals and exiting count as process access, like `core_exit`.
+        let native = match method {
+            "onSignal" => Some("process_signal"),
+            "exit" => Some("exit"),
+            _ => None,
+        };
+        if let (Some(sandbox), Some(native)) = (&interpreter.sandbox, native) {
+            sandbox.check_call(native, Some("process"), &args, position, &interpreter.file)?;
+        }
+
+        match method {
+            "onSignal" => Self::on_signal(interpreter, args, position),
+            "onExit" => {
+                let handler = Self::handler(interpreter, args.first(), "onExit", position)?;
+                EXIT_HANDLERS.with(|handlers| handlers.borrow_mut().push(handler));
+                Ok(RuntimeValue::Null(NullValue::new()))
+            }
+            _ => {
+                let code = args
+                    .first()
+                    .and_then(|code| i32::from_raccoon(code).ok())
+                    .unwrap_or(0);
+                Self::run_exit_handlers(interpreter, code, position).await;
+                std::process::exit(code);
+            }
+        }
+    }
+
+    /// Runs the registered exit handlers, each at most once.
+    pub async fn run_exit_handlers(interpreter: &mut Interpreter, code: i32, position: Position) {
+        let handlers = EXIT_HANDLERS.with(|handlers| std::mem::take(&mut *handlers.borrow_mut()));
+        for handler in handlers {
+            let args = vec![RuntimeValue::Int(IntValue::new(code as i64))];
+            if let Err(error) = Self::run_handler(interpreter, &handler, args, position).await {
+                eprintln!("{}", error);
+            }
+        }
+    }
+
+    fn on_signal(
+        interpreter: &mut Interpreter,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let name = match args.first() {
+            Some(RuntimeValue::Str(name)) => name.value.clone(),
+            other => {
+                return Err(RaccoonError::new(
+                    format!(
+                        "process.onSignal() expects a signal name, got {}",
+                        other.map_or("nothing".to_string(), RuntimeValue::get_name)
+                    ),
+                    position,
+                    interpreter.file.clone(),
+                ))
+            }
+        };
+        let handler = Self::handler(interpreter, args.get(1), "onSignal", position)?;
+        let mut listener = signal::listen(&name)
+            .map_err(|message| RaccoonError::new(message, position, interpreter.file.clone()))?;
+
+        let mut forked = interpreter.fork(interpreter.environment.clone());
+        tokio::task::spawn_local(async move {
+            while listener.recv().await {
+                let args = vec![RuntimeValue::Str(StrValue::new(name.clone()))];
+                if let Err(error) = Self::run_handler(&mut forked, &handler, args, position).await {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+            }
+        });
+        Ok(RuntimeValue::Null(NullValue::new()))
+    }
+
+    fn handler(
+        interpreter: &Interpreter,
+        handler: Option<&RuntimeValue>,
+        method: &str,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        match handler {
+            Some(
+                handler @ (RuntimeValue::Function(_)
+                | RuntimeValue::NativeFunction(_)
+                | RuntimeValue::NativeAsyncFunction(_)),
+            ) => Ok(handler.clone()),
+            other => Err(RaccoonError::new(
+                format!(
+                    "process.{}() handler must be a function, got {}",
+                    method,
+                    other.map_or("nothing".to_string(), RuntimeValue::get_name)
+                ),
+                position,
+                interpreter.file.clone(),
+            )),
+        }
+    }
+
+    async fn run_handler(
+        interpreter: &mut Interpreter,
+        handler: &RuntimeValue,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        match Helpers::call_function(interpreter, handler, args, position).await? {
+            RuntimeValue::Future(future) => future
+                .wait_for_completion()
+                .await
+                .map_err(|rejection| {
+                    Declarations::rejected_error(
+                        &interpreter.environment,
+                        rejection,
+                        position,
+                        interpreter.file.clone(),
+                    )
+                }),
+            value => Ok(value),
+        }
+    }
+}
+
+#[cfg(unix)]
+mod signal {
+    use tokio::signal::unix::{self, Signal, SignalKind};
+
+    pub struct Listener(Signal);
+
+    impl Listener {
+        pub async fn recv(&mut self) -> bool {
+            self.0.recv().await.is_some()
+        }
+    }
+
+    pub fn listen(name: &str) -> Result<Listener, String> {
+        let kind = match name {
+            "SIGINT" => SignalKind::interrupt(),
+            "SIGTERM" => SignalKind::terminate(),
+            "SIGHUP" => SignalKind::hangup(),
+            "SIGQUIT" => SignalKind::quit(),
+            "SIGUSR1" => SignalKind::user_defined1(),
+            "SIGUSR2" => SignalKind::user_defined2(),
+            "SIGWINCH" => SignalKind::window_change(),
+            _ => {
+                return Err(format!(
+                    "Unsupported signal '{}' (expected SIGINT, SIGTERM, SIGHUP, SIGQUIT, SIGUSR1, SIGUSR2 or SIGWINCH)",
+                    name
+                ))
+            }
+        };
+        unix::signal(kind)
+            .map(Listener)
+            .map_err(|error| format!("Cannot listen for {}: {}", name, error))
+    }
+}
+
+#[cfg(not(unix))]
+mod signal {
+    use tokio::signal::windows::{self, CtrlC};
+
+    pub struct Listener(CtrlC);
+
+    impl Listener {
+        pub async fn recv(&mut self) -> bool {
+            self.0.recv().await.is_some()
+        }
+    }
+
+    pub fn listen(name: &str) -> Result<Listener, String> {
+        if name != "SIGINT" {
+            return Err(format!(
+                "Unsupported signal '{}' (only SIGINT is available on this platform)",
+                name
+            ));
+        }
+        windows::ctrl_c()
+            .map(Listener)
+            .map_err(|error| format!("Cannot listen for {}: {}", name, error))
+    }
+}
@@ -0,0 +1,186 @@
+use crate::ast::types::PrimitiveType;
+use crate::runtime::builtins::future;
+use crate::runtime::cancellation::cancellation_message;
+use crate::runtime::{
+    CancellationToken, FutureState, FutureValue, IntValue, NativeAsyncFunctionValue, NullValue,
+    ObjectValue, Rejection, RuntimeValue,
+};
+use crate::tokens::Position;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+use super::helpers::Helpers;
+use super::Interpreter;
+
+type Spawned = Arc<Mutex<Vec<(RuntimeValue, FutureValue)>>>;
+
+/// The `taskGroup(body)` builtin.
+pub struct TaskGroup {
+    token: CancellationToken,
+    spawned: Spawned,
+    wake: Arc<Notify>,
+    children: Vec<FutureValue>,
+}
+
+impl TaskGroup {
+    pub fn start(
+        interpreter: &Interpreter,
+        args: Vec<RuntimeValue>,
+        position: Position,
+    ) -> RuntimeValue {
+        let body = match args.into_iter().next() {
+            Some(body @ (RuntimeValue::Function(_) | RuntimeValue::NativeFunction(_))) => body,
+            _ => {
+                return RuntimeValue::Future(FutureValue::new_rejected(
+                    "taskGroup requires a function".to_string(),
+                    PrimitiveType::any(),
+                ))
+            }
+        };
+
+        let mut group = TaskGroup {
+            token: CancellationToken::new(),
+            spawned: Arc::new(Mutex::new(Vec::new())),
+            wake: Arc::new(Notify::new()),
+            children: Vec::new(),
+        };
+        let parent = interpreter.fork(interpreter.environment.clone());
+        future::spawn(async move { group.run(parent, body, position).await })
+    }
+
+    async fn run(
+        &mut self,
+        parent: Interpreter,
+        body: RuntimeValue,
+        position: Position,
+    ) -> Result<RuntimeValue, Rejection> {
+        let body_result = FutureValue::new(PrimitiveType::any());
+        self.launch(&parent, body, self.handle(), body_result.clone(), position);
+
+        loop {
+            let spawned = std::mem::take(&mut *self.spawned.lock().unwrap());
+            for (task, child) in spawned {
+                self.launch(&parent, task, self.signal(), child.clone(), position);
+                self.children.push(child);
+            }
+
+            let failure = std::iter::once(&body_result)
+                .chain(&self.children)
+                .find_map(|future| match &*future.state.read().unwrap() {
+                    FutureState::Rejected(error) => Some(error.clone()),
+                    _ => None,
+                });
+            if let Some(error) = failure {
+                self.cancel_children(&error.to_string());
+                return Err(error);
+            }
+
+            let finished = !body_result.is_pending()
+                && self.children.iter().all(|child| !child.is_pending())
+                && self.spawned.lock().unwrap().is_empty();
+            if finished {
+                return body_result.wait_for_completion().await;
+            }
+
+            self.wake.notified().await;
+        }
+    }
+
+    fn launch(
+        &self,
+        parent: &Interpreter,
+        callable: RuntimeValue,
+        arg: RuntimeValue,
+        target: FutureValue,
+        position: Position,
+    ) {
+        let mut worker = parent.fork(parent.environment.clone());
+        let wake = Arc::clone(&self.wake);
+        tokio::task::spawn_local(async move {
+            let outcome =
+                match Helpers::call_function(&mut worker, &callable, vec![arg], position).await {
+                    Ok(value) => future::settle(&value).await,
+                    Err(error) => Err(error.into()),
+                };
+            if target.is_pending() {
+                match outcome {
+                    Ok(value) => target.resolve(value),
+                    Err(error) => target.reject(error),
+                }
+            }
+            wake.notify_one();
+        });
+    }
+
+    fn cancel_children(&self, reason: &str) {
+        self.token.cancel(reason);
+        for child in &self.children {
+            if child.is_pending() {
+                child.reject(cancellation_message(reason));
+            }
+        }
+    }
+
+    fn signal(&self) -> RuntimeValue {
+        let mut properties = HashMap::new();
+        properties.insert(
+            "id".to_string(),
+            RuntimeValue::Int(IntValue::new(self.token.id() as i64)),
+        );
+        RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+    }
+
+    fn handle(&self) -> RuntimeValue {
+        let (token, spawned, wake) = (
+            self.token.clone(),
+            Arc::clone(&self.spawned),
+            Arc::clone(&self.wake),
+        );
+        let spawn = NativeAsyncFunctionValue::new(
+            Arc::new(move |args: Vec<RuntimeValue>| {
+                let child = match (token.check(), args.into_iter().next()) {
+                    (Err(message), _) => FutureValue::new_rejected(message, PrimitiveType::any()),
+                    (Ok(()), Some(task)) => {
+                        let child = FutureValue::new(PrimitiveType::any());
+                        spawned.lock().unwrap().push((task, child.clone()));
+                        wake.notify_one();
+                        child
+                    }
+                    (Ok(()), None) => FutureValue::new_rejected(
+                        "group.spawn requires a function".to_string(),
+                        PrimitiveType::any(),
+                    ),
+                };
+                Box::pin(async move { RuntimeValue::Future(child) })
+            }),
+            PrimitiveType::any(),
+        );
+
+        let (token, wake) = (self.token.clone(), Arc::clone(&self.wake));
+        let cancel = NativeAsyncFunctionValue::new(
+            Arc::new(move |args: Vec<RuntimeValue>| {
+                let reason = match args.first() {
+                    None | Some(RuntimeValue::Null(_)) => "The task group was cancelled".into(),
+                    Some(reason) => reason.to_string(),
+                };
+                token.cancel(reason);
+                wake.notify_one();
+                Box::pin(async { RuntimeValue::Null(NullValue::new()) })
+            }),
+            PrimitiveType::any(),
+        );
+
+        let mut properties = HashMap::new();
+        properties.insert("signal".to_string(), self.signal());
+        properties.insert(
+            "spawn".to_string(),
+            RuntimeValue::NativeAsyncFunction(Arc::new(spawn)),
+        );
+        properties.insert(
+            "cancel".to_string(),
+            RuntimeValue::NativeAsyncFunction(Arc::new(cancel)),
+        );
+        RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+    }
+}
@@ -1,6 +1,15 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::analyzer::const_eval::{self, ConstScope};
 use crate::ast::nodes::*;
 use crate::error::RaccoonError;
+use crate::interpreter::declarations::Declarations;
+use crate::runtime::types::operations::overloading::Signature;
 use crate::runtime::RuntimeValue;
+use crate::tokens::{AccessModifier, BinaryOperator, TokenType};
 
 use super::instruction::{
     IRProgram, Instruction, MatchArm as IRMatchArm, MatchPattern as IRMatchPattern, Register,
@@ -12,6 +21,29 @@ pub struct IRCompiler {
     temp_counter: usize,
     label_counter: usize,
     scope_depth: usize,
+    try_regions: Vec<TryRegion>,
+    catch_errors: Vec<Register>,
+    jump_targets: Vec<JumpTarget>,
+    strip_assertions: bool,
+    constants: HashMap<String, RuntimeValue>,
+    is_nested: bool,
+    interfaces: Declared<InterfaceDecl>,
+    classes: Declared<ClassDecl>,
+}
+
+type Declared<T> = Rc<RefCell<HashMap<String, T>>>;
+
+struct JumpTarget {
+    break_label: String,
+    continue_label: Option<String>,
+    scope_depth: usize,
+    try_depth: usize,
+}
+
+struct TryRegion {
+    has_handler: bool,
+    finally_block: Option<Block>,
+    resource: Option<Register>,
 }
 
 impl IRCompiler {
@@ -21,7 +53,65 @@ impl IRCompiler {
             temp_counter: 0,
             label_counter: 0,
             scope_depth: 0,
+            try_regions: Vec::new(),
+            catch_errors: Vec::new(),
+            jump_targets: Vec::new(),
+            strip_assertions: false,
+            constants: HashMap::new(),
+            is_nested: false,
+            interfaces: Declared::default(),
+            classes: Declared::default(),
+        }
+    }
+
+    pub fn strip_assertions(mut self, strip: bool) -> Self {
+        self.strip_assertions = strip;
+        self
+    }
+
+    pub fn constants(mut self, constants: HashMap<String, RuntimeValue>) -> Self {
+        self.constants = constants;
+        self
+    }
+
+    fn nested(&self) -> IRCompiler {
+        let mut compiler = IRCompiler::new()
+            .strip_assertions(self.strip_assertions)
+            .constants(self.constants.clone());
+        compiler.is_nested = true;
+        compiler.interfaces = self.interfaces.clone();
+        compiler.classes = self.classes.clone();
+        compiler
+    }
+
+    fn function_body(&self, parameters: &[FnParam]) -> IRCompiler {
+        let mut compiler = self.nested();
+        for param in parameters {
+            match &param.pattern {
+                VarPattern::Identifier(name) => compiler.bind(name),
+                VarPattern::Destructuring(pattern) => {
+                    for name in pattern.bound_names() {
+                        compiler.bind(&name);
+                    }
+                }
+            }
         }
+        compiler
+    }
+
+    // A name bound again below the top level no longer means the folded constant.
+    fn bind(&mut self, name: &str) {
+        if self.is_nested || self.scope_depth > 0 {
+            self.constants.remove(name);
+        }
+    }
+
+    fn declare(&mut self, name: &str, is_const: bool) {
+        self.bind(name);
+        self.program.emit(Instruction::Declare {
+            name: name.to_string(),
+            is_const,
+        });
     }
 
     pub fn compile(mut self, program: &Program) -> Result<IRProgram, RaccoonError> {
@@ -54,9 +144,9 @@ impl IRCompiler {
             Stmt::VarDecl(decl) => self.compile_var_decl(decl),
             Stmt::FnDecl(decl) => self.compile_fn_decl(decl),
             Stmt::ClassDecl(decl) => self.compile_class_decl(decl),
-            Stmt::InterfaceDecl(_) => Ok(()),
+            Stmt::InterfaceDecl(decl) => self.compile_interface_decl(decl),
             Stmt::EnumDecl(decl) => self.compile_enum_decl(decl),
-            Stmt::TypeAliasDecl(_) => Ok(()),
+            Stmt::TypeAliasDecl(decl) => self.compile_type_alias_decl(decl),
             Stmt::ImportDecl(_) => Ok(()),
             Stmt::ExportDecl(_) => Ok(()),
             Stmt::Block(block) => self.compile_block(block),
@@ -69,16 +159,40 @@ impl IRCompiler {
             Stmt::SwitchStmt(switch_stmt) => self.compile_switch_stmt(switch_stmt),
             Stmt::ReturnStmt(ret) => self.compile_return_stmt(ret),
             Stmt::BreakStmt(_) => {
-                self.program.emit(Instruction::Jump {
-                    label: "break".to_string(),
-                });
-                Ok(())
+                let (label, scope_depth, try_depth) = match self.jump_targets.last() {
+                    Some(target) => (
+                        target.break_label.clone(),
+                        target.scope_depth,
+                        target.try_depth,
+                    ),
+                    None => (
+                        "break".to_string(),
+                        self.scope_depth,
+                        self.try_regions.len(),
+                    ),
+                };
+                self.jump_out_of(label, scope_depth, try_depth)
             }
             Stmt::ContinueStmt(_) => {
-                self.program.emit(Instruction::Jump {
-                    label: "continue".to_string(),
-                });
-                Ok(())
+                let (label, scope_depth, try_depth) = self
+                    .jump_targets
+                    .iter()
+                    .rev()
+                    .find_map(|target| {
+                        Some((
+                            target.continue_label.clone()?,
+                            target.scope_depth,
+                            target.try_depth,
+                        ))
+                    })
+                    .unwrap_or_else(|| {
+                        (
+                            "continue".to_string(),
+                            self.scope_depth,
+                            self.try_regions.len(),
+                        )
+                    });
+                self.jump_out_of(label, scope_depth, try_depth)
             }
             Stmt::ExprStmt(expr_stmt) => {
                 self.compile_expr(&expr_stmt.expression)?;
@@ -86,16 +200,40 @@ impl IRCompiler {
             }
             Stmt::TryStmt(try_stmt) => self.compile_try_stmt(try_stmt),
             Stmt::ThrowStmt(throw) => self.compile_throw_stmt(throw),
+            Stmt::AssertStmt(_) if self.strip_assertions => Ok(()),
+            Stmt::AssertStmt(assert) => self.compile_assert_stmt(assert),
+            Stmt::UsingStmt(using) => self.compile_using_stmt(using),
+            Stmt::DeferStmt(defer) => self.compile_defer_stmt(defer),
         }
     }
 
+    fn jump_out_of(
+        &mut self,
+        label: String,
+        scope_depth: usize,
+        try_depth: usize,
+    ) -> Result<(), RaccoonError> {
+        self.unwind_try_regions(try_depth)?;
+        for _ in scope_depth..self.scope_depth {
+            self.program.emit(Instruction::PopScope);
+        }
+        self.program.emit(Instruction::Jump { label });
+        Ok(())
+    }
+
+    fn push_jump_target(&mut self, break_label: String, continue_label: Option<String>) {
+        self.jump_targets.push(JumpTarget {
+            break_label,
+            continue_label,
+            scope_depth: self.scope_depth,
+            try_depth: self.try_regions.len(),
+        });
+    }
+
     fn compile_var_decl(&mut self, decl: &VarDecl) -> Result<(), RaccoonError> {
         match &decl.pattern {
             VarPattern::Identifier(name) => {
-                self.program.emit(Instruction::Declare {
-                    name: name.clone(),
-                    is_const: decl.is_constant,
-                });
+                self.declare(name, decl.is_constant);
 
                 if let Some(init) = &decl.initializer {
                     let value_reg = self.compile_expr(init)?;
@@ -123,124 +261,116 @@ impl IRCompiler {
     ) -> Result<(), RaccoonError> {
         match pattern {
             DestructuringPattern::Array(array_pattern) => {
-                let mut dests = Vec::new();
-                let mut var_names = Vec::new();
-
-                // Use temporary registers for all destinations
-                for element in &array_pattern.elements {
-                    if let Some(elem) = element {
-                        let dest = self.next_temp();
-                        let var_name = match elem {
-                            ArrayPatternElement::Identifier(ident) => Some(ident.name.clone()),
-                            _ => None,
-                        };
-                        dests.push(dest);
-                        var_names.push(var_name);
-                    }
-                }
-
-                let rest_dest = array_pattern
-                    .rest
-                    .as_ref()
-                    .map(|_| self.next_temp());
-
-                let rest_name = array_pattern
-                    .rest
-                    .as_ref()
-                    .map(|rest| rest.argument.name.clone());
-
-                self.program.emit(Instruction::DestructureArray {
-                    dests: dests.clone(),
-                    src,
-                    has_rest: array_pattern.rest.is_some(),
-                    rest_dest: rest_dest.clone(),
-                });
+                self.compile_array_pattern(array_pattern, src, is_const)
+            }
+            DestructuringPattern::Object(object_pattern) => {
+                self.compile_object_pattern(object_pattern, src, is_const)
+            }
+        }
+    }
 
-                // Now declare and store the variables from temporary registers
-                for (dest, var_name) in dests.iter().zip(var_names.iter()) {
-                    if let Some(name) = var_name {
-                        self.program.emit(Instruction::Declare {
-                            name: name.clone(),
-                            is_const,
-                        });
-                        self.program.emit(Instruction::Store {
-                            name: name.clone(),
-                            src: dest.clone(),
-                        });
-                    }
-                }
+    fn compile_array_pattern(
+        &mut self,
+        array_pattern: &ArrayPattern,
+        src: Register,
+        is_const: bool,
+    ) -> Result<(), RaccoonError> {
+        // Holes get a register too, so every element is read from its own index
+        let dests: Vec<Register> = array_pattern
+            .elements
+            .iter()
+            .map(|_| self.next_temp())
+            .collect();
+
+        let rest_dest = array_pattern
+            .rest
+            .as_ref()
+            .map(|_| self.next_temp());
+
+        self.program.emit(Instruction::DestructureArray {
+            dests: dests.clone(),
+            src,
+            has_rest: array_pattern.rest.is_some(),
+            rest_dest: rest_dest.clone(),
+        });
 
-                if let Some(name) = rest_name {
-                    self.program.emit(Instruction::Declare {
-                        name: name.clone(),
-                        is_const,
+        for (dest, element) in dests.into_iter().zip(&array_pattern.elements) {
+            match element {
+                Some(ArrayPatternElement::Identifier(ident)) => {
+                    self.declare(&ident.name, is_const);
+                    self.program.emit(Instruction::Store {
+                        name: ident.name.clone(),
+                        src: dest,
                     });
-                    if let Some(rest_reg) = rest_dest {
-                        self.program.emit(Instruction::Store {
-                            name: name.clone(),
-                            src: rest_reg,
-                        });
-                    }
                 }
-            }
-            DestructuringPattern::Object(object_pattern) => {
-                let mut mappings = Vec::new();
-                let mut var_names = Vec::new();
-
-                for prop in &object_pattern.properties {
-                    let (dest, var_name) = match &prop.value {
-                        ObjectPatternValue::Identifier(ident) => {
-                            (Register::Local(ident.name.clone()), Some(ident.name.clone()))
-                        }
-                        _ => (self.next_temp(), None),
-                    };
-                    mappings.push((prop.key.clone(), dest));
-                    var_names.push(var_name);
+                Some(ArrayPatternElement::List(nested)) => {
+                    self.compile_array_pattern(nested, dest, is_const)?;
                 }
+                Some(ArrayPatternElement::Object(nested)) => {
+                    self.compile_object_pattern(nested, dest, is_const)?;
+                }
+                None => {}
+            }
+        }
 
-                let rest_dest = object_pattern
-                    .rest
-                    .as_ref()
-                    .map(|rest| Register::Local(rest.argument.name.clone()));
-
-                let rest_name = object_pattern
-                    .rest
-                    .as_ref()
-                    .map(|rest| rest.argument.name.clone());
-
-                self.program.emit(Instruction::DestructureObject {
-                    mappings: mappings.clone(),
-                    src,
-                    rest_dest: rest_dest.clone(),
-                });
+        if let (Some(rest), Some(rest_reg)) = (&array_pattern.rest, rest_dest) {
+            self.declare(&rest.argument.name, is_const);
+            self.program.emit(Instruction::Store {
+                name: rest.argument.name.clone(),
+                src: rest_reg,
+            });
+        }
+        Ok(())
+    }
 
-                for ((_, dest), var_name) in mappings.iter().zip(var_names.iter()) {
-                    if let Some(name) = var_name {
-                        self.program.emit(Instruction::Declare {
-                            name: name.clone(),
-                            is_const,
-                        });
-                        self.program.emit(Instruction::Store {
-                            name: name.clone(),
-                            src: dest.clone(),
-                        });
-                    }
-                }
+    fn compile_object_pattern(
+        &mut self,
+        object_pattern: &ObjectPattern,
+        src: Register,
+        is_const: bool,
+    ) -> Result<(), RaccoonError> {
+        let mappings: Vec<(String, Register)> = object_pattern
+            .properties
+            .iter()
+            .map(|prop| (prop.key.clone(), self.next_temp()))
+            .collect();
+
+        let rest_dest = object_pattern
+            .rest
+            .as_ref()
+            .map(|_| self.next_temp());
+
+        self.program.emit(Instruction::DestructureObject {
+            mappings: mappings.clone(),
+            src,
+            rest_dest: rest_dest.clone(),
+        });
 
-                if let Some(name) = rest_name {
-                    self.program.emit(Instruction::Declare {
-                        name: name.clone(),
-                        is_const,
+        for ((_, dest), prop) in mappings.into_iter().zip(&object_pattern.properties) {
+            match &prop.value {
+                ObjectPatternValue::Identifier(ident) => {
+                    self.declare(&ident.name, is_const);
+                    self.program.emit(Instruction::Store {
+                        name: ident.name.clone(),
+                        src: dest,
                     });
-                    if let Some(rest_reg) = rest_dest {
-                        self.program.emit(Instruction::Store {
-                            name: name.clone(),
-                            src: rest_reg,
-                        });
-                    }
+                }
+                ObjectPatternValue::Array(nested) => {
+                    self.compile_array_pattern(nested, dest, is_const)?;
+                }
+                ObjectPatternValue::Object(nested) => {
+                    self.compile_object_pattern(nested, dest, is_const)?;
                 }
             }
         }
+
+        if let (Some(rest), Some(rest_reg)) = (&object_pattern.rest, rest_dest) {
+            self.declare(&rest.argument.name, is_const);
+            self.program.emit(Instruction::Store {
+                name: rest.argument.name.clone(),
+                src: rest_reg,
+            });
+        }
         Ok(())
     }
 
@@ -252,27 +382,24 @@ impl IRCompiler {
             }
         }
 
-        let mut body_compiler = IRCompiler::new();
-        for stmt in &decl.body {
+        let mut body_compiler = self.function_body(&decl.parameters);
+        for stmt in decl.body.iter() {
             body_compiler.compile_stmt(stmt)?;
         }
 
-        self.program.emit(Instruction::Declare {
-            name: decl.name.clone(),
-            is_const: false,
-        });
-
         let temp_reg = self.next_temp();
         self.program.emit(Instruction::CreateFunction {
             dest: temp_reg.clone(),
             name: decl.name.clone(),
             params,
+            signature: Signature::of(&decl.parameters),
             body: body_compiler.program.instructions,
             labels: body_compiler.program.labels,
             is_async: decl.is_async,
         });
 
-        self.program.emit(Instruction::Store {
+        self.bind(&decl.name);
+        self.program.emit(Instruction::DeclareFunction {
             name: decl.name.clone(),
             src: temp_reg,
         });
@@ -281,11 +408,87 @@ impl IRCompiler {
     }
 
     fn compile_class_decl(&mut self, decl: &ClassDecl) -> Result<(), RaccoonError> {
+        // IR classes have no static storage for a block to initialize.
+        if let Some(block) = decl.static_blocks.first() {
+            return Err(RaccoonError::new(
+                format!(
+                    "Static blocks in class '{}' are not supported with --use-ir; run without it",
+                    decl.name
+                ),
+                block.position,
+                None::<String>,
+            ));
+        }
+        let methods = self
+            .with_default_methods(&decl.implements, decl.superclass.as_deref(), &decl.methods)
+            .into_owned();
+        self.compile_class(
+            decl.name.clone(),
+            decl.superclass.clone(),
+            decl.constructor.as_ref(),
+            &methods,
+            &decl.properties,
+        )?;
+        self.classes.borrow_mut().insert(
+            decl.name.clone(),
+            ClassDecl {
+                methods,
+                ..decl.clone()
+            },
+        );
+        Ok(())
+    }
+
+    fn with_default_methods<'a>(
+        &self,
+        implements: &[String],
+        superclass: Option<&str>,
+        methods: &'a [ClassMethod],
+    ) -> Cow<'a, [ClassMethod]> {
+        if implements.is_empty() {
+            return Cow::Borrowed(methods);
+        }
+
+        let interfaces = self.interfaces.borrow();
+        let classes = self.classes.borrow();
+        let lineage = InterfaceDecl::lineage(implements, |name| interfaces.get(name).cloned());
+        let defaults = InterfaceDecl::default_methods(&lineage, |name| {
+            let mut ancestors =
+                std::iter::successors(superclass.and_then(|name| classes.get(name)), |class| {
+                    class
+                        .superclass
+                        .as_deref()
+                        .and_then(|name| classes.get(name))
+                })
+                .take(classes.len());
+            methods.iter().any(|method| method.is_instance_method(name))
+                || ancestors.any(|class| {
+                    class
+                        .methods
+                        .iter()
+                        .any(|method| method.is_instance_method(name))
+                })
+        });
+
+        if defaults.is_empty() {
+            return Cow::Borrowed(methods);
+        }
+        Cow::Owned(methods.iter().cloned().chain(defaults).collect())
+    }
+
+    fn compile_class(
+        &mut self,
+        name: String,
+        superclass: Option<String>,
+        ctor: Option<&ConstructorDecl>,
+        class_methods: &[ClassMethod],
+        class_properties: &[ClassProperty],
+    ) -> Result<(), RaccoonError> {
         let mut constructor = None;
         let mut methods = Vec::new();
         let mut properties = Vec::new();
 
-        if let Some(ctor) = &decl.constructor {
+        if let Some(ctor) = ctor {
             let mut params = Vec::new();
             for param in &ctor.parameters {
                 if let VarPattern::Identifier(name) = &param.pattern {
@@ -293,15 +496,19 @@ impl IRCompiler {
                 }
             }
 
-            let mut body_compiler = IRCompiler::new();
-            for stmt in &ctor.body {
+            let mut body_compiler = self.function_body(&ctor.parameters);
+            for stmt in ctor.body.iter() {
                 body_compiler.compile_stmt(stmt)?;
             }
 
-            constructor = Some((params, body_compiler.program.instructions));
+            constructor = Some((
+                params,
+                body_compiler.program.instructions,
+                body_compiler.program.labels,
+            ));
         }
 
-        for method in &decl.methods {
+        for method in class_methods {
             let mut params = Vec::new();
             for param in &method.parameters {
                 if let VarPattern::Identifier(name) = &param.pattern {
@@ -309,8 +516,8 @@ impl IRCompiler {
                 }
             }
 
-            let mut body_compiler = IRCompiler::new();
-            for stmt in &method.body {
+            let mut body_compiler = self.function_body(&method.parameters);
+            for stmt in method.body.iter() {
                 body_compiler.compile_stmt(stmt)?;
             }
 
@@ -323,53 +530,124 @@ impl IRCompiler {
             ));
         }
 
-        for prop in &decl.properties {
+        for prop in class_properties {
             if let Some(init) = &prop.initializer {
                 let reg = self.compile_expr(init)?;
                 properties.push((prop.name.clone(), reg));
             }
         }
 
+        let readonly = class_properties
+            .iter()
+            .filter(|prop| prop.is_readonly)
+            .map(|prop| prop.name.clone())
+            .collect();
+        let private = class_properties
+            .iter()
+            .filter(|prop| prop.access_modifier == AccessModifier::Private)
+            .map(|prop| &prop.name)
+            .chain(
+                class_methods
+                    .iter()
+                    .filter(|method| {
+                        !method.is_static && method.access_modifier == AccessModifier::Private
+                    })
+                    .map(|method| &method.name),
+            )
+            .cloned()
+            .collect();
+
+        self.bind(&name);
         self.program.emit(Instruction::CreateClass {
-            name: decl.name.clone(),
+            name,
+            superclass,
             constructor,
             methods,
             properties,
+            readonly,
+            private,
         });
 
         Ok(())
     }
 
     fn compile_enum_decl(&mut self, decl: &EnumDecl) -> Result<(), RaccoonError> {
-        let mut properties = Vec::new();
-        let mut value = 0i64;
+        let mut members = Vec::new();
+        let mut next_value = 0i64;
 
         for member in &decl.members {
-            let member_value = if let Some(val_expr) = &member.value {
-                self.compile_expr(val_expr)?
-            } else {
-                let reg = self.next_temp();
-                self.program.emit(Instruction::LoadConst {
-                    dest: reg.clone(),
-                    value: RuntimeValue::Int(crate::runtime::IntValue::new(value)),
-                });
-                value += 1;
-                reg
+            // Like the interpreter, an integer initializer restarts the implicit numbering.
+            let member_value = match &member.value {
+                Some(value) => match const_eval::fold(self, value) {
+                    Some(RuntimeValue::Int(int)) => {
+                        next_value = int.value;
+                        None
+                    }
+                    Some(folded) => {
+                        let reg = self.next_temp();
+                        self.program.emit(Instruction::LoadConst {
+                            dest: reg.clone(),
+                            value: folded,
+                        });
+                        Some(reg)
+                    }
+                    None => Some(self.compile_expr(value)?),
+                },
+                None => None,
             };
-            properties.push((member.name.clone(), member_value));
+
+            let member_value = match member_value {
+                Some(reg) => reg,
+                None => {
+                    let reg = self.next_temp();
+                    self.program.emit(Instruction::LoadConst {
+                        dest: reg.clone(),
+                        value: RuntimeValue::Int(crate::runtime::IntValue::new(next_value)),
+                    });
+                    reg
+                }
+            };
+            next_value += 1;
+            members.push((member.name.clone(), member_value));
         }
 
-        let enum_obj = self.next_temp();
-        self.program.emit(Instruction::CreateObject {
-            dest: enum_obj.clone(),
-            properties,
+        self.bind(&decl.name);
+        self.program.emit(Instruction::CreateEnum {
+            name: decl.name.clone(),
+            members,
         });
 
+        Ok(())
+    }
+
+    fn compile_type_alias_decl(&mut self, decl: &TypeAliasDecl) -> Result<(), RaccoonError> {
+        self.declare(&decl.name, true);
+        let value = self.next_temp();
+        self.program.emit(Instruction::LoadConst {
+            dest: value.clone(),
+            value: Declarations::alias_type_object(decl),
+        });
         self.program.emit(Instruction::Store {
             name: decl.name.clone(),
-            src: enum_obj,
+            src: value,
         });
+        Ok(())
+    }
 
+    fn compile_interface_decl(&mut self, decl: &InterfaceDecl) -> Result<(), RaccoonError> {
+        self.interfaces
+            .borrow_mut()
+            .insert(decl.name.clone(), decl.clone());
+        self.declare(&decl.name, true);
+        let value = self.next_temp();
+        self.program.emit(Instruction::LoadConst {
+            dest: value.clone(),
+            value: Declarations::interface_type_object(decl),
+        });
+        self.program.emit(Instruction::Store {
+            name: decl.name.clone(),
+            src: value,
+        });
         Ok(())
     }
 
@@ -386,6 +664,35 @@ impl IRCompiler {
         Ok(())
     }
 
+    fn compile_do_expr(&mut self, do_expr: &DoExpr) -> Result<Register, RaccoonError> {
+        let dest = self.next_temp();
+        self.program.emit(Instruction::LoadConst {
+            dest: dest.clone(),
+            value: RuntimeValue::Null(crate::runtime::NullValue::new()),
+        });
+
+        self.program.emit(Instruction::PushScope);
+        self.scope_depth += 1;
+
+        let statements = &do_expr.body.statements;
+        for (i, stmt) in statements.iter().enumerate() {
+            match stmt {
+                Stmt::ExprStmt(expr_stmt) if i + 1 == statements.len() => {
+                    let value = self.compile_expr(&expr_stmt.expression)?;
+                    self.program.emit(Instruction::Move {
+                        dest: dest.clone(),
+                        src: value,
+                    });
+                }
+                _ => self.compile_stmt(stmt)?,
+            }
+        }
+
+        self.scope_depth -= 1;
+        self.program.emit(Instruction::PopScope);
+        Ok(dest)
+    }
+
     fn compile_if_stmt(&mut self, if_stmt: &IfStmt) -> Result<(), RaccoonError> {
         let condition_reg = self.compile_expr(&if_stmt.condition)?;
 
@@ -416,6 +723,9 @@ impl IRCompiler {
         let end_label = self.next_label("while_end");
 
         self.program.emit_label(start_label.clone());
+        self.program.emit(Instruction::LoopHead {
+            position: while_stmt.position,
+        });
 
         let condition_reg = self.compile_expr(&while_stmt.condition)?;
         self.program.emit(Instruction::JumpIfFalse {
@@ -423,7 +733,9 @@ impl IRCompiler {
             label: end_label.clone(),
         });
 
+        self.push_jump_target(end_label.clone(), Some(start_label.clone()));
         self.compile_stmt(&while_stmt.body)?;
+        self.jump_targets.pop();
 
         self.program.emit(Instruction::Jump { label: start_label });
 
@@ -433,17 +745,26 @@ impl IRCompiler {
 
     fn compile_do_while_stmt(&mut self, do_while: &DoWhileStmt) -> Result<(), RaccoonError> {
         let start_label = self.next_label("do_start");
+        let condition_label = self.next_label("do_condition");
+        let end_label = self.next_label("do_end");
 
         self.program.emit_label(start_label.clone());
+        self.program.emit(Instruction::LoopHead {
+            position: do_while.position,
+        });
 
+        self.push_jump_target(end_label.clone(), Some(condition_label.clone()));
         self.compile_stmt(&do_while.body)?;
+        self.jump_targets.pop();
 
+        self.program.emit_label(condition_label);
         let condition_reg = self.compile_expr(&do_while.condition)?;
         self.program.emit(Instruction::JumpIfTrue {
             condition: condition_reg,
             label: start_label,
         });
 
+        self.program.emit_label(end_label);
         Ok(())
     }
 
@@ -459,6 +780,9 @@ impl IRCompiler {
         let continue_label = self.next_label("for_continue");
 
         self.program.emit_label(start_label.clone());
+        self.program.emit(Instruction::LoopHead {
+            position: for_stmt.position,
+        });
 
         if let Some(condition) = &for_stmt.condition {
             let condition_reg = self.compile_expr(condition)?;
@@ -468,7 +792,9 @@ impl IRCompiler {
             });
         }
 
+        self.push_jump_target(end_label.clone(), Some(continue_label.clone()));
         self.compile_stmt(&for_stmt.body)?;
+        self.jump_targets.pop();
 
         self.program.emit_label(continue_label);
 
@@ -486,11 +812,10 @@ impl IRCompiler {
     fn compile_for_in_stmt(&mut self, for_in: &ForInStmt) -> Result<(), RaccoonError> {
         let object_reg = self.compile_expr(&for_in.iterable)?;
 
-        let mut body_compiler = IRCompiler::new();
+        let mut body_compiler = self.nested();
+        let var_name = body_compiler.bind_loop_pattern(&for_in.pattern, for_in.is_const)?;
         body_compiler.compile_stmt(&for_in.body)?;
 
-        let var_name = for_in.variable.clone();
-
         self.program.emit(Instruction::ForIn {
             variable: var_name,
             object: object_reg,
@@ -501,28 +826,124 @@ impl IRCompiler {
     }
 
     fn compile_for_of_stmt(&mut self, for_of: &ForOfStmt) -> Result<(), RaccoonError> {
-        let iterable_reg = self.compile_expr(&for_of.iterable)?;
-
-        let mut body_compiler = IRCompiler::new();
-        body_compiler.compile_stmt(&for_of.body)?;
+        let iterator_var = "$loop_iterator".to_string();
+        let start_label = self.next_label("for_of_start");
+        let continue_label = self.next_label("for_of_continue");
+        let break_label = self.next_label("for_of_break");
+        let end_label = self.next_label("for_of_end");
 
-        let var_name = for_of.variable.clone();
+        self.program.emit(Instruction::PushScope);
+        self.scope_depth += 1;
+        let iterable = self.compile_expr(&for_of.iterable)?;
+        let iterator = self.next_temp();
+        self.program.emit(Instruction::GetIterator {
+            dest: iterator.clone(),
+            iterable,
+            position: for_of.position,
+        });
+        self.declare(&iterator_var, true);
+        self.program.emit(Instruction::Store {
+            name: iterator_var.clone(),
+            src: iterator,
+        });
 
-        self.program.emit(Instruction::ForOf {
-            variable: var_name,
-            iterable: iterable_reg,
-            body: body_compiler.program.instructions,
+        self.program.emit_label(start_label.clone());
+        let iterator = self.next_temp();
+        self.program.emit(Instruction::Load {
+            dest: iterator.clone(),
+            name: iterator_var,
+        });
+        let element = self.next_temp();
+        self.program.emit(Instruction::IteratorNext {
+            dest: element.clone(),
+            iterator,
+            label: end_label.clone(),
+            position: for_of.position,
         });
 
+        self.program.emit(Instruction::PushScope);
+        self.scope_depth += 1;
+        match &for_of.pattern {
+            VarPattern::Identifier(name) => {
+                self.declare(name, for_of.is_const);
+                self.program.emit(Instruction::Store {
+                    name: name.clone(),
+                    src: element,
+                });
+            }
+            VarPattern::Destructuring(pattern) => {
+                self.compile_destructuring_pattern(pattern, element, for_of.is_const)?;
+            }
+        }
+        self.push_jump_target(break_label.clone(), Some(continue_label.clone()));
+        self.compile_stmt(&for_of.body)?;
+        self.jump_targets.pop();
+        self.scope_depth -= 1;
+
+        self.program.emit_label(continue_label);
+        self.program.emit(Instruction::PopScope);
+        self.program.emit(Instruction::Jump { label: start_label });
+
+        self.program.emit_label(break_label);
+        self.program.emit(Instruction::PopScope);
+        self.program.emit_label(end_label);
+        self.scope_depth -= 1;
+        self.program.emit(Instruction::PopScope);
         Ok(())
     }
 
+    fn bind_loop_pattern(
+        &mut self,
+        pattern: &VarPattern,
+        is_const: bool,
+    ) -> Result<String, RaccoonError> {
+        match pattern {
+            VarPattern::Identifier(name) => {
+                self.bind(name);
+                Ok(name.clone())
+            }
+            VarPattern::Destructuring(pattern) => {
+                let name = "$loop_element".to_string();
+                let element = self.next_temp();
+                self.program.emit(Instruction::Load {
+                    dest: element.clone(),
+                    name: name.clone(),
+                });
+                self.compile_destructuring_pattern(pattern, element, is_const)?;
+                Ok(name)
+            }
+        }
+    }
+
     fn compile_switch_stmt(&mut self, switch_stmt: &SwitchStmt) -> Result<(), RaccoonError> {
-        let discriminant_reg = self.compile_expr(&switch_stmt.discriminant)?;
+        self.compile_switch_cases(&switch_stmt.discriminant, &switch_stmt.cases, None)
+    }
+
+    fn compile_switch_expr(&mut self, switch_expr: &SwitchExpr) -> Result<Register, RaccoonError> {
+        let dest = self.next_temp();
+        self.program.emit(Instruction::LoadConst {
+            dest: dest.clone(),
+            value: RuntimeValue::Null(crate::runtime::NullValue::new()),
+        });
+
+        self.compile_switch_cases(&switch_expr.discriminant, &switch_expr.cases, Some(&dest))?;
+        Ok(dest)
+    }
+
+    fn compile_switch_cases(
+        &mut self,
+        discriminant: &Expr,
+        cases: &[SwitchCase],
+        dest: Option<&Register>,
+    ) -> Result<(), RaccoonError> {
+        let discriminant_reg = self.compile_expr(discriminant)?;
         let end_label = self.next_label("switch_end");
+        let body_labels: Vec<String> = (0..cases.len())
+            .map(|i| self.next_label(&format!("case_{}", i)))
+            .collect();
 
-        for (i, case) in switch_stmt.cases.iter().enumerate() {
-            if let Some(test) = &case.test {
+        for (case, body_label) in cases.iter().zip(&body_labels) {
+            for test in &case.tests {
                 let test_reg = self.compile_expr(test)?;
                 let temp = self.next_temp();
 
@@ -533,27 +954,48 @@ impl IRCompiler {
                     op: crate::tokens::BinaryOperator::Equal,
                 });
 
-                let next_case_label = self.next_label(&format!("case_{}", i + 1));
-                self.program.emit(Instruction::JumpIfFalse {
+                self.program.emit(Instruction::JumpIfTrue {
                     condition: temp,
-                    label: next_case_label.clone(),
+                    label: body_label.clone(),
                 });
+            }
+        }
 
-                for stmt in &case.consequent {
-                    self.compile_stmt(stmt)?;
+        let fallback_label = match cases.iter().position(SwitchCase::is_default) {
+            Some(index) => body_labels[index].clone(),
+            None => end_label.clone(),
+        };
+        self.program.emit(Instruction::Jump {
+            label: fallback_label,
+        });
+
+        self.push_jump_target(end_label.clone(), None);
+        for (case, body_label) in cases.iter().zip(body_labels) {
+            self.program.emit_label(body_label);
+
+            for (i, stmt) in case.consequent.iter().enumerate() {
+                match (stmt, dest) {
+                    (Stmt::ExprStmt(expr_stmt), Some(dest))
+                        if i + 1 == case.consequent.len() =>
+                    {
+                        let value = self.compile_expr(&expr_stmt.expression)?;
+                        self.program.emit(Instruction::Move {
+                            dest: dest.clone(),
+                            src: value,
+                        });
+                    }
+                    _ => self.compile_stmt(stmt)?,
                 }
+            }
 
+            // Only an expression's cases stop at their end; a statement's run on until `break`.
+            if dest.is_some() && !case.fallthrough {
                 self.program.emit(Instruction::Jump {
                     label: end_label.clone(),
                 });
-
-                self.program.emit_label(next_case_label);
-            } else {
-                for stmt in &case.consequent {
-                    self.compile_stmt(stmt)?;
-                }
             }
         }
+        self.jump_targets.pop();
 
         self.program.emit_label(end_label);
         Ok(())
@@ -566,51 +1008,249 @@ impl IRCompiler {
             None
         };
 
+        self.unwind_try_regions(0)?;
         self.program.emit(Instruction::Return { value });
         Ok(())
     }
 
     fn compile_try_stmt(&mut self, try_stmt: &TryStmt) -> Result<(), RaccoonError> {
-        let mut try_compiler = IRCompiler::new();
-        for stmt in &try_stmt.try_block.statements {
-            try_compiler.compile_stmt(stmt)?;
+        let catch_label = self.next_label("try_catch");
+        let end_label = self.next_label("try_end");
+        let error = self.next_temp();
+        let finally_block = try_stmt.finally_block.clone();
+
+        self.program.emit(Instruction::EnterTry {
+            handler: catch_label.clone(),
+            error: error.clone(),
+        });
+        self.try_regions.push(TryRegion {
+            has_handler: true,
+            finally_block: finally_block.clone(),
+            resource: None,
+        });
+        self.compile_block(&try_stmt.try_block)?;
+        self.try_regions.pop();
+        self.program.emit(Instruction::ExitTry);
+        self.compile_finally(&finally_block)?;
+        self.program.emit(Instruction::Jump {
+            label: end_label.clone(),
+        });
+
+        self.program.emit_label(catch_label);
+
+        if try_stmt.catch_clauses.is_empty() {
+            self.compile_finally(&finally_block)?;
+            self.program.emit(Instruction::Rethrow { error });
+            self.program.emit_label(end_label);
+            return Ok(());
         }
 
-        let catch_handler = if let Some(catch_clause) = try_stmt.catch_clauses.first() {
-            let error_var = catch_clause.error_var.clone();
+        // An error escaping the catch body still runs the finally block before propagating.
+        let rethrow_label = self.next_label("try_rethrow");
+        let catch_error = self.next_temp();
+        if finally_block.is_some() {
+            self.program.emit(Instruction::EnterTry {
+                handler: rethrow_label.clone(),
+                error: catch_error.clone(),
+            });
+            self.try_regions.push(TryRegion {
+                has_handler: true,
+                finally_block: finally_block.clone(),
+                resource: None,
+            });
+        }
 
-            let mut catch_compiler = IRCompiler::new();
-            for stmt in &catch_clause.body.statements {
-                catch_compiler.compile_stmt(stmt)?;
+        // The first clause whose type the error has runs; when none does, the error goes on.
+        let caught_label = self.next_label("try_caught");
+        for catch_clause in &try_stmt.catch_clauses {
+            let next_label = self.next_label("try_next_catch");
+            if let Some(error_type) = &catch_clause.error_type {
+                let matches = self.next_temp();
+                self.program.emit(Instruction::Is {
+                    dest: matches.clone(),
+                    operand: error.clone(),
+                    target_type: error_type.clone(),
+                });
+                self.program.emit(Instruction::JumpIfFalse {
+                    condition: matches,
+                    label: next_label.clone(),
+                });
             }
 
-            Some((error_var, catch_compiler.program.instructions))
-        } else {
-            None
-        };
+            self.program.emit(Instruction::PushScope);
+            self.scope_depth += 1;
+            self.declare(&catch_clause.error_var, false);
+            self.program.emit(Instruction::Store {
+                name: catch_clause.error_var.clone(),
+                src: error.clone(),
+            });
+            self.catch_errors.push(error.clone());
+            let result = catch_clause
+                .body
+                .statements
+                .iter()
+                .try_for_each(|stmt| self.compile_stmt(stmt));
+            self.catch_errors.pop();
+            result?;
+            self.scope_depth -= 1;
+            self.program.emit(Instruction::PopScope);
+            self.program.emit(Instruction::Jump {
+                label: caught_label.clone(),
+            });
 
-        let finally_body = if let Some(finally_block) = &try_stmt.finally_block {
-            let mut finally_compiler = IRCompiler::new();
-            for stmt in &finally_block.statements {
-                finally_compiler.compile_stmt(stmt)?;
-            }
-            Some(finally_compiler.program.instructions)
-        } else {
-            None
-        };
+            self.program.emit_label(next_label);
+        }
+        self.program.emit(Instruction::Rethrow { error });
+        self.program.emit_label(caught_label);
 
-        self.program.emit(Instruction::TryCatch {
-            try_body: try_compiler.program.instructions,
-            catch_handler,
-            finally_body,
+        if finally_block.is_some() {
+            self.try_regions.pop();
+            self.program.emit(Instruction::ExitTry);
+        }
+        self.compile_finally(&finally_block)?;
+        self.program.emit(Instruction::Jump {
+            label: end_label.clone(),
         });
 
+        if finally_block.is_some() {
+            self.program.emit_label(rethrow_label);
+            self.compile_finally(&finally_block)?;
+            self.program
+                .emit(Instruction::Rethrow { error: catch_error });
+        }
+
+        self.program.emit_label(end_label);
+        Ok(())
+    }
+
+    fn compile_using_stmt(&mut self, using: &UsingStmt) -> Result<(), RaccoonError> {
+        let resource = self.compile_expr(&using.initializer)?;
+        self.program.emit(Instruction::Acquire {
+            resource: resource.clone(),
+        });
+        let fail_label = self.next_label("using_fail");
+        let end_label = self.next_label("using_end");
+        let error = self.next_temp();
+
+        self.program.emit(Instruction::PushScope);
+        self.scope_depth += 1;
+        self.declare(&using.name, true);
+        self.program.emit(Instruction::Store {
+            name: using.name.clone(),
+            src: resource.clone(),
+        });
+
+        self.program.emit(Instruction::EnterTry {
+            handler: fail_label.clone(),
+            error: error.clone(),
+        });
+        self.try_regions.push(TryRegion {
+            has_handler: true,
+            finally_block: None,
+            resource: Some(resource.clone()),
+        });
+        let result = self.compile_block(&using.body);
+        self.try_regions.pop();
+        result?;
+        self.program.emit(Instruction::ExitTry);
+        self.program.emit(Instruction::Dispose {
+            resource: resource.clone(),
+        });
+        self.program.emit(Instruction::Jump {
+            label: end_label.clone(),
+        });
+
+        self.program.emit_label(fail_label);
+        self.program.emit(Instruction::Dispose { resource });
+        self.program.emit(Instruction::Rethrow { error });
+
+        self.program.emit_label(end_label);
+        self.scope_depth -= 1;
+        self.program.emit(Instruction::PopScope);
+        Ok(())
+    }
+
+    fn compile_defer_stmt(&mut self, defer: &DeferStmt) -> Result<(), RaccoonError> {
+        self.compile_try_stmt(&TryStmt {
+            try_block: defer.body.clone(),
+            catch_clauses: Vec::new(),
+            finally_block: Some(Block {
+                statements: vec![Stmt::ExprStmt(ExprStmt {
+                    expression: defer.expression.clone(),
+                    position: defer.position,
+                })],
+                position: defer.position,
+            }),
+            position: defer.position,
+        })
+    }
+
+    fn compile_finally(&mut self, finally_block: &Option<Block>) -> Result<(), RaccoonError> {
+        match finally_block {
+            Some(block) => self.compile_block(block),
+            None => Ok(()),
+        }
+    }
+
+    fn unwind_try_regions(&mut self, depth: usize) -> Result<(), RaccoonError> {
+        for index in (depth..self.try_regions.len()).rev() {
+            if self.try_regions[index].has_handler {
+                self.program.emit(Instruction::ExitTry);
+            }
+
+            if let Some(block) = self.try_regions[index].finally_block.clone() {
+                let inner = self.try_regions.split_off(index);
+                let result = self.compile_block(&block);
+                self.try_regions.extend(inner);
+                result?;
+            }
+            if let Some(resource) = self.try_regions[index].resource.clone() {
+                self.program.emit(Instruction::Dispose { resource });
+            }
+        }
         Ok(())
     }
 
     fn compile_throw_stmt(&mut self, throw: &ThrowStmt) -> Result<(), RaccoonError> {
-        let value_reg = self.compile_expr(&throw.value)?;
-        self.program.emit(Instruction::Throw { value: value_reg });
+        let Some(value) = &throw.value else {
+            let Some(error) = self.catch_errors.last().cloned() else {
+                return Err(RaccoonError::new(
+                    "A bare 'throw' can only rethrow inside a catch block",
+                    throw.position,
+                    None::<String>,
+                ));
+            };
+            self.program.emit(Instruction::Rethrow { error });
+            return Ok(());
+        };
+        let value_reg = self.compile_expr(value)?;
+        self.program.emit(Instruction::Throw {
+            value: value_reg,
+            position: throw.position,
+        });
+        Ok(())
+    }
+
+    fn compile_assert_stmt(&mut self, assert: &AssertStmt) -> Result<(), RaccoonError> {
+        let condition = self.compile_expr(&assert.condition)?;
+        let passed_label = self.next_label("assert_passed");
+        self.program.emit(Instruction::JumpIfTrue {
+            condition: condition.clone(),
+            label: passed_label.clone(),
+        });
+
+        // The message is only evaluated once the check has failed.
+        let message = match &assert.message {
+            Some(message) => Some(self.compile_expr(message)?),
+            None => None,
+        };
+        self.program.emit(Instruction::Assert {
+            condition,
+            message,
+            kind: assert.kind,
+            source: assert.source.clone(),
+        });
+        self.program.emit_label(passed_label);
         Ok(())
     }
 
@@ -658,10 +1298,16 @@ impl IRCompiler {
             }
             Expr::Identifier(ident) => {
                 let dest = self.next_temp();
-                self.program.emit(Instruction::Load {
-                    dest: dest.clone(),
-                    name: ident.name.clone(),
-                });
+                match self.constants.get(&ident.name) {
+                    Some(value) => self.program.emit(Instruction::LoadConst {
+                        dest: dest.clone(),
+                        value: value.clone(),
+                    }),
+                    None => self.program.emit(Instruction::Load {
+                        dest: dest.clone(),
+                        name: ident.name.clone(),
+                    }),
+                }
                 Ok(dest)
             }
             Expr::Binary(binary) => self.compile_binary_expr(binary),
@@ -669,15 +1315,37 @@ impl IRCompiler {
             Expr::Assignment(assign) => self.compile_assignment(assign),
             Expr::Call(call) => self.compile_call_expr(call),
             Expr::ArrayLiteral(array) => self.compile_array_literal(array),
+            Expr::TupleLiteral(tuple) => self.compile_tuple_literal(tuple),
             Expr::ObjectLiteral(obj) => self.compile_object_literal(obj),
             Expr::Member(member) => self.compile_member_expr(member),
             Expr::Index(index) => self.compile_index_expr(index),
+            Expr::Slice(slice) => self.compile_slice_expr(slice),
             Expr::Conditional(cond) => self.compile_conditional_expr(cond),
             Expr::UnaryUpdate(update) => self.compile_unary_update(update),
             Expr::TemplateStr(template) => self.compile_template_str(template),
             Expr::ArrowFn(arrow) => self.compile_arrow_fn(arrow),
             Expr::TypeOf(typeof_expr) => self.compile_typeof_expr(typeof_expr),
             Expr::InstanceOf(instanceof) => self.compile_instanceof_expr(instanceof),
+            Expr::As(as_expr) => {
+                let operand = self.compile_expr(&as_expr.operand)?;
+                let dest = self.next_temp();
+                self.program.emit(Instruction::Cast {
+                    dest: dest.clone(),
+                    operand,
+                    target_type: as_expr.target_type.clone(),
+                });
+                Ok(dest)
+            }
+            Expr::Is(is_expr) => {
+                let operand = self.compile_expr(&is_expr.operand)?;
+                let dest = self.next_temp();
+                self.program.emit(Instruction::Is {
+                    dest: dest.clone(),
+                    operand,
+                    target_type: is_expr.target_type.clone(),
+                });
+                Ok(dest)
+            }
             Expr::OptionalChaining(opt_chain) => self.compile_optional_chaining(opt_chain),
             Expr::MethodCall(method_call) => self.compile_method_call(method_call),
             Expr::New(new_expr) => self.compile_new_expr(new_expr),
@@ -685,6 +1353,8 @@ impl IRCompiler {
             Expr::NullCoalescing(null_coal) => self.compile_null_coalescing(null_coal),
             Expr::Await(await_expr) => self.compile_await_expr(await_expr),
             Expr::Match(match_expr) => self.compile_match_expr(match_expr),
+            Expr::Switch(switch_expr) => self.compile_switch_expr(switch_expr),
+            Expr::Do(do_expr) => self.compile_do_expr(do_expr),
             Expr::This(_) => self.compile_this_expr(),
             Expr::Super(_) => {
                 let dest = self.next_temp();
@@ -738,45 +1408,130 @@ impl IRCompiler {
     }
 
     fn compile_assignment(&mut self, assign: &Assignment) -> Result<Register, RaccoonError> {
-        let value_reg = self.compile_expr(&assign.value)?;
+        let compound = if assign.operator == TokenType::Assign {
+            None
+        } else {
+            Some(Self::compound_operator(assign.operator).ok_or_else(|| {
+                RaccoonError::new(
+                    format!(
+                        "Unknown compound assignment operator: {:?}",
+                        assign.operator
+                    ),
+                    assign.position,
+                    None::<String>,
+                )
+            })?)
+        };
 
         match assign.target.as_ref() {
             Expr::Identifier(ident) => {
+                let current = compound.map(|_| {
+                    let dest = self.next_temp();
+                    self.program.emit(Instruction::Load {
+                        dest: dest.clone(),
+                        name: ident.name.clone(),
+                    });
+                    dest
+                });
+                let value_reg = self.compile_assigned_value(assign, compound, current)?;
                 self.program.emit(Instruction::Store {
                     name: ident.name.clone(),
                     src: value_reg.clone(),
                 });
+                Ok(value_reg)
             }
             Expr::Member(member) => {
                 let object_reg = self.compile_expr(&member.object)?;
+                let current = compound.map(|_| {
+                    let dest = self.next_temp();
+                    self.program.emit(Instruction::LoadProperty {
+                        dest: dest.clone(),
+                        object: object_reg.clone(),
+                        property: member.property.clone(),
+                    });
+                    dest
+                });
+                let value_reg = self.compile_assigned_value(assign, compound, current)?;
                 self.program.emit(Instruction::StoreProperty {
                     object: object_reg,
                     property: member.property.clone(),
                     value: value_reg.clone(),
                 });
+                Ok(value_reg)
             }
             Expr::Index(index) => {
                 let array_reg = self.compile_expr(&index.object)?;
                 let index_reg = self.compile_expr(&index.index)?;
+                let current = compound.map(|_| {
+                    let dest = self.next_temp();
+                    self.program.emit(Instruction::LoadIndex {
+                        dest: dest.clone(),
+                        array: array_reg.clone(),
+                        index: index_reg.clone(),
+                        position: index.position,
+                    });
+                    dest
+                });
+                let value_reg = self.compile_assigned_value(assign, compound, current)?;
                 self.program.emit(Instruction::StoreIndex {
                     array: array_reg,
                     index: index_reg,
                     value: value_reg.clone(),
+                    position: assign.position,
                 });
+                Ok(value_reg)
             }
-            _ => {
-                return Err(RaccoonError::new(
-                    "Invalid assignment target",
-                    assign.position,
-                    None::<String>,
-                ))
-            }
+            _ => Err(RaccoonError::new(
+                "Invalid assignment target",
+                assign.position,
+                None::<String>,
+            )),
         }
+    }
 
-        Ok(value_reg)
+    fn compile_assigned_value(
+        &mut self,
+        assign: &Assignment,
+        compound: Option<BinaryOperator>,
+        current: Option<Register>,
+    ) -> Result<Register, RaccoonError> {
+        let value_reg = self.compile_expr(&assign.value)?;
+        let (Some(op), Some(left)) = (compound, current) else {
+            return Ok(value_reg);
+        };
+        let dest = self.next_temp();
+        self.program.emit(Instruction::BinaryOp {
+            dest: dest.clone(),
+            left,
+            right: value_reg,
+            op,
+        });
+        Ok(dest)
+    }
+
+    fn compound_operator(operator: TokenType) -> Option<BinaryOperator> {
+        match operator {
+            TokenType::PlusAssign => Some(BinaryOperator::Add),
+            TokenType::MinusAssign => Some(BinaryOperator::Subtract),
+            TokenType::MultiplyAssign => Some(BinaryOperator::Multiply),
+            TokenType::DivideAssign => Some(BinaryOperator::Divide),
+            TokenType::ModuloAssign => Some(BinaryOperator::Modulo),
+            TokenType::AmpersandAssign => Some(BinaryOperator::BitwiseAnd),
+            TokenType::BitwiseOrAssign => Some(BinaryOperator::BitwiseOr),
+            TokenType::BitwiseXorAssign => Some(BinaryOperator::BitwiseXor),
+            TokenType::LeftShiftAssign => Some(BinaryOperator::LeftShift),
+            TokenType::RightShiftAssign => Some(BinaryOperator::RightShift),
+            TokenType::UnsignedRightShiftAssign => Some(BinaryOperator::UnsignedRightShift),
+            TokenType::ExponentAssign => Some(BinaryOperator::Exponent),
+            _ => None,
+        }
     }
 
     fn compile_call_expr(&mut self, call: &CallExpr) -> Result<Register, RaccoonError> {
+        if matches!(call.callee.as_ref(), Expr::Super(_)) {
+            return self.compile_super_call("constructor", &call.args);
+        }
+
         let callee = self.compile_expr(&call.callee)?;
 
         let mut args = Vec::new();
@@ -789,6 +1544,22 @@ impl IRCompiler {
             dest: dest.clone(),
             callee,
             args,
+            position: call.position,
+        });
+
+        Ok(dest)
+    }
+
+    fn compile_tuple_literal(&mut self, tuple: &TupleLiteral) -> Result<Register, RaccoonError> {
+        let mut elements = Vec::new();
+        for elem in &tuple.elements {
+            elements.push(self.compile_expr(elem)?);
+        }
+
+        let dest = self.next_temp();
+        self.program.emit(Instruction::CreateTuple {
+            dest: dest.clone(),
+            elements,
         });
 
         Ok(dest)
@@ -821,32 +1592,47 @@ impl IRCompiler {
     }
 
     fn compile_object_literal(&mut self, obj: &ObjectLiteral) -> Result<Register, RaccoonError> {
+        let dest = self.next_temp();
         let mut properties = Vec::new();
+        let mut created = false;
 
         for prop in &obj.properties {
             match prop {
                 ObjectLiteralProperty::KeyValue { key, value } => {
                     let value_reg = self.compile_expr(value)?;
-                    properties.push((key.clone(), value_reg));
+                    if created {
+                        self.program.emit(Instruction::StoreProperty {
+                            object: dest.clone(),
+                            property: key.clone(),
+                            value: value_reg,
+                        });
+                    } else {
+                        properties.push((key.clone(), value_reg));
+                    }
                 }
                 ObjectLiteralProperty::Spread(spread_expr) => {
                     let spread_reg = self.compile_expr(spread_expr)?;
-                    let spread_dest = self.next_temp();
+                    if !created {
+                        self.program.emit(Instruction::CreateObject {
+                            dest: dest.clone(),
+                            properties: std::mem::take(&mut properties),
+                        });
+                        created = true;
+                    }
                     self.program.emit(Instruction::SpreadObject {
-                        dest: spread_dest.clone(),
+                        object: dest.clone(),
                         operand: spread_reg,
                     });
-
-                    properties.push(("...".to_string(), spread_dest));
                 }
             }
         }
 
-        let dest = self.next_temp();
-        self.program.emit(Instruction::CreateObject {
-            dest: dest.clone(),
-            properties,
-        });
+        if !created {
+            self.program.emit(Instruction::CreateObject {
+                dest: dest.clone(),
+                properties,
+            });
+        }
 
         Ok(dest)
     }
@@ -873,6 +1659,30 @@ impl IRCompiler {
             dest: dest.clone(),
             array,
             index: index_reg,
+            position: index.position,
+        });
+
+        Ok(dest)
+    }
+
+    fn compile_slice_expr(&mut self, slice: &SliceExpr) -> Result<Register, RaccoonError> {
+        let object = self.compile_expr(&slice.object)?;
+        let start = match &slice.start {
+            Some(start) => Some(self.compile_expr(start)?),
+            None => None,
+        };
+        let end = match &slice.end {
+            Some(end) => Some(self.compile_expr(end)?),
+            None => None,
+        };
+        let dest = self.next_temp();
+
+        self.program.emit(Instruction::LoadSlice {
+            dest: dest.clone(),
+            object,
+            start,
+            end,
+            inclusive: slice.inclusive,
         });
 
         Ok(dest)
@@ -958,7 +1768,7 @@ impl IRCompiler {
 
         let (body_instructions, body_labels) = match &arrow.body {
             ArrowFnBody::Expr(expr) => {
-                let mut body_compiler = IRCompiler::new();
+                let mut body_compiler = self.function_body(&arrow.parameters);
                 let result = body_compiler.compile_expr(expr)?;
                 body_compiler.program.emit(Instruction::Return {
                     value: Some(result),
@@ -966,8 +1776,8 @@ impl IRCompiler {
                 (body_compiler.program.instructions, body_compiler.program.labels)
             }
             ArrowFnBody::Block(stmts) => {
-                let mut body_compiler = IRCompiler::new();
-                for stmt in stmts {
+                let mut body_compiler = self.function_body(&arrow.parameters);
+                for stmt in stmts.iter() {
                     body_compiler.compile_stmt(stmt)?;
                 }
                 (body_compiler.program.instructions, body_compiler.program.labels)
@@ -979,6 +1789,7 @@ impl IRCompiler {
             dest: dest.clone(),
             name: "<arrow>".to_string(),
             params,
+            signature: Signature::of(&arrow.parameters),
             body: body_instructions,
             labels: body_labels,
             is_async: arrow.is_async,
@@ -1035,6 +1846,10 @@ impl IRCompiler {
         &mut self,
         method_call: &MethodCallExpr,
     ) -> Result<Register, RaccoonError> {
+        if matches!(method_call.object.as_ref(), Expr::Super(_)) {
+            return self.compile_super_call(&method_call.method, &method_call.args);
+        }
+
         let object = self.compile_expr(&method_call.object)?;
 
         let mut args = Vec::new();
@@ -1048,6 +1863,27 @@ impl IRCompiler {
             object,
             method: method_call.method.clone(),
             args,
+            position: method_call.position,
+        });
+
+        Ok(dest)
+    }
+
+    fn compile_super_call(
+        &mut self,
+        method: &str,
+        args: &[Expr],
+    ) -> Result<Register, RaccoonError> {
+        let mut arg_regs = Vec::new();
+        for arg in args {
+            arg_regs.push(self.compile_expr(arg)?);
+        }
+
+        let dest = self.next_temp();
+        self.program.emit(Instruction::CallSuper {
+            dest: dest.clone(),
+            method: method.to_string(),
+            args: arg_regs,
         });
 
         Ok(dest)
@@ -1078,6 +1914,7 @@ impl IRCompiler {
             dest: dest.clone(),
             start,
             end,
+            inclusive: range.inclusive,
         });
 
         Ok(dest)
@@ -1107,6 +1944,7 @@ impl IRCompiler {
         self.program.emit(Instruction::Await {
             dest: dest.clone(),
             future,
+            position: await_expr.position,
         });
 
         Ok(dest)
@@ -1125,7 +1963,7 @@ impl IRCompiler {
                 None
             };
 
-            let mut body_compiler = IRCompiler::new();
+            let mut body_compiler = self.nested();
             let body_reg = body_compiler.compile_expr(&arm.body)?;
             body_compiler.program.emit(Instruction::Return {
                 value: Some(body_reg),
@@ -1190,7 +2028,10 @@ impl IRCompiler {
                 };
                 Ok(IRMatchPattern::Range(start_value, end_value))
             }
-            Pattern::Variable(name) => Ok(IRMatchPattern::Variable(name.clone())),
+            Pattern::Variable(name) => {
+                self.constants.remove(name);
+                Ok(IRMatchPattern::Variable(name.clone()))
+            }
             Pattern::Array(patterns) => {
                 let mut compiled_patterns = Vec::new();
                 for p in patterns {
@@ -1198,6 +2039,13 @@ impl IRCompiler {
                 }
                 Ok(IRMatchPattern::Array(compiled_patterns))
             }
+            Pattern::Tuple(patterns) => {
+                let mut compiled_patterns = Vec::new();
+                for p in patterns {
+                    compiled_patterns.push(self.compile_pattern(p)?);
+                }
+                Ok(IRMatchPattern::Tuple(compiled_patterns))
+            }
             Pattern::Object(props) => {
                 let mut compiled_props = Vec::new();
                 for (key, pattern) in props {
@@ -1278,64 +2126,21 @@ impl IRCompiler {
     }
 
     fn compile_class_expr(&mut self, class_expr: &ClassExpr) -> Result<Register, RaccoonError> {
-        let mut constructor = None;
-        let mut methods = Vec::new();
-        let mut properties = Vec::new();
-
-        if let Some(ctor) = &class_expr.constructor {
-            let mut params = Vec::new();
-            for param in &ctor.parameters {
-                if let VarPattern::Identifier(name) = &param.pattern {
-                    params.push(name.clone());
-                }
-            }
-
-            let mut body_compiler = IRCompiler::new();
-            for stmt in &ctor.body {
-                body_compiler.compile_stmt(stmt)?;
-            }
-
-            constructor = Some((params, body_compiler.program.instructions));
-        }
-
-        for method in &class_expr.methods {
-            let mut params = Vec::new();
-            for param in &method.parameters {
-                if let VarPattern::Identifier(name) = &param.pattern {
-                    params.push(name.clone());
-                }
-            }
-
-            let mut body_compiler = IRCompiler::new();
-            for stmt in &method.body {
-                body_compiler.compile_stmt(stmt)?;
-            }
-
-            methods.push((
-                method.name.clone(),
-                params,
-                body_compiler.program.instructions,
-                body_compiler.program.labels,
-                method.is_async,
-            ));
-        }
-
-        for prop in &class_expr.properties {
-            if let Some(init) = &prop.initializer {
-                let reg = self.compile_expr(init)?;
-                properties.push((prop.name.clone(), reg));
-            }
-        }
-
         let dest = self.next_temp();
         let temp_name = format!("<anonymous_class_{}>", self.temp_counter);
 
-        self.program.emit(Instruction::CreateClass {
-            name: temp_name.clone(),
-            constructor,
-            methods,
-            properties,
-        });
+        let methods = self.with_default_methods(
+            &class_expr.implements,
+            class_expr.superclass.as_deref(),
+            &class_expr.methods,
+        );
+        self.compile_class(
+            temp_name.clone(),
+            class_expr.superclass.clone(),
+            class_expr.constructor.as_ref(),
+            &methods,
+            &class_expr.properties,
+        )?;
 
         self.program.emit(Instruction::Load {
             dest: dest.clone(),
@@ -1346,6 +2151,12 @@ impl IRCompiler {
     }
 }
 
+impl ConstScope for IRCompiler {
+    fn constant(&self, name: &str) -> Option<RuntimeValue> {
+        self.constants.get(name).cloned()
+    }
+}
+
 impl Default for IRCompiler {
     fn default() -> Self {
         Self::new()
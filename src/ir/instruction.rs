@@ -1,7 +1,17 @@
+use crate::ast::nodes::AssertKind;
+use crate::ast::types::Type;
+use crate::runtime::types::operations::overloading::Signature;
 use crate::runtime::RuntimeValue;
-use crate::tokens::{BinaryOperator, UnaryOperator};
+use crate::tokens::{BinaryOperator, Position, UnaryOperator};
 use std::fmt;
 
+/// A class constructor's parameters, body and the label positions in that body.
+pub type IRConstructor = (
+    Vec<String>,
+    Vec<Instruction>,
+    std::collections::HashMap<String, usize>,
+);
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Register {
     Temp(usize),
@@ -43,6 +53,11 @@ pub enum Instruction {
         src: Register,
     },
 
+    DeclareFunction {
+        name: String,
+        src: Register,
+    },
+
     Load {
         dest: Register,
         name: String,
@@ -75,14 +90,27 @@ pub enum Instruction {
         label: String,
     },
 
+    CompareJump {
+        left: Register,
+        right: Register,
+        op: BinaryOperator,
+        label: String,
+        jump_if: bool,
+    },
+
     Label {
         name: String,
     },
 
+    LoopHead {
+        position: Position,
+    },
+
     Call {
         dest: Register,
         callee: Register,
         args: Vec<Register>,
+        position: Position,
     },
 
     Return {
@@ -93,6 +121,7 @@ pub enum Instruction {
         dest: Register,
         name: String,
         params: Vec<String>,
+        signature: Signature,
         body: Vec<Instruction>,
         labels: std::collections::HashMap<String, usize>,
         is_async: bool,
@@ -103,16 +132,31 @@ pub enum Instruction {
         elements: Vec<Register>,
     },
 
+    CreateTuple {
+        dest: Register,
+        elements: Vec<Register>,
+    },
+
     LoadIndex {
         dest: Register,
         array: Register,
         index: Register,
+        position: Position,
+    },
+
+    LoadSlice {
+        dest: Register,
+        object: Register,
+        start: Option<Register>,
+        end: Option<Register>,
+        inclusive: bool,
     },
 
     StoreIndex {
         array: Register,
         index: Register,
         value: Register,
+        position: Position,
     },
 
     CreateObject {
@@ -137,6 +181,7 @@ pub enum Instruction {
         object: Register,
         method: String,
         args: Vec<Register>,
+        position: Position,
     },
 
     NewInstance {
@@ -148,6 +193,7 @@ pub enum Instruction {
     Await {
         dest: Register,
         future: Register,
+        position: Position,
     },
 
     TypeOf {
@@ -161,8 +207,40 @@ pub enum Instruction {
         type_name: String,
     },
 
+    Cast {
+        dest: Register,
+        operand: Register,
+        target_type: Type,
+    },
+
+    Is {
+        dest: Register,
+        operand: Register,
+        target_type: Type,
+    },
+
     Throw {
         value: Register,
+        position: Position,
+    },
+
+    Rethrow {
+        error: Register,
+    },
+
+    Acquire {
+        resource: Register,
+    },
+
+    Dispose {
+        resource: Register,
+    },
+
+    Assert {
+        condition: Register,
+        message: Option<Register>,
+        kind: AssertKind,
+        source: String,
     },
 
     Nop,
@@ -211,6 +289,7 @@ pub enum Instruction {
         dest: Register,
         start: Register,
         end: Register,
+        inclusive: bool,
     },
 
     Spread {
@@ -245,17 +324,26 @@ pub enum Instruction {
 
     Continue,
 
-    TryCatch {
-        try_body: Vec<Instruction>,
-        catch_handler: Option<(String, Vec<Instruction>)>,
-        finally_body: Option<Vec<Instruction>>,
+    EnterTry {
+        handler: String,
+        error: Register,
     },
 
+    ExitTry,
+
     CreateClass {
         name: String,
-        constructor: Option<(Vec<String>, Vec<Instruction>)>,
+        superclass: Option<String>,
+        constructor: Option<IRConstructor>,
         methods: Vec<(String, Vec<String>, Vec<Instruction>, std::collections::HashMap<String, usize>, bool)>,
         properties: Vec<(String, Register)>,
+        readonly: Vec<String>,
+        private: Vec<String>,
+    },
+
+    CreateEnum {
+        name: String,
+        members: Vec<(String, Register)>,
     },
 
     LoadThis {
@@ -274,7 +362,7 @@ pub enum Instruction {
     },
 
     SpreadObject {
-        dest: Register,
+        object: Register,
         operand: Register,
     },
 
@@ -300,12 +388,6 @@ pub enum Instruction {
         body: Vec<Instruction>,
     },
 
-    ForOf {
-        variable: String,
-        iterable: Register,
-        body: Vec<Instruction>,
-    },
-
     CompoundAssign {
         dest: Register,
         src: Register,
@@ -315,11 +397,14 @@ pub enum Instruction {
     GetIterator {
         dest: Register,
         iterable: Register,
+        position: Position,
     },
 
     IteratorNext {
         dest: Register,
         iterator: Register,
+        label: String,
+        position: Position,
     },
 
     Yield {
@@ -388,24 +473,125 @@ pub enum MatchPattern {
     Range(RuntimeValue, RuntimeValue),
     Variable(String),
     Array(Vec<MatchPattern>),
+    Tuple(Vec<MatchPattern>),
     Object(Vec<(String, MatchPattern)>),
     Or(Vec<MatchPattern>),
 }
 
 impl Instruction {
+    pub fn opcode(&self) -> &'static str {
+        match self {
+            Instruction::LoadConst { .. } => "LoadConst",
+            Instruction::Move { .. } => "Move",
+            Instruction::Declare { .. } => "Declare",
+            Instruction::Store { .. } => "Store",
+            Instruction::DeclareFunction { .. } => "DeclareFunction",
+            Instruction::Load { .. } => "Load",
+            Instruction::BinaryOp { .. } => "BinaryOp",
+            Instruction::UnaryOp { .. } => "UnaryOp",
+            Instruction::Jump { .. } => "Jump",
+            Instruction::JumpIfFalse { .. } => "JumpIfFalse",
+            Instruction::JumpIfTrue { .. } => "JumpIfTrue",
+            Instruction::CompareJump { .. } => "CompareJump",
+            Instruction::Label { .. } => "Label",
+            Instruction::LoopHead { .. } => "LoopHead",
+            Instruction::Call { .. } => "Call",
+            Instruction::Return { .. } => "Return",
+            Instruction::CreateFunction { .. } => "CreateFunction",
+            Instruction::CreateArray { .. } => "CreateArray",
+            Instruction::CreateTuple { .. } => "CreateTuple",
+            Instruction::LoadIndex { .. } => "LoadIndex",
+            Instruction::LoadSlice { .. } => "LoadSlice",
+            Instruction::StoreIndex { .. } => "StoreIndex",
+            Instruction::CreateObject { .. } => "CreateObject",
+            Instruction::LoadProperty { .. } => "LoadProperty",
+            Instruction::StoreProperty { .. } => "StoreProperty",
+            Instruction::MethodCall { .. } => "MethodCall",
+            Instruction::NewInstance { .. } => "NewInstance",
+            Instruction::Await { .. } => "Await",
+            Instruction::TypeOf { .. } => "TypeOf",
+            Instruction::InstanceOf { .. } => "InstanceOf",
+            Instruction::Cast { .. } => "Cast",
+            Instruction::Is { .. } => "Is",
+            Instruction::Throw { .. } => "Throw",
+            Instruction::Rethrow { .. } => "Rethrow",
+            Instruction::Acquire { .. } => "Acquire",
+            Instruction::Dispose { .. } => "Dispose",
+            Instruction::Assert { .. } => "Assert",
+            Instruction::Nop => "Nop",
+            Instruction::Comment { .. } => "Comment",
+            Instruction::DestructureArray { .. } => "DestructureArray",
+            Instruction::DestructureObject { .. } => "DestructureObject",
+            Instruction::Increment { .. } => "Increment",
+            Instruction::Decrement { .. } => "Decrement",
+            Instruction::CreateTemplate { .. } => "CreateTemplate",
+            Instruction::Match { .. } => "Match",
+            Instruction::CreateRange { .. } => "CreateRange",
+            Instruction::Spread { .. } => "Spread",
+            Instruction::Conditional { .. } => "Conditional",
+            Instruction::NullCoalesce { .. } => "NullCoalesce",
+            Instruction::OptionalChain { .. } => "OptionalChain",
+            Instruction::PushScope => "PushScope",
+            Instruction::PopScope => "PopScope",
+            Instruction::Break => "Break",
+            Instruction::Continue => "Continue",
+            Instruction::EnterTry { .. } => "EnterTry",
+            Instruction::ExitTry => "ExitTry",
+            Instruction::CreateClass { .. } => "CreateClass",
+            Instruction::CreateEnum { .. } => "CreateEnum",
+            Instruction::LoadThis { .. } => "LoadThis",
+            Instruction::CallSuper { .. } => "CallSuper",
+            Instruction::SpreadArray { .. } => "SpreadArray",
+            Instruction::SpreadObject { .. } => "SpreadObject",
+            Instruction::SpreadCall { .. } => "SpreadCall",
+            Instruction::Import { .. } => "Import",
+            Instruction::Export { .. } => "Export",
+            Instruction::ForIn { .. } => "ForIn",
+            Instruction::CompoundAssign { .. } => "CompoundAssign",
+            Instruction::GetIterator { .. } => "GetIterator",
+            Instruction::IteratorNext { .. } => "IteratorNext",
+            Instruction::Yield { .. } => "Yield",
+            Instruction::CreateGenerator { .. } => "CreateGenerator",
+            Instruction::Catch { .. } => "Catch",
+            Instruction::Finally { .. } => "Finally",
+            Instruction::TaggedTemplate { .. } => "TaggedTemplate",
+            Instruction::NullAssert { .. } => "NullAssert",
+            Instruction::DeleteProperty { .. } => "DeleteProperty",
+            Instruction::In { .. } => "In",
+        }
+    }
+
+    /// The source position of the expression this instruction runs, when it keeps one.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Instruction::Call { position, .. }
+            | Instruction::MethodCall { position, .. }
+            | Instruction::Await { position, .. }
+            | Instruction::LoadIndex { position, .. }
+            | Instruction::StoreIndex { position, .. }
+            | Instruction::Throw { position, .. }
+            | Instruction::GetIterator { position, .. }
+            | Instruction::IteratorNext { position, .. }
+            | Instruction::LoopHead { position } => Some(*position),
+            _ => None,
+        }
+    }
+
     pub fn is_control_flow(&self) -> bool {
         matches!(
             self,
             Instruction::Jump { .. }
                 | Instruction::JumpIfFalse { .. }
                 | Instruction::JumpIfTrue { .. }
+                | Instruction::CompareJump { .. }
+                | Instruction::IteratorNext { .. }
                 | Instruction::Return { .. }
                 | Instruction::Throw { .. }
+                | Instruction::Rethrow { .. }
                 | Instruction::Break
                 | Instruction::Continue
-                | Instruction::TryCatch { .. }
+                | Instruction::EnterTry { .. }
                 | Instruction::ForIn { .. }
-                | Instruction::ForOf { .. }
         )
     }
 
@@ -413,11 +599,17 @@ impl Instruction {
         matches!(
             self,
             Instruction::Store { .. }
+                | Instruction::DeclareFunction { .. }
                 | Instruction::StoreIndex { .. }
                 | Instruction::StoreProperty { .. }
+                | Instruction::SpreadObject { .. }
                 | Instruction::Call { .. }
                 | Instruction::MethodCall { .. }
                 | Instruction::Throw { .. }
+                | Instruction::Rethrow { .. }
+                | Instruction::Acquire { .. }
+                | Instruction::Dispose { .. }
+                | Instruction::Assert { .. }
                 | Instruction::Declare { .. }
         )
     }
@@ -432,7 +624,9 @@ impl Instruction {
             | Instruction::Call { dest, .. }
             | Instruction::CreateFunction { dest, .. }
             | Instruction::CreateArray { dest, .. }
+            | Instruction::CreateTuple { dest, .. }
             | Instruction::LoadIndex { dest, .. }
+            | Instruction::LoadSlice { dest, .. }
             | Instruction::CreateObject { dest, .. }
             | Instruction::LoadProperty { dest, .. }
             | Instruction::MethodCall { dest, .. }
@@ -440,6 +634,8 @@ impl Instruction {
             | Instruction::Await { dest, .. }
             | Instruction::TypeOf { dest, .. }
             | Instruction::InstanceOf { dest, .. }
+            | Instruction::Cast { dest, .. }
+            | Instruction::Is { dest, .. }
             | Instruction::Increment { dest, .. }
             | Instruction::Decrement { dest, .. }
             | Instruction::CreateTemplate { dest, .. }
@@ -452,7 +648,6 @@ impl Instruction {
             | Instruction::LoadThis { dest }
             | Instruction::CallSuper { dest, .. }
             | Instruction::SpreadArray { dest, .. }
-            | Instruction::SpreadObject { dest, .. }
             | Instruction::SpreadCall { dest, .. }
             | Instruction::Import { dest, .. }
             | Instruction::GetIterator { dest, .. }
@@ -472,6 +667,7 @@ impl Instruction {
             Instruction::LoadConst { .. }
             | Instruction::Declare { .. }
             | Instruction::Label { .. }
+            | Instruction::LoopHead { .. }
             | Instruction::Jump { .. }
             | Instruction::Load { .. }
             | Instruction::Nop
@@ -484,7 +680,8 @@ impl Instruction {
             | Instruction::Import { .. }
             | Instruction::CreateGenerator { .. }
             | Instruction::Finally { .. }
-            | Instruction::TryCatch { .. }
+            | Instruction::EnterTry { .. }
+            | Instruction::ExitTry
             | Instruction::CreateClass { .. } => {}
 
             Instruction::Return { value } => {
@@ -494,7 +691,9 @@ impl Instruction {
             }
 
             Instruction::Move { src, .. } => sources.push(src),
-            Instruction::Store { src, .. } => sources.push(src),
+            Instruction::Store { src, .. } | Instruction::DeclareFunction { src, .. } => {
+                sources.push(src)
+            }
 
             Instruction::BinaryOp { left, right, .. } => {
                 sources.push(left);
@@ -505,21 +704,35 @@ impl Instruction {
 
             Instruction::JumpIfFalse { condition, .. }
             | Instruction::JumpIfTrue { condition, .. } => sources.push(condition),
+            Instruction::CompareJump { left, right, .. } => {
+                sources.push(left);
+                sources.push(right);
+            }
 
             Instruction::Call { callee, args, .. } => {
                 sources.push(callee);
                 sources.extend(args.iter());
             }
 
-            Instruction::CreateArray { elements, .. } => sources.extend(elements.iter()),
+            Instruction::CreateArray { elements, .. }
+            | Instruction::CreateTuple { elements, .. } => {
+                sources.extend(elements.iter())
+            }
             Instruction::LoadIndex { array, index, .. } => {
                 sources.push(array);
                 sources.push(index);
             }
+            Instruction::LoadSlice {
+                object, start, end, ..
+            } => {
+                sources.push(object);
+                sources.extend(start.iter().chain(end.iter()));
+            }
             Instruction::StoreIndex {
                 array,
                 index,
                 value,
+                ..
             } => {
                 sources.push(array);
                 sources.push(index);
@@ -529,7 +742,14 @@ impl Instruction {
             Instruction::CreateObject { properties, .. } => {
                 sources.extend(properties.iter().map(|(_, reg)| reg));
             }
+            Instruction::CreateEnum { members, .. } => {
+                sources.extend(members.iter().map(|(_, reg)| reg));
+            }
             Instruction::LoadProperty { object, .. } => sources.push(object),
+            Instruction::SpreadObject { object, operand } => {
+                sources.push(object);
+                sources.push(operand);
+            }
             Instruction::StoreProperty { object, value, .. } => {
                 sources.push(object);
                 sources.push(value);
@@ -546,11 +766,22 @@ impl Instruction {
 
             Instruction::Await { future, .. } => sources.push(future),
 
-            Instruction::TypeOf { operand, .. } | Instruction::InstanceOf { operand, .. } => {
-                sources.push(operand)
-            }
+            Instruction::TypeOf { operand, .. }
+            | Instruction::InstanceOf { operand, .. }
+            | Instruction::Cast { operand, .. }
+            | Instruction::Is { operand, .. } => sources.push(operand),
 
-            Instruction::Throw { value } => sources.push(value),
+            Instruction::Throw { value, .. } => sources.push(value),
+            Instruction::Rethrow { error } => sources.push(error),
+            Instruction::Acquire { resource } | Instruction::Dispose { resource } => {
+                sources.push(resource)
+            }
+            Instruction::Assert {
+                condition, message, ..
+            } => {
+                sources.push(condition);
+                sources.extend(message.iter());
+            }
             Instruction::Catch { promise, .. } => sources.push(promise),
 
             Instruction::DestructureArray { src, .. }
@@ -592,7 +823,6 @@ impl Instruction {
 
             Instruction::Spread { operand, .. }
             | Instruction::SpreadArray { operand, .. }
-            | Instruction::SpreadObject { operand, .. }
             | Instruction::SpreadCall { operand, .. } => sources.push(operand),
 
             Instruction::Conditional {
@@ -618,7 +848,6 @@ impl Instruction {
             Instruction::Export { value, .. } => sources.push(value),
 
             Instruction::ForIn { object, .. } => sources.push(object),
-            Instruction::ForOf { iterable, .. } => sources.push(iterable),
 
             Instruction::CompoundAssign { dest, src, .. } => {
                 sources.push(dest);
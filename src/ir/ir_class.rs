@@ -2,14 +2,18 @@ use crate::ast::types::{PrimitiveType, Type};
 use crate::runtime::{DynamicValue, RuntimeValue};
 use std::collections::HashMap;
 
-use super::instruction::Instruction;
+use super::instruction::{IRConstructor, Instruction};
 
 #[derive(Debug, Clone)]
 pub struct IRClassValue {
     pub name: String,
-    pub constructor: Option<(Vec<String>, Vec<Instruction>)>,
+    pub superclass: Option<String>,
+    pub constructor: Option<IRMethod>,
     pub methods: HashMap<String, IRMethod>,
     pub properties: HashMap<String, RuntimeValue>,
+    pub readonly_properties: Vec<String>,
+    /// Private properties and methods declared by this class itself.
+    pub private_members: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -24,7 +28,8 @@ pub struct IRMethod {
 impl IRClassValue {
     pub fn new(
         name: String,
-        constructor: Option<(Vec<String>, Vec<Instruction>)>,
+        superclass: Option<String>,
+        constructor: Option<IRConstructor>,
         methods: Vec<(String, Vec<String>, Vec<Instruction>, HashMap<String, usize>, bool)>,
         properties: Vec<(String, RuntimeValue)>,
     ) -> Self {
@@ -47,13 +52,30 @@ impl IRClassValue {
             prop_map.insert(prop_name, value);
         }
 
+        let constructor = constructor.map(|(params, body, labels)| IRMethod {
+            name: "constructor".to_string(),
+            params,
+            body,
+            labels,
+            is_async: false,
+        });
+
         Self {
             name,
+            superclass,
             constructor,
             methods: method_map,
             properties: prop_map,
+            readonly_properties: Vec::new(),
+            private_members: Vec::new(),
         }
     }
+
+    pub fn with_access(mut self, readonly: Vec<String>, private: Vec<String>) -> Self {
+        self.readonly_properties = readonly;
+        self.private_members = private;
+        self
+    }
 }
 
 impl DynamicValue for IRClassValue {
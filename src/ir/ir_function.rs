@@ -1,5 +1,6 @@
 use crate::ast::types::{FunctionType, PrimitiveType, Type};
-use crate::runtime::{DynamicValue, RuntimeValue};
+use crate::runtime::types::operations::overloading::Signature;
+use crate::runtime::{BoolValue, DynamicValue, RuntimeValue, StrValue};
 use std::collections::HashMap;
 
 use super::instruction::Instruction;
@@ -8,15 +9,18 @@ use super::instruction::Instruction;
 pub struct IRFunctionValue {
     pub name: String,
     pub params: Vec<String>,
+    pub signature: Signature,
     pub body: Vec<Instruction>,
     pub labels: HashMap<String, usize>,
     pub is_async: bool,
+    pub overloads: Vec<IRFunctionValue>,
 }
 
 impl IRFunctionValue {
     pub fn new(
         name: String,
         params: Vec<String>,
+        signature: Signature,
         body: Vec<Instruction>,
         labels: HashMap<String, usize>,
         is_async: bool,
@@ -24,11 +28,17 @@ impl IRFunctionValue {
         Self {
             name,
             params,
+            signature,
             body,
             labels,
             is_async,
+            overloads: Vec::new(),
         }
     }
+
+    pub fn declarations(&self) -> impl Iterator<Item = &IRFunctionValue> {
+        std::iter::once(self).chain(&self.overloads)
+    }
 }
 
 impl DynamicValue for IRFunctionValue {
@@ -36,7 +46,8 @@ impl DynamicValue for IRFunctionValue {
         Type::Function(Box::new(FunctionType {
             params: vec![PrimitiveType::any(); self.params.len()],
             return_type: PrimitiveType::any(),
-            is_variadic: false,
+            is_variadic: self.signature.variadic,
+            param_names: Vec::new(),
         }))
     }
 
@@ -44,6 +55,17 @@ impl DynamicValue for IRFunctionValue {
         format!("[IR Function: {}]", self.name)
     }
 
+    fn get_property(&self, name: &str) -> Option<RuntimeValue> {
+        match name {
+            "name" if self.name == "<arrow>" => {
+                Some(RuntimeValue::Str(StrValue::new(String::new())))
+            }
+            "name" => Some(RuntimeValue::Str(StrValue::new(self.name.clone()))),
+            "isAsync" => Some(RuntimeValue::Bool(BoolValue::new(self.is_async))),
+            _ => None,
+        }
+    }
+
     fn clone_boxed(&self) -> Box<dyn DynamicValue> {
         Box::new(self.clone())
     }
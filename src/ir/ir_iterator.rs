@@ -0,0 +1,52 @@
+use crate::ast::types::{PrimitiveType, Type};
+use crate::interpreter::iteration::ValueIterator;
+use crate::runtime::DynamicValue;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// The iterator a `for-of` loop steps, shared by its copies.
+#[derive(Clone)]
+pub struct IRIteratorValue {
+    iterator: Arc<Mutex<Option<ValueIterator>>>,
+}
+
+impl IRIteratorValue {
+    pub fn new(iterator: ValueIterator) -> Self {
+        Self {
+            iterator: Arc::new(Mutex::new(Some(iterator))),
+        }
+    }
+
+    /// Takes the iterator out while it is stepped, since stepping may run code.
+    pub fn take(&self) -> Option<ValueIterator> {
+        self.iterator.lock().unwrap().take()
+    }
+
+    pub fn restore(&self, iterator: ValueIterator) {
+        *self.iterator.lock().unwrap() = Some(iterator);
+    }
+}
+
+impl fmt::Debug for IRIteratorValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IRIterator")
+    }
+}
+
+impl DynamicValue for IRIteratorValue {
+    fn get_type(&self) -> Type {
+        PrimitiveType::any()
+    }
+
+    fn to_string(&self) -> String {
+        "[IR Iterator]".to_string()
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DynamicValue> {
+        Box::new(self.clone())
+    }
+
+    fn type_name(&self) -> &str {
+        "IRIterator"
+    }
+}
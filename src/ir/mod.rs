@@ -2,12 +2,16 @@ pub mod compiler;
 pub mod instruction;
 pub mod ir_class;
 pub mod ir_function;
+pub mod ir_iterator;
 pub mod optimizer;
+pub mod stats;
 pub mod vm;
 
 pub use compiler::IRCompiler;
 pub use instruction::{Instruction, Register};
 pub use ir_class::IRClassValue;
 pub use ir_function::IRFunctionValue;
+pub use ir_iterator::IRIteratorValue;
 pub use optimizer::IROptimizer;
+pub use stats::IRStats;
 pub use vm::VM;
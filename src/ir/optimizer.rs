@@ -2,7 +2,7 @@ use crate::runtime::RuntimeValue;
 use crate::tokens::BinaryOperator;
 use std::collections::{HashMap, HashSet};
 
-use super::instruction::{IRProgram, Instruction};
+use super::instruction::{IRProgram, Instruction, Register};
 
 pub struct IROptimizer {
     program: IRProgram,
@@ -21,6 +21,7 @@ impl IROptimizer {
             self.dead_code_elimination();
             self.remove_nops();
             self.jump_threading();
+            self.peephole();
         }
 
         self.program
@@ -256,6 +257,7 @@ impl IROptimizer {
         }
 
         self.program.instructions = new_instructions;
+        self.rebuild_labels();
     }
 
     fn compute_reachable_instructions(&self) -> HashSet<usize> {
@@ -278,14 +280,20 @@ impl IROptimizer {
                     }
                 }
 
-                Instruction::JumpIfFalse { label, .. } | Instruction::JumpIfTrue { label, .. } => {
+                Instruction::JumpIfFalse { label, .. }
+                | Instruction::JumpIfTrue { label, .. }
+                | Instruction::CompareJump { label, .. }
+                | Instruction::IteratorNext { label, .. }
+                | Instruction::EnterTry { handler: label, .. } => {
                     if let Some(target) = self.program.labels.get(label) {
                         worklist.push(*target);
                     }
                     worklist.push(pos + 1);
                 }
 
-                Instruction::Return { .. } | Instruction::Throw { .. } => {}
+                Instruction::Return { .. }
+                | Instruction::Throw { .. }
+                | Instruction::Rethrow { .. } => {}
 
                 _ => {
                     worklist.push(pos + 1);
@@ -300,6 +308,20 @@ impl IROptimizer {
         self.program
             .instructions
             .retain(|inst| !matches!(inst, Instruction::Nop));
+        self.rebuild_labels();
+    }
+
+    fn rebuild_labels(&mut self) {
+        self.program.labels = self
+            .program
+            .instructions
+            .iter()
+            .enumerate()
+            .filter_map(|(position, instruction)| match instruction {
+                Instruction::Label { name } => Some((name.clone(), position)),
+                _ => None,
+            })
+            .collect();
     }
 
     fn jump_threading(&mut self) {
@@ -342,6 +364,20 @@ impl IROptimizer {
                     }
                 }
 
+                Instruction::CompareJump {
+                    left,
+                    right,
+                    op,
+                    label,
+                    jump_if,
+                } => Instruction::CompareJump {
+                    left: left.clone(),
+                    right: right.clone(),
+                    op: *op,
+                    label: Self::resolve_jump_chain(label, &label_targets),
+                    jump_if: *jump_if,
+                },
+
                 _ => instruction.clone(),
             };
 
@@ -422,4 +458,77 @@ impl IROptimizer {
 
         self.program.instructions = new_instructions;
     }
+
+    fn peephole(&mut self) {
+        let mut reads: HashMap<String, usize> = HashMap::new();
+        for instruction in &self.program.instructions {
+            for source in instruction.source_registers() {
+                *reads.entry(source.to_string()).or_default() += 1;
+            }
+        }
+
+        let mut new_instructions: Vec<Instruction> = Vec::new();
+        for instruction in &self.program.instructions {
+            match instruction {
+                Instruction::Move { dest, src } if dest == src => continue,
+
+                Instruction::Move { dest, src } => {
+                    if let Some(Instruction::Move {
+                        dest: prev_dest,
+                        src: prev_src,
+                    }) = new_instructions.last()
+                    {
+                        if prev_dest == src && prev_src == dest {
+                            continue;
+                        }
+                    }
+                }
+
+                Instruction::JumpIfFalse { condition, label }
+                | Instruction::JumpIfTrue { condition, label } => {
+                    let fusable = matches!(condition, Register::Temp(_))
+                        && reads.get(&condition.to_string()) == Some(&1);
+                    if let Some(Instruction::BinaryOp {
+                        dest,
+                        left,
+                        right,
+                        op,
+                    }) = new_instructions.last()
+                    {
+                        if fusable && dest == condition && Self::is_comparison(op) {
+                            let fused = Instruction::CompareJump {
+                                left: left.clone(),
+                                right: right.clone(),
+                                op: *op,
+                                label: label.clone(),
+                                jump_if: matches!(instruction, Instruction::JumpIfTrue { .. }),
+                            };
+                            new_instructions.pop();
+                            new_instructions.push(fused);
+                            continue;
+                        }
+                    }
+                }
+
+                _ => {}
+            }
+
+            new_instructions.push(instruction.clone());
+        }
+
+        self.program.instructions = new_instructions;
+        self.rebuild_labels();
+    }
+
+    fn is_comparison(op: &BinaryOperator) -> bool {
+        matches!(
+            op,
+            BinaryOperator::Equal
+                | BinaryOperator::NotEqual
+                | BinaryOperator::LessThan
+                | BinaryOperator::LessEqual
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterEqual
+        )
+    }
 }
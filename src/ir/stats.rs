@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+/// Executed-instruction counters collected by the VM under `--ir-stats`.
+#[derive(Debug, Default)]
+pub struct IRStats {
+    opcodes: HashMap<&'static str, u64>,
+    functions: HashMap<String, u64>,
+}
+
+impl IRStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, function: &str, opcode: &'static str) {
+        *self.opcodes.entry(opcode).or_default() += 1;
+        match self.functions.get_mut(function) {
+            Some(count) => *count += 1,
+            None => {
+                self.functions.insert(function.to_string(), 1);
+            }
+        }
+    }
+
+    pub fn opcodes(&self) -> &HashMap<&'static str, u64> {
+        &self.opcodes
+    }
+
+    pub fn functions(&self) -> &HashMap<String, u64> {
+        &self.functions
+    }
+
+    pub fn total(&self) -> u64 {
+        self.opcodes.values().sum()
+    }
+
+    pub fn format_summary(&self) -> String {
+        let mut output = format!("{:<32} {:>12}\n", "opcode", "executed");
+        for (opcode, count) in Self::sorted(&self.opcodes) {
+            output.push_str(&format!("{:<32} {:>12}\n", opcode, count));
+        }
+
+        output.push_str(&format!("\n{:<32} {:>12}\n", "function", "executed"));
+        for (function, count) in Self::sorted(&self.functions) {
+            output.push_str(&format!("{:<32} {:>12}\n", function, count));
+        }
+
+        output.push_str(&format!("\n{:<32} {:>12}\n", "total", self.total()));
+        output
+    }
+
+    fn sorted<K: Ord>(counts: &HashMap<K, u64>) -> Vec<(&K, &u64)> {
+        let mut entries: Vec<_> = counts.iter().collect();
+        entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        entries
+    }
+}
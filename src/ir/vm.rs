@@ -1,16 +1,66 @@
 use crate::error::RaccoonError;
-use crate::runtime::{Environment, RuntimeValue, TypeRegistry};
+use crate::interpreter::builtins::Builtins;
+use crate::interpreter::control_flow::{ControlFlow, DISPOSE_METHODS};
+use crate::interpreter::declarations::Declarations;
+use crate::interpreter::expressions::Expressions;
+use crate::interpreter::helpers::Helpers;
+use crate::interpreter::iteration::Iteration;
+use crate::interpreter::overloads::Overloads;
+use crate::interpreter::{Interpreter, InterpreterResult, CLASS_CONTEXT};
+use crate::runtime::builtins::{global, StringBuilderValue};
+use crate::runtime::values::{ClassValue, EnumValueData};
+use crate::runtime::{
+    BoundCall, ClassInstance, Environment, RuntimeValue, Sandbox, TypeRegistry,
+};
+use crate::runtime::types::objects::FunctionType;
+use crate::runtime::types::operations::casting::{self, TypeScope};
+use crate::runtime::types::operations::overloading;
+use crate::runtime::types::special::EnumType;
+use crate::tokens::{AccessModifier, Position};
 use async_recursion::async_recursion;
-use std::collections::HashMap;
+use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use super::instruction::{IRProgram, Instruction, MatchPattern, Register, TemplatePart};
+use super::ir_class::{IRClassValue, IRMethod};
+use super::ir_function::IRFunctionValue;
+use super::ir_iterator::IRIteratorValue;
+use super::stats::IRStats;
 
 pub struct VM {
     registers: HashMap<String, RuntimeValue>,
     environment: Environment,
+    pending_constants: HashSet<String>,
     pc: usize,
     program: Option<IRProgram>,
     type_registry: std::sync::Arc<TypeRegistry>,
+    sandbox: Option<std::sync::Arc<Sandbox>>,
+    handlers: Vec<TryHandler>,
+    caught: HashMap<String, RaccoonError>,
+    method_caches: HashMap<usize, MethodCache>,
+    stats: Option<Arc<Mutex<IRStats>>>,
+    frame: String,
+    file: Option<String>,
+    position: Position,
+}
+
+static SHAPE_EPOCH: AtomicU64 = AtomicU64::new(0);
+
+struct MethodCache {
+    class_name: String,
+    method: String,
+    epoch: u64,
+    owner: String,
+    ir_method: Rc<IRMethod>,
+}
+
+struct TryHandler {
+    label: String,
+    error: Register,
+    scope_depth: usize,
 }
 
 impl VM {
@@ -18,16 +68,98 @@ impl VM {
         Self {
             registers: HashMap::new(),
             environment,
+            pending_constants: HashSet::new(),
             pc: 0,
             program: None,
             type_registry,
+            sandbox: None,
+            handlers: Vec::new(),
+            caught: HashMap::new(),
+            method_caches: HashMap::new(),
+            stats: None,
+            frame: "<main>".to_string(),
+            file: None,
+            position: (0, 0),
         }
     }
 
+    pub fn with_sandbox(mut self, sandbox: Option<std::sync::Arc<Sandbox>>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    pub fn with_stats(mut self, stats: Option<Arc<Mutex<IRStats>>>) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    pub fn with_file(mut self, file: Option<String>) -> Self {
+        self.file = file;
+        self
+    }
+
+    pub fn is_function(value: &RuntimeValue) -> bool {
+        Self::ir_function(value).is_some()
+    }
+
+    /// Calls an IR function the interpreter was handed.
+    pub async fn call_from_interpreter(
+        interpreter: &Interpreter,
+        callee: RuntimeValue,
+        args: Vec<RuntimeValue>,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        Self::for_interpreter(interpreter)
+            .call_function(callee, args)
+            .await
+    }
+
+    /// Runs method `name` of an IR class instance; `None` when no IR class declares it.
+    pub async fn call_method_from_interpreter(
+        interpreter: &Interpreter,
+        instance: &Arc<ClassInstance>,
+        name: &str,
+        args: Vec<RuntimeValue>,
+    ) -> Result<Option<RuntimeValue>, RaccoonError> {
+        let mut vm = Self::for_interpreter(interpreter);
+        let found = vm
+            .ir_class_lineage(&instance.class_name)
+            .into_iter()
+            .find_map(|ir_class| {
+                let ir_method = ir_class.methods.get(name).cloned()?;
+                Some((ir_class.name, ir_method))
+            });
+        let Some((owner, ir_method)) = found else {
+            return Ok(None);
+        };
+        vm.invoke_ir_method(instance, &owner, &ir_method, args)
+            .await
+            .map(Some)
+    }
+
+    fn for_interpreter(interpreter: &Interpreter) -> VM {
+        VM::new(
+            interpreter.environment.clone(),
+            interpreter.type_registry.clone(),
+        )
+        .with_sandbox(interpreter.sandbox.clone())
+        .with_file(interpreter.file.clone())
+    }
+
+    fn nested(&self, environment: Environment, frame: &str) -> VM {
+        let mut vm = VM::new(environment, self.type_registry.clone())
+            .with_sandbox(self.sandbox.clone())
+            .with_stats(self.stats.clone())
+            .with_file(self.file.clone());
+        vm.frame = frame.to_string();
+        vm.position = self.position;
+        vm
+    }
+
     #[async_recursion(?Send)]
     pub async fn execute(&mut self, program: IRProgram) -> Result<RuntimeValue, RaccoonError> {
         self.program = Some(program);
         self.pc = 0;
+        self.method_caches.clear();
 
         let mut last_value = RuntimeValue::Null(crate::runtime::NullValue::new());
 
@@ -35,7 +167,23 @@ impl VM {
             let instruction = self.get_instruction(self.pc).clone();
             self.pc += 1;
 
-            match self.execute_instruction(&instruction).await? {
+            if let Some(position) = instruction.position() {
+                self.position = position;
+            }
+            if let Some(sandbox) = &self.sandbox {
+                sandbox.tick(self.position, &self.file)?;
+            }
+
+            if let Some(stats) = &self.stats {
+                stats.lock().unwrap().record(&self.frame, instruction.opcode());
+            }
+
+            let outcome = match self.execute_instruction(&instruction).await {
+                Ok(outcome) => outcome,
+                Err(error) => self.unwind(error).await?,
+            };
+
+            match outcome {
                 ExecutionResult::Continue => {}
                 ExecutionResult::Return(value) => {
                     last_value = value;
@@ -75,22 +223,68 @@ impl VM {
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::Declare { name, is_const: _ } => {
+            Instruction::Declare { name, is_const } => {
                 self.environment.declare(
                     name.clone(),
                     RuntimeValue::Null(crate::runtime::NullValue::new()),
                 )?;
+                if *is_const {
+                    self.pending_constants.insert(name.clone());
+                }
                 Ok(ExecutionResult::Continue)
             }
 
             Instruction::Store { name, src } => {
                 let value = self.get_register(src)?;
                 self.environment.assign(name, value, (0, 0))?;
+                if self.pending_constants.remove(name) {
+                    self.environment.mark_constant(name, (0, 0));
+                }
+                Ok(ExecutionResult::Continue)
+            }
+
+            Instruction::DeclareFunction { name, src } => {
+                let function = self.get_register(src)?;
+                let existing = self
+                    .environment
+                    .scopes()
+                    .last()
+                    .and_then(|scope| scope.get(name))
+                    .and_then(Self::ir_function)
+                    .filter(|existing| existing.name == *name)
+                    .cloned();
+                match (existing, Self::ir_function(&function)) {
+                    (Some(mut existing), Some(overload)) => {
+                        if existing
+                            .declarations()
+                            .any(|other| other.signature.same_params(&overload.signature))
+                        {
+                            return Err(RaccoonError::new(
+                                format!(
+                                    "Function '{}' already has an overload taking {}",
+                                    name, overload.signature
+                                ),
+                                (0, 0),
+                                None::<String>,
+                            ));
+                        }
+                        existing.overloads.push(overload.clone());
+                        self.environment.update(
+                            name,
+                            RuntimeValue::Dynamic(Box::new(existing)),
+                            (0, 0),
+                        )?;
+                    }
+                    _ => self.environment.declare(name.clone(), function)?,
+                }
                 Ok(ExecutionResult::Continue)
             }
 
             Instruction::Load { dest, name } => {
-                let value = self.environment.get(name, (0, 0))?;
+                let value = self
+                    .environment
+                    .get(name, (0, 0))
+                    .or_else(|error| global::fallback(name).ok_or(error))?;
                 self.set_register(dest, value);
                 Ok(ExecutionResult::Continue)
             }
@@ -104,15 +298,7 @@ impl VM {
                 let left_val = self.get_register(left)?;
                 let right_val = self.get_register(right)?;
 
-                let result = crate::interpreter::operators::apply_binary_op(
-                    left_val,
-                    right_val,
-                    op.clone(),
-                    (0, 0),
-                    &None,
-                    &crate::runtime::CallStack::new(),
-                )
-                .await?;
+                let result = self.binary_op(left_val, right_val, *op).await?;
 
                 self.set_register(dest, result);
                 Ok(ExecutionResult::Continue)
@@ -183,9 +369,32 @@ impl VM {
                 }
             }
 
-            Instruction::Label { .. } => Ok(ExecutionResult::Continue),
+            Instruction::CompareJump {
+                left,
+                right,
+                op,
+                label,
+                jump_if,
+            } => {
+                let left_val = self.get_register(left)?;
+                let right_val = self.get_register(right)?;
+
+                let result = self.binary_op(left_val, right_val, *op).await?;
+
+                if crate::interpreter::operators::is_truthy(&result) == *jump_if {
+                    Ok(ExecutionResult::Jump(label.clone()))
+                } else {
+                    Ok(ExecutionResult::Continue)
+                }
+            }
+
+            Instruction::Label { .. } | Instruction::LoopHead { .. } => {
+                Ok(ExecutionResult::Continue)
+            }
 
-            Instruction::Call { dest, callee, args } => {
+            Instruction::Call {
+                dest, callee, args, ..
+            } => {
                 let callee_val = self.get_register(callee)?;
 
                 let mut arg_values = Vec::new();
@@ -211,6 +420,7 @@ impl VM {
                 dest,
                 name,
                 params,
+                signature,
                 body,
                 labels,
                 is_async,
@@ -218,6 +428,7 @@ impl VM {
                 let ir_func = crate::ir::IRFunctionValue::new(
                     name.clone(),
                     params.clone(),
+                    signature.clone(),
                     body.clone(),
                     labels.clone(),
                     *is_async,
@@ -241,18 +452,63 @@ impl VM {
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::LoadIndex { dest, array, index } => {
+            Instruction::CreateTuple { dest, elements } => {
+                let mut element_values = Vec::new();
+                for elem in elements {
+                    element_values.push(self.get_register(elem)?);
+                }
+
+                let tuple = RuntimeValue::Tuple(crate::runtime::TupleValue::new(element_values));
+                self.set_register(dest, tuple);
+                Ok(ExecutionResult::Continue)
+            }
+
+            Instruction::LoadIndex {
+                dest,
+                array,
+                index,
+                position,
+            } => {
                 let array_val = self.get_register(array)?;
                 let index_val = self.get_register(index)?;
 
+                if let RuntimeValue::ClassInstance(instance) = &array_val {
+                    if instance.methods.contains_key("__index__") {
+                        let result = self
+                            .call_method(array_val.clone(), "__index__", vec![index_val])
+                            .await?;
+                        self.set_register(dest, result);
+                        return Ok(ExecutionResult::Continue);
+                    }
+                }
+
+                if let RuntimeValue::Range(range) = &index_val {
+                    let slice = crate::interpreter::operators::slice_by_range(&array_val, range);
+                    if let Some(slice) = slice {
+                        self.set_register(dest, slice);
+                        return Ok(ExecutionResult::Continue);
+                    }
+                }
+
                 let result = match array_val {
                     RuntimeValue::Array(arr) => {
                         if let RuntimeValue::Int(i) = index_val {
-                            let idx = i.value as usize;
-                            if idx < arr.elements.len() {
-                                arr.elements[idx].clone()
-                            } else {
-                                RuntimeValue::Null(crate::runtime::NullValue::new())
+                            match crate::interpreter::operators::resolve_index(
+                                i.value,
+                                arr.len(),
+                            ) {
+                                Some(idx) => arr.elements.read().unwrap()[idx].clone(),
+                                None => {
+                                    return Err(RaccoonError::index_out_of_range_error(
+                                        format!(
+                                            "Index {} out of bounds (length: {})",
+                                            i.value,
+                                            arr.len()
+                                        ),
+                                        *position,
+                                        self.file.clone(),
+                                    ))
+                                }
                             }
                         } else {
                             return Err(RaccoonError::new(
@@ -262,6 +518,76 @@ impl VM {
                             ));
                         }
                     }
+                    RuntimeValue::Range(range) => {
+                        let idx = match &index_val {
+                            RuntimeValue::Int(i) => {
+                                crate::interpreter::operators::resolve_index(i.value, range.len())
+                            }
+                            _ => None,
+                        };
+                        match idx.and_then(|idx| range.get(idx)) {
+                            Some(value) => RuntimeValue::Int(crate::runtime::IntValue::new(value)),
+                            None => {
+                                return Err(RaccoonError::index_out_of_range_error(
+                                    format!(
+                                        "Range index {} out of bounds (length: {})",
+                                        index_val.to_string(),
+                                        range.len()
+                                    ),
+                                    *position,
+                                    self.file.clone(),
+                                ))
+                            }
+                        }
+                    }
+                    RuntimeValue::Tuple(tuple) => {
+                        let idx = match &index_val {
+                            RuntimeValue::Int(i) => crate::interpreter::operators::resolve_index(
+                                i.value,
+                                tuple.elements.len(),
+                            ),
+                            _ => None,
+                        };
+                        match idx {
+                            Some(idx) => tuple.elements[idx].clone(),
+                            None => {
+                                return Err(RaccoonError::index_out_of_range_error(
+                                    format!(
+                                        "Tuple index {} out of bounds (length: {})",
+                                        index_val.to_string(),
+                                        tuple.elements.len()
+                                    ),
+                                    *position,
+                                    self.file.clone(),
+                                ))
+                            }
+                        }
+                    }
+                    RuntimeValue::Str(s) => {
+                        let chars: Vec<char> = s.value.chars().collect();
+                        let idx = match &index_val {
+                            RuntimeValue::Int(i) => {
+                                crate::interpreter::operators::resolve_index(i.value, chars.len())
+                            }
+                            _ => None,
+                        };
+                        match idx {
+                            Some(idx) => {
+                                RuntimeValue::Str(crate::runtime::StrValue::new(chars[idx].into()))
+                            }
+                            None => {
+                                return Err(RaccoonError::index_out_of_range_error(
+                                    format!(
+                                        "String index {} out of bounds (length: {})",
+                                        index_val.to_string(),
+                                        chars.len()
+                                    ),
+                                    *position,
+                                    self.file.clone(),
+                                ))
+                            }
+                        }
+                    }
                     _ => {
                         return Err(RaccoonError::new(
                             "Cannot index non-array value",
@@ -275,27 +601,66 @@ impl VM {
                 Ok(ExecutionResult::Continue)
             }
 
+            Instruction::LoadSlice {
+                dest,
+                object,
+                start,
+                end,
+                inclusive,
+            } => {
+                let object_val = self.get_register(object)?;
+                let mut bounds = [None, None];
+                for (bound, register) in bounds.iter_mut().zip([start, end]) {
+                    if let Some(register) = register {
+                        match self.get_register(register)? {
+                            RuntimeValue::Int(i) => *bound = Some(i.value),
+                            RuntimeValue::Null(_) => {}
+                            _ => {
+                                return Err(RaccoonError::new(
+                                    "Slice bounds must be integers",
+                                    (0, 0),
+                                    None::<String>,
+                                ))
+                            }
+                        }
+                    }
+                }
+
+                let result = crate::interpreter::operators::slice_value(
+                    &object_val,
+                    bounds[0],
+                    bounds[1],
+                    *inclusive,
+                )
+                .map_err(|message| RaccoonError::new(message, (0, 0), None::<String>))?;
+
+                self.set_register(dest, result);
+                Ok(ExecutionResult::Continue)
+            }
+
             Instruction::StoreIndex {
                 array,
                 index,
                 value,
+                position,
             } => {
                 let array_val = self.get_register(array)?;
                 let index_val = self.get_register(index)?;
                 let value_val = self.get_register(value)?;
 
                 match array_val {
-                    RuntimeValue::Array(mut arr) => {
+                    RuntimeValue::Array(arr) => {
                         if let RuntimeValue::Int(i) = index_val {
-                            let idx = i.value as usize;
-                            if idx < arr.elements.len() {
-                                arr.elements[idx] = value_val;
-                                self.set_register(array, RuntimeValue::Array(arr));
+                            let len = arr.len();
+                            if let Some(idx) =
+                                crate::interpreter::operators::resolve_index(i.value, len)
+                            {
+                                arr.elements.write().unwrap()[idx] = value_val;
                             } else {
-                                return Err(RaccoonError::new(
-                                    "Array index out of bounds",
-                                    (0, 0),
-                                    None::<String>,
+                                return Err(RaccoonError::index_out_of_range_error(
+                                    format!("Index {} out of bounds (length: {})", i.value, len),
+                                    *position,
+                                    self.file.clone(),
                                 ));
                             }
                         } else {
@@ -306,6 +671,25 @@ impl VM {
                             ));
                         }
                     }
+                    RuntimeValue::Object(obj)
+                        if matches!(index_val, RuntimeValue::Str(_) | RuntimeValue::Symbol(_)) =>
+                    {
+                        let key = crate::interpreter::operators::property_key(&index_val);
+                        obj.check_writable(&key).map_err(|message| {
+                            RaccoonError::new(message, *position, self.file.clone())
+                        })?;
+                        obj.properties.write().unwrap().insert(key, value_val);
+                    }
+                    RuntimeValue::ClassInstance(instance)
+                        if matches!(index_val, RuntimeValue::Str(_) | RuntimeValue::Symbol(_)) =>
+                    {
+                        let key = crate::interpreter::operators::property_key(&index_val);
+                        self.check_private_access(&instance, &key)?;
+                        instance.check_writable(&key).map_err(|message| {
+                            RaccoonError::new(message, *position, self.file.clone())
+                        })?;
+                        instance.properties.write().unwrap().insert(key, value_val);
+                    }
                     _ => {
                         return Err(RaccoonError::new(
                             "Cannot index non-array value",
@@ -319,7 +703,7 @@ impl VM {
             }
 
             Instruction::CreateObject { dest, properties } => {
-                let mut prop_map = HashMap::new();
+                let mut prop_map = IndexMap::new();
                 for (key, val_reg) in properties {
                     let value = self.get_register(val_reg)?;
                     prop_map.insert(key.clone(), value);
@@ -343,22 +727,39 @@ impl VM {
                 let result = match object_val {
                     RuntimeValue::Object(obj) => obj
                         .properties
+                        .read()
+                        .unwrap()
                         .get(property)
                         .cloned()
                         .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new())),
                     RuntimeValue::Array(arr) => match property.as_str() {
                         "length" => RuntimeValue::Int(crate::runtime::IntValue::new(
-                            arr.elements.len() as i64,
+                            arr.len() as i64,
                         )),
                         "first" => {
-                            if arr.elements.is_empty() {
+                            if arr.is_empty() {
                                 RuntimeValue::Null(crate::runtime::NullValue::new())
                             } else {
-                                arr.elements[0].clone()
+                                arr.elements.read().unwrap()[0].clone()
                             }
                         }
                         _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
                     },
+                    RuntimeValue::Range(range) => match property.as_str() {
+                        "length" => RuntimeValue::Int(crate::runtime::IntValue::new(
+                            range.len() as i64,
+                        )),
+                        "start" => RuntimeValue::Int(crate::runtime::IntValue::new(range.start)),
+                        "end" => RuntimeValue::Int(crate::runtime::IntValue::new(range.end)),
+                        "step" => RuntimeValue::Int(crate::runtime::IntValue::new(range.step)),
+                        _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
+                    },
+                    RuntimeValue::Tuple(tuple) => match property.as_str() {
+                        "length" => RuntimeValue::Int(crate::runtime::IntValue::new(
+                            tuple.elements.len() as i64,
+                        )),
+                        _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
+                    },
                     RuntimeValue::Str(s) => match property.as_str() {
                         "length" => {
                             RuntimeValue::Int(crate::runtime::IntValue::new(s.value.len() as i64))
@@ -369,12 +770,24 @@ impl VM {
                         _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
                     },
                     RuntimeValue::ClassInstance(instance) => {
+                        self.check_private_access(&instance, property)?;
                         if let Some(value) = instance.properties.read().unwrap().get(property) {
                             value.clone()
                         } else {
                             RuntimeValue::Null(crate::runtime::NullValue::new())
                         }
                     }
+                    RuntimeValue::Type(type_obj) => type_obj
+                        .get_static_property(property)
+                        .cloned()
+                        .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new())),
+                    function if FunctionType::is_function(&function) => {
+                        FunctionType::property(&function, property)
+                            .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()))
+                    }
+                    RuntimeValue::Dynamic(value) => value
+                        .get_property(property)
+                        .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new())),
                     _ => {
                         return Err(RaccoonError::new(
                             "Cannot access property of non-object",
@@ -397,16 +810,27 @@ impl VM {
                 let value_val = self.get_register(value)?;
 
                 match object_val {
-                    RuntimeValue::Object(mut obj) => {
-                        obj.properties.insert(property.clone(), value_val);
-                        self.set_register(object, RuntimeValue::Object(obj));
+                    RuntimeValue::Object(obj) => {
+                        obj.check_writable(property)
+                            .map_err(|message| RaccoonError::new(message, (0, 0), None::<String>))?;
+                        obj.properties
+                            .write()
+                            .unwrap()
+                            .insert(property.clone(), value_val);
                     }
                     RuntimeValue::ClassInstance(instance) => {
+                        self.check_private_access(&instance, property)?;
                         instance
+                            .check_writable(property)
+                            .map_err(|message| RaccoonError::new(message, (0, 0), None::<String>))?;
+                        let previous = instance
                             .properties
                             .write()
                             .unwrap()
                             .insert(property.clone(), value_val);
+                        if previous.is_none() {
+                            SHAPE_EPOCH.fetch_add(1, Ordering::Relaxed);
+                        }
                         self.set_register(object, RuntimeValue::ClassInstance(instance));
                     }
                     _ => {
@@ -426,6 +850,7 @@ impl VM {
                 object,
                 method,
                 args,
+                ..
             } => {
                 let object_val = self.get_register(object)?;
 
@@ -434,7 +859,25 @@ impl VM {
                     arg_values.push(self.get_register(arg)?);
                 }
 
-                let result = self.call_method(object_val, method, arg_values).await?;
+                if let RuntimeValue::ClassInstance(instance) = &object_val {
+                    self.check_private_access(instance, method)?;
+                }
+
+                let site = self.pc - 1;
+                let cached = match &object_val {
+                    RuntimeValue::ClassInstance(instance) => self
+                        .cached_method(site, &instance.class_name, method)
+                        .map(|(owner, ir_method)| (instance.clone(), owner, ir_method)),
+                    _ => None,
+                };
+
+                let result = match cached {
+                    Some((instance, owner, ir_method)) => {
+                        self.invoke_ir_method(&instance, &owner, &ir_method, arg_values)
+                            .await?
+                    }
+                    None => self.call_method(object_val, method, arg_values).await?,
+                };
                 self.set_register(dest, result);
                 Ok(ExecutionResult::Continue)
             }
@@ -449,108 +892,158 @@ impl VM {
                     arg_values.push(self.get_register(arg)?);
                 }
 
-                let class_val = self.environment.get(class_name, (0, 0))?;
-
-                match class_val {
-                    RuntimeValue::Dynamic(dyn_val) if dyn_val.type_name() == "IRClass" => {
-                        let ir_class: &crate::ir::IRClassValue = unsafe {
-                            &*(dyn_val.as_ref() as *const dyn crate::runtime::DynamicValue
-                                as *const crate::ir::IRClassValue)
-                        };
-
-                        let mut instance_props = ir_class.properties.clone();
-
-                        if let Some((params, body)) = &ir_class.constructor {
-                            let mut ctor_env = self.environment.clone();
-                            ctor_env.push_scope();
-
-                            let temp_instance = RuntimeValue::Object(crate::runtime::ObjectValue::new(
-                                instance_props.clone(),
-                                crate::ast::types::PrimitiveType::any(),
-                            ));
-                            ctor_env.declare("this".to_string(), temp_instance)?;
-
-                            for (i, param) in params.iter().enumerate() {
-                                let arg_value = arg_values
-                                    .get(i)
-                                    .cloned()
-                                    .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()));
-                                ctor_env.declare(param.clone(), arg_value)?;
-                            }
-
-                            let mut ctor_vm = VM::new(ctor_env, self.type_registry.clone());
-                            let ctor_program = IRProgram {
-                                instructions: body.clone(),
-                                constant_pool: Vec::new(),
-                                labels: HashMap::new(),
-                            };
-                            ctor_vm.execute(ctor_program).await?;
-
-                            if let Ok(updated_this) = ctor_vm.environment.get("this", (0, 0)) {
-                                if let RuntimeValue::Object(obj) = updated_this {
-                                    instance_props = obj.properties.clone();
-                                }
-                            }
+                let lineage = self.ir_class_lineage(class_name);
+                if lineage.is_empty() && class_name == "Map" {
+                    let map = RuntimeValue::Map(Arc::new(crate::runtime::MapValue::new(
+                        IndexMap::new(),
+                        crate::ast::types::PrimitiveType::any(),
+                        crate::ast::types::PrimitiveType::any(),
+                    )));
+                    self.set_register(dest, map);
+                    return Ok(ExecutionResult::Continue);
+                }
+                if lineage.is_empty() && class_name == StringBuilderValue::TYPE_NAME {
+                    let initial = arg_values.first().map(|arg| arg.to_string());
+                    let builder = StringBuilderValue::new(&initial.unwrap_or_default());
+                    self.set_register(dest, builder.into_value());
+                    return Ok(ExecutionResult::Continue);
+                }
+                let mut interpreter = self.interpreter();
+                let Some(class) = lineage.first() else {
+                    // A class the interpreter declared, such as a builtin error, is built as
+                    // it builds it.
+                    let class = Expressions::resolve_class(&interpreter, class_name, (0, 0));
+                    let instance = match class {
+                        Ok(class) => {
+                            Expressions::instantiate(
+                                &mut interpreter,
+                                &class,
+                                arg_values,
+                                HashMap::new(),
+                                (0, 0),
+                            )
+                            .await?
                         }
+                        Err(_) => RuntimeValue::Object(crate::runtime::ObjectValue::new(
+                            HashMap::new(),
+                            crate::ast::types::PrimitiveType::any(),
+                        )),
+                    };
+                    self.set_register(dest, instance);
+                    return Ok(ExecutionResult::Continue);
+                };
 
-                        // Convert IR methods to FunctionValue
-                        let mut method_map = HashMap::new();
-                        for (method_name, ir_method) in &ir_class.methods {
-                            // Convert IR method to FunctionValue
-                            let params: Vec<crate::ast::nodes::FnParam> = ir_method
-                                .params
+                // Base classes first, so subclasses override inherited properties and methods.
+                let ast_lineage = Self::ast_lineage(&interpreter, &lineage);
+                let mut properties = HashMap::new();
+                let mut method_map = HashMap::new();
+                let mut readonly_properties = Vec::new();
+                let mut private_members = HashMap::new();
+                for ast_class in ast_lineage.iter().rev() {
+                    let declaration = &ast_class.declaration;
+                    for prop in &declaration.properties {
+                        properties.insert(
+                            prop.name.clone(),
+                            RuntimeValue::Null(crate::runtime::NullValue::new()),
+                        );
+                        if prop.is_readonly {
+                            readonly_properties.push(prop.name.clone());
+                        }
+                    }
+                    let private = declaration
+                        .properties
+                        .iter()
+                        .filter(|prop| prop.access_modifier == AccessModifier::Private)
+                        .map(|prop| &prop.name)
+                        .chain(
+                            declaration
+                                .methods
                                 .iter()
-                                .map(|p| crate::ast::nodes::FnParam {
-                                    pattern: crate::ast::nodes::VarPattern::Identifier(p.clone()),
-                                    param_type: crate::ast::types::PrimitiveType::any(),
-                                    default_value: None,
-                                    is_optional: false,
-                                    is_rest: false,
+                                .filter(|method| {
+                                    !method.is_static
+                                        && method.access_modifier == AccessModifier::Private
                                 })
-                                .collect();
-
-                            // We need to convert IR instructions back to AST statements
-                            // For now, we'll store a special marker function that the IR VM will handle
-                            let func_value = crate::runtime::FunctionValue::new(
-                                params,
-                                vec![], // Empty body - we'll execute IR instructions directly
-                                ir_method.is_async,
-                                crate::ast::types::PrimitiveType::any(),
-                            );
-
-                            method_map.insert(method_name.clone(), func_value);
-                        }
-
-                        // Create ClassInstance with methods
-                        let instance = crate::runtime::ClassInstance::new(
-                            ir_class.name.clone(),
-                            instance_props.clone(),
-                            method_map,
-                            vec![],
-                            crate::ast::types::PrimitiveType::any(),
+                                .map(|method| &method.name),
                         );
-
-                        self.set_register(dest, RuntimeValue::ClassInstance(instance));
+                    for member in private {
+                        private_members.insert(member.clone(), ast_class.class_name.clone());
                     }
-                    _ => {
-                        let instance = RuntimeValue::Object(crate::runtime::ObjectValue::new(
-                            HashMap::new(),
-                            crate::ast::types::PrimitiveType::any(),
-                        ));
-                        self.set_register(dest, instance);
+                }
+                for ir_class in lineage.iter().rev() {
+                    properties.extend(ir_class.properties.clone());
+                    for (method_name, ir_method) in &ir_class.methods {
+                        method_map.insert(method_name.clone(), Self::method_stub(ir_method));
+                    }
+                    readonly_properties.extend(ir_class.readonly_properties.iter().cloned());
+                    for member in &ir_class.private_members {
+                        private_members.insert(member.clone(), ir_class.name.clone());
                     }
                 }
 
+                let is_error = ast_lineage.last().is_some_and(|root| {
+                    root.class_name == "Error" && root.declaration.superclass.is_none()
+                });
+                let instance = Arc::new(
+                    ClassInstance::new(
+                        class.name.clone(),
+                        properties,
+                        method_map,
+                        vec![],
+                        crate::ast::types::PrimitiveType::any(),
+                    )
+                    .with_readonly_properties(readonly_properties)
+                    .with_private_members(private_members)
+                    .with_error(is_error),
+                );
+
+                let constructor = lineage.iter().find_map(|ir_class| {
+                    let constructor = ir_class.constructor.clone()?;
+                    Some((ir_class.name.clone(), constructor))
+                });
+                if let Some((owner, constructor)) = constructor {
+                    self.invoke_ir_method(&instance, &owner, &constructor, arg_values)
+                        .await?;
+                } else {
+                    Self::invoke_ast_constructor(
+                        &mut interpreter,
+                        &instance,
+                        &ast_lineage,
+                        arg_values,
+                    )
+                    .await?;
+                }
+
+                instance.finish_construction();
+                self.set_register(dest, RuntimeValue::ClassInstance(instance));
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::Await { dest, future } => {
+            Instruction::Await {
+                dest,
+                future,
+                position,
+            } => {
                 let future_val = self.get_register(future)?;
 
                 let result = match future_val {
-                    RuntimeValue::Future(fut) => fut.wait_for_completion().await.map_err(|e| {
-                        RaccoonError::new(&format!("Await error: {}", e), (0, 0), None::<String>)
-                    })?,
+                    RuntimeValue::Future(fut) => {
+                        let completion = match &self.sandbox {
+                            Some(sandbox) => {
+                                sandbox
+                                    .wait(fut.wait_for_completion(), *position, &self.file)
+                                    .await?
+                            }
+                            None => fut.wait_for_completion().await,
+                        };
+                        completion.map_err(|rejection| {
+                            Declarations::rejected_error(
+                                self,
+                                rejection,
+                                *position,
+                                self.file.clone(),
+                            )
+                        })?
+                    }
                     _ => future_val,
                 };
 
@@ -579,10 +1072,83 @@ impl VM {
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::Throw { value } => {
+            Instruction::Cast {
+                dest,
+                operand,
+                target_type,
+            } => {
+                let operand_val = self.get_register(operand)?;
+                let result = casting::cast(operand_val, target_type, self, (0, 0), &None)?;
+                self.set_register(dest, result);
+                Ok(ExecutionResult::Continue)
+            }
+
+            Instruction::Is {
+                dest,
+                operand,
+                target_type,
+            } => {
+                let operand_val = self.get_register(operand)?;
+                let is_match = casting::value_is(&operand_val, target_type, self);
+                let result = RuntimeValue::Bool(crate::runtime::BoolValue::new(is_match));
+                self.set_register(dest, result);
+                Ok(ExecutionResult::Continue)
+            }
+
+            Instruction::Throw { value, position } => {
                 let value_val = self.get_register(value)?;
-                Err(RaccoonError::new(
-                    &format!("Thrown: {}", value_val.to_string()),
+                Err(Declarations::thrown_error(
+                    self,
+                    value_val,
+                    *position,
+                    self.file.clone(),
+                ))
+            }
+
+            Instruction::Acquire { resource } => {
+                self.disposal_method(&self.get_register(resource)?)?;
+                Ok(ExecutionResult::Continue)
+            }
+
+            Instruction::Dispose { resource } => {
+                let resource = self.get_register(resource)?;
+                if let Some(method) = self.disposal_method(&resource)? {
+                    if let RuntimeValue::Future(future) =
+                        self.call_method(resource, method, Vec::new()).await?
+                    {
+                        future.wait_for_completion().await.map_err(|rejection| {
+                            Declarations::rejected_error(self, rejection, (0, 0), None)
+                        })?;
+                    }
+                }
+                Ok(ExecutionResult::Continue)
+            }
+
+            Instruction::Rethrow { error } => match self.caught.get(&error.to_string()) {
+                Some(caught) => Err(caught.clone()),
+                None => {
+                    let value = self.get_register(error)?;
+                    Err(RaccoonError::new(value.to_string(), (0, 0), None::<String>)
+                        .with_thrown(value))
+                }
+            },
+
+            Instruction::Assert {
+                condition,
+                message,
+                kind,
+                source,
+            } => {
+                let condition_val = self.get_register(condition)?;
+                if crate::interpreter::operators::is_truthy(&condition_val) {
+                    return Ok(ExecutionResult::Continue);
+                }
+                let message = match message {
+                    Some(message) => Some(self.get_register(message)?.to_string()),
+                    None => None,
+                };
+                Err(RaccoonError::assertion_error(
+                    kind.failure_message(source, message.as_deref()),
                     (0, 0),
                     None::<String>,
                 ))
@@ -598,13 +1164,17 @@ impl VM {
                 has_rest,
                 rest_dest,
             } => {
-                let src_val = self.get_register(src)?;
+                let src_val = match self.get_register(src)? {
+                    RuntimeValue::Range(range) => RuntimeValue::Array(range.to_list()),
+                    value => value,
+                };
 
                 match src_val {
                     RuntimeValue::Array(arr) => {
+                        let elements = arr.snapshot();
                         for (i, dest) in dests.iter().enumerate() {
-                            let value = if i < arr.elements.len() {
-                                arr.elements[i].clone()
+                            let value = if i < elements.len() {
+                                elements[i].clone()
                             } else {
                                 RuntimeValue::Null(crate::runtime::NullValue::new())
                             };
@@ -613,7 +1183,7 @@ impl VM {
 
                         if *has_rest {
                             if let Some(rest) = rest_dest {
-                                let rest_elements = arr.elements[dests.len()..].to_vec();
+                                let rest_elements = elements.get(dests.len()..).unwrap_or_default().to_vec();
                                 let rest_array =
                                     RuntimeValue::Array(crate::runtime::ArrayValue::new(
                                         rest_elements,
@@ -623,6 +1193,29 @@ impl VM {
                             }
                         }
                     }
+                    RuntimeValue::Tuple(tuple) => {
+                        let arity = tuple.elements.len();
+                        if arity < dests.len() || (!*has_rest && arity != dests.len()) {
+                            return Err(RaccoonError::new(
+                                format!(
+                                    "Cannot destructure a tuple of {} elements into {} bindings",
+                                    arity,
+                                    dests.len()
+                                ),
+                                (0, 0),
+                                None::<String>,
+                            ));
+                        }
+                        for (dest, value) in dests.iter().zip(&tuple.elements) {
+                            self.set_register(dest, value.clone());
+                        }
+                        if let (true, Some(rest)) = (*has_rest, rest_dest) {
+                            let rest_tuple = RuntimeValue::Tuple(crate::runtime::TupleValue::new(
+                                tuple.elements[dests.len()..].to_vec(),
+                            ));
+                            self.set_register(rest, rest_tuple);
+                        }
+                    }
                     _ => {
                         return Err(RaccoonError::new(
                             "Cannot destructure non-array value",
@@ -638,7 +1231,7 @@ impl VM {
             Instruction::DestructureObject {
                 mappings,
                 src,
-                rest_dest: _,
+                rest_dest,
             } => {
                 let src_val = self.get_register(src)?;
 
@@ -647,11 +1240,24 @@ impl VM {
                         for (key, dest) in mappings {
                             let value = obj
                                 .properties
+                                .read()
+                                .unwrap()
                                 .get(key)
                                 .cloned()
                                 .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()));
                             self.set_register(dest, value);
                         }
+
+                        if let Some(rest) = rest_dest {
+                            let mut remaining = obj.snapshot();
+                            remaining
+                                .retain(|key, _| !mappings.iter().any(|(taken, _)| taken == key));
+                            let rest_object = RuntimeValue::Object(crate::runtime::ObjectValue::new(
+                                remaining,
+                                crate::ast::types::PrimitiveType::any(),
+                            ));
+                            self.set_register(rest, rest_object);
+                        }
                     }
                     _ => {
                         return Err(RaccoonError::new(
@@ -757,7 +1363,14 @@ impl VM {
                         TemplatePart::String(s) => result.push_str(s),
                         TemplatePart::Expr(reg) => {
                             let value = self.get_register(reg)?;
-                            result.push_str(&value.to_string());
+                            if Self::overloads_str(&value) {
+                                let mut interpreter = self.interpreter();
+                                let text =
+                                    Overloads::stringify(&mut interpreter, &value, (0, 0)).await?;
+                                result.push_str(&text);
+                            } else {
+                                result.push_str(&value.to_string());
+                            }
                         }
                     }
                 }
@@ -783,7 +1396,7 @@ impl VM {
                             }
                         }
 
-                        let mut arm_vm = VM::new(self.environment.clone(), self.type_registry.clone());
+                        let mut arm_vm = self.nested(self.environment.clone(), &self.frame);
                         let arm_program = IRProgram {
                             instructions: arm.body.clone(),
                             constant_pool: Vec::new(),
@@ -802,19 +1415,25 @@ impl VM {
                 ));
             }
 
-            Instruction::CreateRange { dest, start, end } => {
-                let start_val = self.get_register(start)?;
-                let end_val = self.get_register(end)?;
-
-                let mut props = HashMap::new();
-                props.insert("start".to_string(), start_val);
-                props.insert("end".to_string(), end_val);
-
-                let range = RuntimeValue::Object(crate::runtime::ObjectValue::new(
-                    props,
-                    crate::ast::types::PrimitiveType::any(),
-                ));
-                self.set_register(dest, range);
+            Instruction::CreateRange {
+                dest,
+                start,
+                end,
+                inclusive,
+            } => {
+                let range = match (self.get_register(start)?, self.get_register(end)?) {
+                    (RuntimeValue::Int(start), RuntimeValue::Int(end)) => {
+                        crate::runtime::RangeValue::new(start.value, end.value, *inclusive)
+                    }
+                    _ => {
+                        return Err(RaccoonError::new(
+                            "Range operator requires integer operands",
+                            (0, 0),
+                            None::<String>,
+                        ))
+                    }
+                };
+                self.set_register(dest, RuntimeValue::Range(range));
                 Ok(ExecutionResult::Continue)
             }
 
@@ -866,6 +1485,8 @@ impl VM {
                     RuntimeValue::Null(_) => RuntimeValue::Null(crate::runtime::NullValue::new()),
                     RuntimeValue::Object(obj) => obj
                         .properties
+                        .read()
+                        .unwrap()
                         .get(property)
                         .cloned()
                         .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new())),
@@ -895,136 +1516,53 @@ impl VM {
                 object,
                 body,
             } => {
-                let obj_val = self.get_register(object)?;
-
-                match obj_val {
-                    RuntimeValue::Object(obj) => {
-                        for key in obj.properties.keys() {
-                            let mut loop_env = self.environment.clone();
-                            loop_env.push_scope();
-                            loop_env.declare(
-                                variable.clone(),
-                                RuntimeValue::Str(crate::runtime::StrValue::new(key.clone())),
-                            )?;
-
-                            let mut loop_vm = VM::new(loop_env, self.type_registry.clone());
-                            let loop_program = IRProgram {
-                                instructions: body.clone(),
-                                constant_pool: Vec::new(),
-                                labels: HashMap::new(),
-                            };
-                            loop_vm.execute(loop_program).await?;
-                        }
-                    }
-                    RuntimeValue::Array(arr) => {
-                        for (i, _) in arr.elements.iter().enumerate() {
-                            let mut loop_env = self.environment.clone();
-                            loop_env.push_scope();
-                            loop_env.declare(
-                                variable.clone(),
-                                RuntimeValue::Int(crate::runtime::IntValue::new(i as i64)),
-                            )?;
-
-                            let mut loop_vm = VM::new(loop_env, self.type_registry.clone());
-                            let loop_program = IRProgram {
-                                instructions: body.clone(),
-                                constant_pool: Vec::new(),
-                                labels: HashMap::new(),
-                            };
-                            loop_vm.execute(loop_program).await?;
-                        }
+                let keys: Vec<RuntimeValue> = match self.get_register(object)? {
+                    RuntimeValue::Object(obj) => obj
+                        .snapshot()
+                        .into_keys()
+                        .filter(|key| !crate::runtime::SymbolValue::is_property_key(key))
+                        .map(|key| RuntimeValue::Str(crate::runtime::StrValue::new(key)))
+                        .collect(),
+                    RuntimeValue::Map(map) => map
+                        .entries
+                        .read()
+                        .unwrap()
+                        .keys()
+                        .map(crate::runtime::MapKey::to_value)
+                        .collect(),
+                    RuntimeValue::Array(arr) => (0..arr.len())
+                        .map(|i| RuntimeValue::Int(crate::runtime::IntValue::new(i as i64)))
+                        .collect(),
+                    RuntimeValue::Range(range) => {
+                        let indices = (0..range.len() as i64)
+                            .map(|i| RuntimeValue::Int(crate::runtime::IntValue::new(i)));
+                        self.run_loop_body(variable, indices, body).await?;
+                        return Ok(ExecutionResult::Continue);
                     }
                     _ => {
                         return Err(RaccoonError::new(
-                            "for-in requires object or array",
+                            "for-in requires object, map or array",
                             (0, 0),
                             None::<String>,
                         ))
                     }
-                }
+                };
 
+                self.run_loop_body(variable, keys, body).await?;
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::ForOf {
-                variable,
-                iterable,
-                body,
-            } => {
-                let iter_val = self.get_register(iterable)?;
-
-                match iter_val {
-                    RuntimeValue::Array(arr) => {
-                        for elem in arr.elements {
-                            let mut loop_env = self.environment.clone();
-                            loop_env.push_scope();
-                            loop_env.declare(variable.clone(), elem)?;
-
-                            let mut loop_vm = VM::new(loop_env, self.type_registry.clone());
-                            let loop_program = IRProgram {
-                                instructions: body.clone(),
-                                constant_pool: Vec::new(),
-                                labels: HashMap::new(),
-                            };
-                            loop_vm.execute(loop_program).await?;
-                        }
-                    }
-                    _ => {
-                        return Err(RaccoonError::new(
-                            "for-of requires iterable",
-                            (0, 0),
-                            None::<String>,
-                        ))
-                    }
-                }
-
+            Instruction::EnterTry { handler, error } => {
+                self.handlers.push(TryHandler {
+                    label: handler.clone(),
+                    error: error.clone(),
+                    scope_depth: self.environment.scopes().len(),
+                });
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::TryCatch {
-                try_body,
-                catch_handler,
-                finally_body,
-            } => {
-                let mut try_vm = VM::new(self.environment.clone(), self.type_registry.clone());
-                let try_program = IRProgram {
-                    instructions: try_body.clone(),
-                    constant_pool: Vec::new(),
-                    labels: HashMap::new(),
-                };
-
-                let try_result = try_vm.execute(try_program).await;
-
-                match try_result {
-                    Err(error) if catch_handler.is_some() => {
-                        let (error_var, catch_body) = catch_handler.as_ref().unwrap();
-                        self.environment.declare(
-                            error_var.clone(),
-                            RuntimeValue::Str(crate::runtime::StrValue::new(error.message.clone())),
-                        )?;
-
-                        let mut catch_vm = VM::new(self.environment.clone(), self.type_registry.clone());
-                        let catch_program = IRProgram {
-                            instructions: catch_body.clone(),
-                            constant_pool: Vec::new(),
-                            labels: HashMap::new(),
-                        };
-                        catch_vm.execute(catch_program).await?;
-                    }
-                    Ok(_) => {}
-                    Err(e) => return Err(e),
-                }
-
-                if let Some(finally) = finally_body {
-                    let mut finally_vm = VM::new(self.environment.clone(), self.type_registry.clone());
-                    let finally_program = IRProgram {
-                        instructions: finally.clone(),
-                        constant_pool: Vec::new(),
-                        labels: HashMap::new(),
-                    };
-                    finally_vm.execute(finally_program).await?;
-                }
-
+            Instruction::ExitTry => {
+                self.handlers.pop();
                 Ok(ExecutionResult::Continue)
             }
 
@@ -1039,20 +1577,85 @@ impl VM {
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::CallSuper {
-                dest,
-                method: _,
-                args: _,
-            } => {
-                self.set_register(dest, RuntimeValue::Null(crate::runtime::NullValue::new()));
+            Instruction::CallSuper { dest, method, args } => {
+                let mut arg_values = Vec::new();
+                for arg in args {
+                    arg_values.push(self.get_register(arg)?);
+                }
+
+                let Ok(RuntimeValue::ClassInstance(instance)) =
+                    self.environment.get("this", (0, 0))
+                else {
+                    return Err(RaccoonError::new(
+                        "'super' can only be used inside a class",
+                        (0, 0),
+                        None::<String>,
+                    ));
+                };
+
+                let owner = match self.environment.get(CLASS_CONTEXT, (0, 0)) {
+                    Ok(RuntimeValue::Str(owner)) => owner.value,
+                    _ => instance.class_name.clone(),
+                };
+                let Some(parent) = self.ir_class(&owner).and_then(|class| class.superclass) else {
+                    return Err(RaccoonError::new(
+                        format!("Class '{}' has no superclass", owner),
+                        (0, 0),
+                        None::<String>,
+                    ));
+                };
+
+                let found = self
+                    .ir_class_lineage(&parent)
+                    .into_iter()
+                    .find_map(|ir_class| {
+                        let target = if method == "constructor" {
+                            ir_class.constructor.clone()
+                        } else {
+                            ir_class.methods.get(method).cloned()
+                        };
+                        Some((ir_class.name, target?))
+                    });
+
+                let result = match found {
+                    Some((owner, target)) => {
+                        self.invoke_ir_method(&instance, &owner, &target, arg_values)
+                            .await?
+                    }
+                    None if method == "constructor" => {
+                        let mut interpreter = self.interpreter();
+                        let ast_lineage =
+                            Self::ast_lineage(&interpreter, &self.ir_class_lineage(&owner));
+                        Self::invoke_ast_constructor(
+                            &mut interpreter,
+                            &instance,
+                            &ast_lineage,
+                            arg_values,
+                        )
+                        .await?;
+                        RuntimeValue::Null(crate::runtime::NullValue::new())
+                    }
+                    None => {
+                        return Err(RaccoonError::new(
+                            format!("Method '{}' not found on superclass '{}'", method, parent),
+                            (0, 0),
+                            None::<String>,
+                        ))
+                    }
+                };
+
+                self.set_register(dest, result);
                 Ok(ExecutionResult::Continue)
             }
 
             Instruction::CreateClass {
                 name,
+                superclass,
                 constructor,
                 methods,
                 properties,
+                readonly,
+                private,
             } => {
                 let mut prop_values = Vec::new();
                 for (prop_name, prop_reg) in properties {
@@ -1060,32 +1663,55 @@ impl VM {
                     prop_values.push((prop_name.clone(), value));
                 }
 
-                let ir_class = crate::ir::IRClassValue::new(
+                let ir_class = IRClassValue::new(
                     name.clone(),
+                    superclass.clone(),
                     constructor.clone(),
                     methods.clone(),
                     prop_values,
-                );
+                )
+                .with_access(readonly.clone(), private.clone());
 
                 let class_value = RuntimeValue::Dynamic(Box::new(ir_class));
                 self.environment.declare(name.clone(), class_value)?;
+                SHAPE_EPOCH.fetch_add(1, Ordering::Relaxed);
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::SpreadArray { dest, operand } => {
-                let val = self.get_register(operand)?;
+            Instruction::CreateEnum { name, members } => {
+                let mut variants = Vec::new();
+                let mut member_data = HashMap::new();
+                for (member_name, member_reg) in members {
+                    let data = match self.get_register(member_reg)? {
+                        RuntimeValue::Int(i) => EnumValueData::Int(i.value),
+                        RuntimeValue::Str(s) => EnumValueData::Str(s.value),
+                        other => EnumValueData::Str(other.to_string()),
+                    };
+                    variants.push(member_name.clone());
+                    member_data.insert(member_name.clone(), data);
+                }
 
-                self.set_register(dest, val);
+                let enum_value = Declarations::enum_type_object(name, variants, member_data);
+                self.environment.declare(name.clone(), enum_value)?;
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::SpreadObject { dest, operand } => {
+            Instruction::SpreadArray { dest, operand } => {
                 let val = self.get_register(operand)?;
 
                 self.set_register(dest, val);
                 Ok(ExecutionResult::Continue)
             }
 
+            Instruction::SpreadObject { object, operand } => {
+                if let (RuntimeValue::Object(target), RuntimeValue::Object(source)) =
+                    (self.get_register(object)?, self.get_register(operand)?)
+                {
+                    target.properties.write().unwrap().extend(source.snapshot());
+                }
+                Ok(ExecutionResult::Continue)
+            }
+
             Instruction::SpreadCall { dest, operand } => {
                 let val = self.get_register(operand)?;
 
@@ -1112,41 +1738,71 @@ impl VM {
                 let dest_val = self.get_register(dest)?;
                 let src_val = self.get_register(src)?;
 
-                let result = crate::interpreter::operators::apply_binary_op(
-                    dest_val,
-                    src_val,
-                    op.clone(),
-                    (0, 0),
-                    &None,
-                    &crate::runtime::CallStack::new(),
-                )
-                .await?;
+                let result = self.binary_op(dest_val, src_val, *op).await?;
 
                 self.set_register(dest, result);
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::GetIterator { dest, iterable } => {
-                let iter_val = self.get_register(iterable)?;
-
-                self.set_register(dest, iter_val);
+            Instruction::GetIterator {
+                dest,
+                iterable,
+                position,
+            } => {
+                let value = self.get_register(iterable)?;
+                let mut interpreter = self.interpreter();
+                let Some(iterator) = Iteration::iterate(&mut interpreter, value, *position).await?
+                else {
+                    return Err(RaccoonError::new(
+                        "For-of requires an iterable value",
+                        *position,
+                        self.file.clone(),
+                    ));
+                };
+                self.set_register(
+                    dest,
+                    RuntimeValue::Dynamic(Box::new(IRIteratorValue::new(iterator))),
+                );
                 Ok(ExecutionResult::Continue)
             }
 
-            Instruction::IteratorNext { dest, iterator } => {
-                let _iter = self.get_register(iterator)?;
-
-                let mut result = HashMap::new();
-                result.insert(
-                    "done".to_string(),
-                    RuntimeValue::Bool(crate::runtime::BoolValue::new(true)),
-                );
-                let iter_result = RuntimeValue::Object(crate::runtime::ObjectValue::new(
-                    result,
-                    crate::ast::types::PrimitiveType::any(),
-                ));
-                self.set_register(dest, iter_result);
-                Ok(ExecutionResult::Continue)
+            Instruction::IteratorNext {
+                dest,
+                iterator,
+                label,
+                position,
+            } => {
+                let handle = match self.get_register(iterator)? {
+                    RuntimeValue::Dynamic(dyn_val) if dyn_val.type_name() == "IRIterator" => {
+                        let handle: &IRIteratorValue = unsafe {
+                            &*(dyn_val.as_ref() as *const dyn crate::runtime::DynamicValue
+                                as *const IRIteratorValue)
+                        };
+                        handle.clone()
+                    }
+                    _ => {
+                        return Err(RaccoonError::new(
+                            "IteratorNext requires an iterator",
+                            *position,
+                            self.file.clone(),
+                        ))
+                    }
+                };
+                let Some(mut stepped) = handle.take() else {
+                    return Ok(ExecutionResult::Jump(label.clone()));
+                };
+                let element = match stepped.next_builtin() {
+                    Some(element) => Ok(element),
+                    None => stepped.next(&mut self.interpreter(), *position).await,
+                };
+                handle.restore(stepped);
+                match element? {
+                    Some(element) => {
+                        self.set_register(dest, element);
+                        Ok(ExecutionResult::Continue)
+                    }
+                    None => Ok(ExecutionResult::Jump(label.clone())),
+                }
             }
 
             Instruction::CreateGenerator {
@@ -1227,8 +1883,8 @@ impl VM {
                 let obj_val = self.get_register(object)?;
 
                 match obj_val {
-                    RuntimeValue::Object(mut obj) => {
-                        obj.properties.remove(property);
+                    RuntimeValue::Object(obj) => {
+                        obj.properties.write().unwrap().shift_remove(property);
                         let result = RuntimeValue::Bool(crate::runtime::BoolValue::new(true));
                         self.set_register(dest, result);
                     }
@@ -1249,7 +1905,7 @@ impl VM {
                 let obj_val = self.get_register(object)?;
 
                 let exists = match obj_val {
-                    RuntimeValue::Object(obj) => obj.properties.contains_key(property),
+                    RuntimeValue::Object(obj) => obj.contains(property),
                     _ => false,
                 };
 
@@ -1271,7 +1927,10 @@ impl VM {
             RuntimeValue::Str(_) => "str".to_string(),
             RuntimeValue::Bool(_) => "bool".to_string(),
             RuntimeValue::Null(_) => "null".to_string(),
+            RuntimeValue::Symbol(_) => "symbol".to_string(),
             RuntimeValue::Array(_) => "array".to_string(),
+            RuntimeValue::Tuple(_) => "tuple".to_string(),
+            RuntimeValue::Range(_) => "range".to_string(),
             RuntimeValue::Map(_) => "map".to_string(),
             RuntimeValue::Object(_) => "object".to_string(),
             RuntimeValue::Class(c) => format!("class {}", c.class_name),
@@ -1317,27 +1976,27 @@ impl VM {
                 }
             }
             MatchPattern::Variable(_) => Ok(true),
-            MatchPattern::Array(patterns) => {
-                match value {
-                    RuntimeValue::Array(arr) => {
-                        if patterns.len() != arr.elements.len() {
-                            return Ok(false);
-                        }
-                        for (i, p) in patterns.iter().enumerate() {
-                            if !self.matches_pattern(&arr.elements[i], p)? {
-                                return Ok(false);
-                            }
-                        }
-                        Ok(true)
+            MatchPattern::Array(patterns) | MatchPattern::Tuple(patterns) => {
+                let elements = match (pattern, value) {
+                    (MatchPattern::Array(_), RuntimeValue::Array(arr)) => arr.snapshot(),
+                    (MatchPattern::Tuple(_), RuntimeValue::Tuple(tuple)) => tuple.elements.clone(),
+                    _ => return Ok(false),
+                };
+                if patterns.len() != elements.len() {
+                    return Ok(false);
+                }
+                for (element, p) in elements.iter().zip(patterns) {
+                    if !self.matches_pattern(element, p)? {
+                        return Ok(false);
                     }
-                    _ => Ok(false),
                 }
+                Ok(true)
             }
             MatchPattern::Object(props) => {
                 match value {
                     RuntimeValue::Object(obj) => {
                         for (key, pattern) in props {
-                            if let Some(prop_value) = obj.properties.get(key) {
+                            if let Some(prop_value) = obj.properties.read().unwrap().get(key) {
                                 if !self.matches_pattern(prop_value, pattern)? {
                                     return Ok(false);
                                 }
@@ -1367,64 +2026,52 @@ impl VM {
         args: Vec<RuntimeValue>,
     ) -> Result<RuntimeValue, RaccoonError> {
         match callee {
-            RuntimeValue::NativeFunction(func) => Ok((func.implementation)(args)),
-            RuntimeValue::NativeAsyncFunction(func) => Ok((func.implementation)(args).await),
-            RuntimeValue::Function(func) => {
-                // Execute normal Function using the interpreter
-                let mut func_env = self.environment.clone();
-                func_env.push_scope();
-
-                // Bind parameters
-                for (i, param) in func.parameters.iter().enumerate() {
-                    let arg_value = args
-                        .get(i)
-                        .cloned()
-                        .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()));
-
-                    match &param.pattern {
-                        crate::ast::nodes::VarPattern::Identifier(name) => {
-                            func_env.declare(name.clone(), arg_value)?;
-                        }
-                        crate::ast::nodes::VarPattern::Destructuring(_) => {
-                            // For now, skip destructuring in parameters
-                        }
-                    }
-                }
-
-                // Execute function body using the interpreter
-                let mut interpreter = crate::interpreter::Interpreter::new(None);
-                interpreter.environment = func_env;
-                let mut result = RuntimeValue::Null(crate::runtime::NullValue::new());
-
-                for stmt in &func.body {
-                    match interpreter.execute_stmt_internal(stmt).await? {
-                        crate::interpreter::InterpreterResult::Value(v) => result = v,
-                        crate::interpreter::InterpreterResult::Return(v) => {
-                            return Ok(v);
-                        }
-                        _ => {}
-                    }
+            RuntimeValue::NativeFunction(func) => {
+                let args = if args.iter().any(Self::overloads_str) {
+                    Overloads::stringify_args(&mut self.interpreter(), args, (0, 0)).await?
+                } else {
+                    args
+                };
+                Ok((func.implementation)(args))
+            }
+            RuntimeValue::NativeAsyncFunction(func) => {
+                if let (Some(sandbox), Some(name)) = (&self.sandbox, &func.name) {
+                    sandbox.check_call(name, func.module, &args, self.position, &self.file)?;
                 }
-
-                Ok(result)
+                Ok((func.implementation)(args).await)
+            }
+            RuntimeValue::Function(func) if func.bound.is_some() => {
+                let bound = func.bound.as_deref().expect("checked by the guard");
+                self.call_bound(bound, args).await
             }
-            RuntimeValue::Dynamic(dyn_val) => {
-                if dyn_val.type_name() == "IRFunction" {
-                    let ir_func: &crate::ir::IRFunctionValue =
-                        unsafe { &*(dyn_val.as_ref() as *const dyn crate::runtime::DynamicValue as *const crate::ir::IRFunctionValue) };
+            RuntimeValue::Function(_) => {
+                let mut interpreter = self.interpreter();
+                Helpers::call_function(&mut interpreter, &callee, args, (0, 0)).await
+            }
+            RuntimeValue::Dynamic(_) => {
+                if let Some(ir_func) = Self::ir_function(&callee) {
+                    let ir_func = self.select_overload(ir_func, &args)?;
 
                     let mut func_env = self.environment.clone();
                     func_env.push_scope();
 
+                    let has_rest = ir_func.signature.variadic
+                        && ir_func.params.len() == ir_func.signature.params.len();
                     for (i, param) in ir_func.params.iter().enumerate() {
-                        let arg_value = args
-                            .get(i)
-                            .cloned()
-                            .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()));
+                        let arg_value = if has_rest && i + 1 == ir_func.params.len() {
+                            RuntimeValue::Array(crate::runtime::ArrayValue::new(
+                                args[i.min(args.len())..].to_vec(),
+                                crate::ast::types::PrimitiveType::any(),
+                            ))
+                        } else {
+                            args.get(i)
+                                .cloned()
+                                .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()))
+                        };
                         func_env.declare(param.clone(), arg_value)?;
                     }
 
-                    let mut func_vm = VM::new(func_env, self.type_registry.clone());
+                    let mut func_vm = self.nested(func_env, &ir_func.name);
                     let func_program = IRProgram {
                         instructions: ir_func.body.clone(),
                         constant_pool: Vec::new(),
@@ -1448,16 +2095,83 @@ impl VM {
         }
     }
 
+    async fn run_loop_body(
+        &mut self,
+        variable: &str,
+        elements: impl IntoIterator<Item = RuntimeValue>,
+        body: &[Instruction],
+    ) -> Result<(), RaccoonError> {
+        for element in elements {
+            let mut loop_env = self.environment.clone();
+            loop_env.push_scope();
+            loop_env.declare(variable.to_string(), element)?;
+
+            let mut loop_vm = self.nested(loop_env, &self.frame);
+            let loop_program = IRProgram {
+                instructions: body.to_vec(),
+                constant_pool: Vec::new(),
+                labels: HashMap::new(),
+            };
+            loop_vm.execute(loop_program).await?;
+        }
+        Ok(())
+    }
+
+    #[async_recursion(?Send)]
+    async fn call_bound(
+        &mut self,
+        bound: &BoundCall,
+        args: Vec<RuntimeValue>,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let args: Vec<RuntimeValue> = bound.args.iter().cloned().chain(args).collect();
+        if matches!(bound.this, RuntimeValue::Null(_)) {
+            return self.call_function(bound.target.clone(), args).await;
+        }
+
+        self.environment.push_scope();
+        let mut declared = self
+            .environment
+            .declare("this".to_string(), bound.this.clone());
+        if let (Ok(()), RuntimeValue::ClassInstance(instance)) = (&declared, &bound.this) {
+            declared = self.environment.declare(
+                CLASS_CONTEXT.to_string(),
+                RuntimeValue::Str(crate::runtime::StrValue::new(instance.class_name.clone())),
+            );
+        }
+        let result = match declared {
+            Ok(()) => self.call_function(bound.target.clone(), args).await,
+            Err(error) => Err(error),
+        };
+        self.environment.pop_scope();
+        result
+    }
+
     async fn call_method(
         &mut self,
         object: RuntimeValue,
         method: &str,
         args: Vec<RuntimeValue>,
     ) -> Result<RuntimeValue, RaccoonError> {
+        if FunctionType::is_function(&object) {
+            if matches!(method, "call" | "apply") {
+                let bound = FunctionType::invocation(&object, method, args, (0, 0), None)?;
+                return self.call_bound(&bound, Vec::new()).await;
+            }
+            if let Some(handler) = self.type_registry.get_handler("function") {
+                return handler.call_instance_method(
+                    &mut object.clone(),
+                    method,
+                    args,
+                    (0, 0),
+                    None::<String>,
+                );
+            }
+        }
+
         match object {
             RuntimeValue::Object(obj) => {
-                if let Some(method_val) = obj.properties.get(method) {
-                    self.call_function(method_val.clone(), args).await
+                if let Some(method_val) = obj.get(method) {
+                    self.call_function(method_val, args).await
                 } else {
                     Err(RaccoonError::new(
                         &format!("Method '{}' not found", method),
@@ -1467,43 +2181,15 @@ impl VM {
                 }
             }
             RuntimeValue::ClassInstance(instance) => {
-                // First, try to get the IR class from the environment to access IR methods
-                if let Ok(class_val) = self.environment.get(&instance.class_name, (0, 0)) {
-                    if let RuntimeValue::Dynamic(dyn_val) = class_val {
-                        if dyn_val.type_name() == "IRClass" {
-                            let ir_class: &crate::ir::IRClassValue = unsafe {
-                                &*(dyn_val.as_ref() as *const dyn crate::runtime::DynamicValue
-                                    as *const crate::ir::IRClassValue)
-                            };
-
-                            // Check if the method exists in IR methods
-                            if let Some(ir_method) = ir_class.methods.get(method) {
-                                // Execute IR method
-                                let mut method_env = self.environment.clone();
-                                method_env.push_scope();
-                                method_env.declare("this".to_string(), RuntimeValue::ClassInstance(instance.clone()))?;
-
-                                // Bind parameters
-                                for (i, param) in ir_method.params.iter().enumerate() {
-                                    let arg_value = args
-                                        .get(i)
-                                        .cloned()
-                                        .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()));
-                                    method_env.declare(param.clone(), arg_value)?;
-                                }
-
-                                // Execute IR instructions
-                                let mut method_vm = VM::new(method_env, self.type_registry.clone());
-                                let method_program = IRProgram {
-                                    instructions: ir_method.body.clone(),
-                                    constant_pool: Vec::new(),
-                                    labels: ir_method.labels.clone(),
-                                };
-
-                                return method_vm.execute(method_program).await;
-                            }
-                        }
-                    }
+                let found = self
+                    .ir_class_lineage(&instance.class_name)
+                    .into_iter()
+                    .find_map(|ir_class| {
+                        let ir_method = ir_class.methods.get(method).cloned()?;
+                        Some((ir_class.name, ir_method))
+                    });
+                if let Some((owner, ir_method)) = found {
+                    return self.invoke_ir_method(&instance, &owner, &ir_method, args).await;
                 }
 
                 // Fallback: check if method exists in instance.methods (for non-IR classes)
@@ -1532,7 +2218,7 @@ impl VM {
                         interpreter.environment = method_env;
                         let mut result = RuntimeValue::Null(crate::runtime::NullValue::new());
 
-                        for stmt in &method_func.body {
+                        for stmt in method_func.body.iter() {
                             match interpreter.execute_stmt_internal(stmt).await? {
                                 crate::interpreter::InterpreterResult::Value(v) => result = v,
                                 crate::interpreter::InterpreterResult::Return(v) => {
@@ -1552,8 +2238,38 @@ impl VM {
                     None::<String>,
                 ))
             }
+            RuntimeValue::Range(range)
+                if !self.type_registry.has_instance_method("range", method) =>
+            {
+                let list = RuntimeValue::Array(range.to_list());
+                Box::pin(self.call_method(list, method, args)).await
+            }
+            RuntimeValue::Array(_) => {
+                let mut object = object;
+                if Builtins::FUNCTIONAL_METHODS.contains(&method) {
+                    let mut interpreter = self.interpreter();
+                    Builtins::handle_array_functional_method(
+                        &mut interpreter,
+                        &mut object,
+                        method,
+                        args,
+                        (0, 0),
+                    )
+                    .await
+                } else {
+                    self.type_registry.call_instance_method(
+                        &mut object,
+                        method,
+                        args,
+                        (0, 0),
+                        self.file.clone(),
+                    )
+                }
+            }
             RuntimeValue::Str(_)
             | RuntimeValue::Map(_)
+            | RuntimeValue::Tuple(_)
+            | RuntimeValue::Range(_)
             | RuntimeValue::Int(_)
             | RuntimeValue::Float(_)
             | RuntimeValue::Decimal(_)
@@ -1568,6 +2284,31 @@ impl VM {
                     None::<String>,
                 )
             }
+            RuntimeValue::Dynamic(value) => value
+                .call_method(method, args)
+                .map_err(|message| RaccoonError::new(message, (0, 0), None::<String>)),
+            RuntimeValue::Type(type_obj) => {
+                if let Some(result) =
+                    EnumType::call_enum_method(&type_obj, method, &args, (0, 0), None)
+                {
+                    return result;
+                }
+                let type_name = type_obj.name();
+                match type_obj.get_static_method(method) {
+                    Some(static_method)
+                        if !self.type_registry.has_static_method(&type_name, method) =>
+                    {
+                        self.call_function(static_method.clone(), args).await
+                    }
+                    _ => self.type_registry.call_static_method(
+                        &type_name,
+                        method,
+                        args,
+                        (0, 0),
+                        None::<String>,
+                    ),
+                }
+            }
             _ => {
                 // For other types, return error
                 Err(RaccoonError::new(
@@ -1579,6 +2320,321 @@ impl VM {
         }
     }
 
+    fn cached_method(
+        &mut self,
+        site: usize,
+        class_name: &str,
+        method: &str,
+    ) -> Option<(String, Rc<IRMethod>)> {
+        let epoch = SHAPE_EPOCH.load(Ordering::Relaxed);
+        if let Some(cache) = self.method_caches.get(&site) {
+            if cache.epoch == epoch && cache.class_name == class_name && cache.method == method {
+                return Some((cache.owner.clone(), cache.ir_method.clone()));
+            }
+        }
+
+        let (owner, ir_method) = self
+            .ir_class_lineage(class_name)
+            .into_iter()
+            .find_map(|ir_class| {
+                let ir_method = ir_class.methods.get(method).cloned()?;
+                Some((ir_class.name, Rc::new(ir_method)))
+            })?;
+
+        self.method_caches.insert(
+            site,
+            MethodCache {
+                class_name: class_name.to_string(),
+                method: method.to_string(),
+                epoch,
+                owner: owner.clone(),
+                ir_method: ir_method.clone(),
+            },
+        );
+        Some((owner, ir_method))
+    }
+
+    async fn unwind(&mut self, error: RaccoonError) -> Result<ExecutionResult, RaccoonError> {
+        let Some(handler) = self.handlers.pop() else {
+            return Err(error);
+        };
+
+        while self.environment.scopes().len() > handler.scope_depth {
+            self.environment.pop_scope();
+        }
+        let caught = ControlFlow::caught_value(&mut self.interpreter(), &error).await;
+        self.set_register(&handler.error, caught);
+        self.caught.insert(handler.error.to_string(), error);
+        Ok(ExecutionResult::Jump(handler.label))
+    }
+
+    fn overloads_str(value: &RuntimeValue) -> bool {
+        matches!(value, RuntimeValue::ClassInstance(instance) if instance.methods.contains_key("__str__"))
+    }
+
+    async fn binary_op(
+        &self,
+        left: RuntimeValue,
+        right: RuntimeValue,
+        op: crate::tokens::BinaryOperator,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        if Overloads::applies(&left, &right) {
+            let mut interpreter = self.interpreter();
+            if let Some(result) =
+                Overloads::binary(&mut interpreter, &left, &right, op, (0, 0)).await?
+            {
+                return Ok(result);
+            }
+        }
+
+        crate::interpreter::operators::apply_binary_op(
+            left,
+            right,
+            op,
+            (0, 0),
+            &None,
+            &crate::runtime::CallStack::new(),
+        )
+        .await
+    }
+
+    fn interpreter(&self) -> Interpreter {
+        let mut interpreter = Interpreter::new(self.file.clone());
+        interpreter.environment = self.environment.clone();
+        interpreter.sandbox = self.sandbox.clone();
+        interpreter
+    }
+
+    fn ast_lineage(interpreter: &Interpreter, lineage: &[IRClassValue]) -> Vec<Arc<ClassValue>> {
+        let Some(superclass) = lineage.last().and_then(|root| root.superclass.as_deref()) else {
+            return Vec::new();
+        };
+        match Expressions::resolve_class(interpreter, superclass, (0, 0)) {
+            Ok(class) => Expressions::class_lineage(interpreter, &class, (0, 0)),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    async fn invoke_ast_constructor(
+        interpreter: &mut Interpreter,
+        instance: &Arc<ClassInstance>,
+        lineage: &[Arc<ClassValue>],
+        args: Vec<RuntimeValue>,
+    ) -> Result<(), RaccoonError> {
+        let Some((owner, constructor)) = lineage.iter().find_map(|declaring| {
+            let constructor = declaring.declaration.constructor.as_ref()?;
+            Some((declaring.class_name.clone(), constructor))
+        }) else {
+            return Ok(());
+        };
+
+        interpreter.environment.push_scope();
+        interpreter.environment.declare(
+            "this".to_string(),
+            RuntimeValue::ClassInstance(instance.clone()),
+        )?;
+        interpreter.environment.declare(
+            CLASS_CONTEXT.to_string(),
+            RuntimeValue::Str(crate::runtime::StrValue::new(owner)),
+        )?;
+        if let Err(error) = Helpers::bind_parameters(
+            interpreter,
+            &constructor.parameters,
+            &args,
+            &HashMap::new(),
+            (0, 0),
+        )
+        .await
+        {
+            interpreter.environment.pop_scope();
+            return Err(error);
+        }
+        for stmt in constructor.body.iter() {
+            if let InterpreterResult::Return(_) = interpreter.execute_stmt_internal(stmt).await? {
+                break;
+            }
+        }
+        interpreter.environment.pop_scope();
+        Ok(())
+    }
+
+    fn ir_function(value: &RuntimeValue) -> Option<&IRFunctionValue> {
+        match value {
+            RuntimeValue::Dynamic(dyn_val) if dyn_val.type_name() == "IRFunction" => {
+                Some(unsafe {
+                    &*(dyn_val.as_ref() as *const dyn crate::runtime::DynamicValue
+                        as *const IRFunctionValue)
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn select_overload<'a>(
+        &self,
+        function: &'a IRFunctionValue,
+        args: &[RuntimeValue],
+    ) -> Result<&'a IRFunctionValue, RaccoonError> {
+        if function.overloads.is_empty() {
+            return Ok(function);
+        }
+        let signatures: Vec<_> = function
+            .declarations()
+            .map(|declaration| &declaration.signature)
+            .collect();
+        match overloading::best(signatures.iter().copied(), args, 0, self) {
+            Some(0) => Ok(function),
+            Some(index) => Ok(&function.overloads[index - 1]),
+            None => Err(RaccoonError::type_error(
+                overloading::no_match_message(&function.name, signatures, args),
+                (0, 0),
+                None::<String>,
+            )),
+        }
+    }
+
+    fn ir_class(&self, name: &str) -> Option<IRClassValue> {
+        match self.environment.get(name, (0, 0)).ok()? {
+            RuntimeValue::Dynamic(dyn_val) if dyn_val.type_name() == "IRClass" => {
+                let ir_class: &IRClassValue = unsafe {
+                    &*(dyn_val.as_ref() as *const dyn crate::runtime::DynamicValue
+                        as *const IRClassValue)
+                };
+                Some(ir_class.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn disposal_method(
+        &self,
+        resource: &RuntimeValue,
+    ) -> Result<Option<&'static str>, RaccoonError> {
+        if matches!(resource, RuntimeValue::Null(_)) {
+            return Ok(None);
+        }
+        let method = ControlFlow::disposal_method(resource).or_else(|| match resource {
+            RuntimeValue::ClassInstance(instance) => {
+                let lineage = self.ir_class_lineage(&instance.class_name);
+                DISPOSE_METHODS.into_iter().find(|method| {
+                    lineage
+                        .iter()
+                        .any(|ir_class| ir_class.methods.contains_key(*method))
+                })
+            }
+            _ => None,
+        });
+        match method {
+            Some(method) => Ok(Some(method)),
+            None => Err(RaccoonError::type_error(
+                format!(
+                    "{} cannot be used with 'using': it has no __dispose__() or close() method",
+                    resource.get_name()
+                ),
+                (0, 0),
+                None::<String>,
+            )),
+        }
+    }
+
+    fn ir_class_lineage(&self, name: &str) -> Vec<IRClassValue> {
+        let mut lineage: Vec<IRClassValue> = Vec::new();
+        let mut current = Some(name.to_string());
+
+        while let Some(name) = current.take() {
+            if lineage.iter().any(|ir_class| ir_class.name == name) {
+                break;
+            }
+            let Some(ir_class) = self.ir_class(&name) else {
+                break;
+            };
+            current = ir_class.superclass.clone();
+            lineage.push(ir_class);
+        }
+
+        lineage
+    }
+
+    fn method_stub(ir_method: &IRMethod) -> crate::runtime::FunctionValue {
+        let params = ir_method
+            .params
+            .iter()
+            .map(|p| crate::ast::nodes::FnParam {
+                pattern: crate::ast::nodes::VarPattern::Identifier(p.clone()),
+                param_type: crate::ast::types::PrimitiveType::any(),
+                default_value: None,
+                is_optional: false,
+                is_rest: false,
+                is_untyped: false,
+                position: (0, 0),
+            })
+            .collect();
+
+        crate::runtime::FunctionValue::new(
+            params,
+            vec![],
+            ir_method.is_async,
+            crate::ast::types::PrimitiveType::any(),
+        )
+    }
+
+    /// Private members are reachable only while `this` shares the declaring class.
+    fn check_private_access(
+        &self,
+        instance: &ClassInstance,
+        member: &str,
+    ) -> Result<(), RaccoonError> {
+        if !instance.private_members.contains_key(member) {
+            return Ok(());
+        }
+
+        let this = self.environment.get("this", (0, 0)).ok();
+        let accessor = match &this {
+            Some(RuntimeValue::ClassInstance(this)) => Some(this.as_ref()),
+            _ => None,
+        };
+
+        instance
+            .check_accessible(member, accessor)
+            .map_err(|message| RaccoonError::new(message, (0, 0), self.file.clone()))
+    }
+
+    async fn invoke_ir_method(
+        &mut self,
+        instance: &Arc<ClassInstance>,
+        owner: &str,
+        method: &IRMethod,
+        args: Vec<RuntimeValue>,
+    ) -> Result<RuntimeValue, RaccoonError> {
+        let mut method_env = self.environment.clone();
+        method_env.push_scope();
+        method_env.declare(
+            "this".to_string(),
+            RuntimeValue::ClassInstance(instance.clone()),
+        )?;
+        method_env.declare(
+            CLASS_CONTEXT.to_string(),
+            RuntimeValue::Str(crate::runtime::StrValue::new(owner.to_string())),
+        )?;
+
+        for (i, param) in method.params.iter().enumerate() {
+            let arg_value = args
+                .get(i)
+                .cloned()
+                .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()));
+            method_env.declare(param.clone(), arg_value)?;
+        }
+
+        let mut method_vm = self.nested(method_env, &format!("{}.{}", owner, method.name));
+        let method_program = IRProgram {
+            instructions: method.body.clone(),
+            constant_pool: Vec::new(),
+            labels: method.labels.clone(),
+        };
+
+        method_vm.execute(method_program).await
+    }
+
     fn get_register(&self, reg: &Register) -> Result<RuntimeValue, RaccoonError> {
         match reg {
             Register::Local(name) => {
@@ -1640,6 +2696,23 @@ impl VM {
     }
 }
 
+impl TypeScope for VM {
+    fn superclass_of(&self, name: &str) -> Option<String> {
+        match self.ir_class(name) {
+            Some(class) => class.superclass,
+            None => self.environment.superclass_of(name),
+        }
+    }
+
+    fn alias(&self, name: &str) -> Option<crate::ast::types::Type> {
+        self.environment.alias(name)
+    }
+
+    fn interface(&self, name: &str) -> Option<crate::ast::types::Type> {
+        self.environment.interface(name)
+    }
+}
+
 enum ExecutionResult {
     Continue,
     Return(RuntimeValue),
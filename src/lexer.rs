@@ -1,6 +1,6 @@
 use crate::{
     error::RaccoonError,
-    tokens::{Position, Token, TokenType},
+    tokens::{Comment, CommentKind, Position, Token, TokenType},
 };
 use phf::phf_map;
 
@@ -17,6 +17,7 @@ static KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
     "match" => TokenType::Match,
     "switch" => TokenType::Switch,
     "case" => TokenType::Case,
+    "fallthrough" => TokenType::Fallthrough,
     "break" => TokenType::Break,
     "continue" => TokenType::Continue,
     "fn" => TokenType::Fn,
@@ -97,6 +98,7 @@ static COMPOUND_OPERATORS: phf::Map<&'static str, TokenType> = phf_map! {
     "||" => TokenType::Or,
     "..." => TokenType::Spread,
     ".." => TokenType::Range,
+    "..<" => TokenType::RangeExclusive,
     "->" => TokenType::Arrow,
     "=>" => TokenType::Arrow,
     "+=" => TokenType::PlusAssign,
@@ -125,6 +127,7 @@ pub struct Lexer {
     source: Vec<char>,
     file: Option<String>,
     tokens: Vec<Token>,
+    comments: Vec<Comment>,
     position: usize,
     line: usize,
     column: usize,
@@ -136,91 +139,124 @@ impl Lexer {
             source: source.chars().collect(),
             file,
             tokens: Vec::new(),
+            comments: Vec::new(),
             position: 0,
             line: 1,
             column: 1,
         }
     }
 
+    /// A lexer for `source` starting at char `start`, which is at `position`.
+    pub(crate) fn resume(
+        source: String,
+        file: Option<String>,
+        start: usize,
+        (line, column): Position,
+    ) -> Self {
+        Self {
+            position: start,
+            line,
+            column,
+            ..Self::new(source, file)
+        }
+    }
+
     pub fn tokenize(&mut self) -> Result<Vec<Token>, RaccoonError> {
-        while !self.is_at_end() {
-            self.skip_whitespace();
-            if self.is_at_end() {
-                break;
-            }
+        while self.scan()? {}
+        Ok(self.finish())
+    }
 
-            let char = self.peek();
-            let next = self.peek_next();
+    /// The tokens lexed so far.
+    pub(crate) fn lexed(&self) -> &[Token] {
+        &self.tokens
+    }
 
-            if char == '_' && (self.is_at_end() || !self.is_alpha_numeric(self.peek_next())) {
-                self.add_token(
-                    TokenType::Underscore,
-                    "_".to_string(),
-                    (self.line, self.column),
-                );
+    /// Ends the token stream and hands it over.
+    pub(crate) fn finish(&mut self) -> Vec<Token> {
+        let eof_pos = (self.line, self.column);
+        self.add_token(TokenType::Eof, String::new(), eof_pos);
+        std::mem::take(&mut self.tokens)
+    }
+
+    /// Lexes the next token or comment; `false` once the source has run out.
+    pub(crate) fn scan(&mut self) -> Result<bool, RaccoonError> {
+        self.skip_whitespace();
+        if self.is_at_end() {
+            return Ok(false);
+        }
+
+        let char = self.peek();
+        let next = self.peek_next();
+
+        if char == '_' && (self.is_at_end() || !self.is_alpha_numeric(self.peek_next())) {
+            self.add_token(
+                TokenType::Underscore,
+                "_".to_string(),
+                (self.line, self.column),
+            );
+            self.advance();
+        } else if char == 'r' && (next == '"' || next == '\'') {
+            self.raw_string()?;
+        } else if self.is_alpha(char) {
+            self.identifier()?;
+        } else if char == '#' && self.is_alpha(next) {
+            self.private_identifier();
+        } else if self.is_digit(char) {
+            self.number()?;
+        } else if char == '\'' || char == '"' {
+            self.string()?;
+        } else if char == '`' {
+            self.template_string()?;
+        } else if char == '/' && next == '/' {
+            self.line_comment();
+        } else if char == '/' && next == '*' {
+            self.block_comment();
+        } else {
+            let four = format!(
+                "{}{}{}{}",
+                char,
+                next,
+                self.peek_ahead(2),
+                self.peek_ahead(3)
+            );
+
+            let start_pos = (self.line, self.column);
+
+            if let Some(&token_type) = COMPOUND_OPERATORS.get(&four.as_str()) {
+                self.advance();
                 self.advance();
-            } else if self.is_alpha(char) {
-                self.identifier()?;
-            } else if self.is_digit(char) {
-                self.number()?;
-            } else if char == '\'' || char == '"' {
-                self.string()?;
-            } else if char == '`' {
-                self.template_string()?;
-            } else if char == '/' && next == '/' {
-                self.skip_line_comment();
-            } else if char == '/' && next == '*' {
-                self.skip_block_comment();
+                self.advance();
+                self.advance();
+                self.add_token(token_type, four, start_pos);
             } else {
-                let four = format!(
-                    "{}{}{}{}",
-                    char,
-                    next,
-                    self.peek_ahead(2),
-                    self.peek_ahead(3)
-                );
+                let three = format!("{}{}{}", char, next, self.peek_ahead(2));
 
-                let start_pos = (self.line, self.column);
-
-                if let Some(&token_type) = COMPOUND_OPERATORS.get(&four.as_str()) {
-                    self.advance();
+                if let Some(&token_type) = COMPOUND_OPERATORS.get(&three.as_str()) {
                     self.advance();
                     self.advance();
                     self.advance();
-                    self.add_token(token_type, four, start_pos);
+                    self.add_token(token_type, three, start_pos);
                 } else {
-                    let three = format!("{}{}{}", char, next, self.peek_ahead(2));
+                    let compound = format!("{}{}", char, next);
 
-                    if let Some(&token_type) = COMPOUND_OPERATORS.get(&three.as_str()) {
+                    if let Some(&token_type) = COMPOUND_OPERATORS.get(&compound.as_str()) {
                         self.advance();
                         self.advance();
+                        self.add_token(token_type, compound, start_pos);
+                    } else if let Some(&token_type) = SIMPLE_OPERATORS.get(&char) {
                         self.advance();
-                        self.add_token(token_type, three, start_pos);
+                        self.add_token(token_type, char.to_string(), start_pos);
                     } else {
-                        let compound = format!("{}{}", char, next);
-
-                        if let Some(&token_type) = COMPOUND_OPERATORS.get(&compound.as_str()) {
-                            self.advance();
-                            self.advance();
-                            self.add_token(token_type, compound, start_pos);
-                        } else if let Some(&token_type) = SIMPLE_OPERATORS.get(&char) {
-                            self.advance();
-                            self.add_token(token_type, char.to_string(), start_pos);
-                        } else {
-                            return Err(RaccoonError::new(
-                                format!("Unexpected character: '{}'", char),
-                                (self.line, self.column),
-                                self.file.clone(),
-                            ));
-                        }
+                        return Err(RaccoonError::syntax_error(
+                            format!("Unexpected character: '{}'", char),
+                            (self.line, self.column),
+                            self.file.clone(),
+                        ));
                     }
                 }
             }
         }
-
-        let eof_pos = (self.line, self.column);
-        self.add_token(TokenType::Eof, String::new(), eof_pos);
-        Ok(self.tokens.clone())
+        Ok(true)
     }
 
     fn identifier(&mut self) -> Result<(), RaccoonError> {
@@ -241,6 +277,19 @@ impl Lexer {
         Ok(())
     }
 
+    fn private_identifier(&mut self) {
+        let start = self.position;
+        let start_pos = (self.line, self.column);
+
+        self.advance();
+        while self.is_alpha_numeric(self.peek()) {
+            self.advance();
+        }
+
+        let text: String = self.source[start..self.position].iter().collect();
+        self.add_token(TokenType::Identifier, text, start_pos);
+    }
+
     fn number(&mut self) -> Result<(), RaccoonError> {
         let start = self.position;
         let start_pos = (self.line, self.column);
@@ -259,17 +308,17 @@ impl Lexer {
                 self.advance();
                 self.advance();
 
+                let is_radix_digit = |ch: char| match next {
+                    'b' | 'B' => ch == '0' || ch == '1',
+                    'o' | 'O' => ('0'..='7').contains(&ch),
+                    _ => ch.is_ascii_hexdigit(),
+                };
+
                 while self.position < self.source.len() {
                     let ch = self.peek();
                     if ch == '_' {
-                        self.advance();
-                        continue;
-                    }
-                    if (next == 'b' || next == 'B') && (ch == '0' || ch == '1') {
-                        self.advance();
-                    } else if (next == 'o' || next == 'O') && ch >= '0' && ch <= '7' {
-                        self.advance();
-                    } else if (next == 'x' || next == 'X') && ch.is_ascii_hexdigit() {
+                        self.numeric_separator(is_radix_digit)?;
+                    } else if is_radix_digit(ch) {
                         self.advance();
                     } else {
                         break;
@@ -288,30 +337,17 @@ impl Lexer {
                     TokenType::IntLiteral
                 };
 
-                self.add_token(token_type, text, start_pos);
+                self.add_literal(token_type, text.replace('_', ""), start_pos, start);
                 return Ok(());
             }
         }
 
-        while self.is_digit(self.peek()) || self.peek() == '_' {
-            if self.peek() != '_' {
-                self.advance();
-            } else {
-                self.advance();
-            }
-        }
+        self.decimal_digits()?;
 
         if self.peek() == '.' && self.is_digit(self.peek_next()) {
             is_float = true;
             self.advance();
-
-            while self.is_digit(self.peek()) || self.peek() == '_' {
-                if self.peek() != '_' {
-                    self.advance();
-                } else {
-                    self.advance();
-                }
-            }
+            self.decimal_digits()?;
         }
 
         if self.peek() == 'n' && !is_float {
@@ -328,20 +364,19 @@ impl Lexer {
             TokenType::IntLiteral
         };
 
-        self.add_token(token_type, text, start_pos);
+        self.add_literal(token_type, text.replace('_', ""), start_pos, start);
         Ok(())
     }
 
     fn string(&mut self) -> Result<(), RaccoonError> {
+        let start = self.position;
         let start_pos = (self.line, self.column);
         let quote = self.advance();
         let mut value = String::new();
 
         while self.peek() != quote && !self.is_at_end() {
             if self.peek() == '\\' {
-                self.advance();
-                value.push(self.get_escaped_char(self.peek()));
-                self.advance();
+                value.push(self.escape_sequence()?);
             } else {
                 if self.peek() == '\n' {
                     self.line += 1;
@@ -355,7 +390,7 @@ impl Lexer {
         }
 
         if self.is_at_end() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Unterminated string",
                 start_pos,
                 self.file.clone(),
@@ -363,7 +398,38 @@ impl Lexer {
         }
 
         self.advance();
-        self.add_token(TokenType::StrLiteral, value, start_pos);
+        self.add_literal(TokenType::StrLiteral, value, start_pos, start);
+        Ok(())
+    }
+
+    fn raw_string(&mut self) -> Result<(), RaccoonError> {
+        let start = self.position;
+        let start_pos = (self.line, self.column);
+        self.advance();
+        let quote = self.advance();
+        let mut value = String::new();
+
+        while self.peek() != quote && !self.is_at_end() {
+            if self.peek() == '\n' {
+                self.line += 1;
+                self.column = 1;
+                value.push(self.source[self.position]);
+                self.position += 1;
+            } else {
+                value.push(self.advance());
+            }
+        }
+
+        if self.is_at_end() {
+            return Err(RaccoonError::syntax_error(
+                "Unterminated raw string",
+                start_pos,
+                self.file.clone(),
+            ));
+        }
+
+        self.advance();
+        self.add_literal(TokenType::StrLiteral, value, start_pos, start);
         Ok(())
     }
 
@@ -407,12 +473,20 @@ impl Lexer {
 
                     let char = self.peek();
 
-                    if self.is_alpha(char) {
+                    if char == 'r' && matches!(self.peek_next(), '"' | '\'') {
+                        self.raw_string()?;
+                    } else if self.is_alpha(char) {
                         self.identifier()?;
                     } else if self.is_digit(char) {
                         self.number()?;
                     } else if char == '\'' || char == '"' {
                         self.string()?;
+                    } else if char == '`' {
+                        self.template_string()?;
+                    } else if char == '/' && self.peek_next() == '/' {
+                        self.line_comment();
+                    } else if char == '/' && self.peek_next() == '*' {
+                        self.block_comment();
                     } else if char == '{' {
                         brace_count += 1;
                         let brace_pos = (self.line, self.column);
@@ -439,7 +513,7 @@ impl Lexer {
                             self.advance();
                             self.add_token(token_type, char.to_string(), op_start_pos);
                         } else {
-                            return Err(RaccoonError::new(
+                            return Err(RaccoonError::syntax_error(
                                 format!("Unexpected character in interpolation: '{}'", char),
                                 (self.line, self.column),
                                 self.file.clone(),
@@ -448,6 +522,14 @@ impl Lexer {
                     }
                 }
 
+                if self.is_at_end() {
+                    return Err(RaccoonError::syntax_error(
+                        "Unterminated template string",
+                        start_pos,
+                        self.file.clone(),
+                    ));
+                }
+
                 let interp_end_pos = (self.line, self.column);
                 self.advance();
                 self.add_token(
@@ -457,9 +539,7 @@ impl Lexer {
                 );
             } else {
                 if self.peek() == '\\' {
-                    self.advance();
-                    value.push(self.get_escaped_char(self.peek()));
-                    self.advance();
+                    value.push(self.escape_sequence()?);
                 } else {
                     if self.peek() == '\n' {
                         self.line += 1;
@@ -474,7 +554,7 @@ impl Lexer {
         }
 
         if self.is_at_end() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Unterminated template string",
                 start_pos,
                 self.file.clone(),
@@ -493,13 +573,25 @@ impl Lexer {
         Ok(())
     }
 
-    fn skip_line_comment(&mut self) {
+    fn line_comment(&mut self) {
+        let start_pos = (self.line, self.column);
+        let start = self.position;
         while self.peek() != '\n' && !self.is_at_end() {
             self.advance();
         }
+
+        let text: String = self.source[start..self.position].iter().collect();
+        let kind = if text.starts_with("///") && !text.starts_with("////") {
+            CommentKind::Doc
+        } else {
+            CommentKind::Line
+        };
+        self.add_comment(kind, text, start_pos);
     }
 
-    fn skip_block_comment(&mut self) {
+    fn block_comment(&mut self) {
+        let start_pos = (self.line, self.column);
+        let start = self.position;
         self.advance();
         self.advance();
 
@@ -517,6 +609,22 @@ impl Lexer {
             self.advance();
             self.advance();
         }
+
+        let text: String = self.source[start..self.position].iter().collect();
+        let kind = if text.starts_with("/**") && text != "/**/" {
+            CommentKind::Doc
+        } else {
+            CommentKind::Block
+        };
+        self.add_comment(kind, text, start_pos);
+    }
+
+    fn add_comment(&mut self, kind: CommentKind, text: String, position: Position) {
+        self.comments.push(Comment {
+            kind,
+            text,
+            position,
+        });
     }
 
     fn skip_whitespace(&mut self) {
@@ -535,18 +643,89 @@ impl Lexer {
         }
     }
 
-    fn get_escaped_char(&self, char: char) -> char {
-        match char {
+    fn escape_sequence(&mut self) -> Result<char, RaccoonError> {
+        let position = (self.line, self.column);
+        self.advance();
+        if self.is_at_end() {
+            return Ok('\\');
+        }
+
+        let char = self.advance();
+        let escaped = match char {
             'n' => '\n',
             't' => '\t',
             'r' => '\r',
-            '\\' => '\\',
-            '\'' => '\'',
-            '"' => '"',
-            '`' => '`',
-            '$' => '$',
-            _ => char,
+            '0' => '\0',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'v' => '\u{b}',
+            'x' => self.code_point(2, position)?,
+            'u' if self.peek() == '{' => {
+                self.advance();
+                let mut digits = String::new();
+                while self.peek().is_ascii_hexdigit() && digits.len() < 6 {
+                    digits.push(self.advance());
+                }
+                if self.peek() != '}' || digits.is_empty() {
+                    return Err(self.invalid_escape(position));
+                }
+                self.advance();
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.invalid_escape(position))?
+            }
+            'u' => self.code_point(4, position)?,
+            '\n' => {
+                self.line += 1;
+                self.column = 1;
+                '\n'
+            }
+            other => other,
+        };
+        Ok(escaped)
+    }
+
+    fn code_point(&mut self, digits: usize, position: Position) -> Result<char, RaccoonError> {
+        let mut hex = String::new();
+        for _ in 0..digits {
+            if !self.peek().is_ascii_hexdigit() {
+                return Err(self.invalid_escape(position));
+            }
+            hex.push(self.advance());
         }
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.invalid_escape(position))
+    }
+
+    fn invalid_escape(&self, position: Position) -> RaccoonError {
+        RaccoonError::syntax_error("Invalid escape sequence", position, self.file.clone())
+    }
+
+    fn decimal_digits(&mut self) -> Result<(), RaccoonError> {
+        while self.is_digit(self.peek()) || self.peek() == '_' {
+            if self.peek() == '_' {
+                self.numeric_separator(|ch| ch.is_ascii_digit())?;
+            } else {
+                self.advance();
+            }
+        }
+        Ok(())
+    }
+
+    fn numeric_separator(&mut self, is_digit: impl Fn(char) -> bool) -> Result<(), RaccoonError> {
+        let previous = self.source[self.position - 1];
+        if !is_digit(previous) || !is_digit(self.peek_next()) {
+            return Err(RaccoonError::syntax_error(
+                "Numeric separators are only allowed between digits",
+                (self.line, self.column),
+                self.file.clone(),
+            ));
+        }
+        self.advance();
+        Ok(())
     }
 
     fn is_alpha(&self, char: char) -> bool {
@@ -601,6 +780,22 @@ impl Lexer {
     }
 
     fn add_token(&mut self, token_type: TokenType, value: String, start_pos: Position) {
-        self.tokens.push(Token::new(token_type, value, start_pos));
+        self.push_token(Token::new(token_type, value, start_pos));
+    }
+
+    fn add_literal(
+        &mut self,
+        token_type: TokenType,
+        value: String,
+        start_pos: Position,
+        start: usize,
+    ) {
+        let lexeme = self.source[start..self.position].iter().collect();
+        self.push_token(Token::new(token_type, value, start_pos).with_lexeme(lexeme));
+    }
+
+    fn push_token(&mut self, mut token: Token) {
+        token.leading_comments = std::mem::take(&mut self.comments);
+        self.tokens.push(token);
     }
 }
@@ -1,5 +1,8 @@
 pub mod analyzer;
 pub mod ast;
+pub mod bench;
+pub mod debugger;
+pub mod docgen;
 pub mod error;
 pub mod interpreter;
 pub mod ir;
@@ -1,12 +1,38 @@
-use raccoon_lang::{Interpreter, Lexer, Parser};
+use raccoon_lang::analyzer::{Lint, LintLevels, Severity};
+use raccoon_lang::ast::nodes::Program;
+use raccoon_lang::bench::{format_report, BenchConfig, BenchRunner, BENCH_PREFIX};
+use raccoon_lang::debugger::{Debugger, DEFAULT_DEBUG_PORT};
+use raccoon_lang::docgen::{file_stem, render_index, DocFormat, DocGenerator, ModuleDoc};
+use raccoon_lang::interpreter::process::ProcessHooks;
+use raccoon_lang::parser::incremental::ParsedFile;
+use raccoon_lang::runtime::builtins::global;
+use raccoon_lang::runtime::natives::stdio::restore_terminal;
+use raccoon_lang::runtime::natives::watch::{FileWatcher, DEFAULT_DEBOUNCE_MS};
+use raccoon_lang::runtime::{
+    format_memory_stats, heap_usage, module_files, shared_editor, CountingAllocator, Permissions,
+    ReadResult, RuntimeValue, SandboxConfig, Snapshot, StdLibLoader, RESTRICTED_MODULES,
+    SNAPSHOT_ENV,
+};
+use raccoon_lang::type_system::StrictnessProfile;
+use raccoon_lang::{Interpreter, Lexer, Parser, RaccoonError, SemanticAnalyzer, SymbolItem, Token};
 use std::env;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::time::Duration;
+
+const DEFAULT_PROFILE_OUTPUT: &str = "raccoon.folded";
+const DEFAULT_SNAPSHOT_OUTPUT: &str = "raccoon.snapshot";
+const HISTORY_FILE: &str = ".raccoon_history";
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 fn main() {
     let runtime = tokio::runtime::Builder::new_current_thread()
         .thread_stack_size(256 * 1024 * 1024)
         .enable_time()
+        .enable_io()
         .build()
         .expect("Failed to create Tokio runtime");
 
@@ -18,65 +44,1028 @@ async fn async_main() {
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 2 {
-        eprintln!("Usage: raccoon [--use-ir] <file.rcc>");
+        eprintln!("Usage: raccoon [--use-ir] [--profile] [--profile-output <path>] <file.rcc>");
+        eprintln!("       raccoon <file.rcc> [args...] [-- <args...>]");
+        eprintln!("       raccoon --use-ir --ir-stats <file.rcc>");
+        eprintln!("       raccoon --debug [--debug-port <port>] <file.rcc>");
+        eprintln!("       raccoon --print-memory-stats <file.rcc>");
+        eprintln!("       raccoon --check [--warn[=<lints>]] <file.rcc>");
+        eprintln!("       raccoon --quiet <file.rcc>");
+        eprintln!("       raccoon --release <file.rcc>");
+        eprintln!("       raccoon --sandbox [--timeout <ms>] [--max-steps <n>] <file.rcc>");
+        eprintln!("       raccoon --deny <io,http,net,process> <file.rcc>");
+        eprintln!("       raccoon [--allow-read[=<paths>]] [--allow-write[=<paths>]] <file.rcc>");
+        eprintln!("       raccoon [--allow-net] [--allow-env] [--allow-all] <file.rcc>");
+        eprintln!("       raccoon [--warn[=<lints>]] [--deny=<lints|warnings>] <file.rcc>");
+        eprintln!("       raccoon --strictness=<loose|standard|strict> <file.rcc>");
+        eprintln!("       raccoon ast <file.rcc> [--json]");
+        eprintln!("       raccoon tokens <file.rcc>");
+        eprintln!("       raccoon bench <file.rcc> [--warmup N] [--iterations N]");
+        eprintln!("       raccoon doc <file.rcc|dir|std> [--format md|html] [--out <dir>]");
+        eprintln!("       raccoon rename <file.rcc> <line:column> <new_name> [--write]");
+        eprintln!("       raccoon references <file.rcc> <line:column>");
+        eprintln!("       raccoon watch <file.rcc> [--clear] [--debounce <ms>] [run options...]");
+        eprintln!("       raccoon snapshot [--out <path>]");
+        eprintln!("       raccoon repl");
         eprintln!("Example: cargo run -- examples/test.rcc");
         eprintln!("Example: cargo run -- --use-ir examples/test.rcc");
         process::exit(1);
     }
 
-    let mut use_ir = false;
-    let mut file_path = &args[1];
+    match args[1].as_str() {
+        "ast" => {
+            let file_path = subcommand_file(&args);
+            let as_json = args[3..].iter().any(|a| a == "--json");
+            dump_ast(file_path, as_json);
+            return;
+        }
+        "tokens" => {
+            dump_tokens(subcommand_file(&args));
+            return;
+        }
+        "bench" => {
+            let file_path = subcommand_file(&args);
+            run_bench(file_path, bench_config(&args[3..])).await;
+            return;
+        }
+        "doc" => {
+            generate_docs(subcommand_file(&args), &args[3..]);
+            return;
+        }
+        "rename" => {
+            rename_symbol(subcommand_file(&args), &args[3..]);
+            return;
+        }
+        "references" => {
+            list_references(subcommand_file(&args), &args[3..]);
+            return;
+        }
+        "watch" => {
+            watch_file(subcommand_file(&args), watch_options(&args[3..])).await;
+            return;
+        }
+        "snapshot" => {
+            write_snapshot(&args[2..]);
+            return;
+        }
+        "repl" => {
+            run_repl().await;
+            return;
+        }
+        _ => {}
+    }
 
-    if args.len() >= 3 && args[1] == "--use-ir" {
-        use_ir = true;
-        file_path = &args[2];
-    } else if args.len() >= 2 && args[1] == "--use-ir" {
-        eprintln!("Error: Missing file path after --use-ir flag");
-        process::exit(1);
+    let (file_path, options) = parse_run_options(&args[1..]);
+    run_file(&file_path, options).await;
+}
+
+#[derive(Debug, Default)]
+struct RunOptions {
+    use_ir: bool,
+    profile_output: Option<String>,
+    debug_port: Option<u16>,
+    print_memory_stats: bool,
+    ir_stats: bool,
+    check: bool,
+    quiet: bool,
+    release: bool,
+    sandbox: SandboxConfig,
+    permissions: Permissions,
+    lints: Option<LintLevels>,
+    strictness: Option<StrictnessProfile>,
+    script_args: Vec<String>,
+}
+
+fn parse_run_options(args: &[String]) -> (String, RunOptions) {
+    let mut options = RunOptions::default();
+    let mut file_path = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--use-ir" => options.use_ir = true,
+            "--profile" => {
+                options.profile_output = Some(DEFAULT_PROFILE_OUTPUT.to_string());
+            }
+            "--debug" => options.debug_port = Some(DEFAULT_DEBUG_PORT),
+            "--print-memory-stats" => options.print_memory_stats = true,
+            "--ir-stats" => options.ir_stats = true,
+            "--check" => options.check = true,
+            "--quiet" | "-q" => options.quiet = true,
+            "--release" => options.release = true,
+            "--sandbox" => {
+                options.sandbox = RESTRICTED_MODULES
+                    .iter()
+                    .fold(options.sandbox, |config, module| config.deny_module(module));
+            }
+            "--timeout" => {
+                let millis = expect_number(iter.next(), arg);
+                options.sandbox = options.sandbox.with_timeout(Duration::from_millis(millis));
+            }
+            "--max-steps" => {
+                let steps = expect_number(iter.next(), arg);
+                options.sandbox = options.sandbox.with_max_steps(steps);
+            }
+            "--deny" => match iter.next() {
+                Some(modules) => {
+                    options.sandbox = split_list(modules)
+                        .fold(options.sandbox, |config, module| config.deny_module(module));
+                }
+                None => {
+                    eprintln!("Error: Missing module list after --deny flag");
+                    process::exit(1);
+                }
+            },
+            "--allow-all" | "-A" => options.permissions = Permissions::all(),
+            "--allow-net" => options.permissions = options.permissions.allow_net(),
+            "--allow-env" => options.permissions = options.permissions.allow_env(),
+            "--allow-read" => options.permissions = options.permissions.allow_read(None),
+            "--allow-write" => options.permissions = options.permissions.allow_write(None),
+            flag if flag.starts_with("--allow-read=") => {
+                options.permissions = split_list(&flag["--allow-read=".len()..])
+                    .fold(options.permissions, |perms, path| perms.allow_read(Some(path)));
+            }
+            flag if flag.starts_with("--allow-write=") => {
+                options.permissions = split_list(&flag["--allow-write=".len()..])
+                    .fold(options.permissions, |perms, path| perms.allow_write(Some(path)));
+            }
+            "--warn" => {
+                options.lints.get_or_insert_with(LintLevels::default);
+            }
+            flag if flag.starts_with("--warn=") => {
+                set_lint_levels(&mut options, &flag["--warn=".len()..], Severity::Warning);
+            }
+            flag if flag.starts_with("--deny=") => {
+                set_lint_levels(&mut options, &flag["--deny=".len()..], Severity::Error);
+            }
+            flag if flag.starts_with("--strictness=") => {
+                let name = &flag["--strictness=".len()..];
+                match StrictnessProfile::from_name(name) {
+                    Some(profile) => options.strictness = Some(profile),
+                    None => {
+                        eprintln!(
+                            "Error: Unknown strictness '{}' (expected loose, standard or strict)",
+                            name
+                        );
+                        process::exit(1);
+                    }
+                }
+            }
+            "--debug-port" => match iter.next().and_then(|p| p.parse::<u16>().ok()) {
+                Some(port) => options.debug_port = Some(port),
+                None => {
+                    eprintln!("Error: Expected a port number after --debug-port flag");
+                    process::exit(1);
+                }
+            },
+            "--profile-output" => match iter.next() {
+                Some(path) => options.profile_output = Some(path.clone()),
+                None => {
+                    eprintln!("Error: Missing path after --profile-output flag");
+                    process::exit(1);
+                }
+            },
+            "--" if file_path.is_some() => {
+                options.script_args.extend(iter.by_ref().cloned());
+            }
+            flag if flag.starts_with("--") => {
+                eprintln!("Error: Unknown flag '{}'", flag);
+                process::exit(1);
+            }
+            path if file_path.is_none() => file_path = Some(path.to_string()),
+            arg => options.script_args.push(arg.to_string()),
+        }
     }
 
-    run_file(file_path, use_ir).await;
+    match file_path {
+        Some(path) => (path, options),
+        None => {
+            eprintln!("Error: Missing file path");
+            process::exit(1);
+        }
+    }
 }
 
-async fn run_file(path: &str, use_ir: bool) {
-    let source = match fs::read_to_string(path) {
+fn set_lint_levels(options: &mut RunOptions, names: &str, severity: Severity) {
+    let levels = options.lints.get_or_insert_with(LintLevels::default);
+
+    for name in split_list(names) {
+        if name == "warnings" {
+            levels.set_all(severity);
+            continue;
+        }
+
+        match Lint::from_name(name) {
+            Some(lint) => levels.set(lint, severity),
+            None => {
+                let known: Vec<&str> = Lint::ALL.iter().map(Lint::name).collect();
+                eprintln!(
+                    "Error: Unknown lint '{}' (expected one of: warnings, {})",
+                    name,
+                    known.join(", ")
+                );
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|item| !item.is_empty())
+}
+
+fn expect_number(value: Option<&String>, flag: &str) -> u64 {
+    match value.and_then(|v| v.parse::<u64>().ok()) {
+        Some(number) => number,
+        None => {
+            eprintln!("Error: Expected a number after {} flag", flag);
+            process::exit(1);
+        }
+    }
+}
+
+fn subcommand_file(args: &[String]) -> &str {
+    match args.get(2) {
+        Some(path) => path,
+        None => {
+            eprintln!("Error: Missing file path after '{}'", args[1]);
+            process::exit(1);
+        }
+    }
+}
+
+fn bench_config(options: &[String]) -> BenchConfig {
+    let mut config = BenchConfig::default();
+    let mut iter = options.iter();
+
+    while let Some(option) = iter.next() {
+        let target = match option.as_str() {
+            "--warmup" => &mut config.warmup,
+            "--iterations" => &mut config.iterations,
+            _ => {
+                eprintln!("Error: Unknown bench option '{}'", option);
+                process::exit(1);
+            }
+        };
+
+        match iter.next().and_then(|v| v.parse::<usize>().ok()) {
+            Some(value) => *target = value,
+            None => {
+                eprintln!("Error: Expected a number after '{}'", option);
+                process::exit(1);
+            }
+        }
+    }
+
+    config
+}
+
+fn read_source(path: &str) -> String {
+    match fs::read_to_string(path) {
         Ok(content) => content,
         Err(error) => {
             eprintln!("Error: Failed to read file '{}': {}", path, error);
             process::exit(1);
         }
+    }
+}
+
+fn tokenize_file(path: &str) -> Vec<Token> {
+    let source = read_source(path);
+    let mut lexer = Lexer::new(source, Some(path.to_string()));
+
+    match lexer.tokenize() {
+        Ok(t) => t,
+        Err(error) => {
+            eprintln!("Lexer Error: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+fn print_json<T: serde::Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(error) => {
+            eprintln!("Error: Failed to serialize output: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+fn dump_tokens(path: &str) {
+    let tokens = tokenize_file(path);
+    print_json(&tokens);
+}
+
+fn dump_ast(path: &str, as_json: bool) {
+    let program = parse_file(path);
+
+    if as_json {
+        print_json(&program);
+    } else {
+        println!("{:#?}", program);
+    }
+}
+
+fn parse_file(path: &str) -> Program {
+    let tokens = tokenize_file(path);
+    let mut parser = Parser::new(tokens, Some(path.to_string()));
+
+    let (program, errors) = parser.parse_recovering();
+    if errors.is_empty() {
+        return program;
+    }
+
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+    if errors.len() > 1 {
+        eprintln!("{} syntax errors in {}", errors.len(), path);
+    }
+    process::exit(1);
+}
+
+fn rename_symbol(path: &str, options: &[String]) {
+    let (position, new_name) = match options {
+        [position, new_name, ..] => (parse_position(position), new_name),
+        _ => {
+            eprintln!("Error: rename expects <line:column> <new_name>");
+            process::exit(1);
+        }
+    };
+    let write = match &options[2..] {
+        [] => false,
+        [flag] if flag == "--write" => true,
+        [other, ..] => {
+            eprintln!("Error: Unknown rename option '{}'", other);
+            process::exit(1);
+        }
     };
 
     let file = Some(path.to_string());
-    let mut lexer = Lexer::new(source, file.clone());
+    let (tokens, analyzer, symbol) = analyze_symbol_at(path, position, "rename");
+    let edits = match analyzer.symbol_table.rename(&symbol, new_name, &tokens) {
+        Ok(edits) => edits,
+        Err(error) => {
+            eprintln!("Error: {}", error.message);
+            process::exit(1);
+        }
+    };
 
-    let tokens = match lexer.tokenize() {
-        Ok(t) => t,
+    let mut renamed = match ParsedFile::parse(read_source(path), file) {
+        Ok(parsed) => parsed,
         Err(error) => {
             eprintln!("Lexer Error: {}", error);
             process::exit(1);
         }
     };
+    for edit in edits.iter().rev() {
+        renamed = match renamed.edit(edit) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                eprintln!("Lexer Error: {}", error);
+                process::exit(1);
+            }
+        };
+    }
 
-    let mut parser = Parser::new(tokens, file.clone());
+    if !write {
+        print!("{}", renamed.source);
+        return;
+    }
+    if let Err(error) = fs::write(path, &renamed.source) {
+        eprintln!("Error: Failed to write '{}': {}", path, error);
+        process::exit(1);
+    }
+    eprintln!(
+        "Renamed {} occurrence(s) of '{}' to '{}' in {}",
+        edits.len(),
+        symbol.name,
+        new_name,
+        path
+    );
+}
 
-    match parser.parse() {
-        Ok(program) => {
-            let mut interpreter = Interpreter::new(file.clone());
-            if use_ir {
-                interpreter.enable_ir_mode();
+fn list_references(path: &str, options: &[String]) {
+    let position = match options {
+        [position] => parse_position(position),
+        _ => {
+            eprintln!("Error: references expects <line:column>");
+            process::exit(1);
+        }
+    };
+
+    let (tokens, analyzer, symbol) = analyze_symbol_at(path, position, "find references");
+    for span in analyzer.symbol_table.find_references(&symbol, &tokens) {
+        println!("{}:{}:{}", path, span.start.0, span.start.1);
+    }
+}
+
+fn analyze_symbol_at(
+    path: &str,
+    position: (usize, usize),
+    action: &str,
+) -> (Vec<Token>, SemanticAnalyzer, SymbolItem) {
+    let tokens = tokenize_file(path);
+    let program = parse_file(path);
+    let file = Some(path.to_string());
+    let interpreter = Interpreter::new(file.clone());
+    let mut analyzer =
+        SemanticAnalyzer::with_symbol_table(interpreter.global_symbols()).with_file(file);
+    if let Err(error) = analyzer.analyze(&program) {
+        eprintln!(
+            "Error: Cannot {} while the file has errors\n{}",
+            action, error
+        );
+        process::exit(1);
+    }
+
+    let symbols = &analyzer.symbol_table;
+    let symbol = match symbols.symbol_at(position, &tokens) {
+        Some(symbol) if symbols.declaration(symbol, &tokens).is_some() => symbol.clone(),
+        Some(symbol) => {
+            eprintln!("Error: '{}' is not declared in {}", symbol.name, path);
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Error: No symbol at {}:{}", position.0, position.1);
+            process::exit(1);
+        }
+    };
+    (tokens, analyzer, symbol)
+}
+
+fn parse_position(value: &str) -> (usize, usize) {
+    let parsed = value
+        .split_once(':')
+        .and_then(|(line, column)| Some((line.parse().ok()?, column.parse().ok()?)));
+    match parsed {
+        Some(position) => position,
+        None => {
+            eprintln!(
+                "Error: Expected a position as <line:column>, got '{}'",
+                value
+            );
+            process::exit(1);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct WatchOptions {
+    clear: bool,
+    debounce: Duration,
+    run_args: Vec<String>,
+}
+
+fn watch_options(options: &[String]) -> WatchOptions {
+    let mut watch = WatchOptions {
+        clear: false,
+        debounce: Duration::from_millis(DEFAULT_DEBOUNCE_MS),
+        run_args: Vec::new(),
+    };
+    let mut iter = options.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--clear" => watch.clear = true,
+            "--debounce" => {
+                watch.debounce = Duration::from_millis(expect_number(iter.next(), "--debounce"))
             }
-            match interpreter.interpret(&program).await {
-                Ok(_result) => {}
-                Err(error) => {
-                    eprintln!("{}", error);
+            "--" => {
+                watch.run_args.push(arg.clone());
+                watch.run_args.extend(iter.by_ref().cloned());
+            }
+            _ => watch.run_args.push(arg.clone()),
+        }
+    }
+    watch
+}
+
+async fn watch_file(path: &str, options: WatchOptions) {
+    let program = env::current_exe().unwrap_or_else(|error| {
+        eprintln!("Error: Cannot find the raccoon executable: {}", error);
+        process::exit(1);
+    });
+
+    loop {
+        // Imports can change between runs, so the files are collected again each time.
+        let files = module_files(path);
+        let mut dirs: Vec<PathBuf> = files
+            .iter()
+            .filter_map(|file| file.parent().map(Path::to_path_buf))
+            .collect();
+        dirs.sort();
+        dirs.dedup();
+        // Editors often save by replacing the file, so the directories are watched instead.
+        let (_watcher, mut changes) = FileWatcher::watch(&dirs, false, options.debounce)
+            .unwrap_or_else(|error| {
+                eprintln!("Error: Cannot watch '{}': {}", path, error);
+                process::exit(1);
+            });
+
+        if options.clear {
+            print!("\x1b[2J\x1b[H");
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+        }
+        let mut child = process::Command::new(&program)
+            .arg(path)
+            .args(&options.run_args)
+            .spawn()
+            .unwrap_or_else(|error| {
+                eprintln!("Error: Cannot run '{}': {}", path, error);
+                process::exit(1);
+            });
+
+        let changed = {
+            let exited = std::pin::pin!(exit_status(&mut child));
+            let changed = std::pin::pin!(next_change(&mut changes, &files));
+            match futures_util::future::select(exited, changed).await {
+                futures_util::future::Either::Left((status, changed)) => {
+                    eprintln!("[watch] {} exited ({}); waiting for changes", path, status);
+                    changed.await
+                }
+                futures_util::future::Either::Right((changed, _)) => changed,
+            }
+        };
+        let _ = child.kill();
+        let _ = child.wait();
+
+        match changed {
+            Some(file) => eprintln!("[watch] {} changed; restarting", file.display()),
+            None => return,
+        }
+    }
+}
+
+async fn exit_status(child: &mut process::Child) -> process::ExitStatus {
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return status,
+            Ok(None) => tokio::time::sleep(Duration::from_millis(50)).await,
+            Err(error) => {
+                eprintln!("Error: Lost track of the program: {}", error);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+async fn next_change(changes: &mut FileWatcher, files: &[PathBuf]) -> Option<PathBuf> {
+    loop {
+        let changed = changes.changed_paths().await?;
+        if let Some(file) = changed.into_iter().find(|file| files.contains(file)) {
+            return Some(file);
+        }
+    }
+}
+
+fn write_snapshot(options: &[String]) {
+    let output = match options {
+        [] => DEFAULT_SNAPSHOT_OUTPUT,
+        [flag, path] if flag == "--out" => path,
+        _ => {
+            eprintln!("Usage: raccoon snapshot [--out <path>]");
+            process::exit(1);
+        }
+    };
+
+    // The snapshot being replaced may be stale; capture from a runtime built without it.
+    env::remove_var(SNAPSHOT_ENV);
+    let snapshot = Snapshot::capture().unwrap_or_else(|error| {
+        eprintln!("Error: Cannot snapshot the standard library\n{}", error);
+        process::exit(1);
+    });
+    if let Err(error) = fs::write(output, snapshot.encode()) {
+        eprintln!("Error: Could not write '{}': {}", output, error);
+        process::exit(1);
+    }
+    println!(
+        "Snapshot of {} globals and {} stdlib modules written to {}",
+        snapshot.global_count(),
+        snapshot.module_count(),
+        output
+    );
+    println!("Set {}={} to start runs from it", SNAPSHOT_ENV, output);
+}
+
+async fn run_bench(path: &str, config: BenchConfig) {
+    let program = parse_file(path);
+
+    if BenchRunner::find_benchmarks(&program).is_empty() {
+        eprintln!(
+            "Error: No benchmark functions found in '{}' (expected functions named {}*)",
+            path, BENCH_PREFIX
+        );
+        process::exit(1);
+    }
+
+    let runner = BenchRunner::new(Some(path.to_string()), config);
+    let results = runner.run(&program).await;
+    print!("{}", format_report(&results));
+
+    if results.iter().any(|r| r.ast.is_err() || r.ir.is_err()) {
+        process::exit(1);
+    }
+}
+
+fn generate_docs(target: &str, options: &[String]) {
+    let mut format = DocFormat::Markdown;
+    let mut out_dir = None;
+    let mut iter = options.iter();
+    while let Some(option) = iter.next() {
+        match option.as_str() {
+            "--format" => match iter.next().and_then(|name| DocFormat::from_name(name)) {
+                Some(value) => format = value,
+                None => {
+                    eprintln!("Error: --format expects 'md' or 'html'");
+                    process::exit(1);
+                }
+            },
+            "--out" => match iter.next() {
+                Some(dir) => out_dir = Some(PathBuf::from(dir)),
+                None => {
+                    eprintln!("Error: --out requires a directory");
                     process::exit(1);
                 }
+            },
+            other => {
+                eprintln!("Error: Unknown doc option '{}'", other);
+                process::exit(1);
             }
         }
-        Err(error) => {
-            eprintln!("{}", error);
+    }
+
+    let interpreter = Interpreter::new(None);
+    let globals = interpreter
+        .environment
+        .names()
+        .map(str::to_string)
+        .collect();
+    let generator = DocGenerator::new(globals);
+
+    let mut modules = Vec::new();
+    for (name, file, source) in doc_sources(target) {
+        match generator.document(&name, source, Some(file)) {
+            Ok(module) => modules.push(module),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+    }
+
+    match out_dir {
+        Some(dir) => write_docs(&dir, &modules, format),
+        None => {
+            for module in &modules {
+                print!("{}", module.render(format));
+            }
+        }
+    }
+}
+
+fn doc_sources(target: &str) -> Vec<(String, String, String)> {
+    if target == "std" {
+        let loader = StdLibLoader::with_default_path();
+        return loader
+            .available_modules()
+            .into_iter()
+            .filter_map(|name| {
+                let (source, file) = loader.module_source(&name)?;
+                Some((name, file, source))
+            })
+            .collect();
+    }
+
+    let root = Path::new(target);
+    let mut files = Vec::new();
+    if root.is_dir() {
+        collect_rcc_files(root, &mut files);
+        files.sort();
+    } else {
+        files.push(root.to_path_buf());
+    }
+
+    files
+        .into_iter()
+        .map(|path| {
+            let name = match path.strip_prefix(root) {
+                Ok(relative) if root.is_dir() => relative.with_extension(""),
+                _ => PathBuf::from(path.file_stem().unwrap_or(path.as_os_str())),
+            };
+            let source = fs::read_to_string(&path).unwrap_or_else(|e| {
+                eprintln!("Error: Could not read '{}': {}", path.display(), e);
+                process::exit(1);
+            });
+            (
+                name.to_string_lossy().replace('\\', "/"),
+                path.display().to_string(),
+                source,
+            )
+        })
+        .collect()
+}
+
+fn collect_rcc_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if path.is_dir() {
+            collect_rcc_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rcc") {
+            files.push(path);
+        }
+    }
+}
+
+fn write_docs(dir: &Path, modules: &[ModuleDoc], format: DocFormat) {
+    let write = |name: String, contents: String| {
+        let path = dir.join(format!("{}.{}", name, format.extension()));
+        if let Err(e) = fs::write(&path, contents) {
+            eprintln!("Error: Could not write '{}': {}", path.display(), e);
             process::exit(1);
         }
+    };
+
+    if let Err(e) = fs::create_dir_all(dir) {
+        eprintln!("Error: Could not create '{}': {}", dir.display(), e);
+        process::exit(1);
+    }
+    for module in modules {
+        write(file_stem(&module.name), module.render(format));
+    }
+    write("index".to_string(), render_index(modules, format));
+    println!(
+        "Documented {} module(s) in {}",
+        modules.len(),
+        dir.display()
+    );
+}
+
+async fn run_file(path: &str, options: RunOptions) {
+    if options.check {
+        check_file(path, &options);
+    }
+
+    let file = Some(path.to_string());
+    let heap_before_parse = heap_usage().current;
+    let mut program = parse_file(path);
+    let parsed_bytes = heap_usage().current.saturating_sub(heap_before_parse);
+
+    let mut interpreter = Interpreter::new(file.clone());
+    if options.quiet {
+        interpreter.diagnostics.silence();
+    }
+    if options.release {
+        interpreter.enable_release_mode();
+    }
+
+    // A profile, from the file's pragma or else the flag, asks for the program to type-check.
+    let strictness = StrictnessProfile::from_pragma(&read_source(path)).or(options.strictness);
+    if options.lints.is_some() || strictness.is_some() {
+        let levels = options
+            .lints
+            .clone()
+            .unwrap_or_else(|| LintLevels::new(Severity::Allow));
+        let (analyzer, errors) = lint_program(&program, &interpreter, file, levels, strictness);
+        report_problems(path, errors, &analyzer, strictness);
+    }
+
+    Interpreter::append_async_main(&mut program);
+    global::set_argv(
+        std::iter::once(path.to_string())
+            .chain(options.script_args.iter().cloned())
+            .collect(),
+    );
+
+    if options.use_ir {
+        interpreter.enable_ir_mode();
+        if options.profile_output.is_some() {
+            eprintln!("Warning: --profile only instruments the AST interpreter");
+        }
+    }
+    if options.ir_stats && !options.use_ir {
+        eprintln!("Warning: --ir-stats only instruments the IR VM; pass --use-ir");
+    }
+    let ir_stats = (options.ir_stats && options.use_ir).then(|| interpreter.enable_ir_stats());
+    interpreter.enable_sandbox(options.sandbox.with_permissions(options.permissions));
+    let profiler = options
+        .profile_output
+        .as_ref()
+        .map(|_| interpreter.enable_profiling());
+
+    if let Some(port) = options.debug_port {
+        if options.use_ir {
+            eprintln!("Warning: --debug only supports the AST interpreter");
+        }
+        eprintln!("Waiting for debug adapter on 127.0.0.1:{}", port);
+        match Debugger::listen(port) {
+            Ok(debugger) => interpreter.attach_debugger(debugger),
+            Err(error) => {
+                eprintln!("Error: Failed to start debugger: {}", error);
+                process::exit(1);
+            }
+        }
+    }
+
+    let result = interpreter.interpret(&program).await;
+    ProcessHooks::run_exit_handlers(&mut interpreter, i32::from(result.is_err()), (0, 0)).await;
+    restore_terminal();
+
+    if let Some(debugger) = &interpreter.debugger {
+        debugger
+            .lock()
+            .unwrap()
+            .finish(if result.is_ok() { 0 } else { 1 });
+    }
+
+    if let (Some(profiler), Some(output)) = (profiler, &options.profile_output) {
+        let profiler = profiler.lock().unwrap();
+        eprint!("{}", profiler.format_summary());
+        match fs::write(output, profiler.to_collapsed_stacks()) {
+            Ok(()) => eprintln!("Profile written to {}", output),
+            Err(error) => eprintln!("Error: Failed to write profile '{}': {}", output, error),
+        }
+    }
+
+    if options.print_memory_stats {
+        eprint!("{}", format_memory_stats());
+        eprintln!("  {:<12} {:>10}", "parsed AST", parsed_bytes);
+    }
+
+    if let Some(stats) = ir_stats {
+        eprint!("{}", stats.lock().unwrap().format_summary());
+    }
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn check_file(path: &str, options: &RunOptions) -> ! {
+    let file = Some(path.to_string());
+    let mut parser = Parser::new(tokenize_file(path), file.clone());
+    let (program, mut errors) = parser.parse_recovering();
+
+    let interpreter = Interpreter::new(file.clone());
+    let strictness = StrictnessProfile::from_pragma(&read_source(path)).or(options.strictness);
+    let levels = options
+        .lints
+        .clone()
+        .unwrap_or_else(|| LintLevels::new(Severity::Allow));
+    let (analyzer, semantic_errors) =
+        lint_program(&program, &interpreter, file, levels, strictness);
+    errors.extend(semantic_errors);
+    report_problems(path, errors, &analyzer, strictness);
+    process::exit(0);
+}
+
+fn lint_program(
+    program: &Program,
+    interpreter: &Interpreter,
+    file: Option<String>,
+    levels: LintLevels,
+    strictness: Option<StrictnessProfile>,
+) -> (SemanticAnalyzer, Vec<RaccoonError>) {
+    let mut analyzer = SemanticAnalyzer::with_symbol_table(interpreter.global_symbols())
+        .with_file(file)
+        .with_lint_levels(levels)
+        .with_strictness(strictness.unwrap_or_default());
+    let errors = analyzer.analyze_recovering(program);
+    (analyzer, errors)
+}
+
+fn report_problems(
+    path: &str,
+    errors: Vec<RaccoonError>,
+    analyzer: &SemanticAnalyzer,
+    strictness: Option<StrictnessProfile>,
+) {
+    for warning in &analyzer.warnings {
+        eprintln!("{}", warning);
+    }
+
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+    if !errors.is_empty() {
+        match strictness {
+            Some(profile) => eprintln!(
+                "Aborting: the program does not type-check in {} mode",
+                profile
+            ),
+            None => eprintln!("Aborting due to {} error(s) in {}", errors.len(), path),
+        }
+        process::exit(1);
+    }
+
+    if analyzer.has_denied_warnings() {
+        let denied = analyzer
+            .warnings
+            .iter()
+            .filter(|warning| warning.severity == Severity::Error)
+            .count();
+        eprintln!("Aborting due to {} denied warning(s)", denied);
+        process::exit(1);
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE))
+}
+
+fn open_brackets(source: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+
+    for c in source.chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+
+    depth
+}
+
+async fn run_repl() {
+    let mut interpreter = Interpreter::new(None);
+    let history = history_path();
+
+    if let Some(path) = &history {
+        shared_editor().lock().unwrap().load_history(path);
+    }
+
+    println!("Raccoon REPL. Press Ctrl-D to exit.");
+
+    loop {
+        let completions = interpreter
+            .environment
+            .names()
+            .filter(|name| !name.starts_with('_'))
+            .map(str::to_string)
+            .collect();
+
+        let mut source = String::new();
+        let mut prompt = "> ";
+        let line = loop {
+            let mut editor = shared_editor().lock().unwrap();
+            editor.set_completions(Vec::clone(&completions));
+            match editor.read_line(prompt) {
+                Ok(ReadResult::Line(line)) => {
+                    source.push_str(&line);
+                    source.push('\n');
+                    if open_brackets(&source) <= 0 {
+                        break Some(source);
+                    }
+                    prompt = "... ";
+                }
+                Ok(ReadResult::Interrupted) => break Some(String::new()),
+                Ok(ReadResult::Eof) | Err(_) => break None,
+            }
+        };
+
+        let Some(source) = line else {
+            println!();
+            break;
+        };
+        if source.trim().is_empty() {
+            continue;
+        }
+
+        let result = async {
+            let tokens = Lexer::new(source, None).tokenize()?;
+            let program = Parser::new(tokens, None).parse()?;
+            interpreter.interpret(&program).await
+        }
+        .await;
+
+        match result {
+            Ok(RuntimeValue::Null(_)) => {}
+            Ok(value) => println!("{}", value.to_string()),
+            Err(error) => eprintln!("{}", error),
+        }
+    }
+
+    restore_terminal();
+    if let Some(path) = &history {
+        if let Err(error) = shared_editor().lock().unwrap().save_history(path) {
+            eprintln!("Warning: Failed to save history '{}': {}", path.display(), error);
+        }
     }
 }
@@ -50,14 +50,14 @@ impl Declarations {
         }
 
         if !decorators.is_empty() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Decorators can only be applied to classes and functions",
                 decorators[0].position,
                 state.file.clone(),
             ));
         }
 
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             "Expected declaration or statement",
             state.current_position(),
             state.file.clone(),
@@ -89,13 +89,13 @@ impl Declarations {
         if Parser::match_token(state, &[TokenType::Assign]) {
             initializer = Some(Expressions::expression(state)?);
         } else if is_constant {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Constants must be initialized",
                 state.previous().unwrap().position,
                 state.file.clone(),
             ));
         } else if type_annotation == PrimitiveType::any() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Variable declaration must have either a type annotation or an initializer",
                 state.previous().unwrap().position,
                 state.file.clone(),
@@ -109,6 +109,7 @@ impl Declarations {
             type_annotation,
             initializer,
             is_constant,
+            doc: None,
             position: state.previous().unwrap().position,
         }))
     }
@@ -175,10 +176,11 @@ impl Declarations {
             type_parameters,
             parameters,
             return_type,
-            body,
+            body: body.into(),
             is_async,
             is_declare,
             decorators,
+            doc: None,
             position,
         }))
     }
@@ -245,9 +247,14 @@ impl Declarations {
             );
         }
 
+        let mut implements = Vec::new();
         if Parser::match_token(state, &[TokenType::Implements]) {
             loop {
-                Parser::consume(state, TokenType::Identifier, "Expected interface name")?;
+                implements.push(
+                    Parser::consume(state, TokenType::Identifier, "Expected interface name")?
+                        .value
+                        .clone(),
+                );
                 if !Parser::match_token(state, &[TokenType::Comma]) {
                     break;
                 }
@@ -293,7 +300,7 @@ impl Declarations {
 
             if Parser::match_token(state, &[TokenType::Constructor]) {
                 if constructor.is_some() {
-                    return Err(RaccoonError::new(
+                    return Err(RaccoonError::syntax_error(
                         "Class cannot have multiple constructors",
                         state.previous().unwrap().position,
                         state.file.clone(),
@@ -355,11 +362,14 @@ impl Declarations {
             name,
             type_parameters,
             superclass,
+            implements,
             properties,
             constructor,
             methods,
             accessors,
+            static_blocks: Vec::new(),
             decorators,
+            doc: None,
             position,
         }))
     }
@@ -369,6 +379,8 @@ impl Declarations {
         decorators: Vec<DecoratorDecl>,
         access_modifier: AccessModifier,
     ) -> Result<ClassProperty, RaccoonError> {
+        let is_readonly = Parser::match_token(state, &[TokenType::Readonly]);
+
         let name = Parser::consume(state, TokenType::Identifier, "Expected property name")?
             .value
             .clone();
@@ -390,6 +402,9 @@ impl Declarations {
             initializer,
             decorators,
             access_modifier,
+            is_readonly,
+            is_static: false,
+            doc: None,
         })
     }
 
@@ -418,7 +433,7 @@ impl Declarations {
 
         Ok(ConstructorDecl {
             parameters,
-            body,
+            body: body.into(),
             position,
         })
     }
@@ -470,11 +485,12 @@ impl Declarations {
             name,
             parameters,
             return_type,
-            body,
+            body: body.into(),
             is_async,
             decorators,
             access_modifier,
             is_static,
+            doc: None,
         })
     }
 
@@ -503,14 +519,14 @@ impl Declarations {
         )?;
 
         if kind == AccessorKind::Get && !parameters.is_empty() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Getter cannot have parameters",
                 position,
                 state.file.clone(),
             ));
         }
         if kind == AccessorKind::Set && parameters.len() != 1 {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Setter must have exactly one parameter",
                 position,
                 state.file.clone(),
@@ -536,7 +552,7 @@ impl Declarations {
             kind,
             parameters,
             return_type,
-            body,
+            body: body.into(),
             access_modifier,
             position,
         })
@@ -583,6 +599,7 @@ impl Declarations {
         if !state.check(&TokenType::RightParen) {
             loop {
                 let is_rest = Parser::match_token(state, &[TokenType::Spread]);
+                let param_position = state.peek().unwrap().position;
 
                 if state.check(&TokenType::LeftBracket) || state.check(&TokenType::LeftBrace) {
                     let pattern =
@@ -597,7 +614,7 @@ impl Declarations {
 
                     let is_optional = Parser::match_token(state, &[TokenType::Question]);
                     if is_optional && is_rest {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Rest parameters cannot be optional".to_string(),
                             state.peek().unwrap().position,
                             None::<String>,
@@ -606,7 +623,7 @@ impl Declarations {
                     if is_optional {
                         has_optional = true;
                     } else if has_optional {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Required parameters cannot follow optional parameters".to_string(),
                             state.peek().unwrap().position,
                             None::<String>,
@@ -619,6 +636,8 @@ impl Declarations {
                         default_value: None,
                         is_rest,
                         is_optional,
+                        is_untyped: false,
+                        position: param_position,
                     });
                 } else {
                     let name =
@@ -632,7 +651,7 @@ impl Declarations {
                     let param_type = PrimitiveType::any();
 
                     if is_optional && is_rest {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Rest parameters cannot be optional".to_string(),
                             state.peek().unwrap().position,
                             None::<String>,
@@ -641,7 +660,7 @@ impl Declarations {
                     if is_optional {
                         has_optional = true;
                     } else if has_optional {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Required parameters cannot follow optional parameters".to_string(),
                             state.peek().unwrap().position,
                             None::<String>,
@@ -659,6 +678,8 @@ impl Declarations {
                         default_value,
                         is_rest,
                         is_optional,
+                        is_untyped: false,
+                        position: param_position,
                     });
                 }
 
@@ -688,7 +709,7 @@ impl Declarations {
                 state,
             )?));
         }
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             "Expected destructuring pattern",
             state.peek().unwrap().position,
             state.file.clone(),
@@ -900,6 +921,7 @@ impl Declarations {
                         params: param_types,
                         return_type,
                         is_variadic: false,
+                        param_names: Vec::new(),
                     })),
                     optional: false,
                 });
@@ -927,7 +949,9 @@ impl Declarations {
             name,
             type_parameters,
             properties,
+            methods: Vec::new(),
             extends,
+            doc: None,
             position,
         }))
     }
@@ -968,6 +992,7 @@ impl Declarations {
         Ok(Stmt::EnumDecl(EnumDecl {
             name,
             members,
+            doc: None,
             position,
         }))
     }
@@ -991,6 +1016,7 @@ impl Declarations {
         Ok(Stmt::TypeAliasDecl(TypeAliasDecl {
             name,
             alias_type,
+            doc: None,
             position,
         }))
     }
@@ -308,12 +308,14 @@ impl Expressions {
     pub fn range(state: &mut ParserState) -> Result<Expr, RaccoonError> {
         let expr = Self::term(state)?;
 
-        if Parser::match_token(state, &[TokenType::Range]) {
+        if Parser::match_token(state, &[TokenType::Range, TokenType::RangeExclusive]) {
+            let inclusive = state.previous().unwrap().token_type == TokenType::Range;
             let end = Box::new(Self::term(state)?);
             let position = expr.position();
             return Ok(Expr::Range(RangeExpr {
                 start: Box::new(expr),
                 end,
+                inclusive,
                 position,
             }));
         }
@@ -511,6 +513,7 @@ impl Expressions {
             class_name,
             type_args,
             args,
+            named_args: HashMap::new(),
             position,
         }))
     }
@@ -567,6 +570,7 @@ impl Expressions {
                         object: Box::new(expr),
                         method: name,
                         args,
+                        named_args: HashMap::new(),
                         position,
                     });
                 } else {
@@ -612,7 +616,7 @@ impl Expressions {
                     named_args.insert(name, value);
                 } else {
                     if !named_args.is_empty() {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Positional arguments must come before named arguments",
                             state.peek().unwrap().position,
                             state.file.clone(),
@@ -749,7 +753,7 @@ impl Expressions {
             return Self::template_string(state);
         }
 
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             "Expected expression",
             state.peek().unwrap().position,
             state.file.clone(),
@@ -851,7 +855,7 @@ impl Expressions {
                 )?;
                 parts.push(TemplateStrPart::Expr(expr));
             } else {
-                return Err(RaccoonError::new(
+                return Err(RaccoonError::syntax_error(
                     "Expected template string part or interpolation",
                     state.peek().unwrap().position,
                     state.file.clone(),
@@ -883,7 +887,7 @@ impl Expressions {
         }
 
         if !Parser::match_token(state, &[TokenType::Arrow]) {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Expected '=>' for arrow function",
                 state.peek().unwrap().position,
                 state.file.clone(),
@@ -894,7 +898,7 @@ impl Expressions {
             state.advance();
 
             let stmts = Vec::new();
-            ArrowFnBody::Block(stmts)
+            ArrowFnBody::Block(stmts.into())
         } else {
             let expr = Self::conditional(state)?;
             ArrowFnBody::Expr(Box::new(expr))
@@ -918,6 +922,7 @@ impl Expressions {
         if !state.check(&TokenType::RightParen) {
             loop {
                 let is_rest = Parser::match_token(state, &[TokenType::Spread]);
+                let param_position = state.peek().unwrap().position;
 
                 if state.check(&TokenType::LeftBracket) || state.check(&TokenType::LeftBrace) {
                     let pattern = VarPattern::Identifier("TODO".to_string());
@@ -931,7 +936,7 @@ impl Expressions {
 
                     let is_optional = Parser::match_token(state, &[TokenType::Question]);
                     if is_optional && is_rest {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Rest parameters cannot be optional".to_string(),
                             state.peek().unwrap().position.clone(),
                             None::<String>,
@@ -940,7 +945,7 @@ impl Expressions {
                     if is_optional {
                         has_optional = true;
                     } else if has_optional {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Required parameters cannot follow optional parameters".to_string(),
                             state.peek().unwrap().position.clone(),
                             None::<String>,
@@ -953,6 +958,8 @@ impl Expressions {
                         default_value: None,
                         is_rest,
                         is_optional,
+                        is_untyped: false,
+                        position: param_position,
                     });
                 } else {
                     let name =
@@ -969,7 +976,7 @@ impl Expressions {
                     };
 
                     if is_optional && is_rest {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Rest parameters cannot be optional".to_string(),
                             state.peek().unwrap().position.clone(),
                             None::<String>,
@@ -978,7 +985,7 @@ impl Expressions {
                     if is_optional {
                         has_optional = true;
                     } else if has_optional {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Required parameters cannot follow optional parameters".to_string(),
                             state.peek().unwrap().position.clone(),
                             None::<String>,
@@ -996,6 +1003,8 @@ impl Expressions {
                         default_value,
                         is_rest,
                         is_optional,
+                        is_untyped: false,
+                        position: param_position,
                     });
                 }
 
@@ -1014,7 +1023,7 @@ impl Expressions {
 
     fn try_parse_call_type_arguments(state: &mut ParserState) -> Result<Vec<Type>, RaccoonError> {
         if !Parser::match_token(state, &[TokenType::Lt]) {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Expected '<'",
                 state.peek().unwrap().position,
                 state.file.clone(),
@@ -1157,7 +1166,7 @@ impl Expressions {
             return Ok(Pattern::Literal(Box::new(expr)));
         }
 
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             "Expected pattern",
             state.peek().unwrap().position,
             state.file.clone(),
@@ -1178,9 +1187,14 @@ impl Expressions {
             );
         }
 
+        let mut implements = Vec::new();
         if Parser::match_token(state, &[TokenType::Implements]) {
             loop {
-                Parser::consume(state, TokenType::Identifier, "Expected interface name")?;
+                implements.push(
+                    Parser::consume(state, TokenType::Identifier, "Expected interface name")?
+                        .value
+                        .clone(),
+                );
                 if !Parser::match_token(state, &[TokenType::Comma]) {
                     break;
                 }
@@ -1226,7 +1240,7 @@ impl Expressions {
 
             if Parser::match_token(state, &[TokenType::Constructor]) {
                 if constructor.is_some() {
-                    return Err(RaccoonError::new(
+                    return Err(RaccoonError::syntax_error(
                         "Class cannot have multiple constructors",
                         state.previous().unwrap().position,
                         state.file.clone(),
@@ -1287,6 +1301,7 @@ impl Expressions {
         Ok(Expr::Class(ClassExpr {
             type_parameters,
             superclass,
+            implements,
             properties,
             constructor,
             methods,
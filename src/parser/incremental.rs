@@ -0,0 +1,824 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+use super::Parser;
+use crate::{ast::nodes::*, Lexer, Position, RaccoonError, Token};
+
+/// A replacement of the text from `start` up to `end` (exclusive) with `text`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    pub start: Position,
+    pub end: Position,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    tokens: Range<usize>,
+    stmt: Option<Arc<Stmt>>,
+    errors: Vec<RaccoonError>,
+}
+
+/// A file kept parsed between edits, reparsing only the statements an edit touches.
+#[derive(Debug, Clone)]
+pub struct ParsedFile {
+    pub source: String,
+    pub file: Option<String>,
+    pub tokens: Vec<Token>,
+    items: Vec<Item>,
+    /// How many top-level statements the edit that made this parse took over unchanged.
+    pub reused: usize,
+}
+
+impl ParsedFile {
+    /// Lexes and parses `source`, failing only when it cannot be lexed.
+    pub fn parse(source: String, file: Option<String>) -> Result<Self, RaccoonError> {
+        let tokens = Lexer::new(source.clone(), file.clone()).tokenize()?;
+        let mut parser = Parser::new(tokens, file.clone());
+        let mut items = Vec::new();
+        while !parser.is_at_end() {
+            items.push(parser.item());
+        }
+        Ok(Self {
+            source,
+            file,
+            tokens: parser.tokens,
+            items,
+            reused: 0,
+        })
+    }
+
+    /// The file after `edit`, reusing the statements it leaves untouched.
+    pub fn edit(&self, edit: &TextEdit) -> Result<Self, RaccoonError> {
+        let start = self.offset(edit.start);
+        let end = self.offset(edit.end).max(start);
+        let source = [&self.source[..start], &edit.text, &self.source[end..]].concat();
+        let lines = edit.text.matches('\n').count() as isize
+            - self.source[start..end].matches('\n').count() as isize;
+        let tokens = self.relex(&source, start, end, &edit.text, lines)?;
+        let unchanged =
+            |old: Range<usize>, new: Range<usize>| tokens.get(new) == self.tokens.get(old);
+
+        // The token after a statement decides where it ends, so it has to be unchanged too.
+        let prefix = self
+            .items
+            .iter()
+            .take_while(|item| {
+                let range = item.tokens.start..item.tokens.end + 1;
+                unchanged(range.clone(), range)
+            })
+            .count();
+        let prefix_end = match prefix {
+            0 => 0,
+            n => self.items[n - 1].tokens.end,
+        };
+
+        // Statements after the edit sit as many tokens from the end of the file as before, and
+        // `lines` further down. They are kept last first, so the one nearest the edit is at
+        // the end.
+        let offset = tokens.len() as isize - self.tokens.len() as isize;
+        let mut suffix: Vec<Item> = self.items[prefix..]
+            .iter()
+            .rev()
+            .map_while(|item| {
+                let start = item.tokens.start.checked_add_signed(offset)?;
+                let end = item.tokens.end.checked_add_signed(offset)?;
+                let reusable = start >= prefix_end
+                    && self.tokens[item.tokens.clone()]
+                        .iter()
+                        .zip(&tokens[start..end])
+                        .all(|(old, new)| moved(old, new, lines));
+                reusable.then(|| item.moved(start..end, lines))?
+            })
+            .collect();
+
+        // Parse from the edit until the parser lands on the start of an unchanged statement.
+        let mut items: Vec<Item> = self.items[..prefix].to_vec();
+        let mut reused = prefix;
+        let mut parser = Parser::new(tokens, self.file.clone());
+        parser.current = prefix_end;
+        while !parser.is_at_end() {
+            while suffix
+                .last()
+                .is_some_and(|item| item.tokens.start < parser.current)
+            {
+                suffix.pop();
+            }
+            if suffix
+                .last()
+                .is_some_and(|item| item.tokens.start == parser.current)
+            {
+                reused += suffix.len();
+                items.extend(suffix.drain(..).rev());
+                break;
+            }
+            items.push(parser.item());
+        }
+
+        Ok(Self {
+            source,
+            file: self.file.clone(),
+            tokens: parser.tokens,
+            items,
+            reused,
+        })
+    }
+
+    fn relex(
+        &self,
+        source: &str,
+        start: usize,
+        end: usize,
+        text: &str,
+        lines: isize,
+    ) -> Result<Vec<Token>, RaccoonError> {
+        let edit_start = position_at(&self.source, start);
+        let old_end = position_at(&self.source, end);
+        let new_end = position_at(source, start + text.len());
+
+        let (first_token, resume_at) = match self
+            .items
+            .iter()
+            .rposition(|item| self.start_of(item.tokens.start) < edit_start)
+        {
+            Some(index) => {
+                let token = self.items[index].tokens.start;
+                (token, self.start_of(token))
+            }
+            None => (0, (1, 1)),
+        };
+        let resume_index = self.source[..self.offset(resume_at)].chars().count();
+        let mut lexer = Lexer::resume(
+            source.to_string(),
+            self.file.clone(),
+            resume_index,
+            resume_at,
+        );
+
+        let mut anchors = self
+            .items
+            .iter()
+            .map(|item| item.tokens.start)
+            .filter(|&index| self.tokens[index].position >= old_end)
+            .peekable();
+        loop {
+            let lexed = lexer.lexed().len();
+            if !lexer.scan()? {
+                break;
+            }
+            let Some(token) = lexer.lexed().get(lexed) else {
+                continue;
+            };
+            if token.position < new_end {
+                continue;
+            }
+            while anchors.peek().is_some_and(|&index| {
+                shift(self.tokens[index].position, lines).is_none_or(|old| old < token.position)
+            }) {
+                anchors.next();
+            }
+            if let Some(&index) = anchors.peek() {
+                if moved(&self.tokens[index], token, lines) {
+                    let mut tokens = self.tokens[..first_token].to_vec();
+                    tokens.extend_from_slice(&lexer.lexed()[..lexed]);
+                    tokens.extend(self.tokens[index..].iter().map(|token| {
+                        let mut token = token.clone();
+                        token.shift(lines);
+                        token
+                    }));
+                    return Ok(tokens);
+                }
+            }
+        }
+
+        let mut tokens = self.tokens[..first_token].to_vec();
+        tokens.extend(lexer.finish());
+        Ok(tokens)
+    }
+
+    fn start_of(&self, index: usize) -> Position {
+        let token = &self.tokens[index];
+        token
+            .leading_comments
+            .first()
+            .map_or(token.position, |comment| comment.position)
+    }
+
+    /// The parsed program, without the statements that failed to parse.
+    pub fn program(&self) -> Program {
+        Program {
+            stmts: self
+                .items
+                .iter()
+                .filter_map(|item| item.stmt.as_deref().cloned())
+                .collect(),
+            position: (1, 1),
+        }
+    }
+
+    /// Every syntax error in the file, in source order.
+    pub fn errors(&self) -> Vec<RaccoonError> {
+        self.items
+            .iter()
+            .flat_map(|item| item.errors.iter().cloned())
+            .collect()
+    }
+
+    fn offset(&self, (line, column): Position) -> usize {
+        let mut line_start = 0;
+        for _ in 1..line {
+            match self.source[line_start..].find('\n') {
+                Some(newline) => line_start += newline + 1,
+                None => return self.source.len(),
+            }
+        }
+        let line_text = &self.source[line_start..];
+        let line_len = line_text.find('\n').unwrap_or(line_text.len());
+        line_start
+            + line_text[..line_len]
+                .char_indices()
+                .nth(column.saturating_sub(1))
+                .map_or(line_len, |(index, _)| index)
+    }
+}
+
+impl Item {
+    fn moved(&self, tokens: Range<usize>, lines: isize) -> Option<Item> {
+        let stmt = match &self.stmt {
+            Some(stmt) if lines != 0 => {
+                let mut stmt = Stmt::clone(stmt);
+                stmt.shift(lines);
+                Some(Arc::new(stmt))
+            }
+            stmt => stmt.clone(),
+        };
+        let errors = self
+            .errors
+            .iter()
+            .map(|error| {
+                let mut error = error.clone();
+                error.position = shift(error.position, lines)?;
+                if let Some(range) = &mut error.range {
+                    range.start = shift(range.start, lines)?;
+                    range.end = shift(range.end, lines)?;
+                }
+                Some(error)
+            })
+            .collect::<Option<_>>()?;
+        Some(Item {
+            tokens,
+            stmt,
+            errors,
+        })
+    }
+}
+
+fn moved(old: &Token, new: &Token, lines: isize) -> bool {
+    shift(old.position, lines) == Some(new.position)
+        && old.token_type == new.token_type
+        && old.value == new.value
+        && old.lexeme == new.lexeme
+        && old.leading_comments.len() == new.leading_comments.len()
+        && old
+            .leading_comments
+            .iter()
+            .zip(&new.leading_comments)
+            .all(|(old, new)| {
+                old.kind == new.kind
+                    && old.text == new.text
+                    && shift(old.position, lines) == Some(new.position)
+            })
+}
+
+fn position_at(source: &str, offset: usize) -> Position {
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+fn shift((line, column): Position, lines: isize) -> Option<Position> {
+    Some((line.checked_add_signed(lines)?, column))
+}
+
+trait Shift {
+    fn shift(&mut self, lines: isize);
+}
+
+impl Shift for Position {
+    fn shift(&mut self, lines: isize) {
+        self.0 = self.0.saturating_add_signed(lines);
+    }
+}
+
+impl<T: Shift> Shift for Option<T> {
+    fn shift(&mut self, lines: isize) {
+        if let Some(node) = self {
+            node.shift(lines);
+        }
+    }
+}
+
+impl<T: Shift> Shift for Box<T> {
+    fn shift(&mut self, lines: isize) {
+        (**self).shift(lines);
+    }
+}
+
+impl<T: Shift> Shift for Vec<T> {
+    fn shift(&mut self, lines: isize) {
+        for node in self {
+            node.shift(lines);
+        }
+    }
+}
+
+impl Shift for Body {
+    fn shift(&mut self, lines: isize) {
+        let mut stmts = self.to_vec();
+        stmts.shift(lines);
+        *self = stmts.into();
+    }
+}
+
+impl Shift for HashMap<String, Expr> {
+    fn shift(&mut self, lines: isize) {
+        for expr in self.values_mut() {
+            expr.shift(lines);
+        }
+    }
+}
+
+impl Shift for Token {
+    fn shift(&mut self, lines: isize) {
+        self.position.shift(lines);
+        for comment in &mut self.leading_comments {
+            comment.position.shift(lines);
+        }
+    }
+}
+
+impl Shift for Stmt {
+    fn shift(&mut self, lines: isize) {
+        match self {
+            Stmt::Program(program) => {
+                program.stmts.shift(lines);
+                program.position.shift(lines);
+            }
+            Stmt::VarDecl(decl) => {
+                decl.pattern.shift(lines);
+                decl.initializer.shift(lines);
+                decl.position.shift(lines);
+            }
+            Stmt::FnDecl(decl) => {
+                decl.parameters.shift(lines);
+                decl.body.shift(lines);
+                decl.decorators.shift(lines);
+                decl.position.shift(lines);
+            }
+            Stmt::ClassDecl(decl) => {
+                decl.properties.shift(lines);
+                decl.constructor.shift(lines);
+                decl.methods.shift(lines);
+                decl.accessors.shift(lines);
+                decl.static_blocks.shift(lines);
+                decl.decorators.shift(lines);
+                decl.position.shift(lines);
+            }
+            Stmt::InterfaceDecl(decl) => {
+                decl.methods.shift(lines);
+                decl.position.shift(lines);
+            }
+            Stmt::EnumDecl(decl) => {
+                for member in &mut decl.members {
+                    member.value.shift(lines);
+                }
+                decl.position.shift(lines);
+            }
+            Stmt::TypeAliasDecl(decl) => decl.position.shift(lines),
+            Stmt::ImportDecl(decl) => decl.position.shift(lines),
+            Stmt::ExportDecl(decl) => {
+                decl.declaration.shift(lines);
+                decl.position.shift(lines);
+            }
+            Stmt::Block(block) => block.shift(lines),
+            Stmt::IfStmt(stmt) => {
+                stmt.condition.shift(lines);
+                stmt.then_branch.shift(lines);
+                stmt.else_branch.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::WhileStmt(stmt) => {
+                stmt.condition.shift(lines);
+                stmt.body.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::DoWhileStmt(stmt) => {
+                stmt.body.shift(lines);
+                stmt.condition.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::ForStmt(stmt) => {
+                stmt.initializer.shift(lines);
+                stmt.condition.shift(lines);
+                stmt.increment.shift(lines);
+                stmt.body.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::ForInStmt(stmt) => {
+                stmt.pattern.shift(lines);
+                stmt.iterable.shift(lines);
+                stmt.body.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::ForOfStmt(stmt) => {
+                stmt.pattern.shift(lines);
+                stmt.iterable.shift(lines);
+                stmt.body.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::SwitchStmt(stmt) => {
+                stmt.discriminant.shift(lines);
+                stmt.cases.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::ReturnStmt(stmt) => {
+                stmt.value.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::BreakStmt(stmt) => stmt.position.shift(lines),
+            Stmt::ContinueStmt(stmt) => stmt.position.shift(lines),
+            Stmt::ExprStmt(stmt) => {
+                stmt.expression.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::TryStmt(stmt) => {
+                stmt.try_block.shift(lines);
+                for clause in &mut stmt.catch_clauses {
+                    clause.body.shift(lines);
+                    clause.position.shift(lines);
+                }
+                stmt.finally_block.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::ThrowStmt(stmt) => {
+                stmt.value.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::AssertStmt(stmt) => {
+                stmt.condition.shift(lines);
+                stmt.message.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::UsingStmt(stmt) => {
+                stmt.initializer.shift(lines);
+                stmt.body.shift(lines);
+                stmt.position.shift(lines);
+            }
+            Stmt::DeferStmt(stmt) => {
+                stmt.expression.shift(lines);
+                stmt.body.shift(lines);
+                stmt.position.shift(lines);
+            }
+        }
+    }
+}
+
+impl Shift for Expr {
+    fn shift(&mut self, lines: isize) {
+        match self {
+            Expr::Binary(expr) => {
+                expr.left.shift(lines);
+                expr.right.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Unary(expr) => {
+                expr.operand.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Call(expr) => {
+                expr.callee.shift(lines);
+                expr.args.shift(lines);
+                expr.named_args.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::New(expr) => {
+                expr.args.shift(lines);
+                expr.named_args.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Member(expr) => {
+                expr.object.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::MethodCall(expr) => {
+                expr.object.shift(lines);
+                expr.args.shift(lines);
+                expr.named_args.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Index(expr) => {
+                expr.object.shift(lines);
+                expr.index.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Slice(expr) => {
+                expr.object.shift(lines);
+                expr.start.shift(lines);
+                expr.end.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Await(expr) => {
+                expr.expression.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::This(expr) => expr.position.shift(lines),
+            Expr::Super(expr) => expr.position.shift(lines),
+            Expr::TypeOf(expr) => {
+                expr.operand.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::InstanceOf(expr) => {
+                expr.operand.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::As(expr) => {
+                expr.operand.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Is(expr) => {
+                expr.operand.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::ArrowFn(expr) => {
+                expr.parameters.shift(lines);
+                match &mut expr.body {
+                    ArrowFnBody::Expr(body) => body.shift(lines),
+                    ArrowFnBody::Block(body) => body.shift(lines),
+                }
+                expr.position.shift(lines);
+            }
+            Expr::Identifier(expr) => expr.position.shift(lines),
+            Expr::Assignment(expr) => {
+                expr.target.shift(lines);
+                expr.value.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Range(expr) => {
+                expr.start.shift(lines);
+                expr.end.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Conditional(expr) => {
+                expr.condition.shift(lines);
+                expr.then_expr.shift(lines);
+                expr.else_expr.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::NullCoalescing(expr) => {
+                expr.left.shift(lines);
+                expr.right.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::OptionalChaining(expr) => {
+                expr.object.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::NullAssertion(expr) => {
+                expr.operand.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::UnaryUpdate(expr) => {
+                expr.operand.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::TemplateStr(expr) => expr.shift(lines),
+            Expr::TaggedTemplate(expr) => {
+                expr.tag.shift(lines);
+                expr.template.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Switch(expr) => {
+                expr.discriminant.shift(lines);
+                expr.cases.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Do(expr) => {
+                expr.body.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::IntLiteral(expr) => expr.position.shift(lines),
+            Expr::BigIntLiteral(expr) => expr.position.shift(lines),
+            Expr::FloatLiteral(expr) => expr.position.shift(lines),
+            Expr::StrLiteral(expr) => expr.position.shift(lines),
+            Expr::BoolLiteral(expr) => expr.position.shift(lines),
+            Expr::NullLiteral(expr) => expr.position.shift(lines),
+            Expr::ArrayLiteral(expr) => {
+                expr.elements.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::TupleLiteral(expr) => {
+                expr.elements.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::ObjectLiteral(expr) => {
+                for property in &mut expr.properties {
+                    match property {
+                        ObjectLiteralProperty::KeyValue { value, .. } => value.shift(lines),
+                        ObjectLiteralProperty::Spread(value) => value.shift(lines),
+                    }
+                }
+                expr.position.shift(lines);
+            }
+            Expr::Spread(expr) => {
+                expr.argument.shift(lines);
+                expr.position.shift(lines);
+            }
+            Expr::Match(expr) => {
+                expr.scrutinee.shift(lines);
+                for arm in &mut expr.arms {
+                    arm.pattern.shift(lines);
+                    arm.guard.shift(lines);
+                    arm.body.shift(lines);
+                    arm.position.shift(lines);
+                }
+                expr.position.shift(lines);
+            }
+            Expr::Class(expr) => {
+                expr.properties.shift(lines);
+                expr.constructor.shift(lines);
+                expr.methods.shift(lines);
+                expr.accessors.shift(lines);
+                expr.position.shift(lines);
+            }
+        }
+    }
+}
+
+impl Shift for Block {
+    fn shift(&mut self, lines: isize) {
+        self.statements.shift(lines);
+        self.position.shift(lines);
+    }
+}
+
+impl Shift for SwitchCase {
+    fn shift(&mut self, lines: isize) {
+        self.tests.shift(lines);
+        self.consequent.shift(lines);
+    }
+}
+
+impl Shift for TemplateStrExpr {
+    fn shift(&mut self, lines: isize) {
+        for part in &mut self.parts {
+            match part {
+                TemplateStrPart::String(literal) => literal.position.shift(lines),
+                TemplateStrPart::Expr(expr) => expr.shift(lines),
+            }
+        }
+        self.position.shift(lines);
+    }
+}
+
+impl Shift for FnParam {
+    fn shift(&mut self, lines: isize) {
+        self.pattern.shift(lines);
+        self.default_value.shift(lines);
+        self.position.shift(lines);
+    }
+}
+
+impl Shift for DecoratorDecl {
+    fn shift(&mut self, lines: isize) {
+        self.args.shift(lines);
+        self.position.shift(lines);
+    }
+}
+
+impl Shift for ClassProperty {
+    fn shift(&mut self, lines: isize) {
+        self.initializer.shift(lines);
+        self.decorators.shift(lines);
+    }
+}
+
+impl Shift for ClassMethod {
+    fn shift(&mut self, lines: isize) {
+        self.parameters.shift(lines);
+        self.body.shift(lines);
+        self.decorators.shift(lines);
+    }
+}
+
+impl Shift for ConstructorDecl {
+    fn shift(&mut self, lines: isize) {
+        self.parameters.shift(lines);
+        self.body.shift(lines);
+        self.position.shift(lines);
+    }
+}
+
+impl Shift for PropertyAccessor {
+    fn shift(&mut self, lines: isize) {
+        self.parameters.shift(lines);
+        self.body.shift(lines);
+        self.position.shift(lines);
+    }
+}
+
+impl Shift for VarPattern {
+    fn shift(&mut self, lines: isize) {
+        if let VarPattern::Destructuring(pattern) = self {
+            pattern.shift(lines);
+        }
+    }
+}
+
+impl Shift for DestructuringPattern {
+    fn shift(&mut self, lines: isize) {
+        match self {
+            DestructuringPattern::Array(pattern) => pattern.shift(lines),
+            DestructuringPattern::Object(pattern) => pattern.shift(lines),
+        }
+    }
+}
+
+impl Shift for ArrayPattern {
+    fn shift(&mut self, lines: isize) {
+        for element in self.elements.iter_mut().flatten() {
+            match element {
+                ArrayPatternElement::Identifier(ident) => ident.position.shift(lines),
+                ArrayPatternElement::List(pattern) => pattern.shift(lines),
+                ArrayPatternElement::Object(pattern) => pattern.shift(lines),
+            }
+        }
+        self.rest.shift(lines);
+        self.position.shift(lines);
+    }
+}
+
+impl Shift for ObjectPattern {
+    fn shift(&mut self, lines: isize) {
+        for property in &mut self.properties {
+            match &mut property.value {
+                ObjectPatternValue::Identifier(ident) => ident.position.shift(lines),
+                ObjectPatternValue::Array(pattern) => pattern.shift(lines),
+                ObjectPatternValue::Object(pattern) => pattern.shift(lines),
+            }
+        }
+        self.rest.shift(lines);
+        self.position.shift(lines);
+    }
+}
+
+impl Shift for RestElement {
+    fn shift(&mut self, lines: isize) {
+        self.argument.position.shift(lines);
+        self.position.shift(lines);
+    }
+}
+
+impl Shift for Pattern {
+    fn shift(&mut self, lines: isize) {
+        match self {
+            Pattern::Wildcard(position) => position.shift(lines),
+            Pattern::Literal(expr) => expr.shift(lines),
+            Pattern::Range(start, end) => {
+                start.shift(lines);
+                end.shift(lines);
+            }
+            Pattern::Array(patterns) | Pattern::Tuple(patterns) | Pattern::Or(patterns) => {
+                patterns.shift(lines)
+            }
+            Pattern::Object(properties) => {
+                for (_, pattern) in properties {
+                    pattern.shift(lines);
+                }
+            }
+            Pattern::Type(_) | Pattern::Variable(_) => {}
+        }
+    }
+}
+
+impl Parser {
+    fn item(&mut self) -> Item {
+        let start = self.current;
+        let stmt = match self.declaration() {
+            Ok(stmt) => Some(Arc::new(stmt)),
+            Err(error) => {
+                self.errors.push(error);
+                self.synchronize();
+                None
+            }
+        };
+        Item {
+            tokens: start..self.current,
+            stmt,
+            errors: std::mem::take(&mut self.errors),
+        }
+    }
+}
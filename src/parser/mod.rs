@@ -2,21 +2,38 @@ use std::collections::HashMap;
 
 use crate::{
     ast::{nodes::*, types::*},
-    tokens::{AccessModifier, BinaryOperator, UnaryOperator},
+    tokens::{AccessModifier, BinaryOperator, CommentKind, UnaryOperator},
     Position, RaccoonError, Token, TokenType,
 };
 
 pub mod declarations;
 pub mod expressions;
+pub mod incremental;
 pub mod state;
 pub mod statements;
 pub mod types;
 pub mod utilities;
 
+const OPERATOR_TYPE_END: [TokenType; 11] = [
+    TokenType::RightParen,
+    TokenType::RightBracket,
+    TokenType::RightBrace,
+    TokenType::Comma,
+    TokenType::Semicolon,
+    TokenType::And,
+    TokenType::Or,
+    TokenType::Eq,
+    TokenType::Neq,
+    TokenType::QuestionQuestion,
+    TokenType::Eof,
+];
+
 pub struct Parser {
     tokens: Vec<Token>,
     file: Option<String>,
     current: usize,
+    errors: Vec<RaccoonError>,
+    block_depth: usize,
 }
 
 impl Parser {
@@ -25,29 +42,82 @@ impl Parser {
             tokens,
             current: 0,
             file,
+            errors: Vec::new(),
+            block_depth: 0,
         }
     }
 
+    /// Parses the whole file, failing with the first syntax error.
     pub fn parse(&mut self) -> Result<Program, RaccoonError> {
+        let (program, mut errors) = self.parse_recovering();
+        if errors.is_empty() {
+            Ok(program)
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+
+    /// Parses the whole file, reporting every syntax error.
+    pub fn parse_recovering(&mut self) -> (Program, Vec<RaccoonError>) {
         let mut statements = Vec::new();
 
         while !self.is_at_end() {
             match self.declaration() {
                 Ok(stmt) => statements.push(stmt),
-                Err(e) => {
-                    eprintln!("{}", e);
+                Err(error) => {
+                    self.errors.push(error);
                     self.synchronize();
                 }
             }
         }
 
-        Ok(Program {
+        let program = Program {
             stmts: statements,
             position: (1, 1),
-        })
+        };
+        (program, std::mem::take(&mut self.errors))
     }
 
     fn declaration(&mut self) -> Result<Stmt, RaccoonError> {
+        let doc = self.doc_comment();
+        let mut stmt = self.undocumented_declaration()?;
+        if let Some(doc) = doc {
+            Self::attach_doc(&mut stmt, doc);
+        }
+        Ok(stmt)
+    }
+
+    fn doc_comment(&self) -> Option<String> {
+        let comments = &self.peek().leading_comments;
+        let start = comments
+            .iter()
+            .rposition(|comment| comment.kind != CommentKind::Doc)
+            .map_or(0, |index| index + 1);
+        let lines: Vec<String> = comments[start..]
+            .iter()
+            .filter_map(|comment| comment.doc_text())
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    fn attach_doc(stmt: &mut Stmt, doc: String) {
+        match stmt {
+            Stmt::VarDecl(decl) => decl.doc = Some(doc),
+            Stmt::FnDecl(decl) => decl.doc = Some(doc),
+            Stmt::ClassDecl(decl) => decl.doc = Some(doc),
+            Stmt::InterfaceDecl(decl) => decl.doc = Some(doc),
+            Stmt::EnumDecl(decl) => decl.doc = Some(doc),
+            Stmt::TypeAliasDecl(decl) => decl.doc = Some(doc),
+            Stmt::ExportDecl(export) => {
+                if let Some(declaration) = export.declaration.as_mut() {
+                    Self::attach_doc(declaration, doc);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn undocumented_declaration(&mut self) -> Result<Stmt, RaccoonError> {
         let mut decorators = Vec::new();
 
         while self.match_token(&[TokenType::At]) {
@@ -86,7 +156,7 @@ impl Parser {
         }
 
         if !decorators.is_empty() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Decorators can only be applied to classes and functions",
                 decorators[0].position,
                 self.file.clone(),
@@ -98,6 +168,7 @@ impl Parser {
 
     fn var_declaration(&mut self) -> Result<Stmt, RaccoonError> {
         let is_constant = self.previous().token_type == TokenType::Const;
+        let position = self.previous().position;
 
         let pattern = if self.check(&TokenType::LeftBracket) || self.check(&TokenType::LeftBrace) {
             VarPattern::Destructuring(self.parse_destructuring_pattern()?)
@@ -120,13 +191,13 @@ impl Parser {
         if self.match_token(&[TokenType::Assign]) {
             initializer = Some(self.expression()?);
         } else if is_constant {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Constants must be initialized",
                 self.previous().position,
                 self.file.clone(),
             ));
         } else if type_annotation == PrimitiveType::any() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Variable declaration must have either a type annotation or an initializer",
                 self.previous().position,
                 self.file.clone(),
@@ -140,7 +211,8 @@ impl Parser {
             type_annotation,
             initializer,
             is_constant,
-            position: self.previous().position,
+            doc: None,
+            position,
         }))
     }
 
@@ -190,10 +262,11 @@ impl Parser {
             type_parameters,
             parameters,
             return_type,
-            body,
+            body: body.into(),
             is_async,
             is_declare,
             decorators,
+            doc: None,
             position,
         }))
     }
@@ -265,9 +338,14 @@ impl Parser {
             );
         }
 
+        let mut implements = Vec::new();
         if self.match_token(&[TokenType::Implements]) {
             loop {
-                self.consume(TokenType::Identifier, "Expected interface name")?;
+                implements.push(
+                    self.consume(TokenType::Identifier, "Expected interface name")?
+                        .value
+                        .clone(),
+                );
                 if !self.match_token(&[TokenType::Comma]) {
                     break;
                 }
@@ -279,9 +357,21 @@ impl Parser {
         let mut properties = Vec::new();
         let mut methods = Vec::new();
         let mut accessors = Vec::new();
+        let mut static_blocks = Vec::new();
         let mut constructor = None;
 
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            if self.check(&TokenType::Static) && self.check_next(&[TokenType::LeftBrace]) {
+                let block_position = self.advance().position;
+                self.advance();
+                static_blocks.push(Block {
+                    statements: self.block_statements()?,
+                    position: block_position,
+                });
+                continue;
+            }
+
+            let doc = self.doc_comment();
             let mut member_decorators = Vec::new();
             while self.match_token(&[TokenType::At]) {
                 member_decorators.push(self.parse_decorator()?);
@@ -313,7 +403,7 @@ impl Parser {
 
             if self.match_token(&[TokenType::Constructor]) {
                 if constructor.is_some() {
-                    return Err(RaccoonError::new(
+                    return Err(RaccoonError::syntax_error(
                         "Class cannot have multiple constructors",
                         self.previous().position,
                         self.file.clone(),
@@ -347,14 +437,13 @@ impl Parser {
                     && (self.check(&TokenType::Get) || self.check(&TokenType::Set)))
                 && self.check_next(&[TokenType::LeftParen])
             {
-                methods.push(self.parse_method(
-                    member_decorators,
-                    access_modifier,
-                    is_static,
-                    is_async,
-                )?);
+                let method =
+                    self.parse_method(member_decorators, access_modifier, is_static, is_async)?;
+                methods.push(ClassMethod { doc, ..method });
             } else {
-                properties.push(self.parse_class_property(member_decorators, access_modifier)?);
+                let property =
+                    self.parse_class_property(member_decorators, access_modifier, is_static)?;
+                properties.push(ClassProperty { doc, ..property });
             }
         }
 
@@ -364,11 +453,14 @@ impl Parser {
             name,
             type_parameters,
             superclass,
+            implements,
             properties,
             constructor,
             methods,
             accessors,
+            static_blocks,
             decorators,
+            doc: None,
             position,
         }))
     }
@@ -377,11 +469,16 @@ impl Parser {
         &mut self,
         decorators: Vec<DecoratorDecl>,
         access_modifier: AccessModifier,
+        is_static: bool,
     ) -> Result<ClassProperty, RaccoonError> {
+        let is_readonly = self.match_token(&[TokenType::Readonly])
+            || decorators.iter().any(|decorator| decorator.name == "readonly");
+
         let name = self
             .consume(TokenType::Identifier, "Expected property name")?
             .value
             .clone();
+        let access_modifier = Self::private_name_modifier(&name, access_modifier);
 
         self.consume(TokenType::Colon, "Expected ':' after property name")?;
         let property_type = self.parse_type()?;
@@ -399,9 +496,20 @@ impl Parser {
             initializer,
             decorators,
             access_modifier,
+            is_readonly,
+            is_static,
+            doc: None,
         })
     }
 
+    fn private_name_modifier(name: &str, access_modifier: AccessModifier) -> AccessModifier {
+        if name.starts_with('#') {
+            AccessModifier::Private
+        } else {
+            access_modifier
+        }
+    }
+
     fn parse_constructor(&mut self) -> Result<ConstructorDecl, RaccoonError> {
         let position = self.previous().position;
 
@@ -414,7 +522,7 @@ impl Parser {
 
         Ok(ConstructorDecl {
             parameters,
-            body,
+            body: body.into(),
             position,
         })
     }
@@ -435,6 +543,7 @@ impl Parser {
                 .value
                 .clone()
         };
+        let access_modifier = Self::private_name_modifier(&name, access_modifier);
 
         self.consume(TokenType::LeftParen, "Expected '(' after method name")?;
         let parameters = self.function_parameters()?;
@@ -453,11 +562,12 @@ impl Parser {
             name,
             parameters,
             return_type,
-            body,
+            body: body.into(),
             is_async,
             decorators,
             access_modifier,
             is_static,
+            doc: None,
         })
     }
 
@@ -479,14 +589,14 @@ impl Parser {
         self.consume(TokenType::RightParen, "Expected ')' after parameters")?;
 
         if kind == AccessorKind::Get && !parameters.is_empty() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Getter cannot have parameters",
                 position,
                 self.file.clone(),
             ));
         }
         if kind == AccessorKind::Set && parameters.len() != 1 {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Setter must have exactly one parameter",
                 position,
                 self.file.clone(),
@@ -507,7 +617,7 @@ impl Parser {
             kind,
             parameters,
             return_type,
-            body,
+            body: body.into(),
             access_modifier,
             position,
         })
@@ -515,10 +625,13 @@ impl Parser {
 
     fn parse_decorator(&mut self) -> Result<DecoratorDecl, RaccoonError> {
         let position = self.previous().position;
-        let name = self
-            .consume(TokenType::Identifier, "Expected decorator name")?
-            .value
-            .clone();
+        let name = if self.match_token(&[TokenType::Readonly]) {
+            self.previous().value.clone()
+        } else {
+            self.consume(TokenType::Identifier, "Expected decorator name")?
+                .value
+                .clone()
+        };
 
         let mut args = Vec::new();
 
@@ -551,6 +664,7 @@ impl Parser {
         if !self.check(&TokenType::RightParen) {
             loop {
                 let is_rest = self.match_token(&[TokenType::Spread]);
+                let param_position = self.peek().position;
 
                 if self.check(&TokenType::LeftBracket) || self.check(&TokenType::LeftBrace) {
                     let pattern = VarPattern::Destructuring(self.parse_destructuring_pattern()?);
@@ -559,7 +673,7 @@ impl Parser {
 
                     let is_optional = self.match_token(&[TokenType::Question]);
                     if is_optional && is_rest {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Rest parameters cannot be optional".to_string(),
                             self.peek().position.clone(),
                             None::<String>,
@@ -568,7 +682,7 @@ impl Parser {
                     if is_optional {
                         has_optional = true;
                     } else if has_optional {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Required parameters cannot follow optional parameters".to_string(),
                             self.peek().position.clone(),
                             None::<String>,
@@ -581,6 +695,8 @@ impl Parser {
                         default_value: None,
                         is_rest,
                         is_optional,
+                        is_untyped: false,
+                        position: param_position,
                     });
                 } else {
                     let name = self
@@ -593,7 +709,7 @@ impl Parser {
                     let param_type = self.parse_type()?;
 
                     if is_optional && is_rest {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Rest parameters cannot be optional".to_string(),
                             self.peek().position.clone(),
                             None::<String>,
@@ -602,7 +718,7 @@ impl Parser {
                     if is_optional {
                         has_optional = true;
                     } else if has_optional {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Required parameters cannot follow optional parameters".to_string(),
                             self.peek().position.clone(),
                             None::<String>,
@@ -620,6 +736,8 @@ impl Parser {
                         default_value,
                         is_rest,
                         is_optional,
+                        is_untyped: false,
+                        position: param_position,
                     });
                 }
 
@@ -643,7 +761,7 @@ impl Parser {
         if self.match_token(&[TokenType::LeftBrace]) {
             return Ok(DestructuringPattern::Object(self.parse_object_pattern()?));
         }
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             "Expected destructuring pattern",
             self.peek().position,
             self.file.clone(),
@@ -802,8 +920,20 @@ impl Parser {
         self.consume(TokenType::LeftBrace, "Expected '{' after interface name")?;
 
         let mut properties = Vec::new();
+        let mut methods = Vec::new();
 
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            if self.check(&TokenType::Async) || self.interface_method_has_body() {
+                let is_async = self.match_token(&[TokenType::Async]);
+                methods.push(self.parse_method(
+                    Vec::new(),
+                    AccessModifier::Public,
+                    false,
+                    is_async,
+                )?);
+                continue;
+            }
+
             let prop_name = self
                 .consume(TokenType::Identifier, "Expected property name")?
                 .value
@@ -835,6 +965,7 @@ impl Parser {
                         params: param_types,
                         return_type,
                         is_variadic: false,
+                        param_names: Vec::new(),
                     })),
                     optional: false,
                 });
@@ -857,11 +988,38 @@ impl Parser {
             name,
             type_parameters,
             properties,
+            methods,
             extends,
+            doc: None,
             position,
         }))
     }
 
+    fn interface_method_has_body(&mut self) -> bool {
+        if !self.check(&TokenType::Identifier) || !self.check_next(&[TokenType::LeftParen]) {
+            return false;
+        }
+        let saved_pos = self.current;
+        self.advance();
+
+        let mut depth = 0;
+        while !self.is_at_end() {
+            match self.advance().token_type {
+                TokenType::LeftParen => depth += 1,
+                TokenType::RightParen => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+        let has_body = (!self.match_token(&[TokenType::Colon]) || self.parse_type().is_ok())
+            && self.check(&TokenType::LeftBrace);
+
+        self.current = saved_pos;
+        has_body
+    }
+
     fn enum_declaration(&mut self) -> Result<Stmt, RaccoonError> {
         let name = self
             .consume(TokenType::Identifier, "Expected enum name")?
@@ -899,6 +1057,7 @@ impl Parser {
         Ok(Stmt::EnumDecl(EnumDecl {
             name,
             members,
+            doc: None,
             position,
         }))
     }
@@ -918,6 +1077,7 @@ impl Parser {
         Ok(Stmt::TypeAliasDecl(TypeAliasDecl {
             name,
             alias_type,
+            doc: None,
             position,
         }))
     }
@@ -1091,6 +1251,43 @@ impl Parser {
         Ok(type_)
     }
 
+    fn parse_operator_type(&mut self) -> Result<Type, RaccoonError> {
+        let mut types = Vec::new();
+        loop {
+            let mut type_ = self.parse_primary_type()?;
+            while self.check(&TokenType::LeftBracket) && self.check_next(&[TokenType::RightBracket])
+            {
+                self.advance();
+                self.advance();
+                type_ = Type::Array(Box::new(ArrayType {
+                    element_type: type_,
+                }));
+            }
+            if self.check(&TokenType::Question) && self.question_ends_type() {
+                self.advance();
+                type_ = Type::Nullable(Box::new(NullableType { inner_type: type_ }));
+            }
+            types.push(type_);
+            if !self.match_token(&[TokenType::BitwiseOr]) {
+                break;
+            }
+        }
+        Ok(match types.len() {
+            1 => types.remove(0),
+            _ => Type::Union(Box::new(UnionType::new(types))),
+        })
+    }
+
+    fn question_ends_type(&self) -> bool {
+        match self.tokens.get(self.current + 1) {
+            Some(next) => {
+                OPERATOR_TYPE_END.contains(&next.token_type)
+                    || next.position.0 > self.peek().position.0
+            }
+            None => true,
+        }
+    }
+
     fn parse_intersection_type(&mut self) -> Result<Type, RaccoonError> {
         let mut type_ = self.parse_postfix_type()?;
 
@@ -1170,6 +1367,7 @@ impl Parser {
                 "unknown" => Some(PrimitiveType::unknown()),
                 "never" => Some(PrimitiveType::never()),
                 "func" => Some(PrimitiveType::func()),
+                "symbol" => Some(PrimitiveType::symbol()),
                 _ => None,
             };
 
@@ -1189,7 +1387,7 @@ impl Parser {
 
                 if name == "Future" {
                     if type_args.len() != 1 {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Future requires exactly one type argument",
                             self.previous().position,
                             self.file.clone(),
@@ -1202,7 +1400,7 @@ impl Parser {
 
                 if name == "Map" {
                     if type_args.len() != 2 {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Map requires exactly two type arguments",
                             self.previous().position,
                             self.file.clone(),
@@ -1252,10 +1450,11 @@ impl Parser {
                 params: param_types,
                 return_type,
                 is_variadic: false,
+                param_names: Vec::new(),
             })));
         }
 
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             "Expected type",
             self.peek().position,
             self.file.clone(),
@@ -1293,6 +1492,24 @@ impl Parser {
         if self.match_token(&[TokenType::Throw]) {
             return self.throw_statement();
         }
+        if self.check(&TokenType::Identifier)
+            && self.peek().value == "using"
+            && self.check_next(&[TokenType::Identifier])
+            && self.check_next_next(&[TokenType::Assign, TokenType::Colon])
+        {
+            return self.using_statement();
+        }
+        if self.check(&TokenType::Identifier)
+            && self.peek().value == "defer"
+            && self.check_next(&[TokenType::Identifier, TokenType::This, TokenType::Await])
+        {
+            return self.defer_statement();
+        }
+        if self.check(&TokenType::Identifier) && self.check_next(&[TokenType::LeftParen]) {
+            if let Some(kind) = AssertKind::from_name(&self.peek().value) {
+                return self.assert_statement(kind);
+            }
+        }
         if self.match_token(&[TokenType::Break]) {
             let stmt = Stmt::BreakStmt(BreakStmt {
                 position: self.previous().position,
@@ -1323,10 +1540,13 @@ impl Parser {
         while self.match_token(&[TokenType::Catch]) {
             let catch_pos = self.previous().position;
             self.consume(TokenType::LeftParen, "Expected '(' after 'catch'")?;
-            let error_var = self
-                .consume(TokenType::Identifier, "Expected error variable name")?
-                .value
-                .clone();
+            let error_var = if self.match_token(&[TokenType::Underscore]) {
+                "_".to_string()
+            } else {
+                self.consume(TokenType::Identifier, "Expected error variable name")?
+                    .value
+                    .clone()
+            };
 
             let mut error_type = None;
             if self.match_token(&[TokenType::Colon]) {
@@ -1358,7 +1578,7 @@ impl Parser {
         }
 
         if catch_clauses.is_empty() && finally_block.is_none() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Try statement must have at least one catch or finally block",
                 position,
                 self.file.clone(),
@@ -1375,18 +1595,145 @@ impl Parser {
 
     fn throw_statement(&mut self) -> Result<Stmt, RaccoonError> {
         let position = self.previous().position;
-        let value = self.expression()?;
+        let mut value = None;
+
+        if !self.check(&TokenType::Semicolon) && !self.can_insert_semicolon() {
+            value = Some(self.expression()?);
+        }
+
         self.optional_semicolon();
         Ok(Stmt::ThrowStmt(ThrowStmt { value, position }))
     }
 
+    fn using_statement(&mut self) -> Result<Stmt, RaccoonError> {
+        let position = self.advance().position;
+        if self.block_depth == 0 {
+            return Err(RaccoonError::syntax_error(
+                "'using' declarations must be inside a block",
+                position,
+                self.file.clone(),
+            ));
+        }
+        let name = self.advance().value.clone();
+
+        let mut type_annotation = PrimitiveType::any();
+        if self.match_token(&[TokenType::Colon]) {
+            type_annotation = self.parse_type()?;
+        }
+        self.consume(TokenType::Assign, "Expected '=' after 'using' name")?;
+        let initializer = self.expression()?;
+        self.optional_semicolon();
+
+        Ok(Stmt::UsingStmt(UsingStmt {
+            name,
+            type_annotation,
+            initializer,
+            body: Block {
+                statements: Vec::new(),
+                position,
+            },
+            position,
+        }))
+    }
+
+    fn defer_statement(&mut self) -> Result<Stmt, RaccoonError> {
+        let position = self.advance().position;
+        if self.block_depth == 0 {
+            return Err(RaccoonError::syntax_error(
+                "'defer' must be inside a block",
+                position,
+                self.file.clone(),
+            ));
+        }
+        let expression = self.expression()?;
+        self.optional_semicolon();
+
+        Ok(Stmt::DeferStmt(DeferStmt {
+            expression,
+            body: Block {
+                statements: Vec::new(),
+                position,
+            },
+            position,
+        }))
+    }
+
+    fn scope_resources(statements: Vec<Stmt>) -> Vec<Stmt> {
+        let mut scoped = Vec::new();
+        let mut statements = statements.into_iter();
+        while let Some(stmt) = statements.next() {
+            match stmt {
+                Stmt::UsingStmt(mut using) => {
+                    using.body.statements = Self::scope_resources(statements.collect());
+                    scoped.push(Stmt::UsingStmt(using));
+                    break;
+                }
+                Stmt::DeferStmt(mut defer) => {
+                    defer.body.statements = Self::scope_resources(statements.collect());
+                    scoped.push(Stmt::DeferStmt(defer));
+                    break;
+                }
+                stmt => scoped.push(stmt),
+            }
+        }
+        scoped
+    }
+
+    fn assert_statement(&mut self, kind: AssertKind) -> Result<Stmt, RaccoonError> {
+        let position = self.advance().position;
+        self.advance();
+
+        let start = self.current;
+        let condition = self.expression()?;
+        let source = self.source_text(start, self.current);
+        let message = if self.match_token(&[TokenType::Comma]) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.consume(TokenType::RightParen, "Expected ')' after assertion")?;
+        self.optional_semicolon();
+
+        Ok(Stmt::AssertStmt(AssertStmt {
+            kind,
+            condition,
+            message,
+            source,
+            position,
+        }))
+    }
+
+    fn source_text(&self, start: usize, end: usize) -> String {
+        let mut text = String::new();
+        let mut previous: Option<&Token> = None;
+        for token in &self.tokens[start..end] {
+            if let Some(previous) = previous {
+                let previous_end = previous.position.1 + previous.text().chars().count();
+                if token.position.0 != previous.position.0 || token.position.1 > previous_end {
+                    text.push(' ');
+                }
+            }
+            text.push_str(token.text());
+            previous = Some(token);
+        }
+        text
+    }
+
     fn block_statements(&mut self) -> Result<Vec<Stmt>, RaccoonError> {
         let mut statements = Vec::new();
+        self.block_depth += 1;
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
-            statements.push(self.declaration()?);
+            match self.declaration() {
+                Ok(stmt) => statements.push(stmt),
+                Err(error) => {
+                    self.errors.push(error);
+                    self.synchronize();
+                }
+            }
         }
+        self.block_depth -= 1;
         self.consume(TokenType::RightBrace, "Expected '}'")?;
-        Ok(statements)
+        Ok(Self::scope_resources(statements))
     }
     fn if_statement(&mut self) -> Result<Stmt, RaccoonError> {
         let position = self.previous().position;
@@ -1429,6 +1776,10 @@ impl Parser {
 
         if self.match_token(&[TokenType::Let, TokenType::Const]) {
             let is_const = self.previous().token_type == TokenType::Const;
+            if self.check(&TokenType::LeftBracket) || self.check(&TokenType::LeftBrace) {
+                let pattern = VarPattern::Destructuring(self.parse_destructuring_pattern()?);
+                return self.for_each_statement(pattern, is_const, None, position);
+            }
             let var_name = self
                 .consume(TokenType::Identifier, "Expected variable name")?
                 .value
@@ -1439,32 +1790,13 @@ impl Parser {
                 type_annotation = Some(self.parse_type()?);
             }
 
-            if self.match_token(&[TokenType::In]) {
-                let iterable = self.expression()?;
-                self.consume(TokenType::RightParen, "Expected ')' after iterable")?;
-                let body = Box::new(self.statement()?);
-                return Ok(Stmt::ForInStmt(ForInStmt {
-                    variable: var_name,
-                    is_const,
-                    type_annotation,
-                    iterable,
-                    body,
-                    position,
-                }));
-            }
-
-            if self.match_token(&[TokenType::Of]) {
-                let iterable = self.expression()?;
-                self.consume(TokenType::RightParen, "Expected ')' after iterable")?;
-                let body = Box::new(self.statement()?);
-                return Ok(Stmt::ForOfStmt(ForOfStmt {
-                    variable: var_name,
+            if self.check(&TokenType::In) || self.check(&TokenType::Of) {
+                return self.for_each_statement(
+                    VarPattern::Identifier(var_name),
                     is_const,
                     type_annotation,
-                    iterable,
-                    body,
                     position,
-                }));
+                );
             }
 
             self.consume(TokenType::Assign, "Expected '=' in variable declaration")?;
@@ -1475,6 +1807,7 @@ impl Parser {
                 type_annotation: type_annotation.unwrap_or(PrimitiveType::any()),
                 initializer: Some(init_value),
                 is_constant: is_const,
+                doc: None,
                 position,
             }));
 
@@ -1529,6 +1862,44 @@ impl Parser {
         }))
     }
 
+    fn for_each_statement(
+        &mut self,
+        pattern: VarPattern,
+        is_const: bool,
+        type_annotation: Option<Type>,
+        position: Position,
+    ) -> Result<Stmt, RaccoonError> {
+        let is_for_in = if self.match_token(&[TokenType::In]) {
+            true
+        } else {
+            self.consume(TokenType::Of, "Expected 'in' or 'of' after loop pattern")?;
+            false
+        };
+        let iterable = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after iterable")?;
+        let body = Box::new(self.statement()?);
+
+        Ok(if is_for_in {
+            Stmt::ForInStmt(ForInStmt {
+                pattern,
+                is_const,
+                type_annotation,
+                iterable,
+                body,
+                position,
+            })
+        } else {
+            Stmt::ForOfStmt(ForOfStmt {
+                pattern,
+                is_const,
+                type_annotation,
+                iterable,
+                body,
+                position,
+            })
+        })
+    }
+
     fn do_while_statement(&mut self) -> Result<Stmt, RaccoonError> {
         let position = self.previous().position;
         let body = Box::new(self.statement()?);
@@ -1547,6 +1918,41 @@ impl Parser {
 
     fn switch_statement(&mut self) -> Result<Stmt, RaccoonError> {
         let position = self.previous().position;
+        let (discriminant, cases) = self.switch_body()?;
+
+        Ok(Stmt::SwitchStmt(SwitchStmt {
+            discriminant,
+            cases,
+            position,
+        }))
+    }
+
+    fn parse_switch_expr(&mut self) -> Result<Expr, RaccoonError> {
+        let position = self.previous().position;
+        let (discriminant, cases) = self.switch_body()?;
+
+        Ok(Expr::Switch(SwitchExpr {
+            discriminant: Box::new(discriminant),
+            cases,
+            position,
+        }))
+    }
+
+    fn parse_do_expr(&mut self) -> Result<Expr, RaccoonError> {
+        let position = self.previous().position;
+        self.consume(TokenType::LeftBrace, "Expected '{' after 'do'")?;
+        let statements = self.block_statements()?;
+
+        Ok(Expr::Do(DoExpr {
+            body: Block {
+                statements,
+                position,
+            },
+            position,
+        }))
+    }
+
+    fn switch_body(&mut self) -> Result<(Expr, Vec<SwitchCase>), RaccoonError> {
         self.consume(TokenType::LeftParen, "Expected '(' after 'switch'")?;
         let discriminant = self.expression()?;
         self.consume(TokenType::RightParen, "Expected ')' after discriminant")?;
@@ -1555,52 +1961,59 @@ impl Parser {
         let mut cases = Vec::new();
 
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
-            if self.match_token(&[TokenType::Case]) {
-                let test = Some(self.expression()?);
-                self.consume(TokenType::Colon, "Expected ':' after case value")?;
+            let mut tests = Vec::new();
 
-                let mut consequent = Vec::new();
-                while !self.check(&TokenType::Case)
-                    && !self.check(&TokenType::Default)
-                    && !self.check(&TokenType::RightBrace)
-                    && !self.is_at_end()
-                {
-                    consequent.push(self.statement()?);
+            if self.match_token(&[TokenType::Case]) {
+                loop {
+                    tests.push(self.expression()?);
+                    if !self.match_token(&[TokenType::Comma]) {
+                        break;
+                    }
                 }
-
-                cases.push(SwitchCase { test, consequent });
+                self.consume(TokenType::Colon, "Expected ':' after case value")?;
             } else if self.match_token(&[TokenType::Default]) {
                 self.consume(TokenType::Colon, "Expected ':' after 'default'")?;
-
-                let mut consequent = Vec::new();
-                while !self.check(&TokenType::Case)
-                    && !self.check(&TokenType::Default)
-                    && !self.check(&TokenType::RightBrace)
-                    && !self.is_at_end()
-                {
-                    consequent.push(self.statement()?);
-                }
-
-                cases.push(SwitchCase {
-                    test: None,
-                    consequent,
-                });
             } else {
-                return Err(RaccoonError::new(
+                return Err(RaccoonError::syntax_error(
                     "Expected 'case' or 'default' in switch statement".to_string(),
                     self.peek().position,
                     self.file.clone(),
                 ));
             }
+
+            let mut consequent = Vec::new();
+            let mut fallthrough = false;
+            while !self.check(&TokenType::Case)
+                && !self.check(&TokenType::Default)
+                && !self.check(&TokenType::RightBrace)
+                && !self.is_at_end()
+            {
+                if self.match_token(&[TokenType::Fallthrough]) {
+                    let fallthrough_position = self.previous().position;
+                    self.optional_semicolon();
+                    if !self.check(&TokenType::Case) && !self.check(&TokenType::Default) {
+                        return Err(RaccoonError::syntax_error(
+                            "'fallthrough' must be the last statement of a non-final case",
+                            fallthrough_position,
+                            self.file.clone(),
+                        ));
+                    }
+                    fallthrough = true;
+                    break;
+                }
+                consequent.push(self.declaration()?);
+            }
+
+            cases.push(SwitchCase {
+                tests,
+                consequent,
+                fallthrough,
+            });
         }
 
         self.consume(TokenType::RightBrace, "Expected '}' after switch body")?;
 
-        Ok(Stmt::SwitchStmt(SwitchStmt {
-            discriminant,
-            cases,
-            position,
-        }))
+        Ok((discriminant, cases))
     }
 
     fn return_statement(&mut self) -> Result<Stmt, RaccoonError> {
@@ -1635,6 +2048,16 @@ impl Parser {
             return self.parse_match_expr();
         }
 
+        if self.check(&TokenType::Switch) {
+            self.advance();
+            return self.parse_switch_expr();
+        }
+
+        if self.check(&TokenType::Do) && self.check_next(&[TokenType::LeftBrace]) {
+            self.advance();
+            return self.parse_do_expr();
+        }
+
         if self.check(&TokenType::Class) {
             self.advance();
             return self.parse_class_expr();
@@ -1823,12 +2246,19 @@ impl Parser {
         let mut expr = self.bitwise_or()?;
 
         loop {
-            if self.match_token(&[TokenType::Lt, TokenType::Gt, TokenType::Lte, TokenType::Gte]) {
+            if self.match_token(&[
+                TokenType::Lt,
+                TokenType::Gt,
+                TokenType::Lte,
+                TokenType::Gte,
+                TokenType::In,
+            ]) {
                 let operator = match self.previous().token_type {
                     TokenType::Lt => BinaryOperator::LessThan,
                     TokenType::Gt => BinaryOperator::GreaterThan,
                     TokenType::Lte => BinaryOperator::LessEqual,
                     TokenType::Gte => BinaryOperator::GreaterEqual,
+                    TokenType::In => BinaryOperator::In,
                     _ => unreachable!(),
                 };
                 let right = Box::new(self.bitwise_or()?);
@@ -1853,6 +2283,26 @@ impl Parser {
                     type_name,
                     position,
                 });
+            } else if self.match_token(&[TokenType::As]) {
+                let position = expr.position();
+                let target_type = self.parse_operator_type()?;
+                expr = Expr::As(AsExpr {
+                    operand: Box::new(expr),
+                    target_type,
+                    position,
+                });
+            } else if self.check(&TokenType::Identifier)
+                && self.peek().value == "is"
+                && !self.previous_token_on_different_line()
+            {
+                self.advance();
+                let position = expr.position();
+                let target_type = self.parse_operator_type()?;
+                expr = Expr::Is(IsExpr {
+                    operand: Box::new(expr),
+                    target_type,
+                    position,
+                });
             } else {
                 break;
             }
@@ -1942,12 +2392,15 @@ impl Parser {
     fn range(&mut self) -> Result<Expr, RaccoonError> {
         let expr = self.term()?;
 
-        if self.match_token(&[TokenType::Range]) {
+        let is_range = self.check(&TokenType::Range) || self.check(&TokenType::RangeExclusive);
+        if is_range && !self.check_next(&[TokenType::RightBracket]) {
+            let inclusive = self.advance().token_type == TokenType::Range;
             let end = Box::new(self.term()?);
             let position = expr.position();
             return Ok(Expr::Range(RangeExpr {
                 start: Box::new(expr),
                 end,
+                inclusive,
                 position,
             }));
         }
@@ -2119,23 +2572,17 @@ impl Parser {
             self.consume(TokenType::Gt, "Expected '>' after type arguments")?;
         }
 
-        let mut args = Vec::new();
-        if self.match_token(&[TokenType::LeftParen]) {
-            if !self.check(&TokenType::RightParen) {
-                loop {
-                    args.push(self.expression()?);
-                    if !self.match_token(&[TokenType::Comma]) {
-                        break;
-                    }
-                }
-            }
-            self.consume(TokenType::RightParen, "Expected ')' after arguments")?;
-        }
+        let (args, named_args) = if self.match_token(&[TokenType::LeftParen]) {
+            self.arguments("Expected ')' after arguments")?
+        } else {
+            (Vec::new(), HashMap::new())
+        };
 
         Ok(Expr::New(NewExpr {
             class_name,
             type_args,
             args,
+            named_args,
             position,
         }))
     }
@@ -2174,20 +2621,13 @@ impl Parser {
 
                 if self.check(&TokenType::LeftParen) {
                     self.advance();
-                    let mut args = Vec::new();
-                    if !self.check(&TokenType::RightParen) {
-                        loop {
-                            args.push(self.expression()?);
-                            if !self.match_token(&[TokenType::Comma]) {
-                                break;
-                            }
-                        }
-                    }
-                    self.consume(TokenType::RightParen, "Expected ')' after method arguments")?;
+                    let (args, named_args) =
+                        self.arguments("Expected ')' after method arguments")?;
                     expr = Expr::MethodCall(MethodCallExpr {
                         object: Box::new(expr),
                         method: name,
                         args,
+                        named_args,
                         position,
                     });
                 } else {
@@ -2198,12 +2638,13 @@ impl Parser {
                     });
                 }
             } else if self.match_token(&[TokenType::LeftBracket]) {
-                let index = Box::new(self.expression()?);
-                self.consume(TokenType::RightBracket, "Expected ']' after index")?;
+                expr = self.finish_index(expr)?;
+            } else if self.match_token(&[TokenType::TemplateStrStart]) {
+                let template = self.template_literal()?;
                 let position = expr.position();
-                expr = Expr::Index(IndexExpr {
-                    object: Box::new(expr),
-                    index,
+                expr = Expr::TaggedTemplate(TaggedTemplateExpr {
+                    tag: Box::new(expr),
+                    template,
                     position,
                 });
             } else {
@@ -2214,7 +2655,64 @@ impl Parser {
         Ok(expr)
     }
 
+    fn finish_index(&mut self, object: Expr) -> Result<Expr, RaccoonError> {
+        let position = object.position();
+
+        let ranges = [TokenType::Range, TokenType::RangeExclusive];
+        let (start, end, inclusive) = if self.match_token(&ranges) {
+            let inclusive = self.previous().token_type == TokenType::Range;
+            (None, self.slice_end()?, inclusive)
+        } else {
+            match self.expression()? {
+                Expr::Range(range) => (Some(range.start), Some(range.end), range.inclusive),
+                index if self.match_token(&ranges) => {
+                    let inclusive = self.previous().token_type == TokenType::Range;
+                    (Some(Box::new(index)), self.slice_end()?, inclusive)
+                }
+                index => {
+                    self.consume(TokenType::RightBracket, "Expected ']' after index")?;
+                    return Ok(Expr::Index(IndexExpr {
+                        object: Box::new(object),
+                        index: Box::new(index),
+                        position,
+                    }));
+                }
+            }
+        };
+
+        self.consume(TokenType::RightBracket, "Expected ']' after slice")?;
+        Ok(Expr::Slice(SliceExpr {
+            object: Box::new(object),
+            start,
+            end,
+            inclusive,
+            position,
+        }))
+    }
+
+    fn slice_end(&mut self) -> Result<Option<Box<Expr>>, RaccoonError> {
+        if self.check(&TokenType::RightBracket) {
+            return Ok(None);
+        }
+        Ok(Some(Box::new(self.expression()?)))
+    }
+
     fn finish_call(&mut self, callee: Expr) -> Result<Expr, RaccoonError> {
+        let (args, named_args) = self.arguments("Expected ')' after arguments")?;
+        let position = callee.position();
+
+        Ok(Expr::Call(CallExpr {
+            callee: Box::new(callee),
+            args,
+            named_args,
+            position,
+        }))
+    }
+
+    fn arguments(
+        &mut self,
+        closing_message: &str,
+    ) -> Result<(Vec<Expr>, HashMap<String, Expr>), RaccoonError> {
         let mut args = Vec::new();
         let mut named_args = HashMap::new();
 
@@ -2226,13 +2724,19 @@ impl Parser {
                     args.push(Expr::Spread(SpreadExpr { argument, position }));
                 } else if self.check(&TokenType::Identifier) && self.check_next(&[TokenType::Colon])
                 {
-                    let name = self.advance().value.clone();
+                    let name_token = self.advance().clone();
                     self.advance();
                     let value = self.expression()?;
-                    named_args.insert(name, value);
+                    if named_args.insert(name_token.value.clone(), value).is_some() {
+                        return Err(RaccoonError::syntax_error(
+                            format!("Duplicate named argument '{}'", name_token.value),
+                            name_token.position,
+                            self.file.clone(),
+                        ));
+                    }
                 } else {
                     if !named_args.is_empty() {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Positional arguments must come before named arguments",
                             self.peek().position,
                             self.file.clone(),
@@ -2247,15 +2751,8 @@ impl Parser {
             }
         }
 
-        self.consume(TokenType::RightParen, "Expected ')' after arguments")?;
-        let position = callee.position();
-
-        Ok(Expr::Call(CallExpr {
-            callee: Box::new(callee),
-            args,
-            named_args,
-            position,
-        }))
+        self.consume(TokenType::RightParen, closing_message)?;
+        Ok((args, named_args))
     }
 
     fn primary(&mut self) -> Result<Expr, RaccoonError> {
@@ -2353,9 +2850,7 @@ impl Parser {
 
             self.current = saved_pos;
             self.advance();
-            let expr = self.expression()?;
-            self.consume(TokenType::RightParen, "Expected ')' after expression")?;
-            return Ok(expr);
+            return self.parenthesized_or_tuple();
         }
 
         if self.match_token(&[TokenType::LeftBracket]) {
@@ -2370,13 +2865,39 @@ impl Parser {
             return self.template_string();
         }
 
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             "Expected expression",
             self.peek().position,
             self.file.clone(),
         ))
     }
 
+    fn parenthesized_or_tuple(&mut self) -> Result<Expr, RaccoonError> {
+        let position = self.previous().position;
+        if self.match_token(&[TokenType::RightParen]) {
+            return Ok(Expr::TupleLiteral(TupleLiteral {
+                elements: Vec::new(),
+                position,
+            }));
+        }
+
+        let expr = self.expression()?;
+        if !self.match_token(&[TokenType::Comma]) {
+            self.consume(TokenType::RightParen, "Expected ')' after expression")?;
+            return Ok(expr);
+        }
+
+        let mut elements = vec![expr];
+        while !self.check(&TokenType::RightParen) {
+            elements.push(self.expression()?);
+            if !self.match_token(&[TokenType::Comma]) {
+                break;
+            }
+        }
+        self.consume(TokenType::RightParen, "Expected ')' after tuple elements")?;
+        Ok(Expr::TupleLiteral(TupleLiteral { elements, position }))
+    }
+
     fn array_literal(&mut self) -> Result<Expr, RaccoonError> {
         let position = self.previous().position;
         let mut elements = Vec::new();
@@ -2449,6 +2970,10 @@ impl Parser {
     }
 
     fn template_string(&mut self) -> Result<Expr, RaccoonError> {
+        Ok(Expr::TemplateStr(self.template_literal()?))
+    }
+
+    fn template_literal(&mut self) -> Result<TemplateStrExpr, RaccoonError> {
         let position = self.previous().position;
         let mut parts = Vec::new();
 
@@ -2466,7 +2991,7 @@ impl Parser {
                 )?;
                 parts.push(TemplateStrPart::Expr(expr));
             } else {
-                return Err(RaccoonError::new(
+                return Err(RaccoonError::syntax_error(
                     "Expected template string part or interpolation",
                     self.peek().position,
                     self.file.clone(),
@@ -2475,7 +3000,7 @@ impl Parser {
         }
 
         self.consume(TokenType::TemplateStrEnd, "Expected end of template string")?;
-        Ok(Expr::TemplateStr(TemplateStrExpr { parts, position }))
+        Ok(TemplateStrExpr { parts, position })
     }
 
     fn optional_semicolon(&mut self) {
@@ -2541,7 +3066,7 @@ impl Parser {
         if self.check(&token_type) {
             return Ok(self.advance());
         }
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             message,
             self.peek().position,
             self.file.clone(),
@@ -2549,10 +3074,15 @@ impl Parser {
     }
 
     fn synchronize(&mut self) {
+        let closes_block =
+            |parser: &Self| parser.block_depth > 0 && parser.check(&TokenType::RightBrace);
+        if closes_block(self) {
+            return;
+        }
         self.advance();
 
         while !self.is_at_end() {
-            if self.previous().token_type == TokenType::Semicolon {
+            if self.previous().token_type == TokenType::Semicolon || closes_block(self) {
                 return;
             }
 
@@ -2614,7 +3144,7 @@ impl Parser {
                 let name = self.advance().value.clone();
                 Ok(name)
             }
-            _ => Err(RaccoonError::new(
+            _ => Err(RaccoonError::syntax_error(
                 "Expected property name after '.'",
                 token.position,
                 self.file.clone(),
@@ -2635,7 +3165,7 @@ impl Parser {
         }
 
         if !self.match_token(&[TokenType::Arrow]) {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Expected '=>' for arrow function",
                 self.peek().position,
                 self.file.clone(),
@@ -2645,7 +3175,7 @@ impl Parser {
         let body = if self.check(&TokenType::LeftBrace) {
             self.advance();
             let stmts = self.block_statements()?;
-            ArrowFnBody::Block(stmts)
+            ArrowFnBody::Block(stmts.into())
         } else {
             let expr = self.conditional()?;
             ArrowFnBody::Expr(Box::new(expr))
@@ -2668,6 +3198,7 @@ impl Parser {
 
         let param_token = self.consume(TokenType::Identifier, "Expected parameter name")?;
         let param_name = param_token.value.clone();
+        let param_position = param_token.position;
 
         let parameter = FnParam {
             pattern: VarPattern::Identifier(param_name),
@@ -2675,10 +3206,12 @@ impl Parser {
             default_value: None,
             is_rest: false,
             is_optional: false,
+            is_untyped: true,
+            position: param_position,
         };
 
         if !self.match_token(&[TokenType::Arrow]) {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Expected '=>' for arrow function",
                 self.peek().position,
                 self.file.clone(),
@@ -2688,7 +3221,7 @@ impl Parser {
         let body = if self.check(&TokenType::LeftBrace) {
             self.advance();
             let stmts = self.block_statements()?;
-            ArrowFnBody::Block(stmts)
+            ArrowFnBody::Block(stmts.into())
         } else {
             let expr = self.assignment()?;
             ArrowFnBody::Expr(Box::new(expr))
@@ -2723,7 +3256,7 @@ impl Parser {
         };
 
         if !self.check(&TokenType::LeftBrace) {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Anonymous function must have explicit block body { ... }. Use arrow function syntax (=>) for concise expressions".to_string(),
                 self.peek().position.clone(),
                 self.file.clone(),
@@ -2732,7 +3265,7 @@ impl Parser {
 
         self.advance();
         let stmts = self.block_statements()?;
-        let body = ArrowFnBody::Block(stmts);
+        let body = ArrowFnBody::Block(stmts.into());
 
         Ok(Expr::ArrowFn(ArrowFnExpr {
             parameters,
@@ -2750,6 +3283,7 @@ impl Parser {
         if !self.check(&TokenType::RightParen) {
             loop {
                 let is_rest = self.match_token(&[TokenType::Spread]);
+                let param_position = self.peek().position;
 
                 if self.check(&TokenType::LeftBracket) || self.check(&TokenType::LeftBrace) {
                     let pattern = VarPattern::Destructuring(self.parse_destructuring_pattern()?);
@@ -2758,7 +3292,7 @@ impl Parser {
 
                     let is_optional = self.match_token(&[TokenType::Question]);
                     if is_optional && is_rest {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Rest parameters cannot be optional".to_string(),
                             self.peek().position.clone(),
                             None::<String>,
@@ -2767,7 +3301,7 @@ impl Parser {
                     if is_optional {
                         has_optional = true;
                     } else if has_optional {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Required parameters cannot follow optional parameters".to_string(),
                             self.peek().position.clone(),
                             None::<String>,
@@ -2780,6 +3314,8 @@ impl Parser {
                         default_value: None,
                         is_rest,
                         is_optional,
+                        is_untyped: false,
+                        position: param_position,
                     });
                 } else {
                     let name = self
@@ -2789,14 +3325,15 @@ impl Parser {
 
                     let is_optional = self.match_token(&[TokenType::Question]);
 
-                    let param_type = if self.match_token(&[TokenType::Colon]) {
-                        self.parse_type()?
-                    } else {
+                    let is_untyped = !self.match_token(&[TokenType::Colon]);
+                    let param_type = if is_untyped {
                         PrimitiveType::any()
+                    } else {
+                        self.parse_type()?
                     };
 
                     if is_optional && is_rest {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Rest parameters cannot be optional".to_string(),
                             self.peek().position.clone(),
                             None::<String>,
@@ -2805,7 +3342,7 @@ impl Parser {
                     if is_optional {
                         has_optional = true;
                     } else if has_optional {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Required parameters cannot follow optional parameters".to_string(),
                             self.peek().position.clone(),
                             None::<String>,
@@ -2823,6 +3360,8 @@ impl Parser {
                         default_value,
                         is_rest,
                         is_optional,
+                        is_untyped,
+                        position: param_position,
                     });
                 }
 
@@ -2898,6 +3437,7 @@ impl Parser {
                         params: param_types,
                         return_type,
                         is_variadic: false,
+                        param_names: Vec::new(),
                     }))
                 } else {
                     self.consume(TokenType::Colon, "Expected ':' after property name")?;
@@ -2922,7 +3462,7 @@ impl Parser {
                 }
 
                 if !has_separator {
-                    return Err(RaccoonError::new(
+                    return Err(RaccoonError::syntax_error(
                         "Expected ',' or ';' after property type",
                         self.peek().position,
                         self.file.clone(),
@@ -2937,7 +3477,7 @@ impl Parser {
 
     fn try_parse_call_type_arguments(&mut self) -> Result<Vec<Type>, RaccoonError> {
         if !self.match_token(&[TokenType::Lt]) {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Expected '<'",
                 self.peek().position,
                 self.file.clone(),
@@ -2966,8 +3506,8 @@ impl Parser {
         let mut arms = Vec::new();
 
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            let pos = self.peek().position;
             let pattern = self.parse_pattern()?;
-            let pos = pattern.position();
 
             self.consume(TokenType::Arrow, "Expected '=>' after pattern")?;
 
@@ -3024,9 +3564,14 @@ impl Parser {
             );
         }
 
+        let mut implements = Vec::new();
         if self.match_token(&[TokenType::Implements]) {
             loop {
-                self.consume(TokenType::Identifier, "Expected interface name")?;
+                implements.push(
+                    self.consume(TokenType::Identifier, "Expected interface name")?
+                        .value
+                        .clone(),
+                );
                 if !self.match_token(&[TokenType::Comma]) {
                     break;
                 }
@@ -3041,6 +3586,7 @@ impl Parser {
         let mut constructor = None;
 
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            let doc = self.doc_comment();
             let mut member_decorators = Vec::new();
             while self.match_token(&[TokenType::At]) {
                 member_decorators.push(self.parse_decorator()?);
@@ -3072,7 +3618,7 @@ impl Parser {
 
             if self.match_token(&[TokenType::Constructor]) {
                 if constructor.is_some() {
-                    return Err(RaccoonError::new(
+                    return Err(RaccoonError::syntax_error(
                         "Class cannot have multiple constructors".to_string(),
                         self.previous().position,
                         self.file.clone(),
@@ -3106,14 +3652,13 @@ impl Parser {
                     && (self.check(&TokenType::Get) || self.check(&TokenType::Set)))
                 && self.check_next(&[TokenType::LeftParen])
             {
-                methods.push(self.parse_method(
-                    member_decorators,
-                    access_modifier,
-                    is_static,
-                    is_async,
-                )?);
+                let method =
+                    self.parse_method(member_decorators, access_modifier, is_static, is_async)?;
+                methods.push(ClassMethod { doc, ..method });
             } else {
-                properties.push(self.parse_class_property(member_decorators, access_modifier)?);
+                let property =
+                    self.parse_class_property(member_decorators, access_modifier, is_static)?;
+                properties.push(ClassProperty { doc, ..property });
             }
         }
 
@@ -3122,6 +3667,7 @@ impl Parser {
         Ok(Expr::Class(ClassExpr {
             type_parameters: type_params,
             superclass,
+            implements,
             properties,
             constructor,
             methods,
@@ -3156,6 +3702,19 @@ impl Parser {
             return Ok(Pattern::Array(patterns));
         }
 
+        if self.match_token(&[TokenType::LeftParen]) {
+            let mut patterns = Vec::new();
+            while !self.check(&TokenType::RightParen) {
+                patterns.push(self.parse_pattern()?);
+                if !self.match_token(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(TokenType::RightParen, "Expected ')' after tuple pattern")?;
+            return Ok(Pattern::Tuple(patterns));
+        }
+
         if self.match_token(&[TokenType::LeftBrace]) {
             let mut properties = Vec::new();
 
@@ -3198,7 +3757,7 @@ impl Parser {
             return Ok(Pattern::Literal(Box::new(expr)));
         }
 
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             "Expected pattern".to_string(),
             self.peek().position,
             self.file.clone(),
@@ -3232,6 +3791,9 @@ impl Stmt {
             Stmt::ExprStmt(s) => s.position,
             Stmt::TryStmt(s) => s.position,
             Stmt::ThrowStmt(s) => s.position,
+            Stmt::AssertStmt(s) => s.position,
+            Stmt::UsingStmt(s) => s.position,
+            Stmt::DeferStmt(s) => s.position,
         }
     }
 }
@@ -3246,11 +3808,14 @@ impl Expr {
             Expr::Member(e) => e.position,
             Expr::MethodCall(e) => e.position,
             Expr::Index(e) => e.position,
+            Expr::Slice(e) => e.position,
             Expr::Await(e) => e.position,
             Expr::This(e) => e.position,
             Expr::Super(e) => e.position,
             Expr::TypeOf(e) => e.position,
             Expr::InstanceOf(e) => e.position,
+            Expr::As(e) => e.position,
+            Expr::Is(e) => e.position,
             Expr::ArrowFn(e) => e.position,
             Expr::Identifier(e) => e.position,
             Expr::Assignment(e) => e.position,
@@ -3262,6 +3827,8 @@ impl Expr {
             Expr::UnaryUpdate(e) => e.position,
             Expr::TemplateStr(e) => e.position,
             Expr::TaggedTemplate(e) => e.position,
+            Expr::Switch(e) => e.position,
+            Expr::Do(e) => e.position,
             Expr::IntLiteral(e) => e.position,
             Expr::BigIntLiteral(e) => e.position,
             Expr::FloatLiteral(e) => e.position,
@@ -3269,6 +3836,7 @@ impl Expr {
             Expr::BoolLiteral(e) => e.position,
             Expr::NullLiteral(e) => e.position,
             Expr::ArrayLiteral(e) => e.position,
+            Expr::TupleLiteral(e) => e.position,
             Expr::ObjectLiteral(e) => e.position,
             Expr::Spread(e) => e.position,
             Expr::Match(e) => e.position,
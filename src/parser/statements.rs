@@ -109,7 +109,7 @@ impl Statements {
         }
 
         if catch_clauses.is_empty() && finally_block.is_none() {
-            return Err(RaccoonError::new(
+            return Err(RaccoonError::syntax_error(
                 "Try statement must have at least one catch or finally block",
                 position,
                 state.file.clone(),
@@ -127,10 +127,10 @@ impl Statements {
     pub fn throw_statement(state: &mut ParserState) -> Result<Stmt, RaccoonError> {
         let position = state.previous().unwrap().position;
 
-        let value = Expr::Identifier(Identifier {
+        let value = Some(Expr::Identifier(Identifier {
             name: "TODO".to_string(),
             position,
-        });
+        }));
         Parser::optional_semicolon(state);
         Ok(Stmt::ThrowStmt(ThrowStmt { value, position }))
     }
@@ -216,7 +216,7 @@ impl Statements {
                 Parser::consume(state, TokenType::RightParen, "Expected ')' after iterable")?;
                 let body = Box::new(Self::statement(state)?);
                 return Ok(Stmt::ForInStmt(ForInStmt {
-                    variable: var_name,
+                    pattern: VarPattern::Identifier(var_name),
                     is_const,
                     type_annotation,
                     iterable,
@@ -233,7 +233,7 @@ impl Statements {
                 Parser::consume(state, TokenType::RightParen, "Expected ')' after iterable")?;
                 let body = Box::new(Self::statement(state)?);
                 return Ok(Stmt::ForOfStmt(ForOfStmt {
-                    variable: var_name,
+                    pattern: VarPattern::Identifier(var_name),
                     is_const,
                     type_annotation,
                     iterable,
@@ -258,6 +258,7 @@ impl Statements {
                 type_annotation: type_annotation.unwrap_or(crate::ast::types::PrimitiveType::any()),
                 initializer: Some(init_value),
                 is_constant: is_const,
+                doc: None,
                 position,
             }));
 
@@ -367,10 +368,10 @@ impl Statements {
 
         while !state.check(&TokenType::RightBrace) && !state.is_at_end() {
             if Parser::match_token(state, &[TokenType::Case]) {
-                let test = Some(Expr::Identifier(Identifier {
+                let tests = vec![Expr::Identifier(Identifier {
                     name: "TODO".to_string(),
                     position,
-                }));
+                })];
                 Parser::consume(state, TokenType::Colon, "Expected ':' after case value")?;
 
                 let mut consequent = Vec::new();
@@ -382,7 +383,11 @@ impl Statements {
                     consequent.push(Self::statement(state)?);
                 }
 
-                cases.push(SwitchCase { test, consequent });
+                cases.push(SwitchCase {
+                    tests,
+                    consequent,
+                    fallthrough: false,
+                });
             } else if Parser::match_token(state, &[TokenType::Default]) {
                 Parser::consume(state, TokenType::Colon, "Expected ':' after 'default'")?;
 
@@ -396,11 +401,12 @@ impl Statements {
                 }
 
                 cases.push(SwitchCase {
-                    test: None,
+                    tests: Vec::new(),
                     consequent,
+                    fallthrough: false,
                 });
             } else {
-                return Err(RaccoonError::new(
+                return Err(RaccoonError::syntax_error(
                     "Expected 'case' or 'default' in switch statement".to_string(),
                     state.peek().unwrap().position,
                     state.file.clone(),
@@ -106,6 +106,7 @@ impl Types {
                 "unknown" => Some(PrimitiveType::unknown()),
                 "never" => Some(PrimitiveType::never()),
                 "func" => Some(PrimitiveType::func()),
+                "symbol" => Some(PrimitiveType::symbol()),
                 _ => None,
             };
 
@@ -125,7 +126,7 @@ impl Types {
 
                 if name == "Future" {
                     if type_args.len() != 1 {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Future requires exactly one type argument",
                             state.previous().unwrap().position,
                             state.file.clone(),
@@ -138,7 +139,7 @@ impl Types {
 
                 if name == "Map" {
                     if type_args.len() != 2 {
-                        return Err(RaccoonError::new(
+                        return Err(RaccoonError::syntax_error(
                             "Map requires exactly two type arguments",
                             state.previous().unwrap().position,
                             state.file.clone(),
@@ -190,10 +191,11 @@ impl Types {
                 params: param_types,
                 return_type,
                 is_variadic: false,
+                param_names: Vec::new(),
             })));
         }
 
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             "Expected type",
             state.peek().unwrap().position,
             state.file.clone(),
@@ -268,6 +270,7 @@ impl Types {
                         params: param_types,
                         return_type,
                         is_variadic: false,
+                        param_names: Vec::new(),
                     }))
                 } else {
                     Parser::consume(state, TokenType::Colon, "Expected ':' after property name")?;
@@ -292,7 +295,7 @@ impl Types {
                 }
 
                 if !has_separator {
-                    return Err(RaccoonError::new(
+                    return Err(RaccoonError::syntax_error(
                         "Expected ',' or ';' after property type",
                         state.peek().unwrap().position,
                         state.file.clone(),
@@ -55,7 +55,7 @@ impl Parser {
         if state.check(&token_type) {
             return Ok(state.advance().unwrap());
         }
-        Err(RaccoonError::new(
+        Err(RaccoonError::syntax_error(
             message,
             state.current_position(),
             state.file.clone(),
@@ -128,7 +128,7 @@ impl Parser {
                 let name = state.advance().unwrap().value.clone();
                 Ok(name)
             }
-            _ => Err(RaccoonError::new(
+            _ => Err(RaccoonError::syntax_error(
                 "Expected property name after '.'",
                 token.position,
                 state.file.clone(),
@@ -1,3 +1,47 @@
-use crate::runtime::Environment;
+use crate::ast::types::{ArrayType, PrimitiveType, Type};
+use crate::fn_type;
+use crate::runtime::{
+    type_object::TypeKind, type_object_builder::TypeObjectBuilder, ArrayValue, Environment,
+    NativeFunctionValue, NullValue, RuntimeValue, StrValue,
+};
+use std::sync::Arc;
 
-pub fn register(_env: &mut Environment) {}
+pub fn register(env: &mut Environment) {
+    env.declare_lazy("Array", array_type);
+}
+
+fn array_type() -> RuntimeValue {
+    let array_type = TypeObjectBuilder::new(
+        Type::Array(Box::new(ArrayType {
+            element_type: PrimitiveType::any(),
+        })),
+        TypeKind::Generic {
+            name: "Array".to_string(),
+            constraints: vec![],
+        },
+    )
+    .static_method(
+        "from",
+        RuntimeValue::NativeFunction(NativeFunctionValue::new(
+            |args: Vec<RuntimeValue>| match args.first() {
+                Some(RuntimeValue::Array(list)) => RuntimeValue::Array(ArrayValue::new(
+                    list.snapshot(),
+                    list.element_type.clone(),
+                )),
+                Some(RuntimeValue::Str(s)) => RuntimeValue::Array(ArrayValue::new(
+                    s.value
+                        .chars()
+                        .map(|c| RuntimeValue::Str(StrValue::new(c.to_string())))
+                        .collect(),
+                    PrimitiveType::str(),
+                )),
+                _ => RuntimeValue::Null(NullValue::new()),
+            },
+            fn_type!(PrimitiveType::any(), PrimitiveType::any()),
+        )
+        .with_param_names(&["source"])),
+    )
+    .documentation("Array type")
+    .build();
+    RuntimeValue::Type(Arc::new(array_type))
+}
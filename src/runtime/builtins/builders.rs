@@ -2,81 +2,14 @@ use crate::ast::types::{PrimitiveType, Type};
 use crate::runtime::type_object::TypeKind;
 use crate::runtime::type_object_builder::TypeObjectBuilder;
 use crate::runtime::values::*;
-use crate::runtime::{FutureState, FutureValue};
+use crate::runtime::FutureValue;
 use std::collections::HashMap;
-
-#[derive(Clone, Copy, Debug)]
-pub enum FutureCollectionStrategy {
-    All,
-    AllSettled,
-    Race,
-    Any,
-}
-
-pub fn collect_futures(
-    futures_list: &ArrayValue,
-    strategy: FutureCollectionStrategy,
-) -> (Vec<RuntimeValue>, bool, Option<String>) {
-    let mut results = Vec::new();
-    let mut has_pending = false;
-    let mut first_error = None;
-
-    for future_value in &futures_list.elements {
-        match future_value {
-            RuntimeValue::Future(future) => {
-                let state = future.state.read().unwrap().clone();
-                match (state, strategy) {
-                    (FutureState::Resolved(value), FutureCollectionStrategy::AllSettled) => {
-                        let mut result_obj = HashMap::new();
-                        result_obj.insert(
-                            "status".to_string(),
-                            RuntimeValue::Str(StrValue::new("fulfilled".to_string())),
-                        );
-                        result_obj.insert("value".to_string(), *value);
-                        results.push(RuntimeValue::Object(ObjectValue::new(
-                            result_obj,
-                            PrimitiveType::any(),
-                        )));
-                    }
-                    (FutureState::Resolved(value), _) => {
-                        results.push(*value);
-                    }
-                    (FutureState::Rejected(error), FutureCollectionStrategy::AllSettled) => {
-                        let mut error_obj = HashMap::new();
-                        error_obj.insert(
-                            "status".to_string(),
-                            RuntimeValue::Str(StrValue::new("rejected".to_string())),
-                        );
-                        error_obj.insert(
-                            "reason".to_string(),
-                            RuntimeValue::Str(StrValue::new(error)),
-                        );
-                        results.push(RuntimeValue::Object(ObjectValue::new(
-                            error_obj,
-                            PrimitiveType::any(),
-                        )));
-                    }
-                    (FutureState::Rejected(error), _) => {
-                        if first_error.is_none() {
-                            first_error = Some(error);
-                        }
-                    }
-                    (FutureState::Pending, _) => {
-                        has_pending = true;
-                    }
-                }
-            }
-            _ => {}
-        }
-    }
-
-    (results, has_pending, first_error)
-}
+use std::sync::Arc;
 
 pub fn validate_futures_array(value: &RuntimeValue) -> Result<ArrayValue, String> {
     match value {
         RuntimeValue::Array(list) => {
-            for element in &list.elements {
+            for element in list.elements.read().unwrap().iter() {
                 if !matches!(element, RuntimeValue::Future(_)) {
                     return Err("List contains non-future elements".to_string());
                 }
@@ -119,7 +52,7 @@ impl TypeMethodBuilder {
         self
     }
 
-    pub fn build(self, env: &mut crate::runtime::Environment) {
+    pub fn build(self) -> RuntimeValue {
         let mut static_methods_map = HashMap::new();
 
         for (name, method) in self.methods {
@@ -154,7 +87,7 @@ impl TypeMethodBuilder {
             .documentation(format!("Built-in {} type", self.type_name))
             .build();
 
-        let _ = env.declare(self.type_name, RuntimeValue::Type(type_obj));
+        RuntimeValue::Type(Arc::new(type_obj))
     }
 }
 
@@ -204,7 +137,7 @@ pub fn extract_array(args: &[RuntimeValue], index: usize) -> Result<ArrayValue,
 
 pub fn extract_map(args: &[RuntimeValue], index: usize) -> Result<MapValue, String> {
     match &args.get(index) {
-        Some(RuntimeValue::Map(m)) => Ok(m.clone()),
+        Some(RuntimeValue::Map(m)) => Ok((**m).clone()),
         _ => Err(format!("Argument {} is not a map", index)),
     }
 }
@@ -19,7 +19,7 @@ macro_rules! builtin_native_fn {
 #[macro_export]
 macro_rules! register_builtin {
     ($env:expr, $name:expr, $value:expr) => {
-        let _ = $env.declare($name.to_string(), $value);
+        $env.declare_lazy($name, move || $value);
     };
 }
 
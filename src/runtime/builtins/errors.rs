@@ -0,0 +1,111 @@
+use crate::analyzer::const_eval::{self, ConstScope};
+use crate::ast::nodes::{ClassDecl, EnumDecl, FnDecl, Stmt};
+use crate::interpreter::declarations::Declarations;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::runtime::stdlib::embedded;
+use crate::runtime::{Environment, RuntimeValue};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+/// Binds `Error`, its builtin subclasses and `Ordering` from `stdlib/core.rcc`.
+pub fn register(env: &mut Environment) {
+    for decl in declarations() {
+        env.declare_lazy(decl.name.clone(), || {
+            Declarations::class_type_object(decl, HashMap::new())
+        });
+    }
+    for decl in enums() {
+        env.declare_lazy(decl.name.clone(), || {
+            let initializers = decl
+                .members
+                .iter()
+                .map(|member| {
+                    let value = member.value.as_ref()?;
+                    const_eval::fold(&Literals, value)
+                })
+                .collect();
+            Declarations::enum_value(decl, initializers)
+        });
+    }
+}
+
+/// The function `std:core` declares as `name`, such as `min`.
+pub fn function(name: &str) -> Option<RuntimeValue> {
+    let decl = functions().iter().find(|decl| decl.name == name)?;
+    Some(RuntimeValue::Function(Arc::new(
+        Declarations::function_value(decl),
+    )))
+}
+
+/// The classes `std:core` exports, parsed on first use.
+pub fn declarations() -> &'static [ClassDecl] {
+    static DECLARATIONS: OnceLock<Vec<ClassDecl>> = OnceLock::new();
+    DECLARATIONS.get_or_init(|| {
+        exports()
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::ClassDecl(decl) => Some(decl.clone()),
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+/// The enums `std:core` exports, parsed on first use.
+pub fn enums() -> &'static [EnumDecl] {
+    static ENUMS: OnceLock<Vec<EnumDecl>> = OnceLock::new();
+    ENUMS.get_or_init(|| {
+        exports()
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::EnumDecl(decl) => Some(decl.clone()),
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+/// The functions `std:core` exports, parsed on first use.
+pub fn functions() -> &'static [FnDecl] {
+    static FUNCTIONS: OnceLock<Vec<FnDecl>> = OnceLock::new();
+    FUNCTIONS.get_or_init(|| {
+        exports()
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::FnDecl(decl) => Some(decl.clone()),
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+fn exports() -> &'static [Stmt] {
+    static EXPORTS: OnceLock<Vec<Stmt>> = OnceLock::new();
+    EXPORTS.get_or_init(|| {
+        let file = Some(embedded::file_name("core"));
+        let source = embedded::source("core").unwrap_or_default().to_string();
+        let Ok(tokens) = Lexer::new(source, file.clone()).tokenize() else {
+            return Vec::new();
+        };
+        let Ok(program) = Parser::new(tokens, file).parse() else {
+            return Vec::new();
+        };
+        program
+            .stmts
+            .into_iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::ExportDecl(export) => export.declaration.map(|decl| *decl),
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+struct Literals;
+
+impl ConstScope for Literals {
+    fn constant(&self, _name: &str) -> Option<RuntimeValue> {
+        None
+    }
+}
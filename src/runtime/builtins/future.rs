@@ -0,0 +1,132 @@
+use crate::ast::types::PrimitiveType;
+use crate::runtime::{
+    ArrayValue, FutureValue, NullValue, ObjectValue, Rejection, RuntimeValue, StrValue,
+};
+use futures_util::future::{join_all, select_all, try_join_all, LocalBoxFuture};
+use futures_util::FutureExt;
+use std::collections::HashMap;
+
+pub type Combinator = fn(Vec<RuntimeValue>) -> RuntimeValue;
+
+type Outcome = Result<RuntimeValue, Rejection>;
+
+/// Entry point shared by the `Future` static methods.
+pub fn combine(args: &[RuntimeValue], name: &str, combinator: Combinator) -> RuntimeValue {
+    match args.first() {
+        None => combinator(Vec::new()),
+        Some(RuntimeValue::Array(list)) => combinator(list.snapshot()),
+        Some(_) => RuntimeValue::Future(FutureValue::new_rejected(
+            format!("Future.{} requires an array", name),
+            PrimitiveType::any(),
+        )),
+    }
+}
+
+/// Resolves with every value in order, or rejects with the first rejection to happen.
+pub fn all(elements: Vec<RuntimeValue>) -> RuntimeValue {
+    spawn(async move {
+        let values = try_join_all(elements.iter().map(settle)).await?;
+        Ok(RuntimeValue::Array(ArrayValue::new(
+            values,
+            PrimitiveType::any(),
+        )))
+    })
+}
+
+/// Settles like the first element to settle; an empty array resolves to `null`.
+pub fn race(elements: Vec<RuntimeValue>) -> RuntimeValue {
+    spawn(async move {
+        if elements.is_empty() {
+            return Ok(RuntimeValue::Null(NullValue::new()));
+        }
+        let (outcome, _, _) = select_all(elements.iter().map(settle)).await;
+        outcome
+    })
+}
+
+/// Resolves with the first element to resolve, or rejects with every reason.
+pub fn any(elements: Vec<RuntimeValue>) -> RuntimeValue {
+    spawn(async move {
+        let mut reasons = vec![String::new(); elements.len()];
+        let mut pending: Vec<_> = elements
+            .iter()
+            .enumerate()
+            .map(|(index, element)| settle(element).map(move |outcome| (index, outcome)))
+            .collect();
+        while !pending.is_empty() {
+            let ((index, outcome), _, rest) = select_all(pending).await;
+            match outcome {
+                Ok(value) => return Ok(value),
+                Err(reason) => reasons[index] = reason.to_string(),
+            }
+            pending = rest;
+        }
+        Err(aggregate_error(&reasons).into())
+    })
+}
+
+fn aggregate_error(reasons: &[String]) -> String {
+    if reasons.is_empty() {
+        return "AggregateError: All futures were rejected".to_string();
+    }
+    let listed: Vec<String> = reasons
+        .iter()
+        .enumerate()
+        .map(|(index, reason)| format!("  [{}] {}", index, reason))
+        .collect();
+    format!(
+        "AggregateError: All {} futures were rejected\n{}",
+        reasons.len(),
+        listed.join("\n")
+    )
+}
+
+/// Resolves once every element has settled, with a status object for each.
+pub fn all_settled(elements: Vec<RuntimeValue>) -> RuntimeValue {
+    spawn(async move {
+        let outcomes = join_all(elements.iter().map(settle)).await;
+        let reports = outcomes.into_iter().map(settlement_report).collect();
+        Ok(RuntimeValue::Array(ArrayValue::new(
+            reports,
+            PrimitiveType::any(),
+        )))
+    })
+}
+
+/// Waits for `value` if it is a future; any other value counts as already resolved.
+pub fn settle(value: &RuntimeValue) -> LocalBoxFuture<'static, Outcome> {
+    match value {
+        RuntimeValue::Future(future) => {
+            let future = future.clone();
+            async move { future.wait_for_completion().await }.boxed_local()
+        }
+        other => futures_util::future::ready(Ok(other.clone())).boxed_local(),
+    }
+}
+
+fn settlement_report(outcome: Outcome) -> RuntimeValue {
+    let mut properties = HashMap::new();
+    let (status, key, payload) = match outcome {
+        Ok(value) => ("fulfilled", "value", value),
+        Err(reason) => ("rejected", "reason", reason.value()),
+    };
+    properties.insert(
+        "status".to_string(),
+        RuntimeValue::Str(StrValue::new(status.to_string())),
+    );
+    properties.insert(key.to_string(), payload);
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+/// Returns a pending future that `task` settles from the current `LocalSet`.
+pub fn spawn(task: impl std::future::Future<Output = Outcome> + 'static) -> RuntimeValue {
+    let result = FutureValue::new(PrimitiveType::any());
+    let settled = result.clone();
+    tokio::task::spawn_local(async move {
+        match task.await {
+            Ok(value) => settled.resolve(value),
+            Err(error) => settled.reject(error),
+        }
+    });
+    RuntimeValue::Future(result)
+}
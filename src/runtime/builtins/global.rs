@@ -1,14 +1,47 @@
 use crate::ast::types::{FunctionType, PrimitiveType, Type};
 use crate::fn_type;
-use crate::runtime::{Environment, IntValue, NullValue, RuntimeValue, StrValue};
-use std::io::{self, Write};
+use crate::runtime::{
+    ArrayValue, Environment, IntValue, NullValue, ObjectValue, ReadResult, RuntimeValue, StrValue,
+};
+use std::collections::HashMap;
+use std::sync::RwLock;
 
 pub fn register(env: &mut Environment) {
-    let _ = env.declare("print".to_string(), print_fn());
-    let _ = env.declare("println".to_string(), println_fn());
-    let _ = env.declare("eprint".to_string(), eprint_fn());
-    let _ = env.declare("input".to_string(), input_fn());
-    let _ = env.declare("len".to_string(), len_fn());
+    env.declare_lazy("print", print_fn);
+    env.declare_lazy("println", println_fn);
+    env.declare_lazy("eprint", eprint_fn);
+    env.declare_lazy("input", input_fn);
+    env.declare_lazy("len", len_fn);
+}
+
+static ARGV: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Sets `process.argv`: the script path followed by its arguments.
+pub fn set_argv(argv: Vec<String>) {
+    *ARGV.write().unwrap() = argv;
+}
+
+/// Globals that resolve only when no binding of that name exists.
+pub fn fallback(name: &str) -> Option<RuntimeValue> {
+    match name {
+        "process" => Some(process_object()),
+        _ => super::errors::function(name),
+    }
+}
+
+fn process_object() -> RuntimeValue {
+    let argv = ARGV
+        .read()
+        .unwrap()
+        .iter()
+        .map(|arg| RuntimeValue::Str(StrValue::new(arg.clone())))
+        .collect();
+    let mut properties = HashMap::new();
+    properties.insert(
+        "argv".to_string(),
+        RuntimeValue::Array(ArrayValue::new(argv, PrimitiveType::str())),
+    );
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
 }
 
 fn print_fn() -> RuntimeValue {
@@ -75,28 +108,25 @@ fn input_fn() -> RuntimeValue {
                 false
             };
 
-            if !prompt.is_empty() {
-                if breakline {
-                    println!("{}", prompt);
-                } else {
-                    print!("{}", prompt);
-                    io::stdout().flush().unwrap();
-                }
-            }
+            let prompt = if breakline {
+                println!("{}", prompt);
+                String::new()
+            } else {
+                prompt
+            };
 
-            let mut input = String::new();
-            match io::stdin().read_line(&mut input) {
-                Ok(_) => {
-                    let trimmed = input.trim_end_matches(&['\r', '\n'][..]).to_string();
-                    RuntimeValue::Str(StrValue::new(trimmed))
-                }
-                Err(_) => RuntimeValue::Null(NullValue::new()),
+            let mut editor = crate::runtime::shared_editor().lock().unwrap();
+            match editor.read_line(&prompt) {
+                Ok(ReadResult::Line(line)) => RuntimeValue::Str(StrValue::new(line)),
+                Ok(ReadResult::Eof) => RuntimeValue::Str(StrValue::new(String::new())),
+                Ok(ReadResult::Interrupted) | Err(_) => RuntimeValue::Null(NullValue::new()),
             }
         },
         Type::Function(Box::new(FunctionType {
             params: vec![PrimitiveType::str(), PrimitiveType::bool()],
             return_type: PrimitiveType::str(),
             is_variadic: false,
+            param_names: vec!["prompt".to_string(), "breakline".to_string()],
         })),
     ))
 }
@@ -110,11 +140,14 @@ fn len_fn() -> RuntimeValue {
 
             match &args[0] {
                 RuntimeValue::Str(s) => RuntimeValue::Int(IntValue::new(s.value.len() as i64)),
-                RuntimeValue::Array(a) => RuntimeValue::Int(IntValue::new(a.elements.len() as i64)),
-                RuntimeValue::Map(m) => RuntimeValue::Int(IntValue::new(m.entries.len() as i64)),
+                RuntimeValue::Array(a) => RuntimeValue::Int(IntValue::new(a.len() as i64)),
+                RuntimeValue::Tuple(t) => RuntimeValue::Int(IntValue::new(t.elements.len() as i64)),
+                RuntimeValue::Range(r) => RuntimeValue::Int(IntValue::new(r.len() as i64)),
+                RuntimeValue::Map(m) => RuntimeValue::Int(IntValue::new(m.len() as i64)),
                 _ => RuntimeValue::Int(IntValue::new(0)),
             }
         },
         fn_type!(PrimitiveType::any(), PrimitiveType::int()),
-    ))
+    )
+    .with_param_names(&["value"]))
 }
@@ -7,6 +7,7 @@ macro_rules! fn_type {
                 params: vec![],
                 return_type: $crate::ast::types::PrimitiveType::void(),
                 is_variadic: false,
+                param_names: Vec::new(),
             }
         ))
     };
@@ -18,6 +19,7 @@ macro_rules! fn_type {
                 params: vec![],
                 return_type: $return,
                 is_variadic: false,
+                param_names: Vec::new(),
             }
         ))
     };
@@ -29,28 +31,31 @@ macro_rules! fn_type {
                 params: vec![],
                 return_type: $return,
                 is_variadic: true,
+                param_names: Vec::new(),
             }
         ))
     };
 
 
-    ($param:expr, $return:expr) => {
+    ([$($param:expr),+], $return:expr) => {
         $crate::ast::types::Type::Function(Box::new(
             $crate::ast::types::FunctionType {
-                params: vec![$param],
+                params: vec![$($param),+],
                 return_type: $return,
                 is_variadic: false,
+                param_names: Vec::new(),
             }
         ))
     };
 
 
-    ([$($param:expr),+], $return:expr) => {
+    ($param:expr, $return:expr) => {
         $crate::ast::types::Type::Function(Box::new(
             $crate::ast::types::FunctionType {
-                params: vec![$($param),+],
+                params: vec![$param],
                 return_type: $return,
                 is_variadic: false,
+                param_names: Vec::new(),
             }
         ))
     };
@@ -90,12 +95,11 @@ macro_rules! list_value {
 #[macro_export]
 macro_rules! declare_builtin {
     ($env:expr, $name:expr, $impl:expr, $type:expr) => {
-        let _ = $env.declare(
-            $name.to_string(),
+        $env.declare_lazy($name, move || {
             $crate::runtime::RuntimeValue::NativeFunction(
                 $crate::runtime::NativeFunctionValue::new($impl, $type),
-            ),
-        );
+            )
+        });
     };
 }
 
@@ -129,10 +133,7 @@ macro_rules! native_functions {
 
     ($env:expr, $( $name:expr => $body:expr ),* $(,)?) => {
         $(
-            let _ = $env.declare(
-                $name.to_string(),
-                $body,
-            );
+            $env.declare_lazy($name, move || $body);
         )*
     };
 }
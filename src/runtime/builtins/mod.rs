@@ -1,16 +1,19 @@
 pub mod array;
 pub mod builders;
 pub mod builtin_macros;
+pub mod errors;
+pub mod future;
 pub mod global;
 pub mod macros;
 pub mod objects;
 pub mod primitives;
+pub mod string_builder;
 
 pub use builders::{
-    check_arg_count, check_arg_count_range, collect_futures, error_future, extract_array,
-    extract_int, extract_map, extract_string, resolved_future, validate_futures_array,
-    FutureCollectionStrategy, TypeMethodBuilder,
+    check_arg_count, check_arg_count_range, error_future, extract_array, extract_int, extract_map,
+    extract_string, resolved_future, validate_futures_array, TypeMethodBuilder,
 };
+pub use string_builder::StringBuilderValue;
 
 use crate::runtime::Environment;
 
@@ -19,4 +22,5 @@ pub fn setup_builtins(env: &mut Environment) {
     primitives::register(env);
     objects::register(env);
     array::register(env);
+    errors::register(env);
 }
@@ -1,17 +1,24 @@
-use super::builders::{collect_futures, FutureCollectionStrategy, TypeMethodBuilder};
+use super::builders::TypeMethodBuilder;
+use super::future;
 use crate::ast::types::PrimitiveType;
 use crate::fn_type;
+use crate::runtime::type_object::TypeKind;
+use crate::runtime::type_object_builder::TypeObjectBuilder;
 use crate::runtime::{
-    ArrayValue, Environment, FutureState, FutureValue, NullValue, RuntimeValue, StrValue,
+    ArrayValue, BoolValue, Environment, FutureValue, NativeFunctionValue, NullValue, Rejection,
+    RuntimeValue, StrValue, SymbolValue,
 };
+use std::sync::Arc;
 
 pub fn register(env: &mut Environment) {
-    register_future(env);
-    register_object(env);
+    env.declare_lazy("Future", future_type);
+    env.declare_lazy("Object", object_type);
+    env.declare_lazy("Symbol", symbol_type);
+    env.declare_lazy("reflect", reflect_type);
     register_type(env);
 }
 
-fn register_future(env: &mut Environment) {
+fn future_type() -> RuntimeValue {
     let mut builder = TypeMethodBuilder::new("Future");
 
     builder.add_method(
@@ -32,10 +39,9 @@ fn register_future(env: &mut Environment) {
         "reject",
         fn_type!(PrimitiveType::any(), PrimitiveType::any()),
         |args: Vec<RuntimeValue>| {
-            let error = if args.is_empty() {
-                "Unknown error".to_string()
-            } else {
-                args[0].to_string()
+            let error = match args.into_iter().next() {
+                Some(reason) => Rejection::Thrown(Box::new(reason)),
+                None => Rejection::Message("Unknown error".to_string()),
             };
             RuntimeValue::Future(FutureValue::new_rejected(error, PrimitiveType::any()))
         },
@@ -44,231 +50,42 @@ fn register_future(env: &mut Environment) {
     builder.add_method(
         "all",
         fn_type!(PrimitiveType::any(), PrimitiveType::any()),
-        |args: Vec<RuntimeValue>| {
-            if args.is_empty() {
-                return RuntimeValue::Future(FutureValue::new_resolved(
-                    RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::any())),
-                    PrimitiveType::any(),
-                ));
-            }
-
-            let futures_list = match &args[0] {
-                RuntimeValue::Array(list) => list.clone(),
-                _ => {
-                    return RuntimeValue::Future(FutureValue::new_rejected(
-                        "Future.all() requires a list of futures".to_string(),
-                        PrimitiveType::any(),
-                    ))
-                }
-            };
-
-            let (results, has_pending, first_error) =
-                collect_futures(&futures_list, FutureCollectionStrategy::All);
-
-            if has_pending {
-                RuntimeValue::Future(FutureValue::new_rejected(
-                    "Cannot call Future.all() on pending futures. Use 'await' on all futures first."
-                        .to_string(),
-                    PrimitiveType::any(),
-                ))
-            } else if let Some(error) = first_error {
-                RuntimeValue::Future(FutureValue::new_rejected(error, PrimitiveType::any()))
-            } else {
-                RuntimeValue::Future(FutureValue::new_resolved(
-                    RuntimeValue::Array(ArrayValue::new(results, PrimitiveType::any())),
-                    PrimitiveType::any(),
-                ))
-            }
-        },
+        |args: Vec<RuntimeValue>| future::combine(&args, "all", future::all),
     );
 
     builder.add_method(
         "race",
         fn_type!(PrimitiveType::any(), PrimitiveType::any()),
-        |args: Vec<RuntimeValue>| {
-            if args.is_empty() {
-                return RuntimeValue::Future(FutureValue::new_rejected(
-                    "Future.race() requires a list of futures".to_string(),
-                    PrimitiveType::any(),
-                ));
-            }
-
-            let futures_list = match &args[0] {
-                RuntimeValue::Array(list) => list.clone(),
-                _ => {
-                    return RuntimeValue::Future(FutureValue::new_rejected(
-                        "Future.race() requires a list of futures".to_string(),
-                        PrimitiveType::any(),
-                    ))
-                }
-            };
-
-            if futures_list.elements.is_empty() {
-                return RuntimeValue::Future(FutureValue::new_rejected(
-                    "Future.race() requires at least one future".to_string(),
-                    PrimitiveType::any(),
-                ));
-            }
-
-            for future_value in &futures_list.elements {
-                match future_value {
-                    RuntimeValue::Future(future) => {
-                        let state = future.state.read().unwrap().clone();
-                        match state {
-                            FutureState::Resolved(value) => {
-                                return RuntimeValue::Future(FutureValue::new_resolved(
-                                    *value,
-                                    PrimitiveType::any(),
-                                ));
-                            }
-                            FutureState::Rejected(error) => {
-                                return RuntimeValue::Future(FutureValue::new_rejected(
-                                    error,
-                                    PrimitiveType::any(),
-                                ));
-                            }
-                            FutureState::Pending => {}
-                        }
-                    }
-                    _ => {
-                        return RuntimeValue::Future(FutureValue::new_rejected(
-                            "Future.race() requires a list of futures".to_string(),
-                            PrimitiveType::any(),
-                        ))
-                    }
-                }
-            }
-
-            RuntimeValue::Future(FutureValue::new_rejected(
-                "Cannot call Future.race() when all futures are pending. Use 'await' on at least one future first.".to_string(),
-                PrimitiveType::any(),
-            ))
-        },
+        |args: Vec<RuntimeValue>| future::combine(&args, "race", future::race),
     );
 
     builder.add_method(
         "allSettled",
         fn_type!(PrimitiveType::any(), PrimitiveType::any()),
-        |args: Vec<RuntimeValue>| {
-            if args.is_empty() {
-                return RuntimeValue::Future(FutureValue::new_resolved(
-                    RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::any())),
-                    PrimitiveType::any(),
-                ));
-            }
-
-            let futures_list = match &args[0] {
-                RuntimeValue::Array(list) => list.clone(),
-                _ => {
-                    return RuntimeValue::Future(FutureValue::new_rejected(
-                        "Future.allSettled() requires a list of futures".to_string(),
-                        PrimitiveType::any(),
-                    ))
-                }
-            };
-
-            let (results, has_pending, _) =
-                collect_futures(&futures_list, FutureCollectionStrategy::AllSettled);
-
-            if has_pending {
-                RuntimeValue::Future(FutureValue::new_rejected(
-                    "Cannot call Future.allSettled() on pending futures. Use 'await' on all futures first.".to_string(),
-                    PrimitiveType::any(),
-                ))
-            } else {
-                RuntimeValue::Future(FutureValue::new_resolved(
-                    RuntimeValue::Array(ArrayValue::new(results, PrimitiveType::any())),
-                    PrimitiveType::any(),
-                ))
-            }
-        },
+        |args: Vec<RuntimeValue>| future::combine(&args, "allSettled", future::all_settled),
     );
 
     builder.add_method(
         "any",
         fn_type!(PrimitiveType::any(), PrimitiveType::any()),
-        |args: Vec<RuntimeValue>| {
-            if args.is_empty() {
-                return RuntimeValue::Future(FutureValue::new_rejected(
-                    "Future.any() requires a list of futures".to_string(),
-                    PrimitiveType::any(),
-                ));
-            }
-
-            let futures_list = match &args[0] {
-                RuntimeValue::Array(list) => list.clone(),
-                _ => {
-                    return RuntimeValue::Future(FutureValue::new_rejected(
-                        "Future.any() requires a list of futures".to_string(),
-                        PrimitiveType::any(),
-                    ))
-                }
-            };
-
-            if futures_list.elements.is_empty() {
-                return RuntimeValue::Future(FutureValue::new_rejected(
-                    "Future.any() requires at least one future".to_string(),
-                    PrimitiveType::any(),
-                ));
-            }
-
-            let (results, has_pending, _) =
-                collect_futures(&futures_list, FutureCollectionStrategy::Any);
-
-            if !results.is_empty() {
-                RuntimeValue::Future(FutureValue::new_resolved(
-                    results[0].clone(),
-                    PrimitiveType::any(),
-                ))
-            } else if has_pending {
-                RuntimeValue::Future(FutureValue::new_rejected(
-                    "Cannot call Future.any() when all resolved futures are rejected and some are pending. Use 'await' on futures first.".to_string(),
-                    PrimitiveType::any(),
-                ))
-            } else {
-                RuntimeValue::Future(FutureValue::new_rejected(
-                    "All futures were rejected".to_string(),
-                    PrimitiveType::any(),
-                ))
-            }
-        },
+        |args: Vec<RuntimeValue>| future::combine(&args, "any", future::any),
     );
 
-    builder.build(env);
+    builder.build()
 }
 
-fn register_object(env: &mut Environment) {
+fn object_type() -> RuntimeValue {
     let mut builder = TypeMethodBuilder::new("Object");
 
     builder.add_method(
         "keys",
         fn_type!(PrimitiveType::any(), PrimitiveType::any()),
         |args: Vec<RuntimeValue>| {
-            if args.is_empty() {
-                return RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::str()));
-            }
-
-            match &args[0] {
-                RuntimeValue::Object(obj) => {
-                    let keys: Vec<RuntimeValue> = obj
-                        .properties
-                        .keys()
-                        .map(|k| RuntimeValue::Str(StrValue::new(k.clone())))
-                        .collect();
-                    RuntimeValue::Array(ArrayValue::new(keys, PrimitiveType::str()))
-                }
-                RuntimeValue::ClassInstance(instance) => {
-                    let keys: Vec<RuntimeValue> = instance
-                        .properties
-                        .read()
-                        .unwrap()
-                        .keys()
-                        .map(|k| RuntimeValue::Str(StrValue::new(k.clone())))
-                        .collect();
-                    RuntimeValue::Array(ArrayValue::new(keys, PrimitiveType::str()))
-                }
-                _ => RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::str())),
-            }
+            let keys = own_properties(args.first(), false)
+                .into_iter()
+                .map(|(key, _)| RuntimeValue::Str(StrValue::new(key)))
+                .collect();
+            RuntimeValue::Array(ArrayValue::new(keys, PrimitiveType::str()))
         },
     );
 
@@ -276,27 +93,53 @@ fn register_object(env: &mut Environment) {
         "values",
         fn_type!(PrimitiveType::any(), PrimitiveType::any()),
         |args: Vec<RuntimeValue>| {
-            if args.is_empty() {
-                return RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::any()));
-            }
+            let values = own_properties(args.first(), false)
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect();
+            RuntimeValue::Array(ArrayValue::new(values, PrimitiveType::any()))
+        },
+    );
 
-            match &args[0] {
-                RuntimeValue::Object(obj) => {
-                    let values: Vec<RuntimeValue> = obj.properties.values().cloned().collect();
-                    RuntimeValue::Array(ArrayValue::new(values, PrimitiveType::any()))
-                }
-                RuntimeValue::ClassInstance(instance) => {
-                    let values: Vec<RuntimeValue> = instance
-                        .properties
-                        .read()
-                        .unwrap()
-                        .values()
-                        .cloned()
-                        .collect();
-                    RuntimeValue::Array(ArrayValue::new(values, PrimitiveType::any()))
-                }
-                _ => RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::any())),
+    builder.add_method(
+        "freeze",
+        fn_type!(PrimitiveType::any(), PrimitiveType::any()),
+        |args: Vec<RuntimeValue>| match args.into_iter().next() {
+            Some(RuntimeValue::Object(obj)) => {
+                obj.freeze();
+                RuntimeValue::Object(obj)
+            }
+            Some(RuntimeValue::ClassInstance(instance)) => {
+                instance.freeze();
+                RuntimeValue::ClassInstance(instance)
             }
+            Some(value) => value,
+            None => RuntimeValue::Null(NullValue::new()),
+        },
+    );
+
+    builder.add_method(
+        "isFrozen",
+        fn_type!(PrimitiveType::any(), PrimitiveType::bool()),
+        |args: Vec<RuntimeValue>| {
+            let frozen = match args.first() {
+                Some(RuntimeValue::Object(obj)) => obj.is_frozen(),
+                Some(RuntimeValue::ClassInstance(instance)) => instance.is_frozen(),
+                _ => false,
+            };
+            RuntimeValue::Bool(BoolValue::new(frozen))
+        },
+    );
+
+    builder.add_method(
+        "deepEquals",
+        fn_type!(PrimitiveType::any(), PrimitiveType::bool()),
+        |args: Vec<RuntimeValue>| {
+            let equal = match (args.first(), args.get(1)) {
+                (Some(left), Some(right)) => left.deep_equals(right),
+                _ => false,
+            };
+            RuntimeValue::Bool(BoolValue::new(equal))
         },
     );
 
@@ -304,41 +147,117 @@ fn register_object(env: &mut Environment) {
         "entries",
         fn_type!(PrimitiveType::any(), PrimitiveType::any()),
         |args: Vec<RuntimeValue>| {
-            if args.is_empty() {
-                return RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::any()));
-            }
+            let entries = own_properties(args.first(), false)
+                .into_iter()
+                .map(|(key, value)| {
+                    let pair = vec![RuntimeValue::Str(StrValue::new(key)), value];
+                    RuntimeValue::Array(ArrayValue::new(pair, PrimitiveType::any()))
+                })
+                .collect();
+            RuntimeValue::Array(ArrayValue::new(entries, PrimitiveType::any()))
+        },
+    );
 
-            match &args[0] {
-                RuntimeValue::Object(obj) => {
-                    let entries: Vec<RuntimeValue> = obj
-                        .properties
-                        .iter()
-                        .map(|(k, v)| {
-                            let pair = vec![RuntimeValue::Str(StrValue::new(k.clone())), v.clone()];
-                            RuntimeValue::Array(ArrayValue::new(pair, PrimitiveType::any()))
-                        })
-                        .collect();
-                    RuntimeValue::Array(ArrayValue::new(entries, PrimitiveType::any()))
-                }
-                RuntimeValue::ClassInstance(instance) => {
-                    let entries: Vec<RuntimeValue> = instance
-                        .properties
-                        .read()
-                        .unwrap()
-                        .iter()
-                        .map(|(k, v)| {
-                            let pair = vec![RuntimeValue::Str(StrValue::new(k.clone())), v.clone()];
-                            RuntimeValue::Array(ArrayValue::new(pair, PrimitiveType::any()))
-                        })
-                        .collect();
-                    RuntimeValue::Array(ArrayValue::new(entries, PrimitiveType::any()))
-                }
-                _ => RuntimeValue::Array(ArrayValue::new(vec![], PrimitiveType::any())),
-            }
+    builder.add_method(
+        "getOwnPropertySymbols",
+        fn_type!(PrimitiveType::any(), PrimitiveType::any()),
+        |args: Vec<RuntimeValue>| {
+            let symbols = own_properties(args.first(), true)
+                .into_iter()
+                .filter_map(|(key, _)| SymbolValue::from_property_key(&key))
+                .map(RuntimeValue::Symbol)
+                .collect();
+            RuntimeValue::Array(ArrayValue::new(symbols, PrimitiveType::symbol()))
+        },
+    );
+
+    builder.build()
+}
+
+fn own_properties(value: Option<&RuntimeValue>, symbols: bool) -> Vec<(String, RuntimeValue)> {
+    let properties: Vec<(String, RuntimeValue)> = match value {
+        Some(RuntimeValue::Object(obj)) => obj.snapshot().into_iter().collect(),
+        Some(RuntimeValue::ClassInstance(instance)) => instance
+            .properties
+            .read()
+            .unwrap()
+            .clone()
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    };
+    properties
+        .into_iter()
+        .filter(|(key, _)| SymbolValue::is_property_key(key) == symbols)
+        .collect()
+}
+
+fn symbol_type() -> RuntimeValue {
+    let create = NativeFunctionValue::new(
+        |args: Vec<RuntimeValue>| {
+            let description = match args.first() {
+                None | Some(RuntimeValue::Null(_)) => None,
+                Some(value) => Some(value.to_string()),
+            };
+            RuntimeValue::Symbol(SymbolValue::new(description))
         },
+        fn_type!(variadic, PrimitiveType::symbol()),
     );
 
-    builder.build(env);
+    let symbol_type = TypeObjectBuilder::new(
+        PrimitiveType::symbol(),
+        TypeKind::Module {
+            name: "Symbol".to_string(),
+        },
+    )
+    .constructor(RuntimeValue::NativeFunction(create))
+    .static_method(
+        "for",
+        RuntimeValue::NativeFunction(
+            NativeFunctionValue::new(
+                |args: Vec<RuntimeValue>| {
+                    let key = args
+                        .first()
+                        .map(RuntimeValue::to_string)
+                        .unwrap_or_default();
+                    RuntimeValue::Symbol(SymbolValue::registered(&key))
+                },
+                fn_type!(PrimitiveType::str(), PrimitiveType::symbol()),
+            )
+            .with_param_names(&["key"]),
+        ),
+    )
+    .static_method(
+        "keyFor",
+        RuntimeValue::NativeFunction(
+            NativeFunctionValue::new(
+                |args: Vec<RuntimeValue>| match args.first() {
+                    Some(RuntimeValue::Symbol(symbol)) => match symbol.registered_key() {
+                        Some(key) => RuntimeValue::Str(StrValue::new(key)),
+                        None => RuntimeValue::Null(NullValue::new()),
+                    },
+                    _ => RuntimeValue::Null(NullValue::new()),
+                },
+                fn_type!(PrimitiveType::symbol(), PrimitiveType::any()),
+            )
+            .with_param_names(&["symbol"]),
+        ),
+    )
+    .documentation("Built-in Symbol type")
+    .build();
+    RuntimeValue::Type(Arc::new(symbol_type))
+}
+
+fn reflect_type() -> RuntimeValue {
+    let reflect_type = TypeObjectBuilder::new(
+        PrimitiveType::any(),
+        TypeKind::Module {
+            name: "reflect".to_string(),
+        },
+    )
+    .documentation("Runtime type and member introspection")
+    .build();
+    RuntimeValue::Type(Arc::new(reflect_type))
 }
 
 fn register_type(_env: &mut Environment) {}
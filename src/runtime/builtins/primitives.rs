@@ -1,47 +1,53 @@
 use crate::ast::types::PrimitiveType;
 use crate::fn_type;
+use crate::runtime::types::primitives::{Float64Type, IntType};
+use crate::runtime::types::TypeHandler;
 use crate::runtime::{
     type_object::{PrimitiveKind, TypeKind},
     type_object_builder::TypeObjectBuilder,
     Environment, FloatValue, IntValue, NativeFunctionValue, NullValue, RuntimeValue, StrValue,
 };
+use std::sync::Arc;
+
+fn parse_with(handler: &dyn TypeHandler, args: Vec<RuntimeValue>) -> RuntimeValue {
+    handler
+        .call_static_method("parse", args, (0, 0), None)
+        .unwrap_or_else(|_| RuntimeValue::Null(NullValue::new()))
+}
 
 pub fn register(env: &mut Environment) {
-    register_int(env);
-    register_str(env);
-    register_bool(env);
-    register_float(env);
+    env.declare_lazy("int", int_type);
+    env.declare_lazy("str", str_type);
+    env.declare_lazy("bool", bool_type);
+    env.declare_lazy("float", float_type);
 }
 
-fn register_int(env: &mut Environment) {
+fn int_type() -> RuntimeValue {
     let int_type = TypeObjectBuilder::new(
         PrimitiveType::int(),
         TypeKind::Primitive(PrimitiveKind::Int),
     )
     .static_method(
         "parse",
-        RuntimeValue::NativeFunction(NativeFunctionValue::new(
-            |args: Vec<RuntimeValue>| {
-                if args.is_empty() {
-                    return RuntimeValue::Null(NullValue::new());
-                }
-                let s = args[0].to_string();
-                match s.parse::<i64>() {
-                    Ok(n) => RuntimeValue::Int(IntValue::new(n)),
-                    Err(_) => RuntimeValue::Null(NullValue::new()),
-                }
-            },
-            fn_type!(PrimitiveType::str(), PrimitiveType::int()),
-        )),
+        RuntimeValue::NativeFunction(
+            NativeFunctionValue::new(
+                |args| parse_with(&IntType, args),
+                fn_type!(
+                    [PrimitiveType::str(), PrimitiveType::int()],
+                    PrimitiveType::int()
+                ),
+            )
+            .with_param_names(&["value", "radix"]),
+        ),
     )
     .static_property("MAX_VALUE", RuntimeValue::Int(IntValue::new(i64::MAX)))
     .static_property("MIN_VALUE", RuntimeValue::Int(IntValue::new(i64::MIN)))
     .documentation("Integer type (i64)")
     .build();
-    let _ = env.declare("int".to_string(), RuntimeValue::Type(int_type));
+    RuntimeValue::Type(Arc::new(int_type))
 }
 
-fn register_str(env: &mut Environment) {
+fn str_type() -> RuntimeValue {
     let str_type = TypeObjectBuilder::new(
         PrimitiveType::str(),
         TypeKind::Primitive(PrimitiveKind::String),
@@ -65,44 +71,39 @@ fn register_str(env: &mut Environment) {
                 }
             },
             fn_type!(PrimitiveType::int(), PrimitiveType::str()),
-        )),
+        )
+        .with_param_names(&["code"])),
     )
     .static_property("empty", RuntimeValue::Str(StrValue::new("".to_string())))
     .documentation("String type (UTF-8)")
     .build();
-    let _ = env.declare("str".to_string(), RuntimeValue::Type(str_type));
+    RuntimeValue::Type(Arc::new(str_type))
 }
 
-fn register_bool(env: &mut Environment) {
+fn bool_type() -> RuntimeValue {
     let bool_type = TypeObjectBuilder::new(
         PrimitiveType::bool(),
         TypeKind::Primitive(PrimitiveKind::Bool),
     )
     .documentation("Boolean type")
     .build();
-    let _ = env.declare("bool".to_string(), RuntimeValue::Type(bool_type));
+    RuntimeValue::Type(Arc::new(bool_type))
 }
 
-fn register_float(env: &mut Environment) {
+fn float_type() -> RuntimeValue {
     let float_type = TypeObjectBuilder::new(
         PrimitiveType::float(),
         TypeKind::Primitive(PrimitiveKind::Float),
     )
     .static_method(
         "parse",
-        RuntimeValue::NativeFunction(NativeFunctionValue::new(
-            |args: Vec<RuntimeValue>| {
-                if args.is_empty() {
-                    return RuntimeValue::Null(NullValue::new());
-                }
-                let s = args[0].to_string();
-                match s.parse::<f64>() {
-                    Ok(n) => RuntimeValue::Float(FloatValue::new(n)),
-                    Err(_) => RuntimeValue::Null(NullValue::new()),
-                }
-            },
-            fn_type!(PrimitiveType::str(), PrimitiveType::float()),
-        )),
+        RuntimeValue::NativeFunction(
+            NativeFunctionValue::new(
+                |args| parse_with(&Float64Type, args),
+                fn_type!(PrimitiveType::str(), PrimitiveType::float()),
+            )
+            .with_param_names(&["value"]),
+        ),
     )
     .static_property("MAX_VALUE", RuntimeValue::Float(FloatValue::new(f64::MAX)))
     .static_property("MIN_VALUE", RuntimeValue::Float(FloatValue::new(f64::MIN)))
@@ -117,5 +118,5 @@ fn register_float(env: &mut Environment) {
     .static_property("NaN", RuntimeValue::Float(FloatValue::new(f64::NAN)))
     .documentation("Floating point type (f64)")
     .build();
-    let _ = env.declare("float".to_string(), RuntimeValue::Type(float_type));
+    RuntimeValue::Type(Arc::new(float_type))
 }
@@ -0,0 +1,88 @@
+use crate::ast::types::{PrimitiveType, Type};
+use crate::runtime::dynamic::DynamicValue;
+use crate::runtime::{BoolValue, IntValue, RuntimeValue, StrValue};
+use std::sync::{Arc, Mutex};
+
+/// Backs `new StringBuilder()`; copies share one buffer.
+#[derive(Debug, Clone, Default)]
+pub struct StringBuilderValue {
+    buffer: Arc<Mutex<String>>,
+}
+
+impl StringBuilderValue {
+    pub const TYPE_NAME: &'static str = "StringBuilder";
+
+    pub fn new(initial: &str) -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(initial.to_string())),
+        }
+    }
+
+    pub fn into_value(self) -> RuntimeValue {
+        RuntimeValue::Dynamic(Box::new(self))
+    }
+
+    fn push(&self, value: &RuntimeValue) {
+        let mut buffer = self.buffer.lock().unwrap();
+        match value {
+            RuntimeValue::Str(s) => buffer.push_str(&s.value),
+            other => buffer.push_str(&other.to_string()),
+        }
+    }
+}
+
+impl DynamicValue for StringBuilderValue {
+    fn get_type(&self) -> Type {
+        PrimitiveType::any()
+    }
+
+    fn to_string(&self) -> String {
+        self.buffer.lock().unwrap().clone()
+    }
+
+    fn get_property(&self, name: &str) -> Option<RuntimeValue> {
+        match name {
+            "length" => Some(RuntimeValue::Int(IntValue::new(
+                self.buffer.lock().unwrap().len() as i64,
+            ))),
+            _ => None,
+        }
+    }
+
+    fn call_method(&self, method: &str, args: Vec<RuntimeValue>) -> Result<RuntimeValue, String> {
+        match method {
+            "append" => {
+                args.iter().for_each(|arg| self.push(arg));
+                Ok(self.clone().into_value())
+            }
+            "appendLine" => {
+                args.iter().for_each(|arg| self.push(arg));
+                self.buffer.lock().unwrap().push('\n');
+                Ok(self.clone().into_value())
+            }
+            "clear" => {
+                self.buffer.lock().unwrap().clear();
+                Ok(self.clone().into_value())
+            }
+            "isEmpty" => Ok(RuntimeValue::Bool(BoolValue::new(
+                self.buffer.lock().unwrap().is_empty(),
+            ))),
+            "toString" | "toStr" => Ok(RuntimeValue::Str(StrValue::new(DynamicValue::to_string(
+                self,
+            )))),
+            _ => Err(format!(
+                "Method '{}' not found on {}",
+                method,
+                Self::TYPE_NAME
+            )),
+        }
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DynamicValue> {
+        Box::new(self.clone())
+    }
+
+    fn type_name(&self) -> &str {
+        Self::TYPE_NAME
+    }
+}
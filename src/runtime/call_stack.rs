@@ -1,10 +1,15 @@
 use crate::tokens::Position;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone)]
 pub struct StackFrame {
     pub function_name: String,
     pub call_position: Position,
     pub file: Option<String>,
+    entered: Option<Instant>,
+    callee_time: Duration,
 }
 
 impl StackFrame {
@@ -13,6 +18,8 @@ impl StackFrame {
             function_name,
             call_position,
             file,
+            entered: None,
+            callee_time: Duration::ZERO,
         }
     }
 
@@ -32,10 +39,93 @@ impl StackFrame {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct FunctionProfile {
+    pub calls: u64,
+    pub inclusive: Duration,
+    pub exclusive: Duration,
+}
+
+/// Totals the calls that each `CallStack`, one per async task, times on its own frames.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    functions: HashMap<String, FunctionProfile>,
+    collapsed: HashMap<String, Duration>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&mut self, frames: &[StackFrame], inclusive: Duration, exclusive: Duration) {
+        let Some((current, callers)) = frames.split_last() else {
+            return;
+        };
+
+        let recursive = callers
+            .iter()
+            .any(|frame| frame.function_name == current.function_name);
+        let entry = self
+            .functions
+            .entry(current.function_name.clone())
+            .or_default();
+        entry.calls += 1;
+        entry.exclusive += exclusive;
+        if !recursive {
+            entry.inclusive += inclusive;
+        }
+
+        let path = std::iter::once("main")
+            .chain(frames.iter().map(|frame| frame.function_name.as_str()))
+            .collect::<Vec<_>>()
+            .join(";");
+        *self.collapsed.entry(path).or_default() += exclusive;
+    }
+
+    pub fn functions(&self) -> &HashMap<String, FunctionProfile> {
+        &self.functions
+    }
+
+    pub fn to_collapsed_stacks(&self) -> String {
+        let mut lines: Vec<_> = self
+            .collapsed
+            .iter()
+            .map(|(path, time)| format!("{} {}", path, time.as_micros()))
+            .collect();
+        lines.sort();
+
+        let mut output = lines.join("\n");
+        output.push('\n');
+        output
+    }
+
+    pub fn format_summary(&self) -> String {
+        let mut functions: Vec<_> = self.functions.iter().collect();
+        functions.sort_by_key(|(_, profile)| std::cmp::Reverse(profile.exclusive));
+
+        let mut output = format!(
+            "{:<32} {:>8} {:>14} {:>14}\n",
+            "function", "calls", "inclusive", "exclusive"
+        );
+        for (name, profile) in functions {
+            output.push_str(&format!(
+                "{:<32} {:>8} {:>12.3}ms {:>12.3}ms\n",
+                name,
+                profile.calls,
+                profile.inclusive.as_secs_f64() * 1000.0,
+                profile.exclusive.as_secs_f64() * 1000.0
+            ));
+        }
+        output
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CallStack {
     frames: Vec<StackFrame>,
     max_display_depth: usize,
+    profiler: Option<Arc<Mutex<Profiler>>>,
 }
 
 impl CallStack {
@@ -43,15 +133,50 @@ impl CallStack {
         Self {
             frames: Vec::new(),
             max_display_depth: 10,
+            profiler: None,
         }
     }
 
-    pub fn push(&mut self, frame: StackFrame) {
+    pub fn with_profiler(profiler: Option<Arc<Mutex<Profiler>>>) -> Self {
+        Self {
+            profiler,
+            ..Self::new()
+        }
+    }
+
+    pub fn enable_profiling(&mut self) -> Arc<Mutex<Profiler>> {
+        self.profiler
+            .get_or_insert_with(|| Arc::new(Mutex::new(Profiler::new())))
+            .clone()
+    }
+
+    pub fn profiler(&self) -> Option<Arc<Mutex<Profiler>>> {
+        self.profiler.clone()
+    }
+
+    pub fn push(&mut self, mut frame: StackFrame) {
+        if self.profiler.is_some() {
+            frame.entered = Some(Instant::now());
+        }
         self.frames.push(frame);
     }
 
     pub fn pop(&mut self) -> Option<StackFrame> {
-        self.frames.pop()
+        let frame = self.frames.last()?;
+        let inclusive = frame.entered.map(|entered| entered.elapsed());
+        if let (Some(profiler), Some(inclusive)) = (&self.profiler, inclusive) {
+            let exclusive = inclusive.saturating_sub(frame.callee_time);
+            profiler
+                .lock()
+                .unwrap()
+                .record(&self.frames, inclusive, exclusive);
+        }
+
+        let frame = self.frames.pop();
+        if let (Some(caller), Some(inclusive)) = (self.frames.last_mut(), inclusive) {
+            caller.callee_time += inclusive;
+        }
+        frame
     }
 
     pub fn depth(&self) -> usize {
@@ -62,6 +187,12 @@ impl CallStack {
         &self.frames
     }
 
+    pub fn truncate(&mut self, depth: usize) {
+        while self.frames.len() > depth {
+            self.pop();
+        }
+    }
+
     pub fn clear(&mut self) {
         self.frames.clear();
     }
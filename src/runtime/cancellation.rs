@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Prefix of the rejection message of a cancelled future.
+pub const CANCELLATION_PREFIX: &str = "CancellationError: ";
+
+pub fn cancellation_message(reason: &str) -> String {
+    format!("{}{}", CANCELLATION_PREFIX, reason)
+}
+
+pub fn is_cancellation(message: &str) -> bool {
+    message.starts_with(CANCELLATION_PREFIX)
+}
+
+lazy_static::lazy_static! {
+    static ref TOKENS: Mutex<HashMap<u64, CancellationToken>> = Mutex::new(HashMap::new());
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Shared cancellation flag behind `AbortController`.
+#[derive(Clone, Debug)]
+pub struct CancellationToken {
+    id: u64,
+    reason: Arc<Mutex<Option<String>>>,
+    notifier: Arc<Notify>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        let token = Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            reason: Arc::new(Mutex::new(None)),
+            notifier: Arc::new(Notify::new()),
+        };
+        TOKENS.lock().unwrap().insert(token.id, token.clone());
+        token
+    }
+
+    pub fn lookup(id: u64) -> Option<Self> {
+        TOKENS.lock().unwrap().get(&id).cloned()
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Cancels the token; only the first reason is kept.
+    pub fn cancel(&self, reason: impl Into<String>) {
+        let mut current = self.reason.lock().unwrap();
+        if current.is_none() {
+            *current = Some(reason.into());
+            self.notifier.notify_waiters();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.reason.lock().unwrap().is_some()
+    }
+
+    pub fn reason(&self) -> Option<String> {
+        self.reason.lock().unwrap().clone()
+    }
+
+    /// `Err` with the cancellation message once the token has been cancelled.
+    pub fn check(&self) -> Result<(), String> {
+        match self.reason() {
+            Some(reason) => Err(cancellation_message(&reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Completes with the cancellation reason once the token is cancelled.
+    pub async fn cancelled(&self) -> String {
+        loop {
+            let notified = self.notifier.notified();
+            if let Some(reason) = self.reason() {
+                return reason;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
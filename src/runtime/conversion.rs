@@ -96,7 +96,13 @@ impl ToRaccoon for String {
 impl<T: FromRaccoon> FromRaccoon for Vec<T> {
     fn from_raccoon(val: &RuntimeValue) -> Result<Self, String> {
         match val {
-            RuntimeValue::Array(list) => list.elements.iter().map(|v| T::from_raccoon(v)).collect(),
+            RuntimeValue::Array(list) => list
+                .elements
+                .read()
+                .unwrap()
+                .iter()
+                .map(|v| T::from_raccoon(v))
+                .collect(),
             _ => Err("Expected list".into()),
         }
     }
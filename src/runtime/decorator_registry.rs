@@ -1,6 +1,10 @@
 use crate::ast::nodes::DecoratorDecl;
 use crate::error::RaccoonError;
+use crate::runtime::{FutureValue, RuntimeValue};
+use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DecoratorVisibility {
@@ -15,6 +19,7 @@ pub struct DecoratorSpec {
     pub visibility: DecoratorVisibility,
     pub description: String,
     pub allowed_on: Vec<DecoratorTarget>,
+    pub wrap: Option<fn(&DecoratorInfo) -> NativeDecorator>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,6 +51,7 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Caches function results for specified time in milliseconds".to_string(),
             allowed_on: vec![DecoratorTarget::Function, DecoratorTarget::AsyncFunction],
+            wrap: None,
         });
 
         self.register_decorator(DecoratorSpec {
@@ -57,6 +63,7 @@ impl DecoratorRegistry {
                 DecoratorTarget::AsyncFunction,
                 DecoratorTarget::Class,
             ],
+            wrap: None,
         });
 
         self.register_decorator(DecoratorSpec {
@@ -64,6 +71,7 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Marks function as pure (no side effects)".to_string(),
             allowed_on: vec![DecoratorTarget::Function, DecoratorTarget::AsyncFunction],
+            wrap: None,
         });
 
         self.register_decorator(DecoratorSpec {
@@ -71,6 +79,7 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Suggests to inline this function at call sites".to_string(),
             allowed_on: vec![DecoratorTarget::Function, DecoratorTarget::AsyncFunction],
+            wrap: None,
         });
 
         self.register_decorator(DecoratorSpec {
@@ -78,6 +87,7 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Marks property as read-only".to_string(),
             allowed_on: vec![DecoratorTarget::ClassProperty],
+            wrap: None,
         });
 
         self.register_decorator(DecoratorSpec {
@@ -85,6 +95,7 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Marks method as override of base class method".to_string(),
             allowed_on: vec![DecoratorTarget::ClassMethod],
+            wrap: None,
         });
 
         self.register_decorator(DecoratorSpec {
@@ -92,20 +103,23 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Measures execution time of function".to_string(),
             allowed_on: vec![DecoratorTarget::Function, DecoratorTarget::AsyncFunction],
+            wrap: None,
         });
 
         self.register_decorator(DecoratorSpec {
             name: "memoize".to_string(),
             visibility: DecoratorVisibility::Public,
-            description: "Memoizes function results (alias for cache)".to_string(),
+            description: "Memoizes function results, keeping the N most recently used".to_string(),
             allowed_on: vec![DecoratorTarget::Function, DecoratorTarget::AsyncFunction],
+            wrap: Some(NativeDecorator::memoize),
         });
 
         self.register_decorator(DecoratorSpec {
             name: "throttle".to_string(),
             visibility: DecoratorVisibility::Public,
-            description: "Throttles function calls to max N times per interval".to_string(),
+            description: "Runs the function at most once per N milliseconds".to_string(),
             allowed_on: vec![DecoratorTarget::Function, DecoratorTarget::AsyncFunction],
+            wrap: Some(NativeDecorator::throttle),
         });
 
         self.register_decorator(DecoratorSpec {
@@ -113,13 +127,15 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Debounces function calls by N milliseconds".to_string(),
             allowed_on: vec![DecoratorTarget::Function, DecoratorTarget::AsyncFunction],
+            wrap: Some(NativeDecorator::debounce),
         });
 
         self.register_decorator(DecoratorSpec {
             name: "retry".to_string(),
             visibility: DecoratorVisibility::Public,
-            description: "Retries function on error".to_string(),
+            description: "Retries function on error, backing off between attempts".to_string(),
             allowed_on: vec![DecoratorTarget::Function, DecoratorTarget::AsyncFunction],
+            wrap: Some(NativeDecorator::retry),
         });
 
         self.register_decorator(DecoratorSpec {
@@ -127,6 +143,7 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Logs function calls with arguments and return value".to_string(),
             allowed_on: vec![DecoratorTarget::Function, DecoratorTarget::AsyncFunction],
+            wrap: None,
         });
 
         self.register_decorator(DecoratorSpec {
@@ -134,6 +151,7 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Marks class as sealed (cannot be extended)".to_string(),
             allowed_on: vec![DecoratorTarget::Class],
+            wrap: None,
         });
 
         self.register_decorator(DecoratorSpec {
@@ -141,6 +159,7 @@ impl DecoratorRegistry {
             visibility: DecoratorVisibility::Public,
             description: "Marks class or method as abstract".to_string(),
             allowed_on: vec![DecoratorTarget::Class, DecoratorTarget::ClassMethod],
+            wrap: None,
         });
     }
 
@@ -178,6 +197,7 @@ impl DecoratorRegistry {
                         DecoratorTarget::Class,
                         DecoratorTarget::ClassMethod,
                     ],
+                    wrap: None,
                 }
             };
 
@@ -236,6 +256,10 @@ impl DecoratorInfo {
         }
     }
 
+    pub fn arg_as_millis(&self, index: usize) -> Duration {
+        Duration::from_millis(self.arg_as_int(index).unwrap_or(0).max(0) as u64)
+    }
+
     pub fn arg_as_bool(&self, index: usize) -> Option<bool> {
         use crate::ast::nodes::Expr;
         match self.decl.args.get(index)? {
@@ -245,6 +269,126 @@ impl DecoratorInfo {
     }
 }
 
+#[derive(Debug, Clone)]
+pub enum NativeDecorator {
+    Memoize {
+        max_size: Option<usize>,
+        cache: Arc<Mutex<IndexMap<String, RuntimeValue>>>,
+    },
+    Debounce {
+        delay: Duration,
+        state: Arc<Mutex<DebounceState>>,
+    },
+    Throttle {
+        interval: Duration,
+        last: Arc<Mutex<Option<(Instant, RuntimeValue)>>>,
+    },
+    Retry {
+        times: u32,
+        backoff: Duration,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct DebounceState {
+    pub generation: u64,
+    pub waiting: Vec<FutureValue>,
+}
+
+impl NativeDecorator {
+    fn memoize(info: &DecoratorInfo) -> Self {
+        Self::Memoize {
+            max_size: info.arg_as_int(0).map(|size| size.max(1) as usize),
+            cache: Arc::new(Mutex::new(IndexMap::new())),
+        }
+    }
+
+    fn debounce(info: &DecoratorInfo) -> Self {
+        Self::Debounce {
+            delay: info.arg_as_millis(0),
+            state: Arc::new(Mutex::new(DebounceState::default())),
+        }
+    }
+
+    fn throttle(info: &DecoratorInfo) -> Self {
+        Self::Throttle {
+            interval: info.arg_as_millis(0),
+            last: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn retry(info: &DecoratorInfo) -> Self {
+        Self::Retry {
+            times: info.arg_as_int(0).unwrap_or(3).clamp(0, u32::MAX as i64) as u32,
+            backoff: info.arg_as_millis(1),
+        }
+    }
+
+    pub fn cache_key(args: &[RuntimeValue]) -> String {
+        args.iter()
+            .map(|arg| format!("{}:{}", arg.get_name(), arg.to_string()))
+            .collect::<Vec<_>>()
+            .join("\u{1f}")
+    }
+
+    pub fn cached(&self, key: &str) -> Option<RuntimeValue> {
+        let Self::Memoize { cache, .. } = self else {
+            return None;
+        };
+        let mut cache = cache.lock().unwrap();
+        let value = cache.shift_remove(key)?;
+        cache.insert(key.to_string(), value.clone());
+        Some(value)
+    }
+
+    pub fn remember(&self, key: String, value: RuntimeValue) {
+        let Self::Memoize { max_size, cache } = self else {
+            return;
+        };
+        let mut cache = cache.lock().unwrap();
+        cache.insert(key, value);
+        if let Some(max_size) = max_size {
+            while cache.len() > *max_size {
+                cache.shift_remove_index(0);
+            }
+        }
+    }
+
+    pub fn throttled(&self) -> Option<RuntimeValue> {
+        let Self::Throttle { interval, last } = self else {
+            return None;
+        };
+        match &*last.lock().unwrap() {
+            Some((at, value)) if at.elapsed() < *interval => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn ran(&self, value: RuntimeValue) {
+        if let Self::Throttle { last, .. } = self {
+            *last.lock().unwrap() = Some((Instant::now(), value));
+        }
+    }
+
+    pub fn wait_debounced(&self, future: FutureValue) -> u64 {
+        let Self::Debounce { state, .. } = self else {
+            return 0;
+        };
+        let mut state = state.lock().unwrap();
+        state.generation += 1;
+        state.waiting.push(future);
+        state.generation
+    }
+
+    pub fn settle_debounced(&self, generation: u64) -> Option<Vec<FutureValue>> {
+        let Self::Debounce { state, .. } = self else {
+            return None;
+        };
+        let mut state = state.lock().unwrap();
+        (state.generation == generation).then(|| std::mem::take(&mut state.waiting))
+    }
+}
+
 impl Default for DecoratorRegistry {
     fn default() -> Self {
         Self::new()
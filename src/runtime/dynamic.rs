@@ -22,6 +22,11 @@ pub trait DynamicValue: Send + Sync + fmt::Debug + DynamicValueClone {
         Err("This value is not callable".to_string())
     }
 
+    fn call_method(&self, method: &str, args: Vec<RuntimeValue>) -> Result<RuntimeValue, String> {
+        let _ = args;
+        Err(format!("Method '{}' not found on {}", method, self.type_name()))
+    }
+
     fn clone_boxed(&self) -> Box<dyn DynamicValue>;
 
     fn type_name(&self) -> &str;
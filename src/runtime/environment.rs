@@ -1,12 +1,44 @@
-use super::values::RuntimeValue;
-use crate::error::RaccoonError;
+use super::values::{AllocationToken, RuntimeValue, ValueKind};
+use crate::error::{did_you_mean, RaccoonError};
 use crate::tokens::Position;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+
+type Thunk = Box<dyn FnOnce() -> RuntimeValue + Send>;
+
+struct LazyGlobal {
+    thunk: Mutex<Option<Thunk>>,
+    value: OnceLock<RuntimeValue>,
+}
+
+impl LazyGlobal {
+    fn value(&self) -> RuntimeValue {
+        self.value
+            .get_or_init(|| {
+                let thunk = self.thunk.lock().unwrap().take();
+                thunk.expect("lazy global is built once")()
+            })
+            .clone()
+    }
+}
+
+impl fmt::Debug for LazyGlobal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value.get() {
+            Some(value) => value.fmt(f),
+            None => f.write_str("<lazy>"),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Environment {
     pub file: Option<String>,
     scopes: Vec<HashMap<String, RuntimeValue>>,
+    constants: Vec<HashMap<String, Position>>,
+    lazy: Arc<HashMap<String, Arc<LazyGlobal>>>,
+    _allocation: AllocationToken,
 }
 
 impl Environment {
@@ -14,22 +46,39 @@ impl Environment {
         Self {
             file,
             scopes: vec![HashMap::new()],
+            constants: vec![HashMap::new()],
+            lazy: Arc::default(),
+            _allocation: AllocationToken::new(ValueKind::Environment),
         }
     }
 
     pub fn push_scope(&mut self) {
         self.scopes.push(HashMap::new());
+        self.constants.push(HashMap::new());
     }
 
     pub fn pop_scope(&mut self) {
         if self.scopes.len() > 1 {
             self.scopes.pop();
+            self.constants.pop();
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Drops the scopes pushed since `depth`, such as those of calls a thrown error unwound.
+    pub fn truncate(&mut self, depth: usize) {
+        while self.scopes.len() > depth.max(1) {
+            self.pop_scope();
         }
     }
 
     pub fn declare(&mut self, name: String, value: RuntimeValue) -> Result<(), RaccoonError> {
+        let global = self.scopes.len() == 1;
         if let Some(scope) = self.scopes.last_mut() {
-            if scope.contains_key(&name) {
+            if scope.contains_key(&name) || (global && self.lazy.contains_key(&name)) {
                 return Err(RaccoonError::new(
                     format!("Variable '{}' is already declared", name),
                     (0, 0),
@@ -47,11 +96,71 @@ impl Environment {
         }
     }
 
+    /// Declares the global `name`, built by `thunk` when first looked up.
+    pub fn declare_lazy(
+        &mut self,
+        name: impl Into<String>,
+        thunk: impl FnOnce() -> RuntimeValue + Send + 'static,
+    ) {
+        let name = name.into();
+        if self.scopes[0].contains_key(&name) || self.lazy.contains_key(&name) {
+            return;
+        }
+        let global = LazyGlobal {
+            thunk: Mutex::new(Some(Box::new(thunk))),
+            value: OnceLock::new(),
+        };
+        Arc::make_mut(&mut self.lazy).insert(name, Arc::new(global));
+    }
+
+    pub fn declare_constant(
+        &mut self,
+        name: String,
+        value: RuntimeValue,
+        position: Position,
+    ) -> Result<(), RaccoonError> {
+        self.declare(name.clone(), value)?;
+        self.mark_constant(&name, position);
+        Ok(())
+    }
+
+    pub fn mark_constant(&mut self, name: &str, position: Position) {
+        if let Some(constants) = self.constants.last_mut() {
+            constants.insert(name.to_string(), position);
+        }
+    }
+
     pub fn assign(
         &mut self,
         name: &str,
         value: RuntimeValue,
         position: Position,
+    ) -> Result<(), RaccoonError> {
+        for (scope, constants) in self.scopes.iter().zip(self.constants.iter()).rev() {
+            if scope.contains_key(name) {
+                if let Some(declared) = constants.get(name) {
+                    let message = if *declared == (0, 0) {
+                        format!("Cannot assign to constant '{}'", name)
+                    } else {
+                        format!(
+                            "Cannot assign to constant '{}' (declared at {}:{})",
+                            name, declared.0, declared.1
+                        )
+                    };
+                    return Err(RaccoonError::new(message, position, self.file.clone()));
+                }
+                break;
+            }
+        }
+
+        self.update(name, value, position)
+    }
+
+    pub fn update(
+        &mut self,
+        name: &str,
+        value: RuntimeValue,
+        position: Position,
     ) -> Result<(), RaccoonError> {
         for scope in self.scopes.iter_mut().rev() {
             if scope.contains_key(name) {
@@ -59,11 +168,11 @@ impl Environment {
                 return Ok(());
             }
         }
-        Err(RaccoonError::new(
-            format!("Variable '{}' is not declared", name),
-            position,
-            self.file.clone(),
-        ))
+        if self.lazy.contains_key(name) {
+            self.scopes[0].insert(name.to_string(), value);
+            return Ok(());
+        }
+        Err(self.undeclared(name, position))
     }
 
     pub fn get(&self, name: &str, position: Position) -> Result<RuntimeValue, RaccoonError> {
@@ -72,11 +181,45 @@ impl Environment {
                 return Ok(value.clone());
             }
         }
-        Err(RaccoonError::new(
+        match self.lazy.get(name) {
+            Some(global) => Ok(global.value()),
+            None => Err(self.undeclared(name, position)),
+        }
+    }
+
+    /// The value bound to `name` in a scope, without building a lazy global.
+    pub fn lookup(&self, name: &str) -> Option<&RuntimeValue> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    fn undeclared(&self, name: &str, position: Position) -> RaccoonError {
+        let error = RaccoonError::new(
             format!("Variable '{}' is not declared", name),
             position,
             self.file.clone(),
-        ))
+        );
+        match did_you_mean(name, self.names()) {
+            Some(suggestion) => error.with_suggestion(suggestion),
+            None => error,
+        }
+    }
+
+    pub fn scopes(&self) -> &[HashMap<String, RuntimeValue>] {
+        &self.scopes
+    }
+
+    /// Every declared name, lazy globals included, innermost scope first.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        let lazy = self
+            .lazy
+            .keys()
+            .filter(|name| !self.scopes[0].contains_key(*name));
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.keys())
+            .chain(lazy)
+            .map(String::as_str)
     }
 
     pub fn exists(&self, name: &str) -> bool {
@@ -84,5 +227,6 @@ impl Environment {
             .iter()
             .rev()
             .any(|scope| scope.contains_key(name))
+            || self.lazy.contains_key(name)
     }
 }
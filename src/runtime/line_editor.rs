@@ -0,0 +1,443 @@
+use crate::runtime::natives::terminal;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+const MAX_HISTORY: usize = 1000;
+
+static SHARED_EDITOR: Mutex<LineEditor> = Mutex::new(LineEditor::new());
+
+pub fn shared_editor() -> &'static Mutex<LineEditor> {
+    &SHARED_EDITOR
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadResult {
+    Line(String),
+    Interrupted,
+    Eof,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Char(char),
+    Ctrl(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    Escape,
+    Unknown,
+}
+
+#[derive(Debug, Default)]
+pub struct LineEditor {
+    history: Vec<String>,
+    completions: Vec<String>,
+}
+
+struct EditState {
+    prompt: String,
+    buffer: Vec<char>,
+    cursor: usize,
+    history_index: Option<usize>,
+    pending: String,
+}
+
+impl LineEditor {
+    pub const fn new() -> Self {
+        Self {
+            history: Vec::new(),
+            completions: Vec::new(),
+        }
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    pub fn add_history(&mut self, line: &str) {
+        if line.trim().is_empty() || self.history.last().is_some_and(|last| last == line) {
+            return;
+        }
+        self.history.push(line.to_string());
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+    }
+
+    pub fn load_history(&mut self, path: &Path) {
+        if let Ok(content) = fs::read_to_string(path) {
+            for line in content.lines() {
+                self.add_history(line);
+            }
+        }
+    }
+
+    pub fn save_history(&self, path: &Path) -> io::Result<()> {
+        let mut content = self.history.join("\n");
+        content.push('\n');
+        fs::write(path, content)
+    }
+
+    pub fn set_completions(&mut self, mut candidates: Vec<String>) {
+        candidates.sort();
+        candidates.dedup();
+        self.completions = candidates;
+    }
+
+    pub fn read_line(&mut self, prompt: &str) -> io::Result<ReadResult> {
+        let result = self.read_raw_line(prompt);
+        if let Ok(ReadResult::Line(line)) = &result {
+            self.add_history(line);
+        }
+        result
+    }
+
+    fn read_raw_line(&mut self, prompt: &str) -> io::Result<ReadResult> {
+        if !terminal::is_tty(terminal::STDIN) || !terminal::is_tty(terminal::STDOUT) {
+            return read_plain_line(prompt);
+        }
+
+        let enabled_raw = !terminal::is_raw_mode();
+        if enabled_raw && !terminal::set_raw_mode(true) {
+            return read_plain_line(prompt);
+        }
+
+        let result = self.edit(prompt);
+
+        if enabled_raw {
+            terminal::set_raw_mode(false);
+        }
+        print!("\r\n");
+        io::stdout().flush()?;
+        result
+    }
+
+    fn edit(&mut self, prompt: &str) -> io::Result<ReadResult> {
+        let mut state = EditState {
+            prompt: prompt.to_string(),
+            buffer: Vec::new(),
+            cursor: 0,
+            history_index: None,
+            pending: String::new(),
+        };
+        state.refresh()?;
+
+        loop {
+            match read_key()? {
+                None => return Ok(ReadResult::Eof),
+                Some(Key::Enter) => return Ok(ReadResult::Line(state.line())),
+                Some(Key::Ctrl('c')) => {
+                    print!("^C");
+                    return Ok(ReadResult::Interrupted);
+                }
+                Some(Key::Ctrl('d')) if state.buffer.is_empty() => return Ok(ReadResult::Eof),
+                Some(Key::Ctrl('d')) | Some(Key::Delete) => {
+                    if state.cursor < state.buffer.len() {
+                        state.buffer.remove(state.cursor);
+                    }
+                }
+                Some(Key::Char(c)) => {
+                    state.buffer.insert(state.cursor, c);
+                    state.cursor += 1;
+                }
+                Some(Key::Backspace) | Some(Key::Ctrl('h')) => {
+                    if state.cursor > 0 {
+                        state.cursor -= 1;
+                        state.buffer.remove(state.cursor);
+                    }
+                }
+                Some(Key::Left) | Some(Key::Ctrl('b')) => {
+                    state.cursor = state.cursor.saturating_sub(1);
+                }
+                Some(Key::Right) | Some(Key::Ctrl('f')) => {
+                    state.cursor = (state.cursor + 1).min(state.buffer.len());
+                }
+                Some(Key::Home) | Some(Key::Ctrl('a')) => state.cursor = 0,
+                Some(Key::End) | Some(Key::Ctrl('e')) => state.cursor = state.buffer.len(),
+                Some(Key::Ctrl('u')) => {
+                    state.buffer.drain(..state.cursor);
+                    state.cursor = 0;
+                }
+                Some(Key::Ctrl('k')) => state.buffer.truncate(state.cursor),
+                Some(Key::Ctrl('w')) => state.delete_word(),
+                Some(Key::Ctrl('l')) => print!("\x1b[2J\x1b[H"),
+                Some(Key::Up) | Some(Key::Ctrl('p')) => self.history_prev(&mut state),
+                Some(Key::Down) | Some(Key::Ctrl('n')) => self.history_next(&mut state),
+                Some(Key::Tab) => self.complete(&mut state)?,
+                Some(Key::Ctrl('r')) => {
+                    if let Some(line) = self.reverse_search(&mut state)? {
+                        return Ok(ReadResult::Line(line));
+                    }
+                }
+                Some(_) => {}
+            }
+            state.refresh()?;
+        }
+    }
+
+    fn history_prev(&self, state: &mut EditState) {
+        let index = match state.history_index {
+            Some(0) => return,
+            Some(index) => index - 1,
+            None if self.history.is_empty() => return,
+            None => {
+                state.pending = state.line();
+                self.history.len() - 1
+            }
+        };
+        state.history_index = Some(index);
+        state.set_line(&self.history[index]);
+    }
+
+    fn history_next(&self, state: &mut EditState) {
+        match state.history_index {
+            Some(index) if index + 1 < self.history.len() => {
+                state.history_index = Some(index + 1);
+                state.set_line(&self.history[index + 1]);
+            }
+            Some(_) => {
+                state.history_index = None;
+                let pending = std::mem::take(&mut state.pending);
+                state.set_line(&pending);
+            }
+            None => {}
+        }
+    }
+
+    fn complete(&self, state: &mut EditState) -> io::Result<()> {
+        let start = state.buffer[..state.cursor]
+            .iter()
+            .rposition(|c| !is_word_char(*c))
+            .map_or(0, |i| i + 1);
+        let word: String = state.buffer[start..state.cursor].iter().collect();
+        if word.is_empty() {
+            return Ok(());
+        }
+
+        let matches: Vec<&String> = self
+            .completions
+            .iter()
+            .filter(|candidate| candidate.starts_with(&word))
+            .collect();
+
+        let Some(first) = matches.first() else {
+            return Ok(());
+        };
+
+        let common = matches.iter().fold(first.to_string(), |prefix, candidate| {
+            prefix
+                .chars()
+                .zip(candidate.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect()
+        });
+
+        if common.len() > word.len() {
+            for c in common[word.len()..].chars() {
+                state.buffer.insert(state.cursor, c);
+                state.cursor += 1;
+            }
+        } else if matches.len() > 1 {
+            let listing: Vec<&str> = matches.iter().map(|m| m.as_str()).collect();
+            print!("\r\n{}\r\n", listing.join("  "));
+        }
+
+        Ok(())
+    }
+
+    fn reverse_search(&self, state: &mut EditState) -> io::Result<Option<String>> {
+        let mut query = String::new();
+        let mut from = self.history.len();
+        let mut found: Option<usize> = None;
+
+        loop {
+            let matched = found.map(|i| self.history[i].as_str()).unwrap_or("");
+            print!("\r\x1b[K(reverse-i-search)`{}': {}", query, matched);
+            io::stdout().flush()?;
+
+            match read_key()? {
+                None => return Ok(None),
+                Some(Key::Char(c)) => {
+                    query.push(c);
+                    found = self.search_history(&query, from);
+                }
+                Some(Key::Backspace) => {
+                    query.pop();
+                    from = self.history.len();
+                    found = self.search_history(&query, from);
+                }
+                Some(Key::Ctrl('r')) => {
+                    if let Some(index) = found {
+                        from = index;
+                        found = self.search_history(&query, from).or(found);
+                    }
+                }
+                Some(Key::Enter) => return Ok(found.map(|i| self.history[i].clone())),
+                Some(Key::Ctrl('g')) | Some(Key::Ctrl('c')) | Some(Key::Escape) => {
+                    return Ok(None);
+                }
+                Some(_) => {
+                    if let Some(index) = found {
+                        state.history_index = Some(index);
+                        state.set_line(&self.history[index]);
+                    }
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    fn search_history(&self, query: &str, before: usize) -> Option<usize> {
+        if query.is_empty() {
+            return None;
+        }
+        self.history[..before.min(self.history.len())]
+            .iter()
+            .rposition(|entry| entry.contains(query))
+    }
+}
+
+impl EditState {
+    fn line(&self) -> String {
+        self.buffer.iter().collect()
+    }
+
+    fn set_line(&mut self, line: &str) {
+        self.buffer = line.chars().collect();
+        self.cursor = self.buffer.len();
+    }
+
+    fn delete_word(&mut self) {
+        let mut start = self.cursor;
+        while start > 0 && self.buffer[start - 1] == ' ' {
+            start -= 1;
+        }
+        while start > 0 && self.buffer[start - 1] != ' ' {
+            start -= 1;
+        }
+        self.buffer.drain(start..self.cursor);
+        self.cursor = start;
+    }
+
+    fn refresh(&self) -> io::Result<()> {
+        let line = self.line();
+        let tail = self.buffer.len() - self.cursor;
+        let mut stdout = io::stdout();
+        write!(stdout, "\r\x1b[K{}{}", self.prompt, line)?;
+        if tail > 0 {
+            write!(stdout, "\x1b[{}D", tail)?;
+        }
+        stdout.flush()
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$' || c == '.'
+}
+
+fn read_plain_line(prompt: &str) -> io::Result<ReadResult> {
+    if !prompt.is_empty() {
+        print!("{}", prompt);
+        io::stdout().flush()?;
+    }
+
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Ok(ReadResult::Eof);
+    }
+    Ok(ReadResult::Line(
+        line.trim_end_matches(&['\r', '\n'][..]).to_string(),
+    ))
+}
+
+fn read_byte() -> io::Result<Option<u8>> {
+    let mut byte = [0u8; 1];
+    match io::stdin().lock().read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+fn read_key() -> io::Result<Option<Key>> {
+    let Some(byte) = read_byte()? else {
+        return Ok(None);
+    };
+
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x7f => Key::Backspace,
+        0x1b => read_escape()?,
+        1..=26 => Key::Ctrl((b'a' + byte - 1) as char),
+        _ => {
+            let mut bytes = vec![byte];
+            let width = match byte {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+            while bytes.len() < width {
+                match read_byte()? {
+                    Some(next) => bytes.push(next),
+                    None => break,
+                }
+            }
+            match std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(c) if !c.is_control() => Key::Char(c),
+                _ => Key::Unknown,
+            }
+        }
+    };
+
+    Ok(Some(key))
+}
+
+fn read_escape() -> io::Result<Key> {
+    let Some(kind) = read_byte()? else {
+        return Ok(Key::Escape);
+    };
+    if kind != b'[' && kind != b'O' {
+        return Ok(Key::Escape);
+    }
+
+    let key = match read_byte()? {
+        Some(b'A') => Key::Up,
+        Some(b'B') => Key::Down,
+        Some(b'C') => Key::Right,
+        Some(b'D') => Key::Left,
+        Some(b'H') => Key::Home,
+        Some(b'F') => Key::End,
+        Some(digit @ b'0'..=b'9') => {
+            let mut code = vec![digit];
+            while let Some(next) = read_byte()? {
+                if next == b'~' {
+                    break;
+                }
+                code.push(next);
+            }
+            match code.as_slice() {
+                b"1" | b"7" => Key::Home,
+                b"4" | b"8" => Key::End,
+                b"3" => Key::Delete,
+                _ => Key::Unknown,
+            }
+        }
+        _ => Key::Unknown,
+    };
+
+    Ok(key)
+}
@@ -0,0 +1,130 @@
+use serde_json::{Map, Value as JsonValue};
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Minimum level written, e.g. `RACCOON_LOG=debug`; `off` silences everything.
+pub const LEVEL_VAR: &str = "RACCOON_LOG";
+/// `pretty` (the default) or `json`.
+pub const FORMAT_VAR: &str = "RACCOON_LOG_FORMAT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub fn parse(name: &str) -> Option<Level> {
+        match name.to_ascii_lowercase().as_str() {
+            "debug" => Some(Level::Debug),
+            "info" => Some(Level::Info),
+            "warn" | "warning" => Some(Level::Warn),
+            "error" => Some(Level::Error),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+
+    /// Whether records at this level pass the `RACCOON_LOG` filter, which defaults to `info`.
+    pub fn enabled(self) -> bool {
+        match std::env::var(LEVEL_VAR) {
+            Ok(value) if value.eq_ignore_ascii_case("off") => false,
+            Ok(value) => Level::parse(&value).map_or(self >= Level::Info, |min| self >= min),
+            Err(_) => self >= Level::Info,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Pretty,
+    Json,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name.to_ascii_lowercase().as_str() {
+            "pretty" => Some(Format::Pretty),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    pub fn from_env() -> Format {
+        std::env::var(FORMAT_VAR)
+            .ok()
+            .and_then(|value| Format::parse(&value))
+            .unwrap_or(Format::Pretty)
+    }
+}
+
+/// Renders one record without a trailing newline.
+pub fn render(
+    format: Format,
+    level: Level,
+    logger: &str,
+    message: &str,
+    fields: &Map<String, JsonValue>,
+) -> String {
+    match format {
+        Format::Pretty => {
+            let mut line = format!("{:<5} {}: {}", level.name().to_uppercase(), logger, message);
+            for (key, value) in fields {
+                match value {
+                    JsonValue::String(text) => line.push_str(&format!(" {}={}", key, text)),
+                    other => line.push_str(&format!(" {}={}", key, other)),
+                }
+            }
+            line
+        }
+        Format::Json => {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64);
+            let mut record = Map::new();
+            record.insert("time".to_string(), time.into());
+            record.insert("level".to_string(), level.name().into());
+            record.insert("logger".to_string(), logger.into());
+            record.insert("message".to_string(), message.into());
+            for (key, value) in fields {
+                record.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+            JsonValue::Object(record).to_string()
+        }
+    }
+}
+
+/// Writes a record to stderr when `level` passes the filter, keeping stdout for program output.
+pub fn log(
+    format: Format,
+    level: Level,
+    logger: &str,
+    message: &str,
+    fields: &Map<String, JsonValue>,
+) {
+    if level.enabled() {
+        let line = render(format, level, logger, message, fields);
+        let _ = writeln!(std::io::stderr().lock(), "{}", line);
+    }
+}
+
+/// Warnings from runtime code that has no interpreter at hand, in the configured format.
+pub fn warn(message: &str) {
+    log(
+        Format::from_env(),
+        Level::Warn,
+        "raccoon",
+        message,
+        &Map::new(),
+    );
+}
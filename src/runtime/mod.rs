@@ -1,37 +1,55 @@
 pub mod builtins;
 pub mod call_stack;
+pub mod cancellation;
 pub mod control_flow;
 pub mod conversion;
 pub mod decorator_registry;
 pub mod dynamic;
 pub mod environment;
+pub mod line_editor;
+pub mod logging;
 pub mod module_registry;
 pub mod module_system;
 pub mod native;
+pub mod permissions;
 pub mod natives;
 pub mod plugin_system;
 pub mod primitives;
 pub mod registrar;
+pub mod sandbox;
+pub mod snapshot;
 pub mod stdlib;
+pub mod template_tags;
 pub mod type_object;
 pub mod type_object_builder;
 pub mod types;
 pub mod values;
 
 pub use builtins::setup_builtins;
-pub use call_stack::{CallStack, StackFrame};
+pub use call_stack::{CallStack, FunctionProfile, Profiler, StackFrame};
+pub use cancellation::CancellationToken;
 pub use control_flow::{BreakValue, ContinueValue, ReturnValue, ThrownValue};
 pub use conversion::{FromRaccoon, ToRaccoon};
-pub use decorator_registry::{DecoratorRegistry, DecoratorTarget, DecoratorVisibility};
+pub use decorator_registry::{
+    DecoratorRegistry, DecoratorTarget, DecoratorVisibility, NativeDecorator,
+};
 pub use dynamic::{DynamicRuntimeValue, DynamicValue};
 pub use environment::Environment;
+pub use line_editor::{shared_editor, LineEditor, ReadResult};
 pub use module_registry::ModuleRegistry;
-pub use module_system::{analyze_exports, resolve_module_path, Module, ModuleCache, ModuleSystem};
+pub use module_system::{
+    analyze_exports, import_chain, module_files, resolve_module_path, LoadingModules, Module,
+    ModuleCache, ModuleSystem,
+};
 pub use native::{NativeDecoratorProcessor, NativeRegistry};
+pub use permissions::{PathGrant, Permission, Permissions};
 pub use plugin_system::{NativePlugin, PluginManager, PluginRegistry};
 pub use primitives::{LazyPrimitiveRegistry, PrimitiveContext};
 pub use registrar::Registrar;
+pub use sandbox::{Sandbox, SandboxConfig, RESTRICTED_MODULES};
+pub use snapshot::{Snapshot, SNAPSHOT_ENV};
 pub use stdlib::{register_stdlib_wrappers, StdLibLoader};
+pub use template_tags::{TemplateTagRegistry, TemplateTagSpec};
 pub use type_object::{PrimitiveKind, SourceLocation, TypeKind, TypeMetadata, TypeObject};
 pub use type_object_builder::TypeObjectBuilder;
 pub use types::registry::TypeRegistry;
@@ -1,8 +1,9 @@
 use crate::ast::nodes::{Program, Stmt};
+use crate::ast::types::PrimitiveType;
 use crate::error::RaccoonError;
-use crate::runtime::RuntimeValue;
+use crate::runtime::{ObjectValue, RuntimeValue};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
 #[derive(Debug, Clone)]
@@ -65,6 +66,118 @@ impl ModuleCache {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct LoadingModules {
+    modules: Arc<RwLock<Vec<(PathBuf, ObjectValue)>>>,
+}
+
+impl LoadingModules {
+    pub fn enter(&self, file: &str) -> Option<PathBuf> {
+        let path = std::fs::canonicalize(file).ok()?;
+        let mut modules = self.modules.write().unwrap();
+        if modules.iter().any(|(running, _)| *running == path) {
+            return None;
+        }
+        let exports = ObjectValue::new(Vec::new(), PrimitiveType::any());
+        modules.push((path.clone(), exports));
+        Some(path)
+    }
+
+    pub fn leave(&self, path: &Path) {
+        self.modules
+            .write()
+            .unwrap()
+            .retain(|(running, _)| running != path);
+    }
+
+    pub fn exports(&self, file: &str) -> Option<ObjectValue> {
+        let path = std::fs::canonicalize(file).ok()?;
+        self.modules
+            .read()
+            .unwrap()
+            .iter()
+            .find(|(running, _)| *running == path)
+            .map(|(_, exports)| exports.clone())
+    }
+
+    pub fn cycle(&self, file: &str) -> Option<String> {
+        let path = std::fs::canonicalize(file).ok()?;
+        let modules = self.modules.read().unwrap();
+        let start = modules.iter().position(|(running, _)| *running == path)?;
+        let mut chain: Vec<PathBuf> = modules[start..]
+            .iter()
+            .map(|(running, _)| running.clone())
+            .collect();
+        chain.push(path);
+        Some(import_chain(&chain))
+    }
+}
+
+pub fn import_chain(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| {
+            path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// `file` and every user module it imports, directly or not.
+pub fn module_files(file: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut queue = vec![PathBuf::from(file)];
+    while let Some(path) = queue.pop() {
+        let canonical = canonical_path(&path);
+        if files.contains(&canonical) {
+            continue;
+        }
+        files.push(canonical);
+
+        let file = path.to_string_lossy().into_owned();
+        let Ok(source) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(tokens) = crate::Lexer::new(source, Some(file.clone())).tokenize() else {
+            continue;
+        };
+        let Ok(program) = crate::Parser::new(tokens, Some(file.clone())).parse() else {
+            continue;
+        };
+        let specifiers = program.stmts.iter().filter_map(|stmt| match stmt {
+            Stmt::ImportDecl(import) => Some(&import.module_specifier),
+            Stmt::ExportDecl(export) => export.module_specifier.as_ref(),
+            _ => None,
+        });
+        for specifier in specifiers {
+            if specifier.starts_with("./") || specifier.starts_with("../") {
+                queue.extend(resolve_module_path(Some(&file), specifier));
+            }
+        }
+    }
+    files
+}
+
+fn canonical_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = std::fs::canonicalize(path) {
+        return canonical;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(dir), Some(name)) => {
+            let dir = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+            std::fs::canonicalize(dir).map_or_else(|_| path.to_path_buf(), |dir| dir.join(name))
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
 pub fn resolve_module_path(
     current_file: Option<&String>,
     module_specifier: &str,
@@ -79,7 +192,11 @@ pub fn resolve_module_path(
             std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
         };
 
-        let mut path = current_dir.join(module_specifier);
+        let mut path: PathBuf = current_dir
+            .join(module_specifier)
+            .components()
+            .filter(|component| *component != Component::CurDir)
+            .collect();
 
         if path.extension().is_none() {
             path.set_extension("rcc");
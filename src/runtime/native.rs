@@ -25,6 +25,7 @@ impl NativeRegistry {
             params: param_types,
             return_type: return_type,
             is_variadic: false,
+            param_names: Vec::new(),
         }));
 
         let native_fn = NativeFunctionValue::new(function, fn_type);
@@ -1,3 +1,4 @@
+use crate::runtime::types::collections::array::join_elements;
 use crate::runtime::{ArrayValue, FromRaccoon, Registrar, RuntimeValue, ToRaccoon};
 
 pub fn register_array_module(registrar: &mut Registrar) {
@@ -5,7 +6,7 @@ pub fn register_array_module(registrar: &mut Registrar) {
         "length",
         Some("array"),
         |args| match &args[0] {
-            RuntimeValue::Array(list) => (list.elements.len() as i32).to_raccoon(),
+            RuntimeValue::Array(list) => (list.len() as i32).to_raccoon(),
             _ => (0i32).to_raccoon(),
         },
         1,
@@ -17,7 +18,7 @@ pub fn register_array_module(registrar: &mut Registrar) {
         Some("array"),
         |args| match &args[0] {
             RuntimeValue::Array(list) => {
-                let mut new_elements = list.elements.clone();
+                let mut new_elements = list.snapshot();
                 if args.len() > 1 {
                     new_elements.push(args[1].clone());
                 }
@@ -34,10 +35,12 @@ pub fn register_array_module(registrar: &mut Registrar) {
         Some("array"),
         |args| match &args[0] {
             RuntimeValue::Array(list) => {
-                if list.elements.is_empty() {
+                if list.is_empty() {
                     RuntimeValue::Null(crate::runtime::NullValue::new())
                 } else {
                     list.elements
+                        .read()
+                        .unwrap()
                         .last()
                         .cloned()
                         .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()))
@@ -54,10 +57,12 @@ pub fn register_array_module(registrar: &mut Registrar) {
         Some("array"),
         |args| match &args[0] {
             RuntimeValue::Array(list) => {
-                if list.elements.is_empty() {
+                if list.is_empty() {
                     RuntimeValue::Null(crate::runtime::NullValue::new())
                 } else {
                     list.elements
+                        .read()
+                        .unwrap()
                         .first()
                         .cloned()
                         .unwrap_or(RuntimeValue::Null(crate::runtime::NullValue::new()))
@@ -75,14 +80,13 @@ pub fn register_array_module(registrar: &mut Registrar) {
         |args| match &args[0] {
             RuntimeValue::Array(list) => {
                 let start = i32::from_raccoon(&args[1]).unwrap_or(0) as usize;
-                let end =
-                    i32::from_raccoon(&args[2]).unwrap_or(list.elements.len() as i32) as usize;
+                let end = i32::from_raccoon(&args[2]).unwrap_or(list.len() as i32) as usize;
 
-                let start = start.min(list.elements.len());
-                let end = end.min(list.elements.len());
+                let start = start.min(list.len());
+                let end = end.min(list.len());
 
                 if start <= end {
-                    let sliced = list.elements[start..end].to_vec();
+                    let sliced = list.elements.read().unwrap()[start..end].to_vec();
                     RuntimeValue::Array(ArrayValue::new(sliced, list.element_type.clone()))
                 } else {
                     RuntimeValue::Array(ArrayValue::new(vec![], list.element_type.clone()))
@@ -102,7 +106,7 @@ pub fn register_array_module(registrar: &mut Registrar) {
         Some("array"),
         |args| match &args[0] {
             RuntimeValue::Array(list) => {
-                let mut reversed = list.elements.clone();
+                let mut reversed = list.snapshot();
                 reversed.reverse();
                 RuntimeValue::Array(ArrayValue::new(reversed, list.element_type.clone()))
             }
@@ -119,6 +123,8 @@ pub fn register_array_module(registrar: &mut Registrar) {
             RuntimeValue::Array(list) => {
                 let found = list
                     .elements
+                    .read()
+                    .unwrap()
                     .iter()
                     .any(|elem| elem.to_string() == args[1].to_string());
                 found.to_raccoon()
@@ -134,7 +140,7 @@ pub fn register_array_module(registrar: &mut Registrar) {
         Some("array"),
         |args| match &args[0] {
             RuntimeValue::Array(list) => {
-                for (i, elem) in list.elements.iter().enumerate() {
+                for (i, elem) in list.elements.read().unwrap().iter().enumerate() {
                     if elem.to_string() == args[1].to_string() {
                         return (i as i32).to_raccoon();
                     }
@@ -153,13 +159,7 @@ pub fn register_array_module(registrar: &mut Registrar) {
         |args| match &args[0] {
             RuntimeValue::Array(list) => {
                 let sep = String::from_raccoon(&args[1]).unwrap_or_default();
-                let joined = list
-                    .elements
-                    .iter()
-                    .map(|e| e.to_string())
-                    .collect::<Vec<_>>()
-                    .join(&sep);
-                joined.to_raccoon()
+                join_elements(&list.elements.read().unwrap(), &sep).to_raccoon()
             }
             _ => String::new().to_raccoon(),
         },
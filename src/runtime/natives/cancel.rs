@@ -0,0 +1,148 @@
+use crate::ast::types::PrimitiveType;
+use crate::fn_type;
+use crate::runtime::{
+    CancellationToken, ClassInstance, Environment, FromRaccoon, FutureValue, NativeFunctionValue,
+    NativeThunk, NullValue, RuntimeValue, ToRaccoon,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+thread_local! {
+    static SIGNAL_TEMPLATE: RefCell<Option<ClassInstance>> = const { RefCell::new(None) };
+}
+
+/// The token behind an `AbortSignal`, given the signal itself or its id.
+pub fn signal_token(value: &RuntimeValue) -> Option<CancellationToken> {
+    let id = match value {
+        RuntimeValue::ClassInstance(instance) => {
+            instance.properties.read().unwrap().get("id")?.clone()
+        }
+        RuntimeValue::Object(object) => object.get("id")?,
+        other => other.clone(),
+    };
+    let id = i64::from_raccoon(&id).ok()?;
+    CancellationToken::lookup(id as u64)
+}
+
+fn token_arg(args: &[RuntimeValue], index: usize) -> Option<CancellationToken> {
+    args.get(index).and_then(signal_token)
+}
+
+/// Resolves with `null` after `ms` milliseconds, or rejects as soon as `token` is cancelled.
+pub fn sleep(ms: u64, token: Option<CancellationToken>) -> FutureValue {
+    let timer = FutureValue::new(PrimitiveType::void());
+    let done = timer.clone();
+    tokio::task::spawn_local(async move {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        done.resolve(RuntimeValue::Null(NullValue::new()));
+    });
+
+    match token {
+        Some(token) => timer.with_cancellation(&token),
+        None => timer,
+    }
+}
+
+/// A new `AbortSignal` on a fresh token, cancelled after `timeout` milliseconds when given.
+fn new_signal(timeout: Option<u64>) -> RuntimeValue {
+    let token = CancellationToken::new();
+    if let Some(ms) = timeout {
+        let timer = token.clone();
+        tokio::task::spawn_local(async move {
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+            timer.cancel("The operation timed out".to_string());
+        });
+    }
+
+    let id = (token.id() as i64).to_raccoon();
+    SIGNAL_TEMPLATE.with(|template| match &*template.borrow() {
+        Some(template) => {
+            let mut signal = template.clone();
+            signal.properties = Arc::new(RwLock::new(HashMap::from([("id".to_string(), id)])));
+            signal.frozen = Arc::new(AtomicBool::new(false));
+            signal.constructed = Arc::new(AtomicBool::new(true));
+            RuntimeValue::ClassInstance(Arc::new(signal))
+        }
+        None => id,
+    })
+}
+
+pub fn register_cancel_wrappers(env: &mut Environment) {
+    let natives: [(&str, NativeThunk); 6] = [
+        ("_native_abort_signal_class", || {
+            NativeFunctionValue::new(
+                |args| {
+                    if let Some(RuntimeValue::ClassInstance(signal)) = args.first() {
+                        SIGNAL_TEMPLATE.with(|template| {
+                            *template.borrow_mut() = Some((**signal).clone());
+                        });
+                    }
+                    RuntimeValue::Null(NullValue::new())
+                },
+                fn_type!(variadic, PrimitiveType::void()),
+            )
+        }),
+        ("_native_abort_signal", || {
+            NativeFunctionValue::new(
+                |args| {
+                    let timeout = args.first().and_then(|ms| i64::from_raccoon(ms).ok());
+                    new_signal(timeout.map(|ms| ms.max(0) as u64))
+                },
+                fn_type!(variadic, PrimitiveType::any()),
+            )
+        }),
+        ("_native_abort", || {
+            NativeFunctionValue::new(
+                |args| {
+                    let reason = match args.get(1) {
+                        None | Some(RuntimeValue::Null(_)) => "The operation was aborted".into(),
+                        Some(reason) => reason.to_string(),
+                    };
+                    if let Some(token) = token_arg(&args, 0) {
+                        token.cancel(reason);
+                    }
+                    RuntimeValue::Null(NullValue::new())
+                },
+                fn_type!(variadic, PrimitiveType::void()),
+            )
+        }),
+        ("_native_abort_reason", || {
+            NativeFunctionValue::new(
+                |args| match token_arg(&args, 0).and_then(|token| token.reason()) {
+                    Some(reason) => reason.to_raccoon(),
+                    None => RuntimeValue::Null(NullValue::new()),
+                },
+                fn_type!(variadic, PrimitiveType::any()),
+            )
+        }),
+        ("_native_abort_sleep", || {
+            NativeFunctionValue::new(
+                |args| {
+                    let ms = args.first().and_then(|ms| i64::from_raccoon(ms).ok());
+                    let future = sleep(ms.unwrap_or(0).max(0) as u64, token_arg(&args, 1));
+                    RuntimeValue::Future(future)
+                },
+                fn_type!(variadic, PrimitiveType::any()),
+            )
+        }),
+        ("_native_abort_bind", || {
+            NativeFunctionValue::new(
+                |args| match (args.first(), token_arg(&args, 1)) {
+                    (Some(RuntimeValue::Future(future)), Some(token)) => {
+                        RuntimeValue::Future(future.with_cancellation(&token))
+                    }
+                    (Some(value), _) => value.clone(),
+                    (None, _) => RuntimeValue::Null(NullValue::new()),
+                },
+                fn_type!(variadic, PrimitiveType::any()),
+            )
+        }),
+    ];
+
+    for (name, native) in natives {
+        env.declare_lazy(name, move || RuntimeValue::NativeFunction(native()));
+    }
+}
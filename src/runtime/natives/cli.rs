@@ -0,0 +1,612 @@
+use crate::ast::types::PrimitiveType;
+use crate::fn_type;
+use crate::runtime::values::NativeFn;
+use crate::runtime::{
+    ArrayValue, BoolValue, Environment, FloatValue, IntValue, NativeFunctionValue, NullValue,
+    ObjectValue, RuntimeValue, StrValue,
+};
+use indexmap::IndexMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Bool,
+    Str,
+    Int,
+    Float,
+    List,
+}
+
+impl Kind {
+    fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "bool" => Ok(Kind::Bool),
+            "str" => Ok(Kind::Str),
+            "int" => Ok(Kind::Int),
+            "float" => Ok(Kind::Float),
+            "list" => Ok(Kind::List),
+            other => Err(format!(
+                "unknown kind '{}' (expected bool, str, int, float or list)",
+                other
+            )),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Bool => "bool",
+            Kind::Str => "str",
+            Kind::Int => "int",
+            Kind::Float => "float",
+            Kind::List => "list",
+        }
+    }
+
+    fn convert(self, what: &str, text: &str) -> Result<RuntimeValue, String> {
+        let invalid = || format!("{} expects {}, got '{}'", what, self.name(), text);
+        match self {
+            Kind::Bool => match text {
+                "true" | "yes" | "1" => Ok(RuntimeValue::Bool(BoolValue::new(true))),
+                "false" | "no" | "0" => Ok(RuntimeValue::Bool(BoolValue::new(false))),
+                _ => Err(invalid()),
+            },
+            Kind::Str | Kind::List => Ok(RuntimeValue::Str(StrValue::new(text.to_string()))),
+            Kind::Int => text
+                .parse::<i64>()
+                .map(|value| RuntimeValue::Int(IntValue::new(value)))
+                .map_err(|_| invalid()),
+            Kind::Float => text
+                .parse::<f64>()
+                .map(|value| RuntimeValue::Float(FloatValue::new(value)))
+                .map_err(|_| invalid()),
+        }
+    }
+}
+
+fn null() -> RuntimeValue {
+    RuntimeValue::Null(NullValue::new())
+}
+
+fn field(spec: &ObjectValue, name: &str) -> Option<RuntimeValue> {
+    spec.get(name)
+        .filter(|value| !matches!(value, RuntimeValue::Null(_)))
+}
+
+fn text_field(spec: &ObjectValue, name: &str) -> String {
+    field(spec, name).map_or(String::new(), |value| value.to_string())
+}
+
+fn bool_field(spec: &ObjectValue, name: &str) -> bool {
+    matches!(field(spec, name), Some(RuntimeValue::Bool(flag)) if flag.value)
+}
+
+fn object_field(spec: &ObjectValue, name: &str) -> Result<Option<ObjectValue>, String> {
+    match field(spec, name) {
+        None => Ok(None),
+        Some(RuntimeValue::Object(object)) => Ok(Some(object)),
+        Some(other) => Err(format!(
+            "'{}' must be an object, got {}",
+            name,
+            other.get_name()
+        )),
+    }
+}
+
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::new();
+    for ch in name.chars() {
+        if ch.is_uppercase() {
+            kebab.push('-');
+            kebab.extend(ch.to_lowercase());
+        } else {
+            kebab.push(if ch == '_' { '-' } else { ch });
+        }
+    }
+    kebab
+}
+
+struct Flag {
+    key: String,
+    long: String,
+    short: Option<char>,
+    kind: Kind,
+    default: RuntimeValue,
+    required: bool,
+    help: String,
+    choices: Vec<String>,
+}
+
+impl Flag {
+    fn parse(key: &str, spec: &RuntimeValue) -> Result<Self, String> {
+        let RuntimeValue::Object(spec) = spec else {
+            return Err(format!("flag '{}' must be described by an object", key));
+        };
+        let kind = match field(spec, "kind") {
+            Some(kind) => Kind::from_name(&kind.to_string())?,
+            None => Kind::Bool,
+        };
+        let short = match text_field(spec, "short").chars().collect::<Vec<_>>()[..] {
+            [] => None,
+            [short] => Some(short),
+            _ => return Err(format!("short name of flag '{}' must be one letter", key)),
+        };
+        let default = match (field(spec, "fallback"), kind) {
+            (Some(default), _) => default,
+            (None, Kind::Bool) => RuntimeValue::Bool(BoolValue::new(false)),
+            (None, Kind::List) => {
+                RuntimeValue::Array(ArrayValue::new(Vec::new(), PrimitiveType::str()))
+            }
+            (None, _) => null(),
+        };
+        let choices = match field(spec, "choices") {
+            Some(RuntimeValue::Array(list)) => {
+                list.snapshot().iter().map(|c| c.to_string()).collect()
+            }
+            _ => Vec::new(),
+        };
+        Ok(Self {
+            key: key.to_string(),
+            long: kebab_case(key),
+            short,
+            kind,
+            default,
+            required: bool_field(spec, "required"),
+            help: text_field(spec, "help"),
+            choices,
+        })
+    }
+
+    fn usage(&self) -> String {
+        let short = self
+            .short
+            .map_or("    ".to_string(), |s| format!("-{}, ", s));
+        match self.kind {
+            Kind::Bool => format!("{}--{}", short, self.long),
+            kind => format!("{}--{} <{}>", short, self.long, kind.name()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        let mut notes = Vec::new();
+        if !self.choices.is_empty() {
+            notes.push(format!("one of: {}", self.choices.join(", ")));
+        }
+        if self.required {
+            notes.push("required".to_string());
+        } else if !matches!(
+            (&self.default, self.kind),
+            (RuntimeValue::Null(_), _) | (_, Kind::Bool) | (_, Kind::List)
+        ) {
+            notes.push(format!("default: {}", self.default.to_string()));
+        }
+        match (self.help.is_empty(), notes.is_empty()) {
+            (_, true) => self.help.clone(),
+            (true, false) => format!("({})", notes.join("; ")),
+            (false, false) => format!("{} ({})", self.help, notes.join("; ")),
+        }
+    }
+}
+
+struct Positional {
+    name: String,
+    kind: Kind,
+    default: RuntimeValue,
+    required: bool,
+    variadic: bool,
+    help: String,
+}
+
+impl Positional {
+    fn parse(spec: &RuntimeValue) -> Result<Self, String> {
+        let spec = match spec {
+            RuntimeValue::Str(name) => ObjectValue::new(
+                [("name".to_string(), RuntimeValue::Str(name.clone()))],
+                PrimitiveType::any(),
+            ),
+            RuntimeValue::Object(spec) => spec.clone(),
+            other => {
+                return Err(format!(
+                    "positionals must be names or objects, got {}",
+                    other.get_name()
+                ))
+            }
+        };
+        let name = text_field(&spec, "name");
+        if name.is_empty() {
+            return Err("every positional needs a name".to_string());
+        }
+        let kind = match field(&spec, "kind") {
+            Some(kind) => Kind::from_name(&kind.to_string())?,
+            None => Kind::Str,
+        };
+        let default = field(&spec, "fallback").unwrap_or_else(null);
+        let required = match field(&spec, "required") {
+            Some(RuntimeValue::Bool(flag)) => flag.value,
+            _ => matches!(default, RuntimeValue::Null(_)) && !bool_field(&spec, "variadic"),
+        };
+        Ok(Self {
+            name,
+            kind,
+            default,
+            required,
+            variadic: bool_field(&spec, "variadic"),
+            help: text_field(&spec, "help"),
+        })
+    }
+
+    fn usage(&self) -> String {
+        match (self.required, self.variadic) {
+            (true, true) => format!("<{}...>", self.name),
+            (true, false) => format!("<{}>", self.name),
+            (false, true) => format!("[{}...]", self.name),
+            (false, false) => format!("[{}]", self.name),
+        }
+    }
+}
+
+struct Command {
+    name: String,
+    description: String,
+    version: Option<String>,
+    flags: Vec<Flag>,
+    positionals: Vec<Positional>,
+    commands: Vec<Command>,
+}
+
+impl Command {
+    fn parse(name: String, spec: &ObjectValue) -> Result<Self, String> {
+        let flags = match object_field(spec, "flags")? {
+            Some(flags) => flags
+                .snapshot()
+                .iter()
+                .map(|(key, flag)| Flag::parse(key, flag))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+        let positionals = match field(spec, "positionals") {
+            Some(RuntimeValue::Array(list)) => list
+                .snapshot()
+                .iter()
+                .map(Positional::parse)
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(other) => {
+                return Err(format!(
+                    "'positionals' must be a list, got {}",
+                    other.get_name()
+                ))
+            }
+            None => Vec::new(),
+        };
+        let commands = match object_field(spec, "commands")? {
+            Some(commands) => commands
+                .snapshot()
+                .iter()
+                .map(|(name, command)| match command {
+                    RuntimeValue::Object(command) => Command::parse(name.clone(), command),
+                    _ => Err(format!("command '{}' must be described by an object", name)),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            name,
+            description: text_field(spec, "description"),
+            version: field(spec, "version").map(|version| version.to_string()),
+            flags,
+            positionals,
+            commands,
+        })
+    }
+
+    fn find_long(&self, name: &str) -> Option<&Flag> {
+        self.flags
+            .iter()
+            .find(|flag| flag.long == name || flag.key == name)
+    }
+
+    fn help(&self, path: &str) -> String {
+        let mut out = String::new();
+        let title = match &self.version {
+            Some(version) => format!("{} {}", path, version),
+            None => path.to_string(),
+        };
+        out.push_str(&title);
+        if !self.description.is_empty() {
+            out.push_str(&format!(" - {}", self.description));
+        }
+
+        let mut usage = format!("\n\nUsage: {} [options]", path);
+        for positional in &self.positionals {
+            usage.push_str(&format!(" {}", positional.usage()));
+        }
+        if !self.commands.is_empty() {
+            usage.push_str(" <command>");
+        }
+        out.push_str(&usage);
+
+        let mut section = |title: &str, rows: Vec<(String, String)>| {
+            if rows.is_empty() {
+                return;
+            }
+            let width = rows.iter().map(|(left, _)| left.len()).max().unwrap_or(0);
+            out.push_str(&format!("\n\n{}:", title));
+            for (left, right) in rows {
+                let line = format!("  {:width$}  {}", left, right, width = width);
+                out.push_str(&format!("\n{}", line.trim_end()));
+            }
+        };
+
+        section(
+            "Arguments",
+            self.positionals
+                .iter()
+                .map(|p| (p.name.clone(), p.help.clone()))
+                .collect(),
+        );
+        section(
+            "Commands",
+            self.commands
+                .iter()
+                .map(|c| (c.name.clone(), c.description.clone()))
+                .collect(),
+        );
+        let mut options: Vec<(String, String)> = self
+            .flags
+            .iter()
+            .map(|flag| (flag.usage(), flag.describe()))
+            .collect();
+        options.push(("-h, --help".to_string(), "Show this help".to_string()));
+        if self.version.is_some() {
+            options.push(("    --version".to_string(), "Show the version".to_string()));
+        }
+        section("Options", options);
+        out
+    }
+}
+
+enum Outcome {
+    Parsed(IndexMap<String, RuntimeValue>),
+    Show(String),
+}
+
+fn set_flag(
+    values: &mut IndexMap<String, RuntimeValue>,
+    flag: &Flag,
+    text: &str,
+) -> Result<(), String> {
+    let what = format!("--{}", flag.long);
+    if !flag.choices.is_empty() && !flag.choices.iter().any(|choice| choice == text) {
+        return Err(format!(
+            "{} must be one of {}, got '{}'",
+            what,
+            flag.choices.join(", "),
+            text
+        ));
+    }
+    let value = flag.kind.convert(&what, text)?;
+    match (flag.kind, values.get(&flag.key)) {
+        (Kind::List, Some(RuntimeValue::Array(list))) => {
+            let mut elements = list.snapshot();
+            elements.push(value);
+            values.insert(
+                flag.key.clone(),
+                RuntimeValue::Array(ArrayValue::new(elements, PrimitiveType::str())),
+            );
+        }
+        _ => {
+            values.insert(flag.key.clone(), value);
+        }
+    }
+    Ok(())
+}
+
+fn parse_command(command: &Command, path: &str, args: &[String]) -> Result<Outcome, String> {
+    let mut values: IndexMap<String, RuntimeValue> = command
+        .flags
+        .iter()
+        .map(|flag| (flag.key.clone(), flag.default.clone()))
+        .collect();
+    let mut given: Vec<&str> = Vec::new();
+    let mut words: Vec<&String> = Vec::new();
+    let mut subcommand = None;
+    let mut only_words = false;
+
+    let mut index = 0;
+    while index < args.len() {
+        let arg = &args[index];
+        index += 1;
+
+        if only_words || arg == "-" || !arg.starts_with('-') || arg.parse::<f64>().is_ok() {
+            if words.is_empty() && !command.commands.is_empty() && !only_words {
+                let sub = command
+                    .commands
+                    .iter()
+                    .find(|sub| &sub.name == arg)
+                    .ok_or_else(|| format!("unknown command '{}' for '{}'", arg, path))?;
+                subcommand = Some((sub, index));
+                break;
+            }
+            words.push(arg);
+            continue;
+        }
+        if arg == "--" {
+            only_words = true;
+            continue;
+        }
+        if arg == "--help" || arg == "-h" {
+            return Ok(Outcome::Show(command.help(path)));
+        }
+        if arg == "--version" {
+            if let Some(version) = &command.version {
+                return Ok(Outcome::Show(format!("{} {}", path, version)));
+            }
+        }
+
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+            let negated = name
+                .strip_prefix("no-")
+                .and_then(|name| command.find_long(name))
+                .filter(|flag| flag.kind == Kind::Bool && inline.is_none());
+            if let Some(flag) = negated {
+                values.insert(flag.key.clone(), RuntimeValue::Bool(BoolValue::new(false)));
+                given.push(&flag.key);
+                continue;
+            }
+            let flag = command
+                .find_long(name)
+                .ok_or_else(|| format!("unknown option '--{}'", name))?;
+            let text = match (inline, flag.kind) {
+                (Some(text), _) => text,
+                (None, Kind::Bool) => "true".to_string(),
+                (None, _) => {
+                    index += 1;
+                    args.get(index - 1)
+                        .cloned()
+                        .ok_or_else(|| format!("--{} needs a value", flag.long))?
+                }
+            };
+            set_flag(&mut values, flag, &text)?;
+            given.push(&flag.key);
+            continue;
+        }
+
+        // A cluster of short flags: `-vq`, or `-o file` / `-ofile` for one taking a value.
+        let cluster: Vec<char> = arg[1..].chars().collect();
+        for (position, short) in cluster.iter().enumerate() {
+            let flag = command
+                .flags
+                .iter()
+                .find(|flag| flag.short == Some(*short))
+                .ok_or_else(|| format!("unknown option '-{}'", short))?;
+            given.push(&flag.key);
+            if flag.kind == Kind::Bool {
+                values.insert(flag.key.clone(), RuntimeValue::Bool(BoolValue::new(true)));
+                continue;
+            }
+            let rest: String = cluster[position + 1..].iter().collect();
+            let text = if rest.is_empty() {
+                index += 1;
+                args.get(index - 1)
+                    .cloned()
+                    .ok_or_else(|| format!("-{} needs a value", short))?
+            } else {
+                rest
+            };
+            set_flag(&mut values, flag, &text)?;
+            break;
+        }
+    }
+
+    if let Some(flag) = command
+        .flags
+        .iter()
+        .find(|flag| flag.required && !given.contains(&flag.key.as_str()))
+    {
+        return Err(format!("missing required option --{}", flag.long));
+    }
+
+    let mut words = words.into_iter();
+    for positional in &command.positionals {
+        let value = if positional.variadic {
+            let rest = words
+                .by_ref()
+                .map(|word| positional.kind.convert(&positional.name, word))
+                .collect::<Result<Vec<_>, _>>()?;
+            if rest.is_empty() && positional.required {
+                return Err(format!("missing argument <{}>", positional.name));
+            }
+            RuntimeValue::Array(ArrayValue::new(rest, PrimitiveType::any()))
+        } else {
+            match words.next() {
+                Some(word) => positional.kind.convert(&positional.name, word)?,
+                None if positional.required => {
+                    return Err(format!("missing argument <{}>", positional.name))
+                }
+                None => positional.default.clone(),
+            }
+        };
+        values.insert(positional.name.clone(), value);
+    }
+    if let Some(extra) = words.next() {
+        return Err(format!("unexpected argument '{}'", extra));
+    }
+
+    if !command.commands.is_empty() {
+        let name = match subcommand {
+            Some((sub, rest)) => {
+                let sub_path = format!("{} {}", path, sub.name);
+                match parse_command(sub, &sub_path, &args[rest..])? {
+                    Outcome::Parsed(parsed) => values.insert(sub.name.clone(), object(parsed)),
+                    show => return Ok(show),
+                };
+                RuntimeValue::Str(StrValue::new(sub.name.clone()))
+            }
+            None => null(),
+        };
+        values.insert("command".to_string(), name);
+    }
+
+    Ok(Outcome::Parsed(values))
+}
+
+fn object(values: IndexMap<String, RuntimeValue>) -> RuntimeValue {
+    RuntimeValue::Object(ObjectValue::new(values, PrimitiveType::any()))
+}
+
+fn program(args: &[RuntimeValue]) -> Result<Command, String> {
+    match args.first() {
+        Some(RuntimeValue::Object(spec)) => {
+            let name = match text_field(spec, "name") {
+                name if name.is_empty() => "program".to_string(),
+                name => name,
+            };
+            Command::parse(name, spec)
+        }
+        _ => Err("the spec must be an object".to_string()),
+    }
+}
+
+fn result(value: RuntimeValue, error: RuntimeValue) -> RuntimeValue {
+    object(IndexMap::from([
+        ("value".to_string(), value),
+        ("error".to_string(), error),
+    ]))
+}
+
+fn parse(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let words: Vec<String> = match args.get(1) {
+        Some(RuntimeValue::Array(list)) => list.snapshot().iter().map(|w| w.to_string()).collect(),
+        _ => Vec::new(),
+    };
+    match program(&args).and_then(|command| parse_command(&command, &command.name, &words)) {
+        Ok(Outcome::Parsed(values)) => result(object(values), null()),
+        Ok(Outcome::Show(text)) => {
+            println!("{}", text);
+            std::process::exit(0);
+        }
+        Err(error) => result(null(), RuntimeValue::Str(StrValue::new(error))),
+    }
+}
+
+fn help(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let text = match program(&args) {
+        Ok(command) => command.help(&command.name),
+        Err(error) => format!("invalid spec: {}", error),
+    };
+    RuntimeValue::Str(StrValue::new(text))
+}
+
+pub fn register_cli_wrappers(env: &mut Environment) {
+    let natives: [(&str, NativeFn); 2] = [("parse", parse), ("help", help)];
+    for (name, implementation) in natives {
+        env.declare_lazy(format!("_native_cli_{}", name), move || {
+            RuntimeValue::NativeFunction(NativeFunctionValue::new(
+                implementation,
+                fn_type!(variadic, PrimitiveType::any()),
+            ))
+        });
+    }
+}
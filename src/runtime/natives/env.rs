@@ -0,0 +1,215 @@
+use crate::ast::types::PrimitiveType;
+use crate::runtime::{
+    FromRaccoon, FutureValue, ObjectValue, Registrar, RuntimeValue, StrValue, ToRaccoon,
+};
+use indexmap::IndexMap;
+use std::fs;
+
+/// Replaces `$NAME`, `${NAME}` and `${NAME:-fallback}` with what `lookup` gives.
+pub fn expand(text: &str, lookup: &dyn Fn(&str) -> Option<String>) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '$' {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        match chars.get(i + 1) {
+            Some('$') => {
+                out.push('$');
+                i += 2;
+            }
+            Some('{') => {
+                let Some(close) = closing_brace(&chars, i + 2) else {
+                    out.extend(&chars[i..]);
+                    break;
+                };
+                let inner: String = chars[i + 2..close].iter().collect();
+                let value = match inner.split_once(":-") {
+                    Some((name, fallback)) => lookup(name)
+                        .filter(|value| !value.is_empty())
+                        .unwrap_or_else(|| expand(fallback, lookup)),
+                    None => lookup(&inner).unwrap_or_default(),
+                };
+                out.push_str(&value);
+                i = close + 1;
+            }
+            Some(&c) if c == '_' || c.is_ascii_alphabetic() => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c != '_' && !c.is_ascii_alphanumeric())
+                    .map_or(chars.len(), |offset| i + 1 + offset);
+                let name: String = chars[i + 1..end].iter().collect();
+                out.push_str(&lookup(&name).unwrap_or_default());
+                i = end;
+            }
+            _ => {
+                out.push('$');
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn closing_brace(chars: &[char], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, &c) in chars.iter().enumerate().skip(start) {
+        match c {
+            '{' if index > 0 && chars[index - 1] == '$' => depth += 1,
+            '}' if depth == 0 => return Some(index),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Parses a dotenv file into its variables, in file order.
+pub fn parse_dotenv(
+    source: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<IndexMap<String, String>, String> {
+    let mut vars: IndexMap<String, String> = IndexMap::new();
+    let lines: Vec<&str> = source.lines().collect();
+    let mut index = 0;
+    while index < lines.len() {
+        let number = index + 1;
+        let line = lines[index].trim();
+        index += 1;
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        let Some((key, raw)) = line.split_once('=') else {
+            return Err(format!("line {}: expected KEY=VALUE", number));
+        };
+        let key = key.trim();
+        if key.is_empty()
+            || key.starts_with(|c: char| c.is_ascii_digit())
+            || !key.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
+        {
+            return Err(format!("line {}: invalid variable name '{}'", number, key));
+        }
+
+        let raw = raw.trim_start();
+        let resolve = |name: &str| vars.get(name).cloned().or_else(|| lookup(name));
+        let value = if let Some(rest) = raw.strip_prefix('\'') {
+            match rest.find('\'') {
+                Some(end) => rest[..end].to_string(),
+                None => return Err(format!("line {}: unterminated single quote", number)),
+            }
+        } else if let Some(rest) = raw.strip_prefix('"') {
+            let mut quoted = rest.to_string();
+            let end = loop {
+                if let Some(end) = closing_quote(&quoted) {
+                    break end;
+                }
+                let Some(next) = lines.get(index) else {
+                    return Err(format!("line {}: unterminated double quote", number));
+                };
+                quoted.push('\n');
+                quoted.push_str(next);
+                index += 1;
+            };
+            expand(&unescape(&quoted[..end]), &resolve)
+        } else {
+            let value = match raw.find(" #") {
+                Some(comment) => &raw[..comment],
+                None => raw,
+            };
+            expand(value.trim_end(), &resolve)
+        };
+        vars.insert(key.to_string(), value);
+    }
+    Ok(vars)
+}
+
+fn closing_quote(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(index),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn rejected(message: String) -> RuntimeValue {
+    RuntimeValue::Future(FutureValue::new_rejected(
+        format!("EnvError: {}", message),
+        PrimitiveType::any(),
+    ))
+}
+
+fn process_var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// `core_env_load(path, {override})`: loads a dotenv file into the environment.
+pub fn core_env_load(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let path = String::from_raccoon(&args[0]).unwrap_or_default();
+    let overwrite = match args.get(1) {
+        Some(RuntimeValue::Object(options)) => {
+            matches!(options.get("override"), Some(RuntimeValue::Bool(flag)) if flag.value)
+        }
+        _ => false,
+    };
+
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(error) => return rejected(format!("cannot read '{}': {}", path, error)),
+    };
+    let vars = match parse_dotenv(&source, &process_var) {
+        Ok(vars) => vars,
+        Err(error) => return rejected(format!("{}: {}", path, error)),
+    };
+
+    for (key, value) in &vars {
+        if overwrite || std::env::var_os(key).is_none() {
+            std::env::set_var(key, value);
+        }
+    }
+    let properties = vars
+        .into_iter()
+        .map(|(key, value)| (key, RuntimeValue::Str(StrValue::new(value))));
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+/// `core_env_expand(text)`: `text` with environment variables substituted.
+pub fn core_env_expand(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let text = args
+        .first()
+        .and_then(|arg| String::from_raccoon(arg).ok())
+        .unwrap_or_default();
+    expand(&text, &process_var).to_raccoon()
+}
+
+pub fn register_env_primitives(registrar: &mut Registrar) {
+    registrar.register_restricted_fn("core_env_load", "process", core_env_load, 1, Some(2));
+    registrar.register_restricted_fn("core_env_expand", "process", core_env_expand, 1, Some(1));
+}
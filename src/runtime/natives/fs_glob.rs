@@ -0,0 +1,380 @@
+use crate::ast::types::PrimitiveType;
+use crate::runtime::{
+    FromRaccoon, NativeAsyncFunctionValue, NullValue, ObjectValue, Registrar, RuntimeValue,
+    ToRaccoon,
+};
+use glob::{MatchOptions, Pattern};
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: true,
+};
+
+const IGNORE_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Expands `{a,b}` alternatives into one pattern per combination.
+pub fn expand_braces(pattern: &str) -> Vec<String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut open = 0;
+    while let Some(start) = chars[open..]
+        .iter()
+        .position(|&c| c == '{')
+        .map(|i| i + open)
+    {
+        let mut depth = 0;
+        let mut commas = Vec::new();
+        let mut close = None;
+        for (i, &c) in chars.iter().enumerate().skip(start) {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = Some(i);
+                        break;
+                    }
+                }
+                ',' if depth == 1 => commas.push(i),
+                _ => {}
+            }
+        }
+        let Some(close) = close else {
+            break;
+        };
+        if commas.is_empty() {
+            open = start + 1;
+            continue;
+        }
+
+        let prefix: String = chars[..start].iter().collect();
+        let suffix: String = chars[close + 1..].iter().collect();
+        let mut bounds = vec![start];
+        bounds.extend(&commas);
+        bounds.push(close);
+        return bounds
+            .windows(2)
+            .flat_map(|window| {
+                let alternative: String = chars[window[0] + 1..window[1]].iter().collect();
+                expand_braces(&format!("{}{}{}", prefix, alternative, suffix))
+            })
+            .collect();
+    }
+    vec![pattern.to_string()]
+}
+
+fn has_wildcards(component: &str) -> bool {
+    component.contains(['*', '?', '['])
+}
+
+struct Root {
+    prefix: String,
+    patterns: Vec<Pattern>,
+    max_depth: Option<usize>,
+}
+
+fn roots(patterns: &[String]) -> Result<Vec<Root>, String> {
+    let mut roots: Vec<Root> = Vec::new();
+    for pattern in patterns {
+        let components: Vec<&str> = pattern.split('/').collect();
+        let literal = components[..components.len() - 1]
+            .iter()
+            .take_while(|component| !has_wildcards(component))
+            .count();
+        let prefix = match components[..literal].join("/") {
+            joined if literal == 0 => joined,
+            joined => format!("{}/", joined),
+        };
+        let depth = (!pattern.contains("**")).then_some(components.len() - literal);
+        let compiled = Pattern::new(pattern)
+            .map_err(|error| format!("invalid pattern '{}': {}", pattern, error.msg))?;
+
+        match roots.iter_mut().find(|root| root.prefix == prefix) {
+            Some(root) => {
+                root.patterns.push(compiled);
+                root.max_depth = root.max_depth.zip(depth).map(|(a, b)| a.max(b));
+            }
+            None => roots.push(Root {
+                prefix,
+                patterns: vec![compiled],
+                max_depth: depth,
+            }),
+        }
+    }
+    Ok(roots)
+}
+
+struct IgnoreRule {
+    base: PathBuf,
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+}
+
+impl IgnoreRule {
+    fn parse(base: &Path, line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        // A slash anywhere but the end anchors the pattern to the .gitignore's directory.
+        let pattern = match line.strip_prefix('/') {
+            Some(anchored) => anchored.to_string(),
+            None if line.contains('/') => line.to_string(),
+            None => format!("**/{}", line),
+        };
+        Some(Self {
+            base: base.to_path_buf(),
+            pattern: Pattern::new(&pattern).ok()?,
+            negated,
+            dir_only,
+        })
+    }
+
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        path.strip_prefix(&self.base)
+            .is_ok_and(|relative| self.pattern.matches_path_with(relative, IGNORE_OPTIONS))
+    }
+}
+
+fn load_ignore_file(dir: &Path, rules: &mut Vec<IgnoreRule>) {
+    if let Ok(contents) = fs::read_to_string(dir.join(".gitignore")) {
+        rules.extend(
+            contents
+                .lines()
+                .filter_map(|line| IgnoreRule::parse(dir, line)),
+        );
+    }
+}
+
+fn ancestor_rules(dir: &Path) -> Vec<IgnoreRule> {
+    let ancestors: Vec<&Path> = dir.ancestors().collect();
+    let repository = ancestors
+        .iter()
+        .position(|ancestor| ancestor.join(".git").exists())
+        .unwrap_or(0);
+    let mut rules = Vec::new();
+    for ancestor in ancestors[..=repository].iter().rev() {
+        load_ignore_file(ancestor, &mut rules);
+    }
+    rules
+}
+
+struct Entry {
+    path: PathBuf,
+    display: String,
+    is_dir: bool,
+    depth: usize,
+}
+
+struct Frame {
+    entries: VecDeque<Entry>,
+    rules_len: usize,
+}
+
+/// Walks the roots depth-first in name order, as far as the caller pulls.
+pub struct GlobWalker {
+    roots: VecDeque<Root>,
+    current: Option<Root>,
+    stack: Vec<Frame>,
+    rules: Vec<IgnoreRule>,
+    gitignore: bool,
+    seen: HashSet<String>,
+}
+
+impl GlobWalker {
+    pub fn new(pattern: &str, gitignore: bool) -> Result<Self, String> {
+        let patterns = expand_braces(pattern);
+        Ok(Self {
+            roots: roots(&patterns)?.into(),
+            current: None,
+            stack: Vec::new(),
+            rules: Vec::new(),
+            gitignore,
+            seen: HashSet::new(),
+        })
+    }
+
+    fn read_dir(dir: &Path, display: &str, depth: usize) -> VecDeque<Entry> {
+        let Ok(read) = fs::read_dir(dir) else {
+            return VecDeque::new();
+        };
+        let mut entries: Vec<Entry> = read
+            .filter_map(Result::ok)
+            .map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                Entry {
+                    is_dir: entry.path().is_dir(),
+                    path: entry.path(),
+                    display: format!("{}{}", display, name),
+                    depth,
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| a.display.cmp(&b.display));
+        entries.into()
+    }
+
+    fn start_root(&mut self, root: Root) {
+        let dir = if root.prefix.is_empty() {
+            PathBuf::from(".")
+        } else {
+            PathBuf::from(&root.prefix)
+        };
+        self.rules.clear();
+        if self.gitignore {
+            let absolute = fs::canonicalize(&dir).unwrap_or_else(|_| dir.clone());
+            self.rules = ancestor_rules(&absolute);
+        }
+        self.stack = vec![Frame {
+            entries: Self::read_dir(&dir, &root.prefix, 1),
+            rules_len: self.rules.len(),
+        }];
+        self.current = Some(root);
+    }
+
+    fn is_ignored(&self, entry: &Entry) -> bool {
+        if !self.gitignore {
+            return false;
+        }
+        if entry.is_dir && entry.path.file_name().is_some_and(|name| name == ".git") {
+            return true;
+        }
+        let absolute = fs::canonicalize(&entry.path).unwrap_or_else(|_| entry.path.clone());
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(&absolute, entry.is_dir))
+            .is_some_and(|rule| !rule.negated)
+    }
+
+    pub fn next_path(&mut self) -> Option<String> {
+        loop {
+            let Some(frame) = self.stack.last_mut() else {
+                let root = self.roots.pop_front()?;
+                self.start_root(root);
+                continue;
+            };
+            let Some(entry) = frame.entries.pop_front() else {
+                let frame = self.stack.pop()?;
+                self.rules.truncate(frame.rules_len);
+                continue;
+            };
+            if self.is_ignored(&entry) {
+                continue;
+            }
+
+            let root = self.current.as_ref()?;
+            let descend = root.max_depth.is_none_or(|max| entry.depth < max);
+            let matched = root
+                .patterns
+                .iter()
+                .any(|pattern| pattern.matches_with(&entry.display, MATCH_OPTIONS));
+
+            if entry.is_dir && descend {
+                let rules_len = self.rules.len();
+                if self.gitignore {
+                    let absolute =
+                        fs::canonicalize(&entry.path).unwrap_or_else(|_| entry.path.clone());
+                    load_ignore_file(&absolute, &mut self.rules);
+                }
+                self.stack.push(Frame {
+                    entries: Self::read_dir(
+                        &entry.path,
+                        &format!("{}/", entry.display),
+                        entry.depth + 1,
+                    ),
+                    rules_len,
+                });
+            }
+            if matched && self.seen.insert(entry.display.clone()) {
+                return Some(entry.display);
+            }
+        }
+    }
+}
+
+fn step(path: Option<String>) -> RuntimeValue {
+    let properties = [
+        ("done".to_string(), path.is_none().to_raccoon()),
+        (
+            "value".to_string(),
+            path.map_or(RuntimeValue::Null(NullValue::new()), |path| {
+                path.to_raccoon()
+            }),
+        ),
+    ];
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+/// `core_fs_glob(pattern, {gitignore})`: a stream over the matching paths.
+pub fn core_fs_glob(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let pattern = args
+        .first()
+        .and_then(|arg| String::from_raccoon(arg).ok())
+        .unwrap_or_default();
+    let gitignore = match args.get(1) {
+        Some(RuntimeValue::Object(options)) => {
+            matches!(options.get("gitignore"), Some(RuntimeValue::Bool(flag)) if flag.value)
+        }
+        _ => false,
+    };
+
+    let Ok(walker) = GlobWalker::new(&pattern, gitignore) else {
+        return RuntimeValue::Null(NullValue::new());
+    };
+    let walker = Arc::new(Mutex::new(walker));
+
+    let stepper = walker.clone();
+    let next = NativeAsyncFunctionValue::new(
+        Arc::new(move |_args| {
+            let path = stepper.lock().unwrap().next_path();
+            Box::pin(async move { step(path) })
+        }),
+        PrimitiveType::any(),
+    );
+    let collect = NativeAsyncFunctionValue::new(
+        Arc::new(move |_args| {
+            let mut walker = walker.lock().unwrap();
+            let paths: Vec<String> = std::iter::from_fn(|| walker.next_path()).collect();
+            Box::pin(async move { paths.to_raccoon() })
+        }),
+        PrimitiveType::any(),
+    );
+
+    let properties = [
+        ("pattern".to_string(), pattern.to_raccoon()),
+        (
+            "next".to_string(),
+            RuntimeValue::NativeAsyncFunction(Arc::new(next)),
+        ),
+        (
+            "collect".to_string(),
+            RuntimeValue::NativeAsyncFunction(Arc::new(collect)),
+        ),
+    ];
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+pub fn register_fs_glob_primitives(registrar: &mut Registrar) {
+    registrar.register_restricted_fn("core_fs_glob", "io", core_fs_glob, 1, Some(2));
+}
@@ -1,79 +1,357 @@
+use super::cancel::signal_token;
+use super::json::{convert_runtime_to_serde, convert_serde_to_runtime};
+use super::network::NetworkConfig;
+use super::url::{append_query, query_pairs};
 use crate::ast::types::PrimitiveType;
 use crate::runtime::{
-    BoolValue, FromRaccoon, FutureValue, IntValue, MapValue, NullValue, ObjectValue, Registrar,
-    RuntimeValue, StrValue, ToRaccoon,
+    ArrayValue, CancellationToken, FromRaccoon, IntValue, NativeAsyncFunctionValue, NullValue,
+    ObjectValue, Registrar, RuntimeValue, StrValue, ToRaccoon,
 };
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::sync::Arc;
+use std::time::Duration;
 
-pub fn register_http_module(registrar: &mut Registrar) {
-    registrar.register_fn(
-        "native_http_fetch_with_options",
-        None::<&str>,
-        |args| {
-            if args.is_empty() {
-                return RuntimeValue::Null(NullValue::new());
-            }
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+const DEFAULT_MAX_REDIRECTS: u32 = 5;
 
-            let url = String::from_raccoon(&args[0]).unwrap_or_default();
+#[derive(Debug, Clone)]
+pub struct RequestOptions {
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+    pub query: Vec<(String, String)>,
+    pub body: Option<String>,
+    pub timeout: Duration,
+    pub max_redirects: u32,
+    pub network: NetworkConfig,
+    pub signal: Option<CancellationToken>,
+}
+
+impl Default for RequestOptions {
+    fn default() -> Self {
+        Self {
+            method: "GET".to_string(),
+            headers: Vec::new(),
+            query: Vec::new(),
+            body: None,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            network: NetworkConfig::new(),
+            signal: None,
+        }
+    }
+}
+
+impl RequestOptions {
+    pub fn from_value(value: Option<&RuntimeValue>) -> Self {
+        let mut options = Self {
+            network: NetworkConfig::global(),
+            ..Self::default()
+        };
+        let Some(RuntimeValue::Object(object)) = value else {
+            return options;
+        };
+        let props = object.snapshot();
+        options.network.apply(&props);
 
-            let method = if args.len() > 1 {
-                if let RuntimeValue::Object(obj) = &args[1] {
-                    if let Some(method_val) = obj.properties.get("method") {
-                        String::from_raccoon(method_val).unwrap_or_else(|_| "GET".to_string())
-                    } else {
-                        "GET".to_string()
-                    }
-                } else {
-                    "GET".to_string()
+        if let Some(method) = props
+            .get("method")
+            .and_then(|m| String::from_raccoon(m).ok())
+        {
+            options.method = method.to_uppercase();
+        }
+
+        if let Some(RuntimeValue::Object(headers)) = props.get("headers") {
+            options.headers = headers
+                .properties
+                .read()
+                .unwrap()
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_string()))
+                .collect();
+        }
+
+        if let Some(query) = props.get("query") {
+            options.query = query_pairs(query);
+        }
+
+        options.body = match props.get("body") {
+            None | Some(RuntimeValue::Null(_)) => None,
+            Some(RuntimeValue::Str(body)) => Some(body.value.clone()),
+            Some(body) => {
+                if !options.has_header("content-type") {
+                    options
+                        .headers
+                        .push(("Content-Type".to_string(), "application/json".to_string()));
                 }
-            } else {
-                "GET".to_string()
-            };
-
-            let mut response_properties = HashMap::new();
-            response_properties.insert("status".to_string(), RuntimeValue::Int(IntValue::new(200)));
-            response_properties.insert(
-                "statusText".to_string(),
-                RuntimeValue::Str(StrValue::new("OK".to_string())),
-            );
-
-            let headers_map =
-                MapValue::new(HashMap::new(), PrimitiveType::str(), PrimitiveType::str());
-            response_properties.insert("headers".to_string(), RuntimeValue::Map(headers_map));
-
-            let body = format!(
-                "{{\"method\":\"{}\",\"url\":\"{}\",\"simulated\":true}}",
-                method, url
-            );
-            response_properties.insert(
-                "body".to_string(),
-                RuntimeValue::Str(StrValue::new(body.clone())),
-            );
-
-            let mut json_properties = HashMap::new();
-            json_properties.insert(
-                "method".to_string(),
-                RuntimeValue::Str(StrValue::new(method)),
-            );
-            json_properties.insert("url".to_string(), RuntimeValue::Str(StrValue::new(url)));
-            json_properties.insert(
-                "simulated".to_string(),
-                RuntimeValue::Bool(BoolValue::new(true)),
-            );
-            let json_obj = ObjectValue::new(json_properties, PrimitiveType::any());
-            response_properties.insert("json".to_string(), RuntimeValue::Object(json_obj));
-
-            let response_obj = ObjectValue::new(response_properties, PrimitiveType::any());
-
-            RuntimeValue::Future(FutureValue::new_resolved(
-                RuntimeValue::Object(response_obj),
-                PrimitiveType::any(),
-            ))
-        },
-        2,
-        Some(2),
+                Some(convert_runtime_to_serde(body).to_string())
+            }
+        };
+
+        if let Some(ms) = props.get("timeout").and_then(|t| i64::from_raccoon(t).ok()) {
+            options.timeout = Duration::from_millis(ms.max(0) as u64);
+        }
+
+        if let Some(max) = props
+            .get("maxRedirects")
+            .and_then(|r| i64::from_raccoon(r).ok())
+        {
+            options.max_redirects = max.max(0) as u32;
+        }
+
+        options.signal = props.get("signal").and_then(signal_token);
+
+        options
+    }
+
+    fn has_header(&self, name: &str) -> bool {
+        self.headers
+            .iter()
+            .any(|(h, _)| h.eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HttpResponse {
+    pub url: String,
+    pub status: u16,
+    pub status_text: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    fn from_ureq(response: ureq::Response) -> io::Result<Self> {
+        let mut result = Self::from_ureq_head(&response);
+        response.into_reader().read_to_end(&mut result.body)?;
+        Ok(result)
+    }
+
+    fn from_ureq_head(response: &ureq::Response) -> Self {
+        let headers = response
+            .headers_names()
+            .into_iter()
+            .filter_map(|name| {
+                let value = response.header(&name)?.to_string();
+                Some((name, value))
+            })
+            .collect();
+
+        Self {
+            url: response.get_url().to_string(),
+            status: response.status(),
+            status_text: response.status_text().to_string(),
+            headers,
+            body: Vec::new(),
+        }
+    }
+
+    fn failed(url: &str, error: impl ToString) -> Self {
+        Self {
+            url: url.to_string(),
+            status_text: error.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).to_string()
+    }
+
+    pub fn to_runtime(&self) -> RuntimeValue {
+        let mut headers = HashMap::new();
+        for (name, value) in &self.headers {
+            headers.insert(name.to_lowercase(), value.clone().to_raccoon());
+        }
+
+        let text = self.text();
+        let bytes: Vec<RuntimeValue> = self
+            .body
+            .iter()
+            .map(|b| RuntimeValue::Int(IntValue::new(*b as i64)))
+            .collect();
+
+        let mut properties = HashMap::new();
+        properties.insert("url".to_string(), self.url.clone().to_raccoon());
+        properties.insert(
+            "status".to_string(),
+            RuntimeValue::Int(IntValue::new(self.status as i64)),
+        );
+        properties.insert(
+            "statusText".to_string(),
+            self.status_text.clone().to_raccoon(),
+        );
+        properties.insert("ok".to_string(), self.is_ok().to_raccoon());
+        properties.insert(
+            "headers".to_string(),
+            RuntimeValue::Object(ObjectValue::new(headers, PrimitiveType::any())),
+        );
+        properties.insert("body".to_string(), text.clone().to_raccoon());
+        properties.insert(
+            "text".to_string(),
+            body_reader(RuntimeValue::Str(StrValue::new(text.clone()))),
+        );
+        properties.insert(
+            "json".to_string(),
+            body_reader(match serde_json::from_str(&text) {
+                Ok(json) => convert_serde_to_runtime(&json),
+                Err(_) => RuntimeValue::Null(NullValue::new()),
+            }),
+        );
+        properties.insert(
+            "bytes".to_string(),
+            body_reader(RuntimeValue::Array(ArrayValue::new(
+                bytes,
+                PrimitiveType::int(),
+            ))),
+        );
+
+        RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+    }
+}
+
+fn body_reader(value: RuntimeValue) -> RuntimeValue {
+    RuntimeValue::NativeAsyncFunction(Arc::new(NativeAsyncFunctionValue::new(
+        Arc::new(move |_args| {
+            let value = value.clone();
+            Box::pin(async move { value })
+        }),
+        PrimitiveType::any(),
+    )))
+}
+
+fn send(url: &str, options: &RequestOptions) -> Result<ureq::Response, String> {
+    let url = append_query(url, &options.query);
+    let mut builder = ureq::AgentBuilder::new()
+        .timeout(options.timeout)
+        .redirects(options.max_redirects);
+
+    if let Some(tls_config) = options.network.tls_config()? {
+        builder = builder.tls_config(tls_config);
+    }
+    if let Some(proxy) = options.network.proxy_for(&url) {
+        let proxy =
+            ureq::Proxy::new(&proxy).map_err(|e| format!("Invalid proxy '{}': {}", proxy, e))?;
+        builder = builder.proxy(proxy);
+    }
+
+    let agent = builder.build();
+    let mut request = agent.request(&options.method, &url);
+    for (name, value) in &options.headers {
+        request = request.set(name, value);
+    }
+
+    let result = match &options.body {
+        Some(body) => request.send_string(body),
+        None => request.call(),
+    };
+
+    match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => Ok(response),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+fn check_signal(options: &RequestOptions) -> Result<(), String> {
+    options
+        .signal
+        .as_ref()
+        .map_or(Ok(()), CancellationToken::check)
+}
+
+pub fn fetch(url: &str, options: &RequestOptions) -> HttpResponse {
+    if let Err(error) = check_signal(options) {
+        return HttpResponse::failed(url, error);
+    }
+    match send(url, options) {
+        Ok(response) => {
+            HttpResponse::from_ureq(response).unwrap_or_else(|e| HttpResponse::failed(url, e))
+        }
+        Err(error) => HttpResponse::failed(url, error),
+    }
+}
+
+pub fn download(url: &str, path: &str, options: &RequestOptions) -> (HttpResponse, u64) {
+    let response = match check_signal(options).and_then(|_| send(url, options)) {
+        Ok(response) => response,
+        Err(error) => return (HttpResponse::failed(url, error), 0),
+    };
+
+    let result = HttpResponse::from_ureq_head(&response);
+    if !result.is_ok() {
+        return (result, 0);
+    }
+    if let Err(error) = check_signal(options) {
+        return (HttpResponse::failed(url, error), 0);
+    }
+
+    let written =
+        File::create(path).and_then(|mut file| io::copy(&mut response.into_reader(), &mut file));
+    match written {
+        Ok(bytes) => (result, bytes),
+        Err(error) => (HttpResponse::failed(url, error), 0),
+    }
+}
+
+pub fn core_http_fetch(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let url = String::from_raccoon(&args[0]).unwrap_or_default();
+    let options = RequestOptions::from_value(args.get(1));
+    fetch(&url, &options).to_runtime()
+}
+
+pub fn core_http_download(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let url = String::from_raccoon(&args[0]).unwrap_or_default();
+    let path = String::from_raccoon(&args[1]).unwrap_or_default();
+    let options = RequestOptions::from_value(args.get(2));
+    let (response, bytes) = download(&url, &path, &options);
+
+    let mut properties = HashMap::new();
+    properties.insert(
+        "status".to_string(),
+        RuntimeValue::Int(IntValue::new(response.status as i64)),
+    );
+    properties.insert(
+        "statusText".to_string(),
+        response.status_text.clone().to_raccoon(),
+    );
+    properties.insert("ok".to_string(), response.is_ok().to_raccoon());
+    properties.insert("path".to_string(), path.to_raccoon());
+    properties.insert(
+        "bytes".to_string(),
+        RuntimeValue::Int(IntValue::new(bytes as i64)),
+    );
+
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+pub fn core_http_configure(args: Vec<RuntimeValue>) -> RuntimeValue {
+    match args.first() {
+        Some(RuntimeValue::Object(options)) => {
+            NetworkConfig::configure_global(&options.snapshot());
+            true.to_raccoon()
+        }
+        _ => false.to_raccoon(),
+    }
+}
+
+pub fn register_http_primitives(registrar: &mut Registrar) {
+    registrar.register_restricted_fn(
+        "core_http_configure",
+        "http",
+        core_http_configure,
+        1,
+        Some(1),
     );
+    registrar.register_restricted_fn("core_http_fetch", "http", core_http_fetch, 1, Some(2));
+    registrar.register_restricted_fn("core_http_download", "http", core_http_download, 2, Some(3));
+}
 
+pub fn register_http_module(registrar: &mut Registrar) {
     registrar.register_fn(
         "fetch",
         Some("http"),
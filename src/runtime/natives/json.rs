@@ -1,7 +1,7 @@
 use crate::ast::types::PrimitiveType;
 use crate::runtime::{
     ArrayValue, BoolValue, FloatValue, FromRaccoon, IntValue, NullValue, ObjectValue, Registrar,
-    RuntimeValue, StrValue, ToRaccoon,
+    RuntimeValue, StrValue, SymbolValue, ToRaccoon,
 };
 use serde_json::Value as JsonValue;
 
@@ -49,7 +49,7 @@ pub fn register_json_module(registrar: &mut Registrar) {
     );
 }
 
-fn convert_serde_to_runtime(value: &JsonValue) -> RuntimeValue {
+pub(crate) fn convert_serde_to_runtime(value: &JsonValue) -> RuntimeValue {
     match value {
         JsonValue::Null => RuntimeValue::Null(NullValue::new()),
         JsonValue::Bool(b) => RuntimeValue::Bool(BoolValue::new(*b)),
@@ -68,7 +68,7 @@ fn convert_serde_to_runtime(value: &JsonValue) -> RuntimeValue {
             RuntimeValue::Array(ArrayValue::new(elements, PrimitiveType::any()))
         }
         JsonValue::Object(obj) => {
-            let properties = obj
+            let properties: Vec<(String, RuntimeValue)> = obj
                 .iter()
                 .map(|(k, v)| (k.clone(), convert_serde_to_runtime(v)))
                 .collect();
@@ -77,7 +77,7 @@ fn convert_serde_to_runtime(value: &JsonValue) -> RuntimeValue {
     }
 }
 
-fn convert_runtime_to_serde(value: &RuntimeValue) -> JsonValue {
+pub(crate) fn convert_runtime_to_serde(value: &RuntimeValue) -> JsonValue {
     match value {
         RuntimeValue::Null(_) => JsonValue::Null,
         RuntimeValue::Bool(b) => JsonValue::Bool(b.value),
@@ -87,13 +87,26 @@ fn convert_runtime_to_serde(value: &RuntimeValue) -> JsonValue {
             .unwrap_or(JsonValue::Null),
         RuntimeValue::Str(s) => JsonValue::String(s.value.clone()),
         RuntimeValue::Array(list) => {
-            let arr = list.elements.iter().map(convert_runtime_to_serde).collect();
+            let arr = list
+                .elements
+                .read()
+                .unwrap()
+                .iter()
+                .map(convert_runtime_to_serde)
+                .collect();
             JsonValue::Array(arr)
         }
+        RuntimeValue::Tuple(tuple) => {
+            JsonValue::Array(tuple.elements.iter().map(convert_runtime_to_serde).collect())
+        }
+        RuntimeValue::Range(range) => JsonValue::Array(range.iter().map(JsonValue::from).collect()),
         RuntimeValue::Object(obj) => {
             let map = obj
                 .properties
+                .read()
+                .unwrap()
                 .iter()
+                .filter(|(k, _)| !SymbolValue::is_property_key(k))
                 .map(|(k, v)| (k.clone(), convert_runtime_to_serde(v)))
                 .collect();
             JsonValue::Object(map)
@@ -101,8 +114,10 @@ fn convert_runtime_to_serde(value: &RuntimeValue) -> JsonValue {
         RuntimeValue::Map(map) => {
             let obj = map
                 .entries
+                .read()
+                .unwrap()
                 .iter()
-                .map(|(k, v)| (k.clone(), convert_runtime_to_serde(v)))
+                .map(|(k, v)| (k.to_string(), convert_runtime_to_serde(v)))
                 .collect();
             JsonValue::Object(obj)
         }
@@ -0,0 +1,104 @@
+use super::json::convert_runtime_to_serde;
+use crate::ast::types::PrimitiveType;
+use crate::fn_type;
+use crate::runtime::logging::{self, Format, Level};
+use crate::runtime::{
+    Environment, NativeFunctionValue, NativeThunk, NullValue, ObjectValue, RuntimeValue, ToRaccoon,
+};
+use serde_json::{Map, Value as JsonValue};
+use indexmap::IndexMap;
+use std::sync::{Arc, RwLock};
+
+fn property(logger: &RuntimeValue, name: &str) -> Option<RuntimeValue> {
+    match logger {
+        RuntimeValue::ClassInstance(instance) => {
+            instance.properties.read().unwrap().get(name).cloned()
+        }
+        RuntimeValue::Object(object) => object.get(name),
+        _ => None,
+    }
+}
+
+fn fields_of(value: Option<&RuntimeValue>) -> Map<String, JsonValue> {
+    match value.map(convert_runtime_to_serde) {
+        Some(JsonValue::Object(fields)) => fields,
+        _ => Map::new(),
+    }
+}
+
+fn write_record(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let level = args
+        .first()
+        .and_then(|level| Level::parse(&level.to_string()));
+    let (Some(level), Some(logger)) = (level, args.get(1)) else {
+        return RuntimeValue::Null(NullValue::new());
+    };
+    if !level.enabled() {
+        return RuntimeValue::Null(NullValue::new());
+    }
+
+    let name = property(logger, "name").map_or_else(|| "app".to_string(), |name| name.to_string());
+    let format = match property(logger, "format") {
+        Some(RuntimeValue::Str(format)) => Format::parse(&format.value),
+        _ => None,
+    }
+    .unwrap_or_else(Format::from_env);
+    let mut fields = fields_of(property(logger, "fields").as_ref());
+    fields.extend(fields_of(args.get(3)));
+    let message = args
+        .get(2)
+        .map(|message| message.to_string())
+        .unwrap_or_default();
+
+    logging::log(format, level, &name, &message, &fields);
+    RuntimeValue::Null(NullValue::new())
+}
+
+fn child_logger(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let Some(RuntimeValue::ClassInstance(parent)) = args.first() else {
+        return RuntimeValue::Null(NullValue::new());
+    };
+
+    let mut fields = match property(&args[0], "fields") {
+        Some(RuntimeValue::Object(fields)) => fields.snapshot(),
+        _ => IndexMap::new(),
+    };
+    if let Some(RuntimeValue::Object(extra)) = args.get(1) {
+        fields.extend(extra.snapshot());
+    }
+
+    let mut properties = parent.properties.read().unwrap().clone();
+    properties.insert(
+        "fields".to_string(),
+        RuntimeValue::Object(ObjectValue::new(fields, PrimitiveType::any())),
+    );
+    let mut child = (**parent).clone();
+    child.properties = Arc::new(RwLock::new(properties));
+    RuntimeValue::ClassInstance(Arc::new(child))
+}
+
+pub fn register_log_wrappers(env: &mut Environment) {
+    let natives: [(&str, NativeThunk); 3] = [
+        ("_native_log_write", || {
+            NativeFunctionValue::new(write_record, fn_type!(variadic, PrimitiveType::void()))
+        }),
+        ("_native_log_enabled", || {
+            NativeFunctionValue::new(
+                |args| {
+                    let level = args
+                        .first()
+                        .and_then(|level| Level::parse(&level.to_string()));
+                    level.is_some_and(Level::enabled).to_raccoon()
+                },
+                fn_type!(PrimitiveType::str(), PrimitiveType::bool()),
+            )
+        }),
+        ("_native_log_child", || {
+            NativeFunctionValue::new(child_logger, fn_type!(variadic, PrimitiveType::any()))
+        }),
+    ];
+
+    for (name, native) in natives {
+        env.declare_lazy(name, move || RuntimeValue::NativeFunction(native()));
+    }
+}
@@ -1,143 +1,250 @@
-use crate::runtime::{FromRaccoon, Registrar, ToRaccoon};
+use crate::ast::types::PrimitiveType;
+use crate::fn_type;
+use crate::runtime::values::NativeFn;
+use crate::runtime::{
+    Environment, FromRaccoon, NativeFunctionValue, NullValue, Registrar, RuntimeValue, ToRaccoon,
+};
 
-pub fn register_math_module(registrar: &mut Registrar) {
-    registrar.register_fn(
-        "sqrt",
-        Some("math"),
-        |args| {
-            let x = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            x.sqrt().to_raccoon()
-        },
-        1,
-        Some(1),
-    );
+fn number(args: &[RuntimeValue], index: usize, default: f64) -> f64 {
+    args.get(index)
+        .and_then(|value| f64::from_raccoon(value).ok())
+        .unwrap_or(default)
+}
 
-    registrar.register_fn(
-        "pow",
-        Some("math"),
-        |args| {
-            let base = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            let exp = f64::from_raccoon(&args[1]).unwrap_or(0.0);
-            base.powf(exp).to_raccoon()
-        },
-        2,
-        Some(2),
-    );
+fn integer(args: &[RuntimeValue], index: usize) -> i64 {
+    args.get(index)
+        .and_then(|value| i64::from_raccoon(value).ok())
+        .unwrap_or(0)
+}
 
-    registrar.register_fn(
-        "sin",
-        Some("math"),
-        |args| {
-            let x = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            x.sin().to_raccoon()
-        },
-        1,
-        Some(1),
-    );
+macro_rules! unary {
+    ($($name:ident => $op:expr),* $(,)?) => {
+        $(
+            fn $name(args: Vec<RuntimeValue>) -> RuntimeValue {
+                let op: fn(f64) -> f64 = $op;
+                op(number(&args, 0, 0.0)).to_raccoon()
+            }
+        )*
+    };
+}
 
-    registrar.register_fn(
-        "cos",
-        Some("math"),
-        |args| {
-            let x = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            x.cos().to_raccoon()
-        },
-        1,
-        Some(1),
-    );
+unary! {
+    sqrt => f64::sqrt,
+    cbrt => f64::cbrt,
+    sin => f64::sin,
+    cos => f64::cos,
+    tan => f64::tan,
+    asin => f64::asin,
+    acos => f64::acos,
+    atan => f64::atan,
+    sinh => f64::sinh,
+    cosh => f64::cosh,
+    tanh => f64::tanh,
+    exp => f64::exp,
+    ln => f64::ln,
+    log2 => f64::log2,
+    log10 => f64::log10,
+    abs => f64::abs,
+    floor => f64::floor,
+    ceil => f64::ceil,
+    round => f64::round,
+    trunc => f64::trunc,
+    sign => |x| if x > 0.0 { 1.0 } else if x < 0.0 { -1.0 } else { 0.0 },
+}
 
-    registrar.register_fn(
-        "tan",
-        Some("math"),
-        |args| {
-            let x = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            x.tan().to_raccoon()
-        },
-        1,
-        Some(1),
-    );
+fn pow(args: Vec<RuntimeValue>) -> RuntimeValue {
+    number(&args, 0, 0.0)
+        .powf(number(&args, 1, 0.0))
+        .to_raccoon()
+}
 
-    registrar.register_fn(
-        "log",
-        Some("math"),
-        |args| {
-            let x = f64::from_raccoon(&args[0]).unwrap_or(1.0);
-            let base = if args.len() > 1 {
-                f64::from_raccoon(&args[1]).unwrap_or(std::f64::consts::E)
-            } else {
-                std::f64::consts::E
-            };
-            x.log(base).to_raccoon()
-        },
-        1,
-        Some(2),
-    );
+fn log(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let base = number(&args, 1, std::f64::consts::E);
+    number(&args, 0, 1.0).log(base).to_raccoon()
+}
 
-    registrar.register_fn(
-        "min",
-        Some("math"),
-        |args| {
-            let a = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            let b = f64::from_raccoon(&args[1]).unwrap_or(0.0);
-            a.min(b).to_raccoon()
-        },
-        2,
-        Some(2),
-    );
+fn min(args: Vec<RuntimeValue>) -> RuntimeValue {
+    number(&args, 0, 0.0)
+        .min(number(&args, 1, 0.0))
+        .to_raccoon()
+}
 
-    registrar.register_fn(
-        "max",
-        Some("math"),
-        |args| {
-            let a = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            let b = f64::from_raccoon(&args[1]).unwrap_or(0.0);
-            a.max(b).to_raccoon()
-        },
-        2,
-        Some(2),
-    );
+fn max(args: Vec<RuntimeValue>) -> RuntimeValue {
+    number(&args, 0, 0.0)
+        .max(number(&args, 1, 0.0))
+        .to_raccoon()
+}
 
-    registrar.register_fn(
-        "abs",
-        Some("math"),
-        |args| {
-            let x = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            x.abs().to_raccoon()
-        },
-        1,
-        Some(1),
-    );
+fn atan2(args: Vec<RuntimeValue>) -> RuntimeValue {
+    number(&args, 0, 0.0)
+        .atan2(number(&args, 1, 0.0))
+        .to_raccoon()
+}
 
-    registrar.register_fn(
-        "floor",
-        Some("math"),
-        |args| {
-            let x = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            x.floor().to_raccoon()
-        },
-        1,
-        Some(1),
-    );
+fn hypot(args: Vec<RuntimeValue>) -> RuntimeValue {
+    number(&args, 0, 0.0)
+        .hypot(number(&args, 1, 0.0))
+        .to_raccoon()
+}
 
-    registrar.register_fn(
-        "ceil",
-        Some("math"),
-        |args| {
-            let x = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            x.ceil().to_raccoon()
-        },
-        1,
-        Some(1),
-    );
+fn clamp(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let min = number(&args, 1, f64::NEG_INFINITY);
+    let max = number(&args, 2, f64::INFINITY);
+    number(&args, 0, 0.0).max(min).min(max).to_raccoon()
+}
 
-    registrar.register_fn(
-        "round",
-        Some("math"),
-        |args| {
-            let x = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            x.round().to_raccoon()
-        },
-        1,
-        Some(1),
+fn lerp(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let (a, b, t) = (
+        number(&args, 0, 0.0),
+        number(&args, 1, 0.0),
+        number(&args, 2, 0.0),
     );
+    (a + (b - a) * t).to_raccoon()
+}
+
+fn gcd_of(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn gcd(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let (a, b) = (integer(&args, 0), integer(&args, 1));
+    (gcd_of(a.unsigned_abs(), b.unsigned_abs()) as i64).to_raccoon()
+}
+
+fn lcm(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let a = integer(&args, 0).unsigned_abs();
+    let b = integer(&args, 1).unsigned_abs();
+    if a == 0 || b == 0 {
+        return 0i64.to_raccoon();
+    }
+    ((a / gcd_of(a, b) * b) as i64).to_raccoon()
+}
+
+fn is_finite(args: Vec<RuntimeValue>) -> RuntimeValue {
+    number(&args, 0, f64::NAN).is_finite().to_raccoon()
+}
+
+fn is_nan(args: Vec<RuntimeValue>) -> RuntimeValue {
+    number(&args, 0, f64::NAN).is_nan().to_raccoon()
+}
+
+fn statistic(args: &[RuntimeValue], op: fn(&[f64]) -> f64) -> RuntimeValue {
+    match args.first().map(Vec::<f64>::from_raccoon) {
+        Some(Ok(values)) if !values.is_empty() => op(&values).to_raccoon(),
+        _ => RuntimeValue::Null(NullValue::new()),
+    }
+}
+
+fn average(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn mean(args: Vec<RuntimeValue>) -> RuntimeValue {
+    statistic(&args, average)
+}
+
+fn median(args: Vec<RuntimeValue>) -> RuntimeValue {
+    statistic(&args, |values| {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let mid = sorted.len() / 2;
+        if sorted.len() % 2 == 0 {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        }
+    })
+}
+
+fn stddev(args: Vec<RuntimeValue>) -> RuntimeValue {
+    statistic(&args, |values| {
+        let mean = average(values);
+        let variance = values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / values.len() as f64;
+        variance.sqrt()
+    })
+}
+
+const FUNCTIONS: [(&str, NativeFn, usize, Option<usize>); 36] = [
+    ("sqrt", sqrt, 1, Some(1)),
+    ("cbrt", cbrt, 1, Some(1)),
+    ("pow", pow, 2, Some(2)),
+    ("sin", sin, 1, Some(1)),
+    ("cos", cos, 1, Some(1)),
+    ("tan", tan, 1, Some(1)),
+    ("asin", asin, 1, Some(1)),
+    ("acos", acos, 1, Some(1)),
+    ("atan", atan, 1, Some(1)),
+    ("atan2", atan2, 2, Some(2)),
+    ("sinh", sinh, 1, Some(1)),
+    ("cosh", cosh, 1, Some(1)),
+    ("tanh", tanh, 1, Some(1)),
+    ("exp", exp, 1, Some(1)),
+    ("ln", ln, 1, Some(1)),
+    ("log", log, 1, Some(2)),
+    ("log2", log2, 1, Some(1)),
+    ("log10", log10, 1, Some(1)),
+    ("min", min, 2, Some(2)),
+    ("max", max, 2, Some(2)),
+    ("abs", abs, 1, Some(1)),
+    ("floor", floor, 1, Some(1)),
+    ("ceil", ceil, 1, Some(1)),
+    ("round", round, 1, Some(1)),
+    ("trunc", trunc, 1, Some(1)),
+    ("sign", sign, 1, Some(1)),
+    ("hypot", hypot, 2, Some(2)),
+    ("clamp", clamp, 3, Some(3)),
+    ("lerp", lerp, 3, Some(3)),
+    ("gcd", gcd, 2, Some(2)),
+    ("lcm", lcm, 2, Some(2)),
+    ("isFinite", is_finite, 1, Some(1)),
+    ("isNaN", is_nan, 1, Some(1)),
+    ("mean", mean, 1, Some(1)),
+    ("median", median, 1, Some(1)),
+    ("stddev", stddev, 1, Some(1)),
+];
+
+const CONSTANTS: [(&str, f64); 8] = [
+    ("PI", std::f64::consts::PI),
+    ("E", std::f64::consts::E),
+    ("TAU", std::f64::consts::TAU),
+    ("SQRT2", std::f64::consts::SQRT_2),
+    ("LN2", std::f64::consts::LN_2),
+    ("LN10", std::f64::consts::LN_10),
+    ("INFINITY", f64::INFINITY),
+    ("NAN", f64::NAN),
+];
+
+pub fn constant(name: &str) -> Option<f64> {
+    CONSTANTS
+        .iter()
+        .find(|(constant, value)| *constant == name && value.is_finite())
+        .map(|(_, value)| *value)
+}
+
+pub fn register_math_module(registrar: &mut Registrar) {
+    for (name, implementation, min_args, max_args) in FUNCTIONS {
+        registrar.register_fn(name, Some("math"), implementation, min_args, max_args);
+    }
+    for (name, value) in CONSTANTS {
+        registrar.register_const(format!("math.{}", name), value.to_raccoon());
+    }
+}
+
+/// Declares each native as `_native_math_<name>` for `std:math`.
+pub fn register_math_wrappers(env: &mut Environment) {
+    for (name, implementation, _, _) in FUNCTIONS {
+        env.declare_lazy(format!("_native_math_{}", name), move || {
+            RuntimeValue::NativeFunction(NativeFunctionValue::new(
+                implementation,
+                fn_type!(variadic, PrimitiveType::any()),
+            ))
+        });
+    }
 }
@@ -2,22 +2,46 @@
 pub mod macros;
 
 pub mod array;
+pub mod cancel;
+pub mod cli;
+pub mod env;
+pub mod fs_glob;
 pub mod http;
 pub mod io;
 pub mod json;
+pub mod log;
 pub mod math;
+pub mod net;
+pub mod network;
+pub mod os;
 pub mod output;
+pub mod path;
 pub mod primitives;
+pub mod prompt;
 pub mod random;
+pub mod serialize;
+pub mod stdio;
+pub mod store;
 pub mod string;
+pub mod term;
+pub mod terminal;
 pub mod time;
+pub mod url;
+pub mod watch;
 
 pub use array::register_array_module;
-pub use http::register_http_module;
+pub use env::register_env_primitives;
+pub use fs_glob::register_fs_glob_primitives;
+pub use http::{register_http_module, register_http_primitives};
 pub use io::register_io_module;
 pub use json::register_json_module;
 pub use math::register_math_module;
+pub use net::register_net_primitives;
+pub use path::register_path_primitives;
 pub use primitives::register_core_primitives;
 pub use random::register_random_module;
+pub use stdio::register_stdio_primitives;
+pub use store::register_store_primitives;
 pub use string::register_string_module;
 pub use time::register_time_module;
+pub use watch::register_watch_primitives;
@@ -0,0 +1,124 @@
+use crate::ast::types::PrimitiveType;
+use crate::runtime::{
+    ArrayValue, FromRaccoon, IntValue, ObjectValue, Registrar, RuntimeValue, ToRaccoon,
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, ToSocketAddrs};
+
+#[cfg(unix)]
+mod platform {
+    use std::ffi::CStr;
+    use std::net::{IpAddr, SocketAddr};
+
+    pub fn reverse(ip: IpAddr) -> Option<String> {
+        let address = to_sockaddr(SocketAddr::new(ip, 0));
+        let mut host = [0 as libc::c_char; libc::NI_MAXHOST as usize];
+
+        let status = unsafe {
+            libc::getnameinfo(
+                &address.0 as *const libc::sockaddr_storage as *const libc::sockaddr,
+                address.1,
+                host.as_mut_ptr(),
+                host.len() as libc::socklen_t,
+                std::ptr::null_mut(),
+                0,
+                libc::NI_NAMEREQD,
+            )
+        };
+
+        if status != 0 {
+            return None;
+        }
+        let name = unsafe { CStr::from_ptr(host.as_ptr()) };
+        Some(name.to_string_lossy().to_string())
+    }
+
+    fn to_sockaddr(address: SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+        let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+
+        let length = match address {
+            SocketAddr::V4(v4) => {
+                let raw = &mut storage as *mut _ as *mut libc::sockaddr_in;
+                unsafe {
+                    (*raw).sin_family = libc::AF_INET as libc::sa_family_t;
+                    (*raw).sin_addr.s_addr = u32::from_ne_bytes(v4.ip().octets());
+                }
+                std::mem::size_of::<libc::sockaddr_in>()
+            }
+            SocketAddr::V6(v6) => {
+                let raw = &mut storage as *mut _ as *mut libc::sockaddr_in6;
+                unsafe {
+                    (*raw).sin6_family = libc::AF_INET6 as libc::sa_family_t;
+                    (*raw).sin6_addr.s6_addr = v6.ip().octets();
+                }
+                std::mem::size_of::<libc::sockaddr_in6>()
+            }
+        };
+
+        (storage, length as libc::socklen_t)
+    }
+}
+
+#[cfg(not(unix))]
+mod platform {
+    use std::net::IpAddr;
+
+    pub fn reverse(_ip: IpAddr) -> Option<String> {
+        None
+    }
+}
+
+pub fn lookup(host: &str) -> Vec<IpAddr> {
+    let mut addresses = Vec::new();
+    if let Ok(resolved) = (host, 0).to_socket_addrs() {
+        for ip in resolved.map(|address| address.ip()) {
+            if !addresses.contains(&ip) {
+                addresses.push(ip);
+            }
+        }
+    }
+    addresses
+}
+
+pub fn reverse(ip: &str) -> Vec<String> {
+    match ip.parse::<IpAddr>() {
+        Ok(ip) => platform::reverse(ip).into_iter().collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn address_object(ip: IpAddr) -> RuntimeValue {
+    let family = if ip.is_ipv4() { 4 } else { 6 };
+    let mut properties = HashMap::new();
+    properties.insert("address".to_string(), ip.to_string().to_raccoon());
+    properties.insert(
+        "family".to_string(),
+        RuntimeValue::Int(IntValue::new(family)),
+    );
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+pub fn core_net_lookup(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let host = String::from_raccoon(&args[0]).unwrap_or_default();
+    let addresses = lookup(&host).into_iter().map(address_object).collect();
+    RuntimeValue::Array(ArrayValue::new(addresses, PrimitiveType::any()))
+}
+
+pub fn core_net_reverse(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let ip = String::from_raccoon(&args[0]).unwrap_or_default();
+    let hostnames = reverse(&ip)
+        .into_iter()
+        .map(|hostname| {
+            let mut properties = HashMap::new();
+            properties.insert("hostname".to_string(), hostname.to_raccoon());
+            properties.insert("address".to_string(), ip.clone().to_raccoon());
+            RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+        })
+        .collect();
+    RuntimeValue::Array(ArrayValue::new(hostnames, PrimitiveType::any()))
+}
+
+pub fn register_net_primitives(registrar: &mut Registrar) {
+    registrar.register_restricted_fn("core_net_lookup", "net", core_net_lookup, 1, Some(1));
+    registrar.register_restricted_fn("core_net_reverse", "net", core_net_reverse, 1, Some(1));
+}
@@ -0,0 +1,202 @@
+use crate::runtime::{FromRaccoon, RuntimeValue};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{self, CryptoProvider};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use indexmap::IndexMap;
+use std::sync::{Arc, Mutex, Once};
+
+static GLOBAL_CONFIG: Mutex<NetworkConfig> = Mutex::new(NetworkConfig::new());
+static INSECURE_WARNING: Once = Once::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ProxySetting {
+    #[default]
+    Environment,
+    Disabled,
+    Url(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkConfig {
+    pub ca_file: Option<String>,
+    pub insecure_skip_verify: bool,
+    pub proxy: ProxySetting,
+}
+
+impl NetworkConfig {
+    pub const fn new() -> Self {
+        Self {
+            ca_file: None,
+            insecure_skip_verify: false,
+            proxy: ProxySetting::Environment,
+        }
+    }
+
+    pub fn global() -> Self {
+        GLOBAL_CONFIG.lock().unwrap().clone()
+    }
+
+    pub fn configure_global(properties: &IndexMap<String, RuntimeValue>) {
+        GLOBAL_CONFIG.lock().unwrap().apply(properties);
+    }
+
+    pub fn apply(&mut self, properties: &IndexMap<String, RuntimeValue>) {
+        match properties.get("caFile") {
+            Some(RuntimeValue::Null(_)) => self.ca_file = None,
+            Some(value) => self.ca_file = String::from_raccoon(value).ok(),
+            None => {}
+        }
+
+        if let Some(insecure) = properties
+            .get("insecureSkipVerify")
+            .and_then(|v| bool::from_raccoon(v).ok())
+        {
+            self.insecure_skip_verify = insecure;
+        }
+
+        match properties.get("proxy") {
+            Some(RuntimeValue::Null(_)) => self.proxy = ProxySetting::Environment,
+            Some(RuntimeValue::Bool(enabled)) if enabled.value => {
+                self.proxy = ProxySetting::Environment
+            }
+            Some(RuntimeValue::Bool(_)) => self.proxy = ProxySetting::Disabled,
+            Some(value) => self.proxy = ProxySetting::Url(value.to_string()),
+            None => {}
+        }
+    }
+
+    pub fn proxy_for(&self, url: &str) -> Option<String> {
+        match &self.proxy {
+            ProxySetting::Disabled => None,
+            ProxySetting::Url(proxy) => Some(proxy.clone()),
+            ProxySetting::Environment => proxy_from_env(url),
+        }
+    }
+
+    pub fn tls_config(&self) -> Result<Option<Arc<ClientConfig>>, String> {
+        if self.ca_file.is_none() && !self.insecure_skip_verify {
+            return Ok(None);
+        }
+
+        let provider = Arc::new(crypto::ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|e| e.to_string())?;
+
+        if self.insecure_skip_verify {
+            INSECURE_WARNING.call_once(|| {
+                crate::runtime::logging::warn(
+                    "TLS certificate verification is disabled (insecureSkipVerify)",
+                );
+            });
+            let verifier = Arc::new(SkipServerVerification(provider));
+            return Ok(Some(Arc::new(
+                builder
+                    .dangerous()
+                    .with_custom_certificate_verifier(verifier)
+                    .with_no_client_auth(),
+            )));
+        }
+
+        let mut roots = RootCertStore::empty();
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+        if let Some(ca_file) = &self.ca_file {
+            let certificates = CertificateDer::pem_file_iter(ca_file)
+                .map_err(|e| format!("Failed to read CA bundle '{}': {}", ca_file, e))?;
+            for certificate in certificates {
+                let certificate = certificate
+                    .map_err(|e| format!("Invalid certificate in '{}': {}", ca_file, e))?;
+                roots
+                    .add(certificate)
+                    .map_err(|e| format!("Invalid certificate in '{}': {}", ca_file, e))?;
+            }
+        }
+
+        Ok(Some(Arc::new(
+            builder.with_root_certificates(roots).with_no_client_auth(),
+        )))
+    }
+}
+
+fn env_var(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .find_map(|name| std::env::var(name).ok())
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn bypasses_proxy(host: &str) -> bool {
+    let Some(no_proxy) = env_var(&["NO_PROXY", "no_proxy"]) else {
+        return false;
+    };
+
+    no_proxy.split(',').map(str::trim).any(|entry| {
+        let entry = entry.trim_start_matches('.');
+        entry == "*"
+            || (!entry.is_empty() && (host == entry || host.ends_with(&format!(".{}", entry))))
+    })
+}
+
+pub fn proxy_from_env(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url).ok()?;
+    if bypasses_proxy(parsed.host_str().unwrap_or_default()) {
+        return None;
+    }
+
+    match parsed.scheme() {
+        "https" => env_var(&["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]),
+        "http" => env_var(&["HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"]),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+struct SkipServerVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
@@ -0,0 +1,138 @@
+use crate::ast::types::PrimitiveType;
+use crate::fn_type;
+use crate::runtime::{Environment, NativeFunctionValue, NativeThunk, RuntimeValue, ToRaccoon};
+use std::path::PathBuf;
+
+/// Memory and uptime figures from the kernel, where the platform exposes them.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemStats {
+    pub total_memory: u64,
+    pub free_memory: u64,
+    pub uptime_secs: u64,
+}
+
+#[cfg(unix)]
+mod platform {
+    use std::ffi::CStr;
+
+    pub fn hostname() -> Option<String> {
+        let mut name = [0 as libc::c_char; 256];
+        let status = unsafe { libc::gethostname(name.as_mut_ptr(), name.len()) };
+        if status != 0 {
+            return None;
+        }
+        let name = unsafe { CStr::from_ptr(name.as_ptr()) };
+        Some(name.to_string_lossy().to_string())
+    }
+}
+
+#[cfg(not(unix))]
+mod platform {
+    pub fn hostname() -> Option<String> {
+        std::env::var("COMPUTERNAME").ok()
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn system_stats() -> Option<SystemStats> {
+    let mut info: libc::sysinfo = unsafe { std::mem::zeroed() };
+    if unsafe { libc::sysinfo(&mut info) } != 0 {
+        return None;
+    }
+    let unit = info.mem_unit.max(1) as u64;
+    Some(SystemStats {
+        total_memory: info.totalram as u64 * unit,
+        free_memory: info.freeram as u64 * unit,
+        uptime_secs: info.uptime.max(0) as u64,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn system_stats() -> Option<SystemStats> {
+    None
+}
+
+pub fn home_dir() -> Option<PathBuf> {
+    ["HOME", "USERPROFILE"]
+        .iter()
+        .find_map(|name| std::env::var_os(name).filter(|value| !value.is_empty()))
+        .map(PathBuf::from)
+}
+
+pub fn cpu_count() -> i64 {
+    std::thread::available_parallelism().map_or(1, |count| count.get() as i64)
+}
+
+fn stat(field: fn(&SystemStats) -> u64) -> RuntimeValue {
+    system_stats()
+        .map(|stats| field(&stats) as i64)
+        .to_raccoon()
+}
+
+fn path_value(path: Option<PathBuf>) -> RuntimeValue {
+    path.map(|path| path.to_string_lossy().to_string())
+        .to_raccoon()
+}
+
+pub fn register_os_wrappers(env: &mut Environment) {
+    let natives: [(&str, NativeThunk); 9] = [
+        ("_native_os_platform", || {
+            NativeFunctionValue::new(
+                |_args| std::env::consts::OS.to_string().to_raccoon(),
+                fn_type!(PrimitiveType::str()),
+            )
+        }),
+        ("_native_os_arch", || {
+            NativeFunctionValue::new(
+                |_args| std::env::consts::ARCH.to_string().to_raccoon(),
+                fn_type!(PrimitiveType::str()),
+            )
+        }),
+        ("_native_os_hostname", || {
+            NativeFunctionValue::new(
+                |_args| platform::hostname().to_raccoon(),
+                fn_type!(PrimitiveType::str()),
+            )
+        }),
+        ("_native_os_cpus", || {
+            NativeFunctionValue::new(
+                |_args| cpu_count().to_raccoon(),
+                fn_type!(PrimitiveType::int()),
+            )
+        }),
+        ("_native_os_total_memory", || {
+            NativeFunctionValue::new(
+                |_args| stat(|stats| stats.total_memory),
+                fn_type!(PrimitiveType::int()),
+            )
+        }),
+        ("_native_os_free_memory", || {
+            NativeFunctionValue::new(
+                |_args| stat(|stats| stats.free_memory),
+                fn_type!(PrimitiveType::int()),
+            )
+        }),
+        ("_native_os_uptime", || {
+            NativeFunctionValue::new(
+                |_args| stat(|stats| stats.uptime_secs),
+                fn_type!(PrimitiveType::int()),
+            )
+        }),
+        ("_native_os_home_dir", || {
+            NativeFunctionValue::new(
+                |_args| path_value(home_dir()),
+                fn_type!(PrimitiveType::str()),
+            )
+        }),
+        ("_native_os_temp_dir", || {
+            NativeFunctionValue::new(
+                |_args| path_value(Some(std::env::temp_dir())),
+                fn_type!(PrimitiveType::str()),
+            )
+        }),
+    ];
+
+    for (name, native) in natives {
+        env.declare_lazy(name, move || RuntimeValue::NativeFunction(native()));
+    }
+}
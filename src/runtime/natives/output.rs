@@ -22,6 +22,7 @@ pub fn register(functions: &mut HashMap<String, NativeFunctionValue>) {
             params: vec![],
             return_type: PrimitiveType::void(),
             is_variadic: true,
+            param_names: Vec::new(),
         })),
     );
 
@@ -42,6 +43,7 @@ pub fn register(functions: &mut HashMap<String, NativeFunctionValue>) {
             params: vec![],
             return_type: PrimitiveType::void(),
             is_variadic: true,
+            param_names: Vec::new(),
         })),
     );
 
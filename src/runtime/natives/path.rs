@@ -0,0 +1,212 @@
+use crate::ast::types::{FunctionType, PrimitiveType, Type};
+use crate::fn_type;
+use crate::runtime::{
+    Environment, FromRaccoon, NativeFunctionValue, NativeThunk, Registrar, RuntimeValue, ToRaccoon,
+};
+use std::path::{Component, Path, PathBuf, MAIN_SEPARATOR_STR};
+
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    let mut depth = 0usize;
+
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => result.push(component.as_os_str()),
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => {
+                result.pop();
+                depth -= 1;
+            }
+            Component::ParentDir if path.has_root() => {}
+            Component::ParentDir => result.push(".."),
+            Component::Normal(part) => {
+                result.push(part);
+                depth += 1;
+            }
+        }
+    }
+
+    if result.as_os_str().is_empty() {
+        result.push(".");
+    }
+    result
+}
+
+pub fn join<S: AsRef<str>>(parts: &[S]) -> PathBuf {
+    let parts: Vec<&str> = parts
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|p| !p.is_empty())
+        .collect();
+    normalize(Path::new(&parts.join(MAIN_SEPARATOR_STR)))
+}
+
+pub fn resolve<S: AsRef<str>>(parts: &[S]) -> PathBuf {
+    let mut result = std::env::current_dir().unwrap_or_default();
+    for part in parts.iter().map(AsRef::as_ref).filter(|p| !p.is_empty()) {
+        result.push(part);
+    }
+    normalize(&result)
+}
+
+pub fn relative(from: &str, to: &str) -> PathBuf {
+    let from = resolve(&[from]);
+    let to = resolve(&[to]);
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut result = PathBuf::new();
+    for _ in common..from.len() {
+        result.push("..");
+    }
+    for component in &to[common..] {
+        result.push(component.as_os_str());
+    }
+    result
+}
+
+pub fn dirname(path: &str) -> String {
+    match Path::new(path).parent() {
+        Some(parent) if parent.as_os_str().is_empty() => ".".to_string(),
+        Some(parent) => display(parent),
+        None if Path::new(path).has_root() => path.to_string(),
+        None => ".".to_string(),
+    }
+}
+
+pub fn basename(path: &str, ext: &str) -> String {
+    let name = Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    match name.strip_suffix(ext) {
+        Some(stem) if !ext.is_empty() && !stem.is_empty() => stem.to_string(),
+        _ => name,
+    }
+}
+
+pub fn extname(path: &str) -> String {
+    let name = basename(path, "");
+    match name.rfind('.') {
+        Some(index) if index > 0 => name[index..].to_string(),
+        _ => String::new(),
+    }
+}
+
+pub fn glob(pattern: &str) -> Vec<String> {
+    let mut matches: Vec<String> = match glob::glob(pattern) {
+        Ok(paths) => paths.filter_map(Result::ok).map(|p| display(&p)).collect(),
+        Err(_) => Vec::new(),
+    };
+    matches.sort();
+    matches
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+fn arg_str(args: &[RuntimeValue], index: usize) -> String {
+    args.get(index)
+        .and_then(|arg| String::from_raccoon(arg).ok())
+        .unwrap_or_default()
+}
+
+fn arg_parts(args: &[RuntimeValue]) -> Vec<String> {
+    match args.first() {
+        Some(RuntimeValue::Array(array)) => array
+            .elements
+            .read()
+            .unwrap()
+            .iter()
+            .map(|e| e.to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub fn core_dir_glob(args: Vec<RuntimeValue>) -> RuntimeValue {
+    glob(&arg_str(&args, 0)).to_raccoon()
+}
+
+pub fn register_path_primitives(registrar: &mut Registrar) {
+    registrar.register_restricted_fn("core_dir_glob", "io", core_dir_glob, 1, Some(1));
+}
+
+pub fn register_path_wrappers(env: &mut Environment) {
+    fn str_to_str() -> Type {
+        fn_type!(PrimitiveType::str(), PrimitiveType::str())
+    }
+    let natives: [(&str, NativeThunk); 9] = [
+        ("_native_path_sep", || {
+            NativeFunctionValue::new(
+                |_args| MAIN_SEPARATOR_STR.to_string().to_raccoon(),
+                fn_type!(PrimitiveType::str()),
+            )
+        }),
+        ("_native_path_join", || {
+            NativeFunctionValue::new(
+                |args| display(&join(&arg_parts(&args))).to_raccoon(),
+                fn_type!(PrimitiveType::any(), PrimitiveType::str()),
+            )
+        }),
+        ("_native_path_resolve", || {
+            NativeFunctionValue::new(
+                |args| display(&resolve(&arg_parts(&args))).to_raccoon(),
+                fn_type!(PrimitiveType::any(), PrimitiveType::str()),
+            )
+        }),
+        ("_native_path_normalize", || {
+            NativeFunctionValue::new(
+                |args| display(&normalize(Path::new(&arg_str(&args, 0)))).to_raccoon(),
+                str_to_str(),
+            )
+        }),
+        ("_native_path_relative", || {
+            NativeFunctionValue::new(
+                |args| display(&relative(&arg_str(&args, 0), &arg_str(&args, 1))).to_raccoon(),
+                Type::Function(Box::new(FunctionType {
+                    params: vec![PrimitiveType::str(), PrimitiveType::str()],
+                    return_type: PrimitiveType::str(),
+                    is_variadic: false,
+                    param_names: Vec::new(),
+                })),
+            )
+        }),
+        ("_native_path_dirname", || {
+            NativeFunctionValue::new(
+                |args| dirname(&arg_str(&args, 0)).to_raccoon(),
+                str_to_str(),
+            )
+        }),
+        ("_native_path_basename", || {
+            NativeFunctionValue::new(
+                |args| basename(&arg_str(&args, 0), &arg_str(&args, 1)).to_raccoon(),
+                Type::Function(Box::new(FunctionType {
+                    params: vec![PrimitiveType::str(), PrimitiveType::str()],
+                    return_type: PrimitiveType::str(),
+                    is_variadic: false,
+                    param_names: Vec::new(),
+                })),
+            )
+        }),
+        ("_native_path_extname", || {
+            NativeFunctionValue::new(
+                |args| extname(&arg_str(&args, 0)).to_raccoon(),
+                str_to_str(),
+            )
+        }),
+        ("_native_path_is_absolute", || {
+            NativeFunctionValue::new(
+                |args| Path::new(&arg_str(&args, 0)).is_absolute().to_raccoon(),
+                fn_type!(PrimitiveType::str(), PrimitiveType::bool()),
+            )
+        }),
+    ];
+
+    for (name, native) in natives {
+        env.declare_lazy(name, move || RuntimeValue::NativeFunction(native()));
+    }
+}
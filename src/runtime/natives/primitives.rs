@@ -439,7 +439,7 @@ pub fn core_eprint(args: Vec<RuntimeValue>) -> RuntimeValue {
 }
 
 pub fn core_input(args: Vec<RuntimeValue>) -> RuntimeValue {
-    use std::io::{self, Write};
+    use crate::runtime::{shared_editor, ReadResult};
 
     let prompt = if !args.is_empty() {
         String::from_raccoon(&args[0]).unwrap_or_default()
@@ -447,18 +447,9 @@ pub fn core_input(args: Vec<RuntimeValue>) -> RuntimeValue {
         String::new()
     };
 
-    if !prompt.is_empty() {
-        print!("{}", prompt);
-        let _ = io::stdout().flush();
-    }
-
-    let mut input = String::new();
-    match io::stdin().read_line(&mut input) {
-        Ok(_) => {
-            let trimmed = input.trim_end_matches(&['\r', '\n'][..]).to_string();
-            trimmed.to_raccoon()
-        }
-        Err(_) => "".to_string().to_raccoon(),
+    match shared_editor().lock().unwrap().read_line(&prompt) {
+        Ok(ReadResult::Line(line)) => line.to_raccoon(),
+        _ => "".to_string().to_raccoon(),
     }
 }
 
@@ -471,8 +462,10 @@ pub fn core_len(args: Vec<RuntimeValue>) -> RuntimeValue {
 
     match &args[0] {
         RuntimeValue::Str(s) => RuntimeValue::Int(IntValue::new(s.value.len() as i64)),
-        RuntimeValue::Array(a) => RuntimeValue::Int(IntValue::new(a.elements.len() as i64)),
-        RuntimeValue::Map(m) => RuntimeValue::Int(IntValue::new(m.entries.len() as i64)),
+        RuntimeValue::Array(a) => RuntimeValue::Int(IntValue::new(a.len() as i64)),
+        RuntimeValue::Tuple(t) => RuntimeValue::Int(IntValue::new(t.elements.len() as i64)),
+        RuntimeValue::Range(r) => RuntimeValue::Int(IntValue::new(r.len() as i64)),
+        RuntimeValue::Map(m) => RuntimeValue::Int(IntValue::new(m.len() as i64)),
         _ => RuntimeValue::Int(IntValue::new(0)),
     }
 }
@@ -510,6 +503,31 @@ pub fn core_random(_args: Vec<RuntimeValue>) -> RuntimeValue {
     (hash as f64 / u64::MAX as f64).to_raccoon()
 }
 
+pub fn core_memory_stats(_args: Vec<RuntimeValue>) -> RuntimeValue {
+    use crate::ast::types::PrimitiveType;
+    use crate::runtime::{memory_stats, IntValue, ObjectValue};
+    use std::collections::HashMap;
+
+    let stats = memory_stats();
+    let total: usize = stats.iter().map(|(_, count)| count).sum();
+
+    let mut properties: HashMap<String, RuntimeValue> = stats
+        .into_iter()
+        .map(|(kind, count)| {
+            (
+                kind.name().to_string(),
+                RuntimeValue::Int(IntValue::new(count as i64)),
+            )
+        })
+        .collect();
+    properties.insert(
+        "total".to_string(),
+        RuntimeValue::Int(IntValue::new(total as i64)),
+    );
+
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
 pub fn register_core_primitives(registrar: &mut Registrar) {
     registrar.register_fn("core_sqrt", None, core_sqrt, 1, Some(1));
     registrar.register_fn("core_cbrt", None, core_cbrt, 1, Some(1));
@@ -535,17 +553,17 @@ pub fn register_core_primitives(registrar: &mut Registrar) {
     registrar.register_fn("core_sign", None, core_sign, 1, Some(1));
     registrar.register_fn("core_pow", None, core_pow, 2, Some(2));
 
-    registrar.register_fn("core_file_read", None, core_file_read, 1, Some(1));
-    registrar.register_fn("core_file_write", None, core_file_write, 2, Some(2));
-    registrar.register_fn("core_file_append", None, core_file_append, 2, Some(2));
-    registrar.register_fn("core_file_exists", None, core_file_exists, 1, Some(1));
-    registrar.register_fn("core_file_delete", None, core_file_delete, 1, Some(1));
-    registrar.register_fn("core_dir_create", None, core_dir_create, 1, Some(1));
-    registrar.register_fn("core_dir_list", None, core_dir_list, 1, Some(1));
+    registrar.register_restricted_fn("core_file_read", "io", core_file_read, 1, Some(1));
+    registrar.register_restricted_fn("core_file_write", "io", core_file_write, 2, Some(2));
+    registrar.register_restricted_fn("core_file_append", "io", core_file_append, 2, Some(2));
+    registrar.register_restricted_fn("core_file_exists", "io", core_file_exists, 1, Some(1));
+    registrar.register_restricted_fn("core_file_delete", "io", core_file_delete, 1, Some(1));
+    registrar.register_restricted_fn("core_dir_create", "io", core_dir_create, 1, Some(1));
+    registrar.register_restricted_fn("core_dir_list", "io", core_dir_list, 1, Some(1));
 
-    registrar.register_fn("core_http_get", None, core_http_get, 1, Some(1));
-    registrar.register_fn("core_http_post", None, core_http_post, 2, Some(2));
-    registrar.register_fn("core_http_request", None, core_http_request, 4, Some(4));
+    registrar.register_restricted_fn("core_http_get", "http", core_http_get, 1, Some(1));
+    registrar.register_restricted_fn("core_http_post", "http", core_http_post, 2, Some(2));
+    registrar.register_restricted_fn("core_http_request", "http", core_http_request, 4, Some(4));
 
     registrar.register_fn("core_time_now", None, core_time_now, 0, Some(0));
     registrar.register_fn(
@@ -622,10 +640,20 @@ pub fn register_core_primitives(registrar: &mut Registrar) {
     registrar.register_fn("core_print", None, core_print, 1, Some(1));
     registrar.register_fn("core_println", None, core_println, 1, Some(1));
     registrar.register_fn("core_eprint", None, core_eprint, 1, Some(1));
-    registrar.register_fn("core_input", None, core_input, 0, None);
+    registrar.register_restricted_fn("core_input", "io", core_input, 0, None);
     registrar.register_fn("core_len", None, core_len, 0, None);
-    registrar.register_fn("core_env_get", None, core_env_get, 1, Some(1));
-    registrar.register_fn("core_env_set", None, core_env_set, 2, Some(2));
-    registrar.register_fn("core_exit", None, core_exit, 1, Some(1));
+    registrar.register_restricted_fn("core_env_get", "process", core_env_get, 1, Some(1));
+    registrar.register_restricted_fn("core_env_set", "process", core_env_set, 2, Some(2));
+    registrar.register_restricted_fn("core_exit", "process", core_exit, 1, Some(1));
     registrar.register_fn("core_random", None, core_random, 0, Some(0));
+    registrar.register_fn("core_memoryStats", None, core_memory_stats, 0, Some(0));
+
+    crate::runtime::natives::register_stdio_primitives(registrar);
+    crate::runtime::natives::register_http_primitives(registrar);
+    crate::runtime::natives::register_net_primitives(registrar);
+    crate::runtime::natives::register_path_primitives(registrar);
+    crate::runtime::natives::register_store_primitives(registrar);
+    crate::runtime::natives::register_watch_primitives(registrar);
+    crate::runtime::natives::register_fs_glob_primitives(registrar);
+    crate::runtime::natives::register_env_primitives(registrar);
 }
@@ -0,0 +1,286 @@
+use super::stdio::read_line;
+use super::term::style;
+use super::terminal;
+use crate::ast::types::PrimitiveType;
+use crate::runtime::values::NativeFn;
+use crate::runtime::{
+    Environment, FromRaccoon, NativeAsyncFunctionValue, NullValue, RuntimeValue, ToRaccoon,
+};
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+fn write_stdout(text: &str) {
+    let mut stdout = io::stdout().lock();
+    let _ = stdout.write_all(text.as_bytes());
+    let _ = stdout.flush();
+}
+
+fn arg_str(args: &[RuntimeValue], index: usize) -> String {
+    args.get(index)
+        .map(|arg| arg.to_string())
+        .unwrap_or_default()
+}
+
+fn interactive() -> bool {
+    terminal::is_tty(terminal::STDIN) && terminal::is_tty(terminal::STDOUT)
+}
+
+struct RawMode {
+    restore: bool,
+}
+
+impl RawMode {
+    fn enable() -> Self {
+        let restore = !terminal::is_raw_mode() && terminal::set_raw_mode(true);
+        Self { restore }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        if self.restore {
+            terminal::set_raw_mode(false);
+        }
+    }
+}
+
+enum Key {
+    Up,
+    Down,
+    Enter,
+    Backspace,
+    Cancel,
+    Char(char),
+    Other,
+}
+
+fn read_byte(stdin: &mut impl Read) -> Option<u8> {
+    let mut byte = [0u8; 1];
+    (stdin.read(&mut byte).ok()? == 1).then_some(byte[0])
+}
+
+fn read_key() -> Option<Key> {
+    let mut stdin = io::stdin().lock();
+    let first = read_byte(&mut stdin)?;
+    let key = match first {
+        b'\r' | b'\n' => Key::Enter,
+        127 | 8 => Key::Backspace,
+        3 | 4 => Key::Cancel,
+        0x1b => match (read_byte(&mut stdin), read_byte(&mut stdin)) {
+            (Some(b'['), Some(b'A')) => Key::Up,
+            (Some(b'['), Some(b'B')) => Key::Down,
+            _ => Key::Other,
+        },
+        byte if byte < 0x20 => Key::Other,
+        byte => {
+            let mut bytes = vec![byte];
+            while std::str::from_utf8(&bytes).is_err() && bytes.len() < 4 {
+                bytes.push(read_byte(&mut stdin)?);
+            }
+            match std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(ch) => Key::Char(ch),
+                None => Key::Other,
+            }
+        }
+    };
+    Some(key)
+}
+
+/// Asks until the answer is yes or no; an empty answer or end of input gives `default`.
+pub fn confirm(question: &str, default: bool) -> bool {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    loop {
+        write_stdout(&format!("{} {} ", question, style(hint, "dim")));
+        let Some(answer) = read_line() else {
+            write_stdout("\n");
+            return default;
+        };
+        match answer.trim().to_lowercase().as_str() {
+            "" => return default,
+            "y" | "yes" => return true,
+            "n" | "no" => return false,
+            _ => write_stdout(&format!("{}\n", style("Please answer y or n.", "yellow"))),
+        }
+    }
+}
+
+/// The answer, or `default` when it is empty or input has ended.
+pub fn input_with_default(question: &str, default: &str) -> String {
+    let hint = if default.is_empty() {
+        String::new()
+    } else {
+        format!("{} ", style(&format!("({})", default), "dim"))
+    };
+    write_stdout(&format!("{} {}", question, hint));
+    match read_line() {
+        Some(answer) if !answer.trim().is_empty() => answer,
+        Some(_) => default.to_string(),
+        None => {
+            write_stdout("\n");
+            default.to_string()
+        }
+    }
+}
+
+/// Reads a line without echoing it; `None` when cancelled or input has ended.
+pub fn password(question: &str) -> Option<String> {
+    write_stdout(&format!("{} ", question));
+    if !interactive() {
+        let line = read_line();
+        if line.is_none() {
+            write_stdout("\n");
+        }
+        return line;
+    }
+
+    let _raw = RawMode::enable();
+    let mut secret = String::new();
+    let result = loop {
+        match read_key() {
+            Some(Key::Enter) => break Some(secret),
+            Some(Key::Backspace) => {
+                secret.pop();
+            }
+            Some(Key::Char(ch)) => secret.push(ch),
+            Some(Key::Cancel) | None => break None,
+            Some(_) => {}
+        }
+    };
+    write_stdout("\n");
+    result
+}
+
+fn render_options(options: &[String], selected: usize) {
+    for (index, option) in options.iter().enumerate() {
+        let line = if index == selected {
+            style(&format!("> {}", option), "cyan")
+        } else {
+            format!("  {}", option)
+        };
+        write_stdout(&format!("\r\x1b[2K{}\n", line));
+    }
+}
+
+/// The index of the chosen option; `None` when cancelled or input has ended.
+pub fn select(question: &str, options: &[String], default: usize) -> Option<usize> {
+    if options.is_empty() {
+        return None;
+    }
+    let default = default.min(options.len() - 1);
+
+    if !interactive() {
+        write_stdout(&format!("{}\n", question));
+        for (index, option) in options.iter().enumerate() {
+            write_stdout(&format!("  {}) {}\n", index + 1, option));
+        }
+        loop {
+            write_stdout(&format!(
+                "Choose 1-{} {} ",
+                options.len(),
+                style(&format!("({})", default + 1), "dim")
+            ));
+            let Some(answer) = read_line() else {
+                write_stdout("\n");
+                return None;
+            };
+            let answer = answer.trim();
+            if answer.is_empty() {
+                return Some(default);
+            }
+            match answer.parse::<usize>() {
+                Ok(choice) if (1..=options.len()).contains(&choice) => return Some(choice - 1),
+                _ => write_stdout(&format!(
+                    "{}\n",
+                    style(
+                        &format!("Enter a number from 1 to {}.", options.len()),
+                        "yellow"
+                    )
+                )),
+            }
+        }
+    }
+
+    let _raw = RawMode::enable();
+    let mut selected = default;
+    write_stdout(&format!("{}\n", question));
+    render_options(options, selected);
+    let chosen = loop {
+        match read_key() {
+            Some(Key::Up) | Some(Key::Char('k')) => {
+                selected = (selected + options.len() - 1) % options.len()
+            }
+            Some(Key::Down) | Some(Key::Char('j')) => selected = (selected + 1) % options.len(),
+            Some(Key::Enter) => break Some(selected),
+            Some(Key::Cancel) | None => break None,
+            Some(_) => continue,
+        }
+        write_stdout(&format!("\x1b[{}A", options.len()));
+        render_options(options, selected);
+    };
+
+    // Collapse the menu into the question line followed by the answer.
+    write_stdout(&format!("\x1b[{}A\r\x1b[J", options.len() + 1));
+    let answer = chosen.map_or(String::new(), |index| style(&options[index], "cyan"));
+    write_stdout(&format!("{} {}\n", question, answer));
+    chosen
+}
+
+fn select_native(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let options = match args.get(1) {
+        Some(RuntimeValue::Array(list)) => list.snapshot(),
+        _ => Vec::new(),
+    };
+    let labels: Vec<String> = options.iter().map(RuntimeValue::to_string).collect();
+    let default = args
+        .get(2)
+        .and_then(|index| i64::from_raccoon(index).ok())
+        .unwrap_or(0)
+        .max(0) as usize;
+    match select(&arg_str(&args, 0), &labels, default) {
+        Some(index) => options[index].clone(),
+        None => RuntimeValue::Null(NullValue::new()),
+    }
+}
+
+fn confirm_native(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let default = args
+        .get(1)
+        .and_then(|default| bool::from_raccoon(default).ok())
+        .unwrap_or(false);
+    confirm(&arg_str(&args, 0), default).to_raccoon()
+}
+
+fn password_native(args: Vec<RuntimeValue>) -> RuntimeValue {
+    match password(&arg_str(&args, 0)) {
+        Some(secret) => secret.to_raccoon(),
+        None => RuntimeValue::Null(NullValue::new()),
+    }
+}
+
+fn input_native(args: Vec<RuntimeValue>) -> RuntimeValue {
+    input_with_default(&arg_str(&args, 0), &arg_str(&args, 1)).to_raccoon()
+}
+
+/// Declares the `std:prompt` natives as `_native_prompt_<name>`.
+pub fn register_prompt_wrappers(env: &mut Environment) {
+    let natives: [(&str, NativeFn); 4] = [
+        ("confirm", confirm_native),
+        ("select", select_native),
+        ("password", password_native),
+        ("input", input_native),
+    ];
+    for (name, implementation) in natives {
+        env.declare_lazy(format!("_native_prompt_{}", name), move || {
+            let native = NativeAsyncFunctionValue::new(
+                Arc::new(move |args| Box::pin(async move { implementation(args) })),
+                PrimitiveType::any(),
+            )
+            .with_name(format!("prompt_{}", name))
+            .with_module(Some("io"));
+            RuntimeValue::NativeAsyncFunction(Arc::new(native))
+        });
+    }
+}
@@ -1,61 +1,261 @@
-use crate::runtime::{FromRaccoon, Registrar, ToRaccoon};
+use crate::ast::types::PrimitiveType;
+use crate::fn_type;
+use crate::runtime::values::NativeFn;
+use crate::runtime::{
+    ArrayValue, Environment, FromRaccoon, IntValue, NativeFunctionValue, NullValue, Registrar,
+    RuntimeValue, ToRaccoon,
+};
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, RwLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-pub fn register_random_module(registrar: &mut Registrar) {
-    registrar.register_fn(
-        "random",
-        Some("random"),
-        |_args| {
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default();
-            let nanos = now.as_nanos() as u128;
-
-            let val = ((nanos % 1000000) as f64) / 1000000.0;
-            val.to_raccoon()
-        },
-        0,
-        Some(0),
-    );
+/// SplitMix64, a small generator fully determined by its 64-bit state.
+#[derive(Debug, Clone, Copy)]
+pub struct SplitMix64 {
+    pub state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// A generator seeded from the hasher's per-process keys and the clock.
+    pub fn from_entropy() -> Self {
+        Self::new(entropy_seed())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`, built from the top 53 bits so every value is exactly representable.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
 
-    registrar.register_fn(
-        "rand_int",
-        Some("random"),
-        |args| {
-            let min = i64::from_raccoon(&args[0]).unwrap_or(0);
-            let max = i64::from_raccoon(&args[1]).unwrap_or(100);
-
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default();
-            let nanos = now.as_nanos() as u128;
-            let range = max - min;
-            if range <= 0 {
-                min.to_raccoon()
-            } else {
-                let val = min + ((nanos % range as u128) as i64);
-                val.to_raccoon()
+    /// Uniform in `[0, bound)`; `bound` must be non-zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
             }
-        },
-        2,
-        Some(2),
+        }
+    }
+
+    /// Uniform integer in `[min, max)`, or `min` when the range is empty.
+    pub fn int_in(&mut self, min: i64, max: i64) -> i64 {
+        if max <= min {
+            return min;
+        }
+        let span = max.wrapping_sub(min) as u64;
+        min.wrapping_add(self.below(span) as i64)
+    }
+
+    pub fn uniform(&mut self, min: f64, max: f64) -> f64 {
+        min + self.next_f64() * (max - min)
+    }
+
+    /// Normally distributed sample via the Box–Muller transform.
+    pub fn gaussian(&mut self, mean: f64, stddev: f64) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+        mean + z * stddev
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// `k` distinct elements in random order (partial Fisher–Yates); `k` is capped at the length.
+    pub fn sample<T: Clone>(&mut self, items: &[T], k: usize) -> Vec<T> {
+        let mut pool = items.to_vec();
+        let k = k.min(pool.len());
+        for i in 0..k {
+            let j = i + self.below((pool.len() - i) as u64) as usize;
+            pool.swap(i, j);
+        }
+        pool.truncate(k);
+        pool
+    }
+}
+
+fn entropy_seed() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    hasher.write_u128(now.as_nanos());
+    hasher.finish()
+}
+
+thread_local! {
+    static SHARED: Cell<SplitMix64> = Cell::new(SplitMix64::from_entropy());
+}
+
+fn with_shared<T>(op: impl FnOnce(&mut SplitMix64) -> T) -> T {
+    SHARED.with(|shared| {
+        let mut rng = shared.get();
+        let result = op(&mut rng);
+        shared.set(rng);
+        result
+    })
+}
+
+fn with_instance<T>(rng: Option<&RuntimeValue>, op: impl FnOnce(&mut SplitMix64) -> T) -> T {
+    let Some(RuntimeValue::ClassInstance(instance)) = rng else {
+        return with_shared(op);
+    };
+    let mut properties = instance.properties.write().unwrap();
+    let seed = properties
+        .get("state")
+        .and_then(|state| i64::from_raccoon(state).ok())
+        .unwrap_or(0);
+    let mut generator = SplitMix64::new(seed as u64);
+    let result = op(&mut generator);
+    properties.insert(
+        "state".to_string(),
+        RuntimeValue::Int(IntValue::new(generator.state as i64)),
     );
+    result
+}
 
-    registrar.register_fn(
-        "rand_float",
-        Some("random"),
-        |args| {
-            let min = f64::from_raccoon(&args[0]).unwrap_or(0.0);
-            let max = f64::from_raccoon(&args[1]).unwrap_or(1.0);
-
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default();
-            let nanos = now.as_nanos() as u128;
-            let frac = ((nanos % 1000000) as f64) / 1000000.0;
-            (min + frac * (max - min)).to_raccoon()
-        },
-        2,
-        Some(2),
+fn number(args: &[RuntimeValue], index: usize, default: f64) -> f64 {
+    args.get(index)
+        .and_then(|value| f64::from_raccoon(value).ok())
+        .unwrap_or(default)
+}
+
+fn integer(args: &[RuntimeValue], index: usize, default: i64) -> i64 {
+    args.get(index)
+        .and_then(|value| i64::from_raccoon(value).ok())
+        .unwrap_or(default)
+}
+
+fn list(args: &[RuntimeValue], index: usize) -> Option<&ArrayValue> {
+    match args.get(index) {
+        Some(RuntimeValue::Array(list)) => Some(list),
+        _ => None,
+    }
+}
+
+fn random(_args: Vec<RuntimeValue>) -> RuntimeValue {
+    with_shared(SplitMix64::next_f64).to_raccoon()
+}
+
+fn rand_int(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let (min, max) = (integer(&args, 0, 0), integer(&args, 1, 100));
+    with_shared(|rng| rng.int_in(min, max)).to_raccoon()
+}
+
+fn rand_float(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let (min, max) = (number(&args, 0, 0.0), number(&args, 1, 1.0));
+    with_shared(|rng| rng.uniform(min, max)).to_raccoon()
+}
+
+fn seed(_args: Vec<RuntimeValue>) -> RuntimeValue {
+    (entropy_seed() as i64).to_raccoon()
+}
+
+fn seeded(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let Some(RuntimeValue::ClassInstance(parent)) = args.first() else {
+        return RuntimeValue::Null(NullValue::new());
+    };
+    let mut properties = parent.properties.read().unwrap().clone();
+    properties.insert(
+        "state".to_string(),
+        RuntimeValue::Int(IntValue::new(integer(&args, 1, 0))),
     );
+    let mut child = (**parent).clone();
+    child.properties = Arc::new(RwLock::new(properties));
+    RuntimeValue::ClassInstance(Arc::new(child))
+}
+
+fn next(args: Vec<RuntimeValue>) -> RuntimeValue {
+    with_instance(args.first(), SplitMix64::next_f64).to_raccoon()
+}
+
+fn int(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let (min, max) = (integer(&args, 1, 0), integer(&args, 2, 100));
+    with_instance(args.first(), |rng| rng.int_in(min, max)).to_raccoon()
+}
+
+fn uniform(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let (min, max) = (number(&args, 1, 0.0), number(&args, 2, 1.0));
+    with_instance(args.first(), |rng| rng.uniform(min, max)).to_raccoon()
+}
+
+fn gaussian(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let (mean, stddev) = (number(&args, 1, 0.0), number(&args, 2, 1.0));
+    with_instance(args.first(), |rng| rng.gaussian(mean, stddev)).to_raccoon()
+}
+
+fn shuffle(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let Some(source) = list(&args, 1) else {
+        return RuntimeValue::Null(NullValue::new());
+    };
+    let mut elements = source.snapshot();
+    with_instance(args.first(), |rng| rng.shuffle(&mut elements));
+    RuntimeValue::Array(ArrayValue::new(elements, source.element_type.clone()))
+}
+
+fn choice(args: Vec<RuntimeValue>) -> RuntimeValue {
+    match list(&args, 1) {
+        Some(source) if !source.is_empty() => {
+            let index = with_instance(args.first(), |rng| rng.below(source.len() as u64) as usize);
+            source.elements.read().unwrap()[index].clone()
+        }
+        _ => RuntimeValue::Null(NullValue::new()),
+    }
+}
+
+fn sample(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let Some(source) = list(&args, 1) else {
+        return RuntimeValue::Null(NullValue::new());
+    };
+    let k = integer(&args, 2, 1).max(0) as usize;
+    let elements = with_instance(args.first(), |rng| rng.sample(&source.snapshot(), k));
+    RuntimeValue::Array(ArrayValue::new(elements, source.element_type.clone()))
+}
+
+pub fn register_random_module(registrar: &mut Registrar) {
+    registrar.register_fn("random", Some("random"), random, 0, Some(0));
+    registrar.register_fn("rand_int", Some("random"), rand_int, 2, Some(2));
+    registrar.register_fn("rand_float", Some("random"), rand_float, 2, Some(2));
+}
+
+const WRAPPERS: [(&str, NativeFn); 9] = [
+    ("seed", seed),
+    ("seeded", seeded),
+    ("next", next),
+    ("int", int),
+    ("uniform", uniform),
+    ("gaussian", gaussian),
+    ("shuffle", shuffle),
+    ("choice", choice),
+    ("sample", sample),
+];
+
+/// Declares each native as `_native_random_<name>` for `std:random`.
+pub fn register_random_wrappers(env: &mut Environment) {
+    for (name, implementation) in WRAPPERS {
+        env.declare_lazy(format!("_native_random_{}", name), move || {
+            RuntimeValue::NativeFunction(NativeFunctionValue::new(
+                implementation,
+                fn_type!(variadic, PrimitiveType::any()),
+            ))
+        });
+    }
 }
@@ -0,0 +1,428 @@
+use crate::ast::types::{EnumType, PrimitiveType, Type};
+use crate::fn_type;
+use crate::runtime::values::NativeFn;
+use crate::runtime::{
+    ArrayValue, BigIntValue, BoolValue, DecimalValue, EnumValue, EnumValueData, Environment,
+    FloatValue, IntValue, MapKey, MapValue, NativeFunctionValue, NullValue, ObjectValue,
+    RangeValue, RuntimeValue, StrValue, SymbolValue, TupleValue,
+};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub const MAGIC: &[u8; 4] = b"RCCV";
+pub const FORMAT_VERSION: u8 = 1;
+
+const NULL: u8 = 0;
+const BOOL: u8 = 1;
+const INT: u8 = 2;
+const BIGINT: u8 = 3;
+const FLOAT: u8 = 4;
+const DECIMAL: u8 = 5;
+const STR: u8 = 6;
+const SYMBOL: u8 = 7;
+const LIST: u8 = 8;
+const TUPLE: u8 = 9;
+const RANGE: u8 = 10;
+const MAP: u8 = 11;
+const OBJECT: u8 = 12;
+const ENUM: u8 = 13;
+
+/// Encodes `value` in the binary `RCCV` format.
+pub fn serialize(value: &RuntimeValue) -> Result<Vec<u8>, String> {
+    let mut encoder = Encoder {
+        bytes: MAGIC.to_vec(),
+        open: Vec::new(),
+    };
+    encoder.bytes.push(FORMAT_VERSION);
+    encoder.value(value)?;
+    Ok(encoder.bytes)
+}
+
+/// Decodes bytes produced by [`serialize`].
+pub fn deserialize(bytes: &[u8]) -> Result<RuntimeValue, String> {
+    if bytes.len() < MAGIC.len() + 1 || &bytes[..MAGIC.len()] != MAGIC {
+        return Err("not a serialized Raccoon value".to_string());
+    }
+    let version = bytes[MAGIC.len()];
+    if version != FORMAT_VERSION {
+        return Err(format!(
+            "unsupported serialization format version {} (expected {})",
+            version, FORMAT_VERSION
+        ));
+    }
+
+    let mut decoder = Decoder {
+        bytes,
+        offset: MAGIC.len() + 1,
+    };
+    let value = decoder.value()?;
+    if decoder.offset != bytes.len() {
+        return Err(format!(
+            "{} unexpected trailing bytes",
+            bytes.len() - decoder.offset
+        ));
+    }
+    Ok(value)
+}
+
+struct Encoder {
+    bytes: Vec<u8>,
+    open: Vec<usize>,
+}
+
+impl Encoder {
+    fn value(&mut self, value: &RuntimeValue) -> Result<(), String> {
+        match value {
+            RuntimeValue::Null(_) => self.bytes.push(NULL),
+            RuntimeValue::Bool(b) => {
+                self.bytes.push(BOOL);
+                self.bytes.push(b.value as u8);
+            }
+            RuntimeValue::Int(i) => {
+                self.bytes.push(INT);
+                self.bytes.extend(i.value.to_le_bytes());
+            }
+            RuntimeValue::BigInt(i) => {
+                self.bytes.push(BIGINT);
+                self.bytes.extend(i.value.to_le_bytes());
+            }
+            RuntimeValue::Float(f) => {
+                self.bytes.push(FLOAT);
+                self.bytes.extend(f.value.to_le_bytes());
+            }
+            RuntimeValue::Decimal(d) => {
+                self.bytes.push(DECIMAL);
+                self.bytes.extend(d.value.to_le_bytes());
+            }
+            RuntimeValue::Str(s) => {
+                self.bytes.push(STR);
+                self.string(&s.value);
+            }
+            RuntimeValue::Symbol(symbol) => match symbol.registered_key() {
+                Some(key) => {
+                    self.bytes.push(SYMBOL);
+                    self.string(&key);
+                }
+                None => {
+                    return Err(format!(
+                        "cannot serialize {}: only symbols from Symbol.for() survive a round trip",
+                        symbol
+                    ))
+                }
+            },
+            RuntimeValue::Array(list) => {
+                self.enter(Arc::as_ptr(&list.elements) as usize)?;
+                let elements = list.snapshot();
+                self.bytes.push(LIST);
+                self.type_def(&list.element_type)?;
+                self.length(elements.len());
+                for element in &elements {
+                    self.value(element)?;
+                }
+                self.open.pop();
+            }
+            RuntimeValue::Tuple(tuple) => {
+                self.bytes.push(TUPLE);
+                self.length(tuple.elements.len());
+                for element in &tuple.elements {
+                    self.value(element)?;
+                }
+            }
+            RuntimeValue::Range(range) => {
+                self.bytes.push(RANGE);
+                for bound in [range.start, range.end, range.step] {
+                    self.bytes.extend(bound.to_le_bytes());
+                }
+                self.bytes.push(range.inclusive as u8);
+            }
+            RuntimeValue::Map(map) => {
+                self.enter(Arc::as_ptr(&map.entries) as usize)?;
+                let entries = map.entries.read().unwrap().clone();
+                self.bytes.push(MAP);
+                self.type_def(&map.key_type)?;
+                self.type_def(&map.value_type)?;
+                self.length(entries.len());
+                for (key, entry) in &entries {
+                    self.value(&key.to_value())?;
+                    self.value(entry)?;
+                }
+                self.open.pop();
+            }
+            RuntimeValue::Object(object) => {
+                self.enter(Arc::as_ptr(&object.properties) as usize)?;
+                // Symbol keys name process-local symbols, so like JSON they are left out.
+                let properties: Vec<(String, RuntimeValue)> = object
+                    .snapshot()
+                    .into_iter()
+                    .filter(|(key, _)| !SymbolValue::is_property_key(key))
+                    .collect();
+                self.bytes.push(OBJECT);
+                self.type_def(&object.obj_type)?;
+                self.length(properties.len());
+                for (key, property) in &properties {
+                    self.string(key);
+                    self.value(property)?;
+                }
+                self.open.pop();
+            }
+            RuntimeValue::Enum(member) => {
+                self.bytes.push(ENUM);
+                self.string(&member.enum_name);
+                self.string(&member.member_name);
+                match &member.value {
+                    EnumValueData::Int(value) => {
+                        self.bytes.push(INT);
+                        self.bytes.extend(value.to_le_bytes());
+                    }
+                    EnumValueData::Str(value) => {
+                        self.bytes.push(STR);
+                        self.string(value);
+                    }
+                }
+            }
+            RuntimeValue::Function(_)
+            | RuntimeValue::NativeFunction(_)
+            | RuntimeValue::NativeAsyncFunction(_) => {
+                return Err("cannot serialize a function".to_string())
+            }
+            RuntimeValue::ClassInstance(instance) => {
+                return Err(format!(
+                    "cannot serialize an instance of {}; convert it to a plain object first",
+                    instance.class_name
+                ))
+            }
+            other => {
+                return Err(format!(
+                    "cannot serialize a value of type {}",
+                    other.get_name()
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn enter(&mut self, storage: usize) -> Result<(), String> {
+        if self.open.contains(&storage) {
+            return Err("cannot serialize a value that contains itself".to_string());
+        }
+        self.open.push(storage);
+        Ok(())
+    }
+
+    fn length(&mut self, mut length: usize) {
+        loop {
+            let byte = (length & 0x7f) as u8;
+            length >>= 7;
+            if length == 0 {
+                self.bytes.push(byte);
+                return;
+            }
+            self.bytes.push(byte | 0x80);
+        }
+    }
+
+    fn string(&mut self, text: &str) {
+        self.length(text.len());
+        self.bytes.extend(text.as_bytes());
+    }
+
+    fn type_def(&mut self, type_def: &Type) -> Result<(), String> {
+        let json = serde_json::to_string(type_def)
+            .map_err(|error| format!("cannot serialize type {}: {}", type_def, error))?;
+        self.string(&json);
+        Ok(())
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Decoder<'_> {
+    fn value(&mut self) -> Result<RuntimeValue, String> {
+        let tag = self.byte()?;
+        let value = match tag {
+            NULL => RuntimeValue::Null(NullValue::new()),
+            BOOL => RuntimeValue::Bool(BoolValue::new(self.byte()? != 0)),
+            INT => RuntimeValue::Int(IntValue::new(i64::from_le_bytes(self.array()?))),
+            BIGINT => RuntimeValue::BigInt(BigIntValue::new(i128::from_le_bytes(self.array()?))),
+            FLOAT => RuntimeValue::Float(FloatValue::new(f64::from_le_bytes(self.array()?))),
+            DECIMAL => RuntimeValue::Decimal(DecimalValue::new(f64::from_le_bytes(self.array()?))),
+            STR => RuntimeValue::Str(StrValue::new(self.string()?)),
+            SYMBOL => RuntimeValue::Symbol(SymbolValue::registered(&self.string()?)),
+            LIST => {
+                let element_type = self.type_def()?;
+                let length = self.length()?;
+                let mut elements = Vec::with_capacity(length.min(self.remaining()));
+                for _ in 0..length {
+                    elements.push(self.value()?);
+                }
+                RuntimeValue::Array(ArrayValue::new(elements, element_type))
+            }
+            TUPLE => {
+                let length = self.length()?;
+                let mut elements = Vec::with_capacity(length.min(self.remaining()));
+                for _ in 0..length {
+                    elements.push(self.value()?);
+                }
+                RuntimeValue::Tuple(TupleValue::new(elements))
+            }
+            RANGE => {
+                let start = i64::from_le_bytes(self.array()?);
+                let end = i64::from_le_bytes(self.array()?);
+                let step = i64::from_le_bytes(self.array()?);
+                let mut range = RangeValue::new(start, end, self.byte()? != 0);
+                range.step = step;
+                RuntimeValue::Range(range)
+            }
+            MAP => {
+                let key_type = self.type_def()?;
+                let value_type = self.type_def()?;
+                let length = self.length()?;
+                let mut entries = IndexMap::with_capacity(length.min(self.remaining()));
+                for _ in 0..length {
+                    let key = MapKey::from_value(&self.value()?)?;
+                    entries.insert(key, self.value()?);
+                }
+                RuntimeValue::Map(Arc::new(MapValue::new(entries, key_type, value_type)))
+            }
+            OBJECT => {
+                let obj_type = self.type_def()?;
+                let length = self.length()?;
+                let mut properties = Vec::with_capacity(length.min(self.remaining()));
+                for _ in 0..length {
+                    let key = self.string()?;
+                    properties.push((key, self.value()?));
+                }
+                RuntimeValue::Object(ObjectValue::new(properties, obj_type))
+            }
+            ENUM => {
+                let enum_name = self.string()?;
+                let member_name = self.string()?;
+                let value = match self.byte()? {
+                    INT => EnumValueData::Int(i64::from_le_bytes(self.array()?)),
+                    STR => EnumValueData::Str(self.string()?),
+                    other => return Err(format!("invalid enum value tag {}", other)),
+                };
+                let enum_type = Type::Enum(Box::new(EnumType {
+                    name: enum_name.clone(),
+                    members: HashMap::new(),
+                }));
+                RuntimeValue::Enum(Arc::new(EnumValue::new(
+                    enum_name,
+                    member_name,
+                    value,
+                    enum_type,
+                )))
+            }
+            other => return Err(format!("invalid value tag {}", other)),
+        };
+        Ok(value)
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+
+    fn take(&mut self, count: usize) -> Result<&[u8], String> {
+        if self.remaining() < count {
+            return Err("serialized value is truncated".to_string());
+        }
+        let slice = &self.bytes[self.offset..self.offset + count];
+        self.offset += count;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn length(&mut self) -> Result<usize, String> {
+        let mut length = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.byte()?;
+            length |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(length);
+            }
+        }
+        Err("invalid length".to_string())
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let length = self.length()?;
+        let bytes = self.take(length)?.to_vec();
+        String::from_utf8(bytes).map_err(|_| "invalid UTF-8 in serialized string".to_string())
+    }
+
+    fn type_def(&mut self) -> Result<Type, String> {
+        let json = self.string()?;
+        Ok(serde_json::from_str(&json).unwrap_or_else(|_| PrimitiveType::any()))
+    }
+}
+
+fn outcome(result: Result<RuntimeValue, String>) -> RuntimeValue {
+    let (value, error) = match result {
+        Ok(value) => (value, RuntimeValue::Null(NullValue::new())),
+        Err(error) => (
+            RuntimeValue::Null(NullValue::new()),
+            RuntimeValue::Str(StrValue::new(error)),
+        ),
+    };
+    RuntimeValue::Object(ObjectValue::new(
+        [("value".to_string(), value), ("error".to_string(), error)],
+        PrimitiveType::any(),
+    ))
+}
+
+fn encode(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let value = args
+        .into_iter()
+        .next()
+        .unwrap_or(RuntimeValue::Null(NullValue::new()));
+    outcome(serialize(&value).map(|bytes| {
+        let bytes = bytes
+            .into_iter()
+            .map(|byte| RuntimeValue::Int(IntValue::new(byte as i64)))
+            .collect();
+        RuntimeValue::Array(ArrayValue::new(bytes, PrimitiveType::int()))
+    }))
+}
+
+fn decode(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let bytes = match args.first() {
+        Some(RuntimeValue::Array(list)) => list
+            .snapshot()
+            .iter()
+            .map(|byte| match byte {
+                RuntimeValue::Int(byte) if (0..=255).contains(&byte.value) => Ok(byte.value as u8),
+                other => Err(format!("{} is not a byte", other.to_string())),
+            })
+            .collect::<Result<Vec<u8>, String>>(),
+        _ => Err("deserialize() expects a list of bytes".to_string()),
+    };
+    outcome(bytes.and_then(|bytes| deserialize(&bytes)))
+}
+
+pub fn register_serialize_wrappers(env: &mut Environment) {
+    let natives: [(&str, NativeFn); 2] = [("encode", encode), ("decode", decode)];
+    for (name, implementation) in natives {
+        env.declare_lazy(format!("_native_serialize_{}", name), move || {
+            RuntimeValue::NativeFunction(NativeFunctionValue::new(
+                implementation,
+                fn_type!(variadic, PrimitiveType::any()),
+            ))
+        });
+    }
+    env.declare_lazy("_native_serialize_version", || {
+        RuntimeValue::Int(IntValue::new(FORMAT_VERSION as i64))
+    });
+}
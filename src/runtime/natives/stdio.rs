@@ -0,0 +1,154 @@
+use super::terminal;
+use crate::ast::types::PrimitiveType;
+use crate::runtime::{
+    ArrayValue, FromRaccoon, NativeAsyncFunctionValue, NullValue, ObjectValue, Registrar,
+    RuntimeValue, StrValue, ToRaccoon,
+};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Read, Write};
+use std::sync::{Arc, Mutex};
+
+pub fn read_line() -> Option<String> {
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim_end_matches(&['\r', '\n'][..]).to_string()),
+    }
+}
+
+fn optional_str(value: Option<String>) -> RuntimeValue {
+    match value {
+        Some(s) => RuntimeValue::Str(StrValue::new(s)),
+        None => RuntimeValue::Null(NullValue::new()),
+    }
+}
+
+fn write_to(mut out: impl Write, args: &[RuntimeValue]) -> RuntimeValue {
+    let text = args.first().map(|arg| arg.to_string()).unwrap_or_default();
+    let ok = out.write_all(text.as_bytes()).is_ok() && out.flush().is_ok();
+    ok.to_raccoon()
+}
+
+pub fn core_stdin_read_line(_args: Vec<RuntimeValue>) -> RuntimeValue {
+    optional_str(read_line())
+}
+
+pub fn core_stdin_read_char(_args: Vec<RuntimeValue>) -> RuntimeValue {
+    let mut stdin = io::stdin().lock();
+    let mut bytes = Vec::with_capacity(4);
+    let mut byte = [0u8; 1];
+
+    while bytes.len() < 4 {
+        match stdin.read(&mut byte) {
+            Ok(1) => bytes.push(byte[0]),
+            _ => break,
+        }
+        if let Ok(s) = std::str::from_utf8(&bytes) {
+            return s.to_string().to_raccoon();
+        }
+    }
+
+    optional_str((!bytes.is_empty()).then(|| String::from_utf8_lossy(&bytes).to_string()))
+}
+
+pub fn core_stdin_lines(_args: Vec<RuntimeValue>) -> RuntimeValue {
+    let finished = Arc::new(Mutex::new(false));
+    let mut properties = HashMap::new();
+
+    let next_finished = finished.clone();
+    let next = NativeAsyncFunctionValue::new(
+        Arc::new(move |_args| {
+            let finished = next_finished.clone();
+            Box::pin(async move {
+                let mut finished = finished.lock().unwrap();
+                let line = if *finished { None } else { read_line() };
+                *finished = line.is_none();
+                optional_str(line)
+            })
+        }),
+        PrimitiveType::any(),
+    );
+
+    let collect = NativeAsyncFunctionValue::new(
+        Arc::new(move |_args| {
+            let finished = finished.clone();
+            Box::pin(async move {
+                let mut finished = finished.lock().unwrap();
+                let mut lines = Vec::new();
+                while !*finished {
+                    match read_line() {
+                        Some(line) => lines.push(RuntimeValue::Str(StrValue::new(line))),
+                        None => *finished = true,
+                    }
+                }
+                RuntimeValue::Array(ArrayValue::new(lines, PrimitiveType::str()))
+            })
+        }),
+        PrimitiveType::any(),
+    );
+
+    properties.insert(
+        "next".to_string(),
+        RuntimeValue::NativeAsyncFunction(Arc::new(next)),
+    );
+    properties.insert(
+        "collect".to_string(),
+        RuntimeValue::NativeAsyncFunction(Arc::new(collect)),
+    );
+
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+pub fn core_stdout_write(args: Vec<RuntimeValue>) -> RuntimeValue {
+    write_to(io::stdout().lock(), &args)
+}
+
+pub fn core_stderr_write(args: Vec<RuntimeValue>) -> RuntimeValue {
+    write_to(io::stderr().lock(), &args)
+}
+
+pub fn core_stdin_is_tty(_args: Vec<RuntimeValue>) -> RuntimeValue {
+    terminal::is_tty(terminal::STDIN).to_raccoon()
+}
+
+pub fn core_stdout_is_tty(_args: Vec<RuntimeValue>) -> RuntimeValue {
+    terminal::is_tty(terminal::STDOUT).to_raccoon()
+}
+
+pub fn core_stdin_set_raw_mode(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let enabled = bool::from_raccoon(&args[0]).unwrap_or(false);
+    terminal::set_raw_mode(enabled).to_raccoon()
+}
+
+pub fn restore_terminal() {
+    terminal::set_raw_mode(false);
+}
+
+pub fn register_stdio_primitives(registrar: &mut Registrar) {
+    registrar.register_restricted_fn(
+        "core_stdin_read_line",
+        "io",
+        core_stdin_read_line,
+        0,
+        Some(0),
+    );
+    registrar.register_restricted_fn(
+        "core_stdin_read_char",
+        "io",
+        core_stdin_read_char,
+        0,
+        Some(0),
+    );
+    registrar.register_restricted_fn("core_stdin_lines", "io", core_stdin_lines, 0, Some(0));
+    registrar.register_restricted_fn("core_stdin_is_tty", "io", core_stdin_is_tty, 0, Some(0));
+    registrar.register_restricted_fn(
+        "core_stdin_set_raw_mode",
+        "io",
+        core_stdin_set_raw_mode,
+        1,
+        Some(1),
+    );
+    registrar.register_fn("core_stdout_write", None, core_stdout_write, 1, Some(1));
+    registrar.register_fn("core_stdout_is_tty", None, core_stdout_is_tty, 0, Some(0));
+    registrar.register_fn("core_stderr_write", None, core_stderr_write, 1, Some(1));
+}
@@ -0,0 +1,148 @@
+use super::json::{convert_runtime_to_serde, convert_serde_to_runtime};
+use crate::ast::types::PrimitiveType;
+use crate::runtime::{
+    ArrayValue, FromRaccoon, NativeAsyncFunctionValue, NullValue, ObjectValue, Registrar,
+    RuntimeValue, StrValue, ToRaccoon,
+};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+type StoreMethod = fn(&mut KeyValueStore, &[RuntimeValue]) -> RuntimeValue;
+
+#[derive(Debug)]
+pub struct KeyValueStore {
+    path: PathBuf,
+    entries: Map<String, JsonValue>,
+}
+
+impl KeyValueStore {
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let entries = match fs::read_to_string(&path) {
+            Ok(content) if content.trim().is_empty() => Map::new(),
+            Ok(content) => match serde_json::from_str(&content)? {
+                JsonValue::Object(entries) => entries,
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "store file must contain a JSON object",
+                    ))
+                }
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Map::new(),
+            Err(error) => return Err(error),
+        };
+
+        Ok(Self { path, entries })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        self.entries.get(key)
+    }
+
+    pub fn set(&mut self, key: &str, value: JsonValue) -> io::Result<()> {
+        self.entries.insert(key.to_string(), value);
+        self.persist()
+    }
+
+    pub fn delete(&mut self, key: &str) -> io::Result<bool> {
+        if self.entries.remove(key).is_none() {
+            return Ok(false);
+        }
+        self.persist().map(|_| true)
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.entries.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn persist(&self) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(&self.entries)?;
+        let temp = temp_path(&self.path);
+
+        let mut file = File::create(&temp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&temp, &self.path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+fn key_arg(args: &[RuntimeValue]) -> String {
+    args.first()
+        .and_then(|arg| String::from_raccoon(arg).ok())
+        .unwrap_or_default()
+}
+
+fn store_method(store: &Arc<Mutex<KeyValueStore>>, method: StoreMethod) -> RuntimeValue {
+    let store = store.clone();
+    RuntimeValue::NativeAsyncFunction(Arc::new(NativeAsyncFunctionValue::new(
+        Arc::new(move |args| {
+            let store = store.clone();
+            Box::pin(async move { method(&mut store.lock().unwrap(), &args) })
+        }),
+        PrimitiveType::any(),
+    )))
+}
+
+pub fn core_store_open(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let path = key_arg(&args);
+    let store = match KeyValueStore::open(&path) {
+        Ok(store) => Arc::new(Mutex::new(store)),
+        Err(_) => return RuntimeValue::Null(NullValue::new()),
+    };
+
+    let methods: [(&str, StoreMethod); 4] = [
+        ("get", |store, args| match store.get(&key_arg(args)) {
+            Some(value) => convert_serde_to_runtime(value),
+            None => RuntimeValue::Null(NullValue::new()),
+        }),
+        ("set", |store, args| {
+            let value = args
+                .get(1)
+                .map(convert_runtime_to_serde)
+                .unwrap_or(JsonValue::Null);
+            store.set(&key_arg(args), value).is_ok().to_raccoon()
+        }),
+        ("delete", |store, args| {
+            store.delete(&key_arg(args)).unwrap_or(false).to_raccoon()
+        }),
+        ("keys", |store, _args| {
+            let keys = store
+                .keys()
+                .into_iter()
+                .map(|key| RuntimeValue::Str(StrValue::new(key)))
+                .collect();
+            RuntimeValue::Array(ArrayValue::new(keys, PrimitiveType::str()))
+        }),
+    ];
+
+    let mut properties = HashMap::new();
+    properties.insert("path".to_string(), path.to_raccoon());
+    for (name, method) in methods {
+        properties.insert(name.to_string(), store_method(&store, method));
+    }
+
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+pub fn register_store_primitives(registrar: &mut Registrar) {
+    registrar.register_restricted_fn("core_store_open", "io", core_store_open, 1, Some(1));
+}
@@ -1,4 +1,9 @@
-use crate::runtime::{FromRaccoon, Registrar, RuntimeValue, ToRaccoon};
+use crate::ast::types::PrimitiveType;
+use crate::fn_type;
+use crate::runtime::{
+    BoolValue, Environment, FloatValue, FromRaccoon, IntValue, NativeFunctionValue, NullValue,
+    ObjectValue, Registrar, RuntimeValue, StrValue, ToRaccoon,
+};
 
 pub fn register_string_module(registrar: &mut Registrar) {
     registrar.register_fn(
@@ -191,3 +196,185 @@ pub fn register_string_module(registrar: &mut Registrar) {
         Some(2),
     );
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Capture {
+    Str,
+    Int,
+    Float,
+    Bool,
+}
+
+impl Capture {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "str" => Some(Capture::Str),
+            "int" => Some(Capture::Int),
+            "float" => Some(Capture::Float),
+            "bool" => Some(Capture::Bool),
+            _ => None,
+        }
+    }
+
+    fn convert(self, text: &str) -> Option<RuntimeValue> {
+        if text.is_empty() {
+            return None;
+        }
+        match self {
+            Capture::Str => Some(RuntimeValue::Str(StrValue::new(text.to_string()))),
+            Capture::Int => text
+                .parse::<i64>()
+                .ok()
+                .map(|value| RuntimeValue::Int(IntValue::new(value))),
+            Capture::Float => text
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .map(|value| RuntimeValue::Float(FloatValue::new(value))),
+            Capture::Bool => match text {
+                "true" => Some(RuntimeValue::Bool(BoolValue::new(true))),
+                "false" => Some(RuntimeValue::Bool(BoolValue::new(false))),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(Vec<char>),
+    Placeholder(String, Capture),
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = Vec::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut body = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(ch) => body.push(ch),
+                        None => return Err(format!("unclosed placeholder '{{{}'", body)),
+                    }
+                }
+                let (name, type_name) = body.split_once(':').unwrap_or((&body, "str"));
+                let (name, type_name) = (name.trim(), type_name.trim());
+                if name.is_empty() {
+                    return Err(format!("placeholder '{{{}}}' has no name", body));
+                }
+                let capture = Capture::from_name(type_name).ok_or_else(|| {
+                    format!(
+                        "unknown placeholder type '{}' (expected str, int, float or bool)",
+                        type_name
+                    )
+                })?;
+                if segments
+                    .iter()
+                    .any(|segment| matches!(segment, Segment::Placeholder(seen, _) if seen == name))
+                {
+                    return Err(format!("placeholder '{}' appears more than once", name));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Placeholder(name.to_string(), capture));
+            }
+            '}' => return Err("unmatched '}' (write '}}' for a literal brace)".to_string()),
+            ch => literal.push(ch),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+fn match_segments(
+    segments: &[Segment],
+    text: &[char],
+    start: usize,
+    captures: &mut Vec<(String, RuntimeValue)>,
+) -> bool {
+    let Some((segment, rest)) = segments.split_first() else {
+        return start == text.len();
+    };
+
+    match segment {
+        Segment::Literal(literal) => {
+            text[start..].starts_with(literal)
+                && match_segments(rest, text, start + literal.len(), captures)
+        }
+        Segment::Placeholder(name, capture) => {
+            let ends: Vec<usize> = if rest.is_empty() {
+                vec![text.len()]
+            } else {
+                (start + 1..=text.len()).collect()
+            };
+            for end in ends {
+                let captured: String = text[start..end].iter().collect();
+                let Some(value) = capture.convert(&captured) else {
+                    continue;
+                };
+                captures.push((name.clone(), value));
+                if match_segments(rest, text, end, captures) {
+                    return true;
+                }
+                captures.pop();
+            }
+            false
+        }
+    }
+}
+
+/// The placeholders of `pattern` in `text`; `Ok(None)` when it does not match.
+pub fn extract(text: &str, pattern: &str) -> Result<Option<Vec<(String, RuntimeValue)>>, String> {
+    let segments = parse_pattern(pattern)?;
+    let text: Vec<char> = text.chars().collect();
+    let mut captures = Vec::new();
+    Ok(match_segments(&segments, &text, 0, &mut captures).then_some(captures))
+}
+
+fn extract_native(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let null = || RuntimeValue::Null(NullValue::new());
+    let text = args
+        .first()
+        .map(RuntimeValue::to_string)
+        .unwrap_or_default();
+    let pattern = args.get(1).map(RuntimeValue::to_string).unwrap_or_default();
+
+    let (value, error) = match extract(&text, &pattern) {
+        Ok(Some(captures)) => (
+            RuntimeValue::Object(ObjectValue::new(captures, PrimitiveType::any())),
+            null(),
+        ),
+        Ok(None) => (null(), null()),
+        Err(error) => (null(), RuntimeValue::Str(StrValue::new(error))),
+    };
+    RuntimeValue::Object(ObjectValue::new(
+        [("value".to_string(), value), ("error".to_string(), error)],
+        PrimitiveType::any(),
+    ))
+}
+
+/// Declares the natives behind `std:string` helpers implemented in Rust.
+pub fn register_string_wrappers(env: &mut Environment) {
+    env.declare_lazy("_native_string_extract", || {
+        RuntimeValue::NativeFunction(NativeFunctionValue::new(
+            extract_native,
+            fn_type!(variadic, PrimitiveType::any()),
+        ))
+    });
+}
@@ -0,0 +1,202 @@
+use super::terminal;
+use crate::ast::types::{FunctionType, PrimitiveType, Type};
+use crate::fn_type;
+use crate::runtime::{
+    Environment, FromRaccoon, IntValue, NativeFunctionValue, NativeThunk, ObjectValue,
+    RuntimeValue, ToRaccoon,
+};
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+const RESET: &str = "\x1b[0m";
+
+fn style_code(name: &str) -> Option<u8> {
+    let code = match name {
+        "bold" => 1,
+        "dim" => 2,
+        "italic" => 3,
+        "underline" => 4,
+        "blink" => 5,
+        "inverse" => 7,
+        "hidden" => 8,
+        "strikethrough" => 9,
+        "black" => 30,
+        "red" => 31,
+        "green" => 32,
+        "yellow" => 33,
+        "blue" => 34,
+        "magenta" => 35,
+        "cyan" => 36,
+        "white" => 37,
+        "gray" | "grey" => 90,
+        "bgBlack" => 40,
+        "bgRed" => 41,
+        "bgGreen" => 42,
+        "bgYellow" => 43,
+        "bgBlue" => 44,
+        "bgMagenta" => 45,
+        "bgCyan" => 46,
+        "bgWhite" => 47,
+        _ => return None,
+    };
+    Some(code)
+}
+
+pub fn style(text: &str, styles: &str) -> String {
+    if !terminal::colors_enabled() {
+        return text.to_string();
+    }
+
+    let codes: Vec<String> = styles
+        .split([' ', ','])
+        .filter_map(style_code)
+        .map(|code| code.to_string())
+        .collect();
+
+    if codes.is_empty() {
+        text.to_string()
+    } else {
+        format!("\x1b[{}m{}{}", codes.join(";"), text, RESET)
+    }
+}
+
+pub fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+pub fn progress_bar(current: i64, total: i64, width: usize, label: &str) -> String {
+    let ratio = if total > 0 {
+        (current as f64 / total as f64).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    let filled = (ratio * width as f64).round() as usize;
+    let bar = format!("{}{}", "█".repeat(filled), "░".repeat(width - filled));
+    let percent = format!("{:>3}%", (ratio * 100.0).round() as i64);
+
+    let line = format!("[{}] {} ({}/{})", bar, percent, current.min(total), total);
+    if label.is_empty() {
+        line
+    } else {
+        format!("{} {}", label, line)
+    }
+}
+
+fn write_stdout(text: &str) -> bool {
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(text.as_bytes()).is_ok() && stdout.flush().is_ok()
+}
+
+fn arg_str(args: &[RuntimeValue], index: usize) -> String {
+    args.get(index)
+        .map(|arg| arg.to_string())
+        .unwrap_or_default()
+}
+
+fn arg_int(args: &[RuntimeValue], index: usize, default: i64) -> i64 {
+    args.get(index)
+        .and_then(|arg| i64::from_raccoon(arg).ok())
+        .unwrap_or(default)
+}
+
+pub fn register_term_wrappers(env: &mut Environment) {
+    let natives: [(&str, NativeThunk); 8] = [
+        ("_native_term_style", || {
+            NativeFunctionValue::new(
+                |args| style(&arg_str(&args, 0), &arg_str(&args, 1)).to_raccoon(),
+                Type::Function(Box::new(FunctionType {
+                    params: vec![PrimitiveType::str(), PrimitiveType::str()],
+                    return_type: PrimitiveType::str(),
+                    is_variadic: false,
+                    param_names: Vec::new(),
+                })),
+            )
+        }),
+        ("_native_term_strip", || {
+            NativeFunctionValue::new(
+                |args| strip_ansi(&arg_str(&args, 0)).to_raccoon(),
+                fn_type!(PrimitiveType::str(), PrimitiveType::str()),
+            )
+        }),
+        ("_native_term_enabled", || {
+            NativeFunctionValue::new(
+                |_args| terminal::colors_enabled().to_raccoon(),
+                fn_type!(PrimitiveType::bool()),
+            )
+        }),
+        ("_native_term_is_tty", || {
+            NativeFunctionValue::new(
+                |_args| terminal::is_tty(terminal::STDOUT).to_raccoon(),
+                fn_type!(PrimitiveType::bool()),
+            )
+        }),
+        ("_native_term_control", || {
+            NativeFunctionValue::new(
+                |args| {
+                    let enabled = terminal::is_tty(terminal::STDOUT);
+                    (enabled && write_stdout(&format!("\x1b[{}", arg_str(&args, 0)))).to_raccoon()
+                },
+                fn_type!(PrimitiveType::str(), PrimitiveType::bool()),
+            )
+        }),
+        ("_native_term_write", || {
+            NativeFunctionValue::new(
+                |args| write_stdout(&arg_str(&args, 0)).to_raccoon(),
+                fn_type!(PrimitiveType::str(), PrimitiveType::bool()),
+            )
+        }),
+        ("_native_term_size", || {
+            NativeFunctionValue::new(
+                |_args| {
+                    let (columns, rows) = terminal::size();
+                    let mut properties = HashMap::new();
+                    properties.insert(
+                        "columns".to_string(),
+                        RuntimeValue::Int(IntValue::new(columns as i64)),
+                    );
+                    properties.insert(
+                        "rows".to_string(),
+                        RuntimeValue::Int(IntValue::new(rows as i64)),
+                    );
+                    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+                },
+                fn_type!(PrimitiveType::any()),
+            )
+        }),
+        ("_native_term_progress", || {
+            NativeFunctionValue::new(
+                |args| {
+                    let width = arg_int(&args, 2, 30).max(1) as usize;
+                    progress_bar(
+                        arg_int(&args, 0, 0),
+                        arg_int(&args, 1, 0),
+                        width,
+                        &arg_str(&args, 3),
+                    )
+                    .to_raccoon()
+                },
+                fn_type!(variadic, PrimitiveType::str()),
+            )
+        }),
+    ];
+
+    for (name, native) in natives {
+        env.declare_lazy(name, move || RuntimeValue::NativeFunction(native()));
+    }
+}
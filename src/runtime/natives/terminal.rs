@@ -0,0 +1,107 @@
+#[cfg(unix)]
+mod platform {
+    use std::sync::Mutex;
+
+    pub const STDIN: i32 = libc::STDIN_FILENO;
+    pub const STDOUT: i32 = libc::STDOUT_FILENO;
+    pub const STDERR: i32 = libc::STDERR_FILENO;
+
+    static ORIGINAL_MODE: Mutex<Option<libc::termios>> = Mutex::new(None);
+
+    pub fn is_tty(fd: i32) -> bool {
+        unsafe { libc::isatty(fd) == 1 }
+    }
+
+    pub fn size() -> Option<(u16, u16)> {
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        let ok = unsafe { libc::ioctl(STDOUT, libc::TIOCGWINSZ, &mut size) } == 0;
+        (ok && size.ws_col > 0).then_some((size.ws_col, size.ws_row))
+    }
+
+    pub fn is_raw_mode() -> bool {
+        ORIGINAL_MODE.lock().unwrap().is_some()
+    }
+
+    pub fn set_raw_mode(enabled: bool) -> bool {
+        let mut original = ORIGINAL_MODE.lock().unwrap();
+
+        if !enabled {
+            return match original.take() {
+                Some(mode) => unsafe { libc::tcsetattr(STDIN, libc::TCSANOW, &mode) == 0 },
+                None => true,
+            };
+        }
+
+        if original.is_some() {
+            return true;
+        }
+
+        unsafe {
+            let mut mode: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(STDIN, &mut mode) != 0 {
+                return false;
+            }
+
+            let mut raw = mode;
+            libc::cfmakeraw(&mut raw);
+            raw.c_oflag |= libc::OPOST;
+            if libc::tcsetattr(STDIN, libc::TCSANOW, &raw) != 0 {
+                return false;
+            }
+
+            *original = Some(mode);
+        }
+
+        true
+    }
+}
+
+#[cfg(not(unix))]
+mod platform {
+    pub const STDIN: i32 = 0;
+    pub const STDOUT: i32 = 1;
+    pub const STDERR: i32 = 2;
+
+    pub fn is_tty(_fd: i32) -> bool {
+        false
+    }
+
+    pub fn size() -> Option<(u16, u16)> {
+        None
+    }
+
+    pub fn is_raw_mode() -> bool {
+        false
+    }
+
+    pub fn set_raw_mode(enabled: bool) -> bool {
+        !enabled
+    }
+}
+
+pub use platform::{is_raw_mode, is_tty, set_raw_mode, STDERR, STDIN, STDOUT};
+
+pub fn size() -> (u16, u16) {
+    let from_env = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u16>().ok());
+
+    platform::size().unwrap_or_else(|| {
+        (
+            from_env("COLUMNS").unwrap_or(80),
+            from_env("LINES").unwrap_or(24),
+        )
+    })
+}
+
+pub fn colors_enabled() -> bool {
+    colors_enabled_for(STDOUT)
+}
+
+pub fn colors_enabled_for(fd: i32) -> bool {
+    if std::env::var_os("NO_COLOR").is_some() {
+        return false;
+    }
+    if std::env::var_os("FORCE_COLOR").is_some() {
+        return true;
+    }
+    is_tty(fd) && std::env::var("TERM").map_or(true, |term| term != "dumb")
+}
@@ -0,0 +1,257 @@
+use crate::ast::types::{PrimitiveType, Type};
+use crate::fn_type;
+use crate::runtime::{
+    Environment, FromRaccoon, IntValue, NativeFunctionValue, NativeThunk, NullValue, ObjectValue,
+    RuntimeValue, ToRaccoon,
+};
+use indexmap::IndexMap;
+use url::{form_urlencoded, Url};
+
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"-_.!~*'()".contains(&byte)
+}
+
+pub fn encode_component(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if is_unreserved(byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+pub fn decode_component(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+pub fn parse_query(query: &str) -> Vec<(String, String)> {
+    form_urlencoded::parse(query.trim_start_matches('?').as_bytes())
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+pub fn stringify_query<K: AsRef<str>, V: AsRef<str>>(pairs: &[(K, V)]) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs.iter().map(|(k, v)| (k.as_ref(), v.as_ref())))
+        .finish()
+}
+
+pub fn append_query<K: AsRef<str>, V: AsRef<str>>(url: &str, pairs: &[(K, V)]) -> String {
+    if pairs.is_empty() {
+        return url.to_string();
+    }
+
+    match Url::parse(url) {
+        Ok(mut parsed) => {
+            parsed
+                .query_pairs_mut()
+                .extend_pairs(pairs.iter().map(|(k, v)| (k.as_ref(), v.as_ref())));
+            parsed.to_string()
+        }
+        Err(_) => {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            format!("{}{}{}", url, separator, stringify_query(pairs))
+        }
+    }
+}
+
+fn query_object(pairs: Vec<(String, String)>) -> RuntimeValue {
+    let properties: Vec<(String, RuntimeValue)> = pairs
+        .into_iter()
+        .map(|(key, value)| (key, value.to_raccoon()))
+        .collect();
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+pub fn query_pairs(value: &RuntimeValue) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = match value {
+        RuntimeValue::Object(object) => object
+            .properties
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.to_string()))
+            .collect(),
+        RuntimeValue::Map(map) => map
+            .entries
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    };
+    pairs.sort();
+    pairs
+}
+
+pub fn parse(input: &str) -> Option<RuntimeValue> {
+    let url = Url::parse(input).ok()?;
+
+    let optional = |value: Option<&str>| match value {
+        Some(value) => value.to_string().to_raccoon(),
+        None => RuntimeValue::Null(NullValue::new()),
+    };
+    let port = match url.port_or_known_default() {
+        Some(port) => RuntimeValue::Int(IntValue::new(port as i64)),
+        None => RuntimeValue::Null(NullValue::new()),
+    };
+
+    let mut properties = IndexMap::new();
+    properties.insert("href".to_string(), url.to_string().to_raccoon());
+    properties.insert("scheme".to_string(), url.scheme().to_string().to_raccoon());
+    properties.insert(
+        "username".to_string(),
+        url.username().to_string().to_raccoon(),
+    );
+    properties.insert("password".to_string(), optional(url.password()));
+    properties.insert("host".to_string(), optional(url.host_str()));
+    properties.insert("port".to_string(), port);
+    properties.insert("path".to_string(), url.path().to_string().to_raccoon());
+    properties.insert("search".to_string(), optional(url.query()));
+    properties.insert(
+        "query".to_string(),
+        query_object(parse_query(url.query().unwrap_or_default())),
+    );
+    properties.insert("fragment".to_string(), optional(url.fragment()));
+
+    Some(RuntimeValue::Object(ObjectValue::new(
+        properties,
+        PrimitiveType::any(),
+    )))
+}
+
+pub fn format(parts: &IndexMap<String, RuntimeValue>) -> String {
+    let text = |key: &str| match parts.get(key) {
+        None | Some(RuntimeValue::Null(_)) => None,
+        Some(value) => Some(value.to_string()).filter(|v| !v.is_empty()),
+    };
+
+    let mut result = String::new();
+    if let Some(host) = text("host") {
+        result.push_str(&text("scheme").unwrap_or_else(|| "http".to_string()));
+        result.push_str("://");
+        if let Some(username) = text("username") {
+            result.push_str(&username);
+            if let Some(password) = text("password") {
+                result.push(':');
+                result.push_str(&password);
+            }
+            result.push('@');
+        }
+        result.push_str(&host);
+        if let Some(port) = text("port") {
+            result.push(':');
+            result.push_str(&port);
+        }
+    }
+
+    let path = text("path").unwrap_or_default();
+    if !path.is_empty() && !path.starts_with('/') && !result.is_empty() {
+        result.push('/');
+    }
+    result.push_str(&path);
+
+    let query = match parts.get("query") {
+        Some(query @ (RuntimeValue::Object(_) | RuntimeValue::Map(_))) => {
+            Some(stringify_query(&query_pairs(query))).filter(|q| !q.is_empty())
+        }
+        _ => text("search").map(|s| s.trim_start_matches('?').to_string()),
+    };
+    if let Some(query) = query {
+        result.push('?');
+        result.push_str(&query);
+    }
+
+    if let Some(fragment) = text("fragment") {
+        result.push('#');
+        result.push_str(fragment.trim_start_matches('#'));
+    }
+
+    result
+}
+
+fn arg_str(args: &[RuntimeValue]) -> String {
+    args.first()
+        .and_then(|arg| String::from_raccoon(arg).ok())
+        .unwrap_or_default()
+}
+
+pub fn register_url_wrappers(env: &mut Environment) {
+    fn str_to_str() -> Type {
+        fn_type!(PrimitiveType::str(), PrimitiveType::str())
+    }
+    let natives: [(&str, NativeThunk); 6] = [
+        ("_native_url_parse", || {
+            NativeFunctionValue::new(
+                |args| parse(&arg_str(&args)).unwrap_or(RuntimeValue::Null(NullValue::new())),
+                fn_type!(PrimitiveType::str(), PrimitiveType::any()),
+            )
+        }),
+        ("_native_url_format", || {
+            NativeFunctionValue::new(
+                |args| match args.first() {
+                    Some(RuntimeValue::Object(parts)) => format(&parts.snapshot()).to_raccoon(),
+                    _ => String::new().to_raccoon(),
+                },
+                fn_type!(PrimitiveType::any(), PrimitiveType::str()),
+            )
+        }),
+        ("_native_url_encode", || {
+            NativeFunctionValue::new(
+                |args| encode_component(&arg_str(&args)).to_raccoon(),
+                str_to_str(),
+            )
+        }),
+        ("_native_url_decode", || {
+            NativeFunctionValue::new(
+                |args| decode_component(&arg_str(&args)).to_raccoon(),
+                str_to_str(),
+            )
+        }),
+        ("_native_url_parse_query", || {
+            NativeFunctionValue::new(
+                |args| query_object(parse_query(&arg_str(&args))),
+                fn_type!(PrimitiveType::str(), PrimitiveType::any()),
+            )
+        }),
+        ("_native_url_stringify_query", || {
+            NativeFunctionValue::new(
+                |args| match args.first() {
+                    Some(query) => stringify_query(&query_pairs(query)).to_raccoon(),
+                    None => String::new().to_raccoon(),
+                },
+                fn_type!(PrimitiveType::any(), PrimitiveType::str()),
+            )
+        }),
+    ];
+
+    for (name, native) in natives {
+        env.declare_lazy(name, move || RuntimeValue::NativeFunction(native()));
+    }
+}
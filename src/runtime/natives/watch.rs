@@ -0,0 +1,225 @@
+use crate::ast::types::PrimitiveType;
+use crate::runtime::{
+    FromRaccoon, NativeAsyncFunctionValue, NullValue, ObjectValue, Registrar, RuntimeValue,
+    ToRaccoon,
+};
+use notify::event::{ModifyKind, RenameMode};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+pub const DEFAULT_DEBOUNCE_MS: u64 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeKind {
+    Create,
+    Modify,
+    Delete,
+}
+
+impl ChangeKind {
+    fn name(self) -> &'static str {
+        match self {
+            ChangeKind::Create => "create",
+            ChangeKind::Modify => "modify",
+            ChangeKind::Delete => "delete",
+        }
+    }
+
+    fn then(self, next: ChangeKind) -> Option<ChangeKind> {
+        match (self, next) {
+            (ChangeKind::Create, ChangeKind::Delete) => None,
+            (ChangeKind::Create, _) => Some(ChangeKind::Create),
+            (ChangeKind::Delete, ChangeKind::Create) => Some(ChangeKind::Modify),
+            (_, next) => Some(next),
+        }
+    }
+}
+
+fn changes(event: notify::Event) -> Vec<(ChangeKind, PathBuf)> {
+    let kinds: Vec<ChangeKind> = match event.kind {
+        EventKind::Create(_) => vec![ChangeKind::Create],
+        EventKind::Remove(_) => vec![ChangeKind::Delete],
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => vec![ChangeKind::Delete],
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => vec![ChangeKind::Create],
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            vec![ChangeKind::Delete, ChangeKind::Create]
+        }
+        EventKind::Modify(_) => vec![ChangeKind::Modify],
+        EventKind::Access(_) | EventKind::Any | EventKind::Other => return Vec::new(),
+    };
+
+    match kinds.as_slice() {
+        [kind] => event.paths.into_iter().map(|path| (*kind, path)).collect(),
+        _ => kinds.into_iter().zip(event.paths).collect(),
+    }
+}
+
+/// The changes to watched paths, a burst at a time.
+pub struct FileWatcher {
+    events: UnboundedReceiver<notify::Event>,
+    pending: VecDeque<(ChangeKind, PathBuf)>,
+    debounce: Duration,
+}
+
+impl FileWatcher {
+    /// Starts watching `paths`, and what is under them when `recursive`.
+    pub fn watch(
+        paths: &[PathBuf],
+        recursive: bool,
+        debounce: Duration,
+    ) -> notify::Result<(RecommendedWatcher, Self)> {
+        let (sender, events) = mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                if let Ok(event) = event {
+                    let _ = sender.send(event);
+                }
+            })?;
+        let mode = if recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        for path in paths {
+            watcher.watch(path, mode)?;
+        }
+        Ok((
+            watcher,
+            Self {
+                events,
+                pending: VecDeque::new(),
+                debounce,
+            },
+        ))
+    }
+
+    async fn next(&mut self) -> Option<(ChangeKind, PathBuf)> {
+        while self.pending.is_empty() {
+            self.pending = self.burst().await?;
+        }
+        self.pending.pop_front()
+    }
+
+    /// The paths the next burst of changes touched; `None` once closed.
+    pub async fn changed_paths(&mut self) -> Option<Vec<PathBuf>> {
+        let burst = match std::mem::take(&mut self.pending) {
+            pending if pending.is_empty() => self.burst().await?,
+            pending => pending,
+        };
+        Some(burst.into_iter().map(|(_, path)| path).collect())
+    }
+
+    async fn burst(&mut self) -> Option<VecDeque<(ChangeKind, PathBuf)>> {
+        let first = self.events.recv().await?;
+        let mut burst = changes(first);
+        while let Ok(Some(event)) = tokio::time::timeout(self.debounce, self.events.recv()).await {
+            burst.extend(changes(event));
+        }
+        Some(coalesce(burst))
+    }
+}
+
+fn coalesce(burst: Vec<(ChangeKind, PathBuf)>) -> VecDeque<(ChangeKind, PathBuf)> {
+    let mut order: Vec<PathBuf> = Vec::new();
+    let mut latest: HashMap<PathBuf, Option<ChangeKind>> = HashMap::new();
+    for (kind, path) in burst {
+        let merged = match latest.get(&path) {
+            Some(Some(current)) => current.then(kind),
+            Some(None) => Some(kind),
+            None => {
+                order.push(path.clone());
+                Some(kind)
+            }
+        };
+        latest.insert(path, merged);
+    }
+    order
+        .into_iter()
+        .filter_map(|path| Some((latest[&path]?, path)))
+        .collect()
+}
+
+fn option(args: &[RuntimeValue], name: &str) -> Option<RuntimeValue> {
+    match args.get(1) {
+        Some(RuntimeValue::Object(options)) => options.get(name),
+        _ => None,
+    }
+}
+
+fn change_event(kind: ChangeKind, path: PathBuf) -> RuntimeValue {
+    let mut properties = HashMap::new();
+    properties.insert("kind".to_string(), kind.name().to_string().to_raccoon());
+    properties.insert(
+        "path".to_string(),
+        path.to_string_lossy().to_string().to_raccoon(),
+    );
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+pub fn core_fs_watch(args: Vec<RuntimeValue>) -> RuntimeValue {
+    let path = args
+        .first()
+        .and_then(|arg| String::from_raccoon(arg).ok())
+        .unwrap_or_default();
+    let recursive = match option(&args, "recursive") {
+        Some(RuntimeValue::Bool(flag)) => flag.value,
+        _ => true,
+    };
+    let debounce = option(&args, "debounce")
+        .and_then(|ms| i64::from_raccoon(&ms).ok())
+        .map_or(DEFAULT_DEBOUNCE_MS, |ms| ms.max(0) as u64);
+
+    let (watcher, state) = match FileWatcher::watch(
+        &[PathBuf::from(&path)],
+        recursive,
+        Duration::from_millis(debounce),
+    ) {
+        Ok(watching) => watching,
+        Err(_) => return RuntimeValue::Null(NullValue::new()),
+    };
+
+    // Held apart from the event state so `close` does not wait behind a pending `next`.
+    // Dropping the watcher closes the channel, which wakes `next` with `null`.
+    let watcher = Arc::new(Mutex::new(Some(watcher)));
+    let state = Arc::new(tokio::sync::Mutex::new(state));
+
+    let next = NativeAsyncFunctionValue::new(
+        Arc::new(move |_args| {
+            let state = state.clone();
+            Box::pin(async move {
+                match state.lock().await.next().await {
+                    Some((kind, path)) => change_event(kind, path),
+                    None => RuntimeValue::Null(NullValue::new()),
+                }
+            })
+        }),
+        PrimitiveType::any(),
+    );
+    let close = NativeAsyncFunctionValue::new(
+        Arc::new(move |_args| {
+            watcher.lock().unwrap().take();
+            Box::pin(async { RuntimeValue::Null(NullValue::new()) })
+        }),
+        PrimitiveType::any(),
+    );
+
+    let mut properties = HashMap::new();
+    properties.insert("path".to_string(), path.to_raccoon());
+    properties.insert(
+        "next".to_string(),
+        RuntimeValue::NativeAsyncFunction(Arc::new(next)),
+    );
+    properties.insert(
+        "close".to_string(),
+        RuntimeValue::NativeAsyncFunction(Arc::new(close)),
+    );
+    RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+}
+
+pub fn register_watch_primitives(registrar: &mut Registrar) {
+    registrar.register_restricted_fn("core_fs_watch", "io", core_fs_watch, 1, Some(2));
+}
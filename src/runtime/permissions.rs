@@ -0,0 +1,185 @@
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    Net,
+    Read,
+    Write,
+    Env,
+}
+
+impl Permission {
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Permission::Net => "--allow-net",
+            Permission::Read => "--allow-read",
+            Permission::Write => "--allow-write",
+            Permission::Env => "--allow-env",
+        }
+    }
+
+    pub fn for_native(name: &str) -> &'static [Permission] {
+        match name.strip_prefix("core_").unwrap_or(name) {
+            "file_read" | "file_exists" | "dir_list" | "dir_glob" | "fs_watch" | "fs_glob"
+            | "env_load" => &[Permission::Read],
+            "file_write" | "file_append" | "file_delete" | "dir_create" => &[Permission::Write],
+            "store_open" => &[Permission::Read, Permission::Write],
+            "http_get" | "http_post" | "http_request" | "http_fetch" | "http_download" => {
+                &[Permission::Net]
+            }
+            "net_lookup" | "net_reverse" => &[Permission::Net],
+            "env_get" | "env_set" | "env_expand" => &[Permission::Env],
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Permission::Net => "net",
+            Permission::Read => "read",
+            Permission::Write => "write",
+            Permission::Env => "env",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum PathGrant {
+    #[default]
+    Denied,
+    All,
+    Paths(Vec<PathBuf>),
+}
+
+impl PathGrant {
+    fn grant(&mut self, path: Option<&str>) {
+        match (path, &mut *self) {
+            (None, _) => *self = PathGrant::All,
+            (Some(_), PathGrant::All) => {}
+            (Some(path), PathGrant::Paths(paths)) => paths.push(resolve(Path::new(path))),
+            (Some(path), PathGrant::Denied) => {
+                *self = PathGrant::Paths(vec![resolve(Path::new(path))])
+            }
+        }
+    }
+
+    fn allows(&self, target: &str) -> bool {
+        match self {
+            PathGrant::Denied => false,
+            PathGrant::All => true,
+            PathGrant::Paths(paths) => {
+                let target = resolve(Path::new(target));
+                paths.iter().any(|allowed| target.starts_with(allowed))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Permissions {
+    pub net: bool,
+    pub env: bool,
+    pub read: PathGrant,
+    pub write: PathGrant,
+}
+
+impl Permissions {
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn all() -> Self {
+        Self {
+            net: true,
+            env: true,
+            read: PathGrant::All,
+            write: PathGrant::All,
+        }
+    }
+
+    pub fn allow_net(mut self) -> Self {
+        self.net = true;
+        self
+    }
+
+    pub fn allow_env(mut self) -> Self {
+        self.env = true;
+        self
+    }
+
+    pub fn allow_read(mut self, path: Option<&str>) -> Self {
+        self.read.grant(path);
+        self
+    }
+
+    pub fn allow_write(mut self, path: Option<&str>) -> Self {
+        self.write.grant(path);
+        self
+    }
+
+    pub fn check(&self, permission: Permission, target: Option<&str>) -> Result<(), String> {
+        let granted = match permission {
+            Permission::Net => self.net,
+            Permission::Env => self.env,
+            Permission::Read => target.is_some_and(|t| self.read.allows(t)),
+            Permission::Write => target.is_some_and(|t| self.write.allows(t)),
+        };
+
+        if granted {
+            return Ok(());
+        }
+
+        let subject = match (permission, target) {
+            (Permission::Read | Permission::Write, Some(target)) => {
+                format!("{} access to '{}'", permission, target)
+            }
+            (Permission::Net, Some(target)) => format!("net access to '{}'", target),
+            _ => format!("{} access", permission),
+        };
+
+        Err(format!(
+            "Requires {}, run again with the {} flag",
+            subject,
+            permission.flag()
+        ))
+    }
+}
+
+/// The path `path` names once symlinks are followed, so a link cannot lead out of a grant.
+///
+/// Paths that do not exist yet are resolved from their nearest existing ancestor.
+fn resolve(path: &Path) -> PathBuf {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(path)
+    };
+
+    for ancestor in path.ancestors() {
+        if let Ok(canonical) = ancestor.canonicalize() {
+            let rest = path.strip_prefix(ancestor).unwrap_or(Path::new(""));
+            return normalize(&canonical.join(rest));
+        }
+    }
+    normalize(&path)
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .fold(PathBuf::new(), |mut normalized, component| {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                other => normalized.push(other),
+            }
+            normalized
+        })
+}
@@ -147,7 +147,7 @@ impl PluginManager {
     pub fn get_async_function(&self, name: &str) -> Option<RuntimeValue> {
         let reg = self.registry.read().unwrap();
         reg.get_async(name)
-            .map(|f| RuntimeValue::NativeAsyncFunction(f))
+            .map(|f| RuntimeValue::NativeAsyncFunction(Arc::new(f)))
     }
 
     pub fn list_functions(&self) -> Vec<String> {
@@ -163,7 +163,7 @@ impl PluginManager {
         }
 
         for (name, func) in &reg.async_functions {
-            let value = RuntimeValue::NativeAsyncFunction(func.clone());
+            let value = RuntimeValue::NativeAsyncFunction(Arc::new(func.clone()));
             let _ = interp.declare_in_env(name.clone(), value);
         }
     }
@@ -11,6 +11,8 @@ pub struct FunctionSignature {
     pub handler: SyncHandler,
     pub min_args: usize,
     pub max_args: Option<usize>,
+    /// The module a sandbox must allow for this native to run, such as `io`.
+    pub module: Option<&'static str>,
 }
 
 pub struct Registrar {
@@ -50,10 +52,28 @@ impl Registrar {
                 handler: Arc::new(handler),
                 min_args,
                 max_args,
+                module: None,
             },
         );
     }
 
+    /// Registers a native the sandbox counts as access to `module`.
+    pub fn register_restricted_fn<F>(
+        &mut self,
+        name: &str,
+        module: &'static str,
+        handler: F,
+        min_args: usize,
+        max_args: Option<usize>,
+    ) where
+        F: Fn(Vec<RuntimeValue>) -> RuntimeValue + 'static + Send + Sync,
+    {
+        self.register_fn(name, None, handler, min_args, max_args);
+        if let Some(signature) = self.functions.get_mut(name) {
+            signature.module = Some(module);
+        }
+    }
+
     pub fn register_const(&mut self, name: impl Into<String>, value: RuntimeValue) {
         self.constants.insert(name.into(), value);
     }
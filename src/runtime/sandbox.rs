@@ -0,0 +1,221 @@
+use crate::error::RaccoonError;
+use crate::runtime::permissions::{Permission, Permissions};
+use crate::runtime::RuntimeValue;
+use crate::tokens::Position;
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+pub const RESTRICTED_MODULES: [&str; 4] = ["io", "http", "net", "process"];
+
+const CLOCK_CHECK_INTERVAL: u64 = 1024;
+
+#[derive(Debug, Clone, Default)]
+pub struct SandboxConfig {
+    pub timeout: Option<Duration>,
+    pub max_steps: Option<u64>,
+    pub denied_modules: HashSet<String>,
+    pub permissions: Option<Permissions>,
+}
+
+impl SandboxConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn strict() -> Self {
+        RESTRICTED_MODULES
+            .iter()
+            .fold(Self::new(), |config, module| config.deny_module(module))
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_max_steps(mut self, max_steps: u64) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    pub fn deny_module(mut self, module: &str) -> Self {
+        self.denied_modules.insert(module.to_string());
+        self
+    }
+
+    pub fn with_permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct Sandbox {
+    config: SandboxConfig,
+    started: Instant,
+    steps: AtomicU64,
+}
+
+impl Sandbox {
+    pub fn new(config: SandboxConfig) -> Self {
+        Self {
+            config,
+            started: Instant::now(),
+            steps: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &SandboxConfig {
+        &self.config
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps.load(Ordering::Relaxed)
+    }
+
+    pub fn tick(&self, position: Position, file: &Option<String>) -> Result<(), RaccoonError> {
+        let steps = self.steps.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if let Some(max_steps) = self.config.max_steps {
+            if steps > max_steps {
+                return Err(RaccoonError::timeout_error(
+                    format!("Execution step limit of {} exceeded", max_steps),
+                    position,
+                    file.clone(),
+                ));
+            }
+        }
+
+        if let Some(timeout) = self.config.timeout {
+            if steps.is_multiple_of(CLOCK_CHECK_INTERVAL) && self.started.elapsed() > timeout {
+                return Err(Self::timed_out(timeout, position, file));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Waits for `future`, failing once the time limit runs out.
+    pub async fn wait<F: Future>(
+        &self,
+        future: F,
+        position: Position,
+        file: &Option<String>,
+    ) -> Result<F::Output, RaccoonError> {
+        let Some(timeout) = self.config.timeout else {
+            return Ok(future.await);
+        };
+        let remaining = timeout.saturating_sub(self.started.elapsed());
+        tokio::time::timeout(remaining, future)
+            .await
+            .map_err(|_| Self::timed_out(timeout, position, file))
+    }
+
+    fn timed_out(timeout: Duration, position: Position, file: &Option<String>) -> RaccoonError {
+        RaccoonError::timeout_error(
+            format!("Execution time limit of {}ms exceeded", timeout.as_millis()),
+            position,
+            file.clone(),
+        )
+    }
+
+    pub fn check_module(
+        &self,
+        module_spec: &str,
+        position: Position,
+        file: &Option<String>,
+    ) -> Result<(), RaccoonError> {
+        let module = module_spec.strip_prefix("std:").unwrap_or(module_spec);
+        self.check_denied(module, module_spec, position, file)
+    }
+
+    pub fn check_native(
+        &self,
+        name: &str,
+        module: Option<&str>,
+        position: Position,
+        file: &Option<String>,
+    ) -> Result<(), RaccoonError> {
+        match module {
+            Some(module) => self.check_denied(module, name, position, file),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether natives tagged with `module` may run.
+    pub fn allows(&self, module: &str) -> bool {
+        !self.config.denied_modules.contains(module)
+    }
+
+    pub fn check_call(
+        &self,
+        name: &str,
+        module: Option<&str>,
+        args: &[RuntimeValue],
+        position: Position,
+        file: &Option<String>,
+    ) -> Result<(), RaccoonError> {
+        self.check_native(name, module, position, file)?;
+
+        let Some(permissions) = &self.config.permissions else {
+            return Ok(());
+        };
+
+        let target_index = if name.ends_with("http_request") { 1 } else { 0 };
+        let target = args.get(target_index).map(|arg| arg.to_string());
+        let mut result = Permission::for_native(name)
+            .iter()
+            .try_for_each(|permission| permissions.check(*permission, target.as_deref()));
+
+        if name.ends_with("http_download") {
+            let destination = args.get(1).map(|arg| arg.to_string());
+            result =
+                result.and_then(|_| permissions.check(Permission::Write, destination.as_deref()));
+        }
+        if name.ends_with("env_load") {
+            result = result.and_then(|_| permissions.check(Permission::Env, None));
+        }
+        // A CA bundle in the options of an http native is read from disk.
+        if name.strip_prefix("core_").unwrap_or(name).starts_with("http_") {
+            for ca_file in args.iter().filter_map(ca_file) {
+                result = result.and_then(|_| permissions.check(Permission::Read, Some(&ca_file)));
+            }
+        }
+
+        result.map_err(|message| {
+            RaccoonError::permission_denied_error(message, position, file.clone())
+        })
+    }
+
+    fn check_denied(
+        &self,
+        module: &str,
+        requested: &str,
+        position: Position,
+        file: &Option<String>,
+    ) -> Result<(), RaccoonError> {
+        if !self.allows(module) {
+            return Err(RaccoonError::permission_denied_error(
+                format!(
+                    "Access to '{}' is denied by the sandbox ('{}' module is disabled)",
+                    requested, module
+                ),
+                position,
+                file.clone(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn ca_file(options: &RuntimeValue) -> Option<String> {
+    match options {
+        RuntimeValue::Object(options) => match options.get("caFile")? {
+            RuntimeValue::Null(_) => None,
+            ca_file => Some(ca_file.to_string()),
+        },
+        _ => None,
+    }
+}
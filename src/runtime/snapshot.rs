@@ -0,0 +1,230 @@
+use crate::ast::nodes::Program;
+use crate::error::RaccoonError;
+use crate::interpreter::Interpreter;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::runtime::natives::serialize;
+use crate::runtime::Environment;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Environment variable naming the snapshot runs start from.
+pub const SNAPSHOT_ENV: &str = "RACCOON_SNAPSHOT";
+
+/// A fresh runtime's globals and parsed stdlib, saved so later runs skip building them.
+pub struct Snapshot {
+    header: Header,
+    programs: Programs,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    version: String,
+    build: u64,
+    globals: usize,
+    values: Vec<(String, Vec<u8>)>,
+    modules: HashMap<String, SavedModule>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedModule {
+    source_hash: u64,
+    start: usize,
+    end: usize,
+}
+
+enum Programs {
+    Captured(String),
+    File { path: PathBuf, offset: u64 },
+}
+
+impl Snapshot {
+    /// Captures the globals of a fresh runtime and parses the stdlib.
+    pub fn capture() -> Result<Self, RaccoonError> {
+        let interpreter = Interpreter::new(None);
+        let env = &interpreter.environment;
+        let names: Vec<&str> = env.names().collect();
+        let mut values: Vec<(String, Vec<u8>)> = names
+            .iter()
+            .filter_map(|name| {
+                let value = env.get(name, (0, 0)).ok()?;
+                Some((name.to_string(), serialize::serialize(&value).ok()?))
+            })
+            .collect();
+        values.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut modules = HashMap::new();
+        let mut programs = String::new();
+        for name in interpreter.stdlib_loader.available_modules() {
+            let Some((source, file)) = interpreter.stdlib_loader.module_source(&name) else {
+                continue;
+            };
+            let source_hash = hash(&source);
+            let tokens = Lexer::new(source, Some(file.clone())).tokenize()?;
+            let program = Parser::new(tokens, Some(file.clone())).parse()?;
+            let json = serde_json::to_string(&program).map_err(|error| {
+                RaccoonError::new(error.to_string(), program.position, Some(file))
+            })?;
+            let start = programs.len();
+            programs.push_str(&json);
+            let end = programs.len();
+            modules.insert(
+                name,
+                SavedModule {
+                    source_hash,
+                    start,
+                    end,
+                },
+            );
+        }
+
+        Ok(Self {
+            header: Header {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                build: build(),
+                globals: names.len(),
+                values,
+                modules,
+            },
+            programs: Programs::Captured(programs),
+        })
+    }
+
+    /// The snapshot as written to its file.
+    pub fn encode(&self) -> String {
+        let header = serde_json::to_string(&self.header).expect("snapshot header is JSON");
+        let programs = match &self.programs {
+            Programs::Captured(programs) => programs.as_str(),
+            Programs::File { .. } => "",
+        };
+        format!("{}\n{}", header, programs)
+    }
+
+    /// The snapshot `$RACCOON_SNAPSHOT` names, loaded once per path.
+    ///
+    /// A snapshot that cannot be read or was written by another build is reported and ignored.
+    pub fn current() -> Option<Arc<Snapshot>> {
+        static CURRENT: Mutex<Option<(OsString, Option<Arc<Snapshot>>)>> = Mutex::new(None);
+        let path = env::var_os(SNAPSHOT_ENV)?;
+        let mut current = CURRENT.lock().unwrap();
+        if let Some((loaded, snapshot)) = current.as_ref() {
+            if *loaded == path {
+                return snapshot.clone();
+            }
+        }
+        let snapshot = match Self::load(Path::new(&path)) {
+            Ok(snapshot) => Some(Arc::new(snapshot)),
+            Err(reason) => {
+                ignored(Path::new(&path), &reason);
+                None
+            }
+        };
+        *current = Some((path, snapshot.clone()));
+        snapshot
+    }
+
+    /// Reads the header of the snapshot at `path`; programs are read as modules need them.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let mut reader = BufReader::new(File::open(path).map_err(|error| error.to_string())?);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|error| error.to_string())?;
+        let header: Header =
+            serde_json::from_str(&line).map_err(|_| "it is not a raccoon snapshot".to_string())?;
+        if header.version != env!("CARGO_PKG_VERSION") {
+            return Err(format!(
+                "it was written by raccoon {}, not {}",
+                header.version,
+                env!("CARGO_PKG_VERSION")
+            ));
+        }
+        if header.build != build() {
+            return Err("it was written by another build of raccoon".to_string());
+        }
+        Ok(Self {
+            header,
+            programs: Programs::File {
+                path: path.to_path_buf(),
+                offset: line.len() as u64,
+            },
+        })
+    }
+
+    /// Declares the plain values the snapshot holds in `env`, before anything registers them.
+    pub fn restore(&self, env: &mut Environment) {
+        for (name, bytes) in &self.header.values {
+            let bytes = bytes.clone();
+            env.declare_lazy(name.clone(), move || {
+                serialize::deserialize(&bytes).expect("snapshot values deserialize")
+            });
+        }
+    }
+
+    /// The parsed program of the stdlib module `name`, when the snapshot file has it for `source`.
+    pub fn program(&self, name: &str, source: &str) -> Option<Program> {
+        let Programs::File { path, offset } = &self.programs else {
+            return None;
+        };
+        let module = self.header.modules.get(name)?;
+        if module.source_hash != hash(source) {
+            ignored(path, &format!("{} changed since it was written", name));
+            return None;
+        }
+        let program = read_range(path, offset + module.start as u64, module.end - module.start)
+            .and_then(|json| serde_json::from_slice(&json).ok());
+        if program.is_none() {
+            ignored(path, &format!("its program for {} cannot be read", name));
+        }
+        program
+    }
+
+    pub fn global_count(&self) -> usize {
+        self.header.globals
+    }
+
+    pub fn module_count(&self) -> usize {
+        self.header.modules.len()
+    }
+}
+
+fn hash(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Identifies the running binary, so a snapshot is only used by the build that wrote it.
+fn build() -> u64 {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    if let Some(metadata) = env::current_exe().and_then(fs::metadata).ok() {
+        metadata.len().hash(&mut hasher);
+        metadata.modified().ok().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn read_range(path: &Path, start: u64, len: usize) -> Option<Vec<u8>> {
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut bytes = vec![0; len];
+    file.read_exact(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+fn ignored(path: &Path, reason: &str) {
+    eprintln!(
+        "Warning: Ignoring snapshot '{}': {}; run `raccoon snapshot` again",
+        path.display(),
+        reason
+    );
+}
@@ -0,0 +1,25 @@
+static MODULES: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/stdlib_modules.rs"));
+
+const FILE_PREFIX: &str = "<stdlib>/";
+
+pub fn names() -> impl Iterator<Item = &'static str> {
+    MODULES.iter().map(|(name, _)| *name)
+}
+
+pub fn source(name: &str) -> Option<&'static str> {
+    MODULES
+        .iter()
+        .find(|(module, _)| *module == name)
+        .map(|(_, source)| *source)
+}
+
+/// File name reported for an embedded module, e.g. `<stdlib>/math.rcc`.
+pub fn file_name(name: &str) -> String {
+    format!("{}{}.rcc", FILE_PREFIX, name)
+}
+
+/// Source behind a file name produced by [`file_name`], used to show code context in errors.
+pub fn source_for_file(file: &str) -> Option<&'static str> {
+    let name = file.strip_prefix(FILE_PREFIX)?.strip_suffix(".rcc")?;
+    source(name)
+}
@@ -5,26 +5,55 @@ use crate::interpreter::Interpreter;
 use crate::lexer::Lexer;
 use crate::parser::Parser;
 use crate::runtime::values::{NullValue, ObjectValue, RuntimeValue};
+use crate::runtime::Snapshot;
+use crate::tokens::Position;
 use std::collections::HashMap;
+use std::env;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 
+use super::embedded;
+
+/// Directory whose `.rcc` files take precedence over the embedded stdlib.
+pub const STDLIB_ENV: &str = "RACCOON_STDLIB";
+
 pub struct StdLibLoader {
-    stdlib_path: PathBuf,
+    stdlib_path: Option<PathBuf>,
     module_cache: Arc<RwLock<HashMap<String, RuntimeValue>>>,
+    snapshot: Option<Arc<Snapshot>>,
+    parsed: Arc<AtomicUsize>,
 }
 
 impl StdLibLoader {
     pub fn new(stdlib_path: PathBuf) -> Self {
+        Self::with_override(Some(stdlib_path))
+    }
+
+    /// Uses the embedded stdlib, overridden by `$RACCOON_STDLIB` when it is set.
+    pub fn with_default_path() -> Self {
+        Self::with_override(env::var_os(STDLIB_ENV).map(PathBuf::from))
+    }
+
+    fn with_override(stdlib_path: Option<PathBuf>) -> Self {
         Self {
             stdlib_path,
             module_cache: Arc::new(RwLock::new(HashMap::new())),
+            snapshot: None,
+            parsed: Arc::new(AtomicUsize::new(0)),
         }
     }
 
-    pub fn with_default_path() -> Self {
-        Self::new(PathBuf::from("./stdlib"))
+    /// Takes modules already parsed from `snapshot` rather than parsing their source.
+    pub fn with_snapshot(mut self, snapshot: Option<Arc<Snapshot>>) -> Self {
+        self.snapshot = snapshot;
+        self
+    }
+
+    /// How many modules this loader and the modules it loaded parsed from source.
+    pub fn parsed_modules(&self) -> usize {
+        self.parsed.load(Ordering::Relaxed)
     }
 
     pub fn module_exists(&self, module_name: &str) -> bool {
@@ -35,15 +64,29 @@ impl StdLibLoader {
         if module_name == "std:core" {
             return false;
         }
-        if !module_name.starts_with("std:") {
+        let Some(basename) = module_name.strip_prefix("std:") else {
             return false;
-        }
-        self.get_module_path(module_name).exists()
+        };
+        self.override_path(basename)
+            .is_some_and(|path| path.exists())
+            || embedded::source(basename).is_some()
     }
 
-    fn get_module_path(&self, module_name: &str) -> PathBuf {
+    /// Source and reported file name of a module, preferring the override directory.
+    pub fn module_source(&self, module_name: &str) -> Option<(String, String)> {
         let basename = module_name.strip_prefix("std:").unwrap_or(module_name);
-        self.stdlib_path.join(format!("{}.rcc", basename))
+        if let Some(path) = self.override_path(basename) {
+            if let Ok(source) = fs::read_to_string(&path) {
+                return Some((source, path.display().to_string()));
+            }
+        }
+        let source = embedded::source(basename)?;
+        Some((source.to_string(), embedded::file_name(basename)))
+    }
+
+    fn override_path(&self, basename: &str) -> Option<PathBuf> {
+        let dir = self.stdlib_path.as_ref()?;
+        Some(dir.join(format!("{}.rcc", basename)))
     }
 
     fn load_core_module(&self) -> Result<RuntimeValue, RaccoonError> {
@@ -66,8 +109,10 @@ impl StdLibLoader {
                         Box::pin(async { result })
                     }),
                     func_type.clone(),
-                );
-                let function_value = RuntimeValue::NativeAsyncFunction(native_async_fn);
+                )
+                .with_name(full_name.clone())
+                .with_module(sig.module);
+                let function_value = RuntimeValue::NativeAsyncFunction(Arc::new(native_async_fn));
 
                 exports.insert(export_name.to_string(), function_value.clone());
                 exports.insert(full_name.to_string(), function_value);
@@ -100,16 +145,42 @@ impl StdLibLoader {
             }
         }
 
-        let module_path = self.get_module_path(module_name);
-        let source = fs::read_to_string(&module_path).map_err(|e| {
-            RaccoonError::new(
-                format!("Failed to read module {}: {}", module_name, e),
+        let Some((source, file)) = self.module_source(module_name) else {
+            return Err(RaccoonError::new(
+                format!("Module {} not found in the standard library", module_name),
                 (0, 0),
-                Some(module_path.display().to_string()),
-            )
-        })?;
+                Option::<String>::None,
+            ));
+        };
+
+        let file_path = Some(file);
+        let snapshot = self.snapshot.as_ref();
+        let program = match snapshot.and_then(|snapshot| snapshot.program(module_name, &source)) {
+            Some(program) => program,
+            None => self.parse_module(module_name, source, &file_path)?,
+        };
+
+        let exports = self
+            .execute_module_and_extract_exports(&program, file_path.clone())
+            .await?;
+
+        let module_obj = RuntimeValue::Object(ObjectValue::new(exports, PrimitiveType::any()));
 
-        let file_path = Some(module_path.display().to_string());
+        {
+            let mut cache = self.module_cache.write().unwrap();
+            cache.insert(module_name.to_string(), module_obj.clone());
+        }
+
+        Ok(module_obj)
+    }
+
+    fn parse_module(
+        &self,
+        module_name: &str,
+        source: String,
+        file_path: &Option<String>,
+    ) -> Result<Program, RaccoonError> {
+        self.parsed.fetch_add(1, Ordering::Relaxed);
         let mut lexer = Lexer::new(source, file_path.clone());
         let tokens = lexer.tokenize().map_err(|e| {
             RaccoonError::new(
@@ -120,47 +191,36 @@ impl StdLibLoader {
         })?;
 
         let mut parser = Parser::new(tokens, file_path.clone());
-        let program = parser.parse().map_err(|e| {
+        parser.parse().map_err(|e| {
             RaccoonError::new(
                 format!("Parser error in {}: {:?}", module_name, e),
                 (0, 0),
                 file_path.clone(),
             )
-        })?;
-
-        let exports = self
-            .execute_module_and_extract_exports(&program, file_path.clone())
-            .await?;
-
-        let module_obj = RuntimeValue::Object(ObjectValue::new(exports, PrimitiveType::any()));
-
-        {
-            let mut cache = self.module_cache.write().unwrap();
-            cache.insert(module_name.to_string(), module_obj.clone());
-        }
-
-        Ok(module_obj)
+        })
     }
 
     pub async fn get_module_export(
         &self,
         module_name: &str,
         export_name: &str,
+        position: Position,
+        file: &Option<String>,
     ) -> Result<RuntimeValue, RaccoonError> {
         let module = self.load_module(module_name).await?;
         if let RuntimeValue::Object(obj) = module {
-            obj.properties.get(export_name).cloned().ok_or_else(|| {
+            obj.get(export_name).ok_or_else(|| {
                 RaccoonError::new(
                     format!("{} does not export '{}'", module_name, export_name),
-                    (0, 0),
-                    Option::<String>::None,
+                    position,
+                    file.clone(),
                 )
             })
         } else {
             Err(RaccoonError::new(
                 format!("Module {} is not an object", module_name),
-                (0, 0),
-                Option::<String>::None,
+                position,
+                file.clone(),
             ))
         }
     }
@@ -175,6 +235,8 @@ impl StdLibLoader {
         interp.stdlib_loader = std::sync::Arc::new(Self {
             stdlib_path: self.stdlib_path.clone(),
             module_cache: self.module_cache.clone(),
+            snapshot: self.snapshot.clone(),
+            parsed: self.parsed.clone(),
         });
         self.setup_native_functions_in_interpreter(&mut interp, &file_path);
 
@@ -317,18 +379,24 @@ impl StdLibLoader {
         super::register_stdlib_wrappers(&mut interp.environment, interp.registrar.clone());
     }
 
+    /// Names (`std:<name>`) of the modules written in Raccoon, including `std:core`.
     pub fn available_modules(&self) -> Vec<String> {
-        let mut modules = Vec::new();
-        if let Ok(entries) = fs::read_dir(&self.stdlib_path) {
+        let mut basenames: Vec<String> = embedded::names().map(str::to_string).collect();
+        if let Some(Ok(entries)) = self.stdlib_path.as_ref().map(fs::read_dir) {
             for entry in entries.flatten() {
                 if entry.path().extension().is_some_and(|e| e == "rcc") {
                     if let Some(name) = entry.path().file_stem() {
-                        modules.push(format!("std:{}", name.to_string_lossy()));
+                        basenames.push(name.to_string_lossy().into_owned());
                     }
                 }
             }
         }
-        modules
+        basenames.sort();
+        basenames.dedup();
+        basenames
+            .into_iter()
+            .map(|name| format!("std:{}", name))
+            .collect()
     }
 
     pub fn clear_cache(&self) {
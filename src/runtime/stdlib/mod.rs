@@ -1,6 +1,7 @@
+pub mod embedded;
 pub mod loader;
 pub mod natives;
 pub mod wrappers;
 
-pub use loader::StdLibLoader;
+pub use loader::{StdLibLoader, STDLIB_ENV};
 pub use wrappers::register_stdlib_wrappers;
@@ -8,214 +8,220 @@ pub fn register_stdlib_wrappers(env: &mut Environment, registrar: Arc<Mutex<Regi
     register_math_wrappers(env, registrar.clone());
     register_json_wrappers(env, registrar.clone());
     register_http_wrappers(env, registrar);
+    crate::runtime::natives::term::register_term_wrappers(env);
+    crate::runtime::natives::path::register_path_wrappers(env);
+    crate::runtime::natives::url::register_url_wrappers(env);
+    crate::runtime::natives::cancel::register_cancel_wrappers(env);
+    crate::runtime::natives::os::register_os_wrappers(env);
+    crate::runtime::natives::log::register_log_wrappers(env);
+    crate::runtime::natives::math::register_math_wrappers(env);
+    crate::runtime::natives::random::register_random_wrappers(env);
+    crate::runtime::natives::serialize::register_serialize_wrappers(env);
+    crate::runtime::natives::string::register_string_wrappers(env);
+    crate::runtime::natives::prompt::register_prompt_wrappers(env);
+    crate::runtime::natives::cli::register_cli_wrappers(env);
 }
 
 fn register_math_wrappers(env: &mut Environment, _registrar: Arc<Mutex<Registrar>>) {
-    let sqrt_fn = NativeFunctionValue::new(
-        |args| {
-            if args.is_empty() {
-                return RuntimeValue::Null(crate::runtime::NullValue::new());
-            }
-            match &args[0] {
-                RuntimeValue::Float(f) => {
-                    RuntimeValue::Float(crate::runtime::FloatValue::new(f.value.sqrt()))
+    env.declare_lazy("_native_sqrt", || {
+        RuntimeValue::NativeFunction(NativeFunctionValue::new(
+            |args| {
+                if args.is_empty() {
+                    return RuntimeValue::Null(crate::runtime::NullValue::new());
                 }
-                RuntimeValue::Int(i) => {
-                    RuntimeValue::Float(crate::runtime::FloatValue::new((i.value as f64).sqrt()))
+                match &args[0] {
+                    RuntimeValue::Float(f) => {
+                        RuntimeValue::Float(crate::runtime::FloatValue::new(f.value.sqrt()))
+                    }
+                    RuntimeValue::Int(i) => RuntimeValue::Float(crate::runtime::FloatValue::new(
+                        (i.value as f64).sqrt(),
+                    )),
+                    _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
                 }
-                _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
-            }
-        },
-        Type::Function(Box::new(FunctionType {
-            params: vec![Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            ))],
-            return_type: Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            )),
-            is_variadic: false,
-        })),
-    );
-    let _ = env.declare(
-        "_native_sqrt".to_string(),
-        RuntimeValue::NativeFunction(sqrt_fn),
-    );
-
-    let pow_fn = NativeFunctionValue::new(
-        |args| {
-            if args.len() < 2 {
-                return RuntimeValue::Null(crate::runtime::NullValue::new());
-            }
-            let base = match &args[0] {
-                RuntimeValue::Float(f) => f.value,
-                RuntimeValue::Int(i) => i.value as f64,
-                _ => return RuntimeValue::Null(crate::runtime::NullValue::new()),
-            };
-            let exp = match &args[1] {
-                RuntimeValue::Float(f) => f.value,
-                RuntimeValue::Int(i) => i.value as f64,
-                _ => return RuntimeValue::Null(crate::runtime::NullValue::new()),
-            };
-            RuntimeValue::Float(crate::runtime::FloatValue::new(base.powf(exp)))
-        },
-        Type::Function(Box::new(FunctionType {
-            params: vec![
-                Type::Primitive(PrimitiveType::new(
+            },
+            Type::Function(Box::new(FunctionType {
+                params: vec![Type::Primitive(PrimitiveType::new(
+                    crate::ast::types::TypeKind::Float,
+                    "float",
+                ))],
+                return_type: Type::Primitive(PrimitiveType::new(
                     crate::ast::types::TypeKind::Float,
                     "float",
                 )),
-                Type::Primitive(PrimitiveType::new(
+                is_variadic: false,
+                param_names: Vec::new(),
+            })),
+        ))
+    });
+
+    env.declare_lazy("_native_pow", || {
+        RuntimeValue::NativeFunction(NativeFunctionValue::new(
+            |args| {
+                if args.len() < 2 {
+                    return RuntimeValue::Null(crate::runtime::NullValue::new());
+                }
+                let base = match &args[0] {
+                    RuntimeValue::Float(f) => f.value,
+                    RuntimeValue::Int(i) => i.value as f64,
+                    _ => return RuntimeValue::Null(crate::runtime::NullValue::new()),
+                };
+                let exp = match &args[1] {
+                    RuntimeValue::Float(f) => f.value,
+                    RuntimeValue::Int(i) => i.value as f64,
+                    _ => return RuntimeValue::Null(crate::runtime::NullValue::new()),
+                };
+                RuntimeValue::Float(crate::runtime::FloatValue::new(base.powf(exp)))
+            },
+            Type::Function(Box::new(FunctionType {
+                params: vec![
+                    Type::Primitive(PrimitiveType::new(
+                        crate::ast::types::TypeKind::Float,
+                        "float",
+                    )),
+                    Type::Primitive(PrimitiveType::new(
+                        crate::ast::types::TypeKind::Float,
+                        "float",
+                    )),
+                ],
+                return_type: Type::Primitive(PrimitiveType::new(
                     crate::ast::types::TypeKind::Float,
                     "float",
                 )),
-            ],
-            return_type: Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            )),
-            is_variadic: false,
-        })),
-    );
-    let _ = env.declare(
-        "_native_pow".to_string(),
-        RuntimeValue::NativeFunction(pow_fn),
-    );
+                is_variadic: false,
+                param_names: Vec::new(),
+            })),
+        ))
+    });
 
-    let sin_fn = NativeFunctionValue::new(
-        |args| {
-            if args.is_empty() {
-                return RuntimeValue::Null(crate::runtime::NullValue::new());
-            }
-            match &args[0] {
-                RuntimeValue::Float(f) => {
-                    RuntimeValue::Float(crate::runtime::FloatValue::new(f.value.sin()))
+    env.declare_lazy("_native_sin", || {
+        RuntimeValue::NativeFunction(NativeFunctionValue::new(
+            |args| {
+                if args.is_empty() {
+                    return RuntimeValue::Null(crate::runtime::NullValue::new());
                 }
-                RuntimeValue::Int(i) => {
-                    RuntimeValue::Float(crate::runtime::FloatValue::new((i.value as f64).sin()))
+                match &args[0] {
+                    RuntimeValue::Float(f) => {
+                        RuntimeValue::Float(crate::runtime::FloatValue::new(f.value.sin()))
+                    }
+                    RuntimeValue::Int(i) => {
+                        RuntimeValue::Float(crate::runtime::FloatValue::new((i.value as f64).sin()))
+                    }
+                    _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
                 }
-                _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
-            }
-        },
-        Type::Function(Box::new(FunctionType {
-            params: vec![Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            ))],
-            return_type: Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            )),
-            is_variadic: false,
-        })),
-    );
-    let _ = env.declare(
-        "_native_sin".to_string(),
-        RuntimeValue::NativeFunction(sin_fn),
-    );
+            },
+            Type::Function(Box::new(FunctionType {
+                params: vec![Type::Primitive(PrimitiveType::new(
+                    crate::ast::types::TypeKind::Float,
+                    "float",
+                ))],
+                return_type: Type::Primitive(PrimitiveType::new(
+                    crate::ast::types::TypeKind::Float,
+                    "float",
+                )),
+                is_variadic: false,
+                param_names: Vec::new(),
+            })),
+        ))
+    });
 
-    let cos_fn = NativeFunctionValue::new(
-        |args| {
-            if args.is_empty() {
-                return RuntimeValue::Null(crate::runtime::NullValue::new());
-            }
-            match &args[0] {
-                RuntimeValue::Float(f) => {
-                    RuntimeValue::Float(crate::runtime::FloatValue::new(f.value.cos()))
+    env.declare_lazy("_native_cos", || {
+        RuntimeValue::NativeFunction(NativeFunctionValue::new(
+            |args| {
+                if args.is_empty() {
+                    return RuntimeValue::Null(crate::runtime::NullValue::new());
                 }
-                RuntimeValue::Int(i) => {
-                    RuntimeValue::Float(crate::runtime::FloatValue::new((i.value as f64).cos()))
+                match &args[0] {
+                    RuntimeValue::Float(f) => {
+                        RuntimeValue::Float(crate::runtime::FloatValue::new(f.value.cos()))
+                    }
+                    RuntimeValue::Int(i) => {
+                        RuntimeValue::Float(crate::runtime::FloatValue::new((i.value as f64).cos()))
+                    }
+                    _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
                 }
-                _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
-            }
-        },
-        Type::Function(Box::new(FunctionType {
-            params: vec![Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            ))],
-            return_type: Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            )),
-            is_variadic: false,
-        })),
-    );
-    let _ = env.declare(
-        "_native_cos".to_string(),
-        RuntimeValue::NativeFunction(cos_fn),
-    );
+            },
+            Type::Function(Box::new(FunctionType {
+                params: vec![Type::Primitive(PrimitiveType::new(
+                    crate::ast::types::TypeKind::Float,
+                    "float",
+                ))],
+                return_type: Type::Primitive(PrimitiveType::new(
+                    crate::ast::types::TypeKind::Float,
+                    "float",
+                )),
+                is_variadic: false,
+                param_names: Vec::new(),
+            })),
+        ))
+    });
 
-    let tan_fn = NativeFunctionValue::new(
-        |args| {
-            if args.is_empty() {
-                return RuntimeValue::Null(crate::runtime::NullValue::new());
-            }
-            match &args[0] {
-                RuntimeValue::Float(f) => {
-                    RuntimeValue::Float(crate::runtime::FloatValue::new(f.value.tan()))
+    env.declare_lazy("_native_tan", || {
+        RuntimeValue::NativeFunction(NativeFunctionValue::new(
+            |args| {
+                if args.is_empty() {
+                    return RuntimeValue::Null(crate::runtime::NullValue::new());
                 }
-                RuntimeValue::Int(i) => {
-                    RuntimeValue::Float(crate::runtime::FloatValue::new((i.value as f64).tan()))
+                match &args[0] {
+                    RuntimeValue::Float(f) => {
+                        RuntimeValue::Float(crate::runtime::FloatValue::new(f.value.tan()))
+                    }
+                    RuntimeValue::Int(i) => {
+                        RuntimeValue::Float(crate::runtime::FloatValue::new((i.value as f64).tan()))
+                    }
+                    _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
                 }
-                _ => RuntimeValue::Null(crate::runtime::NullValue::new()),
-            }
-        },
-        Type::Function(Box::new(FunctionType {
-            params: vec![Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            ))],
-            return_type: Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            )),
-            is_variadic: false,
-        })),
-    );
-    let _ = env.declare(
-        "_native_tan".to_string(),
-        RuntimeValue::NativeFunction(tan_fn),
-    );
+            },
+            Type::Function(Box::new(FunctionType {
+                params: vec![Type::Primitive(PrimitiveType::new(
+                    crate::ast::types::TypeKind::Float,
+                    "float",
+                ))],
+                return_type: Type::Primitive(PrimitiveType::new(
+                    crate::ast::types::TypeKind::Float,
+                    "float",
+                )),
+                is_variadic: false,
+                param_names: Vec::new(),
+            })),
+        ))
+    });
 
-    let log_fn = NativeFunctionValue::new(
-        |args| {
-            if args.is_empty() {
-                return RuntimeValue::Null(crate::runtime::NullValue::new());
-            }
-            let x = match &args[0] {
-                RuntimeValue::Float(f) => f.value,
-                RuntimeValue::Int(i) => i.value as f64,
-                _ => return RuntimeValue::Null(crate::runtime::NullValue::new()),
-            };
-            let base = if args.len() > 1 {
-                match &args[1] {
+    env.declare_lazy("_native_log", || {
+        RuntimeValue::NativeFunction(NativeFunctionValue::new(
+            |args| {
+                if args.is_empty() {
+                    return RuntimeValue::Null(crate::runtime::NullValue::new());
+                }
+                let x = match &args[0] {
                     RuntimeValue::Float(f) => f.value,
                     RuntimeValue::Int(i) => i.value as f64,
-                    _ => std::f64::consts::E,
-                }
-            } else {
-                std::f64::consts::E
-            };
-            RuntimeValue::Float(crate::runtime::FloatValue::new(x.log(base)))
-        },
-        Type::Function(Box::new(FunctionType {
-            params: vec![Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            ))],
-            return_type: Type::Primitive(PrimitiveType::new(
-                crate::ast::types::TypeKind::Float,
-                "float",
-            )),
-            is_variadic: false,
-        })),
-    );
-    let _ = env.declare(
-        "_native_log".to_string(),
-        RuntimeValue::NativeFunction(log_fn),
-    );
+                    _ => return RuntimeValue::Null(crate::runtime::NullValue::new()),
+                };
+                let base = if args.len() > 1 {
+                    match &args[1] {
+                        RuntimeValue::Float(f) => f.value,
+                        RuntimeValue::Int(i) => i.value as f64,
+                        _ => std::f64::consts::E,
+                    }
+                } else {
+                    std::f64::consts::E
+                };
+                RuntimeValue::Float(crate::runtime::FloatValue::new(x.log(base)))
+            },
+            Type::Function(Box::new(FunctionType {
+                params: vec![Type::Primitive(PrimitiveType::new(
+                    crate::ast::types::TypeKind::Float,
+                    "float",
+                ))],
+                return_type: Type::Primitive(PrimitiveType::new(
+                    crate::ast::types::TypeKind::Float,
+                    "float",
+                )),
+                is_variadic: false,
+                param_names: Vec::new(),
+            })),
+        ))
+    });
 }
 
 fn register_json_wrappers(_env: &mut Environment, _registrar: Arc<Mutex<Registrar>>) {}
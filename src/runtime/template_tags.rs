@@ -0,0 +1,257 @@
+use crate::ast::types::PrimitiveType;
+use crate::runtime::{ArrayValue, ObjectValue, RuntimeValue, StrValue};
+use std::collections::HashMap;
+
+pub type TagEscaper = fn(&RuntimeValue) -> String;
+pub type TagTransform = fn(Vec<String>) -> Vec<String>;
+
+#[derive(Debug, Clone)]
+pub struct TemplateTagSpec {
+    pub name: String,
+    pub description: String,
+    pub escape: TagEscaper,
+    pub placeholder: Option<String>,
+    /// Rewrites the literal parts before interpolation.
+    pub transform: Option<TagTransform>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TemplateTagRegistry {
+    tags: HashMap<String, TemplateTagSpec>,
+}
+
+impl TemplateTagRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            tags: HashMap::new(),
+        };
+        registry.register_builtin_tags();
+        registry
+    }
+
+    fn register_builtin_tags(&mut self) {
+        self.register_tag(TemplateTagSpec {
+            name: "html".to_string(),
+            description: "Escapes interpolated values for safe inclusion in HTML".to_string(),
+            escape: escape_html,
+            placeholder: None,
+            transform: None,
+        });
+
+        self.register_tag(TemplateTagSpec {
+            name: "sql".to_string(),
+            description: "Quotes interpolated values as SQL literals and builds a ? query"
+                .to_string(),
+            escape: escape_sql,
+            placeholder: Some("?".to_string()),
+            transform: None,
+        });
+
+        self.register_tag(TemplateTagSpec {
+            name: "dedent".to_string(),
+            description: "Strips the common indentation and surrounding blank lines".to_string(),
+            escape: |value| value.to_string(),
+            placeholder: None,
+            transform: Some(dedent),
+        });
+    }
+
+    pub fn register_tag(&mut self, spec: TemplateTagSpec) {
+        self.tags.insert(spec.name.clone(), spec);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TemplateTagSpec> {
+        self.tags.get(name)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.tags.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+impl Default for TemplateTagRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateTagSpec {
+    pub fn apply(&self, strings: Vec<String>, values: Vec<RuntimeValue>) -> RuntimeValue {
+        let strings = match self.transform {
+            Some(transform) => transform(strings),
+            None => strings,
+        };
+
+        let mut text = String::new();
+        let mut query = String::new();
+
+        for (i, part) in strings.iter().enumerate() {
+            text.push_str(part);
+            query.push_str(part);
+            if let Some(value) = values.get(i) {
+                text.push_str(&self.escape_value(value));
+                if let Some(placeholder) = &self.placeholder {
+                    query.push_str(placeholder);
+                }
+            }
+        }
+
+        if self.transform.is_some() {
+            return str_value(&text);
+        }
+
+        let strings = strings
+            .into_iter()
+            .map(|s| RuntimeValue::Str(StrValue::new(s)))
+            .collect();
+
+        let mut properties = HashMap::new();
+        properties.insert("tag".to_string(), str_value(&self.name));
+        properties.insert("text".to_string(), str_value(&text));
+        properties.insert(
+            "strings".to_string(),
+            RuntimeValue::Array(ArrayValue::new(strings, PrimitiveType::str())),
+        );
+        if self.placeholder.is_some() {
+            properties.insert("query".to_string(), str_value(&query));
+        }
+        properties.insert(
+            "values".to_string(),
+            RuntimeValue::Array(ArrayValue::new(values, PrimitiveType::any())),
+        );
+
+        RuntimeValue::Object(ObjectValue::new(properties, PrimitiveType::any()))
+    }
+
+    fn is_own_value(&self, value: &RuntimeValue) -> bool {
+        match value {
+            RuntimeValue::Object(object) => matches!(
+                object.properties.read().unwrap().get("tag"),
+                Some(RuntimeValue::Str(tag)) if tag.value == self.name
+            ),
+            _ => false,
+        }
+    }
+
+    fn escape_value(&self, value: &RuntimeValue) -> String {
+        match value {
+            RuntimeValue::Object(object) if self.is_own_value(value) => object
+                .properties
+                .read()
+                .unwrap()
+                .get("text")
+                .map(|text| text.to_string())
+                .unwrap_or_default(),
+            RuntimeValue::Array(array) => array
+                .elements
+                .read()
+                .unwrap()
+                .iter()
+                .map(|element| self.escape_value(element))
+                .collect::<Vec<_>>()
+                .join(if self.name == "html" { "" } else { ", " }),
+            _ => (self.escape)(value),
+        }
+    }
+}
+
+fn str_value(text: &str) -> RuntimeValue {
+    RuntimeValue::Str(StrValue::new(text.to_string()))
+}
+
+/// Removes the indentation shared by every non-blank line of the literal parts.
+pub fn dedent(mut strings: Vec<String>) -> Vec<String> {
+    if let Some(first) = strings.first_mut() {
+        if let Some(rest) = first.strip_prefix('\n') {
+            *first = rest.to_string();
+        } else if let Some(rest) = first.strip_prefix("\r\n") {
+            *first = rest.to_string();
+        }
+    }
+    if let Some(last) = strings.last_mut() {
+        if let Some(newline) = last.rfind('\n') {
+            if last[newline + 1..].trim().is_empty() {
+                last.truncate(newline);
+                if last.ends_with('\r') {
+                    last.pop();
+                }
+            }
+        }
+    }
+
+    // A line starts at the beginning of the template or after a newline inside a part; text
+    // following an interpolation continues the previous line and is never a line start.
+    let mut indent: Option<usize> = None;
+    for (i, part) in strings.iter().enumerate() {
+        let lines: Vec<&str> = part.split('\n').collect();
+        for (j, line) in lines.iter().enumerate() {
+            if j == 0 && i > 0 {
+                continue;
+            }
+            // Whitespace right before an interpolation still indents the value that follows.
+            let before_value = j == lines.len() - 1 && i + 1 < strings.len();
+            if line.trim().is_empty() && !before_value {
+                continue;
+            }
+            let width = indent_width(line);
+            indent = Some(indent.map_or(width, |indent| indent.min(width)));
+        }
+    }
+
+    let Some(indent) = indent.filter(|&indent| indent > 0) else {
+        return strings;
+    };
+
+    strings
+        .into_iter()
+        .enumerate()
+        .map(|(i, part)| {
+            part.split('\n')
+                .enumerate()
+                .map(|(j, line)| {
+                    if j == 0 && i > 0 {
+                        line
+                    } else {
+                        &line[indent_width(line).min(indent)..]
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect()
+}
+
+fn indent_width(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+pub fn escape_html(value: &RuntimeValue) -> String {
+    if let RuntimeValue::Null(_) = value {
+        return String::new();
+    }
+
+    let text = value.to_string();
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn escape_sql(value: &RuntimeValue) -> String {
+    match value {
+        RuntimeValue::Null(_) => "NULL".to_string(),
+        RuntimeValue::Bool(b) => if b.value { "TRUE" } else { "FALSE" }.to_string(),
+        RuntimeValue::Int(_) | RuntimeValue::Float(_) => value.to_string(),
+        _ => format!("'{}'", value.to_string().replace('\'', "''")),
+    }
+}
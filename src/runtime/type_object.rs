@@ -1,3 +1,4 @@
+use crate::ast::nodes::InterfaceDecl;
 use crate::ast::types::Type;
 use crate::runtime::values::RuntimeValue;
 use std::collections::HashMap;
@@ -112,6 +113,7 @@ pub enum TypeKind {
 
     Interface {
         name: String,
+        declaration: Box<InterfaceDecl>,
     },
 
     Enum {
@@ -143,7 +145,7 @@ impl TypeKind {
         match self {
             TypeKind::Primitive(p) => p.name().to_string(),
             TypeKind::Class { name, .. } => name.clone(),
-            TypeKind::Interface { name } => name.clone(),
+            TypeKind::Interface { name, .. } => name.clone(),
             TypeKind::Enum { name, .. } => name.clone(),
             TypeKind::Function => "Function".to_string(),
             TypeKind::Generic { name, .. } => name.clone(),
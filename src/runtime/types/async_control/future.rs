@@ -149,13 +149,7 @@ impl TypeHandler for FutureType {
                         Some(Ok(val)) => {
                             return callback_executor(callback, vec![val], position).await;
                         }
-                        Some(Err(err)) => {
-                            return Err(RaccoonError::new(
-                                format!("Future rejected: {}", err),
-                                position,
-                                file,
-                            ));
-                        }
+                        Some(Err(err)) => return Err(err.into_error(position, file)),
                         None => {
                             tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
                         }
@@ -188,8 +182,7 @@ impl TypeHandler for FutureType {
                             return Ok(val);
                         }
                         Some(Err(err)) => {
-                            let err_value = RuntimeValue::Str(StrValue::new(err));
-                            return callback_executor(callback, vec![err_value], position).await;
+                            return callback_executor(callback, vec![err.value()], position).await;
                         }
                         None => {
                             tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
@@ -8,6 +8,21 @@ use async_trait::async_trait;
 
 pub struct ArrayType;
 
+/// Writes every element into a single buffer.
+pub fn join_elements(elements: &[RuntimeValue], separator: &str) -> String {
+    let mut joined = String::new();
+    for (index, element) in elements.iter().enumerate() {
+        if index > 0 {
+            joined.push_str(separator);
+        }
+        match element {
+            RuntimeValue::Str(s) => joined.push_str(&s.value),
+            other => joined.push_str(&other.to_string()),
+        }
+    }
+    joined
+}
+
 impl ArrayType {
     fn extract_array_mut<'a>(
         value: &'a mut RuntimeValue,
@@ -43,60 +58,66 @@ impl TypeHandler for ArrayType {
 
         match method {
             "push" => {
+                let mut elements = list.elements.write().unwrap();
                 require_args(&args, 1, method, position, file)?;
-                list.elements.push(args[0].clone());
+                elements.push(args[0].clone());
                 Ok(RuntimeValue::Null(NullValue::new()))
             }
             "pop" => {
+                let mut elements = list.elements.write().unwrap();
                 require_args(&args, 0, method, position, file.clone())?;
-                list.elements.pop().ok_or_else(|| {
+                elements.pop().ok_or_else(|| {
                     RaccoonError::new("Cannot pop from empty list".to_string(), position, file)
                 })
             }
             "shift" => {
+                let mut elements = list.elements.write().unwrap();
                 require_args(&args, 0, method, position, file.clone())?;
-                if list.elements.is_empty() {
+                if elements.is_empty() {
                     return Err(RaccoonError::new(
                         "Cannot shift from empty list".to_string(),
                         position,
                         file,
                     ));
                 }
-                Ok(list.elements.remove(0))
+                Ok(elements.remove(0))
             }
             "unshift" => {
+                let mut elements = list.elements.write().unwrap();
                 require_min_args(&args, 1, method, position, file)?;
                 for (i, arg) in args.iter().enumerate() {
-                    list.elements.insert(i, arg.clone());
+                    elements.insert(i, arg.clone());
                 }
-                Ok(RuntimeValue::Int(IntValue::new(list.elements.len() as i64)))
+                Ok(RuntimeValue::Int(IntValue::new(elements.len() as i64)))
             }
             "clear" => {
+                let mut elements = list.elements.write().unwrap();
                 require_args(&args, 0, method, position, file)?;
-                list.elements.clear();
+                elements.clear();
                 Ok(RuntimeValue::Null(NullValue::new()))
             }
             "splice" => {
+                let mut elements = list.elements.write().unwrap();
                 require_min_args(&args, 1, method, position, file.clone())?;
                 let start = extract_int(&args[0], "start", position, file.clone())?.max(0) as usize;
                 let delete_count = if args.len() > 1 {
                     extract_int(&args[1], "deleteCount", position, file.clone())?.max(0) as usize
                 } else {
-                    list.elements.len() - start
+                    elements.len() - start
                 };
 
                 let mut removed = Vec::new();
-                let actual_start = start.min(list.elements.len());
-                let actual_count = delete_count.min(list.elements.len() - actual_start);
+                let actual_start = start.min(elements.len());
+                let actual_count = delete_count.min(elements.len() - actual_start);
 
                 for _ in 0..actual_count {
-                    if actual_start < list.elements.len() {
-                        removed.push(list.elements.remove(actual_start));
+                    if actual_start < elements.len() {
+                        removed.push(elements.remove(actual_start));
                     }
                 }
 
                 for (i, arg) in args.iter().skip(2).enumerate() {
-                    list.elements.insert(actual_start + i, arg.clone());
+                    elements.insert(actual_start + i, arg.clone());
                 }
 
                 Ok(RuntimeValue::Array(ArrayValue::new(
@@ -105,6 +126,7 @@ impl TypeHandler for ArrayType {
                 )))
             }
             "fill" => {
+                let mut elements = list.elements.write().unwrap();
                 require_min_args(&args, 1, method, position, file.clone())?;
                 let value = &args[0];
                 let start = if args.len() > 1 {
@@ -115,29 +137,31 @@ impl TypeHandler for ArrayType {
                 let end = if args.len() > 2 {
                     extract_int(&args[2], "end", position, file)?.max(0) as usize
                 } else {
-                    list.elements.len()
+                    elements.len()
                 };
 
-                for i in start..end.min(list.elements.len()) {
-                    list.elements[i] = value.clone();
+                for i in start..end.min(elements.len()) {
+                    elements[i] = value.clone();
                 }
 
                 Ok(RuntimeValue::Null(NullValue::new()))
             }
 
             "concat" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 1, method, position, file.clone())?;
                 let other = extract_array(&args[0], "other", position, file)?;
-                let mut combined = list.elements.clone();
-                combined.extend(other.elements.clone());
+                let mut combined = elements.clone();
+                combined.extend(other.snapshot());
                 Ok(RuntimeValue::Array(ArrayValue::new(
                     combined,
                     list.element_type.clone(),
                 )))
             }
             "reverse" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 0, method, position, file)?;
-                let mut reversed = list.elements.clone();
+                let mut reversed = elements.clone();
                 reversed.reverse();
                 Ok(RuntimeValue::Array(ArrayValue::new(
                     reversed,
@@ -145,6 +169,7 @@ impl TypeHandler for ArrayType {
                 )))
             }
             "slice" => {
+                let elements = list.elements.read().unwrap();
                 require_args_range(&args, 1, 2, method, position, file.clone())?;
                 let start = extract_int(&args[0], "start", position, file.clone())? as isize;
                 let end = if args.len() == 2 {
@@ -153,26 +178,26 @@ impl TypeHandler for ArrayType {
                     None
                 };
 
-                let len = list.elements.len() as isize;
+                let len = elements.len() as isize;
                 let real_start = if start < 0 {
                     (len + start).max(0) as usize
                 } else {
-                    (start as usize).min(list.elements.len())
+                    (start as usize).min(elements.len())
                 };
                 let real_end = match end {
                     Some(e) => {
                         if e < 0 {
                             (len + e).max(0) as usize
                         } else {
-                            (e as usize).min(list.elements.len())
+                            (e as usize).min(elements.len())
                         }
                     }
-                    None => list.elements.len(),
+                    None => elements.len(),
                 };
 
                 if real_start <= real_end {
                     Ok(RuntimeValue::Array(ArrayValue::new(
-                        list.elements[real_start..real_end].to_vec(),
+                        elements[real_start..real_end].to_vec(),
                         list.element_type.clone(),
                     )))
                 } else {
@@ -183,6 +208,7 @@ impl TypeHandler for ArrayType {
                 }
             }
             "flat" => {
+                let elements = list.elements.read().unwrap();
                 let depth = if args.is_empty() {
                     1
                 } else {
@@ -198,7 +224,7 @@ impl TypeHandler for ArrayType {
                     for elem in elements {
                         match elem {
                             RuntimeValue::Array(inner_list) => {
-                                result.extend(flatten_recursive(&inner_list.elements, depth - 1));
+                                result.extend(flatten_recursive(&inner_list.snapshot(), depth - 1));
                             }
                             _ => result.push(elem.clone()),
                         }
@@ -206,16 +232,17 @@ impl TypeHandler for ArrayType {
                     result
                 }
 
-                let flattened = flatten_recursive(&list.elements, depth);
+                let flattened = flatten_recursive(&elements, depth);
                 Ok(RuntimeValue::Array(ArrayValue::new(
                     flattened,
                     PrimitiveType::any(),
                 )))
             }
             "unique" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 0, method, position, file)?;
                 let mut unique_elements = Vec::new();
-                for elem in &list.elements {
+                for elem in elements.iter() {
                     let mut is_unique = true;
                     for unique_elem in &unique_elements {
                         if elem.equals(unique_elem) {
@@ -234,8 +261,9 @@ impl TypeHandler for ArrayType {
             }
 
             "indexOf" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 1, method, position, file)?;
-                for (i, elem) in list.elements.iter().enumerate() {
+                for (i, elem) in elements.iter().enumerate() {
                     if elem.equals(&args[0]) {
                         return Ok(RuntimeValue::Int(IntValue::new(i as i64)));
                     }
@@ -243,8 +271,9 @@ impl TypeHandler for ArrayType {
                 Ok(RuntimeValue::Int(IntValue::new(-1)))
             }
             "lastIndexOf" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 1, method, position, file)?;
-                for (i, elem) in list.elements.iter().enumerate().rev() {
+                for (i, elem) in elements.iter().enumerate().rev() {
                     if elem.equals(&args[0]) {
                         return Ok(RuntimeValue::Int(IntValue::new(i as i64)));
                     }
@@ -252,8 +281,9 @@ impl TypeHandler for ArrayType {
                 Ok(RuntimeValue::Int(IntValue::new(-1)))
             }
             "includes" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 1, method, position, file)?;
-                for elem in &list.elements {
+                for elem in elements.iter() {
                     if elem.equals(&args[0]) {
                         return Ok(RuntimeValue::Bool(BoolValue::new(true)));
                     }
@@ -261,49 +291,61 @@ impl TypeHandler for ArrayType {
                 Ok(RuntimeValue::Bool(BoolValue::new(false)))
             }
             "at" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 1, method, position, file.clone())?;
                 let index = extract_int(&args[0], "index", position, file)?;
-                let len = list.elements.len() as i64;
+                let len = elements.len() as i64;
                 let actual_index = if index < 0 { len + index } else { index };
 
                 if actual_index < 0 || actual_index >= len {
                     Ok(RuntimeValue::Null(NullValue::new()))
                 } else {
-                    Ok(list.elements[actual_index as usize].clone())
+                    Ok(elements[actual_index as usize].clone())
                 }
             }
 
             "first" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 0, method, position, file)?;
-                Ok(list
-                    .elements
+                Ok(elements
                     .first()
                     .cloned()
                     .unwrap_or(RuntimeValue::Null(NullValue::new())))
             }
             "last" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 0, method, position, file)?;
-                Ok(list
-                    .elements
+                Ok(elements
                     .last()
                     .cloned()
                     .unwrap_or(RuntimeValue::Null(NullValue::new())))
             }
 
             "length" | "len" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 0, method, position, file)?;
-                Ok(RuntimeValue::Int(IntValue::new(list.elements.len() as i64)))
+                Ok(RuntimeValue::Int(IntValue::new(elements.len() as i64)))
             }
             "isEmpty" => {
+                let elements = list.elements.read().unwrap();
                 require_args(&args, 0, method, position, file)?;
-                Ok(RuntimeValue::Bool(BoolValue::new(list.elements.is_empty())))
+                Ok(RuntimeValue::Bool(BoolValue::new(elements.is_empty())))
             }
 
             "join" => {
-                require_args(&args, 1, method, position, file.clone())?;
-                let separator = extract_str(&args[0], "separator", position, file)?;
-                let parts: Vec<String> = list.elements.iter().map(|v| v.to_string()).collect();
-                Ok(RuntimeValue::Str(StrValue::new(parts.join(separator))))
+                let elements = list.elements.read().unwrap();
+                require_args_range(&args, 0, 1, method, position, file.clone())?;
+                let separator = match args.first() {
+                    Some(separator) => extract_str(separator, "separator", position, file)?,
+                    None => ",",
+                };
+                Ok(RuntimeValue::Str(StrValue::new(join_elements(
+                    &elements, separator,
+                ))))
+            }
+            "copy" => {
+                require_args(&args, 0, method, position, file)?;
+                Ok(RuntimeValue::Array(list.copy()))
             }
             "toStr" => {
                 require_args(&args, 0, method, position, file)?;
@@ -351,6 +393,7 @@ impl TypeHandler for ArrayType {
                 | "len"
                 | "isEmpty"
                 | "join"
+                | "copy"
                 | "toStr"
         )
     }
@@ -384,6 +427,8 @@ impl TypeHandler for ArrayType {
         callback_executor: &CallbackExecutor,
     ) -> Result<RuntimeValue, RaccoonError> {
         let list = Self::extract_array_mut(value, position, file.clone())?;
+        // Callbacks may mutate the list, so they walk a snapshot rather than holding its lock.
+        let elements = list.snapshot();
 
         match method {
             "map" => {
@@ -391,7 +436,7 @@ impl TypeHandler for ArrayType {
                 let callback = &args[0];
                 let mut mapped = Vec::new();
 
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in elements.iter().enumerate() {
                     let result = callback_executor(
                         callback.clone(),
                         vec![
@@ -418,7 +463,7 @@ impl TypeHandler for ArrayType {
                 let callback = &args[0];
                 let mut filtered = Vec::new();
 
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in elements.iter().enumerate() {
                     let result = callback_executor(
                         callback.clone(),
                         vec![
@@ -443,7 +488,7 @@ impl TypeHandler for ArrayType {
             "reduce" => {
                 require_args(&args, 1, method, position, file.clone())?;
                 let callback = &args[0];
-                if list.elements.is_empty() && args.len() < 2 {
+                if elements.is_empty() && args.len() < 2 {
                     return Err(RaccoonError::new(
                         "reduce of empty array with no initial value".to_string(),
                         position,
@@ -454,12 +499,12 @@ impl TypeHandler for ArrayType {
                 let mut accumulator = if args.len() >= 2 {
                     args[1].clone()
                 } else {
-                    list.elements[0].clone()
+                    elements[0].clone()
                 };
 
                 let start_index = if args.len() >= 2 { 0 } else { 1 };
 
-                for (index, element) in list.elements.iter().enumerate().skip(start_index) {
+                for (index, element) in elements.iter().enumerate().skip(start_index) {
                     accumulator = callback_executor(
                         callback.clone(),
                         vec![
@@ -478,7 +523,7 @@ impl TypeHandler for ArrayType {
             "forEach" => {
                 require_args(&args, 1, method, position, file)?;
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in elements.iter().enumerate() {
                     callback_executor(
                         callback.clone(),
                         vec![
@@ -495,7 +540,7 @@ impl TypeHandler for ArrayType {
             "find" => {
                 require_args(&args, 1, method, position, file)?;
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in elements.iter().enumerate() {
                     let result = callback_executor(
                         callback.clone(),
                         vec![
@@ -516,7 +561,7 @@ impl TypeHandler for ArrayType {
             "findIndex" => {
                 require_args(&args, 1, method, position, file)?;
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in elements.iter().enumerate() {
                     let result = callback_executor(
                         callback.clone(),
                         vec![
@@ -537,7 +582,7 @@ impl TypeHandler for ArrayType {
             "some" => {
                 require_args(&args, 1, method, position, file)?;
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in elements.iter().enumerate() {
                     let result = callback_executor(
                         callback.clone(),
                         vec![
@@ -558,7 +603,7 @@ impl TypeHandler for ArrayType {
             "every" => {
                 require_args(&args, 1, method, position, file)?;
                 let callback = &args[0];
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in elements.iter().enumerate() {
                     let result = callback_executor(
                         callback.clone(),
                         vec![
@@ -581,7 +626,7 @@ impl TypeHandler for ArrayType {
                 let callback = &args[0];
                 let mut result_elements = Vec::new();
 
-                for (index, element) in list.elements.iter().enumerate() {
+                for (index, element) in elements.iter().enumerate() {
                     let result = callback_executor(
                         callback.clone(),
                         vec![
@@ -594,7 +639,7 @@ impl TypeHandler for ArrayType {
 
                     match result {
                         RuntimeValue::Array(inner_list) => {
-                            result_elements.extend(inner_list.elements);
+                            result_elements.extend(inner_list.snapshot());
                         }
                         _ => result_elements.push(result),
                     }
@@ -1,18 +1,19 @@
 use crate::error::RaccoonError;
 use crate::runtime::types::helpers::*;
 use crate::runtime::types::TypeHandler;
-use crate::runtime::{BoolValue, IntValue, MapValue, NullValue, RuntimeValue, StrValue};
+use crate::runtime::{BoolValue, IntValue, MapKey, MapValue, NullValue, RuntimeValue, StrValue};
 use crate::tokens::Position;
 use async_trait::async_trait;
+use std::sync::Arc;
 
 pub struct MapType;
 
 impl MapType {
-    fn extract_map_mut<'a>(
-        value: &'a mut RuntimeValue,
+    fn extract_map<'a>(
+        value: &'a RuntimeValue,
         position: Position,
         file: Option<String>,
-    ) -> Result<&'a mut MapValue, RaccoonError> {
+    ) -> Result<&'a MapValue, RaccoonError> {
         match value {
             RuntimeValue::Map(m) => Ok(m),
             _ => Err(RaccoonError::new(
@@ -22,6 +23,14 @@ impl MapType {
             )),
         }
     }
+
+    fn key(
+        value: &RuntimeValue,
+        position: Position,
+        file: Option<String>,
+    ) -> Result<MapKey, RaccoonError> {
+        MapKey::from_value(value).map_err(|message| RaccoonError::new(message, position, file))
+    }
 }
 
 #[async_trait]
@@ -38,71 +47,87 @@ impl TypeHandler for MapType {
         position: Position,
         file: Option<String>,
     ) -> Result<RuntimeValue, RaccoonError> {
-        let map = Self::extract_map_mut(value, position, file.clone())?;
+        let map = Self::extract_map(value, position, file.clone())?;
 
         match method {
             "get" => {
-                require_args(&args, 1, method, position, file)?;
-                let key = args[0].to_string();
+                require_args(&args, 1, method, position, file.clone())?;
+                let key = Self::key(&args[0], position, file)?;
                 Ok(map
                     .entries
+                    .read()
+                    .unwrap()
                     .get(&key)
                     .cloned()
                     .unwrap_or(RuntimeValue::Null(NullValue::new())))
             }
             "set" => {
-                require_args(&args, 2, method, position, file)?;
-                let key = args[0].to_string();
+                require_args(&args, 2, method, position, file.clone())?;
+                let key = Self::key(&args[0], position, file)?;
                 let value = args[1].clone();
-                map.entries.insert(key, value);
+                map.entries.write().unwrap().insert(key, value);
                 Ok(RuntimeValue::Null(NullValue::new()))
             }
             "has" => {
-                require_args(&args, 1, method, position, file)?;
-                let key = args[0].to_string();
+                require_args(&args, 1, method, position, file.clone())?;
+                let key = Self::key(&args[0], position, file)?;
                 Ok(RuntimeValue::Bool(BoolValue::new(
-                    map.entries.contains_key(&key),
+                    map.entries.read().unwrap().contains_key(&key),
                 )))
             }
             "delete" => {
-                require_args(&args, 1, method, position, file)?;
-                let key = args[0].to_string();
-                let existed = map.entries.remove(&key).is_some();
+                require_args(&args, 1, method, position, file.clone())?;
+                let key = Self::key(&args[0], position, file)?;
+                let existed = map.entries.write().unwrap().shift_remove(&key).is_some();
                 Ok(RuntimeValue::Bool(BoolValue::new(existed)))
             }
             "clear" => {
                 require_args(&args, 0, method, position, file)?;
-                map.entries.clear();
+                map.entries.write().unwrap().clear();
                 Ok(RuntimeValue::Null(NullValue::new()))
             }
             "size" | "length" => {
                 require_args(&args, 0, method, position, file)?;
-                Ok(RuntimeValue::Int(IntValue::new(map.entries.len() as i64)))
+                Ok(RuntimeValue::Int(IntValue::new(map.len() as i64)))
             }
             "isEmpty" => {
                 require_args(&args, 0, method, position, file)?;
-                Ok(RuntimeValue::Bool(BoolValue::new(map.entries.is_empty())))
+                Ok(RuntimeValue::Bool(BoolValue::new(map.is_empty())))
             }
             "keys" => {
                 require_args(&args, 0, method, position, file)?;
                 let keys: Vec<RuntimeValue> = map
                     .entries
+                    .read()
+                    .unwrap()
                     .keys()
-                    .map(|k| RuntimeValue::Str(StrValue::new(k.clone())))
+                    .map(MapKey::to_value)
                     .collect();
                 Ok(RuntimeValue::Array(crate::runtime::ArrayValue::new(
                     keys,
-                    crate::ast::types::PrimitiveType::str(),
+                    map.key_type.clone(),
                 )))
             }
             "values" => {
                 require_args(&args, 0, method, position, file)?;
-                let values: Vec<RuntimeValue> = map.entries.values().cloned().collect();
+                let values: Vec<RuntimeValue> =
+                    map.entries.read().unwrap().values().cloned().collect();
                 Ok(RuntimeValue::Array(crate::runtime::ArrayValue::new(
                     values,
                     crate::ast::types::PrimitiveType::any(),
                 )))
             }
+            "entries" => {
+                require_args(&args, 0, method, position, file)?;
+                Ok(RuntimeValue::Array(crate::runtime::ArrayValue::new(
+                    map.entry_pairs(),
+                    crate::ast::types::PrimitiveType::any(),
+                )))
+            }
+            "copy" => {
+                require_args(&args, 0, method, position, file)?;
+                Ok(RuntimeValue::Map(Arc::new(map.copy())))
+            }
             "toStr" => {
                 require_args(&args, 0, method, position, file)?;
                 Ok(RuntimeValue::Str(StrValue::new(map.to_string())))
@@ -134,6 +159,8 @@ impl TypeHandler for MapType {
                 | "isEmpty"
                 | "keys"
                 | "values"
+                | "entries"
+                | "copy"
                 | "toStr"
         )
     }
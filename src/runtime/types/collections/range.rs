@@ -1,13 +1,36 @@
-use crate::ast::types::PrimitiveType;
 use crate::error::RaccoonError;
 use crate::runtime::types::helpers::*;
 use crate::runtime::types::TypeHandler;
-use crate::runtime::{ArrayValue, IntValue, RuntimeValue};
+use crate::runtime::{BoolValue, IntValue, NullValue, RangeValue, RuntimeValue, StrValue};
 use crate::tokens::Position;
 use async_trait::async_trait;
 
 pub struct RangeType;
 
+impl RangeType {
+    fn extract_range(
+        value: &RuntimeValue,
+        position: Position,
+        file: Option<String>,
+    ) -> Result<RangeValue, RaccoonError> {
+        match value {
+            RuntimeValue::Range(range) => Ok(*range),
+            _ => Err(RaccoonError::new(
+                format!("Expected range, got {}", value.get_name()),
+                position,
+                file,
+            )),
+        }
+    }
+
+    fn int_or_null(value: Option<i64>) -> RuntimeValue {
+        match value {
+            Some(value) => RuntimeValue::Int(IntValue::new(value)),
+            None => RuntimeValue::Null(NullValue::new()),
+        }
+    }
+}
+
 #[async_trait]
 impl TypeHandler for RangeType {
     fn type_name(&self) -> &str {
@@ -16,13 +39,60 @@ impl TypeHandler for RangeType {
 
     fn call_instance_method(
         &self,
-        _value: &mut RuntimeValue,
+        value: &mut RuntimeValue,
         method: &str,
-        _args: Vec<RuntimeValue>,
+        args: Vec<RuntimeValue>,
         position: Position,
         file: Option<String>,
     ) -> Result<RuntimeValue, RaccoonError> {
-        Err(method_not_found_error("range", method, position, file))
+        let range = Self::extract_range(value, position, file.clone())?;
+
+        match method {
+            "contains" | "includes" => {
+                require_args(&args, 1, method, position, file)?;
+                let found = matches!(&args[0], RuntimeValue::Int(i) if range.contains(i.value));
+                Ok(RuntimeValue::Bool(BoolValue::new(found)))
+            }
+            "step" => {
+                require_args(&args, 1, method, position, file.clone())?;
+                let step = extract_int(&args[0], "step", position, file.clone())?;
+                if step == 0 {
+                    return Err(RaccoonError::new(
+                        "Range step cannot be zero".to_string(),
+                        position,
+                        file,
+                    ));
+                }
+                Ok(RuntimeValue::Range(range.with_step(step)))
+            }
+            "toList" | "toArray" => {
+                require_args(&args, 0, method, position, file)?;
+                Ok(RuntimeValue::Array(range.to_list()))
+            }
+            "length" | "size" => {
+                require_args(&args, 0, method, position, file)?;
+                Ok(RuntimeValue::Int(IntValue::new(range.len() as i64)))
+            }
+            "isEmpty" => {
+                require_args(&args, 0, method, position, file)?;
+                Ok(RuntimeValue::Bool(BoolValue::new(range.is_empty())))
+            }
+            "first" => {
+                require_args(&args, 0, method, position, file)?;
+                Ok(Self::int_or_null(range.get(0)))
+            }
+            "last" => {
+                require_args(&args, 0, method, position, file)?;
+                Ok(Self::int_or_null(
+                    range.len().checked_sub(1).and_then(|last| range.get(last)),
+                ))
+            }
+            "toString" | "toStr" => {
+                require_args(&args, 0, method, position, file)?;
+                Ok(RuntimeValue::Str(StrValue::new(range.to_string())))
+            }
+            _ => Err(method_not_found_error("range", method, position, file)),
+        }
     }
 
     fn call_static_method(
@@ -55,35 +125,10 @@ impl TypeHandler for RangeType {
                     ));
                 }
 
-                let mut elements = Vec::new();
-                let mut current = start;
-
                 let inclusive = method == "inclusive";
-
-                if step > 0 {
-                    while if inclusive {
-                        current <= end
-                    } else {
-                        current < end
-                    } {
-                        elements.push(RuntimeValue::Int(IntValue::new(current)));
-                        current += step;
-                    }
-                } else {
-                    while if inclusive {
-                        current >= end
-                    } else {
-                        current > end
-                    } {
-                        elements.push(RuntimeValue::Int(IntValue::new(current)));
-                        current += step;
-                    }
-                }
-
-                Ok(RuntimeValue::Array(ArrayValue::new(
-                    elements,
-                    PrimitiveType::int(),
-                )))
+                Ok(RuntimeValue::Range(
+                    RangeValue::new(start, end, inclusive).with_step(step),
+                ))
             }
             _ => Err(static_method_not_found_error(
                 "range", method, position, file,
@@ -91,8 +136,22 @@ impl TypeHandler for RangeType {
         }
     }
 
-    fn has_instance_method(&self, _method: &str) -> bool {
-        false
+    fn has_instance_method(&self, method: &str) -> bool {
+        matches!(
+            method,
+            "contains"
+                | "includes"
+                | "step"
+                | "toList"
+                | "toArray"
+                | "length"
+                | "size"
+                | "isEmpty"
+                | "first"
+                | "last"
+                | "toString"
+                | "toStr"
+        )
     }
 
     fn has_static_method(&self, method: &str) -> bool {
@@ -46,51 +46,53 @@ impl TypeHandler for SetType {
                 require_args(&args, 1, method, position, file)?;
                 let item = &args[0];
 
-                let exists = set.elements.iter().any(|e| e.equals(item));
+                let exists = set.elements.read().unwrap().iter().any(|e| e.equals(item));
                 if !exists {
-                    set.elements.push(item.clone());
+                    set.elements.write().unwrap().push(item.clone());
                 }
                 Ok(RuntimeValue::Null(NullValue::new()))
             }
             "remove" => {
                 require_args(&args, 1, method, position, file)?;
                 let item = &args[0];
-                set.elements.retain(|e| !e.equals(item));
+                // Compared outside the lock, since `item` may be the set itself.
+                let kept = set.snapshot().into_iter().filter(|e| !e.equals(item));
+                *set.elements.write().unwrap() = kept.collect();
                 Ok(RuntimeValue::Null(NullValue::new()))
             }
             "contains" | "has" => {
                 require_args(&args, 1, method, position, file)?;
                 let item = &args[0];
-                let exists = set.elements.iter().any(|e| e.equals(item));
+                let exists = set.elements.read().unwrap().iter().any(|e| e.equals(item));
                 Ok(RuntimeValue::Bool(BoolValue::new(exists)))
             }
             "size" | "length" => {
                 require_args(&args, 0, method, position, file)?;
-                Ok(RuntimeValue::Int(IntValue::new(set.elements.len() as i64)))
+                Ok(RuntimeValue::Int(IntValue::new(set.len() as i64)))
             }
             "clear" => {
                 require_args(&args, 0, method, position, file)?;
-                set.elements.clear();
+                set.elements.write().unwrap().clear();
                 Ok(RuntimeValue::Null(NullValue::new()))
             }
             "isEmpty" => {
                 require_args(&args, 0, method, position, file)?;
-                Ok(RuntimeValue::Bool(BoolValue::new(set.elements.is_empty())))
+                Ok(RuntimeValue::Bool(BoolValue::new(set.is_empty())))
             }
             "toList" => {
                 require_args(&args, 0, method, position, file)?;
                 Ok(RuntimeValue::Array(ArrayValue::new(
-                    set.elements.clone(),
+                    set.snapshot(),
                     PrimitiveType::any(),
                 )))
             }
             "union" => {
                 require_args(&args, 1, method, position, file.clone())?;
                 let other = extract_array(&args[0], "other", position, file)?;
-                let mut result = set.elements.clone();
-                for item in &other.elements {
-                    if !result.iter().any(|e| e.equals(item)) {
-                        result.push(item.clone());
+                let mut result = set.snapshot();
+                for item in other.snapshot() {
+                    if !result.iter().any(|e| e.equals(&item)) {
+                        result.push(item);
                     }
                 }
                 Ok(RuntimeValue::Array(ArrayValue::new(
@@ -101,11 +103,11 @@ impl TypeHandler for SetType {
             "intersection" => {
                 require_args(&args, 1, method, position, file.clone())?;
                 let other = extract_array(&args[0], "other", position, file)?;
+                let other = other.snapshot();
                 let result: Vec<RuntimeValue> = set
-                    .elements
-                    .iter()
-                    .filter(|e| other.elements.iter().any(|o| o.equals(e)))
-                    .cloned()
+                    .snapshot()
+                    .into_iter()
+                    .filter(|e| other.iter().any(|o| o.equals(e)))
                     .collect();
                 Ok(RuntimeValue::Array(ArrayValue::new(
                     result,
@@ -115,11 +117,11 @@ impl TypeHandler for SetType {
             "difference" => {
                 require_args(&args, 1, method, position, file.clone())?;
                 let other = extract_array(&args[0], "other", position, file)?;
+                let other = other.snapshot();
                 let result: Vec<RuntimeValue> = set
-                    .elements
-                    .iter()
-                    .filter(|e| !other.elements.iter().any(|o| o.equals(e)))
-                    .cloned()
+                    .snapshot()
+                    .into_iter()
+                    .filter(|e| !other.iter().any(|o| o.equals(e)))
                     .collect();
                 Ok(RuntimeValue::Array(ArrayValue::new(
                     result,
@@ -142,9 +144,9 @@ impl TypeHandler for SetType {
                 require_args(&args, 1, method, position, file.clone())?;
                 let list = extract_array(&args[0], "array", position, file)?;
                 let mut unique = Vec::new();
-                for item in &list.elements {
-                    if !unique.iter().any(|e: &RuntimeValue| e.equals(item)) {
-                        unique.push(item.clone());
+                for item in list.snapshot() {
+                    if !unique.iter().any(|e: &RuntimeValue| e.equals(&item)) {
+                        unique.push(item);
                     }
                 }
                 Ok(RuntimeValue::Array(ArrayValue::new(
@@ -2,7 +2,7 @@ use crate::ast::types::PrimitiveType;
 use crate::error::RaccoonError;
 use crate::runtime::types::helpers::*;
 use crate::runtime::types::TypeHandler;
-use crate::runtime::{ArrayValue, IntValue, RuntimeValue, StrValue};
+use crate::runtime::{ArrayValue, IntValue, RuntimeValue, StrValue, TupleValue};
 use crate::tokens::Position;
 use async_trait::async_trait;
 
@@ -13,9 +13,9 @@ impl TupleType {
         value: &'a RuntimeValue,
         position: Position,
         file: Option<String>,
-    ) -> Result<&'a ArrayValue, RaccoonError> {
+    ) -> Result<&'a TupleValue, RaccoonError> {
         match value {
-            RuntimeValue::Array(list) => Ok(list),
+            RuntimeValue::Tuple(tuple) => Ok(tuple),
             _ => Err(RaccoonError::new(
                 format!("Expected tuple, got {}", value.get_name()),
                 position,
@@ -48,7 +48,7 @@ impl TypeHandler for TupleType {
                 if index < tuple.elements.len() {
                     Ok(tuple.elements[index].clone())
                 } else {
-                    Err(RaccoonError::new(
+                    Err(RaccoonError::index_out_of_range_error(
                         format!(
                             "Index {} out of bounds for tuple of length {}",
                             index,
@@ -67,11 +67,7 @@ impl TypeHandler for TupleType {
             }
             "toString" | "toStr" => {
                 require_args(&args, 0, method, position, file)?;
-                let items: Vec<String> = tuple.elements.iter().map(|e| e.to_string()).collect();
-                Ok(RuntimeValue::Str(StrValue::new(format!(
-                    "({})",
-                    items.join(", ")
-                ))))
+                Ok(RuntimeValue::Str(StrValue::new(tuple.to_string())))
             }
             "toList" => {
                 require_args(&args, 0, method, position, file)?;
@@ -116,10 +112,7 @@ impl TypeHandler for TupleType {
         file: Option<String>,
     ) -> Result<RuntimeValue, RaccoonError> {
         match method {
-            "of" => Ok(RuntimeValue::Array(ArrayValue::new(
-                args,
-                PrimitiveType::any(),
-            ))),
+            "of" => Ok(RuntimeValue::Tuple(TupleValue::new(args))),
             _ => Err(static_method_not_found_error(
                 "tuple", method, position, file,
             )),
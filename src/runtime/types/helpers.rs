@@ -10,7 +10,7 @@ pub fn require_args(
     file: Option<String>,
 ) -> Result<(), RaccoonError> {
     if args.len() != expected {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::type_error(
             format!(
                 "{} requires {} argument(s), got {}",
                 method,
@@ -34,7 +34,7 @@ pub fn require_args_range(
 ) -> Result<(), RaccoonError> {
     let count = args.len();
     if count < min || count > max {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::type_error(
             format!(
                 "{} requires {}-{} argument(s), got {}",
                 method, min, max, count
@@ -54,7 +54,7 @@ pub fn require_min_args(
     file: Option<String>,
 ) -> Result<(), RaccoonError> {
     if args.len() < min {
-        return Err(RaccoonError::new(
+        return Err(RaccoonError::type_error(
             format!(
                 "{} requires at least {} argument(s), got {}",
                 method,
@@ -76,7 +76,7 @@ pub fn extract_str<'a>(
 ) -> Result<&'a str, RaccoonError> {
     match value {
         RuntimeValue::Str(s) => Ok(&s.value),
-        _ => Err(RaccoonError::new(
+        _ => Err(RaccoonError::type_error(
             format!("{} must be a string, got {}", arg_name, value.get_name()),
             position,
             file,
@@ -92,7 +92,7 @@ pub fn extract_int(
 ) -> Result<i64, RaccoonError> {
     match value {
         RuntimeValue::Int(i) => Ok(i.value),
-        _ => Err(RaccoonError::new(
+        _ => Err(RaccoonError::type_error(
             format!("{} must be an integer, got {}", arg_name, value.get_name()),
             position,
             file,
@@ -108,7 +108,7 @@ pub fn extract_float(
 ) -> Result<f64, RaccoonError> {
     match value {
         RuntimeValue::Float(f) => Ok(f.value),
-        _ => Err(RaccoonError::new(
+        _ => Err(RaccoonError::type_error(
             format!("{} must be a float, got {}", arg_name, value.get_name()),
             position,
             file,
@@ -124,7 +124,7 @@ pub fn extract_decimal(
 ) -> Result<f64, RaccoonError> {
     match value {
         RuntimeValue::Decimal(d) => Ok(d.value),
-        _ => Err(RaccoonError::new(
+        _ => Err(RaccoonError::type_error(
             format!("{} must be a decimal, got {}", arg_name, value.get_name()),
             position,
             file,
@@ -140,7 +140,7 @@ pub fn extract_bigint(
 ) -> Result<i128, RaccoonError> {
     match value {
         RuntimeValue::BigInt(b) => Ok(b.value),
-        _ => Err(RaccoonError::new(
+        _ => Err(RaccoonError::type_error(
             format!("{} must be a bigint, got {}", arg_name, value.get_name()),
             position,
             file,
@@ -156,7 +156,7 @@ pub fn extract_bool(
 ) -> Result<bool, RaccoonError> {
     match value {
         RuntimeValue::Bool(b) => Ok(b.value),
-        _ => Err(RaccoonError::new(
+        _ => Err(RaccoonError::type_error(
             format!("{} must be a boolean, got {}", arg_name, value.get_name()),
             position,
             file,
@@ -173,7 +173,7 @@ pub fn extract_numeric(
     match value {
         RuntimeValue::Int(i) => Ok(i.value as f64),
         RuntimeValue::Float(f) => Ok(f.value),
-        _ => Err(RaccoonError::new(
+        _ => Err(RaccoonError::type_error(
             format!("{} must be a number, got {}", arg_name, value.get_name()),
             position,
             file,
@@ -189,7 +189,7 @@ pub fn extract_array<'a>(
 ) -> Result<&'a crate::runtime::ArrayValue, RaccoonError> {
     match value {
         RuntimeValue::Array(l) => Ok(l),
-        _ => Err(RaccoonError::new(
+        _ => Err(RaccoonError::type_error(
             format!("{} must be a list, got {}", arg_name, value.get_name()),
             position,
             file,
@@ -204,7 +204,7 @@ pub fn to_truthy(value: &RuntimeValue) -> bool {
         RuntimeValue::Int(i) => i.value != 0,
         RuntimeValue::Float(f) => f.value != 0.0,
         RuntimeValue::Str(s) => !s.value.is_empty(),
-        RuntimeValue::Array(l) => !l.elements.is_empty(),
+        RuntimeValue::Array(l) => !l.is_empty(),
         _ => true,
     }
 }
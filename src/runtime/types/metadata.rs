@@ -76,7 +76,7 @@ impl MethodMetadata {
     }
 
     pub fn matches(&self, name: &str) -> bool {
-        self.name == name || self.aliases.iter().any(|&alias| alias == name)
+        self.name == name || self.aliases.contains(&name)
     }
 }
 
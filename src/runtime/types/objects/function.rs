@@ -1,11 +1,151 @@
+use crate::ast::nodes::Stmt;
+use crate::ast::types::{FunctionType as FnType, Type};
 use crate::error::RaccoonError;
 use crate::runtime::types::TypeHandler;
-use crate::runtime::{IntValue, RuntimeValue, StrValue};
+use crate::runtime::{
+    BoolValue, BoundCall, FunctionValue, IntValue, NullValue, RuntimeValue, StrValue,
+};
 use crate::tokens::Position;
 use async_trait::async_trait;
+use std::sync::Arc;
 
 pub struct FunctionType;
 
+impl FunctionType {
+    pub fn is_function(value: &RuntimeValue) -> bool {
+        match value {
+            RuntimeValue::Function(_)
+            | RuntimeValue::NativeFunction(_)
+            | RuntimeValue::NativeAsyncFunction(_) => true,
+            RuntimeValue::Dynamic(dynamic) => dynamic.type_name() == "IRFunction",
+            _ => false,
+        }
+    }
+
+    pub fn name(function: &RuntimeValue) -> String {
+        match function {
+            RuntimeValue::Function(function) => function.name.clone().unwrap_or_default(),
+            RuntimeValue::NativeAsyncFunction(function) => {
+                function.name.clone().unwrap_or_default()
+            }
+            RuntimeValue::Dynamic(dynamic) => dynamic
+                .get_property("name")
+                .map(|name| name.to_string())
+                .unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+
+    pub fn arity(function: &RuntimeValue) -> usize {
+        match function {
+            RuntimeValue::Function(function) => match &function.bound {
+                Some(bound) => Self::arity(&bound.target).saturating_sub(bound.args.len()),
+                None => function.parameters.iter().filter(|p| !p.is_rest).count(),
+            },
+            other => match other.get_type() {
+                Type::Function(fn_type) => fn_type.params.len() - usize::from(fn_type.is_variadic),
+                _ => 0,
+            },
+        }
+    }
+
+    pub fn property(function: &RuntimeValue, property: &str) -> Option<RuntimeValue> {
+        match property {
+            "name" => Some(RuntimeValue::Str(StrValue::new(Self::name(function)))),
+            "arity" => Some(RuntimeValue::Int(IntValue::new(
+                Self::arity(function) as i64
+            ))),
+            _ => None,
+        }
+    }
+
+    pub fn bind(function: &RuntimeValue, args: Vec<RuntimeValue>) -> RuntimeValue {
+        let mut args = args.into_iter();
+        let this = args.next().unwrap_or(RuntimeValue::Null(NullValue::new()));
+        let partial: Vec<RuntimeValue> = args.collect();
+
+        let (is_async, fn_type) = match function {
+            RuntimeValue::Function(function) => (function.is_async, function.fn_type.clone()),
+            RuntimeValue::NativeAsyncFunction(function) => (true, function.fn_type.clone()),
+            other => (false, other.get_type()),
+        };
+        let fn_type = match fn_type {
+            Type::Function(fn_type) => Type::Function(Box::new(FnType {
+                params: fn_type.params.iter().skip(partial.len()).cloned().collect(),
+                param_names: fn_type
+                    .param_names
+                    .iter()
+                    .skip(partial.len())
+                    .cloned()
+                    .collect(),
+                ..*fn_type
+            })),
+            other => other,
+        };
+
+        let inner = match function {
+            RuntimeValue::Function(value) => value.bound.as_deref(),
+            _ => None,
+        };
+        let bound = match inner {
+            Some(inner) => BoundCall {
+                target: inner.target.clone(),
+                this: inner.this.clone(),
+                args: inner.args.iter().cloned().chain(partial).collect(),
+            },
+            None => BoundCall {
+                target: function.clone(),
+                this,
+                args: partial,
+            },
+        };
+
+        RuntimeValue::Function(Arc::new(
+            FunctionValue::new(Vec::new(), Vec::<Stmt>::new(), is_async, fn_type)
+                .with_name(format!("bound {}", Self::name(function)))
+                .with_bound(bound),
+        ))
+    }
+
+    pub fn invocation(
+        function: &RuntimeValue,
+        method: &str,
+        mut args: Vec<RuntimeValue>,
+        position: Position,
+        file: Option<String>,
+    ) -> Result<BoundCall, RaccoonError> {
+        let this = if method == "call" || args.len() > 1 {
+            (!args.is_empty()).then(|| args.remove(0))
+        } else {
+            None
+        };
+        let args = if method == "apply" {
+            match args.first() {
+                Some(RuntimeValue::Array(list)) => list.snapshot(),
+                Some(RuntimeValue::Tuple(tuple)) => tuple.elements.clone(),
+                None => Vec::new(),
+                Some(other) => {
+                    return Err(RaccoonError::type_error(
+                        format!(
+                            "apply() expects a list of arguments, got {}",
+                            other.get_name()
+                        ),
+                        position,
+                        file,
+                    ))
+                }
+            }
+        } else {
+            args
+        };
+        Ok(BoundCall {
+            target: function.clone(),
+            this: this.unwrap_or(RuntimeValue::Null(NullValue::new())),
+            args,
+        })
+    }
+}
+
 #[async_trait]
 impl TypeHandler for FunctionType {
     fn type_name(&self) -> &str {
@@ -16,44 +156,36 @@ impl TypeHandler for FunctionType {
         &self,
         value: &mut RuntimeValue,
         method: &str,
-        _args: Vec<RuntimeValue>,
+        args: Vec<RuntimeValue>,
         position: Position,
         file: Option<String>,
     ) -> Result<RuntimeValue, RaccoonError> {
-        match value {
-            RuntimeValue::Function(func) => match method {
-                "length" => Ok(RuntimeValue::Int(IntValue::new(
-                    func.parameters.len() as i64
-                ))),
-                "isAsync" => Ok(RuntimeValue::Bool(crate::runtime::BoolValue::new(
-                    func.is_async,
-                ))),
-                "toString" | "toStr" => Ok(RuntimeValue::Str(StrValue::new(format!(
-                    "[Function: {} params]",
-                    func.parameters.len()
-                )))),
-                _ => Err(RaccoonError::new(
-                    format!("Method '{}' not found on function", method),
-                    position,
-                    file,
-                )),
-            },
-            RuntimeValue::NativeFunction(_f) => match method {
-                "name" => Ok(RuntimeValue::Str(StrValue::new("[native]".to_string()))),
-                "length" => Ok(RuntimeValue::Int(IntValue::new(0))),
-                "toString" | "toStr" => Ok(RuntimeValue::Str(StrValue::new(
-                    "[Native Function]".to_string(),
-                ))),
-                _ => Err(RaccoonError::new(
-                    format!("Method '{}' not found on native function", method),
-                    position,
-                    file,
-                )),
-            },
-            _ => Err(RaccoonError::new(
+        if !Self::is_function(value) {
+            return Err(RaccoonError::new(
                 format!("Expected function, got {}", value.get_name()),
                 position,
                 file,
+            ));
+        }
+        match method {
+            "bind" => Ok(Self::bind(value, args)),
+            "isAsync" => {
+                let is_async = match value {
+                    RuntimeValue::Function(function) => function.is_async,
+                    RuntimeValue::NativeAsyncFunction(_) => true,
+                    RuntimeValue::Dynamic(dynamic) => matches!(
+                        dynamic.get_property("isAsync"),
+                        Some(RuntimeValue::Bool(flag)) if flag.value
+                    ),
+                    _ => false,
+                };
+                Ok(RuntimeValue::Bool(BoolValue::new(is_async)))
+            }
+            "toString" | "toStr" => Ok(RuntimeValue::Str(StrValue::new(value.to_string()))),
+            _ => Err(RaccoonError::new(
+                format!("Method '{}' not found on function", method),
+                position,
+                file,
             )),
         }
     }
@@ -73,7 +205,10 @@ impl TypeHandler for FunctionType {
     }
 
     fn has_instance_method(&self, method: &str) -> bool {
-        matches!(method, "length" | "name" | "isAsync" | "toString" | "toStr")
+        matches!(
+            method,
+            "bind" | "call" | "apply" | "isAsync" | "toString" | "toStr"
+        )
     }
 
     fn has_static_method(&self, _method: &str) -> bool {
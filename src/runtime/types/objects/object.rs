@@ -1,7 +1,7 @@
 use crate::ast::types::PrimitiveType;
 use crate::error::RaccoonError;
 use crate::runtime::types::TypeHandler;
-use crate::runtime::{ArrayValue, IntValue, RuntimeValue, StrValue};
+use crate::runtime::{ArrayValue, IntValue, RuntimeValue, StrValue, SymbolValue};
 use crate::tokens::Position;
 use async_trait::async_trait;
 
@@ -36,7 +36,10 @@ impl TypeHandler for ObjectType {
             "keys" => {
                 let keys: Vec<RuntimeValue> = obj
                     .properties
+                    .read()
+                    .unwrap()
                     .keys()
+                    .filter(|k| !SymbolValue::is_property_key(k))
                     .map(|k| RuntimeValue::Str(StrValue::new(k.clone())))
                     .collect();
                 Ok(RuntimeValue::Array(ArrayValue::new(
@@ -45,29 +48,26 @@ impl TypeHandler for ObjectType {
                 )))
             }
             "values" => {
-                let values: Vec<RuntimeValue> = obj.properties.values().cloned().collect();
-                Ok(RuntimeValue::Array(ArrayValue::new(
-                    values,
-                    PrimitiveType::any(),
-                )))
-            }
-            "entries" => {
-                let entries: Vec<RuntimeValue> = obj
+                let values: Vec<RuntimeValue> = obj
                     .properties
+                    .read()
+                    .unwrap()
                     .iter()
-                    .map(|(k, v)| {
-                        let pair = vec![RuntimeValue::Str(StrValue::new(k.clone())), v.clone()];
-                        RuntimeValue::Array(ArrayValue::new(pair, PrimitiveType::any()))
-                    })
+                    .filter(|(k, _)| !SymbolValue::is_property_key(k))
+                    .map(|(_, v)| v.clone())
                     .collect();
                 Ok(RuntimeValue::Array(ArrayValue::new(
-                    entries,
+                    values,
                     PrimitiveType::any(),
                 )))
             }
-            "size" | "length" => Ok(RuntimeValue::Int(
-                IntValue::new(obj.properties.len() as i64),
-            )),
+            "entries" => Ok(RuntimeValue::Array(ArrayValue::new(
+                obj.entry_pairs(),
+                PrimitiveType::any(),
+            ))),
+            "size" | "length" => Ok(RuntimeValue::Int(IntValue::new(
+                obj.properties.read().unwrap().len() as i64,
+            ))),
             "hasOwnProperty" => {
                 if _args.len() != 1 {
                     return Err(RaccoonError::new(
@@ -78,11 +78,15 @@ impl TypeHandler for ObjectType {
                 }
                 match &_args[0] {
                     RuntimeValue::Str(s) => {
-                        let has_prop = obj.properties.contains_key(&s.value);
+                        let has_prop = obj.contains(&s.value);
+                        Ok(RuntimeValue::Bool(crate::runtime::BoolValue::new(has_prop)))
+                    }
+                    RuntimeValue::Symbol(symbol) => {
+                        let has_prop = obj.contains(&symbol.property_key());
                         Ok(RuntimeValue::Bool(crate::runtime::BoolValue::new(has_prop)))
                     }
                     _ => Err(RaccoonError::new(
-                        "hasOwnProperty requires string argument".to_string(),
+                        "hasOwnProperty requires a string or symbol argument".to_string(),
                         position,
                         file,
                     )),
@@ -1,6 +1,13 @@
+use crate::ast::types::{
+    ArrayType, InterfaceType, IntersectionType, NullableType, Type, TypeKind, UnionType,
+};
 use crate::error::RaccoonError;
-use crate::runtime::{BoolValue, FloatValue, IntValue, RuntimeValue, StrValue};
+use crate::runtime::{
+    BoolValue, Environment, FloatValue, IntValue, RuntimeValue, StrValue,
+    TypeKind as RuntimeTypeKind,
+};
 use crate::tokens::Position;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WidenResult {
@@ -25,98 +32,306 @@ pub fn determine_widening(left_type: &RuntimeValue, right_type: &RuntimeValue) -
     }
 }
 
-pub fn try_cast(
+/// The names `as` and `is` can refer to besides builtin types.
+pub trait TypeScope {
+    fn superclass_of(&self, name: &str) -> Option<String>;
+
+    fn alias(&self, name: &str) -> Option<Type>;
+
+    fn interface(&self, name: &str) -> Option<Type>;
+}
+
+impl TypeScope for Environment {
+    fn superclass_of(&self, name: &str) -> Option<String> {
+        match self.get(name, (0, 0)).ok()? {
+            RuntimeValue::Type(type_object) => match &type_object.kind {
+                RuntimeTypeKind::Class { superclass, .. } => superclass.clone(),
+                _ => None,
+            },
+            RuntimeValue::Class(class) => class.declaration.superclass.clone(),
+            _ => None,
+        }
+    }
+
+    fn alias(&self, name: &str) -> Option<Type> {
+        match self.get(name, (0, 0)).ok()? {
+            RuntimeValue::Type(type_object) => match &type_object.kind {
+                RuntimeTypeKind::Alias { target, .. } => Some(*target.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn interface(&self, name: &str) -> Option<Type> {
+        match self.get(name, (0, 0)).ok()? {
+            RuntimeValue::Type(type_object) if type_object.is_interface() => {
+                Some(type_object.type_def.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+pub fn expand(target: &Type, scope: &dyn TypeScope) -> Type {
+    expand_aliases(target, scope, &mut Vec::new())
+}
+
+fn expand_aliases(target: &Type, scope: &dyn TypeScope, expanding: &mut Vec<String>) -> Type {
+    if let Type::TypeRef(reference) = target {
+        if expanding.contains(&reference.name) {
+            return target.clone();
+        }
+        let Some(aliased) = scope.alias(&reference.name) else {
+            return target.clone();
+        };
+        expanding.push(reference.name.clone());
+        let expanded = expand_aliases(&aliased, scope, expanding);
+        expanding.pop();
+        return expanded;
+    }
+
+    let mut expand = |inner: &Type| expand_aliases(inner, scope, expanding);
+    match target {
+        Type::Array(array) => Type::Array(Box::new(ArrayType {
+            element_type: expand(&array.element_type),
+        })),
+        Type::Nullable(nullable) => Type::Nullable(Box::new(NullableType {
+            inner_type: expand(&nullable.inner_type),
+        })),
+        Type::Union(union) => Type::Union(Box::new(UnionType::new(
+            union.types.iter().map(expand).collect(),
+        ))),
+        Type::Intersection(intersection) => Type::Intersection(Box::new(IntersectionType::new(
+            intersection.types.iter().map(expand).collect(),
+        ))),
+        Type::Readonly(readonly) => expand(&readonly.inner_type),
+        _ => target.clone(),
+    }
+}
+
+/// Whether `value` has type `target`, the check behind `x is T`.
+pub fn value_is(value: &RuntimeValue, target: &Type, scope: &dyn TypeScope) -> bool {
+    has_type(
+        value,
+        &expand_aliases(target, scope, &mut Vec::new()),
+        scope,
+    )
+}
+
+fn has_type(value: &RuntimeValue, target: &Type, scope: &dyn TypeScope) -> bool {
+    match target {
+        Type::Primitive(primitive) => primitive_is(value, &primitive.kind),
+        Type::Nullable(nullable) => {
+            matches!(value, RuntimeValue::Null(_)) || has_type(value, &nullable.inner_type, scope)
+        }
+        Type::Union(union) => union
+            .types
+            .iter()
+            .any(|member| has_type(value, member, scope)),
+        Type::Intersection(intersection) => intersection
+            .types
+            .iter()
+            .all(|member| has_type(value, member, scope)),
+        Type::Array(array) => match value {
+            RuntimeValue::Array(list) => list
+                .snapshot()
+                .iter()
+                .all(|element| has_type(element, &array.element_type, scope)),
+            _ => false,
+        },
+        Type::Tuple(_) => matches!(value, RuntimeValue::Tuple(_)),
+        Type::Map(_) => matches!(value, RuntimeValue::Map(_)),
+        Type::Future(_) => matches!(value, RuntimeValue::Future(_)),
+        Type::Function(_) => primitive_is(value, &TypeKind::Func),
+        Type::Object(_) => matches!(
+            value,
+            RuntimeValue::Object(_) | RuntimeValue::ClassInstance(_)
+        ),
+        Type::Class(class) => named_is(value, &class.name, scope),
+        Type::Enum(enum_type) => named_is(value, &enum_type.name, scope),
+        Type::TypeRef(reference) => {
+            named_is(value, &reference.name, scope)
+                || scope
+                    .interface(&reference.name)
+                    .is_some_and(|shape| has_type(value, &shape, scope))
+        }
+        Type::Interface(interface) => has_shape(value, interface, scope),
+        Type::Generic(generic) => has_type(value, &generic.base, scope),
+        _ => false,
+    }
+}
+
+fn primitive_is(value: &RuntimeValue, kind: &TypeKind) -> bool {
+    match kind {
+        TypeKind::Any | TypeKind::Unknown => true,
+        TypeKind::Int
+        | TypeKind::I8
+        | TypeKind::I16
+        | TypeKind::I32
+        | TypeKind::I64
+        | TypeKind::U8
+        | TypeKind::U16
+        | TypeKind::U32
+        | TypeKind::U64 => matches!(value, RuntimeValue::Int(_)),
+        TypeKind::BigInt => matches!(value, RuntimeValue::BigInt(_)),
+        TypeKind::Float | TypeKind::F32 | TypeKind::F64 => matches!(value, RuntimeValue::Float(_)),
+        TypeKind::Decimal => matches!(value, RuntimeValue::Decimal(_)),
+        TypeKind::Str => matches!(value, RuntimeValue::Str(_)),
+        TypeKind::Bool => matches!(value, RuntimeValue::Bool(_)),
+        TypeKind::Null | TypeKind::Void => matches!(value, RuntimeValue::Null(_)),
+        TypeKind::Symbol => matches!(value, RuntimeValue::Symbol(_)),
+        TypeKind::Func => matches!(
+            value,
+            RuntimeValue::Function(_)
+                | RuntimeValue::NativeFunction(_)
+                | RuntimeValue::NativeAsyncFunction(_)
+        ),
+        _ => false,
+    }
+}
+
+fn named_is(value: &RuntimeValue, name: &str, scope: &dyn TypeScope) -> bool {
+    match value {
+        RuntimeValue::ClassInstance(instance) => {
+            let mut current = Some(instance.class_name.clone());
+            let mut visited = Vec::new();
+            while let Some(class_name) = current {
+                if class_name == name {
+                    return true;
+                }
+                if visited.contains(&class_name) {
+                    return false;
+                }
+                current = scope.superclass_of(&class_name);
+                visited.push(class_name);
+            }
+            false
+        }
+        RuntimeValue::Enum(member) => member.enum_name == name,
+        RuntimeValue::Map(_) => name == "Map",
+        RuntimeValue::Future(_) => name == "Future",
+        _ => false,
+    }
+}
+
+fn has_shape(value: &RuntimeValue, interface: &InterfaceType, scope: &dyn TypeScope) -> bool {
+    let property = |name: &str| match value {
+        RuntimeValue::Object(object) => object.get(name),
+        RuntimeValue::ClassInstance(instance) => instance
+            .properties
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .or_else(|| {
+                instance
+                    .methods
+                    .get(name)
+                    .map(|method| RuntimeValue::Function(Arc::new(method.clone())))
+            }),
+        _ => None,
+    };
+    if !matches!(
+        value,
+        RuntimeValue::Object(_) | RuntimeValue::ClassInstance(_)
+    ) {
+        return false;
+    }
+    interface
+        .properties
+        .iter()
+        .all(|(name, declared)| match property(name) {
+            Some(found) => has_type(&found, &declared.property_type, scope),
+            None => declared.optional,
+        })
+}
+
+/// Converts `value` to `target`, the conversion behind `x as T`.
+pub fn cast(
     value: RuntimeValue,
-    target_type_name: &str,
+    target: &Type,
+    scope: &dyn TypeScope,
     position: Position,
     file: &Option<String>,
 ) -> Result<RuntimeValue, RaccoonError> {
-    match value {
-        RuntimeValue::Int(i) => match target_type_name {
-            "int" => Ok(RuntimeValue::Int(i)),
-            "float" => Ok(RuntimeValue::Float(FloatValue::new(i.value as f64))),
-            "str" => Ok(RuntimeValue::Str(StrValue::new(i.value.to_string()))),
-            "bool" => Ok(RuntimeValue::Bool(BoolValue::new(i.value != 0))),
-            _ => Err(RaccoonError::new(
-                format!("Cannot cast int to {}", target_type_name),
-                position,
-                file.clone(),
-            )),
-        },
-        RuntimeValue::Float(f) => match target_type_name {
-            "float" => Ok(RuntimeValue::Float(f)),
-            "int" => Ok(RuntimeValue::Int(IntValue::new(f.value as i64))),
-            "str" => Ok(RuntimeValue::Str(StrValue::new(f.value.to_string()))),
-            "bool" => Ok(RuntimeValue::Bool(BoolValue::new(f.value != 0.0))),
-            _ => Err(RaccoonError::new(
-                format!("Cannot cast float to {}", target_type_name),
-                position,
-                file.clone(),
-            )),
-        },
-        RuntimeValue::Str(s) => match target_type_name {
-            "str" => Ok(RuntimeValue::Str(s)),
-            "int" => s
-                .value
-                .parse::<i64>()
-                .map(|v| RuntimeValue::Int(IntValue::new(v)))
-                .map_err(|_| {
-                    RaccoonError::new(
-                        format!("Cannot cast '{}' to int", s.value),
-                        position,
-                        file.clone(),
-                    )
-                }),
-            "float" => s
-                .value
-                .parse::<f64>()
-                .map(|v| RuntimeValue::Float(FloatValue::new(v)))
-                .map_err(|_| {
-                    RaccoonError::new(
-                        format!("Cannot cast '{}' to float", s.value),
-                        position,
-                        file.clone(),
-                    )
-                }),
-            "bool" => Ok(RuntimeValue::Bool(BoolValue::new(!s.value.is_empty()))),
-            _ => Err(RaccoonError::new(
-                format!("Cannot cast str to {}", target_type_name),
-                position,
-                file.clone(),
-            )),
-        },
-        RuntimeValue::Bool(b) => match target_type_name {
-            "bool" => Ok(RuntimeValue::Bool(b)),
-            "int" => Ok(RuntimeValue::Int(IntValue::new(if b.value {
-                1
-            } else {
-                0
-            }))),
-            "float" => Ok(RuntimeValue::Float(FloatValue::new(if b.value {
-                1.0
-            } else {
-                0.0
-            }))),
-            "str" => Ok(RuntimeValue::Str(StrValue::new(b.value.to_string()))),
-            _ => Err(RaccoonError::new(
-                format!("Cannot cast bool to {}", target_type_name),
-                position,
-                file.clone(),
-            )),
-        },
-        RuntimeValue::Null(_) => match target_type_name {
-            "str" => Ok(RuntimeValue::Str(StrValue::new("null".to_string()))),
-            _ => Err(RaccoonError::new(
-                format!("Cannot cast null to {}", target_type_name),
-                position,
-                file.clone(),
-            )),
-        },
-        v => Err(RaccoonError::new(
-            format!("Cannot cast {} to {}", v.get_name(), target_type_name),
+    let expanded = expand_aliases(target, scope, &mut Vec::new());
+    convert(&value, &expanded, scope).ok_or_else(|| {
+        let shown = match &value {
+            RuntimeValue::Str(s) => format!(" '{}'", s.value),
+            RuntimeValue::Int(_) | RuntimeValue::Float(_) | RuntimeValue::Bool(_) => {
+                format!(" {}", value.to_string())
+            }
+            _ => String::new(),
+        };
+        RaccoonError::type_error(
+            format!("Cannot cast {}{} to {}", type_name(&value), shown, target),
             position,
             file.clone(),
-        )),
+        )
+    })
+}
+
+fn convert(value: &RuntimeValue, target: &Type, scope: &dyn TypeScope) -> Option<RuntimeValue> {
+    if has_type(value, target, scope) {
+        return Some(value.clone());
+    }
+    match target {
+        Type::Primitive(primitive) => convert_primitive(value, &primitive.kind),
+        Type::Nullable(nullable) => convert(value, &nullable.inner_type, scope),
+        Type::Union(union) => union
+            .types
+            .iter()
+            .find_map(|member| convert(value, member, scope)),
+        _ => None,
+    }
+}
+
+fn convert_primitive(value: &RuntimeValue, kind: &TypeKind) -> Option<RuntimeValue> {
+    let converted = match (value, kind) {
+        (RuntimeValue::Int(i), TypeKind::Float) => {
+            RuntimeValue::Float(FloatValue::new(i.value as f64))
+        }
+        (RuntimeValue::Float(f), TypeKind::Int) if f.value.is_finite() => {
+            RuntimeValue::Int(IntValue::new(f.value.trunc() as i64))
+        }
+        (RuntimeValue::Int(i), TypeKind::Bool) => RuntimeValue::Bool(BoolValue::new(i.value != 0)),
+        (RuntimeValue::Float(f), TypeKind::Bool) => {
+            RuntimeValue::Bool(BoolValue::new(f.value != 0.0))
+        }
+        (RuntimeValue::Bool(b), TypeKind::Int) => {
+            RuntimeValue::Int(IntValue::new(i64::from(b.value)))
+        }
+        (RuntimeValue::Bool(b), TypeKind::Float) => {
+            RuntimeValue::Float(FloatValue::new(if b.value { 1.0 } else { 0.0 }))
+        }
+        (RuntimeValue::Int(_) | RuntimeValue::Float(_) | RuntimeValue::Bool(_), TypeKind::Str) => {
+            RuntimeValue::Str(StrValue::new(value.to_string()))
+        }
+        (RuntimeValue::Str(s), TypeKind::Int) => {
+            RuntimeValue::Int(IntValue::new(s.value.trim().parse::<i64>().ok()?))
+        }
+        (RuntimeValue::Str(s), TypeKind::Float) => {
+            RuntimeValue::Float(FloatValue::new(s.value.trim().parse::<f64>().ok()?))
+        }
+        (RuntimeValue::Str(s), TypeKind::Bool) => match s.value.trim() {
+            "true" => RuntimeValue::Bool(BoolValue::new(true)),
+            "false" => RuntimeValue::Bool(BoolValue::new(false)),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(converted)
+}
+
+pub fn type_name(value: &RuntimeValue) -> String {
+    match value {
+        RuntimeValue::Int(_) => "int".to_string(),
+        RuntimeValue::Float(_) => "float".to_string(),
+        RuntimeValue::Str(_) => "str".to_string(),
+        RuntimeValue::Bool(_) => "bool".to_string(),
+        RuntimeValue::Null(_) => "null".to_string(),
+        other => other.get_name(),
     }
 }
 
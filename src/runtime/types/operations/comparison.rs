@@ -1,6 +1,7 @@
 use crate::error::RaccoonError;
-use crate::runtime::{BoolValue, RuntimeValue};
+use crate::runtime::{BoolValue, MapKey, RuntimeValue};
 use crate::tokens::Position;
+use std::cmp::Ordering;
 
 pub fn equal(
     left: RuntimeValue,
@@ -8,16 +9,7 @@ pub fn equal(
     _position: Position,
     _file: &Option<String>,
 ) -> Result<RuntimeValue, RaccoonError> {
-    let result = match (&left, &right) {
-        (RuntimeValue::Int(a), RuntimeValue::Int(b)) => a.value == b.value,
-        (RuntimeValue::Int(a), RuntimeValue::Float(b)) => a.value as f64 == b.value,
-        (RuntimeValue::Float(a), RuntimeValue::Int(b)) => a.value == b.value as f64,
-        (RuntimeValue::Float(a), RuntimeValue::Float(b)) => a.value == b.value,
-        (RuntimeValue::Str(a), RuntimeValue::Str(b)) => a.value == b.value,
-        (RuntimeValue::Bool(a), RuntimeValue::Bool(b)) => a.value == b.value,
-        (RuntimeValue::Null(_), RuntimeValue::Null(_)) => true,
-        _ => false,
-    };
+    let result = left.equals(&right);
     Ok(RuntimeValue::Bool(BoolValue::new(result)))
 }
 
@@ -34,21 +26,45 @@ pub fn not_equal(
     }
 }
 
-pub fn less_than(
+pub fn contains(
     left: RuntimeValue,
     right: RuntimeValue,
     position: Position,
     file: &Option<String>,
 ) -> Result<RuntimeValue, RaccoonError> {
     let result = match (&left, &right) {
-        (RuntimeValue::Int(a), RuntimeValue::Int(b)) => a.value < b.value,
-        (RuntimeValue::Int(a), RuntimeValue::Float(b)) => (a.value as f64) < b.value,
-        (RuntimeValue::Float(a), RuntimeValue::Int(b)) => a.value < b.value as f64,
-        (RuntimeValue::Float(a), RuntimeValue::Float(b)) => a.value < b.value,
-        (RuntimeValue::Str(a), RuntimeValue::Str(b)) => a.value < b.value,
+        (_, RuntimeValue::Array(list)) => list
+            .elements
+            .read()
+            .unwrap()
+            .iter()
+            .any(|item| item.equals(&left)),
+        (RuntimeValue::Int(value), RuntimeValue::Range(range)) => range.contains(value.value),
+        (_, RuntimeValue::Map(map)) => MapKey::from_value(&left)
+            .is_ok_and(|key| map.entries.read().unwrap().contains_key(&key)),
+        (RuntimeValue::Str(key), RuntimeValue::Object(obj)) => obj.contains(&key.value),
+        (RuntimeValue::Symbol(symbol), RuntimeValue::Object(obj)) => {
+            obj.contains(&symbol.property_key())
+        }
+        (RuntimeValue::Symbol(symbol), RuntimeValue::ClassInstance(inst)) => inst
+            .properties
+            .read()
+            .unwrap()
+            .contains_key(&symbol.property_key()),
+        (RuntimeValue::Str(key), RuntimeValue::ClassInstance(inst)) => {
+            inst.properties.read().unwrap().contains_key(&key.value)
+                || inst.methods.contains_key(&key.value)
+        }
+        (RuntimeValue::Str(needle), RuntimeValue::Str(haystack)) => {
+            haystack.value.contains(&needle.value)
+        }
         _ => {
             return Err(RaccoonError::new(
-                "Invalid operands for less than comparison".to_string(),
+                format!(
+                    "Invalid operands for 'in': cannot search {} in {}",
+                    left.get_name(),
+                    right.get_name()
+                ),
                 position,
                 file.clone(),
             ))
@@ -57,21 +73,52 @@ pub fn less_than(
     Ok(RuntimeValue::Bool(BoolValue::new(result)))
 }
 
-pub fn less_or_equal(
+/// The order of two values, or `None` when they have none.
+pub fn compare(left: &RuntimeValue, right: &RuntimeValue) -> Option<Ordering> {
+    match (left, right) {
+        (RuntimeValue::Int(a), RuntimeValue::Int(b)) => Some(a.value.cmp(&b.value)),
+        (RuntimeValue::Int(a), RuntimeValue::Float(b)) => (a.value as f64).partial_cmp(&b.value),
+        (RuntimeValue::Float(a), RuntimeValue::Int(b)) => a.value.partial_cmp(&(b.value as f64)),
+        (RuntimeValue::Float(a), RuntimeValue::Float(b)) => a.value.partial_cmp(&b.value),
+        (RuntimeValue::Str(a), RuntimeValue::Str(b)) => Some(a.value.cmp(&b.value)),
+        (RuntimeValue::Array(a), RuntimeValue::Array(b)) => {
+            lexicographic(&a.elements.read().unwrap(), &b.elements.read().unwrap())
+        }
+        (RuntimeValue::Tuple(a), RuntimeValue::Tuple(b)) => lexicographic(&a.elements, &b.elements),
+        _ => None,
+    }
+}
+
+fn lexicographic(left: &[RuntimeValue], right: &[RuntimeValue]) -> Option<Ordering> {
+    for (a, b) in left.iter().zip(right) {
+        match compare(a, b)? {
+            Ordering::Equal => {}
+            order => return Some(order),
+        }
+    }
+    Some(left.len().cmp(&right.len()))
+}
+
+fn numeric(left: &RuntimeValue, right: &RuntimeValue) -> bool {
+    let number =
+        |value: &RuntimeValue| matches!(value, RuntimeValue::Int(_) | RuntimeValue::Float(_));
+    number(left) && number(right)
+}
+
+fn ordered(
     left: RuntimeValue,
     right: RuntimeValue,
+    holds: fn(Ordering) -> bool,
+    comparison: &str,
     position: Position,
     file: &Option<String>,
 ) -> Result<RuntimeValue, RaccoonError> {
-    let result = match (&left, &right) {
-        (RuntimeValue::Int(a), RuntimeValue::Int(b)) => a.value <= b.value,
-        (RuntimeValue::Int(a), RuntimeValue::Float(b)) => (a.value as f64) <= b.value,
-        (RuntimeValue::Float(a), RuntimeValue::Int(b)) => a.value <= b.value as f64,
-        (RuntimeValue::Float(a), RuntimeValue::Float(b)) => a.value <= b.value,
-        (RuntimeValue::Str(a), RuntimeValue::Str(b)) => a.value <= b.value,
-        _ => {
+    let result = match compare(&left, &right) {
+        Some(order) => holds(order),
+        None if numeric(&left, &right) => false,
+        None => {
             return Err(RaccoonError::new(
-                "Invalid operands for less or equal comparison".to_string(),
+                format!("Invalid operands for {} comparison", comparison),
                 position,
                 file.clone(),
             ))
@@ -80,27 +127,38 @@ pub fn less_or_equal(
     Ok(RuntimeValue::Bool(BoolValue::new(result)))
 }
 
+pub fn less_than(
+    left: RuntimeValue,
+    right: RuntimeValue,
+    position: Position,
+    file: &Option<String>,
+) -> Result<RuntimeValue, RaccoonError> {
+    ordered(left, right, Ordering::is_lt, "less than", position, file)
+}
+
+pub fn less_or_equal(
+    left: RuntimeValue,
+    right: RuntimeValue,
+    position: Position,
+    file: &Option<String>,
+) -> Result<RuntimeValue, RaccoonError> {
+    ordered(
+        left,
+        right,
+        Ordering::is_le,
+        "less or equal",
+        position,
+        file,
+    )
+}
+
 pub fn greater_than(
     left: RuntimeValue,
     right: RuntimeValue,
     position: Position,
     file: &Option<String>,
 ) -> Result<RuntimeValue, RaccoonError> {
-    let result = match (&left, &right) {
-        (RuntimeValue::Int(a), RuntimeValue::Int(b)) => a.value > b.value,
-        (RuntimeValue::Int(a), RuntimeValue::Float(b)) => (a.value as f64) > b.value,
-        (RuntimeValue::Float(a), RuntimeValue::Int(b)) => a.value > b.value as f64,
-        (RuntimeValue::Float(a), RuntimeValue::Float(b)) => a.value > b.value,
-        (RuntimeValue::Str(a), RuntimeValue::Str(b)) => a.value > b.value,
-        _ => {
-            return Err(RaccoonError::new(
-                "Invalid operands for greater than comparison".to_string(),
-                position,
-                file.clone(),
-            ))
-        }
-    };
-    Ok(RuntimeValue::Bool(BoolValue::new(result)))
+    ordered(left, right, Ordering::is_gt, "greater than", position, file)
 }
 
 pub fn greater_or_equal(
@@ -109,19 +167,12 @@ pub fn greater_or_equal(
     position: Position,
     file: &Option<String>,
 ) -> Result<RuntimeValue, RaccoonError> {
-    let result = match (&left, &right) {
-        (RuntimeValue::Int(a), RuntimeValue::Int(b)) => a.value >= b.value,
-        (RuntimeValue::Int(a), RuntimeValue::Float(b)) => (a.value as f64) >= b.value,
-        (RuntimeValue::Float(a), RuntimeValue::Int(b)) => a.value >= b.value as f64,
-        (RuntimeValue::Float(a), RuntimeValue::Float(b)) => a.value >= b.value,
-        (RuntimeValue::Str(a), RuntimeValue::Str(b)) => a.value >= b.value,
-        _ => {
-            return Err(RaccoonError::new(
-                "Invalid operands for greater or equal comparison".to_string(),
-                position,
-                file.clone(),
-            ))
-        }
-    };
-    Ok(RuntimeValue::Bool(BoolValue::new(result)))
+    ordered(
+        left,
+        right,
+        Ordering::is_ge,
+        "greater or equal",
+        position,
+        file,
+    )
 }
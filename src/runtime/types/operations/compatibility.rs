@@ -45,7 +45,10 @@ pub fn get_type_name(value: &RuntimeValue) -> &'static str {
         RuntimeValue::Str(_) => "str",
         RuntimeValue::Bool(_) => "bool",
         RuntimeValue::Null(_) => "null",
+        RuntimeValue::Symbol(_) => "symbol",
         RuntimeValue::Array(_) => "array",
+        RuntimeValue::Tuple(_) => "tuple",
+        RuntimeValue::Range(_) => "range",
         RuntimeValue::Map(_) => "map",
         RuntimeValue::Object(_) => "object",
         RuntimeValue::Class(_) => "class",
@@ -52,13 +52,18 @@ pub fn to_string(value: &RuntimeValue) -> String {
         RuntimeValue::Decimal(d) => d.value.to_string(),
         RuntimeValue::Bool(b) => b.value.to_string(),
         RuntimeValue::Null(_) => "null".to_string(),
+        RuntimeValue::Symbol(s) => s.to_string(),
         RuntimeValue::Array(l) => {
-            let elements: Vec<String> = l.elements.iter().map(to_string).collect();
+            let elements: Vec<String> = l.elements.read().unwrap().iter().map(to_string).collect();
             format!("[{}]", elements.join(", "))
         }
+        RuntimeValue::Tuple(t) => t.to_string(),
+        RuntimeValue::Range(r) => r.to_string(),
         RuntimeValue::Map(m) => {
             let entries: Vec<String> = m
                 .entries
+                .read()
+                .unwrap()
                 .iter()
                 .map(|(k, v)| format!("{}: {}", k, to_string(v)))
                 .collect();
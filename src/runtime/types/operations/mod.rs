@@ -5,6 +5,7 @@ pub mod comparison;
 pub mod compatibility;
 pub mod conversion;
 pub mod logical;
+pub mod overloading;
 pub mod type_narrowing;
 
 use crate::error::RaccoonError;
@@ -42,6 +43,7 @@ pub async fn apply_binary_operation(
         BinaryOperator::LessEqual => comparison::less_or_equal(left, right, position, file),
         BinaryOperator::GreaterThan => comparison::greater_than(left, right, position, file),
         BinaryOperator::GreaterEqual => comparison::greater_or_equal(left, right, position, file),
+        BinaryOperator::In => comparison::contains(left, right, position, file),
 
         BinaryOperator::And => logical::and(left, right, position, file),
         BinaryOperator::Or => logical::or(left, right, position, file),
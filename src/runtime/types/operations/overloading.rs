@@ -0,0 +1,127 @@
+use super::casting::{self, TypeScope};
+use crate::ast::nodes::FnParam;
+use crate::ast::types::{Type, TypeKind};
+use crate::runtime::RuntimeValue;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    pub params: Vec<Type>,
+    pub required: usize,
+    pub variadic: bool,
+}
+
+impl Signature {
+    pub fn of(parameters: &[FnParam]) -> Self {
+        Self {
+            params: parameters.iter().map(|p| p.param_type.clone()).collect(),
+            required: parameters
+                .iter()
+                .take_while(|p| !p.is_rest && !p.is_optional && p.default_value.is_none())
+                .count(),
+            variadic: parameters.last().is_some_and(|p| p.is_rest),
+        }
+    }
+
+    pub fn same_params(&self, other: &Signature) -> bool {
+        self.params == other.params && self.variadic == other.variadic
+    }
+
+    pub fn score(&self, args: &[RuntimeValue], named: usize, scope: &dyn TypeScope) -> Option<u32> {
+        if args.len() + named < self.required || (!self.variadic && args.len() > self.params.len())
+        {
+            return None;
+        }
+        let mut total = 0;
+        for (index, arg) in args.iter().enumerate() {
+            let param = if self.variadic && index + 1 >= self.params.len() {
+                match self.params.last()? {
+                    Type::Array(array) => &array.element_type,
+                    other => other,
+                }
+            } else {
+                &self.params[index]
+            };
+            total += arg_score(arg, param, scope)?;
+        }
+        Some(total)
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .enumerate()
+            .map(|(index, param)| {
+                if self.variadic && index + 1 == self.params.len() {
+                    format!("...{}", param)
+                } else {
+                    param.to_string()
+                }
+            })
+            .collect();
+        write!(f, "({})", params.join(", "))
+    }
+}
+
+fn arg_score(value: &RuntimeValue, param: &Type, scope: &dyn TypeScope) -> Option<u32> {
+    let param = casting::expand(param, scope);
+    if let Type::Primitive(primitive) = &param {
+        match primitive.kind {
+            TypeKind::Any | TypeKind::Unknown => return Some(0),
+            TypeKind::Float | TypeKind::F32 | TypeKind::F64
+                if matches!(value, RuntimeValue::Int(_)) =>
+            {
+                return Some(1)
+            }
+            _ => {}
+        }
+    }
+    if !casting::value_is(value, &param, scope) {
+        return None;
+    }
+    let exact_class = |name: &str| match value {
+        RuntimeValue::ClassInstance(instance) => instance.class_name == name,
+        _ => true,
+    };
+    match &param {
+        Type::Union(_) | Type::Nullable(_) => Some(1),
+        Type::Class(class) if !exact_class(&class.name) => Some(2),
+        Type::TypeRef(reference) if !exact_class(&reference.name) => Some(2),
+        _ => Some(3),
+    }
+}
+
+pub fn best<'a>(
+    signatures: impl IntoIterator<Item = &'a Signature>,
+    args: &[RuntimeValue],
+    named: usize,
+    scope: &dyn TypeScope,
+) -> Option<usize> {
+    let mut best: Option<(usize, u32)> = None;
+    for (index, signature) in signatures.into_iter().enumerate() {
+        if let Some(score) = signature.score(args, named, scope) {
+            if best.is_none_or(|(_, top)| score > top) {
+                best = Some((index, score));
+            }
+        }
+    }
+    best.map(|(index, _)| index)
+}
+
+pub fn no_match_message<'a>(
+    name: &str,
+    signatures: impl IntoIterator<Item = &'a Signature>,
+    args: &[RuntimeValue],
+) -> String {
+    let given: Vec<String> = args.iter().map(casting::type_name).collect();
+    let declared: Vec<String> = signatures.into_iter().map(|s| s.to_string()).collect();
+    format!(
+        "No overload of '{}' accepts ({}); declared: {}",
+        name,
+        given.join(", "),
+        declared.join(", ")
+    )
+}
@@ -8,6 +8,7 @@ pub enum NarrowedType {
     Bool,
     Null,
     Array,
+    Tuple,
     Map,
     Object,
     Function,
@@ -23,7 +24,10 @@ pub fn narrow_type(value: &RuntimeValue) -> NarrowedType {
         RuntimeValue::Str(_) => NarrowedType::Str,
         RuntimeValue::Bool(_) => NarrowedType::Bool,
         RuntimeValue::Null(_) => NarrowedType::Null,
+        RuntimeValue::Symbol(_) => NarrowedType::Unknown,
         RuntimeValue::Array(_) => NarrowedType::Array,
+        RuntimeValue::Tuple(_) => NarrowedType::Tuple,
+        RuntimeValue::Range(_) => NarrowedType::Array,
         RuntimeValue::Map(_) => NarrowedType::Map,
         RuntimeValue::Object(_) => NarrowedType::Object,
         RuntimeValue::Class(_) => NarrowedType::Object,
@@ -60,8 +64,12 @@ pub fn typeof_check(value: &RuntimeValue, type_name: &str) -> bool {
                 | RuntimeValue::ClassInstance(_)
                 | RuntimeValue::Map(_)
                 | RuntimeValue::Array(_)
+                | RuntimeValue::Tuple(_)
+                | RuntimeValue::Range(_)
         ),
         "array" => matches!(value, RuntimeValue::Array(_)),
+        "tuple" => matches!(value, RuntimeValue::Tuple(_)),
+        "range" => matches!(value, RuntimeValue::Range(_)),
         "map" | "dict" => matches!(value, RuntimeValue::Map(_)),
         "function" => matches!(
             value,
@@ -1,5 +1,6 @@
 use crate::error::RaccoonError;
 use crate::runtime::types::helpers::*;
+use crate::runtime::types::primitives::number_format;
 use crate::runtime::types::TypeHandler;
 use crate::runtime::{
     BoolValue, DecimalValue, FloatValue, IntValue, NullValue, RuntimeValue, StrValue,
@@ -30,6 +31,27 @@ impl TypeHandler for Float64Type {
                 require_args(&args, 0, method, position, file)?;
                 Ok(RuntimeValue::Str(StrValue::new(num.to_string())))
             }
+            "toString" => {
+                require_args_range(&args, 0, 1, method, position, file.clone())?;
+                let radix = number_format::radix_arg(&args, 0, position, file)?;
+                Ok(RuntimeValue::Str(StrValue::new(
+                    number_format::float_to_radix(num, radix),
+                )))
+            }
+            "toFixed" => {
+                require_args_range(&args, 0, 1, method, position, file.clone())?;
+                let digits = number_format::digits_arg(&args, 0, position, file)?.unwrap_or(0);
+                Ok(RuntimeValue::Str(StrValue::new(number_format::to_fixed(
+                    num, digits,
+                ))))
+            }
+            "toExponential" => {
+                require_args_range(&args, 0, 1, method, position, file.clone())?;
+                let digits = number_format::digits_arg(&args, 0, position, file)?;
+                Ok(RuntimeValue::Str(StrValue::new(
+                    number_format::to_exponential(num, digits),
+                )))
+            }
             "toInt" => {
                 require_args(&args, 0, method, position, file)?;
                 Ok(RuntimeValue::Int(IntValue::new(num as i64)))
@@ -211,19 +233,15 @@ impl TypeHandler for Float64Type {
             "parse" => {
                 require_args(&args, 1, method, position, file.clone())?;
                 let s = extract_str(&args[0], "value", position, file.clone())?;
-                match s.trim().parse::<f64>() {
+                match number_format::parse_float(s) {
                     Ok(num) => Ok(RuntimeValue::Float(FloatValue::new(num))),
-                    Err(_) => Err(RaccoonError::new(
-                        format!("Failed to parse '{}' as float", s),
-                        position,
-                        file,
-                    )),
+                    Err(message) => Err(RaccoonError::new(message, position, file)),
                 }
             }
             "tryParse" => {
                 require_args(&args, 1, method, position, file.clone())?;
                 let s = extract_str(&args[0], "value", position, file)?;
-                match s.trim().parse::<f64>() {
+                match number_format::parse_float(s) {
                     Ok(num) => Ok(RuntimeValue::Float(FloatValue::new(num))),
                     Err(_) => Ok(RuntimeValue::Null(NullValue::new())),
                 }
@@ -282,6 +300,9 @@ impl TypeHandler for Float64Type {
         matches!(
             method,
             "toStr"
+                | "toString"
+                | "toFixed"
+                | "toExponential"
                 | "toInt"
                 | "toI8"
                 | "toI16"
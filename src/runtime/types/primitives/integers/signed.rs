@@ -1,5 +1,6 @@
 use crate::error::RaccoonError;
 use crate::runtime::types::helpers::*;
+use crate::runtime::types::primitives::number_format;
 use crate::runtime::types::TypeHandler;
 use crate::runtime::{
     BoolValue, DecimalValue, FloatValue, IntValue, NullValue, RuntimeValue, StrValue,
@@ -30,6 +31,27 @@ impl TypeHandler for IntType {
                 require_args(&args, 0, method, position, file)?;
                 Ok(RuntimeValue::Str(StrValue::new(num.to_string())))
             }
+            "toString" => {
+                require_args_range(&args, 0, 1, method, position, file.clone())?;
+                let radix = number_format::radix_arg(&args, 0, position, file)?;
+                Ok(RuntimeValue::Str(StrValue::new(
+                    number_format::int_to_radix(num, radix),
+                )))
+            }
+            "toFixed" => {
+                require_args_range(&args, 0, 1, method, position, file.clone())?;
+                let digits = number_format::digits_arg(&args, 0, position, file)?.unwrap_or(0);
+                Ok(RuntimeValue::Str(StrValue::new(number_format::to_fixed(
+                    num as f64, digits,
+                ))))
+            }
+            "toExponential" => {
+                require_args_range(&args, 0, 1, method, position, file.clone())?;
+                let digits = number_format::digits_arg(&args, 0, position, file)?;
+                Ok(RuntimeValue::Str(StrValue::new(
+                    number_format::to_exponential(num as f64, digits),
+                )))
+            }
             "toInt" => {
                 require_args(&args, 0, method, position, file)?;
                 Ok(RuntimeValue::Int(IntValue::new(num)))
@@ -153,21 +175,19 @@ impl TypeHandler for IntType {
     ) -> Result<RuntimeValue, RaccoonError> {
         match method {
             "parse" => {
-                require_args(&args, 1, method, position, file.clone())?;
+                require_args_range(&args, 1, 2, method, position, file.clone())?;
                 let s = extract_str(&args[0], "value", position, file.clone())?;
-                match s.trim().parse::<i64>() {
+                let radix = number_format::radix_arg(&args, 1, position, file.clone())?;
+                match number_format::parse_int(s, radix) {
                     Ok(num) => Ok(RuntimeValue::Int(IntValue::new(num))),
-                    Err(_) => Err(RaccoonError::new(
-                        format!("Failed to parse '{}' as int", s),
-                        position,
-                        file,
-                    )),
+                    Err(message) => Err(RaccoonError::new(message, position, file)),
                 }
             }
             "tryParse" => {
-                require_args(&args, 1, method, position, file.clone())?;
-                let s = extract_str(&args[0], "value", position, file)?;
-                match s.trim().parse::<i64>() {
+                require_args_range(&args, 1, 2, method, position, file.clone())?;
+                let s = extract_str(&args[0], "value", position, file.clone())?;
+                let radix = number_format::radix_arg(&args, 1, position, file)?;
+                match number_format::parse_int(s, radix) {
                     Ok(num) => Ok(RuntimeValue::Int(IntValue::new(num))),
                     Err(_) => Ok(RuntimeValue::Null(NullValue::new())),
                 }
@@ -206,6 +226,9 @@ impl TypeHandler for IntType {
         matches!(
             method,
             "toStr"
+                | "toString"
+                | "toFixed"
+                | "toExponential"
                 | "toInt"
                 | "toI8"
                 | "toI16"
@@ -3,6 +3,7 @@ pub mod char;
 pub mod floats;
 pub mod integers;
 pub mod null;
+pub mod number_format;
 pub mod numeric_trait;
 pub mod string;
 pub mod unit;
@@ -0,0 +1,164 @@
+use crate::error::RaccoonError;
+use crate::runtime::types::helpers::extract_int;
+use crate::runtime::RuntimeValue;
+use crate::tokens::Position;
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const MAX_FRACTION_DIGITS: usize = 52;
+
+/// The radix argument at `index`, defaulting to 10; only 2 through 36 are accepted.
+pub fn radix_arg(
+    args: &[RuntimeValue],
+    index: usize,
+    position: Position,
+    file: Option<String>,
+) -> Result<u32, RaccoonError> {
+    let Some(radix) = args.get(index) else {
+        return Ok(10);
+    };
+    let radix = extract_int(radix, "radix", position, file.clone())?;
+    if !(2..=36).contains(&radix) {
+        return Err(RaccoonError::new(
+            format!("Radix must be between 2 and 36, got {}", radix),
+            position,
+            file,
+        ));
+    }
+    Ok(radix as u32)
+}
+
+/// The digit count argument at `index` for `toFixed`/`toExponential`, limited to 0 through 100.
+pub fn digits_arg(
+    args: &[RuntimeValue],
+    index: usize,
+    position: Position,
+    file: Option<String>,
+) -> Result<Option<usize>, RaccoonError> {
+    let Some(digits) = args.get(index) else {
+        return Ok(None);
+    };
+    let digits = extract_int(digits, "digits", position, file.clone())?;
+    if !(0..=100).contains(&digits) {
+        return Err(RaccoonError::new(
+            format!("Digit count must be between 0 and 100, got {}", digits),
+            position,
+            file,
+        ));
+    }
+    Ok(Some(digits as usize))
+}
+
+/// Parses an integer in `radix`, allowing a sign and the matching prefix.
+pub fn parse_int(text: &str, radix: u32) -> Result<i64, String> {
+    let trimmed = text.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let prefix = match radix {
+        16 => Some("0x"),
+        8 => Some("0o"),
+        2 => Some("0b"),
+        _ => None,
+    };
+    let digits = prefix
+        .and_then(|prefix| {
+            digits
+                .strip_prefix(prefix)
+                .or_else(|| digits.strip_prefix(&prefix.to_uppercase()))
+        })
+        .unwrap_or(digits);
+
+    let signed = if negative {
+        format!("-{}", digits)
+    } else {
+        digits.to_string()
+    };
+    if digits.starts_with(['+', '-']) {
+        return Err(parse_error(text, radix));
+    }
+    i64::from_str_radix(&signed, radix).map_err(|_| parse_error(text, radix))
+}
+
+pub fn parse_float(text: &str) -> Result<f64, String> {
+    text.trim()
+        .parse::<f64>()
+        .map_err(|_| format!("Failed to parse '{}' as float", text))
+}
+
+fn parse_error(text: &str, radix: u32) -> String {
+    match radix {
+        10 => format!("Failed to parse '{}' as int", text),
+        _ => format!("Failed to parse '{}' as int in radix {}", text, radix),
+    }
+}
+
+fn push_digits(out: &mut String, mut value: u128, radix: u32) {
+    let mut digits = Vec::new();
+    loop {
+        digits.push(DIGITS[(value % radix as u128) as usize] as char);
+        value /= radix as u128;
+        if value == 0 {
+            break;
+        }
+    }
+    out.extend(digits.iter().rev());
+}
+
+pub fn int_to_radix(value: i64, radix: u32) -> String {
+    let mut out = String::new();
+    if value < 0 {
+        out.push('-');
+    }
+    push_digits(&mut out, value.unsigned_abs() as u128, radix);
+    out
+}
+
+/// Same digits in every locale: `.` as the decimal point and no grouping.
+pub fn float_to_radix(value: f64, radix: u32) -> String {
+    if !value.is_finite() || radix == 10 {
+        return crate::runtime::FloatValue::new(value).to_string();
+    }
+
+    let mut out = String::new();
+    if value < 0.0 {
+        out.push('-');
+    }
+    let magnitude = value.abs();
+    push_digits(&mut out, magnitude.trunc() as u128, radix);
+
+    let mut fraction = magnitude.fract();
+    if fraction > 0.0 {
+        out.push('.');
+        for _ in 0..MAX_FRACTION_DIGITS {
+            fraction *= radix as f64;
+            out.push(DIGITS[fraction.trunc() as usize] as char);
+            fraction = fraction.fract();
+            if fraction == 0.0 {
+                break;
+            }
+        }
+    }
+    out
+}
+
+pub fn to_fixed(value: f64, digits: usize) -> String {
+    format!("{:.*}", digits, value)
+}
+
+/// Scientific notation with a signed exponent, e.g. `1.25e+3`.
+pub fn to_exponential(value: f64, digits: Option<usize>) -> String {
+    if !value.is_finite() {
+        return crate::runtime::FloatValue::new(value).to_string();
+    }
+    let formatted = match digits {
+        Some(digits) => format!("{:.*e}", digits, value),
+        None => format!("{:e}", value),
+    };
+    match formatted.split_once('e') {
+        Some((mantissa, exponent)) if !exponent.starts_with('-') => {
+            format!("{}e+{}", mantissa, exponent)
+        }
+        _ => formatted,
+    }
+}
@@ -178,7 +178,7 @@ impl TypeHandler for StrType {
                 let index = extract_int(&args[0], "index", position, file.clone())? as usize;
                 match s.chars().nth(index) {
                     Some(ch) => Ok(RuntimeValue::Int(IntValue::new(ch as i64))),
-                    None => Err(RaccoonError::new(
+                    None => Err(RaccoonError::index_out_of_range_error(
                         format!("charCodeAt: index {} out of bounds", index),
                         position,
                         file,
@@ -310,6 +310,8 @@ impl TypeHandler for StrType {
                         RuntimeValue::Str(s) => s.value.clone(),
                         RuntimeValue::Array(l) => l
                             .elements
+                            .read()
+                            .unwrap()
                             .iter()
                             .map(|e| match e {
                                 RuntimeValue::Str(s) => s.value.clone(),
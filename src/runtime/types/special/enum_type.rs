@@ -1,11 +1,84 @@
+use crate::ast::types::PrimitiveType;
 use crate::error::RaccoonError;
+use crate::runtime::type_object::{TypeKind, TypeObject};
 use crate::runtime::types::TypeHandler;
-use crate::runtime::{RuntimeValue, StrValue};
+use crate::runtime::{ArrayValue, BoolValue, NullValue, RuntimeValue, StrValue};
 use crate::tokens::Position;
 use async_trait::async_trait;
 
 pub struct EnumType;
 
+impl EnumType {
+    /// Static methods every enum type answers, such as `values()`.
+    pub fn call_enum_method(
+        type_obj: &TypeObject,
+        method: &str,
+        args: &[RuntimeValue],
+        position: Position,
+        file: Option<String>,
+    ) -> Option<Result<RuntimeValue, RaccoonError>> {
+        let TypeKind::Enum { name, variants } = &type_obj.kind else {
+            return None;
+        };
+        let members = variants.iter().filter_map(|variant| {
+            type_obj
+                .get_static_property(variant)
+                .map(|value| (variant, value))
+        });
+        let list = |elements: Vec<RuntimeValue>| {
+            RuntimeValue::Array(ArrayValue::new(elements, PrimitiveType::any()))
+        };
+
+        let result = match method {
+            "values" => Ok(list(members.map(|(_, value)| value.clone()).collect())),
+            "names" => Ok(list(
+                members
+                    .map(|(variant, _)| RuntimeValue::Str(StrValue::new(variant.clone())))
+                    .collect(),
+            )),
+            "entries" => Ok(list(
+                members
+                    .map(|(variant, value)| {
+                        list(vec![
+                            RuntimeValue::Str(StrValue::new(variant.clone())),
+                            value.clone(),
+                        ])
+                    })
+                    .collect(),
+            )),
+            "fromValue" => {
+                let wanted = args
+                    .first()
+                    .cloned()
+                    .unwrap_or(RuntimeValue::Null(NullValue::new()));
+                members
+                    .map(|(_, value)| value)
+                    .find(|value| value.equals(&wanted))
+                    .cloned()
+                    .ok_or_else(|| {
+                        RaccoonError::new(
+                            format!(
+                                "No member of enum {} has value {}",
+                                name,
+                                wanted.to_string()
+                            ),
+                            position,
+                            file,
+                        )
+                    })
+            }
+            "has" => {
+                let wanted = args.first().map(|value| value.to_string());
+                Ok(RuntimeValue::Bool(BoolValue::new(
+                    wanted.is_some_and(|wanted| variants.contains(&wanted)),
+                )))
+            }
+            _ => return None,
+        };
+        Some(result)
+    }
+}
+
 #[async_trait]
 impl TypeHandler for EnumType {
     fn type_name(&self) -> &str {
@@ -14,14 +14,14 @@ impl TypeHandler for SymbolType {
 
     fn call_instance_method(
         &self,
-        _value: &mut RuntimeValue,
+        value: &mut RuntimeValue,
         method: &str,
         _args: Vec<RuntimeValue>,
         position: Position,
         file: Option<String>,
     ) -> Result<RuntimeValue, RaccoonError> {
         match method {
-            "toString" | "toStr" => Ok(RuntimeValue::Str(StrValue::new("Symbol()".to_string()))),
+            "toString" | "toStr" => Ok(RuntimeValue::Str(StrValue::new(value.to_string()))),
             _ => Err(RaccoonError::new(
                 format!("Method '{}' not found on symbol", method),
                 position,
@@ -3,6 +3,7 @@ use crate::runtime::types::TypeHandler;
 use crate::runtime::values::{BoolValue, ArrayValue, NullValue, RuntimeValue, StrValue};
 use crate::{Position, RaccoonError};
 use async_trait::async_trait;
+use std::sync::Arc;
 
 pub struct TypeType;
 
@@ -127,7 +128,7 @@ impl TypeHandler for TypeType {
 
                 let value = &args[0];
                 let type_obj = value.get_type_object();
-                Ok(RuntimeValue::Type(type_obj))
+                Ok(RuntimeValue::Type(Arc::new(type_obj)))
             }
             "name" => {
                 if args.len() != 1 {
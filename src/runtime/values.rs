@@ -1,13 +1,22 @@
 use crate::ast::{nodes::*, types::*};
+use crate::runtime::cancellation::{cancellation_message, is_cancellation, CancellationToken};
+use crate::runtime::decorator_registry::NativeDecorator;
 use crate::runtime::dynamic::DynamicRuntimeValue;
 use crate::runtime::type_object::TypeObject;
+use futures_util::future::Either;
+use indexmap::IndexMap;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use tokio::sync::Notify;
 
+// Heavy variants are shared behind `Arc` rather than `Rc` because values must be `Send + Sync`:
+// `DynamicValue` requires it, and the `#[async_trait]` type handlers return `Send` futures.
 #[derive(Debug, Clone)]
 pub enum RuntimeValue {
     Int(IntValue),
@@ -17,19 +26,22 @@ pub enum RuntimeValue {
     Str(StrValue),
     Bool(BoolValue),
     Null(NullValue),
+    Symbol(SymbolValue),
     Array(ArrayValue),
-    Map(MapValue),
+    Tuple(TupleValue),
+    Range(RangeValue),
+    Map(Arc<MapValue>),
     Object(ObjectValue),
-    Class(ClassValue),
-    ClassInstance(ClassInstance),
-    Function(FunctionValue),
+    Class(Arc<ClassValue>),
+    ClassInstance(Arc<ClassInstance>),
+    Function(Arc<FunctionValue>),
     NativeFunction(NativeFunctionValue),
-    NativeAsyncFunction(NativeAsyncFunctionValue),
+    NativeAsyncFunction(Arc<NativeAsyncFunctionValue>),
     Future(FutureValue),
-    Enum(EnumValue),
-    PrimitiveTypeObject(PrimitiveTypeObject),
-    EnumObject(EnumObject),
-    Type(TypeObject),
+    Enum(Arc<EnumValue>),
+    PrimitiveTypeObject(Arc<PrimitiveTypeObject>),
+    EnumObject(Arc<EnumObject>),
+    Type(Arc<TypeObject>),
     Dynamic(DynamicRuntimeValue),
 }
 
@@ -43,9 +55,16 @@ impl RuntimeValue {
             RuntimeValue::Str(_) => PrimitiveType::str(),
             RuntimeValue::Bool(_) => PrimitiveType::bool(),
             RuntimeValue::Null(_) => PrimitiveType::null(),
+            RuntimeValue::Symbol(_) => PrimitiveType::symbol(),
             RuntimeValue::Array(l) => Type::Array(Box::new(ArrayType {
                 element_type: l.element_type.clone(),
             })),
+            RuntimeValue::Tuple(t) => {
+                Type::Tuple(Box::new(TupleType::new(t.element_types.clone())))
+            }
+            RuntimeValue::Range(_) => Type::Array(Box::new(ArrayType {
+                element_type: PrimitiveType::int(),
+            })),
             RuntimeValue::Map(m) => Type::Map(Box::new(MapType {
                 key_type: m.key_type.clone(),
                 value_type: m.value_type.clone(),
@@ -128,7 +147,7 @@ impl RuntimeValue {
                 None,
                 TypeMetadata::new(),
             ),
-            RuntimeValue::Type(t) => t.clone(),
+            RuntimeValue::Type(t) => (**t).clone(),
             RuntimeValue::Class(c) => TypeObject::new(
                 c.class_type.clone(),
                 TypeKind::Class {
@@ -155,6 +174,16 @@ impl RuntimeValue {
                     TypeMetadata::new(),
                 )
             }
+            RuntimeValue::Function(_)
+            | RuntimeValue::NativeFunction(_)
+            | RuntimeValue::NativeAsyncFunction(_) => TypeObject::new(
+                self.get_type(),
+                TypeKind::Function,
+                HashMap::new(),
+                HashMap::new(),
+                None,
+                TypeMetadata::new(),
+            ),
             _ => TypeObject::new(
                 self.get_type(),
                 TypeKind::Unknown,
@@ -175,7 +204,10 @@ impl RuntimeValue {
             RuntimeValue::Str(v) => v.to_string(),
             RuntimeValue::Bool(v) => v.to_string(),
             RuntimeValue::Null(v) => v.to_string(),
+            RuntimeValue::Symbol(v) => v.to_string(),
             RuntimeValue::Array(v) => v.to_string(),
+            RuntimeValue::Tuple(v) => v.to_string(),
+            RuntimeValue::Range(v) => v.to_string(),
             RuntimeValue::Map(v) => v.to_string(),
             RuntimeValue::Object(v) => v.to_string(),
             RuntimeValue::Class(v) => v.to_string(),
@@ -193,12 +225,95 @@ impl RuntimeValue {
     }
 
     pub fn equals(&self, other: &RuntimeValue) -> bool {
+        self.structural_equals(other, false, &mut Vec::new())
+    }
+
+    pub fn deep_equals(&self, other: &RuntimeValue) -> bool {
+        self.structural_equals(other, true, &mut Vec::new())
+    }
+
+    fn structural_equals(
+        &self,
+        other: &RuntimeValue,
+        compare_instances: bool,
+        visited: &mut Vec<(usize, usize)>,
+    ) -> bool {
         match (self, other) {
             (RuntimeValue::Int(a), RuntimeValue::Int(b)) => a.value == b.value,
+            (RuntimeValue::Int(a), RuntimeValue::Float(b)) => a.value as f64 == b.value,
+            (RuntimeValue::Float(a), RuntimeValue::Int(b)) => a.value == b.value as f64,
             (RuntimeValue::Float(a), RuntimeValue::Float(b)) => a.value == b.value,
             (RuntimeValue::Str(a), RuntimeValue::Str(b)) => a.value == b.value,
             (RuntimeValue::Bool(a), RuntimeValue::Bool(b)) => a.value == b.value,
             (RuntimeValue::Null(_), RuntimeValue::Null(_)) => true,
+            (RuntimeValue::Symbol(a), RuntimeValue::Symbol(b)) => a == b,
+            (RuntimeValue::Enum(a), RuntimeValue::Enum(b)) => {
+                a.enum_name == b.enum_name && a.member_name == b.member_name
+            }
+            (RuntimeValue::Array(a), RuntimeValue::Array(b)) => {
+                if Arc::ptr_eq(&a.elements, &b.elements) {
+                    return true;
+                }
+                if !enter_pair(&a.elements, &b.elements, visited) {
+                    return true;
+                }
+                let (a, b) = (a.snapshot(), b.snapshot());
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(x, y)| x.structural_equals(y, compare_instances, visited))
+            }
+            (RuntimeValue::Tuple(a), RuntimeValue::Tuple(b)) => {
+                a.elements.len() == b.elements.len()
+                    && a.elements
+                        .iter()
+                        .zip(b.elements.iter())
+                        .all(|(x, y)| x.structural_equals(y, compare_instances, visited))
+            }
+            (RuntimeValue::Range(a), RuntimeValue::Range(b)) => a.iter().eq(b.iter()),
+            (RuntimeValue::Map(a), RuntimeValue::Map(b)) => {
+                if Arc::ptr_eq(&a.entries, &b.entries) {
+                    return true;
+                }
+                if !enter_pair(&a.entries, &b.entries, visited) {
+                    return true;
+                }
+                let (a, b) = (
+                    a.entries.read().unwrap().clone(),
+                    b.entries.read().unwrap().clone(),
+                );
+                a.len() == b.len()
+                    && a.iter().all(|(key, value)| {
+                        b.get(key).is_some_and(|other| {
+                            value.structural_equals(other, compare_instances, visited)
+                        })
+                    })
+            }
+            (RuntimeValue::Object(a), RuntimeValue::Object(b)) => {
+                if Arc::ptr_eq(&a.properties, &b.properties) {
+                    return true;
+                }
+                if !enter_pair(&a.properties, &b.properties, visited) {
+                    return true;
+                }
+                entries_equal(&a.snapshot(), &b.snapshot(), compare_instances, visited)
+            }
+            (RuntimeValue::ClassInstance(a), RuntimeValue::ClassInstance(b)) => {
+                if Arc::ptr_eq(&a.properties, &b.properties) {
+                    return true;
+                }
+                if !compare_instances || a.class_name != b.class_name {
+                    return false;
+                }
+
+                if !enter_pair(&a.properties, &b.properties, visited) {
+                    return true;
+                }
+
+                let left = a.properties.read().unwrap().clone().into_iter().collect();
+                let right = b.properties.read().unwrap().clone().into_iter().collect();
+                entries_equal(&left, &right, compare_instances, visited)
+            }
             _ => false,
         }
     }
@@ -228,7 +343,10 @@ impl RuntimeValue {
             RuntimeValue::Str(_) => "str".to_string(),
             RuntimeValue::Bool(_) => "bool".to_string(),
             RuntimeValue::Null(_) => "null".to_string(),
+            RuntimeValue::Symbol(_) => "symbol".to_string(),
             RuntimeValue::Array(_) => "array".to_string(),
+            RuntimeValue::Tuple(_) => "tuple".to_string(),
+            RuntimeValue::Range(_) => "range".to_string(),
             RuntimeValue::Map(_) => "map".to_string(),
             RuntimeValue::Object(_) => "object".to_string(),
             RuntimeValue::Dynamic(d) => d.type_name().to_string(),
@@ -236,7 +354,7 @@ impl RuntimeValue {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct IntValue {
     pub value: i64,
 }
@@ -251,7 +369,7 @@ impl IntValue {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct BigIntValue {
     pub value: i128,
 }
@@ -266,7 +384,7 @@ impl BigIntValue {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct FloatValue {
     pub value: f64,
 }
@@ -281,7 +399,7 @@ impl FloatValue {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct DecimalValue {
     pub value: f64,
 }
@@ -305,13 +423,15 @@ impl StrValue {
     pub fn new(value: String) -> Self {
         Self { value }
     }
+}
 
-    pub fn to_string(&self) -> String {
-        self.value.clone()
+impl fmt::Display for StrValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct BoolValue {
     pub value: bool,
 }
@@ -330,7 +450,7 @@ impl BoolValue {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct NullValue;
 
 impl NullValue {
@@ -343,45 +463,414 @@ impl NullValue {
     }
 }
 
+fn enter_pair<T>(a: &Arc<T>, b: &Arc<T>, visited: &mut Vec<(usize, usize)>) -> bool {
+    let pair = (Arc::as_ptr(a) as usize, Arc::as_ptr(b) as usize);
+    if visited.contains(&pair) {
+        return false;
+    }
+    visited.push(pair);
+    true
+}
+
+fn entries_equal(
+    left: &IndexMap<String, RuntimeValue>,
+    right: &IndexMap<String, RuntimeValue>,
+    compare_instances: bool,
+    visited: &mut Vec<(usize, usize)>,
+) -> bool {
+    left.len() == right.len()
+        && left.iter().all(|(key, value)| {
+            right
+                .get(key)
+                .is_some_and(|other| value.structural_equals(other, compare_instances, visited))
+        })
+}
+
+static NEXT_SYMBOL: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static REGISTERED_SYMBOLS: RefCell<HashMap<String, SymbolValue>> = RefCell::new(HashMap::new());
+}
+
+/// A value equal only to itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolValue {
+    pub id: u64,
+    pub description: Option<String>,
+}
+
+impl SymbolValue {
+    const KEY_PREFIX: &'static str = "\u{0}@";
+
+    pub fn new(description: Option<String>) -> Self {
+        Self {
+            id: NEXT_SYMBOL.fetch_add(1, Ordering::Relaxed),
+            description,
+        }
+    }
+
+    /// The symbol registered under `key`, creating it on first use.
+    pub fn registered(key: &str) -> Self {
+        REGISTERED_SYMBOLS.with(|registry| {
+            registry
+                .borrow_mut()
+                .entry(key.to_string())
+                .or_insert_with(|| Self::new(Some(key.to_string())))
+                .clone()
+        })
+    }
+
+    /// The key this symbol was registered under with `Symbol.for`, if any.
+    pub fn registered_key(&self) -> Option<String> {
+        let key = self.description.as_ref()?;
+        REGISTERED_SYMBOLS.with(|registry| {
+            (registry.borrow().get(key) == Some(self)).then(|| key.clone())
+        })
+    }
+
+    /// The key that stores a property named by this symbol in an object or instance.
+    pub fn property_key(&self) -> String {
+        match &self.description {
+            Some(description) => format!("{}{}:{}", Self::KEY_PREFIX, self.id, description),
+            None => format!("{}{}", Self::KEY_PREFIX, self.id),
+        }
+    }
+
+    /// The symbol behind a key made by `property_key`, or `None` for a string key.
+    pub fn from_property_key(key: &str) -> Option<Self> {
+        let rest = key.strip_prefix(Self::KEY_PREFIX)?;
+        let (id, description) = match rest.split_once(':') {
+            Some((id, description)) => (id, Some(description.to_string())),
+            None => (rest, None),
+        };
+        Some(Self {
+            id: id.parse().ok()?,
+            description,
+        })
+    }
+
+    pub fn is_property_key(key: &str) -> bool {
+        key.starts_with(Self::KEY_PREFIX)
+    }
+
+    /// How a property key prints: symbol keys as `[Symbol(description)]`.
+    pub fn display_key(key: &str) -> String {
+        match Self::from_property_key(key) {
+            Some(symbol) => format!("[{}]", symbol),
+            None => key.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for SymbolValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Symbol({})", self.description.as_deref().unwrap_or(""))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ArrayValue {
-    pub elements: Vec<RuntimeValue>,
+    pub elements: Arc<RwLock<Vec<RuntimeValue>>>,
     pub element_type: Type,
+    _allocation: Arc<AllocationToken>,
 }
 
 impl ArrayValue {
     pub fn new(elements: Vec<RuntimeValue>, element_type: Type) -> Self {
         Self {
-            elements,
+            elements: Arc::new(RwLock::new(elements)),
             element_type,
+            _allocation: Arc::new(AllocationToken::new(ValueKind::Array)),
         }
     }
 
+    /// A new list holding the same elements.
+    pub fn copy(&self) -> Self {
+        Self::new(self.snapshot(), self.element_type.clone())
+    }
+
+    /// The current elements, detached from the list.
+    pub fn snapshot(&self) -> Vec<RuntimeValue> {
+        self.elements.read().unwrap().clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<RuntimeValue> {
+        self.elements.read().unwrap().get(index).cloned()
+    }
+
     pub fn to_string(&self) -> String {
-        let elements_str: Vec<String> = self.elements.iter().map(|e| e.to_string()).collect();
+        let Some(_guard) = CycleGuard::enter(Arc::as_ptr(&self.elements) as usize) else {
+            return "[...]".to_string();
+        };
+        let elements_str: Vec<String> = self
+            .elements
+            .read()
+            .unwrap()
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
         format!("[{}]", elements_str.join(", "))
     }
 }
 
+/// Fixed-arity, immutable sequence; `element_types` records each slot's type at creation.
+#[derive(Debug, Clone)]
+pub struct TupleValue {
+    pub elements: Vec<RuntimeValue>,
+    pub element_types: Vec<Type>,
+    _allocation: AllocationToken,
+}
+
+impl TupleValue {
+    pub fn new(elements: Vec<RuntimeValue>) -> Self {
+        let element_types = elements.iter().map(RuntimeValue::get_type).collect();
+        Self {
+            elements,
+            element_types,
+            _allocation: AllocationToken::new(ValueKind::Tuple),
+        }
+    }
+}
+
+impl fmt::Display for TupleValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items: Vec<String> = self.elements.iter().map(|e| e.to_string()).collect();
+        match items.as_slice() {
+            [item] => write!(f, "({},)", item),
+            _ => write!(f, "({})", items.join(", ")),
+        }
+    }
+}
+
+/// Integer sequence from `start` towards `end` in steps of `step`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeValue {
+    pub start: i64,
+    pub end: i64,
+    pub step: i64,
+    pub inclusive: bool,
+}
+
+impl RangeValue {
+    pub fn new(start: i64, end: i64, inclusive: bool) -> Self {
+        Self {
+            start,
+            end,
+            step: 1,
+            inclusive,
+        }
+    }
+
+    /// The same bounds walked in strides of `step`, which must not be zero.
+    pub fn with_step(self, step: i64) -> Self {
+        Self { step, ..self }
+    }
+
+    pub fn len(&self) -> usize {
+        let step = self.step as i128;
+        let span = (self.end as i128 - self.start as i128) * step.signum();
+        let count = match (self.inclusive, span) {
+            (true, span) if span >= 0 => span / step.abs() + 1,
+            (false, span) if span > 0 => (span - 1) / step.abs() + 1,
+            _ => 0,
+        };
+        count.min(usize::MAX as i128) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<i64> {
+        (index < self.len()).then(|| self.start + index as i64 * self.step)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let offset = value as i128 - self.start as i128;
+        let step = self.step as i128;
+        offset % step == 0 && offset / step >= 0 && ((offset / step) as usize) < self.len()
+    }
+
+    pub fn iter(&self) -> RangeIter {
+        RangeIter {
+            next: self.start,
+            step: self.step,
+            remaining: self.len(),
+        }
+    }
+
+    pub fn to_list(&self) -> ArrayValue {
+        ArrayValue::new(
+            self.iter()
+                .map(|i| RuntimeValue::Int(IntValue::new(i)))
+                .collect(),
+            PrimitiveType::int(),
+        )
+    }
+}
+
+impl fmt::Display for RangeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = if self.inclusive { ".." } else { "..<" };
+        match self.step {
+            1 => write!(f, "{}{}{}", self.start, operator, self.end),
+            step => write!(f, "{}{}{} step {}", self.start, operator, self.end, step),
+        }
+    }
+}
+
+pub struct RangeIter {
+    next: i64,
+    step: i64,
+    remaining: usize,
+}
+
+impl Iterator for RangeIter {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let current = self.next;
+        self.next = self.next.wrapping_add(self.step);
+        self.remaining -= 1;
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// A map key. Only immutable values can be keys, so `1` and `"1"` stay distinct entries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    Int(i64),
+    Str(String),
+    Bool(bool),
+    Symbol(SymbolValue),
+    Tuple(Vec<MapKey>),
+}
+
+impl MapKey {
+    pub fn from_value(value: &RuntimeValue) -> Result<MapKey, String> {
+        match value {
+            RuntimeValue::Int(i) => Ok(MapKey::Int(i.value)),
+            RuntimeValue::Str(s) => Ok(MapKey::Str(s.value.clone())),
+            RuntimeValue::Bool(b) => Ok(MapKey::Bool(b.value)),
+            RuntimeValue::Symbol(symbol) => Ok(MapKey::Symbol(symbol.clone())),
+            RuntimeValue::Tuple(tuple) => tuple
+                .elements
+                .iter()
+                .map(MapKey::from_value)
+                .collect::<Result<Vec<_>, _>>()
+                .map(MapKey::Tuple),
+            other => Err(format!(
+                "Map keys must be int, str, bool, symbol or a tuple of those, got {}",
+                other.get_name()
+            )),
+        }
+    }
+
+    pub fn to_value(&self) -> RuntimeValue {
+        match self {
+            MapKey::Int(i) => RuntimeValue::Int(IntValue::new(*i)),
+            MapKey::Str(s) => RuntimeValue::Str(StrValue::new(s.clone())),
+            MapKey::Bool(b) => RuntimeValue::Bool(BoolValue::new(*b)),
+            MapKey::Symbol(symbol) => RuntimeValue::Symbol(symbol.clone()),
+            MapKey::Tuple(items) => {
+                RuntimeValue::Tuple(TupleValue::new(items.iter().map(MapKey::to_value).collect()))
+            }
+        }
+    }
+}
+
+impl fmt::Display for MapKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapKey::Int(i) => write!(f, "{}", i),
+            MapKey::Str(s) => write!(f, "{}", s),
+            MapKey::Bool(b) => write!(f, "{}", b),
+            MapKey::Symbol(symbol) => write!(f, "{}", symbol),
+            MapKey::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                match items.as_slice() {
+                    [item] => write!(f, "({},)", item),
+                    _ => write!(f, "({})", items.join(", ")),
+                }
+            }
+        }
+    }
+}
+
+/// Entries keep insertion order; copies of the value share `entries`.
 #[derive(Debug, Clone)]
 pub struct MapValue {
-    pub entries: HashMap<String, RuntimeValue>,
+    pub entries: Arc<RwLock<IndexMap<MapKey, RuntimeValue>>>,
     pub key_type: Type,
     pub value_type: Type,
+    _allocation: Arc<AllocationToken>,
 }
 
 impl MapValue {
-    pub fn new(entries: HashMap<String, RuntimeValue>, key_type: Type, value_type: Type) -> Self {
+    pub fn new(entries: IndexMap<MapKey, RuntimeValue>, key_type: Type, value_type: Type) -> Self {
         Self {
-            entries,
+            entries: Arc::new(RwLock::new(entries)),
             key_type,
             value_type,
+            _allocation: Arc::new(AllocationToken::new(ValueKind::Map)),
         }
     }
 
+    /// A new map holding the same entries.
+    pub fn copy(&self) -> Self {
+        Self::new(
+            self.entries.read().unwrap().clone(),
+            self.key_type.clone(),
+            self.value_type.clone(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `[key, value]` pairs in insertion order, as `entries()` and `for-of` produce them.
+    pub fn entry_pairs(&self) -> Vec<RuntimeValue> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(key, value)| {
+                RuntimeValue::Array(ArrayValue::new(
+                    vec![key.to_value(), value.clone()],
+                    PrimitiveType::any(),
+                ))
+            })
+            .collect()
+    }
+
     pub fn to_string(&self) -> String {
+        let Some(_guard) = CycleGuard::enter(Arc::as_ptr(&self.entries) as usize) else {
+            return "Map { ... }".to_string();
+        };
         let entries_str: Vec<String> = self
             .entries
+            .read()
+            .unwrap()
             .iter()
             .map(|(k, v)| format!("{}: {}", k, v.to_string()))
             .collect();
@@ -389,30 +878,121 @@ impl MapValue {
     }
 }
 
+/// A plain object. Copies of the value share `properties` and the frozen flag.
 #[derive(Debug, Clone)]
 pub struct ObjectValue {
-    pub properties: HashMap<String, RuntimeValue>,
+    /// Kept in insertion order, which is the order iteration and printing see.
+    pub properties: Arc<RwLock<IndexMap<String, RuntimeValue>>>,
     pub obj_type: Type,
+    pub frozen: Arc<AtomicBool>,
+    _allocation: Arc<AllocationToken>,
 }
 
 impl ObjectValue {
-    pub fn new(properties: HashMap<String, RuntimeValue>, obj_type: Type) -> Self {
+    pub fn new(
+        properties: impl IntoIterator<Item = (String, RuntimeValue)>,
+        obj_type: Type,
+    ) -> Self {
         Self {
-            properties,
+            properties: Arc::new(RwLock::new(properties.into_iter().collect())),
             obj_type,
+            frozen: Arc::new(AtomicBool::new(false)),
+            _allocation: Arc::new(AllocationToken::new(ValueKind::Object)),
+        }
+    }
+
+    /// A new, unfrozen object holding the same properties.
+    pub fn copy(&self) -> Self {
+        Self::new(self.snapshot(), self.obj_type.clone())
+    }
+
+    /// The current properties, detached from the object.
+    pub fn snapshot(&self) -> IndexMap<String, RuntimeValue> {
+        self.properties.read().unwrap().clone()
+    }
+
+    pub fn get(&self, property: &str) -> Option<RuntimeValue> {
+        self.properties.read().unwrap().get(property).cloned()
+    }
+
+    pub fn contains(&self, property: &str) -> bool {
+        self.properties.read().unwrap().contains_key(property)
+    }
+
+    /// `[key, value]` pairs of the string-keyed properties.
+    pub fn entry_pairs(&self) -> Vec<RuntimeValue> {
+        self.properties
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(key, _)| !SymbolValue::is_property_key(key))
+            .map(|(key, value)| {
+                RuntimeValue::Array(ArrayValue::new(
+                    vec![RuntimeValue::Str(StrValue::new(key.clone())), value.clone()],
+                    PrimitiveType::any(),
+                ))
+            })
+            .collect()
+    }
+
+    pub fn freeze(&self) {
+        self.frozen.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.load(Ordering::SeqCst)
+    }
+
+    pub fn check_writable(&self, property: &str) -> Result<(), String> {
+        if self.is_frozen() {
+            return Err(format!(
+                "Cannot assign to property '{}' of a frozen object",
+                property
+            ));
         }
+        Ok(())
     }
 
     pub fn to_string(&self) -> String {
+        let Some(_guard) = CycleGuard::enter(Arc::as_ptr(&self.properties) as usize) else {
+            return "{ ... }".to_string();
+        };
         let props_str: Vec<String> = self
             .properties
+            .read()
+            .unwrap()
             .iter()
-            .map(|(k, v)| format!("{}: {}", k, v.to_string()))
+            .map(|(k, v)| format!("{}: {}", SymbolValue::display_key(k), v.to_string()))
             .collect();
         format!("{{ {} }}", props_str.join(", "))
     }
 }
 
+thread_local! {
+    static PRINTING: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+struct CycleGuard(usize);
+
+impl CycleGuard {
+    fn enter(address: usize) -> Option<Self> {
+        PRINTING.with(|printing| {
+            let mut printing = printing.borrow_mut();
+            if printing.contains(&address) {
+                return None;
+            }
+            printing.push(address);
+            Some(Self(address))
+        })
+    }
+}
+
+impl Drop for CycleGuard {
+    fn drop(&mut self) {
+        PRINTING.with(|printing| printing.borrow_mut().retain(|address| *address != self.0));
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ClassValue {
     pub class_name: String,
@@ -420,6 +1000,7 @@ pub struct ClassValue {
     pub static_properties: HashMap<String, RuntimeValue>,
     pub class_type: Type,
     pub declaration: ClassDecl,
+    _allocation: AllocationToken,
 }
 
 impl ClassValue {
@@ -435,6 +1016,7 @@ impl ClassValue {
             static_properties: HashMap::new(),
             class_type,
             declaration,
+            _allocation: AllocationToken::new(ValueKind::Class),
         }
     }
 
@@ -451,6 +1033,7 @@ impl ClassValue {
             static_properties,
             class_type,
             declaration,
+            _allocation: AllocationToken::new(ValueKind::Class),
         }
     }
 
@@ -466,6 +1049,14 @@ pub struct ClassInstance {
     pub methods: HashMap<String, FunctionValue>,
     pub accessors: Vec<PropertyAccessor>,
     pub class_type: Type,
+    pub readonly_properties: Vec<String>,
+    pub private_members: HashMap<String, String>,
+    pub method_owners: HashMap<String, String>,
+    /// Set on instances of `Error` and its subclasses, which print as `Name: message`.
+    pub is_error: bool,
+    pub frozen: Arc<AtomicBool>,
+    pub constructed: Arc<AtomicBool>,
+    _allocation: AllocationToken,
 }
 
 impl ClassInstance {
@@ -482,14 +1073,108 @@ impl ClassInstance {
             methods,
             accessors,
             class_type,
+            readonly_properties: Vec::new(),
+            private_members: HashMap::new(),
+            method_owners: HashMap::new(),
+            is_error: false,
+            frozen: Arc::new(AtomicBool::new(false)),
+            constructed: Arc::new(AtomicBool::new(true)),
+            _allocation: AllocationToken::new(ValueKind::ClassInstance),
+        }
+    }
+
+    pub fn with_readonly_properties(mut self, readonly_properties: Vec<String>) -> Self {
+        self.readonly_properties = readonly_properties;
+        self.constructed.store(false, Ordering::SeqCst);
+        self
+    }
+
+    /// Private members map to the class that declared them.
+    pub fn with_private_members(mut self, private_members: HashMap<String, String>) -> Self {
+        self.private_members = private_members;
+        self
+    }
+
+    /// Inherited methods map to the class that declared them.
+    pub fn with_method_owners(mut self, method_owners: HashMap<String, String>) -> Self {
+        self.method_owners = method_owners;
+        self
+    }
+
+    pub fn with_error(mut self, is_error: bool) -> Self {
+        self.is_error = is_error;
+        self
+    }
+
+    /// Private members are reachable only from code whose `this` shares the declaring class.
+    pub fn check_accessible(
+        &self,
+        member: &str,
+        accessor: Option<&ClassInstance>,
+    ) -> Result<(), String> {
+        let Some(owner) = self.private_members.get(member) else {
+            return Ok(());
+        };
+
+        if accessor.is_some_and(|this| this.private_members.get(member) == Some(owner)) {
+            return Ok(());
+        }
+
+        Err(format!(
+            "Member '{}' is private to class {}",
+            member, owner
+        ))
+    }
+
+    pub fn finish_construction(&self) {
+        self.constructed.store(true, Ordering::SeqCst);
+    }
+
+    pub fn freeze(&self) {
+        self.frozen.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.load(Ordering::SeqCst)
+    }
+
+    pub fn check_writable(&self, property: &str) -> Result<(), String> {
+        if self.is_frozen() {
+            return Err(format!(
+                "Cannot assign to property '{}' of a frozen {} instance",
+                property, self.class_name
+            ));
         }
+
+        if self.constructed.load(Ordering::SeqCst)
+            && self.readonly_properties.iter().any(|name| name == property)
+        {
+            return Err(format!(
+                "Cannot assign to readonly property '{}' of {}",
+                property, self.class_name
+            ));
+        }
+
+        Ok(())
     }
 
     pub fn to_string(&self) -> String {
         let properties = self.properties.read().unwrap();
+        if self.is_error {
+            let name = match properties.get("name") {
+                Some(RuntimeValue::Str(name)) => name.value.clone(),
+                _ => self.class_name.clone(),
+            };
+            return match properties.get("message") {
+                Some(RuntimeValue::Str(message)) if !message.value.is_empty() => {
+                    format!("{}: {}", name, message.value)
+                }
+                _ => name,
+            };
+        }
         let props_str: Vec<String> = properties
             .iter()
-            .map(|(k, v)| format!("{}: {}", k, v.to_string()))
+            .map(|(k, v)| format!("{}: {}", SymbolValue::display_key(k), v.to_string()))
             .collect();
         format!("{} {{ {} }}", self.class_name, props_str.join(", "))
     }
@@ -499,21 +1184,47 @@ impl ClassInstance {
 pub struct FunctionValue {
     pub name: Option<String>,
     pub parameters: Vec<FnParam>,
-    pub body: Vec<Stmt>,
+    pub body: Body,
     pub is_async: bool,
     pub fn_type: Type,
     pub decorators: Vec<DecoratorDecl>,
+    pub overloads: Vec<FunctionValue>,
+    pub bound: Option<Box<BoundCall>>,
+    pub decorated: Option<Box<DecoratedCall>>,
+    _allocation: AllocationToken,
+}
+
+#[derive(Debug, Clone)]
+pub struct BoundCall {
+    pub target: RuntimeValue,
+    pub this: RuntimeValue,
+    pub args: Vec<RuntimeValue>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecoratedCall {
+    pub target: RuntimeValue,
+    pub decorator: NativeDecorator,
 }
 
 impl FunctionValue {
-    pub fn new(parameters: Vec<FnParam>, body: Vec<Stmt>, is_async: bool, fn_type: Type) -> Self {
+    pub fn new(
+        parameters: Vec<FnParam>,
+        body: impl Into<Body>,
+        is_async: bool,
+        fn_type: Type,
+    ) -> Self {
         Self {
             name: None,
             parameters,
-            body,
+            body: body.into(),
             is_async,
             fn_type,
             decorators: Vec::new(),
+            overloads: Vec::new(),
+            bound: None,
+            decorated: None,
+            _allocation: AllocationToken::new(ValueKind::Function),
         }
     }
 
@@ -527,6 +1238,25 @@ impl FunctionValue {
         self
     }
 
+    pub fn with_bound(mut self, bound: BoundCall) -> Self {
+        self.bound = Some(Box::new(bound));
+        self
+    }
+
+    pub fn with_decorated(mut self, decorated: DecoratedCall) -> Self {
+        self.decorated = Some(Box::new(decorated));
+        self
+    }
+
+    pub fn with_overload(mut self, overload: FunctionValue) -> Self {
+        self.overloads.push(overload);
+        self
+    }
+
+    pub fn declarations(&self) -> impl Iterator<Item = &FunctionValue> {
+        std::iter::once(self).chain(&self.overloads)
+    }
+
     pub fn to_string(&self) -> String {
         match &self.name {
             Some(name) => {
@@ -571,6 +1301,9 @@ impl crate::runtime::dynamic::DynamicValue for FunctionValue {
 
 pub type NativeFn = fn(Vec<RuntimeValue>) -> RuntimeValue;
 
+/// Builds a native when the global it is declared under is first looked up.
+pub type NativeThunk = fn() -> NativeFunctionValue;
+
 #[derive(Clone)]
 pub struct NativeFunctionValue {
     pub implementation: NativeFn,
@@ -585,6 +1318,14 @@ impl NativeFunctionValue {
         }
     }
 
+    /// Names the parameters so callers can pass them as named arguments.
+    pub fn with_param_names(mut self, names: &[&str]) -> Self {
+        if let Type::Function(fn_type) = &mut self.fn_type {
+            fn_type.param_names = names.iter().map(|name| name.to_string()).collect();
+        }
+        self
+    }
+
     pub fn to_string(&self) -> String {
         "[Native Function]".to_string()
     }
@@ -609,6 +1350,9 @@ pub type NativeAsyncFn = Arc<
 pub struct NativeAsyncFunctionValue {
     pub implementation: NativeAsyncFn,
     pub fn_type: Type,
+    pub name: Option<String>,
+    /// The module a sandbox must allow for this native to run, such as `io`.
+    pub module: Option<&'static str>,
 }
 
 impl NativeAsyncFunctionValue {
@@ -616,9 +1360,21 @@ impl NativeAsyncFunctionValue {
         Self {
             implementation,
             fn_type,
+            name: None,
+            module: None,
         }
     }
 
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_module(mut self, module: Option<&'static str>) -> Self {
+        self.module = module;
+        self
+    }
+
     pub fn to_string(&self) -> String {
         "[Native Async Function]".to_string()
     }
@@ -745,6 +1501,7 @@ pub struct FutureValue {
     pub state: Arc<RwLock<FutureState>>,
     pub value_type: Type,
     pub notifier: Arc<Notify>,
+    _allocation: AllocationToken,
 }
 
 impl fmt::Debug for FutureValue {
@@ -760,7 +1517,85 @@ impl fmt::Debug for FutureValue {
 pub enum FutureState {
     Pending,
     Resolved(Box<RuntimeValue>),
-    Rejected(String),
+    Rejected(Rejection),
+}
+
+/// Why a future rejected.
+#[derive(Debug, Clone)]
+pub enum Rejection {
+    Message(String),
+    Thrown(Box<RuntimeValue>),
+    Error(Box<crate::error::RaccoonError>),
+}
+
+impl Rejection {
+    /// The error raised at `position` for this rejection.
+    pub fn into_error(
+        self,
+        position: crate::tokens::Position,
+        file: Option<String>,
+    ) -> crate::error::RaccoonError {
+        use crate::error::{ErrorKind, RaccoonError};
+        match self {
+            Rejection::Error(error) => *error,
+            Rejection::Thrown(value) => {
+                let (kind, message) = match &*value {
+                    RuntimeValue::ClassInstance(instance) if instance.is_error => (
+                        ErrorKind::from_error_class(&instance.class_name)
+                            .unwrap_or(ErrorKind::RuntimeError),
+                        instance
+                            .properties
+                            .read()
+                            .unwrap()
+                            .get("message")
+                            .map_or_else(String::new, |message| message.to_string()),
+                    ),
+                    value => (ErrorKind::RuntimeError, value.to_string()),
+                };
+                RaccoonError::with_kind(kind, message, position, file).with_thrown(*value)
+            }
+            Rejection::Message(message) if is_cancellation(&message) => {
+                RaccoonError::cancellation_error(message, position, file)
+            }
+            Rejection::Message(message) => {
+                RaccoonError::new(format!("Future rejected: {}", message), position, file)
+            }
+        }
+    }
+
+    /// The value a rejection handler receives: what was thrown, or else the message.
+    pub fn value(&self) -> RuntimeValue {
+        match self {
+            Rejection::Thrown(value) => (**value).clone(),
+            Rejection::Error(error) => match &error.thrown {
+                Some(thrown) => (**thrown).clone(),
+                None => RuntimeValue::Str(StrValue::new(error.message.clone())),
+            },
+            Rejection::Message(message) => RuntimeValue::Str(StrValue::new(message.clone())),
+        }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Message(message) => write!(f, "{}", message),
+            Rejection::Thrown(value) => write!(f, "{}", value.to_string()),
+            Rejection::Error(error) => write!(f, "{}", error.message),
+        }
+    }
+}
+
+impl From<String> for Rejection {
+    fn from(message: String) -> Self {
+        Rejection::Message(message)
+    }
+}
+
+impl From<crate::error::RaccoonError> for Rejection {
+    fn from(error: crate::error::RaccoonError) -> Self {
+        Rejection::Error(Box::new(error))
+    }
 }
 
 impl FutureValue {
@@ -769,6 +1604,7 @@ impl FutureValue {
             state: Arc::new(RwLock::new(FutureState::Pending)),
             value_type,
             notifier: Arc::new(Notify::new()),
+            _allocation: AllocationToken::new(ValueKind::Future),
         }
     }
 
@@ -779,16 +1615,18 @@ impl FutureValue {
             state: Arc::new(RwLock::new(FutureState::Resolved(Box::new(value)))),
             value_type,
             notifier,
+            _allocation: AllocationToken::new(ValueKind::Future),
         }
     }
 
-    pub fn new_rejected(error: String, value_type: Type) -> Self {
+    pub fn new_rejected(error: impl Into<Rejection>, value_type: Type) -> Self {
         let notifier = Arc::new(Notify::new());
         notifier.notify_waiters();
         Self {
-            state: Arc::new(RwLock::new(FutureState::Rejected(error))),
+            state: Arc::new(RwLock::new(FutureState::Rejected(error.into()))),
             value_type,
             notifier,
+            _allocation: AllocationToken::new(ValueKind::Future),
         }
     }
 
@@ -797,12 +1635,12 @@ impl FutureValue {
         self.notifier.notify_waiters();
     }
 
-    pub fn reject(&self, error: String) {
-        *self.state.write().unwrap() = FutureState::Rejected(error);
+    pub fn reject(&self, error: impl Into<Rejection>) {
+        *self.state.write().unwrap() = FutureState::Rejected(error.into());
         self.notifier.notify_waiters();
     }
 
-    pub async fn wait_for_completion(&self) -> Result<RuntimeValue, String> {
+    pub async fn wait_for_completion(&self) -> Result<RuntimeValue, Rejection> {
         loop {
             {
                 let state = self.state.read().unwrap();
@@ -817,6 +1655,29 @@ impl FutureValue {
         }
     }
 
+    /// A future that settles like this one unless `token` is cancelled first.
+    pub fn with_cancellation(&self, token: &CancellationToken) -> FutureValue {
+        if let Err(message) = token.check() {
+            return FutureValue::new_rejected(message, self.value_type.clone());
+        }
+        if !self.is_pending() {
+            return self.clone();
+        }
+
+        let bound = FutureValue::new(self.value_type.clone());
+        let (source, target, token) = (self.clone(), bound.clone(), token.clone());
+        tokio::task::spawn_local(async move {
+            let completion = Box::pin(async move { source.wait_for_completion().await });
+            let cancelled = Box::pin(async move { token.cancelled().await });
+            match futures_util::future::select(completion, cancelled).await {
+                Either::Left((Ok(value), _)) => target.resolve(value),
+                Either::Left((Err(error), _)) => target.reject(error),
+                Either::Right((reason, _)) => target.reject(cancellation_message(&reason)),
+            }
+        });
+        bound
+    }
+
     pub fn is_resolved(&self) -> bool {
         matches!(*self.state.read().unwrap(), FutureState::Resolved(_))
     }
@@ -857,3 +1718,165 @@ impl crate::runtime::dynamic::DynamicValue for FutureValue {
         "future"
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Array,
+    Tuple,
+    Map,
+    Object,
+    Class,
+    ClassInstance,
+    Function,
+    Future,
+    Environment,
+}
+
+impl ValueKind {
+    pub const ALL: [ValueKind; 9] = [
+        ValueKind::Array,
+        ValueKind::Tuple,
+        ValueKind::Map,
+        ValueKind::Object,
+        ValueKind::Class,
+        ValueKind::ClassInstance,
+        ValueKind::Function,
+        ValueKind::Future,
+        ValueKind::Environment,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ValueKind::Array => "array",
+            ValueKind::Tuple => "tuple",
+            ValueKind::Map => "map",
+            ValueKind::Object => "object",
+            ValueKind::Class => "class",
+            ValueKind::ClassInstance => "instance",
+            ValueKind::Function => "function",
+            ValueKind::Future => "future",
+            ValueKind::Environment => "environment",
+        }
+    }
+}
+
+static LIVE_VALUES: [AtomicUsize; ValueKind::ALL.len()] =
+    [const { AtomicUsize::new(0) }; ValueKind::ALL.len()];
+
+pub struct AllocationToken(ValueKind);
+
+impl AllocationToken {
+    pub fn new(kind: ValueKind) -> Self {
+        LIVE_VALUES[kind as usize].fetch_add(1, Ordering::Relaxed);
+        Self(kind)
+    }
+}
+
+impl Clone for AllocationToken {
+    fn clone(&self) -> Self {
+        Self::new(self.0)
+    }
+}
+
+impl Drop for AllocationToken {
+    fn drop(&mut self) {
+        LIVE_VALUES[self.0 as usize].fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Debug for AllocationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AllocationToken({})", self.0.name())
+    }
+}
+
+pub fn memory_stats() -> Vec<(ValueKind, usize)> {
+    ValueKind::ALL
+        .iter()
+        .map(|kind| (*kind, LIVE_VALUES[*kind as usize].load(Ordering::Relaxed)))
+        .collect()
+}
+
+pub fn format_memory_stats() -> String {
+    let stats = memory_stats();
+    let total: usize = stats.iter().map(|(_, count)| count).sum();
+
+    let mut output = String::from("Live values:\n");
+    for (kind, count) in stats {
+        output.push_str(&format!("  {:<12} {:>10}\n", kind.name(), count));
+    }
+    output.push_str(&format!("  {:<12} {:>10}\n", "total", total));
+
+    let heap = heap_usage();
+    if heap.peak > 0 {
+        output.push_str("Heap bytes:\n");
+        output.push_str(&format!("  {:<12} {:>10}\n", "current", heap.current));
+        output.push_str(&format!("  {:<12} {:>10}\n", "peak", heap.peak));
+    }
+    output
+}
+
+static HEAP_CURRENT: AtomicUsize = AtomicUsize::new(0);
+static HEAP_PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Tracks live and peak heap bytes when installed as the global allocator.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn grow(bytes: usize) {
+        let current = HEAP_CURRENT.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        HEAP_PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(bytes: usize) {
+        HEAP_CURRENT.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                Self::grow(new_size - layout.size());
+            } else {
+                Self::shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeapUsage {
+    pub current: usize,
+    pub peak: usize,
+}
+
+pub fn heap_usage() -> HeapUsage {
+    HeapUsage {
+        current: HEAP_CURRENT.load(Ordering::Relaxed),
+        peak: HEAP_PEAK.load(Ordering::Relaxed),
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
     ast::{nodes::*, types::*},
     error::RaccoonError,
+    lexer::Lexer,
+    parser::incremental::TextEdit,
     runtime::RuntimeValue,
+    tokens::{Position, Token, TokenType},
 };
 use std::collections::HashMap;
 
@@ -16,16 +19,20 @@ pub enum SymbolKind {
     Parameter,
     Property,
     Method,
+    Import,
 }
 
 #[derive(Debug, Clone)]
 pub struct SymbolItem {
+    pub id: usize,
     pub name: String,
     pub kind: SymbolKind,
     pub symbol_type: Type,
     pub is_constant: bool,
     pub declaration: Option<Box<Stmt>>,
     pub value: Option<RuntimeValue>,
+    pub position: Option<Position>,
+    pub used: bool,
 }
 
 impl SymbolItem {
@@ -37,24 +44,47 @@ impl SymbolItem {
         declaration: Option<Box<Stmt>>,
     ) -> Self {
         Self {
+            id: 0,
             name,
             kind,
             symbol_type,
             is_constant,
             declaration,
             value: None,
+            position: None,
+            used: false,
         }
     }
 
+    pub fn is_unused(&self) -> bool {
+        self.position.is_some() && !self.used && !self.name.starts_with('_')
+    }
+
     pub fn with_value(mut self, value: RuntimeValue) -> Self {
         self.value = Some(value);
         self
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub symbol: usize,
+    pub at: Position,
+    pub is_declaration: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
 pub struct SymbolTable {
     file: Option<String>,
     scopes: Vec<HashMap<String, SymbolItem>>,
+    unused: Vec<SymbolItem>,
+    symbols: Vec<SymbolItem>,
+    references: Vec<Reference>,
 }
 
 impl SymbolTable {
@@ -62,6 +92,9 @@ impl SymbolTable {
         let mut table = Self {
             file,
             scopes: Vec::new(),
+            unused: Vec::new(),
+            symbols: Vec::new(),
+            references: Vec::new(),
         };
 
         table.scopes.push(HashMap::new());
@@ -152,27 +185,208 @@ impl SymbolTable {
 
     pub fn exit_scope(&mut self) {
         if self.scopes.len() > 1 {
-            self.scopes.pop();
+            if let Some(scope) = self.scopes.pop() {
+                self.unused.extend(scope.into_values().filter(SymbolItem::is_unused));
+            }
         }
     }
 
-    pub fn define(
+    pub fn define_at(
         &mut self,
         name: String,
         kind: SymbolKind,
         symbol_type: Type,
         is_constant: bool,
         declaration: Option<Box<Stmt>>,
+        position: Position,
     ) -> SymbolItem {
-        let symbol = SymbolItem::new(name.clone(), kind, symbol_type, is_constant, declaration);
+        let mut symbol = SymbolItem::new(name, kind, symbol_type, is_constant, declaration);
+        symbol.position = Some(position);
+        let symbol = self.insert(symbol);
+        self.references.push(Reference {
+            symbol: symbol.id,
+            at: position,
+            is_declaration: true,
+        });
+        symbol
+    }
+
+    fn insert(&mut self, mut symbol: SymbolItem) -> SymbolItem {
+        symbol.id = match self.lookup_current_scope(&symbol.name) {
+            Some(existing) => existing.id,
+            None => self.symbols.len(),
+        };
+        match self.symbols.get_mut(symbol.id) {
+            Some(archived) => *archived = symbol.clone(),
+            None => self.symbols.push(symbol.clone()),
+        }
 
         if let Some(current_scope) = self.scopes.last_mut() {
-            current_scope.insert(name, symbol.clone());
+            current_scope.insert(symbol.name.clone(), symbol.clone());
         }
 
         symbol
     }
 
+    pub fn reference(&mut self, name: &str, at: Position) {
+        if let Some(symbol) = self.lookup_mut(name) {
+            symbol.used = true;
+            let symbol = symbol.id;
+            self.references.push(Reference {
+                symbol,
+                at,
+                is_declaration: false,
+            });
+        }
+    }
+
+    pub fn declared_at(&mut self, name: &str, at: Position) {
+        if let Some(symbol) = self.lookup(name) {
+            self.references.push(Reference {
+                symbol: symbol.id,
+                at,
+                is_declaration: true,
+            });
+        }
+    }
+
+    pub fn declaration(&self, symbol: &SymbolItem, tokens: &[Token]) -> Option<Span> {
+        self.references
+            .iter()
+            .filter(|reference| reference.symbol == symbol.id && reference.is_declaration)
+            .find_map(|reference| name_span(tokens, &symbol.name, reference.at))
+    }
+
+    pub fn symbol(&self, id: usize) -> Option<&SymbolItem> {
+        self.symbols.get(id)
+    }
+
+    pub fn symbol_at(&self, position: Position, tokens: &[Token]) -> Option<&SymbolItem> {
+        self.references.iter().find_map(|reference| {
+            let symbol = self.symbols.get(reference.symbol)?;
+            let span = name_span(tokens, &symbol.name, reference.at)?;
+            (span.start <= position && position < span.end).then_some(symbol)
+        })
+    }
+
+    pub fn find_references(&self, symbol: &SymbolItem, tokens: &[Token]) -> Vec<Span> {
+        let spans_of = |same: bool| -> Vec<Span> {
+            self.references
+                .iter()
+                .filter(|reference| (reference.symbol == symbol.id) == same)
+                .filter(|reference| {
+                    self.symbols
+                        .get(reference.symbol)
+                        .is_some_and(|other| other.name == symbol.name)
+                })
+                .filter_map(|reference| name_span(tokens, &symbol.name, reference.at))
+                .collect()
+        };
+        let mut spans = spans_of(true);
+
+        let is_type = matches!(
+            symbol.kind,
+            SymbolKind::Class | SymbolKind::Interface | SymbolKind::Enum | SymbolKind::TypeAlias
+        );
+        let is_file_level = self.scopes[0]
+            .get(&symbol.name)
+            .is_some_and(|item| item.id == symbol.id);
+        if is_type || is_file_level {
+            let others = spans_of(false);
+            spans.extend(
+                unresolved_names(tokens, &symbol.name, is_type)
+                    .into_iter()
+                    .filter(|span| !others.contains(span)),
+            );
+        }
+
+        spans.sort();
+        spans.dedup();
+        spans
+    }
+
+    pub fn rename(
+        &self,
+        symbol: &SymbolItem,
+        new_name: &str,
+        tokens: &[Token],
+    ) -> Result<Vec<TextEdit>, RaccoonError> {
+        let valid = new_name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && new_name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        let is_keyword = Lexer::new(new_name.to_string(), None)
+            .tokenize()
+            .map_or(true, |tokens| {
+                tokens.first().map(|token| &token.token_type) != Some(&TokenType::Identifier)
+            });
+        if !valid || is_keyword {
+            return Err(RaccoonError::new(
+                format!("'{}' is not a valid identifier", new_name),
+                symbol.position.unwrap_or((0, 0)),
+                self.file.clone(),
+            ));
+        }
+
+        Ok(self
+            .find_references(symbol, tokens)
+            .into_iter()
+            .map(|span| {
+                let text = match module_name(tokens, span) {
+                    Some(TokenType::Import) => format!("{} as {}", symbol.name, new_name),
+                    Some(_) => format!("{} as {}", new_name, symbol.name),
+                    None => new_name.to_string(),
+                };
+                TextEdit {
+                    start: span.start,
+                    end: span.end,
+                    text,
+                }
+            })
+            .collect())
+    }
+
+    pub fn mark_used(&mut self, name: &str) {
+        if let Some(symbol) = self.lookup_mut(name) {
+            symbol.used = true;
+        }
+    }
+
+    pub fn lookup_outer(&self, name: &str) -> Option<&SymbolItem> {
+        let outer = self.scopes.len().saturating_sub(1);
+        self.scopes[..outer]
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+    }
+
+    pub fn take_unused(&mut self) -> Vec<SymbolItem> {
+        let mut unused = std::mem::take(&mut self.unused);
+        for scope in &self.scopes {
+            unused.extend(scope.values().filter(|s| s.is_unused()).cloned());
+        }
+        unused.sort_by_key(|symbol| symbol.position);
+        unused
+    }
+
+    pub fn define(
+        &mut self,
+        name: String,
+        kind: SymbolKind,
+        symbol_type: Type,
+        is_constant: bool,
+        declaration: Option<Box<Stmt>>,
+    ) -> SymbolItem {
+        self.insert(SymbolItem::new(
+            name,
+            kind,
+            symbol_type,
+            is_constant,
+            declaration,
+        ))
+    }
+
     pub fn lookup(&self, name: &str) -> Option<&SymbolItem> {
         for scope in self.scopes.iter().rev() {
             if let Some(symbol) = scope.get(name) {
@@ -182,6 +396,12 @@ impl SymbolTable {
         None
     }
 
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.scopes
+            .iter()
+            .flat_map(|scope| scope.keys().map(String::as_str))
+    }
+
     pub fn lookup_current_scope(&self, name: &str) -> Option<&SymbolItem> {
         self.scopes.last().and_then(|scope| scope.get(name))
     }
@@ -228,6 +448,33 @@ impl SymbolTable {
         ))
     }
 
+    pub fn fold_constant(&mut self, name: &str, value: RuntimeValue) {
+        let Some(symbol) = self.lookup_mut(name).filter(|symbol| symbol.is_constant) else {
+            return;
+        };
+        symbol.value = Some(value.clone());
+        let id = symbol.id;
+        if let Some(archived) = self.symbols.get_mut(id) {
+            archived.value = Some(value);
+        }
+    }
+
+    pub fn constants(&self) -> HashMap<String, RuntimeValue> {
+        let Some(globals) = self.scopes.first() else {
+            return HashMap::new();
+        };
+        globals
+            .values()
+            .filter(|symbol| symbol.is_constant && symbol.kind == SymbolKind::Variable)
+            .filter(|symbol| {
+                self.symbols
+                    .iter()
+                    .all(|other| other.id == symbol.id || other.name != symbol.name)
+            })
+            .filter_map(|symbol| Some((symbol.name.clone(), symbol.value.clone()?)))
+            .collect()
+    }
+
     pub fn define_with_value(
         &mut self,
         name: String,
@@ -237,14 +484,8 @@ impl SymbolTable {
         is_constant: bool,
         declaration: Option<Box<Stmt>>,
     ) -> SymbolItem {
-        let mut symbol = SymbolItem::new(name.clone(), kind, symbol_type, is_constant, declaration);
-        symbol.value = Some(value);
-
-        if let Some(current_scope) = self.scopes.last_mut() {
-            current_scope.insert(name, symbol.clone());
-        }
-
-        symbol
+        let symbol = SymbolItem::new(name, kind, symbol_type, is_constant, declaration);
+        self.insert(symbol.with_value(value))
     }
 
     pub fn lookup_mut(&mut self, name: &str) -> Option<&mut SymbolItem> {
@@ -279,6 +520,95 @@ impl SymbolTable {
     }
 }
 
+fn name_span(tokens: &[Token], name: &str, at: Position) -> Option<Span> {
+    let start = tokens.partition_point(|token| token.position < at);
+    tokens[start..]
+        .iter()
+        .find(|token| token.token_type == TokenType::Identifier && token.value == name)
+        .map(token_span)
+}
+
+fn unresolved_names(tokens: &[Token], name: &str, anywhere: bool) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut class_bodies: Vec<usize> = Vec::new();
+    let mut in_class_header = false;
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        match token.token_type {
+            TokenType::Class => in_class_header = true,
+            TokenType::LeftBrace => {
+                depth += 1;
+                if std::mem::take(&mut in_class_header) {
+                    class_bodies.push(depth);
+                }
+            }
+            TokenType::RightBrace => {
+                if class_bodies.last() == Some(&depth) {
+                    class_bodies.pop();
+                }
+                depth = depth.saturating_sub(1);
+            }
+            TokenType::Identifier if token.value == name => {
+                let in_method = class_bodies.last().is_some_and(|&body| depth > body);
+                if (anywhere || in_method) && !is_member_name(tokens, index) {
+                    spans.push(token_span(token));
+                }
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+fn is_member_name(tokens: &[Token], index: usize) -> bool {
+    let before = index
+        .checked_sub(1)
+        .map(|before| &tokens[before].token_type);
+    let after = tokens.get(index + 1).map(|after| &after.token_type);
+    matches!(before, Some(TokenType::Dot | TokenType::QuestionDot))
+        || (matches!(before, Some(TokenType::LeftBrace | TokenType::Comma))
+            && after == Some(&TokenType::Colon))
+}
+
+fn module_name(tokens: &[Token], span: Span) -> Option<TokenType> {
+    let index = tokens
+        .iter()
+        .position(|token| token.position == span.start)?;
+    let before = index
+        .checked_sub(1)
+        .map(|before| &tokens[before].token_type);
+    let after = tokens.get(index + 1).map(|after| &after.token_type);
+    if !matches!(before, Some(TokenType::LeftBrace | TokenType::Comma))
+        || !matches!(after, Some(TokenType::RightBrace | TokenType::Comma))
+    {
+        return None;
+    }
+
+    let brace = tokens[..index].iter().rposition(|token| {
+        !matches!(
+            token.token_type,
+            TokenType::Identifier | TokenType::As | TokenType::Comma
+        )
+    })?;
+    if tokens[brace].token_type != TokenType::LeftBrace {
+        return None;
+    }
+    let keyword = tokens[..brace]
+        .iter()
+        .rev()
+        .find(|token| !matches!(token.token_type, TokenType::Identifier | TokenType::Comma))?;
+    matches!(keyword.token_type, TokenType::Import | TokenType::Export)
+        .then_some(keyword.token_type)
+}
+
+fn token_span(token: &Token) -> Span {
+    let (line, column) = token.position;
+    Span {
+        start: token.position,
+        end: (line, column + token.value.chars().count()),
+    }
+}
+
 impl Default for SymbolTable {
     fn default() -> Self {
         Self::new(Option::None)
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 pub type Position = (usize, usize);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Range {
     pub start: Position,
     pub end: Position,
@@ -12,7 +14,7 @@ impl Range {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TokenType {
     Let,
     Const,
@@ -32,6 +34,7 @@ pub enum TokenType {
     Match,
     Switch,
     Case,
+    Fallthrough,
     Break,
     Continue,
     Of,
@@ -94,6 +97,7 @@ pub enum TokenType {
     Not,
     Dot,
     Range,
+    RangeExclusive,
     Spread,
     Comma,
     Colon,
@@ -135,11 +139,17 @@ pub enum TokenType {
     Eof,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Token {
     pub token_type: TokenType,
     pub value: String,
     pub position: Position,
+    /// The literal exactly as written, when it differs from `value`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lexeme: Option<String>,
+    /// Comments between the previous token and this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub leading_comments: Vec<Comment>,
 }
 
 impl Token {
@@ -148,11 +158,65 @@ impl Token {
             token_type,
             value,
             position,
+            lexeme: None,
+            leading_comments: Vec::new(),
         }
     }
+
+    pub fn with_lexeme(mut self, lexeme: String) -> Self {
+        if lexeme != self.value {
+            self.lexeme = Some(lexeme);
+        }
+        self
+    }
+
+    /// Source text of the token.
+    pub fn text(&self) -> &str {
+        self.lexeme.as_deref().unwrap_or(&self.value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommentKind {
+    Line,
+    Block,
+    Doc,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub kind: CommentKind,
+    /// Source text including the delimiters.
+    pub text: String,
+    pub position: Position,
+}
+
+impl Comment {
+    /// Text of a doc comment without its delimiters and the leading `*` of block lines.
+    pub fn doc_text(&self) -> Option<String> {
+        if self.kind != CommentKind::Doc {
+            return None;
+        }
+
+        if let Some(line) = self.text.strip_prefix("///") {
+            let line = line.strip_prefix(' ').unwrap_or(line);
+            return Some(line.trim_end().to_string());
+        }
+
+        let inner = self.text.trim_start_matches("/**").trim_end_matches("*/");
+        let lines: Vec<&str> = inner
+            .lines()
+            .map(|line| {
+                let line = line.trim();
+                let line = line.strip_prefix('*').unwrap_or(line);
+                line.strip_prefix(' ').unwrap_or(line).trim_end()
+            })
+            .collect();
+        Some(lines.join("\n").trim().to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BinaryOperator {
     Add,
     Subtract,
@@ -176,16 +240,17 @@ pub enum BinaryOperator {
     RightShift,
     UnsignedRightShift,
     Exponent,
+    In,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UnaryOperator {
     Negate,
     Not,
     BitwiseNot,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AccessModifier {
     Public,
     Private,
@@ -1,10 +1,79 @@
 use crate::{
+    ast::nodes::{FnParam, VarPattern},
     ast::types::*,
     error::RaccoonError,
     symbol_table::SymbolItem,
     tokens::{AccessModifier, BinaryOperator, Position, TokenType, UnaryOperator},
 };
 use std::collections::HashMap;
+use std::fmt;
+
+/// How much the checker lets pass without being spelled out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrictnessProfile {
+    Loose,
+    #[default]
+    Standard,
+    Strict,
+}
+
+impl StrictnessProfile {
+    pub const ALL: [StrictnessProfile; 3] = [
+        StrictnessProfile::Loose,
+        StrictnessProfile::Standard,
+        StrictnessProfile::Strict,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StrictnessProfile::Loose => "loose",
+            StrictnessProfile::Standard => "standard",
+            StrictnessProfile::Strict => "strict",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|profile| profile.name() == name)
+    }
+
+    /// The profile a `// @strictness <name>` comment at the top of the file picks.
+    pub fn from_pragma(source: &str) -> Option<Self> {
+        source
+            .lines()
+            .map(str::trim)
+            .take_while(|line| line.is_empty() || line.starts_with("//"))
+            .find_map(|line| {
+                let pragma = line.trim_start_matches('/').trim();
+                Self::from_name(pragma.strip_prefix("@strictness")?.trim())
+            })
+    }
+
+    /// Whether a parameter written without a type is taken as `any`.
+    pub fn allows_implicit_any(&self) -> bool {
+        *self != StrictnessProfile::Strict
+    }
+
+    /// Whether an int goes where a float is expected.
+    pub fn allows_int_widening(&self) -> bool {
+        *self != StrictnessProfile::Strict
+    }
+
+    /// Whether conditions, `!`, `&&` and `||` take any type, by its truthiness.
+    pub fn allows_truthiness(&self) -> bool {
+        *self == StrictnessProfile::Loose
+    }
+
+    /// Whether `+` turns a non-string into a string when the other side is one.
+    pub fn allows_implicit_concat(&self) -> bool {
+        *self != StrictnessProfile::Strict
+    }
+}
+
+impl fmt::Display for StrictnessProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
 
 pub struct TypeChecker {
     pub file: Option<String>,
@@ -12,6 +81,7 @@ pub struct TypeChecker {
     pub current_class: Option<SymbolItem>,
     pub in_loop: bool,
     pub in_async_function: bool,
+    pub strictness: StrictnessProfile,
 }
 
 impl TypeChecker {
@@ -22,6 +92,74 @@ impl TypeChecker {
             current_class: None,
             in_loop: false,
             in_async_function: false,
+            strictness: StrictnessProfile::default(),
+        }
+    }
+
+    /// Checks the condition of an `if`, loop or `?:`, which `what` names in the error.
+    pub fn check_condition(
+        &self,
+        cond_type: &Type,
+        what: &str,
+        position: Position,
+    ) -> Result<(), RaccoonError> {
+        if matches!(cond_type.kind(), TypeKind::Bool) || self.strictness.allows_truthiness() {
+            return Ok(());
+        }
+        Err(RaccoonError::new(
+            format!("{} condition must be boolean, got '{:?}'", what, cond_type),
+            position,
+            self.file.clone(),
+        ))
+    }
+
+    /// Rejects an int given where a float is expected, when the profile says so.
+    pub fn check_widening(
+        &self,
+        value_type: &Type,
+        target_type: &Type,
+        position: Position,
+    ) -> Result<(), RaccoonError> {
+        let is_float = matches!(
+            target_type.kind(),
+            TypeKind::Float | TypeKind::F32 | TypeKind::F64 | TypeKind::Decimal
+        );
+        if self.strictness.allows_int_widening() || !is_float || !self.is_integer_type(value_type) {
+            return Ok(());
+        }
+        Err(RaccoonError::new(
+            format!(
+                "Cannot implicitly widen '{}' to '{}' in {} mode",
+                value_type, target_type, self.strictness
+            ),
+            position,
+            self.file.clone(),
+        ))
+    }
+
+    /// Rejects the untyped `parameters` of a function when the profile wants them typed.
+    pub fn check_implicit_any(
+        &self,
+        parameters: &[FnParam],
+        position: Position,
+    ) -> Result<(), RaccoonError> {
+        if self.strictness.allows_implicit_any() {
+            return Ok(());
+        }
+        let untyped = parameters.iter().find_map(|param| match &param.pattern {
+            VarPattern::Identifier(name) if param.is_untyped => Some(name),
+            _ => None,
+        });
+        match untyped {
+            Some(name) => Err(RaccoonError::new(
+                format!(
+                    "Parameter '{}' needs a type in {} mode",
+                    name, self.strictness
+                ),
+                position,
+                self.file.clone(),
+            )),
+            None => Ok(()),
         }
     }
 
@@ -94,6 +232,7 @@ impl TypeChecker {
             | BinaryOperator::GreaterThan
             | BinaryOperator::LessEqual
             | BinaryOperator::GreaterEqual
+            | BinaryOperator::In
             | BinaryOperator::And
             | BinaryOperator::Or => Ok(PrimitiveType::bool()),
 
@@ -175,8 +314,10 @@ impl TypeChecker {
         }
 
         if operator == BinaryOperator::Add {
-            if matches!(left.kind(), TypeKind::Str) || matches!(right.kind(), TypeKind::Str) {
-                return true;
+            let left_str = matches!(left.kind(), TypeKind::Str);
+            let right_str = matches!(right.kind(), TypeKind::Str);
+            if left_str || right_str {
+                return (left_str && right_str) || self.strictness.allows_implicit_concat();
             }
         }
 
@@ -221,13 +362,30 @@ impl TypeChecker {
         }
 
         if matches!(operator, BinaryOperator::And | BinaryOperator::Or) {
-            return matches!(left.kind(), TypeKind::Bool) && matches!(right.kind(), TypeKind::Bool);
+            return self.strictness.allows_truthiness()
+                || (matches!(left.kind(), TypeKind::Bool)
+                    && matches!(right.kind(), TypeKind::Bool));
         }
 
         if matches!(operator, BinaryOperator::Equal | BinaryOperator::NotEqual) {
             return true;
         }
 
+        if operator == BinaryOperator::In {
+            return matches!(
+                right.kind(),
+                TypeKind::Array
+                    | TypeKind::Map
+                    | TypeKind::Object
+                    | TypeKind::Interface
+                    | TypeKind::Class
+                    | TypeKind::Str
+                    | TypeKind::Union
+                    | TypeKind::Nullable
+                    | TypeKind::Any
+            );
+        }
+
         false
     }
 
@@ -345,7 +503,9 @@ impl TypeChecker {
             }
 
             UnaryOperator::Not => {
-                if matches!(operand_type.kind(), TypeKind::Bool) {
+                if matches!(operand_type.kind(), TypeKind::Bool)
+                    || self.strictness.allows_truthiness()
+                {
                     Ok(PrimitiveType::bool())
                 } else {
                     Err(RaccoonError::new(
@@ -399,6 +559,7 @@ impl TypeChecker {
             value_type.clone()
         };
 
+        self.check_widening(&final_value_type, target_type, position)?;
         if !final_value_type.is_assignable_to(target_type) {
             return Err(RaccoonError::new(
                 format!(
@@ -465,6 +626,14 @@ impl TypeChecker {
         index_type: &Type,
         position: Position,
     ) -> Result<Type, RaccoonError> {
+        let is_range = matches!(
+            index_type,
+            Type::Array(list) if list.element_type.kind() == TypeKind::Int
+        );
+        if is_range && matches!(object_type.kind(), TypeKind::Array | TypeKind::Str) {
+            return Ok(object_type.clone());
+        }
+
         if !matches!(index_type.kind(), TypeKind::Int) {
             return Err(RaccoonError::new(
                 format!("Index must be integer, got '{:?}'", index_type),
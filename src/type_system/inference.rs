@@ -3,7 +3,7 @@ use crate::{
     error::RaccoonError,
     symbol_table::SymbolTable,
     tokens::Position,
-    type_system::TypeChecker,
+    type_system::{TypeChecker, TypeResolver},
 };
 use std::collections::HashMap;
 
@@ -114,6 +114,7 @@ impl TypeInferenceEngine {
                 params: Vec::new(),
                 return_type: PrimitiveType::unknown(),
                 is_variadic: false,
+                param_names: Vec::new(),
             })))
         }
     }
@@ -147,8 +148,14 @@ impl TypeInferenceEngine {
                 self.analyze_null_check_narrowing(bin, symbol_table)
             }
 
+            Expr::Binary(bin) if matches!(bin.operator, crate::tokens::BinaryOperator::In) => {
+                self.analyze_in_narrowing(bin, symbol_table)
+            }
+
             Expr::InstanceOf(inst) => self.analyze_instanceof_narrowing(inst, symbol_table),
 
+            Expr::Is(is) => self.analyze_is_narrowing(is, symbol_table),
+
             Expr::Unary(un) if matches!(un.operator, crate::tokens::UnaryOperator::Not) => {
                 let inner_info = self.analyze_type_narrowing(&un.operand, symbol_table)?;
                 Ok(TypeNarrowingInfo {
@@ -259,6 +266,79 @@ impl TypeInferenceEngine {
         Ok(TypeNarrowingInfo::default())
     }
 
+    fn analyze_is_narrowing(
+        &mut self,
+        is: &IsExpr,
+        symbol_table: &SymbolTable,
+    ) -> Result<TypeNarrowingInfo, RaccoonError> {
+        let Expr::Identifier(ident) = &*is.operand else {
+            return Ok(TypeNarrowingInfo::default());
+        };
+        let target = TypeResolver::new(symbol_table, self.file.clone())
+            .resolve(&is.target_type)
+            .unwrap_or_else(|_| is.target_type.clone());
+
+        let mut info = TypeNarrowingInfo::default();
+        info.then_narrows.insert(ident.name.clone(), target.clone());
+
+        if let Some(symbol) = symbol_table.lookup(&ident.name) {
+            let members = match &symbol.symbol_type {
+                Type::Union(union) => union.types.clone(),
+                Type::Nullable(nullable) => {
+                    vec![nullable.inner_type.clone(), PrimitiveType::null()]
+                }
+                _ => Vec::new(),
+            };
+            let rest: Vec<Type> = members
+                .into_iter()
+                .filter(|member| *member != target)
+                .collect();
+            if let Some(narrowed) = combine_members(rest) {
+                info.else_narrows.insert(ident.name.clone(), narrowed);
+            }
+        }
+
+        Ok(info)
+    }
+
+    fn analyze_in_narrowing(
+        &mut self,
+        bin: &BinaryExpr,
+        symbol_table: &SymbolTable,
+    ) -> Result<TypeNarrowingInfo, RaccoonError> {
+        let (Expr::StrLiteral(key), Expr::Identifier(ident)) = (&*bin.left, &*bin.right) else {
+            return Ok(TypeNarrowingInfo::default());
+        };
+        let Some(symbol) = symbol_table.lookup(&ident.name) else {
+            return Ok(TypeNarrowingInfo::default());
+        };
+
+        let members = match &symbol.symbol_type {
+            Type::Union(union) => union.types.clone(),
+            Type::Nullable(nullable) => vec![nullable.inner_type.clone(), PrimitiveType::null()],
+            other => vec![other.clone()],
+        };
+
+        let with_key: Vec<Type> = members
+            .iter()
+            .filter(|member| has_property(member, &key.value) != Some(false))
+            .cloned()
+            .collect();
+        let without_key: Vec<Type> = members
+            .into_iter()
+            .filter(|member| has_property(member, &key.value) != Some(true))
+            .collect();
+
+        let mut info = TypeNarrowingInfo::default();
+        if let Some(narrowed) = combine_members(with_key) {
+            info.then_narrows.insert(ident.name.clone(), narrowed);
+        }
+        if let Some(narrowed) = combine_members(without_key) {
+            info.else_narrows.insert(ident.name.clone(), narrowed);
+        }
+        Ok(info)
+    }
+
     fn analyze_and_narrowing(
         &mut self,
         bin: &BinaryExpr,
@@ -453,3 +533,23 @@ pub struct TypeNarrowingInfo {
 
     pub else_narrows: HashMap<String, Type>,
 }
+
+fn has_property(ty: &Type, key: &str) -> Option<bool> {
+    match ty {
+        Type::Object(object) => Some(object.properties.contains_key(key)),
+        Type::Interface(interface) => Some(interface.properties.contains_key(key)),
+        Type::Class(class) => {
+            Some(class.properties.contains_key(key) || class.methods.contains_key(key))
+        }
+        Type::Primitive(primitive) if primitive.kind == TypeKind::Null => Some(false),
+        _ => None,
+    }
+}
+
+fn combine_members(mut members: Vec<Type>) -> Option<Type> {
+    match members.len() {
+        0 => None,
+        1 => members.pop(),
+        _ => Some(Type::Union(Box::new(UnionType::new(members)))),
+    }
+}
@@ -3,7 +3,7 @@ pub mod inference;
 pub mod resolver;
 pub mod substitutor;
 
-pub use checker::TypeChecker;
+pub use checker::{StrictnessProfile, TypeChecker};
 pub use inference::TypeInferenceEngine;
 pub use resolver::TypeResolver;
 pub use substitutor::TypeSubstitutor;
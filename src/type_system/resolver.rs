@@ -58,6 +58,32 @@ impl<'a> TypeResolver<'a> {
                 Ok(Type::Union(Box::new(UnionType::new(resolved_types?))))
             }
 
+            Type::Intersection(intersection_type) => {
+                let resolved_types: Result<Vec<Type>, RaccoonError> = intersection_type
+                    .types
+                    .iter()
+                    .map(|t| self.resolve(t))
+                    .collect();
+
+                Ok(Type::Intersection(Box::new(IntersectionType::new(
+                    resolved_types?,
+                ))))
+            }
+
+            Type::Tuple(tuple_type) => {
+                let resolved_types: Result<Vec<Type>, RaccoonError> = tuple_type
+                    .element_types
+                    .iter()
+                    .map(|t| self.resolve(t))
+                    .collect();
+
+                Ok(Type::Tuple(Box::new(TupleType::new(resolved_types?))))
+            }
+
+            Type::Readonly(readonly_type) => Ok(Type::Readonly(Box::new(ReadonlyType::new(
+                self.resolve(&readonly_type.inner_type)?,
+            )))),
+
             Type::Function(fn_type) => {
                 let resolved_params: Result<Vec<Type>, RaccoonError> =
                     fn_type.params.iter().map(|p| self.resolve(p)).collect();
@@ -66,6 +92,7 @@ impl<'a> TypeResolver<'a> {
                     params: resolved_params?,
                     return_type: self.resolve(&fn_type.return_type)?,
                     is_variadic: fn_type.is_variadic,
+                    param_names: fn_type.param_names.clone(),
                 })))
             }
 
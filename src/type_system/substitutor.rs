@@ -71,6 +71,7 @@ impl TypeSubstitutor {
                     params: substituted_params,
                     return_type: self.substitute(&fn_type.return_type),
                     is_variadic: fn_type.is_variadic,
+                    param_names: fn_type.param_names.clone(),
                 }))
             }
 
@@ -100,6 +101,7 @@ impl TypeSubstitutor {
                         ClassPropertyInfo {
                             property_type: self.substitute(&info.property_type),
                             access_modifier: info.access_modifier,
+                            is_static: info.is_static,
                         },
                     );
                 }
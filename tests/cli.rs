@@ -0,0 +1,230 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Command, Output};
+
+fn raccoon(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_raccoon"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn script(name: &str, source: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("raccoon-cli-{}-{}.rcc", process::id(), name));
+    fs::write(&path, source).unwrap();
+    path
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn running_a_file_reports_every_syntax_error() {
+    let output = raccoon(&["tests/test_parse_recovery.rcc"]);
+    let stderr = stderr(&output);
+
+    assert!(!output.status.success());
+    for position in ["6:9", "12:23", "18:17"] {
+        assert!(stderr.contains(&format!("test_parse_recovery.rcc {} ->", position)));
+    }
+    assert!(stderr.contains("3 syntax errors in tests/test_parse_recovery.rcc"));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn check_reports_every_syntax_and_semantic_error() {
+    let path = script(
+        "check_errors",
+        "const limit: int = 3;\nlimit = 4;\n\nlet broken = ;\n\nlet total: int = \"many\";\nprint(total + limit);\n",
+    );
+    let output = raccoon(&["--check", path.to_str().unwrap()]);
+    let stderr = stderr(&output);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("4:14 -> Expected expression"));
+    assert!(stderr.contains("2:1 -> Cannot assign to constant 'limit'"));
+    assert!(stderr.contains("6:1 -> Cannot assign type 'str' to variable of type 'int'"));
+    assert!(!stderr.contains("Undefined variable 'total'"));
+    assert!(stderr.contains("Aborting due to 3 error(s)"));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn check_accepts_a_valid_file_without_running_it() {
+    let path = script("check_valid", "let a: int = 1;\nprint(a);\n");
+    let output = raccoon(&["--check", path.to_str().unwrap()]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn missing_return_is_an_error_whenever_the_analyzer_runs() {
+    let path = script(
+        "missing_return",
+        "fn f(x: bool): int {\n    if (x) {\n        return 1;\n    }\n}\nprint(f(true));\n",
+    );
+    for flags in [&["--check"][..], &["--warn"], &["--check", "--warn"]] {
+        let mut args = flags.to_vec();
+        args.push(path.to_str().unwrap());
+        let output = raccoon(&args);
+
+        assert!(!output.status.success(), "{:?} accepted the file", flags);
+        assert!(stderr(&output).contains(
+            "Function 'f' is declared to return 'int' but not every path returns a value"
+        ));
+        assert!(output.stdout.is_empty());
+    }
+}
+
+#[test]
+fn importing_an_export_before_its_module_makes_it_names_the_cycle() {
+    for flags in [&[][..], &["--check"]] {
+        let mut args = flags.to_vec();
+        args.push("tests/modules/cycle_early_a.rcc");
+        let output = raccoon(&args);
+
+        assert!(!output.status.success(), "{:?} accepted the cycle", flags);
+        assert!(stderr(&output).contains(
+            "tests/modules/cycle_early_b.rcc 1:1 -> Cannot import 'unit' from ./cycle_early_a.rcc \
+             before it is initialized: cycle_early_a.rcc -> cycle_early_b.rcc -> cycle_early_a.rcc"
+        ));
+        assert!(output.stdout.is_empty());
+    }
+}
+
+#[test]
+fn check_declares_the_bindings_of_a_failed_destructuring() {
+    let path = script(
+        "check_destructuring",
+        "let [a, b, c] = (1, 2);\nprint(a + b + c);\n",
+    );
+    let output = raccoon(&["--check", path.to_str().unwrap()]);
+    let stderr = stderr(&output);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("1:1 -> Cannot destructure a tuple of 2 elements into 3 bindings"));
+    assert!(!stderr.contains("Undefined variable"));
+    assert!(stderr.contains("Aborting due to 1 error(s)"));
+}
+
+#[test]
+fn a_missing_stdlib_export_is_reported_at_the_import() {
+    let path = script("missing_export", "let a = 1;\nimport { nope } from \"std:time\";\n");
+    let output = raccoon(&[path.to_str().unwrap()]);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains(&format!(
+        "{} 2:1 -> std:time does not export 'nope'",
+        path.display()
+    )));
+}
+
+#[test]
+fn an_unusable_snapshot_is_reported_and_ignored() {
+    let snapshot = script("bad_snapshot", "not a snapshot\n");
+    let output = Command::new(env!("CARGO_BIN_EXE_raccoon"))
+        .arg("tests/test_snapshot.rcc")
+        .env("RACCOON_SNAPSHOT", &snapshot)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Ignoring snapshot"));
+    assert!(stderr(&output).contains("it is not a raccoon snapshot"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("a/b 3"));
+}
+
+#[test]
+fn a_symlink_cannot_lead_out_of_a_read_grant() {
+    let root = env::temp_dir().join(format!("raccoon-cli-{}-grant", process::id()));
+    let outside = env::temp_dir().join(format!("raccoon-cli-{}-outside", process::id()));
+    fs::create_dir_all(&root).unwrap();
+    fs::create_dir_all(&outside).unwrap();
+    fs::write(outside.join("secret.txt"), "secret").unwrap();
+    let link = root.join("escape");
+    let _ = fs::remove_file(&link);
+    std::os::unix::fs::symlink(&outside, &link).unwrap();
+
+    let path = script(
+        "symlink_escape",
+        &format!(
+            "import {{ file_read }} from \"std:runtime\";\nprint(await file_read(\"{}\"));\n",
+            link.join("secret.txt").display()
+        ),
+    );
+    let grant = format!("--allow-read={}", root.display());
+    let output = raccoon(&[&grant, path.to_str().unwrap()]);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Requires read access"));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn opening_a_store_needs_read_access_too() {
+    let store = env::temp_dir().join(format!("raccoon-cli-{}-store.json", process::id()));
+    let path = script(
+        "store_read",
+        &format!(
+            "import * as store from \"std:store\";\nawait store.open(\"{}\");\n",
+            store.display()
+        ),
+    );
+    let grant = format!("--allow-write={}", store.display());
+    let output = raccoon(&[&grant, path.to_str().unwrap()]);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Requires read access"));
+}
+
+#[test]
+fn compile_time_errors_print_their_own_codes() {
+    let output = raccoon(&["tests/test_parse_recovery.rcc"]);
+    let syntax = stderr(&output);
+    assert!(syntax.contains("SyntaxError[RCC0001] tests/test_parse_recovery.rcc 6:9"));
+    assert!(!syntax.contains("RuntimeError"));
+
+    let path = script(
+        "error_codes",
+        "const limit: int = 3;\nlimit = 4;\nlet total: int = \"many\";\n",
+    );
+    let output = raccoon(&["--check", path.to_str().unwrap()]);
+    let check = stderr(&output);
+    assert!(check.contains("SemanticError[RCC0002]"));
+    assert!(check.contains("TypeError[RCC0003]"));
+    assert!(!check.contains("RuntimeError"));
+}
+
+#[test]
+fn the_ir_backend_prints_what_the_tree_walker_prints() {
+    for test in [
+        "tests/test_ordered_iteration.rcc",
+        "tests/test_destruct_nested.rcc",
+        "tests/test_destruct_object.rcc",
+        "tests/test_readonly_freeze.rcc",
+    ] {
+        let tree_walker = raccoon(&[test]);
+        let ir = raccoon(&["--use-ir", test]);
+
+        assert!(tree_walker.status.success(), "{}", stderr(&tree_walker));
+        assert!(ir.status.success(), "{} under --use-ir: {}", test, stderr(&ir));
+        assert_eq!(ir.stdout, tree_walker.stdout, "{} prints differently under --use-ir", test);
+    }
+}
+
+#[test]
+fn static_blocks_run_in_the_tree_walker_and_are_rejected_by_the_ir() {
+    let tree_walker = raccoon(&["tests/test_class_private_static.rcc"]);
+    assert!(tree_walker.status.success(), "{}", stderr(&tree_walker));
+    assert!(String::from_utf8_lossy(&tree_walker.stdout).contains("103\n[USD, EUR]\n"));
+
+    let ir = raccoon(&["--use-ir", "tests/test_class_private_static.rcc"]);
+    assert!(!ir.status.success());
+    assert!(stderr(&ir).contains(
+        "test_class_private_static.rcc 9:5 -> Static blocks in class 'Account' are not supported"
+    ));
+    assert!(ir.stdout.is_empty());
+}
@@ -0,0 +1,79 @@
+use raccoon_lang::parser::incremental::{ParsedFile, TextEdit};
+
+const SOURCE: &str = "let a = 1;\nlet b = 2;\n\n// c is last\nfn c(x: int): int {\n    return x + b;\n}\nprint(c(a));\n";
+
+fn parse(source: &str) -> ParsedFile {
+    ParsedFile::parse(source.to_string(), None).unwrap()
+}
+
+fn edit(file: &ParsedFile, start: (usize, usize), end: (usize, usize), text: &str) -> ParsedFile {
+    let edited = file
+        .edit(&TextEdit {
+            start,
+            end,
+            text: text.to_string(),
+        })
+        .unwrap();
+    let fresh = parse(&edited.source);
+    assert_eq!(edited.tokens, fresh.tokens);
+    assert_eq!(edited.program(), fresh.program());
+    assert_eq!(edited.errors().len(), fresh.errors().len());
+    edited
+}
+
+#[test]
+fn reuses_statements_after_an_edit_on_the_same_line() {
+    let file = parse(SOURCE);
+    let edited = edit(&file, (2, 9), (2, 10), "20");
+    assert_eq!(edited.reused, 3);
+}
+
+#[test]
+fn reuses_statements_moved_down_by_inserted_lines() {
+    let file = parse(SOURCE);
+    let edited = edit(&file, (2, 1), (2, 1), "let x = 0;\nlet y = 0;\n");
+    assert_eq!(edited.reused, 4);
+    assert!(edited
+        .source
+        .starts_with("let a = 1;\nlet x = 0;\nlet y = 0;\nlet b = 2;"));
+}
+
+#[test]
+fn reuses_statements_moved_up_by_removed_lines() {
+    let file = parse(SOURCE);
+    let edited = edit(&file, (2, 1), (4, 1), "let b = 3;\n");
+    assert_eq!(edited.reused, 3);
+}
+
+#[test]
+fn shifts_syntax_errors_in_reused_statements() {
+    let source = "let a = 1;\nlet b = ;\nprint(a);\n";
+    let file = parse(source);
+    let edited = edit(&file, (1, 1), (1, 1), "\n\n");
+    assert_eq!(edited.reused, 3);
+    assert_eq!(
+        edited.errors()[0].position.0,
+        file.errors()[0].position.0 + 2
+    );
+}
+
+#[test]
+fn reparses_statements_whose_columns_moved() {
+    let file = parse(SOURCE);
+    let edited = edit(&file, (8, 1), (8, 1), "  ");
+    assert_eq!(edited.reused, 2);
+}
+
+#[test]
+fn an_edit_to_a_large_file_reparses_only_the_touched_statement() {
+    let source: String = (0..2000)
+        .map(|i| format!("fn f{}(x: int): int {{\n    let y = x * {};\n    return y + 1;\n}}\n", i, i))
+        .collect();
+    let file = parse(&source);
+
+    let moved = edit(&file, (1, 1), (1, 1), "\n");
+    assert_eq!(moved.reused, 2000);
+
+    let same_line = edit(&file, (2, 17), (2, 18), "7");
+    assert_eq!(same_line.reused, 1999);
+}
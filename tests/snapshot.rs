@@ -0,0 +1,42 @@
+use raccoon_lang::runtime::{Snapshot, StdLibLoader};
+use std::env;
+use std::fs;
+use std::process;
+use std::sync::Arc;
+
+fn load(loader: &StdLibLoader, module: &str) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    let local = tokio::task::LocalSet::new();
+    runtime
+        .block_on(local.run_until(loader.load_module(module)))
+        .unwrap();
+}
+
+#[test]
+fn a_snapshot_skips_parsing_the_stdlib() {
+    let path = env::temp_dir().join(format!("raccoon-{}.snapshot", process::id()));
+    fs::write(&path, Snapshot::capture().unwrap().encode()).unwrap();
+    let snapshot = Arc::new(Snapshot::load(&path).unwrap());
+
+    let parsing = StdLibLoader::with_default_path();
+    load(&parsing, "std:path");
+    assert!(parsing.parsed_modules() > 0);
+
+    let restored = StdLibLoader::with_default_path().with_snapshot(Some(snapshot));
+    load(&restored, "std:path");
+    assert_eq!(restored.parsed_modules(), 0);
+}
+
+#[test]
+fn a_snapshot_from_another_version_is_rejected() {
+    let path = env::temp_dir().join(format!("raccoon-{}-old.snapshot", process::id()));
+    let encoded = Snapshot::capture().unwrap().encode();
+    let version = format!("\"version\":\"{}\"", env!("CARGO_PKG_VERSION"));
+    fs::write(&path, encoded.replacen(&version, "\"version\":\"0.0.0\"", 1)).unwrap();
+
+    let error = Snapshot::load(&path).err().unwrap();
+    assert!(error.contains("raccoon 0.0.0"), "{}", error);
+}